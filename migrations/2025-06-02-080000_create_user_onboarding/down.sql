@@ -0,0 +1,3 @@
+-- migrations/2025-06-02-080000_create_user_onboarding/down.sql
+DROP POLICY IF EXISTS "Users can manage their own onboarding state" ON user_onboarding;
+DROP TABLE user_onboarding;
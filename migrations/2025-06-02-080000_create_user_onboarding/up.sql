@@ -0,0 +1,15 @@
+-- migrations/2025-06-02-080000_create_user_onboarding/up.sql
+
+-- One row per user once the sample data has been created, so seeding stays idempotent
+CREATE TABLE user_onboarding (
+    user_id UUID PRIMARY KEY,
+    project_id UUID REFERENCES projects(id) ON DELETE SET NULL,
+    seeded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE user_onboarding ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own onboarding state" ON user_onboarding
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,3 @@
+-- migrations/2025-06-04-101500_create_ai_summaries/down.sql
+DROP POLICY IF EXISTS "Users can manage their own ai_summaries" ON ai_summaries;
+DROP TABLE ai_summaries;
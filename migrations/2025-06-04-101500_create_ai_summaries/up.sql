@@ -0,0 +1,20 @@
+-- migrations/2025-06-04-101500_create_ai_summaries/up.sql
+
+-- Cache of generated weekly reviews: one summary per user and period
+CREATE TABLE ai_summaries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    summary TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_user_summary_period UNIQUE (user_id, period_start, period_end)
+);
+
+ALTER TABLE ai_summaries ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own ai_summaries" ON ai_summaries
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
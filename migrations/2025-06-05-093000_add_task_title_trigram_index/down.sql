@@ -0,0 +1,3 @@
+-- migrations/2025-06-05-093000_add_task_title_trigram_index/down.sql
+DROP INDEX IF EXISTS idx_tasks_title_trgm;
+-- L'extension pg_trgm est conservée : d'autres objets peuvent en dépendre.
@@ -0,0 +1,6 @@
+-- migrations/2025-06-05-093000_add_task_title_trigram_index/up.sql
+
+-- Trigram similarity on task titles (duplicate detection)
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_tasks_title_trgm ON tasks USING GIN (title gin_trgm_ops);
@@ -0,0 +1,3 @@
+-- migrations/2025-06-06-140000_create_task_links/down.sql
+DROP POLICY IF EXISTS "Users can manage task_links for their own tasks" ON task_links;
+DROP TABLE task_links;
@@ -0,0 +1,34 @@
+-- migrations/2025-06-06-140000_create_task_links/up.sql
+
+-- Links parsed from [[task:<uuid>]] references in task descriptions
+CREATE TABLE task_links (
+    source_task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    target_task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (source_task_id, target_task_id)
+);
+
+-- Backlinks are looked up by target
+CREATE INDEX idx_task_links_target ON task_links (target_task_id);
+
+ALTER TABLE task_links ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage task_links for their own tasks" ON task_links
+    FOR ALL
+    TO authenticated
+    USING (
+        EXISTS (
+            SELECT 1 FROM tasks
+            WHERE tasks.id = task_links.source_task_id AND tasks.user_id = auth.uid()
+        )
+    )
+    WITH CHECK (
+        EXISTS (
+            SELECT 1 FROM tasks
+            WHERE tasks.id = task_links.source_task_id AND tasks.user_id = auth.uid()
+        )
+        AND
+        EXISTS (
+            SELECT 1 FROM tasks
+            WHERE tasks.id = task_links.target_task_id AND tasks.user_id = auth.uid()
+        )
+    );
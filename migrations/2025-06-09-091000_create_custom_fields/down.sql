@@ -0,0 +1,7 @@
+-- migrations/2025-06-09-091000_create_custom_fields/down.sql
+DROP POLICY IF EXISTS "Users can manage custom values of their own tasks" ON task_custom_values;
+DROP POLICY IF EXISTS "Users can manage their own custom_field_definitions" ON custom_field_definitions;
+DROP TRIGGER IF EXISTS set_task_custom_values_timestamp ON task_custom_values;
+DROP TRIGGER IF EXISTS set_custom_field_definitions_timestamp ON custom_field_definitions;
+DROP TABLE task_custom_values;
+DROP TABLE custom_field_definitions;
@@ -0,0 +1,60 @@
+-- migrations/2025-06-09-091000_create_custom_fields/up.sql
+
+-- Per-project custom field definitions (story points, client ticket id, ...)
+CREATE TABLE custom_field_definitions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    field_type TEXT NOT NULL CHECK (field_type IN ('text', 'number', 'date', 'select')),
+    -- Allowed values for 'select' fields (JSON array of strings)
+    options JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_project_custom_field_name UNIQUE (project_id, name)
+);
+
+-- Values are stored in a canonical text form and typed on output from field_type
+CREATE TABLE task_custom_values (
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    field_id UUID NOT NULL REFERENCES custom_field_definitions(id) ON DELETE CASCADE,
+    value TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (task_id, field_id)
+);
+
+CREATE INDEX idx_task_custom_values_field_value ON task_custom_values (field_id, value);
+
+CREATE TRIGGER set_custom_field_definitions_timestamp
+BEFORE UPDATE ON custom_field_definitions
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+CREATE TRIGGER set_task_custom_values_timestamp
+BEFORE UPDATE ON task_custom_values
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+ALTER TABLE custom_field_definitions ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own custom_field_definitions" ON custom_field_definitions
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
+
+ALTER TABLE task_custom_values ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage custom values of their own tasks" ON task_custom_values
+    FOR ALL
+    TO authenticated
+    USING (
+        EXISTS (
+            SELECT 1 FROM tasks
+            WHERE tasks.id = task_custom_values.task_id AND tasks.user_id = auth.uid()
+        )
+    )
+    WITH CHECK (
+        EXISTS (
+            SELECT 1 FROM tasks
+            WHERE tasks.id = task_custom_values.task_id AND tasks.user_id = auth.uid()
+        )
+    );
@@ -0,0 +1,8 @@
+-- migrations/2025-06-10-083000_create_watchers_notifications_settings/down.sql
+DROP POLICY IF EXISTS "Users can manage their own notifications" ON notifications;
+DROP POLICY IF EXISTS "Users can manage their own task_watchers" ON task_watchers;
+DROP POLICY IF EXISTS "Users can manage their own user_settings" ON user_settings;
+DROP TABLE notifications;
+DROP TABLE task_watchers;
+DROP TRIGGER IF EXISTS set_user_settings_timestamp ON user_settings;
+DROP TABLE user_settings;
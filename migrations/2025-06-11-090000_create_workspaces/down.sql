@@ -0,0 +1,10 @@
+-- migrations/2025-06-11-090000_create_workspaces/down.sql
+DROP INDEX IF EXISTS idx_projects_workspace_id;
+ALTER TABLE projects DROP COLUMN IF EXISTS workspace_id;
+DROP POLICY IF EXISTS "Owners can manage workspace members" ON workspace_members;
+DROP POLICY IF EXISTS "Members can view workspace members" ON workspace_members;
+DROP POLICY IF EXISTS "Owners can manage their workspaces" ON workspaces;
+DROP POLICY IF EXISTS "Members can view their workspaces" ON workspaces;
+DROP TRIGGER IF EXISTS set_workspaces_timestamp ON workspaces;
+DROP TABLE workspace_members;
+DROP TABLE workspaces;
@@ -0,0 +1,74 @@
+-- migrations/2025-06-11-090000_create_workspaces/up.sql
+
+-- Espaces partagés (foyer, équipe) regroupant des projets et des membres
+CREATE TABLE workspaces (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    owner_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE workspace_members (
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    role TEXT NOT NULL DEFAULT 'editor' CHECK (role IN ('owner', 'editor', 'viewer')),
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (workspace_id, user_id)
+);
+
+CREATE INDEX idx_workspace_members_user_id ON workspace_members(user_id);
+
+ALTER TABLE projects ADD COLUMN workspace_id UUID REFERENCES workspaces(id) ON DELETE SET NULL;
+CREATE INDEX idx_projects_workspace_id ON projects(workspace_id);
+
+CREATE TRIGGER set_workspaces_timestamp
+BEFORE UPDATE ON workspaces
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+ALTER TABLE workspaces ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Members can view their workspaces" ON workspaces
+    FOR SELECT
+    TO authenticated
+    USING (
+        EXISTS (
+            SELECT 1 FROM workspace_members
+            WHERE workspace_members.workspace_id = workspaces.id
+            AND workspace_members.user_id = auth.uid()
+        )
+    );
+CREATE POLICY "Owners can manage their workspaces" ON workspaces
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = owner_id)
+    WITH CHECK (auth.uid() = owner_id);
+
+ALTER TABLE workspace_members ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Members can view workspace members" ON workspace_members
+    FOR SELECT
+    TO authenticated
+    USING (
+        EXISTS (
+            SELECT 1 FROM workspace_members AS me
+            WHERE me.workspace_id = workspace_members.workspace_id
+            AND me.user_id = auth.uid()
+        )
+    );
+CREATE POLICY "Owners can manage workspace members" ON workspace_members
+    FOR ALL
+    TO authenticated
+    USING (
+        EXISTS (
+            SELECT 1 FROM workspaces
+            WHERE workspaces.id = workspace_members.workspace_id
+            AND workspaces.owner_id = auth.uid()
+        )
+    )
+    WITH CHECK (
+        EXISTS (
+            SELECT 1 FROM workspaces
+            WHERE workspaces.id = workspace_members.workspace_id
+            AND workspaces.owner_id = auth.uid()
+        )
+    );
@@ -0,0 +1,3 @@
+-- migrations/2025-06-12-094500_create_inbound_email_addresses/down.sql
+DROP POLICY IF EXISTS "Users can manage their own inbound_email_addresses" ON inbound_email_addresses;
+DROP TABLE inbound_email_addresses;
@@ -0,0 +1,15 @@
+-- migrations/2025-06-12-094500_create_inbound_email_addresses/up.sql
+
+-- Adresse secrète de capture par email (add+<token>@domaine), une par utilisateur
+CREATE TABLE inbound_email_addresses (
+    user_id UUID PRIMARY KEY,
+    token TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE inbound_email_addresses ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own inbound_email_addresses" ON inbound_email_addresses
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,2 @@
+-- migrations/2025-06-13-081500_add_task_source/down.sql
+ALTER TABLE tasks DROP COLUMN IF EXISTS source;
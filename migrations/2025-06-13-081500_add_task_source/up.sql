@@ -0,0 +1,4 @@
+-- migrations/2025-06-13-081500_add_task_source/up.sql
+
+-- Origine structurée d'une tâche capturée (extension navigateur, email...)
+ALTER TABLE tasks ADD COLUMN source JSONB;
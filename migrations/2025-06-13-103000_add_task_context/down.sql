@@ -0,0 +1,3 @@
+-- migrations/2025-06-13-103000_add_task_context/down.sql
+DROP INDEX IF EXISTS idx_tasks_context;
+ALTER TABLE tasks DROP COLUMN IF EXISTS context;
@@ -0,0 +1,6 @@
+-- migrations/2025-06-13-103000_add_task_context/up.sql
+
+-- Contexte de la tâche (lieu, appareil, application source), filtré via l'opérateur @>
+ALTER TABLE tasks ADD COLUMN context JSONB;
+
+CREATE INDEX idx_tasks_context ON tasks USING GIN (context jsonb_path_ops);
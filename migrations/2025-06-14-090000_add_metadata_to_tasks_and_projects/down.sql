@@ -0,0 +1,3 @@
+-- migrations/2025-06-14-090000_add_metadata_to_tasks_and_projects/down.sql
+ALTER TABLE projects DROP COLUMN IF EXISTS metadata;
+ALTER TABLE tasks DROP COLUMN IF EXISTS metadata;
@@ -0,0 +1,7 @@
+-- migrations/2025-06-14-090000_add_metadata_to_tasks_and_projects/up.sql
+
+-- Clés libres des intégrations (ex: numéro d'issue GitHub), objet JSON uniquement
+ALTER TABLE tasks ADD COLUMN metadata JSONB NOT NULL DEFAULT '{}'::jsonb
+    CHECK (jsonb_typeof(metadata) = 'object');
+ALTER TABLE projects ADD COLUMN metadata JSONB NOT NULL DEFAULT '{}'::jsonb
+    CHECK (jsonb_typeof(metadata) = 'object');
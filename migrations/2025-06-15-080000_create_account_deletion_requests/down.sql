@@ -0,0 +1,3 @@
+-- migrations/2025-06-15-080000_create_account_deletion_requests/down.sql
+DROP POLICY IF EXISTS "Users can manage their own account_deletion_requests" ON account_deletion_requests;
+DROP TABLE account_deletion_requests;
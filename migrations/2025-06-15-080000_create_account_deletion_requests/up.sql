@@ -0,0 +1,17 @@
+-- migrations/2025-06-15-080000_create_account_deletion_requests/up.sql
+
+-- Suppression de compte programmée ; le compte est en lecture seule tant que la ligne existe
+CREATE TABLE account_deletion_requests (
+    user_id UUID PRIMARY KEY,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    scheduled_for TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_account_deletion_requests_scheduled_for ON account_deletion_requests(scheduled_for);
+
+ALTER TABLE account_deletion_requests ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own account_deletion_requests" ON account_deletion_requests
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
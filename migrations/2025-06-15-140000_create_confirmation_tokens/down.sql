@@ -0,0 +1,3 @@
+-- migrations/2025-06-15-140000_create_confirmation_tokens/down.sql
+DROP POLICY IF EXISTS "Users can manage their own confirmation_tokens" ON confirmation_tokens;
+DROP TABLE confirmation_tokens;
@@ -0,0 +1,20 @@
+-- migrations/2025-06-15-140000_create_confirmation_tokens/up.sql
+
+-- Jetons à usage unique confirmant une opération destructive (deuxième appel)
+CREATE TABLE confirmation_tokens (
+    token TEXT PRIMARY KEY,
+    user_id UUID NOT NULL,
+    action TEXT NOT NULL,
+    target_id UUID,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_confirmation_tokens_user_id ON confirmation_tokens(user_id);
+
+ALTER TABLE confirmation_tokens ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own confirmation_tokens" ON confirmation_tokens
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
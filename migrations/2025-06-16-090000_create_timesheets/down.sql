@@ -0,0 +1,4 @@
+-- migrations/2025-06-16-090000_create_timesheets/down.sql
+DROP POLICY IF EXISTS "Users can manage their own timesheets" ON timesheets;
+DROP TRIGGER IF EXISTS set_timesheets_timestamp ON timesheets;
+DROP TABLE timesheets;
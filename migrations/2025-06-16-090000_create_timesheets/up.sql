@@ -0,0 +1,32 @@
+-- migrations/2025-06-16-090000_create_timesheets/up.sql
+
+-- Feuille de temps hebdomadaire (semaine commençant le lundi, UTC).
+-- Une fois soumise, les entrées de temps de la semaine sont verrouillées.
+CREATE TABLE timesheets (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    week_start DATE NOT NULL CHECK (EXTRACT(ISODOW FROM week_start) = 1),
+    status TEXT NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'submitted', 'approved')),
+    total_duration_seconds BIGINT NOT NULL DEFAULT 0,
+    submitted_at TIMESTAMPTZ,
+    reviewed_by UUID,
+    reviewed_at TIMESTAMPTZ,
+    review_note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, week_start)
+);
+
+CREATE INDEX idx_timesheets_status ON timesheets(status);
+
+CREATE TRIGGER set_timesheets_timestamp
+BEFORE UPDATE ON timesheets
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+ALTER TABLE timesheets ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own timesheets" ON timesheets
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
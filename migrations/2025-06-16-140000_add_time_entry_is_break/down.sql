@@ -0,0 +1,3 @@
+-- migrations/2025-06-16-140000_add_time_entry_is_break/down.sql
+
+ALTER TABLE time_entries DROP COLUMN is_break;
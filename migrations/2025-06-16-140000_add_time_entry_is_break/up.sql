@@ -0,0 +1,5 @@
+-- migrations/2025-06-16-140000_add_time_entry_is_break/up.sql
+
+-- Pauses détachées d'une entrée lors de la réconciliation du temps d'inactivité ;
+-- elles sont conservées mais exclues des totaux de temps suivi
+ALTER TABLE time_entries ADD COLUMN is_break BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+-- migrations/2025-06-17-090000_create_calendar_integrations/down.sql
+
+DROP TABLE IF EXISTS calendar_suggestions;
+DROP TABLE IF EXISTS calendar_project_links;
+DROP TABLE IF EXISTS calendar_oauth_states;
+DROP TABLE IF EXISTS calendar_integrations;
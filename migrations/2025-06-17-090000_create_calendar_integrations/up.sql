@@ -0,0 +1,88 @@
+-- migrations/2025-06-17-090000_create_calendar_integrations/up.sql
+
+-- Connexion d'un utilisateur à un calendrier externe (Google pour l'instant)
+CREATE TABLE calendar_integrations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    provider TEXT NOT NULL CHECK (provider IN ('google')),
+    access_token TEXT NOT NULL,
+    refresh_token TEXT,
+    token_expires_at TIMESTAMPTZ NOT NULL,
+    calendar_id TEXT NOT NULL DEFAULT 'primary',
+    last_synced_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, provider)
+);
+
+CREATE TRIGGER set_calendar_integrations_timestamp
+BEFORE UPDATE ON calendar_integrations
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+-- Paramètre "state" du flux OAuth, à usage unique et de courte durée
+CREATE TABLE calendar_oauth_states (
+    state TEXT PRIMARY KEY,
+    user_id UUID NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+-- Projets liés : un événement dont le titre contient le mot-clé est rattaché au projet
+CREATE TABLE calendar_project_links (
+    user_id UUID NOT NULL,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    keyword TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, project_id)
+);
+
+-- Entrées de temps suggérées à partir des événements, en attente de validation
+CREATE TABLE calendar_suggestions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    external_event_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    start_time TIMESTAMPTZ NOT NULL,
+    end_time TIMESTAMPTZ NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'accepted', 'rejected')),
+    time_entry_id UUID REFERENCES time_entries(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, external_event_id)
+);
+
+CREATE INDEX idx_calendar_suggestions_user_status ON calendar_suggestions(user_id, status);
+
+CREATE TRIGGER set_calendar_suggestions_timestamp
+BEFORE UPDATE ON calendar_suggestions
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+ALTER TABLE calendar_integrations ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own calendar integrations" ON calendar_integrations
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
+
+ALTER TABLE calendar_oauth_states ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own calendar oauth states" ON calendar_oauth_states
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
+
+ALTER TABLE calendar_project_links ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own calendar project links" ON calendar_project_links
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
+
+ALTER TABLE calendar_suggestions ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own calendar suggestions" ON calendar_suggestions
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,8 @@
+-- migrations/2025-06-17-140000_add_task_schedule_and_calendar_events/down.sql
+
+DROP TABLE IF EXISTS calendar_event_mappings;
+
+ALTER TABLE tasks
+    DROP CONSTRAINT IF EXISTS tasks_schedule_check,
+    DROP COLUMN IF EXISTS scheduled_end,
+    DROP COLUMN IF EXISTS scheduled_start;
@@ -0,0 +1,22 @@
+-- migrations/2025-06-17-140000_add_task_schedule_and_calendar_events/up.sql
+
+-- Créneau planifié d'une tâche (les deux bornes ensemble), poussé vers le calendrier connecté
+ALTER TABLE tasks
+    ADD COLUMN scheduled_start TIMESTAMPTZ,
+    ADD COLUMN scheduled_end TIMESTAMPTZ,
+    ADD CONSTRAINT tasks_schedule_check CHECK (
+        (scheduled_start IS NULL) = (scheduled_end IS NULL)
+        AND (scheduled_end IS NULL OR scheduled_end > scheduled_start)
+    );
+
+-- Événement externe créé pour une tâche planifiée. Pas de clé étrangère vers tasks :
+-- la correspondance doit survivre à la suppression de la tâche pour effacer l'événement.
+CREATE TABLE calendar_event_mappings (
+    integration_id UUID NOT NULL REFERENCES calendar_integrations(id) ON DELETE CASCADE,
+    task_id UUID NOT NULL,
+    external_event_id TEXT NOT NULL,
+    synced_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (integration_id, task_id)
+);
+
+CREATE INDEX idx_calendar_event_mappings_task_id ON calendar_event_mappings(task_id);
@@ -0,0 +1,3 @@
+-- migrations/2025-06-18-090000_create_app_passwords/down.sql
+
+DROP TABLE IF EXISTS app_passwords;
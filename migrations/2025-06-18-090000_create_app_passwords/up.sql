@@ -0,0 +1,21 @@
+-- migrations/2025-06-18-090000_create_app_passwords/up.sql
+
+-- Mots de passe d'application (accès CalDAV en authentification Basic).
+-- Seule l'empreinte SHA-256 est stockée ; le mot de passe n'est affiché qu'à la création.
+CREATE TABLE app_passwords (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    password_hash TEXT NOT NULL UNIQUE,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_app_passwords_user_id ON app_passwords(user_id);
+
+ALTER TABLE app_passwords ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own app passwords" ON app_passwords
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
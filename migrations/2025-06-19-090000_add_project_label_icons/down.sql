@@ -0,0 +1,4 @@
+-- migrations/2025-06-19-090000_add_project_label_icons/down.sql
+
+ALTER TABLE labels DROP COLUMN icon;
+ALTER TABLE projects DROP COLUMN icon;
@@ -0,0 +1,5 @@
+-- migrations/2025-06-19-090000_add_project_label_icons/up.sql
+
+-- Icône (emoji ou identifiant d'icône) pour distinguer visuellement projets et labels
+ALTER TABLE projects ADD COLUMN icon VARCHAR(64);
+ALTER TABLE labels ADD COLUMN icon VARCHAR(64);
@@ -0,0 +1,4 @@
+-- migrations/2025-06-19-140000_add_task_is_pinned/down.sql
+
+DROP INDEX IF EXISTS idx_tasks_user_pinned;
+ALTER TABLE tasks DROP COLUMN is_pinned;
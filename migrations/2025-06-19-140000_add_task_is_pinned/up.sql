@@ -0,0 +1,6 @@
+-- migrations/2025-06-19-140000_add_task_is_pinned/up.sql
+
+-- Tâches épinglées : affichées en tête des listes par défaut et de la vue du jour
+ALTER TABLE tasks ADD COLUMN is_pinned BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_tasks_user_pinned ON tasks(user_id) WHERE is_pinned;
@@ -0,0 +1,4 @@
+-- migrations/2025-06-20-090000_add_task_snoozed_until/down.sql
+
+DROP INDEX IF EXISTS idx_tasks_user_snoozed_until;
+ALTER TABLE tasks DROP COLUMN snoozed_until;
@@ -0,0 +1,6 @@
+-- migrations/2025-06-20-090000_add_task_snoozed_until/up.sql
+
+-- Tâches reportées : masquées des listes par défaut et de la vue du jour jusqu'à cette date
+ALTER TABLE tasks ADD COLUMN snoozed_until TIMESTAMPTZ;
+
+CREATE INDEX idx_tasks_user_snoozed_until ON tasks(user_id, snoozed_until) WHERE snoozed_until IS NOT NULL;
@@ -0,0 +1,3 @@
+-- migrations/2025-06-20-140000_add_project_wip_limits/down.sql
+
+ALTER TABLE projects DROP COLUMN wip_limits;
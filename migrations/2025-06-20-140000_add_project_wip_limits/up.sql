@@ -0,0 +1,4 @@
+-- migrations/2025-06-20-140000_add_project_wip_limits/up.sql
+
+-- Limites WIP par statut pour le board du projet, ex: {"inprogress": 3}
+ALTER TABLE projects ADD COLUMN wip_limits JSONB NOT NULL DEFAULT '{}'::jsonb;
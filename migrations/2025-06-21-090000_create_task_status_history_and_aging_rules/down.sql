@@ -0,0 +1,7 @@
+-- migrations/2025-06-21-090000_create_task_status_history_and_aging_rules/down.sql
+
+DROP TABLE IF EXISTS task_aging_rule_hits;
+DROP TABLE IF EXISTS task_aging_rules;
+DROP TRIGGER IF EXISTS record_tasks_status ON tasks;
+DROP FUNCTION IF EXISTS trigger_record_task_status();
+DROP TABLE IF EXISTS task_status_history;
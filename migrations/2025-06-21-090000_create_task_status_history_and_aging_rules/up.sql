@@ -0,0 +1,92 @@
+-- migrations/2025-06-21-090000_create_task_status_history_and_aging_rules/up.sql
+
+-- Historique des statuts, alimenté par trigger pour couvrir tous les chemins d'écriture
+CREATE TABLE task_status_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    project_id UUID,
+    old_status TEXT,
+    new_status TEXT NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_task_status_history_task ON task_status_history(task_id, changed_at);
+CREATE INDEX idx_task_status_history_project ON task_status_history(project_id, changed_at);
+
+CREATE OR REPLACE FUNCTION trigger_record_task_status()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT'
+        OR OLD.status IS DISTINCT FROM NEW.status
+        OR OLD.project_id IS DISTINCT FROM NEW.project_id THEN
+        INSERT INTO task_status_history (task_id, user_id, project_id, old_status, new_status)
+        VALUES (
+            NEW.id,
+            NEW.user_id,
+            NEW.project_id,
+            CASE WHEN TG_OP = 'UPDATE' THEN OLD.status END,
+            NEW.status
+        );
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_tasks_status
+AFTER INSERT OR UPDATE ON tasks
+FOR EACH ROW
+EXECUTE FUNCTION trigger_record_task_status();
+
+-- Point de départ pour les tâches existantes : leur statut actuel
+INSERT INTO task_status_history (task_id, user_id, project_id, old_status, new_status, changed_at)
+SELECT id, user_id, project_id, NULL, status,
+       CASE WHEN status = 'todo' THEN created_at ELSE updated_at END
+FROM tasks;
+
+ALTER TABLE task_status_history ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can read their own task status history" ON task_status_history
+    FOR SELECT
+    TO authenticated
+    USING (auth.uid() = user_id);
+
+-- Règles d'escalade : une tâche restée plus de max_days dans un statut reçoit
+-- un label et/ou déclenche une notification (une fois par séjour dans le statut)
+CREATE TABLE task_aging_rules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    project_id UUID REFERENCES projects(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    status TEXT NOT NULL,
+    max_days INTEGER NOT NULL CHECK (max_days BETWEEN 1 AND 365),
+    add_label TEXT,
+    notify BOOLEAN NOT NULL DEFAULT TRUE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    last_run_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (add_label IS NOT NULL OR notify)
+);
+
+CREATE INDEX idx_task_aging_rules_user ON task_aging_rules(user_id);
+
+CREATE TRIGGER set_task_aging_rules_timestamp
+BEFORE UPDATE ON task_aging_rules
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+ALTER TABLE task_aging_rules ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own task aging rules" ON task_aging_rules
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
+
+-- Escalades déjà appliquées, par séjour dans le statut (status_entered_at)
+CREATE TABLE task_aging_rule_hits (
+    rule_id UUID NOT NULL REFERENCES task_aging_rules(id) ON DELETE CASCADE,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    status_entered_at TIMESTAMPTZ NOT NULL,
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (rule_id, task_id)
+);
@@ -0,0 +1,3 @@
+-- migrations/2025-06-21-140000_create_automation_rules/down.sql
+
+DROP TABLE IF EXISTS automation_rules;
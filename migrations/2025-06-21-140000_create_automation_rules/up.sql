@@ -0,0 +1,31 @@
+-- migrations/2025-06-21-140000_create_automation_rules/up.sql
+
+-- Règles d'automatisation : déclencheur, conditions (JSONB) et liste d'actions (JSONB),
+-- exécutées de manière synchrone par les handlers concernés
+CREATE TABLE automation_rules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    trigger TEXT NOT NULL CHECK (trigger IN ('task_created', 'task_completed', 'label_added', 'timer_stopped')),
+    conditions JSONB NOT NULL DEFAULT '{}'::jsonb,
+    actions JSONB NOT NULL DEFAULT '[]'::jsonb,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    last_triggered_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_automation_rules_user_trigger ON automation_rules(user_id, trigger) WHERE is_active;
+
+CREATE TRIGGER set_automation_rules_timestamp
+BEFORE UPDATE ON automation_rules
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+ALTER TABLE automation_rules ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own automation rules" ON automation_rules
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,3 @@
+-- migrations/2025-06-22-090000_add_task_follow_up/down.sql
+
+ALTER TABLE tasks DROP COLUMN follow_up;
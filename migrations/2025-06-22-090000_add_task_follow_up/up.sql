@@ -0,0 +1,4 @@
+-- migrations/2025-06-22-090000_add_task_follow_up/up.sql
+
+-- Tâche de suivi créée à la complétion : {"title": "...", "due_in_days": 7}
+ALTER TABLE tasks ADD COLUMN follow_up JSONB;
@@ -0,0 +1,5 @@
+-- migrations/2025-06-22-140000_add_sla_targets/down.sql
+
+DROP TABLE IF EXISTS task_sla_breaches;
+ALTER TABLE labels DROP COLUMN sla_hours;
+ALTER TABLE projects DROP COLUMN sla_hours;
@@ -0,0 +1,27 @@
+-- migrations/2025-06-22-140000_add_sla_targets/up.sql
+
+-- Délai cible (en heures) entre la création et la complétion des tâches
+-- d'un projet ou portant un label, ex: label 'bug' => 48
+ALTER TABLE projects ADD COLUMN sla_hours INTEGER CHECK (sla_hours BETWEEN 1 AND 8760);
+ALTER TABLE labels ADD COLUMN sla_hours INTEGER CHECK (sla_hours BETWEEN 1 AND 8760);
+
+-- Dépassements détectés par la vérification périodique (une notification par cible)
+CREATE TABLE task_sla_breaches (
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    target_type TEXT NOT NULL CHECK (target_type IN ('project', 'label')),
+    target_id UUID NOT NULL,
+    target_hours INTEGER NOT NULL,
+    due_at TIMESTAMPTZ NOT NULL,
+    breached_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (task_id, target_type, target_id)
+);
+
+CREATE INDEX idx_task_sla_breaches_user ON task_sla_breaches(user_id);
+
+ALTER TABLE task_sla_breaches ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own task SLA breaches" ON task_sla_breaches
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
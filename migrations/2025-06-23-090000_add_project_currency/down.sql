@@ -0,0 +1,3 @@
+-- migrations/2025-06-23-090000_add_project_currency/down.sql
+
+ALTER TABLE projects DROP COLUMN currency;
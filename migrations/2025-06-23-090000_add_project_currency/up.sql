@@ -0,0 +1,4 @@
+-- migrations/2025-06-23-090000_add_project_currency/up.sql
+
+-- Devise (code ISO 4217) dans laquelle sont exprimés les montants du projet
+ALTER TABLE projects ADD COLUMN currency VARCHAR(3) CHECK (currency ~ '^[A-Z]{3}$');
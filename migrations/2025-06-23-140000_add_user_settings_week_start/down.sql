@@ -0,0 +1,3 @@
+-- migrations/2025-06-23-140000_add_user_settings_week_start/down.sql
+
+ALTER TABLE user_settings DROP COLUMN week_start;
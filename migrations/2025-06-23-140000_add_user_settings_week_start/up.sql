@@ -0,0 +1,5 @@
+-- migrations/2025-06-23-140000_add_user_settings_week_start/up.sql
+
+-- Premier jour de la semaine pour les analytics (semaine en cours, totaux hebdomadaires)
+ALTER TABLE user_settings ADD COLUMN week_start VARCHAR(10) NOT NULL DEFAULT 'monday'
+    CHECK (week_start IN ('monday', 'sunday', 'saturday'));
@@ -0,0 +1,4 @@
+-- migrations/2025-06-24-090000_add_time_locked_before/down.sql
+
+ALTER TABLE workspaces DROP COLUMN time_locked_before;
+ALTER TABLE user_settings DROP COLUMN time_locked_before;
@@ -0,0 +1,5 @@
+-- migrations/2025-06-24-090000_add_time_locked_before/up.sql
+
+-- Clôture comptable : les entrées de temps antérieures à cette date ne sont plus modifiables
+ALTER TABLE user_settings ADD COLUMN time_locked_before DATE;
+ALTER TABLE workspaces ADD COLUMN time_locked_before DATE;
@@ -0,0 +1,4 @@
+-- migrations/2025-06-24-140000_create_analytics_snapshots/down.sql
+
+DROP TABLE analytics_snapshots;
+DROP FUNCTION prevent_analytics_snapshot_update();
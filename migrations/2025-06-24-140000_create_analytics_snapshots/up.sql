@@ -0,0 +1,32 @@
+-- migrations/2025-06-24-140000_create_analytics_snapshots/up.sql
+
+-- Métriques figées d'une période terminée (paie, facturation) : une par utilisateur et période.
+-- Les lignes ne sont jamais modifiées ; seule la suppression du compte les efface.
+CREATE TABLE analytics_snapshots (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    metrics JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_user_snapshot_period UNIQUE (user_id, period_start, period_end)
+);
+
+CREATE OR REPLACE FUNCTION prevent_analytics_snapshot_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'analytics_snapshots rows are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER analytics_snapshots_immutable
+BEFORE UPDATE ON analytics_snapshots
+FOR EACH ROW
+EXECUTE FUNCTION prevent_analytics_snapshot_update();
+
+ALTER TABLE analytics_snapshots ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own analytics_snapshots" ON analytics_snapshots
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,3 @@
+-- migrations/2025-06-25-090000_create_devices/down.sql
+
+DROP TABLE devices;
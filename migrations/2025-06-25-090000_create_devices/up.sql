@@ -0,0 +1,31 @@
+-- migrations/2025-06-25-090000_create_devices/up.sql
+
+-- Appareils de l'utilisateur : jeton de notification push et état de synchronisation
+CREATE TABLE devices (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    platform VARCHAR(16) NOT NULL CHECK (platform IN ('ios', 'android', 'web', 'desktop')),
+    -- Un jeton n'appartient qu'à un appareil (il est retiré de l'ancien lors d'un nouvel enregistrement)
+    push_token TEXT UNIQUE,
+    -- Curseur opaque de la dernière synchronisation réussie
+    sync_cursor TEXT,
+    last_synced_at TIMESTAMPTZ,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_devices_user_id ON devices(user_id);
+
+CREATE TRIGGER set_devices_timestamp
+BEFORE UPDATE ON devices
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+ALTER TABLE devices ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own devices" ON devices
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,8 @@
+-- migrations/2025-06-25-140000_create_push_deliveries/down.sql
+
+DROP TABLE push_deliveries;
+DROP INDEX idx_notifications_push_pending;
+ALTER TABLE notifications DROP COLUMN pushed_at;
+ALTER TABLE user_settings DROP COLUMN push_mentions;
+ALTER TABLE user_settings DROP COLUMN push_timer_nudges;
+ALTER TABLE user_settings DROP COLUMN push_due_reminders;
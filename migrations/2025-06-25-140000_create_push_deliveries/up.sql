@@ -0,0 +1,32 @@
+-- migrations/2025-06-25-140000_create_push_deliveries/up.sql
+
+-- Catégories de notifications push (toutes actives par défaut)
+ALTER TABLE user_settings ADD COLUMN push_due_reminders BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE user_settings ADD COLUMN push_timer_nudges BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE user_settings ADD COLUMN push_mentions BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- Renseigné quand le dispatcher a traité la notification (envoyée ou ignorée)
+ALTER TABLE notifications ADD COLUMN pushed_at TIMESTAMPTZ;
+CREATE INDEX idx_notifications_push_pending ON notifications(created_at) WHERE pushed_at IS NULL;
+
+-- Accusés d'envoi : une ligne par notification et par appareil
+CREATE TABLE push_deliveries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    notification_id UUID NOT NULL REFERENCES notifications(id) ON DELETE CASCADE,
+    device_id UUID REFERENCES devices(id) ON DELETE SET NULL,
+    user_id UUID NOT NULL,
+    provider TEXT NOT NULL,
+    status VARCHAR(16) NOT NULL CHECK (status IN ('sent', 'failed', 'invalid_token')),
+    provider_message_id TEXT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_push_deliveries_notification_id ON push_deliveries(notification_id);
+CREATE INDEX idx_push_deliveries_user_created ON push_deliveries(user_id, created_at DESC);
+
+ALTER TABLE push_deliveries ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can read their own push_deliveries" ON push_deliveries
+    FOR SELECT
+    TO authenticated
+    USING (auth.uid() = user_id);
@@ -0,0 +1,7 @@
+-- migrations/2025-06-26-090000_add_quiet_hours/down.sql
+
+ALTER TABLE user_settings DROP CONSTRAINT quiet_hours_complete;
+ALTER TABLE user_settings DROP COLUMN push_batch_minutes;
+ALTER TABLE user_settings DROP COLUMN utc_offset_minutes;
+ALTER TABLE user_settings DROP COLUMN quiet_hours_end;
+ALTER TABLE user_settings DROP COLUMN quiet_hours_start;
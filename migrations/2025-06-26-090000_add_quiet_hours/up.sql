@@ -0,0 +1,13 @@
+-- migrations/2025-06-26-090000_add_quiet_hours/up.sql
+
+-- Heures calmes (heure locale) : aucune notification push n'est envoyée entre début et fin
+ALTER TABLE user_settings ADD COLUMN quiet_hours_start TIME;
+ALTER TABLE user_settings ADD COLUMN quiet_hours_end TIME;
+-- Décalage de l'heure locale de l'utilisateur par rapport à UTC
+ALTER TABLE user_settings ADD COLUMN utc_offset_minutes INTEGER NOT NULL DEFAULT 0
+    CHECK (utc_offset_minutes BETWEEN -840 AND 840);
+-- Fenêtre de regroupement : les notifications reçues pendant cette durée partent en un seul envoi
+ALTER TABLE user_settings ADD COLUMN push_batch_minutes INTEGER NOT NULL DEFAULT 2
+    CHECK (push_batch_minutes BETWEEN 0 AND 60);
+ALTER TABLE user_settings ADD CONSTRAINT quiet_hours_complete
+    CHECK ((quiet_hours_start IS NULL) = (quiet_hours_end IS NULL));
@@ -0,0 +1,4 @@
+-- migrations/2025-06-26-140000_add_task_stage/down.sql
+
+DROP INDEX IF EXISTS idx_tasks_user_stage;
+ALTER TABLE tasks DROP COLUMN stage;
@@ -0,0 +1,8 @@
+-- migrations/2025-06-26-140000_add_task_stage/up.sql
+
+-- Horizon de planification, indépendant du statut : backlog (pas encore planifiée),
+-- active (board et vue du jour) ou archive
+ALTER TABLE tasks ADD COLUMN stage VARCHAR(16) NOT NULL DEFAULT 'active'
+    CHECK (stage IN ('backlog', 'active', 'archive'));
+
+CREATE INDEX idx_tasks_user_stage ON tasks (user_id, stage);
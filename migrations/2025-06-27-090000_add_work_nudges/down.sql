@@ -0,0 +1,5 @@
+-- migrations/2025-06-27-090000_add_work_nudges/down.sql
+
+ALTER TABLE user_settings DROP COLUMN nudge_check_time;
+ALTER TABLE user_settings DROP COLUMN nudge_no_time_tracked;
+ALTER TABLE user_settings DROP COLUMN nudge_planned_tasks;
@@ -0,0 +1,8 @@
+-- migrations/2025-06-27-090000_add_work_nudges/up.sql
+
+-- Relances sur le rythme de travail (voir nudges.rs), désactivées par défaut :
+-- tâches planifiées du jour peu avancées, aucun temps suivi dans la journée
+ALTER TABLE user_settings ADD COLUMN nudge_planned_tasks BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE user_settings ADD COLUMN nudge_no_time_tracked BOOLEAN NOT NULL DEFAULT FALSE;
+-- Heure locale (utc_offset_minutes) à partir de laquelle la journée est évaluée
+ALTER TABLE user_settings ADD COLUMN nudge_check_time TIME NOT NULL DEFAULT '15:00';
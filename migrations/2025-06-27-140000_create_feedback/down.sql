@@ -0,0 +1,3 @@
+-- migrations/2025-06-27-140000_create_feedback/down.sql
+
+DROP TABLE IF EXISTS feedback;
@@ -0,0 +1,23 @@
+-- migrations/2025-06-27-140000_create_feedback/up.sql
+
+-- Retours des utilisateurs, consultés par les administrateurs (GET /admin/feedback)
+CREATE TABLE feedback (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    category VARCHAR(20) NOT NULL CHECK (category IN ('bug', 'feature_request', 'praise', 'other')),
+    message TEXT NOT NULL,
+    app_version VARCHAR(50),
+    -- Informations de diagnostic fournies volontairement par le client (appareil, logs, ...)
+    diagnostics JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_feedback_user_created ON feedback(user_id, created_at);
+CREATE INDEX idx_feedback_created ON feedback(created_at);
+
+ALTER TABLE feedback ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can read and send their own feedback" ON feedback
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
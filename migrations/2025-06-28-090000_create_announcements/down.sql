@@ -0,0 +1,4 @@
+-- migrations/2025-06-28-090000_create_announcements/down.sql
+
+DROP TABLE IF EXISTS announcement_acks;
+DROP TABLE IF EXISTS announcements;
@@ -0,0 +1,44 @@
+-- migrations/2025-06-28-090000_create_announcements/up.sql
+
+-- Nouveautés et annonces affichées dans l'application, gérées via /admin/announcements.
+-- Une annonce est visible à partir de published_at (NULL = brouillon).
+CREATE TABLE announcements (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    kind VARCHAR(16) NOT NULL DEFAULT 'feature' CHECK (kind IN ('feature', 'improvement', 'fix', 'notice')),
+    published_at TIMESTAMPTZ,
+    created_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_announcements_published_at ON announcements(published_at);
+
+CREATE TRIGGER set_announcements_timestamp
+BEFORE UPDATE ON announcements
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+-- Annonces lues par chaque utilisateur
+CREATE TABLE announcement_acks (
+    announcement_id UUID NOT NULL REFERENCES announcements(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    acked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (announcement_id, user_id)
+);
+
+CREATE INDEX idx_announcement_acks_user_id ON announcement_acks(user_id);
+
+ALTER TABLE announcements ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can read published announcements" ON announcements
+    FOR SELECT
+    TO authenticated
+    USING (published_at IS NOT NULL AND published_at <= NOW());
+
+ALTER TABLE announcement_acks ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own announcement acks" ON announcement_acks
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,4 @@
+-- migrations/2025-06-28-140000_create_feature_flags/down.sql
+
+DROP TABLE IF EXISTS feature_flag_overrides;
+DROP TABLE IF EXISTS feature_flags;
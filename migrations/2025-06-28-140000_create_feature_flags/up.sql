@@ -0,0 +1,39 @@
+-- migrations/2025-06-28-140000_create_feature_flags/up.sql
+
+-- Drapeaux de fonctionnalités (voir feature_flags.rs) : interrupteur global, déploiement
+-- progressif par pourcentage d'utilisateurs et exceptions par utilisateur
+CREATE TABLE feature_flags (
+    key VARCHAR(64) PRIMARY KEY CHECK (key ~ '^[a-z0-9][a-z0-9_.-]*$'),
+    description TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    rollout_percentage INTEGER NOT NULL DEFAULT 0 CHECK (rollout_percentage BETWEEN 0 AND 100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER set_feature_flags_timestamp
+BEFORE UPDATE ON feature_flags
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+-- Valeur imposée pour un utilisateur, prioritaire sur l'interrupteur et le pourcentage
+CREATE TABLE feature_flag_overrides (
+    flag_key VARCHAR(64) NOT NULL REFERENCES feature_flags(key) ON DELETE CASCADE ON UPDATE CASCADE,
+    user_id UUID NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (flag_key, user_id)
+);
+
+CREATE INDEX idx_feature_flag_overrides_user_id ON feature_flag_overrides(user_id);
+
+ALTER TABLE feature_flags ENABLE ROW LEVEL SECURITY;
+ALTER TABLE feature_flag_overrides ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can read their own flag overrides" ON feature_flag_overrides
+    FOR SELECT
+    TO authenticated
+    USING (auth.uid() = user_id);
+
+-- Le résumé IA existant reste actif pour tous
+INSERT INTO feature_flags (key, description, enabled, rollout_percentage)
+VALUES ('ai_summary', 'POST /analytics/ai-summary', TRUE, 100);
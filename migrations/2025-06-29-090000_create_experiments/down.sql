@@ -0,0 +1,5 @@
+-- migrations/2025-06-29-090000_create_experiments/down.sql
+
+DROP TABLE IF EXISTS experiment_events;
+DROP TABLE IF EXISTS experiment_assignments;
+DROP TABLE IF EXISTS experiments;
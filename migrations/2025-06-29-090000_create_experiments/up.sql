@@ -0,0 +1,54 @@
+-- migrations/2025-06-29-090000_create_experiments/up.sql
+
+-- Expériences A/B (voir experiments.rs) : variantes pondérées, affectation stable par utilisateur
+CREATE TABLE experiments (
+    key VARCHAR(64) PRIMARY KEY CHECK (key ~ '^[a-z0-9][a-z0-9_.-]*$'),
+    description TEXT,
+    -- [{"name": "control", "weight": 1}, {"name": "treatment", "weight": 1}]
+    variants JSONB NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER set_experiments_timestamp
+BEFORE UPDATE ON experiments
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+-- Première exposition de l'utilisateur : la variante reste acquise même si les poids changent
+CREATE TABLE experiment_assignments (
+    experiment_key VARCHAR(64) NOT NULL REFERENCES experiments(key) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    variant VARCHAR(64) NOT NULL,
+    assigned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (experiment_key, user_id)
+);
+
+CREATE INDEX idx_experiment_assignments_user_id ON experiment_assignments(user_id);
+
+-- Événements de conversion rattachés à la variante de l'utilisateur
+CREATE TABLE experiment_events (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    experiment_key VARCHAR(64) NOT NULL REFERENCES experiments(key) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    variant VARCHAR(64) NOT NULL,
+    event VARCHAR(64) NOT NULL,
+    value DOUBLE PRECISION,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_experiment_events_experiment ON experiment_events(experiment_key, event);
+CREATE INDEX idx_experiment_events_user_id ON experiment_events(user_id);
+
+ALTER TABLE experiments ENABLE ROW LEVEL SECURITY;
+ALTER TABLE experiment_assignments ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can read their own experiment assignments" ON experiment_assignments
+    FOR SELECT
+    TO authenticated
+    USING (auth.uid() = user_id);
+ALTER TABLE experiment_events ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can read their own experiment events" ON experiment_events
+    FOR SELECT
+    TO authenticated
+    USING (auth.uid() = user_id);
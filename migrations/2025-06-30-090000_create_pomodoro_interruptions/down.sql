@@ -0,0 +1,3 @@
+-- migrations/2025-06-30-090000_create_pomodoro_interruptions/down.sql
+
+DROP TABLE IF EXISTS pomodoro_interruptions;
@@ -0,0 +1,22 @@
+-- migrations/2025-06-30-090000_create_pomodoro_interruptions/up.sql
+
+-- Interruptions notées pendant une session pomodoro (time entry is_pomodoro_session)
+CREATE TABLE pomodoro_interruptions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    time_entry_id UUID NOT NULL REFERENCES time_entries(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    reason VARCHAR(20) NOT NULL CHECK (reason IN ('notification', 'colleague', 'meeting', 'call', 'self', 'other')),
+    note TEXT,
+    interrupted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_pomodoro_interruptions_time_entry ON pomodoro_interruptions(time_entry_id);
+CREATE INDEX idx_pomodoro_interruptions_user_interrupted ON pomodoro_interruptions(user_id, interrupted_at);
+
+ALTER TABLE pomodoro_interruptions ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own pomodoro interruptions" ON pomodoro_interruptions
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
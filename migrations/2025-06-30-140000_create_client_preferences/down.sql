@@ -0,0 +1,3 @@
+-- migrations/2025-06-30-140000_create_client_preferences/down.sql
+
+DROP TABLE IF EXISTS client_preferences;
@@ -0,0 +1,24 @@
+-- migrations/2025-06-30-140000_create_client_preferences/up.sql
+
+-- Préférences d'interface des clients (web, mobile), un document JSON par espace de noms
+CREATE TABLE client_preferences (
+    user_id UUID NOT NULL,
+    namespace VARCHAR(64) NOT NULL CHECK (namespace ~ '^[a-z0-9][a-z0-9_.-]*$'),
+    data JSONB NOT NULL CHECK (jsonb_typeof(data) = 'object'),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Sert à la détection des conflits d'écriture (expected_updated_at)
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, namespace)
+);
+
+CREATE TRIGGER set_client_preferences_timestamp
+BEFORE UPDATE ON client_preferences
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+ALTER TABLE client_preferences ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own client preferences" ON client_preferences
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,3 @@
+-- migrations/2025-07-01-090000_create_maintenance_jobs/down.sql
+
+DROP TABLE IF EXISTS maintenance_jobs;
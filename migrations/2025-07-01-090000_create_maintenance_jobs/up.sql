@@ -0,0 +1,19 @@
+-- migrations/2025-07-01-090000_create_maintenance_jobs/up.sql
+
+-- File des opérations de maintenance déclenchées par un administrateur (voir maintenance.rs)
+CREATE TABLE maintenance_jobs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    kind VARCHAR(64) NOT NULL CHECK (kind IN ('vacuum_analyze', 'rebuild_search_indexes', 'purge_deleted_accounts', 'recompute_counters')),
+    status VARCHAR(16) NOT NULL DEFAULT 'queued' CHECK (status IN ('queued', 'running', 'succeeded', 'failed')),
+    requested_by UUID NOT NULL,
+    -- Compte rendu de l'opération (tables traitées, lignes corrigées...)
+    result JSONB,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_maintenance_jobs_status ON maintenance_jobs(status, created_at);
+
+ALTER TABLE maintenance_jobs ENABLE ROW LEVEL SECURITY;
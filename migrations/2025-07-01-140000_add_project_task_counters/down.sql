@@ -0,0 +1,8 @@
+-- migrations/2025-07-01-140000_add_project_task_counters/down.sql
+
+DROP TRIGGER IF EXISTS update_project_task_counts ON tasks;
+DROP FUNCTION IF EXISTS trigger_update_project_task_counts();
+
+ALTER TABLE projects
+    DROP COLUMN IF EXISTS open_task_count,
+    DROP COLUMN IF EXISTS completed_task_count;
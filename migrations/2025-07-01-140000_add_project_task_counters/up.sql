@@ -0,0 +1,54 @@
+-- migrations/2025-07-01-140000_add_project_task_counters/up.sql
+
+-- Compteurs de tâches dénormalisés : les listes de projets n'ont plus à agréger la table tasks.
+-- Tenus à jour par trigger pour couvrir tous les chemins d'écriture (handlers, imports, macros...).
+ALTER TABLE projects
+    ADD COLUMN open_task_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN completed_task_count INTEGER NOT NULL DEFAULT 0;
+
+-- Statuts terminés : doit rester aligné sur DONE_TASK_STATUSES (models.rs)
+CREATE OR REPLACE FUNCTION trigger_update_project_task_counts()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE'
+        AND OLD.project_id IS NOT DISTINCT FROM NEW.project_id
+        AND (OLD.status IN ('completed', 'done')) = (NEW.status IN ('completed', 'done')) THEN
+        RETURN NULL;
+    END IF;
+
+    IF TG_OP IN ('UPDATE', 'DELETE') AND OLD.project_id IS NOT NULL THEN
+        UPDATE projects SET
+            open_task_count = open_task_count - CASE WHEN OLD.status IN ('completed', 'done') THEN 0 ELSE 1 END,
+            completed_task_count = completed_task_count - CASE WHEN OLD.status IN ('completed', 'done') THEN 1 ELSE 0 END
+        WHERE id = OLD.project_id;
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') AND NEW.project_id IS NOT NULL THEN
+        UPDATE projects SET
+            open_task_count = open_task_count + CASE WHEN NEW.status IN ('completed', 'done') THEN 0 ELSE 1 END,
+            completed_task_count = completed_task_count + CASE WHEN NEW.status IN ('completed', 'done') THEN 1 ELSE 0 END
+        WHERE id = NEW.project_id;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_project_task_counts
+AFTER INSERT OR DELETE OR UPDATE OF status, project_id ON tasks
+FOR EACH ROW
+EXECUTE FUNCTION trigger_update_project_task_counts();
+
+-- Valeurs initiales pour les projets existants
+UPDATE projects p SET
+    open_task_count = counts.open_count,
+    completed_task_count = counts.completed_count
+FROM (
+    SELECT project_id,
+           COUNT(*) FILTER (WHERE status NOT IN ('completed', 'done')) AS open_count,
+           COUNT(*) FILTER (WHERE status IN ('completed', 'done')) AS completed_count
+    FROM tasks
+    WHERE project_id IS NOT NULL
+    GROUP BY project_id
+) counts
+WHERE p.id = counts.project_id;
@@ -0,0 +1,13 @@
+-- migrations/2025-07-02-090000_create_daily_tracked_time/down.sql
+
+DELETE FROM maintenance_jobs WHERE kind = 'rebuild_tracked_time';
+ALTER TABLE maintenance_jobs DROP CONSTRAINT maintenance_jobs_kind_check;
+ALTER TABLE maintenance_jobs ADD CONSTRAINT maintenance_jobs_kind_check
+    CHECK (kind IN ('vacuum_analyze', 'rebuild_search_indexes', 'purge_deleted_accounts', 'recompute_counters'));
+
+DROP TRIGGER IF EXISTS queue_tasks_tracked_time_refresh ON tasks;
+DROP FUNCTION IF EXISTS trigger_queue_task_tracked_time_refresh();
+DROP TRIGGER IF EXISTS queue_time_entries_tracked_time_refresh ON time_entries;
+DROP FUNCTION IF EXISTS trigger_queue_tracked_time_refresh();
+DROP TABLE IF EXISTS daily_tracked_time_refresh;
+DROP TABLE IF EXISTS daily_tracked_time;
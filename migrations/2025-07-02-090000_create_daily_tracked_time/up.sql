@@ -0,0 +1,82 @@
+-- migrations/2025-07-02-090000_create_daily_tracked_time/up.sql
+
+-- Temps suivi agrégé par utilisateur, projet et jour (UTC), lu par les analytics à la place
+-- de time_entries. project_id NULL : tâches sans projet. Voir tracked_time.rs.
+CREATE TABLE daily_tracked_time (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    project_id UUID,
+    day DATE NOT NULL,
+    tracked_seconds BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_daily_tracked_time_user_day ON daily_tracked_time(user_id, day);
+
+-- Jours à recalculer, alimentés par trigger à chaque écriture de temps
+CREATE TABLE daily_tracked_time_refresh (
+    user_id UUID NOT NULL,
+    day DATE NOT NULL,
+    queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, day)
+);
+
+CREATE OR REPLACE FUNCTION trigger_queue_tracked_time_refresh()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        INSERT INTO daily_tracked_time_refresh (user_id, day)
+        VALUES (OLD.user_id, (OLD.start_time AT TIME ZONE 'UTC')::date)
+        ON CONFLICT DO NOTHING;
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        INSERT INTO daily_tracked_time_refresh (user_id, day)
+        VALUES (NEW.user_id, (NEW.start_time AT TIME ZONE 'UTC')::date)
+        ON CONFLICT DO NOTHING;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER queue_time_entries_tracked_time_refresh
+AFTER INSERT OR DELETE OR UPDATE OF user_id, start_time, duration_seconds, is_break, task_id ON time_entries
+FOR EACH ROW
+EXECUTE FUNCTION trigger_queue_tracked_time_refresh();
+
+-- Déplacer une tâche vers un autre projet change l'attribution de tout son temps
+CREATE OR REPLACE FUNCTION trigger_queue_task_tracked_time_refresh()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO daily_tracked_time_refresh (user_id, day)
+    SELECT DISTINCT te.user_id, (te.start_time AT TIME ZONE 'UTC')::date
+    FROM time_entries te
+    WHERE te.task_id = NEW.id
+    ON CONFLICT DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER queue_tasks_tracked_time_refresh
+AFTER UPDATE OF project_id ON tasks
+FOR EACH ROW
+WHEN (OLD.project_id IS DISTINCT FROM NEW.project_id)
+EXECUTE FUNCTION trigger_queue_task_tracked_time_refresh();
+
+-- Valeurs initiales
+INSERT INTO daily_tracked_time (user_id, project_id, day, tracked_seconds)
+SELECT te.user_id, t.project_id, (te.start_time AT TIME ZONE 'UTC')::date,
+       COALESCE(SUM(te.duration_seconds), 0)
+FROM time_entries te
+JOIN tasks t ON t.id = te.task_id
+WHERE te.is_break = FALSE
+GROUP BY te.user_id, t.project_id, (te.start_time AT TIME ZONE 'UTC')::date;
+
+ALTER TABLE maintenance_jobs DROP CONSTRAINT maintenance_jobs_kind_check;
+ALTER TABLE maintenance_jobs ADD CONSTRAINT maintenance_jobs_kind_check
+    CHECK (kind IN ('vacuum_analyze', 'rebuild_search_indexes', 'purge_deleted_accounts', 'recompute_counters', 'rebuild_tracked_time'));
+
+ALTER TABLE daily_tracked_time ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can read their own tracked time" ON daily_tracked_time
+    FOR SELECT
+    TO authenticated
+    USING (auth.uid() = user_id);
+ALTER TABLE daily_tracked_time_refresh ENABLE ROW LEVEL SECURITY;
@@ -0,0 +1,56 @@
+-- migrations/2025-07-03-090000_partition_time_entries_by_month/down.sql
+
+DELETE FROM maintenance_jobs WHERE kind = 'ensure_partitions';
+ALTER TABLE maintenance_jobs DROP CONSTRAINT maintenance_jobs_kind_check;
+ALTER TABLE maintenance_jobs ADD CONSTRAINT maintenance_jobs_kind_check
+    CHECK (kind IN ('vacuum_analyze', 'rebuild_search_indexes', 'purge_deleted_accounts', 'recompute_counters', 'rebuild_tracked_time'));
+
+ALTER TABLE time_entries RENAME TO time_entries_partitioned;
+
+CREATE TABLE time_entries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    start_time TIMESTAMPTZ NOT NULL,
+    end_time TIMESTAMPTZ,
+    duration_seconds INTEGER,
+    is_pomodoro_session BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    is_break BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+INSERT INTO time_entries (
+    id, user_id, task_id, start_time, end_time, duration_seconds,
+    is_pomodoro_session, created_at, updated_at, is_break
+)
+SELECT id, user_id, task_id, start_time, end_time, duration_seconds,
+       is_pomodoro_session, created_at, updated_at, is_break
+FROM time_entries_partitioned;
+
+-- Supprime aussi toutes les partitions
+DROP TABLE time_entries_partitioned;
+DROP FUNCTION IF EXISTS trigger_cleanup_time_entry_references();
+DROP FUNCTION IF EXISTS ensure_time_entries_partition(DATE);
+
+CREATE TRIGGER set_time_entries_timestamp
+BEFORE UPDATE ON time_entries
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+CREATE TRIGGER queue_time_entries_tracked_time_refresh
+AFTER INSERT OR DELETE OR UPDATE OF user_id, start_time, duration_seconds, is_break, task_id ON time_entries
+FOR EACH ROW
+EXECUTE FUNCTION trigger_queue_tracked_time_refresh();
+
+ALTER TABLE pomodoro_interruptions ADD CONSTRAINT pomodoro_interruptions_time_entry_id_fkey
+    FOREIGN KEY (time_entry_id) REFERENCES time_entries(id) ON DELETE CASCADE;
+ALTER TABLE calendar_suggestions ADD CONSTRAINT calendar_suggestions_time_entry_id_fkey
+    FOREIGN KEY (time_entry_id) REFERENCES time_entries(id) ON DELETE SET NULL;
+
+ALTER TABLE time_entries ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own time_entries" ON time_entries
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
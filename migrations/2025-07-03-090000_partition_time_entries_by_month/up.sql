@@ -0,0 +1,127 @@
+-- migrations/2025-07-03-090000_partition_time_entries_by_month/up.sql
+
+-- time_entries devient une table partitionnée par mois sur start_time : les requêtes
+-- bornées par date ne lisent que les mois concernés, et l'historique des sessions pomodoro
+-- peut grossir sans dégrader les écritures courantes. Les partitions à venir sont créées
+-- par la tâche de maintenance (ensure_time_entries_partition) ; time_entries_default
+-- recueille les lignes hors des mois existants.
+
+ALTER TABLE time_entries RENAME TO time_entries_unpartitioned;
+ALTER TABLE pomodoro_interruptions DROP CONSTRAINT pomodoro_interruptions_time_entry_id_fkey;
+ALTER TABLE calendar_suggestions DROP CONSTRAINT calendar_suggestions_time_entry_id_fkey;
+
+-- La clé primaire d'une table partitionnée doit contenir la clé de partitionnement
+CREATE TABLE time_entries (
+    id UUID NOT NULL DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    start_time TIMESTAMPTZ NOT NULL,
+    end_time TIMESTAMPTZ,
+    duration_seconds INTEGER,
+    is_pomodoro_session BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    is_break BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (id, start_time)
+) PARTITION BY RANGE (start_time);
+
+CREATE TABLE time_entries_default PARTITION OF time_entries DEFAULT;
+
+CREATE INDEX idx_time_entries_user_start ON time_entries(user_id, start_time);
+CREATE INDEX idx_time_entries_task_id ON time_entries(task_id);
+-- Timers en cours (end_time NULL) : une entrée par utilisateur au plus, lue à chaque saisie
+CREATE INDEX idx_time_entries_running ON time_entries(user_id) WHERE end_time IS NULL;
+
+-- Crée la partition du mois contenant month_start (no-op si elle existe). Les lignes de ce
+-- mois déjà présentes dans la partition par défaut y sont déplacées avant le rattachement.
+CREATE OR REPLACE FUNCTION ensure_time_entries_partition(month_start DATE)
+RETURNS BOOLEAN AS $$
+DECLARE
+    range_start TIMESTAMPTZ := date_trunc('month', month_start)::timestamp AT TIME ZONE 'UTC';
+    range_end TIMESTAMPTZ := (date_trunc('month', month_start) + INTERVAL '1 month')::timestamp AT TIME ZONE 'UTC';
+    partition_name TEXT := 'time_entries_' || to_char(month_start, 'YYYY_MM');
+BEGIN
+    IF to_regclass(partition_name) IS NOT NULL THEN
+        RETURN FALSE;
+    END IF;
+
+    EXECUTE format(
+        'CREATE TABLE %I (LIKE time_entries INCLUDING DEFAULTS INCLUDING CONSTRAINTS)',
+        partition_name
+    );
+    PERFORM set_config('optitask.moving_time_entries', 'on', TRUE);
+    EXECUTE format(
+        'WITH moved AS (DELETE FROM time_entries_default WHERE start_time >= %L AND start_time < %L RETURNING *) '
+        'INSERT INTO %I SELECT * FROM moved',
+        range_start, range_end, partition_name
+    );
+    PERFORM set_config('optitask.moving_time_entries', 'off', TRUE);
+    EXECUTE format(
+        'ALTER TABLE time_entries ATTACH PARTITION %I FOR VALUES FROM (%L) TO (%L)',
+        partition_name, range_start, range_end
+    );
+    RETURN TRUE;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Un mois par mois déjà suivi, plus le mois courant et les trois suivants
+SELECT ensure_time_entries_partition(month_start::date)
+FROM (
+    SELECT DISTINCT date_trunc('month', start_time AT TIME ZONE 'UTC') AS month_start
+    FROM time_entries_unpartitioned
+    UNION
+    SELECT date_trunc('month', NOW() AT TIME ZONE 'UTC') + make_interval(months => ahead)
+    FROM generate_series(0, 3) AS ahead
+) months;
+
+INSERT INTO time_entries (
+    id, user_id, task_id, start_time, end_time, duration_seconds,
+    is_pomodoro_session, created_at, updated_at, is_break
+)
+SELECT id, user_id, task_id, start_time, end_time, duration_seconds,
+       is_pomodoro_session, created_at, updated_at, is_break
+FROM time_entries_unpartitioned;
+
+DROP TABLE time_entries_unpartitioned;
+
+CREATE TRIGGER set_time_entries_timestamp
+BEFORE UPDATE ON time_entries
+FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+CREATE TRIGGER queue_time_entries_tracked_time_refresh
+AFTER INSERT OR DELETE OR UPDATE OF user_id, start_time, duration_seconds, is_break, task_id ON time_entries
+FOR EACH ROW
+EXECUTE FUNCTION trigger_queue_tracked_time_refresh();
+
+-- Remplace les clés étrangères vers time_entries(id), impossibles sans start_time :
+-- interruptions supprimées, suggestions d'agenda détachées. Ignoré lorsqu'une ligne ne fait
+-- que changer de partition (déplacement depuis la partition par défaut, start_time modifié).
+CREATE OR REPLACE FUNCTION trigger_cleanup_time_entry_references()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF current_setting('optitask.moving_time_entries', TRUE) = 'on'
+        OR EXISTS (SELECT 1 FROM time_entries WHERE id = OLD.id) THEN
+        RETURN NULL;
+    END IF;
+    DELETE FROM pomodoro_interruptions WHERE time_entry_id = OLD.id;
+    UPDATE calendar_suggestions SET time_entry_id = NULL WHERE time_entry_id = OLD.id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER cleanup_time_entry_references
+AFTER DELETE ON time_entries
+FOR EACH ROW
+EXECUTE FUNCTION trigger_cleanup_time_entry_references();
+
+ALTER TABLE time_entries ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own time_entries" ON time_entries
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
+
+ALTER TABLE maintenance_jobs DROP CONSTRAINT maintenance_jobs_kind_check;
+ALTER TABLE maintenance_jobs ADD CONSTRAINT maintenance_jobs_kind_check
+    CHECK (kind IN ('vacuum_analyze', 'rebuild_search_indexes', 'purge_deleted_accounts', 'recompute_counters', 'rebuild_tracked_time', 'ensure_partitions'));
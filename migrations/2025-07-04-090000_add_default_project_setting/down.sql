@@ -0,0 +1,4 @@
+-- migrations/2025-07-04-090000_add_default_project_setting/down.sql
+
+DROP INDEX IF EXISTS idx_tasks_inbox;
+ALTER TABLE user_settings DROP COLUMN default_project_id;
@@ -0,0 +1,9 @@
+-- migrations/2025-07-04-090000_add_default_project_setting/up.sql
+
+-- Projet appliqué aux tâches créées sans project_id ; NULL = elles restent dans l'Inbox
+-- (tâches sans projet). Supprimer le projet ramène les nouvelles tâches dans l'Inbox.
+ALTER TABLE user_settings
+    ADD COLUMN default_project_id UUID REFERENCES projects(id) ON DELETE SET NULL;
+
+-- Vue Inbox (GET /tasks?view=inbox) et compteurs du tableau de bord
+CREATE INDEX idx_tasks_inbox ON tasks (user_id, created_at DESC) WHERE project_id IS NULL;
@@ -0,0 +1,5 @@
+-- migrations/2025-07-05-090000_add_time_entry_clock_skew/down.sql
+
+DROP INDEX IF EXISTS idx_time_entries_suspect_clock;
+ALTER TABLE time_entries DROP COLUMN suspect_clock;
+ALTER TABLE time_entries DROP COLUMN clock_skew_seconds;
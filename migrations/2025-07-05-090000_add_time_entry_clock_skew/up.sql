@@ -0,0 +1,10 @@
+-- migrations/2025-07-05-090000_add_time_entry_clock_skew/up.sql
+
+-- Décalage mesuré entre l'horloge du client (client_time) et celle du serveur à la
+-- création, en secondes (positif = client en avance). NULL si le client ne l'a pas fourni
+-- ou si le décalage était dans la tolérance.
+ALTER TABLE time_entries ADD COLUMN clock_skew_seconds INTEGER;
+-- Décalage invraisemblable : horaires conservés tels que reçus, à revoir (rapport d'intégrité)
+ALTER TABLE time_entries ADD COLUMN suspect_clock BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_time_entries_suspect_clock ON time_entries (user_id) WHERE suspect_clock;
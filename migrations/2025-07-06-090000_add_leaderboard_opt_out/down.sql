@@ -0,0 +1,3 @@
+-- migrations/2025-07-06-090000_add_leaderboard_opt_out/down.sql
+
+ALTER TABLE user_settings DROP COLUMN leaderboard_opt_out;
@@ -0,0 +1,4 @@
+-- migrations/2025-07-06-090000_add_leaderboard_opt_out/up.sql
+
+-- Exclut l'utilisateur des classements de ses espaces (GET /workspaces/{id}/leaderboard)
+ALTER TABLE user_settings ADD COLUMN leaderboard_opt_out BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- migrations/2025-07-07-090000_create_public_badge_tokens/down.sql
+
+DROP TABLE IF EXISTS public_badge_tokens;
@@ -0,0 +1,16 @@
+-- migrations/2025-07-07-090000_create_public_badge_tokens/up.sql
+
+-- Jeton secret du badge public de statistiques (GET /public/badge/<token>.svg),
+-- un par utilisateur ; le supprimer désactive le badge
+CREATE TABLE public_badge_tokens (
+    user_id UUID PRIMARY KEY,
+    token TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE public_badge_tokens ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own public_badge_tokens" ON public_badge_tokens
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,3 @@
+-- migrations/2025-07-08-090000_create_api_keys/down.sql
+
+DROP TABLE IF EXISTS api_keys;
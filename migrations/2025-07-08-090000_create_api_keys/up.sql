@@ -0,0 +1,23 @@
+-- migrations/2025-07-08-090000_create_api_keys/up.sql
+
+-- Clés d'API des intégrations sans code (déclencheurs Zapier par interrogation).
+-- La clé identifie seule l'utilisateur : seule son empreinte SHA-256 est stockée,
+-- le préfixe en clair sert à la reconnaître dans la liste.
+CREATE TABLE api_keys (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_api_keys_user_id ON api_keys(user_id);
+
+ALTER TABLE api_keys ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own API keys" ON api_keys
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,3 @@
+-- migrations/2025-07-09-090000_create_backup_configs/down.sql
+
+DROP TABLE IF EXISTS backup_configs;
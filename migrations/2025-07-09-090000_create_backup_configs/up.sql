@@ -0,0 +1,32 @@
+-- migrations/2025-07-09-090000_create_backup_configs/up.sql
+
+-- Sauvegarde chiffrée des données de l'utilisateur vers son propre stockage (WebDAV ou S3).
+-- La clé de chiffrement est générée à la configuration et n'est montrée qu'une fois.
+CREATE TABLE backup_configs (
+    user_id UUID PRIMARY KEY,
+    target_type TEXT NOT NULL CHECK (target_type IN ('webdav', 's3')),
+    endpoint_url TEXT NOT NULL,
+    bucket TEXT,
+    region TEXT,
+    path_prefix TEXT NOT NULL DEFAULT '',
+    username TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    encryption_key TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_attempt_at TIMESTAMPTZ,
+    last_success_at TIMESTAMPTZ,
+    last_error TEXT,
+    last_object_key TEXT,
+    last_size_bytes BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_backup_configs_due ON backup_configs(last_attempt_at) WHERE enabled;
+
+ALTER TABLE backup_configs ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own backup configuration" ON backup_configs
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,11 @@
+-- migrations/2025-07-10-090000_add_restore_jobs/down.sql
+
+DROP INDEX IF EXISTS idx_maintenance_jobs_requested_by;
+
+DELETE FROM maintenance_jobs WHERE kind = 'restore_backup';
+ALTER TABLE maintenance_jobs DROP CONSTRAINT maintenance_jobs_kind_check;
+ALTER TABLE maintenance_jobs ADD CONSTRAINT maintenance_jobs_kind_check
+    CHECK (kind IN ('vacuum_analyze', 'rebuild_search_indexes', 'purge_deleted_accounts', 'recompute_counters', 'rebuild_tracked_time', 'ensure_partitions'));
+
+ALTER TABLE maintenance_jobs DROP COLUMN IF EXISTS progress;
+ALTER TABLE maintenance_jobs DROP COLUMN IF EXISTS payload;
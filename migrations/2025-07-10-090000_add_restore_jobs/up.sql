@@ -0,0 +1,12 @@
+-- migrations/2025-07-10-090000_add_restore_jobs/up.sql
+
+-- Restauration de sauvegarde exécutée par la file des opérations de fond (voir backup_restore.rs).
+-- payload : données d'entrée de l'opération, effacées à la fin ; progress : avancement.
+ALTER TABLE maintenance_jobs ADD COLUMN payload JSONB;
+ALTER TABLE maintenance_jobs ADD COLUMN progress JSONB;
+
+ALTER TABLE maintenance_jobs DROP CONSTRAINT maintenance_jobs_kind_check;
+ALTER TABLE maintenance_jobs ADD CONSTRAINT maintenance_jobs_kind_check
+    CHECK (kind IN ('vacuum_analyze', 'rebuild_search_indexes', 'purge_deleted_accounts', 'recompute_counters', 'rebuild_tracked_time', 'ensure_partitions', 'restore_backup'));
+
+CREATE INDEX idx_maintenance_jobs_requested_by ON maintenance_jobs(requested_by, kind, status);
@@ -0,0 +1,3 @@
+-- migrations/2025-07-11-090000_add_project_themes/down.sql
+
+ALTER TABLE projects DROP COLUMN theme;
@@ -0,0 +1,5 @@
+-- migrations/2025-07-11-090000_add_project_themes/up.sql
+
+-- Personnalisation visuelle synchronisée entre appareils :
+-- {"preset": "ocean", "accent_color": "#0ea5e9", "cover_image_url": "https://...", "emoji": "🌊"}
+ALTER TABLE projects ADD COLUMN theme JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,4 @@
+-- migrations/2025-07-12-090000_add_suggest_trigram_indexes/down.sql
+
+DROP INDEX IF EXISTS idx_labels_name_trgm;
+DROP INDEX IF EXISTS idx_projects_name_trgm;
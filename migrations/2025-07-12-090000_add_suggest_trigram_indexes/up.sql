@@ -0,0 +1,8 @@
+-- migrations/2025-07-12-090000_add_suggest_trigram_indexes/up.sql
+
+-- Suggestions de saisie (GET /suggest) : ILIKE par préfixe de mot sur les noms.
+-- Les titres de tâches sont déjà couverts par idx_tasks_title_trgm.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_projects_name_trgm ON projects USING GIN (name gin_trgm_ops);
+CREATE INDEX idx_labels_name_trgm ON labels USING GIN (name gin_trgm_ops);
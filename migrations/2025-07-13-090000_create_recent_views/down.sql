@@ -0,0 +1,3 @@
+-- migrations/2025-07-13-090000_create_recent_views/down.sql
+
+DROP TABLE IF EXISTS recent_views;
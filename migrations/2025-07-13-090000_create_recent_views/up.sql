@@ -0,0 +1,23 @@
+-- migrations/2025-07-13-090000_create_recent_views/up.sql
+
+-- Éléments récemment consultés (GET /recent), partagés entre les appareils.
+-- Une ligne par élément ; score = fréquence amortie (demi-vie de 3 jours),
+-- incrémenté à chaque consultation.
+CREATE TABLE recent_views (
+    user_id UUID NOT NULL,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('task', 'project')),
+    entity_id UUID NOT NULL,
+    view_count INTEGER NOT NULL DEFAULT 1,
+    score DOUBLE PRECISION NOT NULL DEFAULT 1,
+    last_viewed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, entity_type, entity_id)
+);
+
+CREATE INDEX idx_recent_views_user_last_viewed ON recent_views(user_id, last_viewed_at DESC);
+
+ALTER TABLE recent_views ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own recent views" ON recent_views
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
@@ -0,0 +1,5 @@
+-- migrations/2025-07-14-090000_add_time_entry_heartbeats/down.sql
+
+DROP INDEX IF EXISTS idx_time_entries_running_heartbeat;
+ALTER TABLE time_entries DROP COLUMN auto_stopped;
+ALTER TABLE time_entries DROP COLUMN last_heartbeat_at;
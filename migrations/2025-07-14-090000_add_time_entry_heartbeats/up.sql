@@ -0,0 +1,11 @@
+-- migrations/2025-07-14-090000_add_time_entry_heartbeats/up.sql
+
+-- Dernier signal de vie d'un minuteur en cours (POST /time-entries/{id}/heartbeat).
+-- NULL pour les clients qui n'en envoient pas : leurs entrées ne sont jamais closes d'office.
+ALTER TABLE time_entries ADD COLUMN last_heartbeat_at TIMESTAMPTZ;
+-- Entrée close par la tâche de fond faute de signal (navigateur fermé, crash...),
+-- sa fin étant ramenée au dernier signal reçu
+ALTER TABLE time_entries ADD COLUMN auto_stopped BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_time_entries_running_heartbeat ON time_entries (last_heartbeat_at)
+    WHERE end_time IS NULL AND last_heartbeat_at IS NOT NULL;
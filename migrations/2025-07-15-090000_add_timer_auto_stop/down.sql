@@ -0,0 +1,4 @@
+-- migrations/2025-07-15-090000_add_timer_auto_stop/down.sql
+
+ALTER TABLE user_settings DROP COLUMN timer_auto_stop_mode;
+ALTER TABLE user_settings DROP COLUMN timer_auto_stop_time;
@@ -0,0 +1,8 @@
+-- migrations/2025-07-15-090000_add_timer_auto_stop/up.sql
+
+-- Heure locale (utc_offset_minutes) à laquelle un minuteur encore en cours est arrêté,
+-- NULL = désactivé. "cap" arrête l'entrée à cette heure ; "split" l'arrête et poursuit
+-- le suivi dans une nouvelle entrée, pour que chaque journée garde son propre temps.
+ALTER TABLE user_settings ADD COLUMN timer_auto_stop_time TIME;
+ALTER TABLE user_settings ADD COLUMN timer_auto_stop_mode VARCHAR(10) NOT NULL DEFAULT 'cap'
+    CHECK (timer_auto_stop_mode IN ('cap', 'split'));
@@ -0,0 +1,3 @@
+-- migrations/2025-07-16-090000_add_project_archived_at/down.sql
+
+ALTER TABLE projects DROP COLUMN archived_at;
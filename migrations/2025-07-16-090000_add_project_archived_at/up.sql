@@ -0,0 +1,5 @@
+-- migrations/2025-07-16-090000_add_project_archived_at/up.sql
+
+-- Date d'archivage d'un projet terminé (POST /projects/{id}/complete), NULL = actif.
+-- Les projets archivés restent consultables mais sont masqués de la liste par défaut.
+ALTER TABLE projects ADD COLUMN archived_at TIMESTAMPTZ;
@@ -0,0 +1,4 @@
+-- migrations/2025-07-17-090000_add_task_time_budget/down.sql
+
+ALTER TABLE tasks DROP COLUMN time_budget_alerted_pct;
+ALTER TABLE tasks DROP COLUMN time_budget_seconds;
@@ -0,0 +1,7 @@
+-- migrations/2025-07-17-090000_add_task_time_budget/up.sql
+
+-- Budget de temps d'une tâche, en secondes (NULL = pas de budget).
+-- time_budget_alerted_pct retient le dernier seuil notifié (0, 80 ou 100) pour ne pas
+-- répéter l'alerte à chaque entrée de temps ; il redescend si le temps suivi diminue.
+ALTER TABLE tasks ADD COLUMN time_budget_seconds INTEGER CHECK (time_budget_seconds > 0);
+ALTER TABLE tasks ADD COLUMN time_budget_alerted_pct SMALLINT NOT NULL DEFAULT 0;
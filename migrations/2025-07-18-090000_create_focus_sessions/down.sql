@@ -0,0 +1,4 @@
+-- migrations/2025-07-18-090000_create_focus_sessions/down.sql
+
+ALTER TABLE time_entries DROP COLUMN focus_session_id;
+DROP TABLE focus_sessions;
@@ -0,0 +1,32 @@
+-- migrations/2025-07-18-090000_create_focus_sessions/up.sql
+
+-- Sessions de concentration (voir focus_sessions.rs) : liées à un label ou à un projet.
+-- Une seule session active (ended_at NULL) par utilisateur. Le bilan est figé à la fin.
+CREATE TABLE focus_sessions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    label_id UUID REFERENCES labels(id) ON DELETE CASCADE,
+    project_id UUID REFERENCES projects(id) ON DELETE CASCADE,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ended_at TIMESTAMPTZ,
+    summary JSONB,
+    CHECK ((label_id IS NULL) <> (project_id IS NULL)),
+    CHECK (ended_at IS NULL OR ended_at >= started_at)
+);
+
+CREATE UNIQUE INDEX idx_focus_sessions_one_active ON focus_sessions(user_id) WHERE ended_at IS NULL;
+CREATE INDEX idx_focus_sessions_user_started ON focus_sessions(user_id, started_at DESC);
+
+ALTER TABLE focus_sessions ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own focus sessions" ON focus_sessions
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
+
+-- Entrées de temps créées pendant une session
+ALTER TABLE time_entries
+    ADD COLUMN focus_session_id UUID REFERENCES focus_sessions(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_time_entries_focus_session ON time_entries(focus_session_id)
+    WHERE focus_session_id IS NOT NULL;
@@ -0,0 +1,3 @@
+-- migrations/2025-07-19-090000_create_task_external_links/down.sql
+
+DROP TABLE task_external_links;
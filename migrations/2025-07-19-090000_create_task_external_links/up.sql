@@ -0,0 +1,26 @@
+-- migrations/2025-07-19-090000_create_task_external_links/up.sql
+
+-- Références externes typées attachées à une tâche (voir external_links.rs). La table
+-- task_links est déjà prise par les liens entre tâches issus des mentions.
+CREATE TABLE task_external_links (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('url', 'github_issue', 'jira', 'email')),
+    reference TEXT NOT NULL,
+    url TEXT,
+    title TEXT,
+    favicon_url TEXT,
+    metadata_fetched_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (task_id, kind, reference)
+);
+
+CREATE INDEX idx_task_external_links_task ON task_external_links(task_id, created_at);
+
+ALTER TABLE task_external_links ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "Users can manage their own task external links" ON task_external_links
+    FOR ALL
+    TO authenticated
+    USING (auth.uid() = user_id)
+    WITH CHECK (auth.uid() = user_id);
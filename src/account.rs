@@ -0,0 +1,363 @@
+// OptiTask/backend-api/src/account.rs
+// Cycle de vie du compte : suppression programmée avec délai de grâce, lecture seule
+// pendant ce délai, puis effacement des données par une tâche de fond.
+use crate::db::DbPool;
+use crate::demo::DEMO_TOKEN_HEADER;
+use crate::error_handler::ServiceError;
+use crate::models::{AccountDeletionRequest, NewNotification};
+use crate::notifications::{KIND_ACCOUNT_DELETED, KIND_ACCOUNT_DELETION_SCHEDULED};
+use crate::read_only_mode::{self, ReadOnlyMode};
+use crate::schema::{
+    account_deletion_requests, ai_summaries, analytics_snapshots, announcement_acks, api_keys,
+    app_passwords, automation_rules, backup_configs, calendar_integrations, calendar_oauth_states,
+    calendar_project_links, calendar_suggestions, client_preferences, confirmation_tokens,
+    daily_tracked_time, daily_tracked_time_refresh, devices, experiment_assignments,
+    experiment_events, feature_flag_overrides, feedback, focus_sessions, inbound_email_addresses,
+    labels, notifications, pomodoro_interruptions, projects, public_badge_tokens, recent_views,
+    task_aging_rules, task_external_links, task_watchers, tasks, time_entries, timesheets,
+    user_onboarding, user_settings, workspace_members, workspaces,
+};
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, ResponseError};
+use chrono::{Duration as ChronoDuration, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const DELETION_GRACE_PERIOD_DAYS: i64 = 14;
+// Fréquence de recherche des suppressions arrivées à échéance
+const DELETION_JOB_INTERVAL_SECS: u64 = 3600;
+
+// Supprime toutes les données appartenant à un utilisateur.
+// Les associations task_labels sont supprimées en cascade avec les tâches et labels.
+pub async fn wipe_user_data(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<(), ServiceError> {
+    diesel::delete(ai_summaries::table.filter(ai_summaries::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(analytics_snapshots::table.filter(analytics_snapshots::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(announcement_acks::table.filter(announcement_acks::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(automation_rules::table.filter(automation_rules::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(task_aging_rules::table.filter(task_aging_rules::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(app_passwords::table.filter(app_passwords::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(api_keys::table.filter(api_keys::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(backup_configs::table.filter(backup_configs::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(recent_views::table.filter(recent_views::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(devices::table.filter(devices::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(feedback::table.filter(feedback::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(
+        experiment_assignments::table.filter(experiment_assignments::user_id.eq(user_uuid)),
+    )
+    .execute(conn)
+    .await?;
+    diesel::delete(experiment_events::table.filter(experiment_events::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(
+        feature_flag_overrides::table.filter(feature_flag_overrides::user_id.eq(user_uuid)),
+    )
+    .execute(conn)
+    .await?;
+    diesel::delete(client_preferences::table.filter(client_preferences::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(calendar_suggestions::table.filter(calendar_suggestions::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(
+        calendar_project_links::table.filter(calendar_project_links::user_id.eq(user_uuid)),
+    )
+    .execute(conn)
+    .await?;
+    diesel::delete(
+        calendar_oauth_states::table.filter(calendar_oauth_states::user_id.eq(user_uuid)),
+    )
+    .execute(conn)
+    .await?;
+    diesel::delete(
+        calendar_integrations::table.filter(calendar_integrations::user_id.eq(user_uuid)),
+    )
+    .execute(conn)
+    .await?;
+    diesel::delete(timesheets::table.filter(timesheets::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(
+        pomodoro_interruptions::table.filter(pomodoro_interruptions::user_id.eq(user_uuid)),
+    )
+    .execute(conn)
+    .await?;
+    diesel::delete(time_entries::table.filter(time_entries::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(focus_sessions::table.filter(focus_sessions::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    // Après time_entries, dont la suppression programme des recalculs
+    diesel::delete(daily_tracked_time::table.filter(daily_tracked_time::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(
+        daily_tracked_time_refresh::table.filter(daily_tracked_time_refresh::user_id.eq(user_uuid)),
+    )
+    .execute(conn)
+    .await?;
+    // Les liens ajoutés par l'utilisateur sur des tâches partagées ne partent pas avec ses tâches
+    diesel::delete(task_external_links::table.filter(task_external_links::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(tasks::table.filter(tasks::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(labels::table.filter(labels::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(projects::table.filter(projects::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(user_onboarding::table.filter(user_onboarding::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(notifications::table.filter(notifications::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(task_watchers::table.filter(task_watchers::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(user_settings::table.filter(user_settings::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(workspace_members::table.filter(workspace_members::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(workspaces::table.filter(workspaces::owner_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(
+        inbound_email_addresses::table.filter(inbound_email_addresses::user_id.eq(user_uuid)),
+    )
+    .execute(conn)
+    .await?;
+    diesel::delete(public_badge_tokens::table.filter(public_badge_tokens::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    diesel::delete(confirmation_tokens::table.filter(confirmation_tokens::user_id.eq(user_uuid)))
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+pub async fn find_deletion_request(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<AccountDeletionRequest>, ServiceError> {
+    account_deletion_requests::table
+        .find(user_uuid)
+        .select(AccountDeletionRequest::as_select())
+        .first::<AccountDeletionRequest>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// Programme la suppression ; renvoie la demande existante si elle est déjà programmée
+pub async fn schedule_deletion(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<(AccountDeletionRequest, bool), ServiceError> {
+    if let Some(existing) = find_deletion_request(conn, user_uuid).await? {
+        return Ok((existing, false));
+    }
+
+    let scheduled_for = Utc::now() + ChronoDuration::days(DELETION_GRACE_PERIOD_DAYS);
+    let request = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let request = diesel::insert_into(account_deletion_requests::table)
+                    .values((
+                        account_deletion_requests::user_id.eq(user_uuid),
+                        account_deletion_requests::scheduled_for.eq(scheduled_for),
+                    ))
+                    .returning(AccountDeletionRequest::as_returning())
+                    .get_result::<AccountDeletionRequest>(conn)
+                    .await?;
+
+                diesel::insert_into(notifications::table)
+                    .values(&NewNotification {
+                        user_id: user_uuid,
+                        task_id: None,
+                        actor_id: Some(user_uuid),
+                        kind: KIND_ACCOUNT_DELETION_SCHEDULED.to_string(),
+                        message: format!(
+                            "Your account will be deleted on {}. Cancel the request to keep it.",
+                            scheduled_for.format("%Y-%m-%d")
+                        ),
+                        payload: json!({ "scheduled_for": scheduled_for }),
+                    })
+                    .execute(conn)
+                    .await?;
+                Ok(request)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    log::info!(
+        "Account deletion scheduled for user {} on {}",
+        user_uuid,
+        request.scheduled_for
+    );
+    Ok((request, true))
+}
+
+pub async fn cancel_deletion(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<bool, ServiceError> {
+    let num_deleted = diesel::delete(account_deletion_requests::table.find(user_uuid))
+        .execute(conn)
+        .await?;
+    if num_deleted > 0 {
+        log::info!("Account deletion cancelled for user {}", user_uuid);
+    }
+    Ok(num_deleted > 0)
+}
+
+// Efface le compte puis laisse une notification de confirmation, seule trace restante
+async fn perform_deletion(pool: &DbPool, user_uuid: Uuid) -> Result<(), ServiceError> {
+    let mut conn = pool.get().await?;
+
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            wipe_user_data(conn, user_uuid).await?;
+            diesel::delete(account_deletion_requests::table.find(user_uuid))
+                .execute(conn)
+                .await?;
+            diesel::insert_into(notifications::table)
+                .values(&NewNotification {
+                    user_id: user_uuid,
+                    task_id: None,
+                    actor_id: None,
+                    kind: KIND_ACCOUNT_DELETED.to_string(),
+                    message: "Your account data has been permanently deleted.".to_string(),
+                    payload: json!({ "deleted_at": Utc::now() }),
+                })
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+pub async fn run_due_deletions(pool: &DbPool) -> Result<usize, ServiceError> {
+    let due_users = {
+        let mut conn = pool.get().await?;
+        account_deletion_requests::table
+            .filter(account_deletion_requests::scheduled_for.le(Utc::now()))
+            .select(account_deletion_requests::user_id)
+            .load::<Uuid>(&mut conn)
+            .await?
+    };
+
+    let mut deleted = 0;
+    for user_uuid in due_users {
+        match perform_deletion(pool, user_uuid).await {
+            Ok(()) => {
+                log::info!("Account data deleted for user {}", user_uuid);
+                deleted += 1;
+            }
+            Err(e) => log::error!("Account deletion failed for user {}: {}", user_uuid, e),
+        }
+    }
+    Ok(deleted)
+}
+
+// Lance la tâche de fond qui exécute les suppressions arrivées à échéance
+pub fn spawn_deletion_job(pool: DbPool, read_only: Arc<ReadOnlyMode>) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(
+            DELETION_JOB_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            if let Err(e) = run_due_deletions(&pool).await {
+                log::error!("Account deletion job failed: {}", e);
+            }
+        }
+    });
+}
+
+// Middleware : refuse les écritures d'un compte dont la suppression est programmée.
+// Les routes /account restent accessibles pour pouvoir annuler la demande.
+pub async fn read_only_guard(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let is_read = read_only_mode::is_read_method(req.method());
+    let is_exempt =
+        req.path().starts_with("/account") || req.headers().contains_key(DEMO_TOKEN_HEADER);
+    let user_uuid = req
+        .headers()
+        .get("X-User-Id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value).ok());
+
+    if let (false, false, Some(user_uuid), Some(pool)) = (
+        is_read,
+        is_exempt,
+        user_uuid,
+        req.app_data::<web::Data<DbPool>>().cloned(),
+    ) {
+        let pending = match pool.get().await {
+            Ok(mut conn) => find_deletion_request(&mut conn, user_uuid).await,
+            Err(e) => Err(ServiceError::from(e)),
+        };
+        match pending {
+            Ok(Some(request)) => {
+                let error = ServiceError::ConflictError(format!(
+                    "Account is scheduled for deletion on {} and is read-only. Cancel the deletion request to make changes.",
+                    request.scheduled_for.format("%Y-%m-%d")
+                ));
+                return Ok(req.into_response(error.error_response()));
+            }
+            Ok(None) => {}
+            Err(e) => return Ok(req.into_response(e.error_response())),
+        }
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_boxed_body)
+}
@@ -0,0 +1,281 @@
+// OptiTask/backend-api/src/activity_feed.rs
+// Fil "ce qui a changé" d'un utilisateur sur une période : tâches créées et terminées,
+// projets ajoutés, et journées de suivi notables par projet. Une seule construction sert
+// la revue hebdomadaire (GET /activity) et le futur digest e-mail. Les éléments sont
+// regroupés par jour UTC, comme daily_tracked_time, et portent une clé de regroupement
+// (projet ou Inbox) pour que les clients puissent replier les suites d'éléments voisins.
+use crate::error_handler::ServiceError;
+use crate::ids::{ProjectId, TaskId};
+use crate::models::DONE_TASK_STATUSES;
+use crate::schema::{daily_tracked_time, projects, task_status_history, tasks};
+use crate::tracked_time;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+pub const ACTIVITY_TASK_CREATED: &str = "task_created";
+pub const ACTIVITY_TASK_COMPLETED: &str = "task_completed";
+pub const ACTIVITY_PROJECT_CREATED: &str = "project_created";
+pub const ACTIVITY_TIME_TRACKED: &str = "time_tracked";
+
+pub const DEFAULT_FEED_DAYS: i64 = 7;
+pub const MAX_FEED_DAYS: i64 = 31;
+// Temps suivi par projet et par jour à partir duquel la journée apparaît dans le fil
+const NOTABLE_TRACKED_SECONDS: i64 = 3600;
+const INBOX_GROUP_KEY: &str = "inbox";
+
+#[derive(Serialize, Debug)]
+pub struct ActivityItem {
+    pub kind: &'static str,
+    pub occurred_at: DateTime<Utc>,
+    // Regroupement suggéré : "project:<id>" ou "inbox"
+    pub group_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<TaskId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<ProjectId>,
+    // Titre de la tâche ou nom du projet
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracked_seconds: Option<i64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ActivityDay {
+    pub day: NaiveDate,
+    // Temps total suivi ce jour-là, y compris sous le seuil notable
+    pub tracked_seconds: i64,
+    pub items: Vec<ActivityItem>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct ActivityTotals {
+    pub tasks_created: usize,
+    pub tasks_completed: usize,
+    pub projects_created: usize,
+    pub tracked_seconds: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ActivityFeed {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub totals: ActivityTotals,
+    // Jours avec au moins un élément ou du temps suivi, du plus ancien au plus récent
+    pub days: Vec<ActivityDay>,
+}
+
+// Début de la période : `since` fourni (au plus MAX_FEED_DAYS en arrière), sinon 7 jours
+pub fn resolve_since(
+    since: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, ServiceError> {
+    let Some(since) = since else {
+        return Ok(now - Duration::days(DEFAULT_FEED_DAYS));
+    };
+    if since > now {
+        return Err(ServiceError::ValidationError(
+            "since cannot be in the future".to_string(),
+        ));
+    }
+    if now - since > Duration::days(MAX_FEED_DAYS) {
+        return Err(ServiceError::ValidationError(format!(
+            "since cannot be more than {} days ago",
+            MAX_FEED_DAYS
+        )));
+    }
+    Ok(since)
+}
+
+fn group_key(project_id: Option<ProjectId>) -> String {
+    match project_id {
+        Some(project_uuid) => format!("project:{}", project_uuid),
+        None => INBOX_GROUP_KEY.to_string(),
+    }
+}
+
+pub async fn build_activity_feed(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<ActivityFeed, ServiceError> {
+    // Rattraper les jours de suivi en attente pour refléter les dernières saisies
+    tracked_time::refresh_pending(conn, Some(user_uuid)).await?;
+
+    let project_names: HashMap<ProjectId, String> = projects::table
+        .filter(projects::user_id.eq(user_uuid))
+        .select((projects::id, projects::name))
+        .load::<(ProjectId, String)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+    let project_name = |project_id: Option<ProjectId>| {
+        project_id.and_then(|project_uuid| project_names.get(&project_uuid).cloned())
+    };
+
+    let mut totals = ActivityTotals::default();
+    let mut items = Vec::new();
+
+    let created_tasks = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .filter(tasks::created_at.ge(since))
+        .filter(tasks::created_at.lt(until))
+        .select((
+            tasks::id,
+            tasks::project_id,
+            tasks::title,
+            tasks::created_at,
+        ))
+        .load::<(TaskId, Option<ProjectId>, String, DateTime<Utc>)>(conn)
+        .await?;
+    totals.tasks_created = created_tasks.len();
+    for (task_uuid, project_id, title, created_at) in created_tasks {
+        items.push(ActivityItem {
+            kind: ACTIVITY_TASK_CREATED,
+            occurred_at: created_at,
+            group_key: group_key(project_id),
+            task_id: Some(task_uuid),
+            project_id,
+            title,
+            project_name: project_name(project_id),
+            tracked_seconds: None,
+        });
+    }
+
+    // Passages à un statut terminé ; une tâche rouverte puis terminée compte deux fois
+    let completions = task_status_history::table
+        .inner_join(tasks::table)
+        .filter(task_status_history::user_id.eq(user_uuid))
+        .filter(task_status_history::new_status.eq_any(DONE_TASK_STATUSES))
+        .filter(task_status_history::changed_at.ge(since))
+        .filter(task_status_history::changed_at.lt(until))
+        .select((
+            task_status_history::task_id,
+            task_status_history::project_id,
+            task_status_history::old_status,
+            tasks::title,
+            task_status_history::changed_at,
+        ))
+        .load::<(
+            TaskId,
+            Option<ProjectId>,
+            Option<String>,
+            String,
+            DateTime<Utc>,
+        )>(conn)
+        .await?;
+    for (task_uuid, project_id, old_status, title, changed_at) in completions {
+        // Changement de projet d'une tâche déjà terminée : pas une complétion
+        if old_status.is_some_and(|old| DONE_TASK_STATUSES.contains(&old.as_str())) {
+            continue;
+        }
+        totals.tasks_completed += 1;
+        items.push(ActivityItem {
+            kind: ACTIVITY_TASK_COMPLETED,
+            occurred_at: changed_at,
+            group_key: group_key(project_id),
+            task_id: Some(task_uuid),
+            project_id,
+            title,
+            project_name: project_name(project_id),
+            tracked_seconds: None,
+        });
+    }
+
+    let created_projects = projects::table
+        .filter(projects::user_id.eq(user_uuid))
+        .filter(projects::created_at.ge(since))
+        .filter(projects::created_at.lt(until))
+        .select((projects::id, projects::name, projects::created_at))
+        .load::<(ProjectId, String, DateTime<Utc>)>(conn)
+        .await?;
+    totals.projects_created = created_projects.len();
+    for (project_uuid, name, created_at) in created_projects {
+        items.push(ActivityItem {
+            kind: ACTIVITY_PROJECT_CREATED,
+            occurred_at: created_at,
+            group_key: group_key(Some(project_uuid)),
+            task_id: None,
+            project_id: Some(project_uuid),
+            title: name,
+            project_name: None,
+            tracked_seconds: None,
+        });
+    }
+
+    // Les journées de suivi sont entières : celles qui chevauchent la période sont incluses
+    let tracked_days = daily_tracked_time::table
+        .filter(daily_tracked_time::user_id.eq(user_uuid))
+        .filter(daily_tracked_time::day.ge(since.date_naive()))
+        .filter(daily_tracked_time::day.le(until.date_naive()))
+        .select((
+            daily_tracked_time::day,
+            daily_tracked_time::project_id,
+            daily_tracked_time::tracked_seconds,
+        ))
+        .load::<(NaiveDate, Option<ProjectId>, i64)>(conn)
+        .await?;
+    let mut tracked_by_day: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    for (day, project_id, tracked_seconds) in tracked_days {
+        *tracked_by_day.entry(day).or_insert(0) += tracked_seconds;
+        totals.tracked_seconds += tracked_seconds;
+        if tracked_seconds < NOTABLE_TRACKED_SECONDS {
+            continue;
+        }
+        // Placée en fin de journée (ou à `until` pour la journée en cours)
+        let end_of_day = (day + Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            - Duration::seconds(1);
+        items.push(ActivityItem {
+            kind: ACTIVITY_TIME_TRACKED,
+            occurred_at: end_of_day.min(until),
+            group_key: group_key(project_id),
+            task_id: None,
+            project_id,
+            title: project_name(project_id).unwrap_or_else(|| "Inbox".to_string()),
+            project_name: project_name(project_id),
+            tracked_seconds: Some(tracked_seconds),
+        });
+    }
+
+    items.sort_by_key(|item| item.occurred_at);
+    let mut days: BTreeMap<NaiveDate, ActivityDay> = tracked_by_day
+        .into_iter()
+        .map(|(day, tracked_seconds)| {
+            (
+                day,
+                ActivityDay {
+                    day,
+                    tracked_seconds,
+                    items: Vec::new(),
+                },
+            )
+        })
+        .collect();
+    for item in items {
+        let day = item.occurred_at.date_naive();
+        days.entry(day)
+            .or_insert_with(|| ActivityDay {
+                day,
+                tracked_seconds: 0,
+                items: Vec::new(),
+            })
+            .items
+            .push(item);
+    }
+
+    Ok(ActivityFeed {
+        since,
+        until,
+        totals,
+        days: days.into_values().collect(),
+    })
+}
@@ -0,0 +1,22 @@
+// OptiTask/backend-api/src/admin.rs
+// Administrateurs de l'instance : identifiants listés dans ADMIN_USER_IDS (séparés par
+// des virgules). Sans la variable, les routes /admin sont refusées à tous.
+use crate::auth_utils::AuthenticatedUser;
+use crate::error_handler::ServiceError;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct AdminConfig {
+    pub user_ids: HashSet<Uuid>,
+}
+
+impl AdminConfig {
+    pub fn require_admin(&self, user: &AuthenticatedUser) -> Result<(), ServiceError> {
+        if self.user_ids.contains(&user.id) {
+            Ok(())
+        } else {
+            Err(ServiceError::Forbidden("admin".to_string()))
+        }
+    }
+}
@@ -0,0 +1,262 @@
+// OptiTask/backend-api/src/aging_rules.rs
+// Règles d'escalade : "si une tâche est in_progress depuis plus de 7 jours, ajouter le
+// label 'stalled' et notifier". L'entrée dans le statut est lue dans task_status_history ;
+// chaque escalade n'est appliquée qu'une fois par séjour dans le statut.
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::{LabelId, TaskId};
+use crate::models::{NewNotification, Task, TaskAgingRule};
+use crate::notifications::KIND_TASK_AGING_ESCALATION;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::{
+    labels, notifications, task_aging_rule_hits, task_aging_rules, task_labels,
+    task_status_history, tasks,
+};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Fréquence d'évaluation des règles actives
+const AGING_JOB_INTERVAL_SECS: u64 = 3600;
+
+// Tâche qui dépasse la durée autorisée dans le statut de la règle
+#[derive(Serialize, Debug)]
+pub struct AgedTask {
+    pub task_id: TaskId,
+    pub title: String,
+    pub status_entered_at: DateTime<Utc>,
+    pub days_in_status: i64,
+    // Escalade déjà appliquée pour ce séjour dans le statut
+    pub already_escalated: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RuleEvaluation {
+    pub rule_id: Uuid,
+    pub dry_run: bool,
+    pub matched: Vec<AgedTask>,
+    // Tâches escaladées par cette évaluation (celles qui le seraient en dry-run)
+    pub escalated: usize,
+}
+
+async fn find_aged_tasks(
+    conn: &mut AsyncPgConnection,
+    rule: &TaskAgingRule,
+    now: DateTime<Utc>,
+) -> Result<Vec<(Task, DateTime<Utc>)>, ServiceError> {
+    let mut task_query = tasks::table
+        .filter(tasks::user_id.eq(rule.user_id))
+        .filter(tasks::status.eq(&rule.status))
+        .into_boxed();
+    if let Some(project_uuid) = rule.project_id {
+        task_query = task_query.filter(tasks::project_id.eq(project_uuid));
+    }
+    let candidates = task_query
+        .select(Task::as_select())
+        .load::<Task>(conn)
+        .await?;
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let candidate_ids: Vec<TaskId> = candidates.iter().map(|task| task.id).collect();
+    let entered_at: HashMap<TaskId, DateTime<Utc>> = task_status_history::table
+        .filter(task_status_history::task_id.eq_any(&candidate_ids))
+        .filter(task_status_history::new_status.eq(&rule.status))
+        .group_by(task_status_history::task_id)
+        .select((
+            task_status_history::task_id,
+            diesel::dsl::max(task_status_history::changed_at),
+        ))
+        .load::<(TaskId, Option<DateTime<Utc>>)>(conn)
+        .await?
+        .into_iter()
+        .filter_map(|(task_uuid, changed_at)| changed_at.map(|at| (task_uuid, at)))
+        .collect();
+
+    let cutoff = now - ChronoDuration::days(rule.max_days as i64);
+    Ok(candidates
+        .into_iter()
+        .map(|task| {
+            // Sans historique, la dernière modification sert d'approximation
+            let since = entered_at
+                .get(&task.id)
+                .copied()
+                .unwrap_or_else(|| task.updated_at.and_utc());
+            (task, since)
+        })
+        .filter(|(_, since)| *since <= cutoff)
+        .collect())
+}
+
+// Label de la règle pour l'utilisateur, créé s'il n'existe pas encore
+async fn find_or_create_label(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    label_name: &str,
+) -> Result<LabelId, ServiceError> {
+    diesel::insert_into(labels::table)
+        .values((labels::user_id.eq(user_uuid), labels::name.eq(label_name)))
+        .on_conflict((labels::user_id, labels::name))
+        .do_nothing()
+        .execute(conn)
+        .await?;
+    labels::table
+        .filter(labels::user_id.eq(user_uuid))
+        .filter(labels::name.eq(label_name))
+        .select(labels::id)
+        .first::<LabelId>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Évalue une règle ; en dry-run, rien n'est modifié
+pub async fn evaluate_rule(
+    conn: &mut AsyncPgConnection,
+    rule: &TaskAgingRule,
+    dry_run: bool,
+) -> Result<RuleEvaluation, ServiceError> {
+    let now = Utc::now();
+    let aged = find_aged_tasks(conn, rule, now).await?;
+
+    let aged_ids: Vec<TaskId> = aged.iter().map(|(task, _)| task.id).collect();
+    let previous_hits: HashMap<TaskId, DateTime<Utc>> = task_aging_rule_hits::table
+        .filter(task_aging_rule_hits::rule_id.eq(rule.id))
+        .filter(task_aging_rule_hits::task_id.eq_any(&aged_ids))
+        .select((
+            task_aging_rule_hits::task_id,
+            task_aging_rule_hits::status_entered_at,
+        ))
+        .load::<(TaskId, DateTime<Utc>)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let matched: Vec<AgedTask> = aged
+        .iter()
+        .map(|(task, since)| AgedTask {
+            task_id: task.id,
+            title: task.title.clone(),
+            status_entered_at: *since,
+            days_in_status: (now - *since).num_days(),
+            already_escalated: previous_hits.get(&task.id) == Some(since),
+        })
+        .collect();
+    let to_escalate: Vec<&AgedTask> = matched
+        .iter()
+        .filter(|aged_task| !aged_task.already_escalated)
+        .collect();
+
+    if !dry_run && !to_escalate.is_empty() {
+        let label_uuid = match rule.add_label.as_deref() {
+            Some(label_name) => Some(find_or_create_label(conn, rule.user_id, label_name).await?),
+            None => None,
+        };
+
+        for aged_task in &to_escalate {
+            if let Some(label_uuid) = label_uuid {
+                diesel::insert_into(task_labels::table)
+                    .values((
+                        task_labels::task_id.eq(aged_task.task_id),
+                        task_labels::label_id.eq(label_uuid),
+                    ))
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .await?;
+            }
+            if rule.notify {
+                diesel::insert_into(notifications::table)
+                    .values(&NewNotification {
+                        user_id: rule.user_id,
+                        task_id: Some(aged_task.task_id),
+                        actor_id: None,
+                        kind: KIND_TASK_AGING_ESCALATION.to_string(),
+                        message: format!(
+                            "'{}' has been {} for {} days (rule '{}')",
+                            aged_task.title, rule.status, aged_task.days_in_status, rule.name
+                        ),
+                        payload: json!({
+                            "rule_id": rule.id,
+                            "status": rule.status,
+                            "days_in_status": aged_task.days_in_status,
+                            "added_label": rule.add_label,
+                        }),
+                    })
+                    .execute(conn)
+                    .await?;
+            }
+            diesel::insert_into(task_aging_rule_hits::table)
+                .values((
+                    task_aging_rule_hits::rule_id.eq(rule.id),
+                    task_aging_rule_hits::task_id.eq(aged_task.task_id),
+                    task_aging_rule_hits::status_entered_at.eq(aged_task.status_entered_at),
+                ))
+                .on_conflict((task_aging_rule_hits::rule_id, task_aging_rule_hits::task_id))
+                .do_update()
+                .set((
+                    task_aging_rule_hits::status_entered_at
+                        .eq(excluded(task_aging_rule_hits::status_entered_at)),
+                    task_aging_rule_hits::applied_at.eq(now),
+                ))
+                .execute(conn)
+                .await?;
+        }
+    }
+
+    if !dry_run {
+        diesel::update(task_aging_rules::table.find(rule.id))
+            .set(task_aging_rules::last_run_at.eq(now))
+            .execute(conn)
+            .await?;
+    }
+
+    Ok(RuleEvaluation {
+        rule_id: rule.id,
+        dry_run,
+        escalated: to_escalate.len(),
+        matched,
+    })
+}
+
+async fn run_active_rules(pool: &DbPool) -> Result<usize, ServiceError> {
+    let mut conn = pool.get().await?;
+    let active_rules = task_aging_rules::table
+        .filter(task_aging_rules::is_active.eq(true))
+        .select(TaskAgingRule::as_select())
+        .load::<TaskAgingRule>(&mut conn)
+        .await?;
+
+    let mut escalated = 0;
+    for rule in &active_rules {
+        match evaluate_rule(&mut conn, rule, false).await {
+            Ok(evaluation) => escalated += evaluation.escalated,
+            Err(e) => log::error!("Aging rule {} evaluation failed: {}", rule.id, e),
+        }
+    }
+    Ok(escalated)
+}
+
+// Lance la tâche de fond qui évalue périodiquement les règles actives
+pub fn spawn_aging_job(pool: DbPool, read_only: Arc<ReadOnlyMode>) {
+    actix_web::rt::spawn(async move {
+        let mut interval =
+            actix_web::rt::time::interval(std::time::Duration::from_secs(AGING_JOB_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            match run_active_rules(&pool).await {
+                Ok(0) => {}
+                Ok(escalated) => log::info!("Aging rules escalated {} task(s)", escalated),
+                Err(e) => log::error!("Aging rules job failed: {}", e),
+            }
+        }
+    });
+}
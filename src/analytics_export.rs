@@ -0,0 +1,410 @@
+// OptiTask/backend-api/src/analytics_export.rs
+// Export des analytics d'un utilisateur en classeur Excel (GET /analytics/export) : temps
+// par projet, tendance quotidienne (temps suivi et tâches terminées, un jour par ligne, y
+// compris les jours vides) et détail par tâche. Mêmes sources que les endpoints JSON : le
+// rollup daily_tracked_time pour les totaux, les entrées de temps pour le détail par tâche.
+// Intitulés dans la langue de la requête (clés "analytics_export.").
+// Le classeur est écrit par rust_xlsxwriter dans un thread bloquant (compression ZIP) et
+// envoyé au fil de l'écriture : la réponse n'attend pas le fichier complet.
+use crate::error_handler::ServiceError;
+use crate::handlers::analytics_handlers::{
+    load_time_by_project, load_tracked_seconds, period_bounds,
+};
+use crate::i18n;
+use crate::ids::{ProjectId, TaskId};
+use crate::models::{TimeByProjectStat, DONE_TASK_STATUSES};
+use crate::schema::{daily_tracked_time, projects, task_status_history, tasks, time_entries};
+use actix_web::web::Bytes;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use futures_util::Stream;
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, BufWriter, Write};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+pub const XLSX_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+// Au-delà, la tendance quotidienne n'est plus lisible dans un tableur
+pub const MAX_EXPORT_DAYS: i64 = 366;
+// Taille des morceaux envoyés au client, et morceaux en attente au plus
+const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+const STREAM_BUFFERED_CHUNKS: usize = 4;
+
+enum Cell {
+    Text(String),
+    // Intitulé (lignes de total)
+    Bold(String),
+    Integer(i64),
+    Number(f64),
+    // Durée en heures décimales, affichée avec deux décimales
+    Hours(f64),
+    Date(NaiveDate),
+    Empty,
+}
+
+impl Cell {
+    fn hours_from_seconds(seconds: i64) -> Cell {
+        Cell::Hours(seconds as f64 / 3600.0)
+    }
+}
+
+struct Formats {
+    bold: Format,
+    date: Format,
+    hours: Format,
+}
+
+impl Formats {
+    fn new() -> Formats {
+        Formats {
+            bold: Format::new().set_bold(),
+            date: Format::new().set_num_format("yyyy-mm-dd"),
+            hours: Format::new().set_num_format("0.00"),
+        }
+    }
+}
+
+fn workbook_error(e: XlsxError) -> ServiceError {
+    log::error!("Failed to write analytics workbook: {}", e);
+    ServiceError::InternalServerError("Failed to write workbook".to_string())
+}
+
+// Feuille avec une ligne d'en-tête en gras, figée ; (intitulé, largeur) par colonne
+fn add_sheet<'a>(
+    workbook: &'a mut Workbook,
+    formats: &Formats,
+    name: &str,
+    columns: &[(&str, f64)],
+) -> Result<&'a mut Worksheet, XlsxError> {
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(name)?;
+    sheet.set_freeze_panes(1, 0)?;
+    for (index, (title, width)) in columns.iter().enumerate() {
+        let column = index as u16;
+        sheet.set_column_width(column, *width)?;
+        sheet.write_string_with_format(0, column, *title, &formats.bold)?;
+    }
+    Ok(sheet)
+}
+
+fn write_row(
+    sheet: &mut Worksheet,
+    formats: &Formats,
+    row: u32,
+    cells: Vec<Cell>,
+) -> Result<(), XlsxError> {
+    for (index, cell) in cells.into_iter().enumerate() {
+        let column = index as u16;
+        match cell {
+            Cell::Text(text) => sheet.write_string(row, column, text)?,
+            Cell::Bold(text) => sheet.write_string_with_format(row, column, text, &formats.bold)?,
+            Cell::Integer(value) => sheet.write_number(row, column, value as f64)?,
+            Cell::Number(value) => sheet.write_number(row, column, value)?,
+            Cell::Hours(value) => {
+                sheet.write_number_with_format(row, column, value, &formats.hours)?
+            }
+            Cell::Date(date) => sheet.write_date_with_format(row, column, date, &formats.date)?,
+            Cell::Empty => sheet,
+        };
+    }
+    Ok(())
+}
+
+pub fn validate_export_range(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<(), ServiceError> {
+    if (end_date - start_date).num_days() >= MAX_EXPORT_DAYS {
+        return Err(ServiceError::ValidationError(format!(
+            "Export period cannot exceed {} days",
+            MAX_EXPORT_DAYS
+        )));
+    }
+    Ok(())
+}
+
+fn time_by_project_sheet(
+    workbook: &mut Workbook,
+    formats: &Formats,
+    stats: Vec<TimeByProjectStat>,
+    total_seconds: i64,
+) -> Result<(), XlsxError> {
+    let sheet = add_sheet(
+        workbook,
+        formats,
+        i18n::t("analytics_export.sheet_time_by_project"),
+        &[
+            (i18n::t("analytics_export.project"), 36.0),
+            (i18n::t("analytics_export.hours"), 12.0),
+            (i18n::t("analytics_export.share"), 12.0),
+        ],
+    )?;
+    let mut row = 1;
+    let mut project_seconds = 0;
+    for stat in stats {
+        project_seconds += stat.total_duration_seconds;
+        write_row(
+            sheet,
+            formats,
+            row,
+            vec![
+                Cell::Text(stat.project_name),
+                Cell::hours_from_seconds(stat.total_duration_seconds),
+                share_cell(stat.total_duration_seconds, total_seconds),
+            ],
+        )?;
+        row += 1;
+    }
+    // Temps suivi sur des tâches sans projet
+    let inbox_seconds = total_seconds - project_seconds;
+    if inbox_seconds > 0 {
+        write_row(
+            sheet,
+            formats,
+            row,
+            vec![
+                Cell::Text(i18n::t("analytics_export.inbox").to_string()),
+                Cell::hours_from_seconds(inbox_seconds),
+                share_cell(inbox_seconds, total_seconds),
+            ],
+        )?;
+        row += 1;
+    }
+    write_row(
+        sheet,
+        formats,
+        row,
+        vec![
+            Cell::Bold(i18n::t("analytics_export.total").to_string()),
+            Cell::hours_from_seconds(total_seconds),
+            Cell::Empty,
+        ],
+    )
+}
+
+// Part du total en pourcentage (au dixième), vide si rien n'a été suivi
+fn share_cell(seconds: i64, total_seconds: i64) -> Cell {
+    if total_seconds <= 0 {
+        return Cell::Empty;
+    }
+    Cell::Number((seconds as f64 * 1000.0 / total_seconds as f64).round() / 10.0)
+}
+
+async fn daily_trend_sheet(
+    conn: &mut AsyncPgConnection,
+    workbook: &mut Workbook,
+    formats: &Formats,
+    user_uuid: Uuid,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<(), ServiceError> {
+    // Une ligne par jour et par projet dans le rollup
+    let mut tracked_by_day: HashMap<NaiveDate, i64> = HashMap::new();
+    let tracked_days = daily_tracked_time::table
+        .filter(daily_tracked_time::user_id.eq(user_uuid))
+        .filter(daily_tracked_time::day.ge(start_date))
+        .filter(daily_tracked_time::day.le(end_date))
+        .select((daily_tracked_time::day, daily_tracked_time::tracked_seconds))
+        .load::<(NaiveDate, i64)>(conn)
+        .await?;
+    for (day, tracked_seconds) in tracked_days {
+        *tracked_by_day.entry(day).or_insert(0) += tracked_seconds;
+    }
+
+    // Passages à un statut terminé, comme le fil d'activité
+    let (start_datetime, end_datetime) = period_bounds(start_date, end_date);
+    let completions = task_status_history::table
+        .filter(task_status_history::user_id.eq(user_uuid))
+        .filter(task_status_history::new_status.eq_any(DONE_TASK_STATUSES))
+        .filter(task_status_history::changed_at.ge(start_datetime))
+        .filter(task_status_history::changed_at.le(end_datetime))
+        .select((
+            task_status_history::old_status,
+            task_status_history::changed_at,
+        ))
+        .load::<(Option<String>, DateTime<Utc>)>(conn)
+        .await?;
+    let mut completed_by_day: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    for (old_status, changed_at) in completions {
+        if old_status.is_some_and(|old| DONE_TASK_STATUSES.contains(&old.as_str())) {
+            continue;
+        }
+        *completed_by_day.entry(changed_at.date_naive()).or_insert(0) += 1;
+    }
+
+    let sheet = add_sheet(
+        workbook,
+        formats,
+        i18n::t("analytics_export.sheet_daily_trend"),
+        &[
+            (i18n::t("analytics_export.date"), 14.0),
+            (i18n::t("analytics_export.hours"), 12.0),
+            (i18n::t("analytics_export.tasks_completed"), 18.0),
+        ],
+    )
+    .map_err(workbook_error)?;
+    let mut row = 1;
+    let mut day = start_date;
+    while day <= end_date {
+        write_row(
+            sheet,
+            formats,
+            row,
+            vec![
+                Cell::Date(day),
+                Cell::hours_from_seconds(tracked_by_day.get(&day).copied().unwrap_or(0)),
+                Cell::Integer(completed_by_day.get(&day).copied().unwrap_or(0)),
+            ],
+        )
+        .map_err(workbook_error)?;
+        row += 1;
+        day += Duration::days(1);
+    }
+    Ok(())
+}
+
+async fn task_breakdown_sheet(
+    conn: &mut AsyncPgConnection,
+    workbook: &mut Workbook,
+    formats: &Formats,
+    user_uuid: Uuid,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<(), ServiceError> {
+    let (start_datetime, end_datetime) = period_bounds(start_date, end_date);
+    let mut task_rows = time_entries::table
+        .inner_join(tasks::table)
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::is_break.eq(false))
+        .filter(time_entries::start_time.ge(start_datetime))
+        .filter(time_entries::start_time.le(end_datetime))
+        .group_by((tasks::id, tasks::title, tasks::status, tasks::project_id))
+        .select((
+            tasks::id,
+            tasks::title,
+            tasks::status,
+            tasks::project_id,
+            diesel::dsl::sum(time_entries::duration_seconds),
+            diesel::dsl::count(time_entries::id),
+        ))
+        .load::<(TaskId, String, String, Option<ProjectId>, Option<i64>, i64)>(conn)
+        .await?;
+    task_rows.sort_by_key(|row| std::cmp::Reverse(row.4.unwrap_or(0)));
+
+    let project_ids: Vec<ProjectId> = task_rows.iter().filter_map(|row| row.3).collect();
+    let project_names: HashMap<ProjectId, String> = projects::table
+        .filter(projects::id.eq_any(&project_ids))
+        .select((projects::id, projects::name))
+        .load::<(ProjectId, String)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let sheet = add_sheet(
+        workbook,
+        formats,
+        i18n::t("analytics_export.sheet_tasks"),
+        &[
+            (i18n::t("analytics_export.task"), 40.0),
+            (i18n::t("analytics_export.project"), 28.0),
+            (i18n::t("analytics_export.status"), 14.0),
+            (i18n::t("analytics_export.entries"), 14.0),
+            (i18n::t("analytics_export.hours"), 12.0),
+        ],
+    )
+    .map_err(workbook_error)?;
+    for (index, (_, title, status, project_id, tracked_seconds, entry_count)) in
+        task_rows.into_iter().enumerate()
+    {
+        let project_name = project_id
+            .and_then(|project_uuid| project_names.get(&project_uuid).cloned())
+            .unwrap_or_else(|| i18n::t("analytics_export.inbox").to_string());
+        write_row(
+            sheet,
+            formats,
+            index as u32 + 1,
+            vec![
+                Cell::Text(title),
+                Cell::Text(project_name),
+                Cell::Text(status),
+                Cell::Integer(entry_count),
+                Cell::hours_from_seconds(tracked_seconds.unwrap_or(0)),
+            ],
+        )
+        .map_err(workbook_error)?;
+    }
+    Ok(())
+}
+
+pub async fn build_analytics_workbook(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Workbook, ServiceError> {
+    // Les deux chargements rattrapent les jours de suivi en attente
+    let stats = load_time_by_project(conn, user_uuid, start_date, end_date).await?;
+    let total_seconds = load_tracked_seconds(conn, user_uuid, start_date, end_date).await?;
+
+    let formats = Formats::new();
+    let mut workbook = Workbook::new();
+    time_by_project_sheet(&mut workbook, &formats, stats, total_seconds).map_err(workbook_error)?;
+    daily_trend_sheet(
+        conn,
+        &mut workbook,
+        &formats,
+        user_uuid,
+        start_date,
+        end_date,
+    )
+    .await?;
+    task_breakdown_sheet(
+        conn,
+        &mut workbook,
+        &formats,
+        user_uuid,
+        start_date,
+        end_date,
+    )
+    .await?;
+    Ok(workbook)
+}
+
+// Transmet au corps de la réponse ce que rust_xlsxwriter écrit
+struct ChunkSender(mpsc::Sender<io::Result<Bytes>>);
+
+impl Write for ChunkSender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "client disconnected"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Corps de réponse du classeur ; une erreur d'écriture interrompt la réponse
+pub fn stream_workbook(mut workbook: Workbook) -> impl Stream<Item = io::Result<Bytes>> {
+    let (sender, receiver) = mpsc::channel(STREAM_BUFFERED_CHUNKS);
+    actix_web::rt::task::spawn_blocking(move || {
+        let mut writer = BufWriter::with_capacity(STREAM_CHUNK_BYTES, ChunkSender(sender.clone()));
+        let result = workbook
+            .save_to_writer(&mut writer)
+            .map_err(io::Error::other)
+            .and_then(|()| writer.flush());
+        if let Err(e) = result {
+            log::error!("Failed to stream analytics workbook: {}", e);
+            let _ = sender.blocking_send(Err(e));
+        }
+    });
+    futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    })
+}
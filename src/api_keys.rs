@@ -0,0 +1,84 @@
+// OptiTask/backend-api/src/api_keys.rs
+// Clés d'API des intégrations sans code (Zapier...). Contrairement aux mots de passe
+// d'application, la clé identifie seule l'utilisateur : elle est cherchée par son empreinte.
+use crate::caldav;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::api_keys;
+use actix_web::http::header;
+use actix_web::{web, HttpRequest};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+pub const API_KEY_HEADER: &str = "X-API-Key";
+const API_KEY_MARKER: &str = "otk_";
+// Marqueur et premiers caractères, affichés dans la liste des clés
+const API_KEY_PREFIX_CHARS: usize = 10;
+
+// Même entropie que les mots de passe d'application, préfixée pour être reconnaissable
+pub fn generate_api_key() -> String {
+    format!("{}{}", API_KEY_MARKER, caldav::generate_app_password())
+}
+
+pub fn key_prefix(key: &str) -> String {
+    key.chars().take(API_KEY_PREFIX_CHARS).collect()
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    caldav::hash_app_password(key)
+}
+
+// Clé lue dans X-API-Key, ou en jeton Bearer pour les clients qui ne savent faire que ça
+fn extract_key(req: &HttpRequest) -> Option<String> {
+    let headers = req.headers();
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+        })
+        .map(|key| key.trim().to_string())
+        .filter(|key| key.starts_with(API_KEY_MARKER))
+}
+
+pub async fn authenticate(req: &HttpRequest, pool: &DbPool) -> Result<Uuid, ServiceError> {
+    let key = extract_key(req).ok_or_else(|| {
+        ServiceError::Unauthorized(format!("Missing or malformed {} header", API_KEY_HEADER))
+    })?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let key_hash = hash_api_key(&key);
+    let user_uuid = api_keys::table
+        .filter(api_keys::key_hash.eq(&key_hash))
+        .select(api_keys::user_id)
+        .first::<Uuid>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            log::warn!("Rejected API key {}", key_prefix(&key));
+            ServiceError::Unauthorized("Invalid API key".to_string())
+        })?;
+
+    // Information indicative : ni bloquante, ni écrite pendant le mode lecture seule
+    let read_only = req
+        .app_data::<web::Data<ReadOnlyMode>>()
+        .is_some_and(|mode| mode.is_enabled());
+    if !read_only {
+        if let Err(e) = diesel::update(api_keys::table.filter(api_keys::key_hash.eq(&key_hash)))
+            .set(api_keys::last_used_at.eq(Utc::now()))
+            .execute(&mut conn)
+            .await
+        {
+            log::warn!("Could not record API key usage: {}", e);
+        }
+    }
+    Ok(user_uuid)
+}
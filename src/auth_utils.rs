@@ -19,6 +19,20 @@ impl FromRequest for AuthenticatedUser {
             req.headers()
         ); // Gardez ce log pour le debug
 
+        // Mode démo : un token spécial donne accès au jeu de données isolé de démo
+        if let Some(demo_result) = crate::demo::authenticate(req) {
+            return match demo_result {
+                Ok(demo_user) => {
+                    log::debug!("Authenticated demo user: {}", demo_user.id);
+                    ok(demo_user)
+                }
+                Err(reason) => {
+                    log::warn!("Rejected demo token: {}", reason);
+                    err(actix_web::error::ErrorUnauthorized(reason))
+                }
+            };
+        }
+
         if let Some(user_id_header_value) = req.headers().get("X-User-Id") {
             if let Ok(user_id_str) = user_id_header_value.to_str() {
                 if user_id_str.is_empty() {
@@ -31,7 +45,7 @@ impl FromRequest for AuthenticatedUser {
                 match Uuid::parse_str(user_id_str) {
                     Ok(user_id_uuid) => {
                         log::debug!("Successfully parsed X-User-Id: {}", user_id_uuid);
-                        return ok(AuthenticatedUser { id: user_id_uuid });
+                        ok(AuthenticatedUser { id: user_id_uuid })
                     }
                     Err(parse_err) => {
                         log::warn!(
@@ -40,23 +54,35 @@ impl FromRequest for AuthenticatedUser {
                             parse_err
                         );
                         // Retourner un 400 Bad Request pour un format invalide
-                        return err(actix_web::error::ErrorBadRequest(
+                        err(actix_web::error::ErrorBadRequest(
                             "Invalid X-User-Id header format (not a valid UUID).",
-                        ));
+                        ))
                     }
                 }
             } else {
                 log::warn!("X-User-Id header is not valid UTF-8.");
-                return err(actix_web::error::ErrorBadRequest(
+                err(actix_web::error::ErrorBadRequest(
                     "X-User-Id header contains invalid characters.",
-                ));
+                ))
             }
         } else {
             log::warn!("X-User-Id header was NOT found in request headers.");
             // Retourner un 401 Unauthorized pour un header manquant
-            return err(actix_web::error::ErrorUnauthorized(
+            err(actix_web::error::ErrorUnauthorized(
                 "Missing X-User-Id header. Authentication required.",
-            ));
+            ))
         }
     }
 }
+
+// Comparaison de secrets en temps constant, pour ne pas en divulguer le contenu
+pub fn constant_time_eq(expected: &str, candidate: &str) -> bool {
+    let expected = expected.as_bytes();
+    let candidate = candidate.as_bytes();
+    expected.len() == candidate.len()
+        && expected
+            .iter()
+            .zip(candidate)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
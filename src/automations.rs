@@ -0,0 +1,468 @@
+// OptiTask/backend-api/src/automations.rs
+// Moteur d'automatisation : à chaque événement (tâche créée/terminée, label ajouté,
+// timer arrêté), les règles actives du propriétaire de la tâche dont les conditions
+// correspondent exécutent leurs actions. Exécution synchrone dans la requête ; les
+// actions ne redéclenchent pas d'autres règles. Les webhooks partent en arrière-plan,
+// vers une adresse publique épinglée (outbound::public_client), sans suivre de redirection.
+use crate::error_handler::ServiceError;
+use crate::handlers::task_handlers::ensure_pin_capacity;
+use crate::ids::{LabelId, TaskId};
+use crate::mentions;
+use crate::models::{
+    AutomationAction, AutomationConditions, AutomationField, AutomationRule, AutomationTrigger,
+    NewTask, NewTaskLabelAssociation, Task, TaskApiResponse,
+};
+use crate::outbound;
+use crate::permissions::{self, Permission};
+use crate::schema::{automation_rules, labels, task_labels, tasks};
+use crate::wip_limits;
+use chrono::{Duration as ChronoDuration, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_ACTIONS_PER_RULE: usize = 10;
+const MAX_FOLLOW_UP_TITLE_CHARS: usize = 255;
+const MAX_FOLLOW_UP_DAYS: i64 = 365;
+
+// Événement transmis par les handlers
+pub struct AutomationEvent {
+    pub trigger: AutomationTrigger,
+    pub task_id: TaskId,
+    // Label ajouté (déclencheur label_added)
+    pub label_id: Option<LabelId>,
+}
+
+impl AutomationEvent {
+    pub fn for_task(trigger: AutomationTrigger, task_id: TaskId) -> Self {
+        AutomationEvent {
+            trigger,
+            task_id,
+            label_id: None,
+        }
+    }
+}
+
+async fn ensure_label_owned(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    label_uuid: LabelId,
+) -> Result<(), ServiceError> {
+    let owned = labels::table
+        .filter(labels::id.eq(label_uuid))
+        .filter(labels::user_id.eq(user_uuid))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+    if owned == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "Label with id {} not found or not owned by user",
+            label_uuid
+        )));
+    }
+    Ok(())
+}
+
+// Titre (gabarit) et décalage d'échéance d'une tâche de suivi
+pub fn validate_follow_up(title: &str, due_in_days: Option<i64>) -> Result<(), ServiceError> {
+    let title_chars = title.trim().chars().count();
+    if title_chars == 0 || title_chars > MAX_FOLLOW_UP_TITLE_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "Follow-up title must contain between 1 and {} characters",
+            MAX_FOLLOW_UP_TITLE_CHARS
+        )));
+    }
+    if due_in_days.is_some_and(|days| !(0..=MAX_FOLLOW_UP_DAYS).contains(&days)) {
+        return Err(ServiceError::ValidationError(format!(
+            "due_in_days must be between 0 and {}",
+            MAX_FOLLOW_UP_DAYS
+        )));
+    }
+    Ok(())
+}
+
+// Valide conditions et actions d'une règle avant enregistrement
+pub async fn validate_rule(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    conditions: &AutomationConditions,
+    actions: &[AutomationAction],
+) -> Result<(), ServiceError> {
+    if actions.is_empty() || actions.len() > MAX_ACTIONS_PER_RULE {
+        return Err(ServiceError::ValidationError(format!(
+            "A rule must have between 1 and {} actions",
+            MAX_ACTIONS_PER_RULE
+        )));
+    }
+    if let Some(project_uuid) = conditions.project_id {
+        permissions::require_project(conn, user_uuid, project_uuid, Permission::ProjectRead)
+            .await?;
+    }
+    if let Some(label_uuid) = conditions.label_id {
+        ensure_label_owned(conn, user_uuid, label_uuid).await?;
+    }
+
+    for action in actions {
+        match action {
+            AutomationAction::SetField { field, value } => {
+                let valid = match field {
+                    AutomationField::Status => value.as_str().is_some_and(|s| !s.trim().is_empty()),
+                    AutomationField::DueDate => {
+                        value.is_null()
+                            || value
+                                .as_i64()
+                                .is_some_and(|days| (0..=MAX_FOLLOW_UP_DAYS).contains(&days))
+                    }
+                    AutomationField::IsPinned => value.is_boolean(),
+                };
+                if !valid {
+                    return Err(ServiceError::ValidationError(format!(
+                        "Invalid value for set_field {:?}: status expects a non-empty string, due_date a number of days (0-{}) or null, is_pinned a boolean",
+                        field, MAX_FOLLOW_UP_DAYS
+                    )));
+                }
+            }
+            AutomationAction::AddLabel { label_id } => {
+                ensure_label_owned(conn, user_uuid, *label_id).await?;
+            }
+            AutomationAction::CreateFollowUpTask { title, due_in_days } => {
+                validate_follow_up(title, *due_in_days)?;
+            }
+            AutomationAction::CallWebhook { url } => {
+                let parsed = reqwest::Url::parse(url).map_err(|_| {
+                    ServiceError::ValidationError(format!("Invalid webhook URL: {}", url))
+                })?;
+                if parsed.scheme() != "https" {
+                    return Err(ServiceError::ValidationError(
+                        "Webhook URLs must use https".to_string(),
+                    ));
+                }
+                // Vérifié de nouveau à chaque appel : la résolution DNS peut changer
+                if outbound::public_client(&parsed).await.is_none() {
+                    return Err(ServiceError::ValidationError(
+                        "Webhook URLs must resolve to a public address".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn conditions_match(
+    conn: &mut AsyncPgConnection,
+    conditions: &AutomationConditions,
+    task: &Task,
+    event: &AutomationEvent,
+) -> Result<bool, ServiceError> {
+    if conditions
+        .project_id
+        .is_some_and(|project_uuid| task.project_id != Some(project_uuid))
+    {
+        return Ok(false);
+    }
+    if let Some(priority) = &conditions.priority {
+        let task_priority = task.metadata.get("priority").and_then(|p| p.as_str());
+        if !task_priority.is_some_and(|p| p.eq_ignore_ascii_case(priority)) {
+            return Ok(false);
+        }
+    }
+    if let Some(label_uuid) = conditions.label_id {
+        // Pour label_added, la condition porte sur le label qui vient d'être ajouté
+        if event.trigger == AutomationTrigger::LabelAdded {
+            return Ok(event.label_id == Some(label_uuid));
+        }
+        let has_label = task_labels::table
+            .filter(task_labels::task_id.eq(task.id))
+            .filter(task_labels::label_id.eq(label_uuid))
+            .count()
+            .get_result::<i64>(conn)
+            .await?;
+        return Ok(has_label > 0);
+    }
+    Ok(true)
+}
+
+async fn apply_action(
+    conn: &mut AsyncPgConnection,
+    rule: &AutomationRule,
+    task: &Task,
+    action: &AutomationAction,
+    result: &mut AutomationOutcome,
+) -> Result<(), ServiceError> {
+    let now = Utc::now();
+    match action {
+        AutomationAction::SetField { field, value } => {
+            let target = tasks::table.find(task.id);
+            match field {
+                AutomationField::Status => {
+                    let new_status = value.as_str().unwrap_or_default().trim();
+                    // Même limite WIP que les changements de statut manuels ; un refus fait
+                    // échouer la règle (last_error) au lieu de dépasser la limite
+                    if let Some(project_uuid) = task.project_id {
+                        wip_limits::ensure_capacity(conn, project_uuid, new_status, Some(task.id))
+                            .await?;
+                    }
+                    diesel::update(target)
+                        .set((
+                            tasks::status.eq(new_status),
+                            tasks::updated_at.eq(now.naive_utc()),
+                        ))
+                        .execute(conn)
+                        .await?;
+                }
+                AutomationField::DueDate => {
+                    let due = value
+                        .as_i64()
+                        .map(|days| now.date_naive() + ChronoDuration::days(days));
+                    diesel::update(target)
+                        .set((
+                            tasks::due_date.eq(due),
+                            tasks::updated_at.eq(now.naive_utc()),
+                        ))
+                        .execute(conn)
+                        .await?;
+                }
+                AutomationField::IsPinned => {
+                    let pinned = value.as_bool().unwrap_or_default();
+                    if pinned {
+                        ensure_pin_capacity(conn, task.user_id, task.id).await?;
+                    }
+                    diesel::update(target)
+                        .set((
+                            tasks::is_pinned.eq(pinned),
+                            tasks::updated_at.eq(now.naive_utc()),
+                        ))
+                        .execute(conn)
+                        .await?;
+                }
+            }
+        }
+        AutomationAction::AddLabel { label_id } => {
+            diesel::insert_into(task_labels::table)
+                .values(&NewTaskLabelAssociation {
+                    task_id: task.id,
+                    label_id: *label_id,
+                })
+                .on_conflict_do_nothing()
+                .execute(conn)
+                .await?;
+        }
+        AutomationAction::CreateFollowUpTask { title, due_in_days } => {
+            let follow_up = create_follow_up_task(
+                conn,
+                task,
+                title,
+                *due_in_days,
+                json!({ "type": "automation", "rule_id": rule.id, "task_id": task.id }),
+            )
+            .await?;
+            result.follow_up_tasks.push(follow_up);
+        }
+        AutomationAction::CallWebhook { url } => {
+            let url = reqwest::Url::parse(url).map_err(|_| {
+                ServiceError::ValidationError(format!("Invalid webhook URL: {}", url))
+            })?;
+            result.webhooks.push(PendingWebhook {
+                rule_id: rule.id,
+                url,
+                body: json!({
+                    "event": rule.trigger,
+                    "rule_id": rule.id,
+                    "triggered_at": now,
+                    "task": TaskApiResponse::from(task.clone()),
+                }),
+            });
+        }
+    }
+    Ok(())
+}
+
+// Appel de webhook décidé par une règle, envoyé une fois les écritures validées
+pub struct PendingWebhook {
+    rule_id: Uuid,
+    url: reqwest::Url,
+    body: serde_json::Value,
+}
+
+impl PendingWebhook {
+    // L'appel sortant ne bloque pas la requête de l'utilisateur
+    pub fn spawn(self) {
+        actix_web::rt::spawn(async move {
+            let PendingWebhook { rule_id, url, body } = self;
+            let result = match outbound::public_client(&url).await {
+                Some(client) => {
+                    let request = client.post(url.clone()).json(&body);
+                    outbound::send_via(&client, &outbound::WEBHOOK_POLICY, request)
+                        .await
+                        .map_err(|e| e.to_string())
+                        .and_then(|response| response.error_for_status().map_err(|e| e.to_string()))
+                        .map(|_| ())
+                }
+                None => Err("URL does not resolve to a public address".to_string()),
+            };
+            if let Err(e) = result {
+                log::warn!("Automation rule {} webhook {} failed: {}", rule_id, url, e);
+            }
+        });
+    }
+}
+
+// Tâche de suivi liée à la tâche d'origine par une référence [[task:uuid]]
+pub async fn create_follow_up_task(
+    conn: &mut AsyncPgConnection,
+    origin: &Task,
+    title_template: &str,
+    due_in_days: Option<i64>,
+    source: serde_json::Value,
+) -> Result<Task, ServiceError> {
+    let description = format!("Follow-up of [[task:{}]]", origin.id);
+    // Le titre d'origine substitué peut dépasser la longueur maximale
+    let follow_up_title: String = title_template
+        .trim()
+        .replace("{title}", &origin.title)
+        .chars()
+        .take(MAX_FOLLOW_UP_TITLE_CHARS)
+        .collect();
+    let follow_up = diesel::insert_into(tasks::table)
+        .values(&NewTask {
+            user_id: origin.user_id,
+            project_id: origin.project_id,
+            title: follow_up_title,
+            description: Some(description),
+            status: None,
+            due_date: due_in_days.map(|days| Utc::now().date_naive() + ChronoDuration::days(days)),
+            order: None,
+            source: Some(source),
+            context: None,
+            scheduled_start: None,
+            scheduled_end: None,
+            follow_up: None,
+            stage: None,
+            time_budget_seconds: None,
+        })
+        .get_result::<Task>(conn)
+        .await?;
+
+    mentions::sync_task_links(
+        conn,
+        origin.user_id,
+        follow_up.id,
+        follow_up.description.as_deref(),
+    )
+    .await?;
+    Ok(follow_up)
+}
+
+// Résultat de l'exécution des règles pour un événement
+#[derive(Default)]
+pub struct AutomationOutcome {
+    // Règles appliquées sans erreur (la tâche a pu changer)
+    pub applied: usize,
+    pub follow_up_tasks: Vec<Task>,
+    // Webhooks à envoyer (voir run_automations_deferred)
+    pub webhooks: Vec<PendingWebhook>,
+}
+
+async fn run_matching_rules(
+    conn: &mut AsyncPgConnection,
+    event: &AutomationEvent,
+) -> Result<AutomationOutcome, ServiceError> {
+    let mut result = AutomationOutcome::default();
+    let Some(task) = tasks::table
+        .find(event.task_id)
+        .select(Task::as_select())
+        .first::<Task>(conn)
+        .await
+        .optional()?
+    else {
+        return Ok(result);
+    };
+
+    let rules = automation_rules::table
+        .filter(automation_rules::user_id.eq(task.user_id))
+        .filter(automation_rules::trigger.eq(event.trigger.as_str()))
+        .filter(automation_rules::is_active.eq(true))
+        .order(automation_rules::created_at.asc())
+        .select(AutomationRule::as_select())
+        .load::<AutomationRule>(conn)
+        .await?;
+
+    for rule in &rules {
+        let parsed = serde_json::from_value::<AutomationConditions>(rule.conditions.clone())
+            .and_then(|conditions| {
+                serde_json::from_value::<Vec<AutomationAction>>(rule.actions.clone())
+                    .map(|actions| (conditions, actions))
+            });
+        let outcome = match parsed {
+            Ok((conditions, actions)) => {
+                if !conditions_match(conn, &conditions, &task, event).await? {
+                    continue;
+                }
+                let mut outcome = Ok(());
+                for action in &actions {
+                    if let Err(e) = apply_action(conn, rule, &task, action, &mut result).await {
+                        outcome = Err(e);
+                        break;
+                    }
+                }
+                outcome
+            }
+            Err(e) => Err(ServiceError::from(e)),
+        };
+
+        let last_error = outcome.as_ref().err().map(|e| e.to_string());
+        if let Some(message) = &last_error {
+            log::warn!(
+                "Automation rule {} failed on task {}: {}",
+                rule.id,
+                task.id,
+                message
+            );
+        } else {
+            result.applied += 1;
+        }
+        diesel::update(automation_rules::table.find(rule.id))
+            .set((
+                automation_rules::last_triggered_at.eq(Utc::now()),
+                automation_rules::last_error.eq(last_error),
+            ))
+            .execute(conn)
+            .await?;
+    }
+    Ok(result)
+}
+
+// Point d'entrée des handlers : les échecs sont journalisés sans faire échouer la requête.
+// Les webhooks partent aussitôt ; dans une transaction, utiliser run_automations_deferred.
+pub async fn run_automations(
+    conn: &mut AsyncPgConnection,
+    event: AutomationEvent,
+) -> AutomationOutcome {
+    let mut outcome = run_automations_deferred(conn, event).await;
+    for webhook in outcome.webhooks.drain(..) {
+        webhook.spawn();
+    }
+    outcome
+}
+
+// Comme run_automations, mais les webhooks restent dans le résultat : l'appelant les envoie
+// après le commit, pour ne pas signaler un changement ensuite annulé
+pub async fn run_automations_deferred(
+    conn: &mut AsyncPgConnection,
+    event: AutomationEvent,
+) -> AutomationOutcome {
+    match run_matching_rules(conn, &event).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            log::error!(
+                "Automations for {} on task {} failed: {}",
+                event.trigger.as_str(),
+                event.task_id,
+                e
+            );
+            AutomationOutcome::default()
+        }
+    }
+}
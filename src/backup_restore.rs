@@ -0,0 +1,734 @@
+// OptiTask/backend-api/src/backup_restore.rs
+// Restauration d'une sauvegarde (voir backups.rs). L'archive est validée à la réception puis
+// appliquée par la file des opérations de fond (maintenance_jobs), dont la colonne progress
+// permet de suivre l'avancement. Deux modes :
+// - replace : les projets personnels, labels, tâches et entrées de temps absents de l'archive
+//   sont supprimés, ceux qu'elle contient sont remis dans leur état sauvegardé ;
+// - merge : seuls les éléments absents sont ajoutés, ceux déjà présents sont conservés et
+//   signalés comme conflits.
+// Dans les deux cas, un élément appartenant à un autre utilisateur n'est jamais modifié, et
+// les entrées de temps verrouillées (feuille soumise ou validée, période close, voir
+// timesheets.rs) ne sont ni supprimées ni réécrites : elles sont signalées comme conflits.
+use crate::backups::{self, BACKUP_FORMAT_VERSION};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::{LabelId, ProjectId, TaskId, TimeEntryId};
+use crate::maintenance;
+use crate::models::{Label, MaintenanceJob, Project, TaskApiResponse, TimeEntry};
+use crate::permissions;
+use crate::schema::{labels, projects, task_labels, tasks, time_entries, workspace_members};
+use crate::time_budgets;
+use crate::timesheets::EntryLocks;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub const RESTORE_MODE_REPLACE: &str = "replace";
+pub const RESTORE_MODE_MERGE: &str = "merge";
+pub const RESTORE_MODES: [&str; 2] = [RESTORE_MODE_REPLACE, RESTORE_MODE_MERGE];
+
+// Motifs de conflit
+const CONFLICT_ALREADY_EXISTS: &str = "already_exists";
+const CONFLICT_OWNED_BY_ANOTHER_USER: &str = "owned_by_another_user";
+const CONFLICT_NAME_TAKEN: &str = "name_taken";
+const CONFLICT_PROJECT_MISSING: &str = "project_missing";
+const CONFLICT_TASK_MISSING: &str = "task_missing";
+const CONFLICT_LOCKED: &str = "locked";
+// Au-delà, les conflits sont seulement comptés
+const MAX_REPORTED_CONFLICTS: usize = 500;
+// L'avancement est enregistré tous les N éléments
+const PROGRESS_STEP: usize = 200;
+
+// Contenu d'une sauvegarde ; les réglages exportés ne sont pas restaurés
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupArchive {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub user_id: Uuid,
+    #[serde(default)]
+    pub projects: Vec<Project>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub tasks: Vec<TaskApiResponse>,
+    #[serde(default)]
+    pub time_entries: Vec<TimeEntry>,
+}
+
+// Données d'entrée de l'opération (colonne payload)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RestoreRequest {
+    pub mode: String,
+    pub archive: BackupArchive,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RestoreConflict {
+    pub entity: &'static str,
+    pub id: Uuid,
+    pub reason: &'static str,
+}
+
+// Compte rendu de l'opération (colonne result)
+#[derive(Serialize, Debug, Default)]
+pub struct RestoreSummary {
+    pub mode: String,
+    pub projects_restored: usize,
+    pub labels_restored: usize,
+    pub tasks_restored: usize,
+    pub time_entries_restored: usize,
+    // Éléments de l'utilisateur absents de l'archive, supprimés (replace)
+    pub projects_deleted: usize,
+    pub labels_deleted: usize,
+    pub tasks_deleted: usize,
+    pub time_entries_deleted: usize,
+    pub conflict_count: usize,
+    pub conflicts: Vec<RestoreConflict>,
+}
+
+impl RestoreSummary {
+    fn conflict(&mut self, entity: &'static str, id: impl Into<Uuid>, reason: &'static str) {
+        self.conflict_count += 1;
+        if self.conflicts.len() < MAX_REPORTED_CONFLICTS {
+            self.conflicts.push(RestoreConflict {
+                entity,
+                id: id.into(),
+                reason,
+            });
+        }
+    }
+}
+
+pub fn validate_mode(mode: &str) -> Result<&'static str, ServiceError> {
+    RESTORE_MODES
+        .iter()
+        .find(|known| **known == mode)
+        .copied()
+        .ok_or_else(|| {
+            ServiceError::ValidationError(format!(
+                "mode must be one of: {}",
+                RESTORE_MODES.join(", ")
+            ))
+        })
+}
+
+// Accepte l'archive chiffrée produite par les sauvegardes, ou l'export JSON déjà déchiffré
+pub fn parse_archive(
+    bytes: &[u8],
+    encryption_key: Option<&str>,
+    user_uuid: Uuid,
+) -> Result<BackupArchive, ServiceError> {
+    let is_plain_json = bytes
+        .iter()
+        .find(|byte| !byte.is_ascii_whitespace())
+        .is_some_and(|byte| *byte == b'{');
+    let json_bytes = if is_plain_json {
+        bytes.to_vec()
+    } else {
+        let key = encryption_key.ok_or_else(|| {
+            ServiceError::ValidationError(
+                "Encrypted archives can only be restored once a backup target is configured"
+                    .to_string(),
+            )
+        })?;
+        backups::decrypt(key, bytes)?
+    };
+
+    let format_version = serde_json::from_slice::<serde_json::Value>(&json_bytes)
+        .ok()
+        .and_then(|value| value.get("format_version").and_then(|v| v.as_u64()))
+        .ok_or_else(|| {
+            ServiceError::ValidationError("The archive is not an OptiTask backup".to_string())
+        })?;
+    if format_version == 0 || format_version > u64::from(BACKUP_FORMAT_VERSION) {
+        return Err(ServiceError::ValidationError(format!(
+            "Unsupported backup format_version {} (supported: 1 to {})",
+            format_version, BACKUP_FORMAT_VERSION
+        )));
+    }
+
+    let archive = serde_json::from_slice::<BackupArchive>(&json_bytes)
+        .map_err(|e| ServiceError::ValidationError(format!("Invalid backup archive: {}", e)))?;
+    if archive.user_id != user_uuid {
+        return Err(ServiceError::ValidationError(
+            "The archive belongs to another account".to_string(),
+        ));
+    }
+    Ok(archive)
+}
+
+struct Progress<'a> {
+    pool: &'a DbPool,
+    job_uuid: Uuid,
+    total: usize,
+    processed: usize,
+}
+
+impl Progress<'_> {
+    async fn record(&self, phase: &str) {
+        maintenance::record_progress(
+            self.pool,
+            self.job_uuid,
+            json!({ "phase": phase, "processed": self.processed, "total": self.total }),
+        )
+        .await;
+    }
+
+    async fn advance(&mut self, phase: &str) {
+        self.processed += 1;
+        if self.processed.is_multiple_of(PROGRESS_STEP) {
+            self.record(phase).await;
+        }
+    }
+}
+
+async fn restore_projects(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    replace: bool,
+    archive_projects: &[Project],
+    summary: &mut RestoreSummary,
+    progress: &mut Progress<'_>,
+) -> Result<(), ServiceError> {
+    let archive_ids: Vec<ProjectId> = archive_projects.iter().map(|p| p.id).collect();
+    if replace {
+        summary.projects_deleted = diesel::delete(
+            projects::table
+                .filter(projects::user_id.eq(user_uuid))
+                .filter(projects::workspace_id.is_null())
+                .filter(projects::id.ne_all(&archive_ids)),
+        )
+        .execute(conn)
+        .await?;
+    }
+
+    let owners: HashMap<ProjectId, Uuid> = projects::table
+        .filter(projects::id.eq_any(&archive_ids))
+        .select((projects::id, projects::user_id))
+        .load::<(ProjectId, Uuid)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+    // Un projet d'un espace dont l'utilisateur n'est plus membre revient en projet personnel
+    let member_workspaces: HashSet<Uuid> = workspace_members::table
+        .filter(workspace_members::user_id.eq(user_uuid))
+        .select(workspace_members::workspace_id)
+        .load::<Uuid>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    for project in archive_projects {
+        progress.advance("projects").await;
+        match owners.get(&project.id) {
+            Some(owner) if *owner != user_uuid => {
+                summary.conflict("project", project.id, CONFLICT_OWNED_BY_ANOTHER_USER);
+            }
+            Some(_) if !replace => {
+                summary.conflict("project", project.id, CONFLICT_ALREADY_EXISTS);
+            }
+            Some(_) => {
+                // Le rattachement à un espace n'est pas modifié : il concerne aussi ses membres
+                diesel::update(projects::table.find(project.id))
+                    .set((
+                        projects::name.eq(&project.name),
+                        projects::color.eq(&project.color),
+                        projects::metadata.eq(&project.metadata),
+                        projects::icon.eq(&project.icon),
+                        projects::wip_limits.eq(&project.wip_limits),
+                        projects::sla_hours.eq(project.sla_hours),
+                        projects::currency.eq(&project.currency),
+                        projects::theme.eq(&project.theme),
+                        projects::archived_at.eq(project.archived_at),
+                        projects::updated_at.eq(project.updated_at),
+                    ))
+                    .execute(conn)
+                    .await?;
+                summary.projects_restored += 1;
+            }
+            None => {
+                let workspace_id = project
+                    .workspace_id
+                    .filter(|workspace| member_workspaces.contains(workspace));
+                diesel::insert_into(projects::table)
+                    .values((
+                        projects::id.eq(project.id),
+                        projects::user_id.eq(user_uuid),
+                        projects::name.eq(&project.name),
+                        projects::color.eq(&project.color),
+                        projects::created_at.eq(project.created_at),
+                        projects::updated_at.eq(project.updated_at),
+                        projects::workspace_id.eq(workspace_id),
+                        projects::metadata.eq(&project.metadata),
+                        projects::icon.eq(&project.icon),
+                        projects::wip_limits.eq(&project.wip_limits),
+                        projects::sla_hours.eq(project.sla_hours),
+                        projects::currency.eq(&project.currency),
+                        projects::theme.eq(&project.theme),
+                        projects::archived_at.eq(project.archived_at),
+                    ))
+                    .execute(conn)
+                    .await?;
+                summary.projects_restored += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Renvoie la correspondance identifiant archivé -> label effectif de l'utilisateur
+async fn restore_labels(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    replace: bool,
+    archive_labels: &[Label],
+    summary: &mut RestoreSummary,
+    progress: &mut Progress<'_>,
+) -> Result<HashMap<LabelId, LabelId>, ServiceError> {
+    let archive_ids: Vec<LabelId> = archive_labels.iter().map(|l| l.id).collect();
+    if replace {
+        summary.labels_deleted = diesel::delete(
+            labels::table
+                .filter(labels::user_id.eq(user_uuid))
+                .filter(labels::id.ne_all(&archive_ids)),
+        )
+        .execute(conn)
+        .await?;
+    }
+
+    let owners: HashMap<LabelId, Uuid> = labels::table
+        .filter(labels::id.eq_any(&archive_ids))
+        .select((labels::id, labels::user_id))
+        .load::<(LabelId, Uuid)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+    // Les noms de labels sont uniques par utilisateur
+    let ids_by_name: HashMap<String, LabelId> = labels::table
+        .filter(labels::user_id.eq(user_uuid))
+        .select((labels::name, labels::id))
+        .load::<(String, LabelId)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut label_map = HashMap::new();
+    for label in archive_labels {
+        progress.advance("labels").await;
+        match owners.get(&label.id) {
+            Some(owner) if *owner != user_uuid => {
+                summary.conflict("label", label.id, CONFLICT_OWNED_BY_ANOTHER_USER);
+            }
+            Some(_) if !replace => {
+                summary.conflict("label", label.id, CONFLICT_ALREADY_EXISTS);
+                label_map.insert(label.id, label.id);
+            }
+            Some(_) => {
+                diesel::update(labels::table.find(label.id))
+                    .set((
+                        labels::name.eq(&label.name),
+                        labels::color.eq(&label.color),
+                        labels::icon.eq(&label.icon),
+                        labels::sla_hours.eq(label.sla_hours),
+                        labels::updated_at.eq(label.updated_at),
+                    ))
+                    .execute(conn)
+                    .await?;
+                summary.labels_restored += 1;
+                label_map.insert(label.id, label.id);
+            }
+            None => match ids_by_name.get(&label.name) {
+                // Un label homonyme existe déjà : les tâches restaurées l'utilisent
+                Some(existing) => {
+                    summary.conflict("label", label.id, CONFLICT_NAME_TAKEN);
+                    label_map.insert(label.id, *existing);
+                }
+                None => {
+                    diesel::insert_into(labels::table)
+                        .values((
+                            labels::id.eq(label.id),
+                            labels::user_id.eq(user_uuid),
+                            labels::name.eq(&label.name),
+                            labels::color.eq(&label.color),
+                            labels::created_at.eq(label.created_at),
+                            labels::updated_at.eq(label.updated_at),
+                            labels::icon.eq(&label.icon),
+                            labels::sla_hours.eq(label.sla_hours),
+                        ))
+                        .execute(conn)
+                        .await?;
+                    summary.labels_restored += 1;
+                    label_map.insert(label.id, label.id);
+                }
+            },
+        }
+    }
+    Ok(label_map)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn restore_tasks(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    replace: bool,
+    archive_tasks: &[TaskApiResponse],
+    label_map: &HashMap<LabelId, LabelId>,
+    locks: &EntryLocks,
+    summary: &mut RestoreSummary,
+    progress: &mut Progress<'_>,
+) -> Result<(), ServiceError> {
+    let archive_ids: Vec<TaskId> = archive_tasks.iter().map(|t| t.id).collect();
+    if replace {
+        let absent_ids: Vec<TaskId> = tasks::table
+            .filter(tasks::user_id.eq(user_uuid))
+            .filter(tasks::id.ne_all(&archive_ids))
+            .select(tasks::id)
+            .load::<TaskId>(conn)
+            .await?;
+        // La suppression d'une tâche emporte ses entrées de temps : une tâche qui porte une
+        // entrée verrouillée est conservée
+        let locked_tasks: HashSet<TaskId> = time_entries::table
+            .filter(time_entries::user_id.eq(user_uuid))
+            .filter(time_entries::task_id.eq_any(&absent_ids))
+            .select((time_entries::task_id, time_entries::start_time))
+            .load::<(TaskId, DateTime<Utc>)>(conn)
+            .await?
+            .into_iter()
+            .filter(|(_, start_time)| locks.is_locked(*start_time))
+            .map(|(task_uuid, _)| task_uuid)
+            .collect();
+        let mut deletable_ids = Vec::with_capacity(absent_ids.len());
+        for task_uuid in absent_ids {
+            if locked_tasks.contains(&task_uuid) {
+                summary.conflict("task", task_uuid, CONFLICT_LOCKED);
+            } else {
+                deletable_ids.push(task_uuid);
+            }
+        }
+        summary.tasks_deleted =
+            diesel::delete(tasks::table.filter(tasks::id.eq_any(&deletable_ids)))
+                .execute(conn)
+                .await?;
+    }
+
+    let owners: HashMap<TaskId, Uuid> = tasks::table
+        .filter(tasks::id.eq_any(&archive_ids))
+        .select((tasks::id, tasks::user_id))
+        .load::<(TaskId, Uuid)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+    let visible_projects: HashSet<ProjectId> = permissions::accessible_project_ids(conn, user_uuid)
+        .await?
+        .into_iter()
+        .collect();
+
+    for task in archive_tasks {
+        progress.advance("tasks").await;
+        let exists = match owners.get(&task.id) {
+            Some(owner) if *owner != user_uuid => {
+                summary.conflict("task", task.id, CONFLICT_OWNED_BY_ANOTHER_USER);
+                continue;
+            }
+            Some(_) if !replace => {
+                summary.conflict("task", task.id, CONFLICT_ALREADY_EXISTS);
+                continue;
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        // Projet disparu ou devenu inaccessible : la tâche est restaurée dans l'Inbox
+        let project_id = task.project_id.filter(|project| {
+            let visible = visible_projects.contains(project);
+            if !visible {
+                summary.conflict("task", task.id, CONFLICT_PROJECT_MISSING);
+            }
+            visible
+        });
+
+        let values = (
+            tasks::project_id.eq(project_id),
+            tasks::title.eq(&task.title),
+            tasks::description.eq(&task.description),
+            tasks::status.eq(&task.status),
+            tasks::due_date.eq(task.due_date),
+            tasks::task_order.eq(task.task_order),
+            tasks::updated_at.eq(task.updated_at),
+            tasks::source.eq(&task.source),
+            tasks::context.eq(&task.context),
+            tasks::metadata.eq(&task.metadata),
+            tasks::scheduled_start.eq(task.scheduled_start),
+            tasks::scheduled_end.eq(task.scheduled_end),
+            tasks::is_pinned.eq(task.is_pinned),
+            tasks::snoozed_until.eq(task.snoozed_until),
+            tasks::follow_up.eq(&task.follow_up),
+            tasks::stage.eq(&task.stage),
+            tasks::time_budget_seconds.eq(task.time_budget_seconds),
+        );
+        if exists {
+            diesel::update(tasks::table.find(task.id))
+                .set(values)
+                .execute(conn)
+                .await?;
+            diesel::delete(task_labels::table.filter(task_labels::task_id.eq(task.id)))
+                .execute(conn)
+                .await?;
+        } else {
+            diesel::insert_into(tasks::table)
+                .values((
+                    tasks::id.eq(task.id),
+                    tasks::user_id.eq(user_uuid),
+                    tasks::created_at.eq(task.created_at),
+                    values,
+                ))
+                .execute(conn)
+                .await?;
+        }
+
+        let associations: Vec<_> = task
+            .labels
+            .iter()
+            .filter_map(|label| label_map.get(&label.id))
+            .map(|label_uuid| {
+                (
+                    task_labels::task_id.eq(task.id),
+                    task_labels::label_id.eq(*label_uuid),
+                )
+            })
+            .collect();
+        if !associations.is_empty() {
+            diesel::insert_into(task_labels::table)
+                .values(associations)
+                .on_conflict_do_nothing()
+                .execute(conn)
+                .await?;
+        }
+        summary.tasks_restored += 1;
+    }
+    Ok(())
+}
+
+// Renvoie les tâches dont le temps suivi a changé (entrées supprimées ou restaurées)
+async fn restore_time_entries(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    replace: bool,
+    archive_entries: &[TimeEntry],
+    locks: &EntryLocks,
+    summary: &mut RestoreSummary,
+    progress: &mut Progress<'_>,
+) -> Result<HashSet<TaskId>, ServiceError> {
+    let archive_ids: Vec<TimeEntryId> = archive_entries.iter().map(|e| e.id).collect();
+    let mut affected_tasks = HashSet::new();
+    if replace {
+        let absent_entries = time_entries::table
+            .filter(time_entries::user_id.eq(user_uuid))
+            .filter(time_entries::id.ne_all(&archive_ids))
+            .select((
+                time_entries::id,
+                time_entries::task_id,
+                time_entries::start_time,
+            ))
+            .load::<(TimeEntryId, TaskId, DateTime<Utc>)>(conn)
+            .await?;
+        let mut deletable_ids = Vec::with_capacity(absent_entries.len());
+        for (entry_uuid, task_uuid, start_time) in absent_entries {
+            if locks.is_locked(start_time) {
+                summary.conflict("time_entry", entry_uuid, CONFLICT_LOCKED);
+            } else {
+                deletable_ids.push(entry_uuid);
+                affected_tasks.insert(task_uuid);
+            }
+        }
+        summary.time_entries_deleted =
+            diesel::delete(time_entries::table.filter(time_entries::id.eq_any(&deletable_ids)))
+                .execute(conn)
+                .await?;
+    }
+
+    let existing: HashMap<TimeEntryId, (Uuid, DateTime<Utc>)> = time_entries::table
+        .filter(time_entries::id.eq_any(&archive_ids))
+        .select((
+            time_entries::id,
+            time_entries::user_id,
+            time_entries::start_time,
+        ))
+        .load::<(TimeEntryId, Uuid, DateTime<Utc>)>(conn)
+        .await?
+        .into_iter()
+        .map(|(entry_uuid, owner, start_time)| (entry_uuid, (owner, start_time)))
+        .collect();
+    let archive_task_ids: Vec<TaskId> = archive_entries.iter().map(|e| e.task_id).collect();
+    let owned_tasks: HashSet<TaskId> = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .filter(tasks::id.eq_any(&archive_task_ids))
+        .select(tasks::id)
+        .load::<TaskId>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    for entry in archive_entries {
+        progress.advance("time_entries").await;
+        let exists = match existing.get(&entry.id) {
+            Some((owner, _)) if *owner != user_uuid => {
+                summary.conflict("time_entry", entry.id, CONFLICT_OWNED_BY_ANOTHER_USER);
+                continue;
+            }
+            Some(_) if !replace => {
+                summary.conflict("time_entry", entry.id, CONFLICT_ALREADY_EXISTS);
+                continue;
+            }
+            Some(_) => true,
+            None => false,
+        };
+        // Ni réécrire une entrée verrouillée, ni en créer une dans une période verrouillée
+        let current_start = existing.get(&entry.id).map(|(_, start_time)| *start_time);
+        if current_start.is_some_and(|start_time| locks.is_locked(start_time))
+            || locks.is_locked(entry.start_time)
+        {
+            summary.conflict("time_entry", entry.id, CONFLICT_LOCKED);
+            continue;
+        }
+        if !owned_tasks.contains(&entry.task_id) {
+            summary.conflict("time_entry", entry.id, CONFLICT_TASK_MISSING);
+            continue;
+        }
+
+        let values = (
+            time_entries::task_id.eq(entry.task_id),
+            time_entries::start_time.eq(entry.start_time),
+            time_entries::end_time.eq(entry.end_time),
+            time_entries::duration_seconds.eq(entry.duration_seconds),
+            time_entries::is_pomodoro_session.eq(entry.is_pomodoro_session),
+            time_entries::updated_at.eq(entry.updated_at),
+            time_entries::is_break.eq(entry.is_break),
+            time_entries::clock_skew_seconds.eq(entry.clock_skew_seconds),
+            time_entries::suspect_clock.eq(entry.suspect_clock),
+        );
+        if exists {
+            diesel::update(time_entries::table.filter(time_entries::id.eq(entry.id)))
+                .set(values)
+                .execute(conn)
+                .await?;
+        } else {
+            diesel::insert_into(time_entries::table)
+                .values((
+                    time_entries::id.eq(entry.id),
+                    time_entries::user_id.eq(user_uuid),
+                    time_entries::created_at.eq(entry.created_at),
+                    values,
+                ))
+                .execute(conn)
+                .await?;
+        }
+        summary.time_entries_restored += 1;
+        affected_tasks.insert(entry.task_id);
+    }
+    Ok(affected_tasks)
+}
+
+// Exécution par la file : tout ou rien, dans une seule transaction
+pub async fn run_restore_job(
+    pool: &DbPool,
+    job: &MaintenanceJob,
+) -> Result<serde_json::Value, ServiceError> {
+    let user_uuid = job.requested_by;
+    let payload = {
+        let mut conn = pool.get().await?;
+        maintenance::load_payload(&mut conn, job.id).await?
+    }
+    .ok_or_else(|| ServiceError::BadRequest("The restore archive is missing".to_string()))?;
+    let RestoreRequest { mode, archive } = serde_json::from_value::<RestoreRequest>(payload)?;
+    let replace = validate_mode(&mode)? == RESTORE_MODE_REPLACE;
+
+    let mut progress = Progress {
+        pool,
+        job_uuid: job.id,
+        total: archive.projects.len()
+            + archive.labels.len()
+            + archive.tasks.len()
+            + archive.time_entries.len(),
+        processed: 0,
+    };
+    progress.record("started").await;
+
+    let mut conn = pool.get().await?;
+    let (summary, budget_tasks) = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            let progress = &mut progress;
+            let archive = &archive;
+            async move {
+                let mut summary = RestoreSummary {
+                    mode: mode.clone(),
+                    ..Default::default()
+                };
+                let locks = EntryLocks::load(conn, user_uuid).await?;
+                restore_projects(
+                    conn,
+                    user_uuid,
+                    replace,
+                    &archive.projects,
+                    &mut summary,
+                    progress,
+                )
+                .await?;
+                let label_map = restore_labels(
+                    conn,
+                    user_uuid,
+                    replace,
+                    &archive.labels,
+                    &mut summary,
+                    progress,
+                )
+                .await?;
+                restore_tasks(
+                    conn,
+                    user_uuid,
+                    replace,
+                    &archive.tasks,
+                    &label_map,
+                    &locks,
+                    &mut summary,
+                    progress,
+                )
+                .await?;
+                let mut budget_tasks = restore_time_entries(
+                    conn,
+                    user_uuid,
+                    replace,
+                    &archive.time_entries,
+                    &locks,
+                    &mut summary,
+                    progress,
+                )
+                .await?;
+                // Les budgets restaurés comptent aussi pour les alertes
+                budget_tasks.extend(archive.tasks.iter().map(|task| task.id));
+                Ok((summary, budget_tasks))
+            }
+            .scope_boxed()
+        })
+        .await?;
+    // Après le commit : les alertes de budget ne doivent pas précéder la restauration
+    for task_uuid in budget_tasks {
+        time_budgets::check_time_budget(&mut conn, task_uuid).await;
+    }
+    drop(conn);
+    progress.record("completed").await;
+
+    log::info!(
+        "Restored backup for user {} ({}): {} task(s), {} conflict(s)",
+        user_uuid,
+        summary.mode,
+        summary.tasks_restored,
+        summary.conflict_count
+    );
+    Ok(serde_json::to_value(summary)?)
+}
@@ -0,0 +1,461 @@
+// OptiTask/backend-api/src/backups.rs
+// Sauvegarde des données de l'utilisateur vers son propre stockage (WebDAV ou S3).
+// L'export JSON est chiffré en AES-256-GCM avec une clé propre à l'utilisateur ; le fichier
+// contient le nonce (12 octets) suivi du texte chiffré et de son tag. Une sauvegarde
+// automatique part chaque nuit, une autre peut être lancée à la demande.
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{
+    BackupConfig, Label, Project, Task, TaskApiResponse, TimeEntry, UserSettings, BACKUP_TARGETS,
+    BACKUP_TARGET_S3, BACKUP_TARGET_WEBDAV,
+};
+use crate::outbound;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::repository;
+use crate::schema::{backup_configs, labels, projects, tasks, time_entries, user_settings};
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Version du format de l'export, incrémentée à chaque changement incompatible
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+const BACKUP_KEY_BYTES: usize = 32;
+// Intervalle entre deux sauvegardes automatiques, et fréquence de vérification
+const BACKUP_INTERVAL_HOURS: i64 = 24;
+const BACKUP_JOB_INTERVAL_SECS: u64 = 3600;
+const MAX_PATH_PREFIX_CHARS: usize = 200;
+const MAX_ERROR_CHARS: usize = 500;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn generate_encryption_key() -> Result<String, ServiceError> {
+    let mut key = [0u8; BACKUP_KEY_BYTES];
+    SystemRandom::new().fill(&mut key).map_err(|_| {
+        ServiceError::InternalServerError("Could not generate a backup key".to_string())
+    })?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(key))
+}
+
+// Segments du préfixe : lettres, chiffres, '-', '_' et '.', séparés par '/'
+fn valid_path_prefix(prefix: &str) -> bool {
+    prefix.chars().count() <= MAX_PATH_PREFIX_CHARS
+        && prefix.split('/').all(|segment| {
+            segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+}
+
+// Nom de bucket S3 : minuscules, chiffres, '-' et '.', 3 à 63 caractères
+fn valid_bucket(bucket: &str) -> bool {
+    (3..=63).contains(&bucket.len())
+        && bucket
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.'))
+}
+
+pub fn normalize_path_prefix(prefix: Option<&str>) -> Result<String, ServiceError> {
+    let prefix = prefix.unwrap_or_default().trim().trim_matches('/');
+    if !valid_path_prefix(prefix) {
+        return Err(ServiceError::ValidationError(format!(
+            "path_prefix may only contain letters, digits, '-', '_', '.' and '/' (at most {} characters)",
+            MAX_PATH_PREFIX_CHARS
+        )));
+    }
+    Ok(prefix.to_string())
+}
+
+// Cible : https obligatoire (les identifiants partent à chaque envoi), bucket et région pour S3
+pub fn validate_target(
+    target_type: &str,
+    endpoint_url: &str,
+    bucket: Option<&str>,
+    region: Option<&str>,
+) -> Result<(), ServiceError> {
+    if !BACKUP_TARGETS.contains(&target_type) {
+        return Err(ServiceError::ValidationError(format!(
+            "target_type must be one of: {}",
+            BACKUP_TARGETS.join(", ")
+        )));
+    }
+    let parsed = reqwest::Url::parse(endpoint_url).map_err(|_| {
+        ServiceError::ValidationError(format!("Invalid endpoint_url: {}", endpoint_url))
+    })?;
+    if parsed.scheme() != "https" || parsed.host_str().is_none() {
+        return Err(ServiceError::ValidationError(
+            "endpoint_url must be an https URL".to_string(),
+        ));
+    }
+    if target_type == BACKUP_TARGET_S3 {
+        if !bucket.is_some_and(valid_bucket) {
+            return Err(ServiceError::ValidationError(
+                "A valid S3 bucket name is required".to_string(),
+            ));
+        }
+        if region.is_none_or(|region| region.trim().is_empty()) {
+            return Err(ServiceError::ValidationError(
+                "region is required for S3 targets".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn next_run_at(config: &BackupConfig) -> Option<DateTime<Utc>> {
+    if !config.enabled {
+        return None;
+    }
+    Some(
+        config
+            .last_attempt_at
+            .map(|at| at + Duration::hours(BACKUP_INTERVAL_HOURS))
+            .unwrap_or_else(Utc::now),
+    )
+}
+
+pub async fn load_config(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<BackupConfig>, ServiceError> {
+    backup_configs::table
+        .find(user_uuid)
+        .select(BackupConfig::as_select())
+        .first::<BackupConfig>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// Export complet des données propres à l'utilisateur
+async fn build_export(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<serde_json::Value, ServiceError> {
+    let settings = user_settings::table
+        .find(user_uuid)
+        .select(UserSettings::as_select())
+        .first::<UserSettings>(conn)
+        .await
+        .optional()?;
+    let project_list = projects::table
+        .filter(projects::user_id.eq(user_uuid))
+        .order(projects::created_at.asc())
+        .select(Project::as_select())
+        .load::<Project>(conn)
+        .await?;
+    let label_list = labels::table
+        .filter(labels::user_id.eq(user_uuid))
+        .order(labels::name.asc())
+        .select(Label::as_select())
+        .load::<Label>(conn)
+        .await?;
+    let task_list = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .order(tasks::created_at.asc())
+        .select(Task::as_select())
+        .load::<Task>(conn)
+        .await?;
+    let mut task_responses: Vec<TaskApiResponse> =
+        task_list.into_iter().map(TaskApiResponse::from).collect();
+    repository::attach_labels(conn, &mut task_responses).await?;
+    let entry_list = time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .order(time_entries::start_time.asc())
+        .select(TimeEntry::as_select())
+        .load::<TimeEntry>(conn)
+        .await?;
+
+    Ok(json!({
+        "format_version": BACKUP_FORMAT_VERSION,
+        "exported_at": Utc::now(),
+        "user_id": user_uuid,
+        "settings": settings,
+        "projects": project_list,
+        "labels": label_list,
+        "tasks": task_responses,
+        "time_entries": entry_list,
+    }))
+}
+
+// nonce || AES-256-GCM(plaintext) || tag
+fn encrypt(encoded_key: &str, plaintext: &[u8]) -> Result<Vec<u8>, ServiceError> {
+    let key_error = || ServiceError::InternalServerError("Invalid backup key".to_string());
+    let key_bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded_key)
+        .map_err(|_| key_error())?;
+    let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &key_bytes).map_err(|_| key_error())?);
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    SystemRandom::new().fill(&mut nonce_bytes).map_err(|_| {
+        ServiceError::InternalServerError("Could not generate a backup nonce".to_string())
+    })?;
+    let mut sealed = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce_bytes),
+        Aad::empty(),
+        &mut sealed,
+    )
+    .map_err(|_| ServiceError::InternalServerError("Backup encryption failed".to_string()))?;
+
+    let mut output = Vec::with_capacity(NONCE_LEN + sealed.len());
+    output.extend_from_slice(&nonce_bytes);
+    output.extend_from_slice(&sealed);
+    Ok(output)
+}
+
+// Inverse de `encrypt` ; une archive illisible avec cette clé est une erreur de l'appelant
+pub fn decrypt(encoded_key: &str, archive: &[u8]) -> Result<Vec<u8>, ServiceError> {
+    let key_bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded_key)
+        .map_err(|_| ServiceError::InternalServerError("Invalid backup key".to_string()))?;
+    let key = LessSafeKey::new(
+        UnboundKey::new(&AES_256_GCM, &key_bytes)
+            .map_err(|_| ServiceError::InternalServerError("Invalid backup key".to_string()))?,
+    );
+    let unreadable = || {
+        ServiceError::ValidationError(
+            "The archive could not be decrypted with this account's backup key".to_string(),
+        )
+    };
+    if archive.len() < NONCE_LEN {
+        return Err(unreadable());
+    }
+    let (nonce_bytes, sealed) = archive.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce_bytes).map_err(|_| unreadable())?;
+    let mut buffer = sealed.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::empty(), &mut buffer)
+        .map_err(|_| unreadable())?;
+    Ok(plaintext.to_vec())
+}
+
+fn object_key(config: &BackupConfig, now: DateTime<Utc>) -> String {
+    let file_name = format!("optitask-backup-{}.json.enc", now.format("%Y%m%dT%H%M%SZ"));
+    if config.path_prefix.is_empty() {
+        file_name
+    } else {
+        format!("{}/{}", config.path_prefix, file_name)
+    }
+}
+
+fn upload_error(e: impl std::fmt::Display) -> String {
+    e.to_string().chars().take(MAX_ERROR_CHARS).collect()
+}
+
+// Seul le statut est conservé : last_error est lisible par l'utilisateur, le corps d'une
+// réponse ne doit pas lui être renvoyé
+fn check_upload_response(response: reqwest::Response) -> Result<(), String> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    Err(format!("storage responded {}", status))
+}
+
+// Envoie le PUT à une adresse publique contrôlée, sans suivre de redirection
+async fn send_upload(
+    url: &reqwest::Url,
+    request: impl FnOnce(&reqwest::Client) -> reqwest::RequestBuilder,
+) -> Result<(), String> {
+    let client = outbound::public_client(url)
+        .await
+        .ok_or_else(|| "endpoint_url does not resolve to a public address".to_string())?;
+    let response = outbound::send_via(&client, &outbound::BACKUP_POLICY, request(&client))
+        .await
+        .map_err(upload_error)?;
+    check_upload_response(response)
+}
+
+async fn upload_webdav(config: &BackupConfig, key: &str, body: Vec<u8>) -> Result<(), String> {
+    let url = reqwest::Url::parse(&format!(
+        "{}/{}",
+        config.endpoint_url.trim_end_matches('/'),
+        key
+    ))
+    .map_err(upload_error)?;
+    send_upload(&url, |client| {
+        client
+            .put(url.clone())
+            .basic_auth(&config.username, Some(&config.secret))
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(body)
+    })
+    .await
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes())
+        .as_ref()
+        .to_vec()
+}
+
+// PUT signé AWS Signature V4, en adressage par chemin (compatible MinIO, B2, R2...)
+async fn upload_s3(config: &BackupConfig, key: &str, body: Vec<u8>) -> Result<(), String> {
+    let bucket = config.bucket.as_deref().unwrap_or_default();
+    let region = config.region.as_deref().unwrap_or_default();
+    let url = reqwest::Url::parse(&format!(
+        "{}/{}/{}",
+        config.endpoint_url.trim_end_matches('/'),
+        bucket,
+        key
+    ))
+    .map_err(upload_error)?;
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => return Err("endpoint_url has no host".to_string()),
+    };
+
+    let now = Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let short_date = now.format("%Y%m%d").to_string();
+    let payload_hash = hex(&Sha256::digest(&body));
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+    let canonical_request = format!(
+        "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        url.path(),
+        host,
+        payload_hash,
+        amz_date,
+        signed_headers,
+        payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", short_date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+    let signing_key = ["s3", "aws4_request"].iter().fold(
+        hmac_sha256(
+            &hmac_sha256(format!("AWS4{}", config.secret).as_bytes(), &short_date),
+            region,
+        ),
+        |key, part| hmac_sha256(&key, part),
+    );
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        config.username,
+        scope,
+        signed_headers,
+        hex(&hmac_sha256(&signing_key, &string_to_sign))
+    );
+
+    send_upload(&url, |client| {
+        client
+            .put(url.clone())
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header(reqwest::header::AUTHORIZATION, authorization)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(body)
+    })
+    .await
+}
+
+// Exporte, chiffre et envoie ; le résultat (succès ou erreur du stockage) est enregistré
+// sur la configuration. Seules les erreurs internes remontent en Err.
+pub async fn run_backup(
+    conn: &mut AsyncPgConnection,
+    config: &BackupConfig,
+) -> Result<BackupConfig, ServiceError> {
+    let now = Utc::now();
+    let export = build_export(conn, config.user_id).await?;
+    let encrypted = encrypt(&config.encryption_key, &serde_json::to_vec(&export)?)?;
+    let size_bytes = encrypted.len() as i64;
+    let key = object_key(config, now);
+
+    let outcome = match config.target_type.as_str() {
+        BACKUP_TARGET_WEBDAV => upload_webdav(config, &key, encrypted).await,
+        _ => upload_s3(config, &key, encrypted).await,
+    };
+
+    let target = backup_configs::table.find(config.user_id);
+    let updated = match outcome {
+        Ok(()) => {
+            diesel::update(target)
+                .set((
+                    backup_configs::last_attempt_at.eq(now),
+                    backup_configs::last_success_at.eq(now),
+                    backup_configs::last_error.eq(None::<String>),
+                    backup_configs::last_object_key.eq(&key),
+                    backup_configs::last_size_bytes.eq(size_bytes),
+                ))
+                .returning(BackupConfig::as_returning())
+                .get_result::<BackupConfig>(conn)
+                .await?
+        }
+        Err(error) => {
+            log::warn!(
+                "Backup upload failed for user {}: {}",
+                config.user_id,
+                error
+            );
+            diesel::update(target)
+                .set((
+                    backup_configs::last_attempt_at.eq(now),
+                    backup_configs::last_error.eq(error),
+                ))
+                .returning(BackupConfig::as_returning())
+                .get_result::<BackupConfig>(conn)
+                .await?
+        }
+    };
+    Ok(updated)
+}
+
+// Sauvegardes automatiques dont la dernière tentative date de plus d'un intervalle
+pub async fn run_due_backups(pool: &DbPool) -> Result<usize, ServiceError> {
+    let mut conn = pool.get().await?;
+    let threshold = Utc::now() - Duration::hours(BACKUP_INTERVAL_HOURS);
+    let due = backup_configs::table
+        .filter(backup_configs::enabled.eq(true))
+        .filter(
+            backup_configs::last_attempt_at
+                .is_null()
+                .or(backup_configs::last_attempt_at.le(threshold)),
+        )
+        .select(BackupConfig::as_select())
+        .load::<BackupConfig>(&mut conn)
+        .await?;
+
+    let mut succeeded = 0;
+    for config in &due {
+        match run_backup(&mut conn, config).await {
+            Ok(updated) if updated.last_error.is_none() => succeeded += 1,
+            Ok(_) => {}
+            Err(e) => log::error!("Backup failed for user {}: {}", config.user_id, e),
+        }
+    }
+    Ok(succeeded)
+}
+
+pub fn spawn_backup_job(pool: DbPool, read_only: Arc<ReadOnlyMode>) {
+    actix_web::rt::spawn(async move {
+        let mut interval =
+            actix_web::rt::time::interval(std::time::Duration::from_secs(BACKUP_JOB_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            match run_due_backups(&pool).await {
+                Ok(count) if count > 0 => log::info!("Uploaded {} scheduled backup(s)", count),
+                Ok(_) => {}
+                Err(e) => log::error!("Backup job failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,151 @@
+// OptiTask/backend-api/src/badges.rs
+// Badge public de statistiques ("37h focused this month") à intégrer dans un blog ou un
+// README. L'utilisateur génère un jeton secret ; l'image est servie sans authentification
+// sur /public/badge/<token>.svg, mise en cache par les clients et limitée par badge.
+use crate::cost_limits::{CostClass, CostLimitConfig, CostLimiter};
+use crate::error_handler::ServiceError;
+use crate::models::PublicBadgeToken;
+use crate::schema::public_badge_tokens;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+// Durée de mise en cache de l'image par les navigateurs et CDN
+pub const BADGE_CACHE_MAX_AGE_SECS: u32 = 900;
+// Rendus autorisés par badge et par fenêtre, au-delà de ce que le cache absorbe
+const BADGE_RENDERS_PER_WINDOW: u32 = 30;
+const BADGE_RATE_WINDOW_SECS: u64 = 60;
+
+const BADGE_LABEL: &str = "OptiTask";
+const LABEL_COLOR: &str = "#555";
+const VALUE_COLOR: &str = "#4c1";
+// Largeur moyenne d'un caractère en Verdana 11px, et marge de chaque côté du texte
+const CHAR_WIDTH: usize = 7;
+const TEXT_PADDING: usize = 10;
+
+// Limiteur propre aux badges : une image très consultée n'entame pas le budget
+// des appels authentifiés de son propriétaire
+pub struct BadgeRateLimiter(CostLimiter);
+
+impl BadgeRateLimiter {
+    pub fn new() -> BadgeRateLimiter {
+        BadgeRateLimiter(CostLimiter::new(CostLimitConfig {
+            budget: BADGE_RENDERS_PER_WINDOW,
+            window_secs: BADGE_RATE_WINDOW_SECS,
+        }))
+    }
+
+    pub fn charge(&self, user_uuid: Uuid) -> Result<(), ServiceError> {
+        self.0.charge(user_uuid, CostClass::PublicBadge)
+    }
+}
+
+fn generate_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+pub fn badge_path(token: &str) -> String {
+    format!("/public/badge/{}.svg", token)
+}
+
+pub async fn load_token(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<PublicBadgeToken>, ServiceError> {
+    public_badge_tokens::table
+        .find(user_uuid)
+        .select(PublicBadgeToken::as_select())
+        .first::<PublicBadgeToken>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// Crée le jeton, ou le remplace avec `rotate` : l'ancienne URL cesse alors de fonctionner
+pub async fn issue_token(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    rotate: bool,
+) -> Result<PublicBadgeToken, ServiceError> {
+    let new_token = generate_token();
+    let insert = diesel::insert_into(public_badge_tokens::table).values((
+        public_badge_tokens::user_id.eq(user_uuid),
+        public_badge_tokens::token.eq(&new_token),
+    ));
+
+    if rotate {
+        insert
+            .on_conflict(public_badge_tokens::user_id)
+            .do_update()
+            .set((
+                public_badge_tokens::token.eq(&new_token),
+                public_badge_tokens::created_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)
+            .await?;
+    } else {
+        insert.on_conflict_do_nothing().execute(conn).await?;
+    }
+
+    public_badge_tokens::table
+        .find(user_uuid)
+        .select(PublicBadgeToken::as_select())
+        .first::<PublicBadgeToken>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+pub async fn revoke_token(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<usize, ServiceError> {
+    diesel::delete(public_badge_tokens::table.find(user_uuid))
+        .execute(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+pub async fn find_owner(
+    conn: &mut AsyncPgConnection,
+    token: &str,
+) -> Result<Option<Uuid>, ServiceError> {
+    public_badge_tokens::table
+        .filter(public_badge_tokens::token.eq(token.to_lowercase()))
+        .select(public_badge_tokens::user_id)
+        .first::<Uuid>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// "37h", ou "42m" sous la première heure
+pub fn format_tracked_time(seconds: i64) -> String {
+    if seconds >= 3600 {
+        format!("{}h", seconds / 3600)
+    } else {
+        format!("{}m", seconds.max(0) / 60)
+    }
+}
+
+// Badge au format "flat" de shields.io : libellé à gauche, valeur à droite.
+// Le texte est généré par le serveur (chiffres et mots fixes), sans saisie utilisateur.
+pub fn render_svg(value: &str) -> String {
+    let label_width = BADGE_LABEL.chars().count() * CHAR_WIDTH + 2 * TEXT_PADDING;
+    let value_width = value.chars().count() * CHAR_WIDTH + 2 * TEXT_PADDING;
+    let total_width = label_width + value_width;
+    let label_x = label_width / 2;
+    let value_x = label_width + value_width / 2;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="20" role="img" aria-label="{label}: {value}"><title>{label}: {value}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{total}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="{label_color}"/><rect x="{label_width}" width="{value_width}" height="20" fill="{value_color}"/><rect width="{total}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="14">{label}</text><text x="{value_x}" y="14">{value}</text></g></svg>"##,
+        total = total_width,
+        label = BADGE_LABEL,
+        value = value,
+        label_width = label_width,
+        value_width = value_width,
+        label_color = LABEL_COLOR,
+        value_color = VALUE_COLOR,
+        label_x = label_x,
+        value_x = value_x,
+    )
+}
@@ -0,0 +1,123 @@
+// OptiTask/backend-api/src/batch.rs
+// Forme commune des réponses des opérations groupées (statut groupé, promote/demote, import
+// CSV, labels en masse) : un résultat par élément, dans l'ordre de la requête, avec son
+// statut HTTP, son issue, l'entité concernée et, en cas d'échec, un code stable. La réponse
+// est 207 Multi-Status quand les issues sont mélangées ; si tous les éléments échouent avec
+// le même statut, c'est ce statut qui est renvoyé.
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct BatchItemResult<T> {
+    // Position de l'élément dans la requête (ou dans le fichier importé)
+    pub index: usize,
+    pub status: u16,
+    // Issue propre à l'endpoint ("updated", "created", "skipped"...)
+    pub outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl<T> BatchItemResult<T> {
+    pub fn succeeded(index: usize, status: StatusCode, outcome: &'static str, entity: T) -> Self {
+        BatchItemResult {
+            index,
+            status: status.as_u16(),
+            outcome,
+            entity: Some(entity),
+            error_code: None,
+            message: None,
+        }
+    }
+
+    pub fn failed(
+        index: usize,
+        status: StatusCode,
+        outcome: &'static str,
+        error_code: &'static str,
+        message: String,
+        entity: Option<T>,
+    ) -> Self {
+        BatchItemResult {
+            index,
+            status: status.as_u16(),
+            outcome,
+            entity,
+            error_code: Some(error_code),
+            message: Some(message),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct BatchReport<T> {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BatchItemResult<T>>,
+}
+
+impl<T> BatchReport<T> {
+    pub fn new(results: Vec<BatchItemResult<T>>) -> Self {
+        let succeeded = results.iter().filter(|result| result.is_success()).count();
+        BatchReport {
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
+        }
+    }
+
+    // `success_status` si tout a réussi, le statut commun si tout a échoué de la même
+    // façon, 207 sinon
+    pub fn status(&self, success_status: StatusCode) -> StatusCode {
+        if self.failed == 0 {
+            return success_status;
+        }
+        if self.succeeded == 0 {
+            let first_status = self.results[0].status;
+            if self
+                .results
+                .iter()
+                .all(|result| result.status == first_status)
+            {
+                return StatusCode::from_u16(first_status).unwrap_or(StatusCode::MULTI_STATUS);
+            }
+        }
+        StatusCode::MULTI_STATUS
+    }
+}
+
+#[derive(Serialize)]
+struct BatchBody<M, T> {
+    #[serde(flatten)]
+    meta: M,
+    #[serde(flatten)]
+    report: BatchReport<T>,
+}
+
+// Réponse avec le statut déduit des résultats ; `meta` (objet) ajoute les champs propres
+// à l'endpoint à côté de succeeded/failed/results
+pub fn respond<M: Serialize, T: Serialize>(
+    success_status: StatusCode,
+    meta: M,
+    report: BatchReport<T>,
+) -> HttpResponse {
+    respond_with_status(report.status(success_status), meta, report)
+}
+
+// Statut imposé, pour les opérations tout-ou-rien dont l'échec n'applique aucun élément
+pub fn respond_with_status<M: Serialize, T: Serialize>(
+    status: StatusCode,
+    meta: M,
+    report: BatchReport<T>,
+) -> HttpResponse {
+    HttpResponse::build(status).json(BatchBody { meta, report })
+}
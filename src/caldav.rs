@@ -0,0 +1,369 @@
+// OptiTask/backend-api/src/caldav.rs
+// Interface CalDAV (VTODO) : authentification Basic par mot de passe d'application,
+// sérialisation iCalendar des tâches et petits utilitaires XML WebDAV.
+use crate::db::DbPool;
+use crate::models::{Task, DONE_TASK_STATUSES};
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::app_passwords;
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse};
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+pub const DAV_REALM: &str = "OptiTask CalDAV";
+const APP_PASSWORD_BYTES: usize = 24;
+// Longueur maximale d'une ligne iCalendar (RFC 5545 §3.1), hors CRLF
+const ICAL_LINE_OCTETS: usize = 75;
+
+// Les mots de passe d'application sont aléatoires (192 bits) : un SHA-256 suffit
+pub fn hash_app_password(password: &str) -> String {
+    Sha256::digest(password.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub fn generate_app_password() -> String {
+    let mut bytes = Vec::with_capacity(APP_PASSWORD_BYTES);
+    while bytes.len() < APP_PASSWORD_BYTES {
+        bytes.extend_from_slice(Uuid::new_v4().as_bytes());
+    }
+    bytes.truncate(APP_PASSWORD_BYTES);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized()
+        .insert_header((
+            header::WWW_AUTHENTICATE,
+            format!("Basic realm=\"{}\", charset=\"UTF-8\"", DAV_REALM),
+        ))
+        .finish()
+}
+
+// Basic auth : identifiant = UUID de l'utilisateur, mot de passe = mot de passe d'application
+pub async fn authenticate(req: &HttpRequest, pool: &DbPool) -> Result<Uuid, HttpResponse> {
+    let credentials = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|encoded| {
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded.trim())
+                .ok()
+        })
+        .and_then(|decoded| String::from_utf8(decoded).ok());
+    let Some((username, password)) = credentials
+        .as_deref()
+        .and_then(|value| value.split_once(':'))
+    else {
+        return Err(unauthorized());
+    };
+    let Ok(user_uuid) = Uuid::parse_str(username.trim()) else {
+        return Err(unauthorized());
+    };
+
+    let mut conn = pool.get().await.map_err(|e| {
+        log::error!("CalDAV authentication could not get a connection: {}", e);
+        HttpResponse::ServiceUnavailable().finish()
+    })?;
+
+    let password_hash = hash_app_password(password);
+    let matching = app_passwords::table
+        .filter(app_passwords::user_id.eq(user_uuid))
+        .filter(app_passwords::password_hash.eq(&password_hash));
+    let matched = matching
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await
+        .map_err(|e| {
+            log::error!("CalDAV authentication query failed: {}", e);
+            HttpResponse::InternalServerError().finish()
+        })?;
+    if matched == 0 {
+        log::warn!("Rejected CalDAV credentials for user {}", user_uuid);
+        return Err(unauthorized());
+    }
+
+    // Information indicative : ni bloquante, ni écrite pendant le mode lecture seule
+    let read_only = req
+        .app_data::<web::Data<ReadOnlyMode>>()
+        .is_some_and(|mode| mode.is_enabled());
+    if !read_only {
+        if let Err(e) = diesel::update(matching)
+            .set(app_passwords::last_used_at.eq(Utc::now()))
+            .execute(&mut conn)
+            .await
+        {
+            log::warn!("Could not record app password usage: {}", e);
+        }
+    }
+    Ok(user_uuid)
+}
+
+// ETag fort dérivé de la date de dernière modification
+pub fn task_etag(task: &Task) -> String {
+    format!(
+        "\"{}-{}\"",
+        task.id.as_uuid().simple(),
+        task.updated_at.and_utc().timestamp_micros()
+    )
+}
+
+// Marqueur de version de la collection (getctag), change à chaque ajout, modification ou suppression
+pub fn collection_ctag(latest_update: Option<NaiveDateTime>, task_count: i64) -> String {
+    format!(
+        "\"{}-{}\"",
+        latest_update
+            .map(|updated| updated.and_utc().timestamp_micros())
+            .unwrap_or(0),
+        task_count
+    )
+}
+
+pub fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn multistatus(responses: &[String]) -> HttpResponse {
+    HttpResponse::build(actix_web::http::StatusCode::MULTI_STATUS)
+        .content_type("application/xml; charset=utf-8")
+        .body(format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+             <d:multistatus xmlns:d=\"DAV:\" xmlns:c=\"urn:ietf:params:xml:ns:caldav\" \
+             xmlns:cs=\"http://calendarserver.org/ns/\">{}</d:multistatus>",
+            responses.concat()
+        ))
+}
+
+pub fn propstat_response(href: &str, props: &str) -> String {
+    format!(
+        "<d:response><d:href>{}</d:href><d:propstat><d:prop>{}</d:prop>\
+         <d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+        xml_escape(href),
+        props
+    )
+}
+
+pub fn not_found_response(href: &str) -> String {
+    format!(
+        "<d:response><d:href>{}</d:href><d:status>HTTP/1.1 404 Not Found</d:status></d:response>",
+        xml_escape(href)
+    )
+}
+
+// Contenu des éléments <href> d'un corps XML, quel que soit le préfixe d'espace de noms
+pub fn extract_hrefs(body: &str) -> Vec<String> {
+    let mut hrefs = Vec::new();
+    let mut rest = body;
+    while let Some(open) = rest.find("href>") {
+        let after_open = &rest[open + 5..];
+        // Ignorer les balises fermantes "</d:href>"
+        let tag_start = rest[..open].rfind('<').unwrap_or(0);
+        if rest[tag_start..open].starts_with("</") {
+            rest = after_open;
+            continue;
+        }
+        match after_open.find('<') {
+            Some(close) => {
+                let href = after_open[..close].trim();
+                if !href.is_empty() {
+                    hrefs.push(
+                        href.replace("&amp;", "&")
+                            .replace("&lt;", "<")
+                            .replace("&gt;", ">"),
+                    );
+                }
+                rest = &after_open[close..];
+            }
+            None => break,
+        }
+    }
+    hrefs
+}
+
+fn ical_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn ical_unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+// Repli des lignes longues : CRLF suivi d'un espace, sans couper un caractère UTF-8
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut current_octets = 0;
+    for c in line.chars() {
+        if current_octets + c.len_utf8() > ICAL_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            current_octets = 1;
+        }
+        folded.push(c);
+        current_octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn ical_datetime(value: DateTime<Utc>) -> String {
+    value.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn vtodo_status(task_status: &str) -> &'static str {
+    match task_status {
+        s if DONE_TASK_STATUSES.contains(&s) => "COMPLETED",
+        "inprogress" | "in_progress" => "IN-PROCESS",
+        "cancelled" => "CANCELLED",
+        _ => "NEEDS-ACTION",
+    }
+}
+
+// Statut OptiTask correspondant ; le statut courant est conservé s'il se traduit déjà ainsi
+pub fn task_status_from_vtodo(todo_status: Option<&str>, current: Option<&str>) -> String {
+    let wanted = todo_status
+        .map(|s| s.to_ascii_uppercase())
+        .unwrap_or_else(|| "NEEDS-ACTION".to_string());
+    if let Some(current) = current {
+        if vtodo_status(current) == wanted {
+            return current.to_string();
+        }
+    }
+    match wanted.as_str() {
+        "COMPLETED" => "completed",
+        "IN-PROCESS" => "inprogress",
+        "CANCELLED" => "cancelled",
+        _ => "todo",
+    }
+    .to_string()
+}
+
+pub fn task_to_ical(task: &Task) -> String {
+    let updated = task.updated_at.and_utc();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//OptiTask//CalDAV//EN".to_string(),
+        "BEGIN:VTODO".to_string(),
+        format!("UID:{}", task.id),
+        format!("DTSTAMP:{}", ical_datetime(updated)),
+        format!("CREATED:{}", ical_datetime(task.created_at.and_utc())),
+        format!("LAST-MODIFIED:{}", ical_datetime(updated)),
+        format!("SUMMARY:{}", ical_escape(&task.title)),
+        format!("STATUS:{}", vtodo_status(&task.status)),
+    ];
+    if let Some(description) = task.description.as_deref().filter(|d| !d.is_empty()) {
+        lines.push(format!("DESCRIPTION:{}", ical_escape(description)));
+    }
+    if let Some(due) = task.due_date {
+        lines.push(format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+    }
+    if let Some(start) = task.scheduled_start {
+        lines.push(format!("DTSTART:{}", ical_datetime(start)));
+    }
+    if DONE_TASK_STATUSES.contains(&task.status.as_str()) {
+        lines.push(format!("COMPLETED:{}", ical_datetime(updated)));
+    }
+    lines.push("END:VTODO".to_string());
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+// Champs d'un VTODO reçu qui sont repris dans la tâche
+#[derive(Debug, Default)]
+pub struct ParsedTodo {
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub due: Option<NaiveDate>,
+}
+
+fn parse_ical_date(value: &str) -> Option<NaiveDate> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+}
+
+// Lit le premier VTODO ; None si le corps n'en contient pas
+pub fn parse_vtodo(body: &str) -> Option<ParsedTodo> {
+    // Dépliage : une ligne commençant par un espace ou une tabulation prolonge la précédente
+    let mut unfolded: Vec<String> = Vec::new();
+    for raw_line in body.split('\n') {
+        let line = raw_line.strip_suffix('\r').unwrap_or(raw_line);
+        match (
+            line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')),
+            unfolded.last_mut(),
+        ) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ => unfolded.push(line.to_string()),
+        }
+    }
+
+    let mut todo = ParsedTodo::default();
+    let mut in_todo = false;
+    let mut nested_depth = 0;
+    let mut found = false;
+    for line in unfolded {
+        let Some((name_and_params, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = name_and_params
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        match (name.as_str(), value.trim().to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VTODO") if !found => {
+                in_todo = true;
+                found = true;
+                continue;
+            }
+            ("END", "VTODO") if in_todo => break,
+            // Composants imbriqués (VALARM) : leurs propriétés sont ignorées
+            ("BEGIN", _) if in_todo => nested_depth += 1,
+            ("END", _) if in_todo => nested_depth -= 1,
+            _ => {}
+        }
+        if !in_todo || nested_depth > 0 {
+            continue;
+        }
+        match name.as_str() {
+            "UID" => todo.uid = Some(value.trim().to_string()),
+            "SUMMARY" => todo.summary = Some(ical_unescape(value)),
+            "DESCRIPTION" => todo.description = Some(ical_unescape(value)),
+            "STATUS" => todo.status = Some(value.trim().to_string()),
+            "DUE" => todo.due = parse_ical_date(value.trim()),
+            _ => {}
+        }
+    }
+    found.then_some(todo)
+}
@@ -0,0 +1,106 @@
+// OptiTask/backend-api/src/clock_skew.rs
+// Horloge des clients (mobiles surtout) : à la création d'une entrée de temps, le client peut
+// envoyer l'heure de son horloge (client_time) et son décalage UTC déclaré (client_offset).
+// L'écart avec l'heure serveur est corrigé s'il est vraisemblable, sinon l'entrée est
+// marquée suspect_clock et remonte dans le rapport d'intégrité.
+use crate::error_handler::ServiceError;
+use chrono::{DateTime, Duration, Utc};
+
+// En deçà : latence réseau et petites dérives, aucune correction
+pub const CLOCK_SKEW_TOLERANCE_SECS: i64 = 120;
+// Au-delà, l'horloge n'est plus jugée fiable : les horaires sont gardés tels quels
+pub const MAX_CORRECTABLE_SKEW_SECS: i64 = 24 * 3600;
+// Décalages UTC réels : ±14h
+const MAX_CLIENT_OFFSET_MINUTES: i32 = 14 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClockAssessment {
+    // Horloge juste (ou non fournie)
+    Trusted,
+    // Horaires à décaler de `correction_seconds` ; skew_seconds = écart mesuré
+    Adjusted {
+        skew_seconds: i64,
+        correction_seconds: i64,
+    },
+    // Écart invraisemblable : horaires conservés, entrée marquée suspect_clock
+    Suspect {
+        skew_seconds: i64,
+    },
+}
+
+impl ClockAssessment {
+    // Écart conservé sur l'entrée (colonne clock_skew_seconds)
+    pub fn skew_seconds(self) -> Option<i32> {
+        match self {
+            ClockAssessment::Trusted => None,
+            ClockAssessment::Adjusted { skew_seconds, .. }
+            | ClockAssessment::Suspect { skew_seconds } => {
+                Some(skew_seconds.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+            }
+        }
+    }
+
+    pub fn is_suspect(self) -> bool {
+        matches!(self, ClockAssessment::Suspect { .. })
+    }
+
+    // Ramène un horaire client sur l'horloge du serveur
+    pub fn correct(self, client_timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            ClockAssessment::Adjusted {
+                correction_seconds, ..
+            } => client_timestamp - Duration::seconds(correction_seconds),
+            _ => client_timestamp,
+        }
+    }
+}
+
+pub fn validate_client_offset(client_offset: Option<i32>) -> Result<(), ServiceError> {
+    match client_offset {
+        Some(offset) if offset.abs() > MAX_CLIENT_OFFSET_MINUTES => {
+            Err(ServiceError::ValidationError(format!(
+                "client_offset must be between -{} and {} minutes",
+                MAX_CLIENT_OFFSET_MINUTES, MAX_CLIENT_OFFSET_MINUTES
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Compare l'horloge du client à celle du serveur (server_now, à la réception).
+// Un écart proche du décalage déclaré signale une heure locale envoyée comme UTC :
+// la correction est alors exactement ce décalage, sans l'imprécision de la latence.
+pub fn assess(
+    client_time: Option<DateTime<Utc>>,
+    client_offset: Option<i32>,
+    server_now: DateTime<Utc>,
+) -> ClockAssessment {
+    let Some(client_time) = client_time else {
+        return ClockAssessment::Trusted;
+    };
+    let skew_seconds = (client_time - server_now).num_seconds();
+    if skew_seconds.abs() <= CLOCK_SKEW_TOLERANCE_SECS {
+        return ClockAssessment::Trusted;
+    }
+
+    let offset_seconds = client_offset
+        .map(|offset| i64::from(offset) * 60)
+        .filter(|offset_seconds| *offset_seconds != 0);
+    if let Some(offset_seconds) = offset_seconds {
+        if (skew_seconds - offset_seconds).abs() <= CLOCK_SKEW_TOLERANCE_SECS {
+            return ClockAssessment::Adjusted {
+                skew_seconds,
+                correction_seconds: offset_seconds,
+            };
+        }
+    }
+
+    if skew_seconds.abs() <= MAX_CORRECTABLE_SKEW_SECS {
+        ClockAssessment::Adjusted {
+            skew_seconds,
+            correction_seconds: skew_seconds,
+        }
+    } else {
+        ClockAssessment::Suspect { skew_seconds }
+    }
+}
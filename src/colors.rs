@@ -0,0 +1,196 @@
+// OptiTask/backend-api/src/colors.rs
+// Attribution de couleurs aux projets et labels : parmi une palette lisible sur fond clair
+// (contraste d'au moins 3:1 avec le blanc, WCAG 1.4.11), on choisit les couleurs les plus
+// éloignées (distance CIELAB) de celles déjà utilisées par l'utilisateur.
+use crate::error_handler::ServiceError;
+use crate::schema::{labels, projects};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use uuid::Uuid;
+
+// Contraste minimal avec le fond blanc de l'interface
+const MIN_BACKGROUND_CONTRAST: f64 = 3.0;
+const WHITE: Rgb = Rgb(0xff, 0xff, 0xff);
+// Texte sombre proposé sur les couleurs claires
+const DARK_TEXT: Rgb = Rgb(0x11, 0x18, 0x27);
+
+// Teintes réparties sur le cercle chromatique, en deux luminosités
+const PALETTE: [&str; 28] = [
+    "#dc2626", "#ea580c", "#d97706", "#65a30d", "#16a34a", "#059669", "#0d9488", "#0891b2",
+    "#0284c7", "#2563eb", "#4f46e5", "#7c3aed", "#9333ea", "#c026d3", "#db2777", "#e11d48",
+    "#475569", "#78716c", "#991b1b", "#9a3412", "#854d0e", "#166534", "#115e59", "#155e75",
+    "#1e40af", "#5b21b6", "#86198f", "#9f1239",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgb(u8, u8, u8);
+
+impl Rgb {
+    // "#RRGGBB" ou "#RGB" ; None pour toute autre valeur (noms CSS, etc.)
+    pub fn parse(value: &str) -> Option<Rgb> {
+        let hex = value.trim().strip_prefix('#')?;
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let expanded: String = match hex.len() {
+            6 => hex.to_string(),
+            3 => hex.chars().flat_map(|c| [c, c]).collect(),
+            _ => return None,
+        };
+        let channel = |at: usize| u8::from_str_radix(&expanded[at..at + 2], 16).ok();
+        Some(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+
+    fn linear_channels(self) -> [f64; 3] {
+        [self.0, self.1, self.2].map(|channel| {
+            let c = f64::from(channel) / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    }
+
+    // Luminance relative (WCAG)
+    fn luminance(self) -> f64 {
+        let [r, g, b] = self.linear_channels();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    fn lab(self) -> [f64; 3] {
+        let [r, g, b] = self.linear_channels();
+        // sRGB -> XYZ (D65), normalisé par le blanc de référence
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+        let f = |t: f64| {
+            if t > 0.008856 {
+                t.cbrt()
+            } else {
+                7.787 * t + 16.0 / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+    }
+
+    fn distance(self, other: Rgb) -> f64 {
+        let [l1, a1, b1] = self.lab();
+        let [l2, a2, b2] = other.lab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+}
+
+fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+    let (la, lb) = (a.luminance(), b.luminance());
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[derive(Serialize, Debug)]
+pub struct ColorSuggestion {
+    pub color: String,
+    // Couleur de texte lisible sur cette couleur (contraste maximal)
+    pub text_color: String,
+    pub text_contrast_ratio: f64,
+}
+
+impl ColorSuggestion {
+    fn new(color: Rgb) -> ColorSuggestion {
+        let light = contrast_ratio(color, WHITE);
+        let dark = contrast_ratio(color, DARK_TEXT);
+        let (text_color, ratio) = if light >= dark {
+            (WHITE, light)
+        } else {
+            (DARK_TEXT, dark)
+        };
+        ColorSuggestion {
+            color: color.hex(),
+            text_color: text_color.hex(),
+            text_contrast_ratio: (ratio * 100.0).round() / 100.0,
+        }
+    }
+}
+
+// Couleurs des projets et labels de l'utilisateur
+pub async fn used_colors(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Vec<Rgb>, ServiceError> {
+    let mut stored = projects::table
+        .filter(projects::user_id.eq(user_uuid))
+        .filter(projects::color.is_not_null())
+        .select(projects::color)
+        .load::<Option<String>>(conn)
+        .await?;
+    stored.extend(
+        labels::table
+            .filter(labels::user_id.eq(user_uuid))
+            .filter(labels::color.is_not_null())
+            .select(labels::color)
+            .load::<Option<String>>(conn)
+            .await?,
+    );
+    Ok(stored
+        .iter()
+        .flatten()
+        .filter_map(|color| Rgb::parse(color))
+        .collect())
+}
+
+// Choix glouton : à chaque tour, la couleur de la palette la plus éloignée de toutes
+// celles déjà utilisées ou choisies ; l'ordre de la palette départage les égalités
+pub fn suggest(used: &[Rgb], count: usize) -> Vec<ColorSuggestion> {
+    let mut candidates: Vec<Rgb> = PALETTE
+        .iter()
+        .filter_map(|hex| Rgb::parse(hex))
+        .filter(|color| contrast_ratio(*color, WHITE) >= MIN_BACKGROUND_CONTRAST)
+        .collect();
+    let mut taken: Vec<Rgb> = used.to_vec();
+    let mut picked = Vec::with_capacity(count);
+
+    while picked.len() < count && !candidates.is_empty() {
+        let farthest = |color: &Rgb| {
+            taken
+                .iter()
+                .map(|other| color.distance(*other))
+                .fold(f64::INFINITY, f64::min)
+        };
+        let best = (1..candidates.len()).fold(0, |best, index| {
+            if farthest(&candidates[index]) > farthest(&candidates[best]) {
+                index
+            } else {
+                best
+            }
+        });
+        let color = candidates.remove(best);
+        taken.push(color);
+        picked.push(ColorSuggestion::new(color));
+    }
+    picked
+}
+
+// Couleur attribuée à un projet ou label créé sans couleur ; elle rejoint `used` pour
+// que les créations suivantes d'un même lot reçoivent des couleurs différentes
+pub fn next_color(used: &mut Vec<Rgb>) -> String {
+    let color = suggest(used, 1)
+        .into_iter()
+        .next()
+        .map(|suggestion| suggestion.color)
+        .unwrap_or_else(|| PALETTE[0].to_string());
+    used.extend(Rgb::parse(&color));
+    color
+}
+
+pub async fn assign_color(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<String, ServiceError> {
+    let mut used = used_colors(conn, user_uuid).await?;
+    Ok(next_color(&mut used))
+}
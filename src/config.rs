@@ -0,0 +1,377 @@
+// OptiTask/backend-api/src/config.rs
+// Configuration de l'application : toutes les variables d'environnement sont lues ici,
+// une seule fois au démarrage, et validées avant de lancer le serveur. Une valeur invalide
+// arrête le démarrage avec un message nommant la variable fautive.
+use crate::admin::AdminConfig;
+use crate::cors::CorsConfig;
+use crate::cost_limits::CostLimitConfig;
+use crate::demo::DemoConfig;
+use crate::events::EventSinkConfig;
+use crate::inbound_email::InboundEmailConfig;
+use crate::integrations::google_calendar::GoogleCalendarConfig;
+use crate::metadata::MetadataConfig;
+use crate::read_only_mode::ReadOnlyConfig;
+use crate::schema_check::SchemaCheckMode;
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+const DEFAULT_HOST: &str = "0.0.0.0";
+const DEFAULT_PORT: u16 = 8080;
+const DEFAULT_FRONTEND_URL_PROD: &str = "https://opti-task-six.vercel.app";
+const DEFAULT_FRONTEND_URL_DEV: &str = "http://localhost:3000";
+const DEFAULT_POOL_MAX_SIZE: u32 = 15;
+const DEFAULT_POOL_MIN_IDLE: u32 = 5;
+const DEFAULT_LLM_MODEL: &str = "gpt-4o-mini";
+// Utilisateur de démo par défaut si DEMO_USER_ID n'est pas défini
+const DEFAULT_DEMO_USER_ID: Uuid = Uuid::from_u128(0x0000_0000_0000_4000_8000_0000_0000_d3e0);
+const DEFAULT_DEMO_RESET_HOUR_UTC: u32 = 3;
+const DEFAULT_INBOUND_DOMAIN: &str = "optitask.app";
+// Taille maximale par défaut des métadonnées libres (16 Ko)
+const DEFAULT_METADATA_MAX_BYTES: usize = 16 * 1024;
+// Budget de coût des endpoints coûteux par utilisateur et par fenêtre glissante
+const DEFAULT_COST_LIMIT_BUDGET: u32 = 120;
+const DEFAULT_COST_LIMIT_WINDOW_SECS: u64 = 60;
+// Cache des preflights CORS : API (1 h) et routes publiques (24 h)
+const DEFAULT_CORS_MAX_AGE_SECS: usize = 3600;
+const DEFAULT_CORS_PUBLIC_MAX_AGE_SECS: usize = 86400;
+// Délai sans signal de vie avant la clôture d'office d'un minuteur
+const DEFAULT_TIMER_HEARTBEAT_TIMEOUT_MINUTES: i64 = 10;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Missing(&'static str),
+    Invalid(&'static str, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Missing(name) => write!(f, "{} must be set", name),
+            ConfigError::Invalid(name, reason) => write!(f, "Invalid {}: {}", name, reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<ConfigError> for std::io::Error {
+    fn from(error: ConfigError) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, error.to_string())
+    }
+}
+
+// Valeur de la variable, None si absente ou vide
+fn read(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// Variable numérique (ou autre FromStr) ; `valid` décrit la plage acceptée dans `expected`
+fn parse<T: FromStr>(
+    name: &'static str,
+    default: T,
+    valid: impl Fn(&T) -> bool,
+    expected: &str,
+) -> Result<T, ConfigError> {
+    match read(name) {
+        None => Ok(default),
+        Some(raw) => raw
+            .parse::<T>()
+            .ok()
+            .filter(|value| valid(value))
+            .ok_or_else(|| ConfigError::Invalid(name, format!("'{}' is not {}", raw, expected))),
+    }
+}
+
+fn parse_uuid(name: &'static str, raw: &str) -> Result<Uuid, ConfigError> {
+    Uuid::parse_str(raw.trim())
+        .map_err(|_| ConfigError::Invalid(name, format!("'{}' is not a valid UUID", raw)))
+}
+
+fn parse_url(name: &'static str, raw: String) -> Result<String, ConfigError> {
+    reqwest::Url::parse(&raw)
+        .map_err(|e| ConfigError::Invalid(name, format!("'{}' is not a valid URL ({})", raw, e)))?;
+    Ok(raw)
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub pool_max_size: u32,
+    pub pool_min_idle: u32,
+}
+
+// Fournisseur LLM compatible OpenAI ; sans LLM_API_URL, le fournisseur hors-ligne est utilisé
+#[derive(Debug, Clone)]
+pub struct LlmConfig {
+    pub api_url: String,
+    pub api_key: Option<String>,
+    pub model: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub cors: CorsConfig,
+    pub admin: AdminConfig,
+    pub metadata: MetadataConfig,
+    pub cost_limits: CostLimitConfig,
+    pub demo: Option<DemoConfig>,
+    pub inbound_email: Option<InboundEmailConfig>,
+    pub google_calendar: Option<GoogleCalendarConfig>,
+    pub llm: Option<LlmConfig>,
+    // Collecteur des événements métier ; sans EVENT_SINK, aucun événement n'est émis
+    pub events: Option<EventSinkConfig>,
+    // Clé JSON du compte de service Firebase ; sans elle, pas de notifications push
+    pub fcm_service_account_file: Option<String>,
+    // Jeton Bearer exigé par GET /metrics ; sans METRICS_TOKEN, les métriques sont publiques
+    pub metrics_token: Option<String>,
+    // Minutes sans heartbeat avant qu'un minuteur en cours soit clos (TIMER_HEARTBEAT_TIMEOUT_MINUTES)
+    pub timer_heartbeat_timeout_minutes: i64,
+    // Mode lecture seule au démarrage (READ_ONLY_MODE, READ_ONLY_MESSAGE)
+    pub read_only: ReadOnlyConfig,
+    // Vérification du schéma de la base au démarrage (SCHEMA_CHECK, strict par défaut)
+    pub schema_check: SchemaCheckMode,
+}
+
+impl AppConfig {
+    pub fn from_env() -> Result<AppConfig, ConfigError> {
+        Ok(AppConfig {
+            server: server_from_env()?,
+            database: database_from_env()?,
+            cors: cors_from_env()?,
+            admin: admin_from_env()?,
+            metadata: MetadataConfig {
+                max_bytes: parse(
+                    "METADATA_MAX_BYTES",
+                    DEFAULT_METADATA_MAX_BYTES,
+                    |bytes| *bytes > 0,
+                    "a positive number of bytes",
+                )?,
+            },
+            cost_limits: CostLimitConfig {
+                budget: parse(
+                    "COST_LIMIT_BUDGET",
+                    DEFAULT_COST_LIMIT_BUDGET,
+                    |_| true,
+                    "a number of cost units (0 disables the limit)",
+                )?,
+                window_secs: parse(
+                    "COST_LIMIT_WINDOW_SECS",
+                    DEFAULT_COST_LIMIT_WINDOW_SECS,
+                    |secs| *secs > 0,
+                    "a positive number of seconds",
+                )?,
+            },
+            demo: demo_from_env()?,
+            inbound_email: inbound_email_from_env(),
+            google_calendar: google_calendar_from_env()?,
+            llm: read("LLM_API_URL")
+                .map(|api_url| -> Result<LlmConfig, ConfigError> {
+                    Ok(LlmConfig {
+                        api_url: parse_url("LLM_API_URL", api_url)?,
+                        api_key: read("LLM_API_KEY"),
+                        model: read("LLM_MODEL").unwrap_or_else(|| DEFAULT_LLM_MODEL.to_string()),
+                    })
+                })
+                .transpose()?,
+            events: events_from_env()?,
+            fcm_service_account_file: read("FCM_SERVICE_ACCOUNT_FILE"),
+            metrics_token: read("METRICS_TOKEN"),
+            timer_heartbeat_timeout_minutes: parse(
+                "TIMER_HEARTBEAT_TIMEOUT_MINUTES",
+                DEFAULT_TIMER_HEARTBEAT_TIMEOUT_MINUTES,
+                |minutes| (2..=1440).contains(minutes),
+                "a number of minutes between 2 and 1440",
+            )?,
+            read_only: ReadOnlyConfig {
+                enabled: parse(
+                    "READ_ONLY_MODE",
+                    false,
+                    |_| true,
+                    "a boolean (true or false)",
+                )?,
+                message: read("READ_ONLY_MESSAGE"),
+            },
+            schema_check: parse(
+                "SCHEMA_CHECK",
+                SchemaCheckMode::Strict,
+                |_| true,
+                "one of strict, warn, off",
+            )?,
+        })
+    }
+}
+
+fn cors_from_env() -> Result<CorsConfig, ConfigError> {
+    Ok(CorsConfig {
+        allowed_origins: vec![
+            parse_url(
+                "FRONTEND_URL_PROD",
+                read("FRONTEND_URL_PROD").unwrap_or_else(|| DEFAULT_FRONTEND_URL_PROD.to_string()),
+            )?,
+            parse_url(
+                "FRONTEND_URL_DEV",
+                read("FRONTEND_URL_DEV").unwrap_or_else(|| DEFAULT_FRONTEND_URL_DEV.to_string()),
+            )?,
+        ],
+        max_age_secs: parse(
+            "CORS_MAX_AGE_SECS",
+            DEFAULT_CORS_MAX_AGE_SECS,
+            |_| true,
+            "a number of seconds (0 disables preflight caching)",
+        )?,
+        public_max_age_secs: parse(
+            "CORS_PUBLIC_MAX_AGE_SECS",
+            DEFAULT_CORS_PUBLIC_MAX_AGE_SECS,
+            |_| true,
+            "a number of seconds (0 disables preflight caching)",
+        )?,
+    })
+}
+
+fn server_from_env() -> Result<ServerConfig, ConfigError> {
+    Ok(ServerConfig {
+        host: read("HOST").unwrap_or_else(|| DEFAULT_HOST.to_string()),
+        port: parse(
+            "PORT",
+            DEFAULT_PORT,
+            |port| *port > 0,
+            "a valid port number",
+        )?,
+    })
+}
+
+fn database_from_env() -> Result<DatabaseConfig, ConfigError> {
+    let url = read("DATABASE_URL").ok_or(ConfigError::Missing("DATABASE_URL"))?;
+    let pool_max_size = parse(
+        "DATABASE_POOL_MAX_SIZE",
+        DEFAULT_POOL_MAX_SIZE,
+        |size| *size > 0,
+        "a positive number of connections",
+    )?;
+    let pool_min_idle = parse(
+        "DATABASE_POOL_MIN_IDLE",
+        DEFAULT_POOL_MIN_IDLE.min(pool_max_size),
+        |idle| *idle <= pool_max_size,
+        "a number of connections no greater than DATABASE_POOL_MAX_SIZE",
+    )?;
+
+    Ok(DatabaseConfig {
+        url,
+        pool_max_size,
+        pool_min_idle,
+    })
+}
+
+// ADMIN_USER_IDS : identifiants séparés par des virgules ; vide, les routes /admin sont fermées
+fn admin_from_env() -> Result<AdminConfig, ConfigError> {
+    let user_ids = read("ADMIN_USER_IDS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .map(|raw| parse_uuid("ADMIN_USER_IDS", raw))
+        .collect::<Result<HashSet<Uuid>, ConfigError>>()?;
+
+    Ok(AdminConfig { user_ids })
+}
+
+// Le mode démo n'est actif que si DEMO_AUTH_TOKEN est défini
+fn demo_from_env() -> Result<Option<DemoConfig>, ConfigError> {
+    let Some(token) = read("DEMO_AUTH_TOKEN") else {
+        return Ok(None);
+    };
+    let user_id = match read("DEMO_USER_ID") {
+        Some(raw) => parse_uuid("DEMO_USER_ID", &raw)?,
+        None => DEFAULT_DEMO_USER_ID,
+    };
+    let reset_hour_utc = parse(
+        "DEMO_RESET_HOUR_UTC",
+        DEFAULT_DEMO_RESET_HOUR_UTC,
+        |hour| *hour < 24,
+        "an hour between 0 and 23",
+    )?;
+
+    Ok(Some(DemoConfig {
+        token,
+        user_id,
+        reset_hour_utc,
+    }))
+}
+
+// La capture par email n'est active que si INBOUND_EMAIL_WEBHOOK_SECRET est défini
+fn inbound_email_from_env() -> Option<InboundEmailConfig> {
+    let webhook_secret = read("INBOUND_EMAIL_WEBHOOK_SECRET")?;
+    let domain = read("INBOUND_EMAIL_DOMAIN").unwrap_or_else(|| DEFAULT_INBOUND_DOMAIN.to_string());
+
+    Some(InboundEmailConfig {
+        domain: domain.to_lowercase(),
+        webhook_secret,
+    })
+}
+
+// Les trois variables GOOGLE_CALENDAR_CLIENT_ID / _CLIENT_SECRET / _REDIRECT_URI vont ensemble
+fn google_calendar_from_env() -> Result<Option<GoogleCalendarConfig>, ConfigError> {
+    let client_id = read("GOOGLE_CALENDAR_CLIENT_ID");
+    let client_secret = read("GOOGLE_CALENDAR_CLIENT_SECRET");
+    let redirect_uri = read("GOOGLE_CALENDAR_REDIRECT_URI");
+
+    match (client_id, client_secret, redirect_uri) {
+        (Some(client_id), Some(client_secret), Some(redirect_uri)) => {
+            Ok(Some(GoogleCalendarConfig::new(
+                client_id,
+                client_secret,
+                parse_url("GOOGLE_CALENDAR_REDIRECT_URI", redirect_uri)?,
+                read("GOOGLE_CALENDAR_RETURN_URL")
+                    .map(|url| parse_url("GOOGLE_CALENDAR_RETURN_URL", url))
+                    .transpose()?,
+            )))
+        }
+        (None, None, None) => Ok(None),
+        (None, ..) => Err(ConfigError::Missing("GOOGLE_CALENDAR_CLIENT_ID")),
+        (_, None, _) => Err(ConfigError::Missing("GOOGLE_CALENDAR_CLIENT_SECRET")),
+        (_, _, None) => Err(ConfigError::Missing("GOOGLE_CALENDAR_REDIRECT_URI")),
+    }
+}
+
+// EVENT_SINK : stdout, http (EVENT_SINK_URL) ou kafka (proxy REST EVENT_SINK_URL et
+// EVENT_KAFKA_TOPIC) ; EVENT_SINK_TOKEN est envoyé en Bearer aux collecteurs HTTP
+fn events_from_env() -> Result<Option<EventSinkConfig>, ConfigError> {
+    let Some(sink) = read("EVENT_SINK") else {
+        return Ok(None);
+    };
+    let url = || -> Result<String, ConfigError> {
+        parse_url(
+            "EVENT_SINK_URL",
+            read("EVENT_SINK_URL").ok_or(ConfigError::Missing("EVENT_SINK_URL"))?,
+        )
+    };
+    let token = read("EVENT_SINK_TOKEN");
+
+    match sink.to_lowercase().as_str() {
+        "stdout" => Ok(Some(EventSinkConfig::Stdout)),
+        "http" => Ok(Some(EventSinkConfig::Http { url: url()?, token })),
+        "kafka" => Ok(Some(EventSinkConfig::KafkaRest {
+            url: url()?,
+            topic: read("EVENT_KAFKA_TOPIC").ok_or(ConfigError::Missing("EVENT_KAFKA_TOPIC"))?,
+            token,
+        })),
+        _ => Err(ConfigError::Invalid(
+            "EVENT_SINK",
+            format!("'{}' is not one of stdout, http, kafka", sink),
+        )),
+    }
+}
@@ -0,0 +1,111 @@
+// OptiTask/backend-api/src/confirmations.rs
+// Opérations destructives en deux temps : le premier appel renvoie un résumé et un
+// jeton de confirmation ; le second appel, avec ce jeton, exécute l'opération.
+use crate::error_handler::ServiceError;
+use crate::schema::confirmation_tokens;
+use actix_web::HttpResponse;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use uuid::Uuid;
+
+// Durée de validité d'un jeton de confirmation
+const CONFIRMATION_TTL_MINUTES: i64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DestructiveAction {
+    DeleteProjectWithTasks,
+    WipeAccount,
+}
+
+impl DestructiveAction {
+    fn code(self) -> &'static str {
+        match self {
+            DestructiveAction::DeleteProjectWithTasks => "project.delete_with_tasks",
+            DestructiveAction::WipeAccount => "account.wipe",
+        }
+    }
+}
+
+// Émet un jeton lié à l'utilisateur, à l'action et à sa cible
+async fn issue_token(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    action: DestructiveAction,
+    target: Option<Uuid>,
+) -> Result<(String, DateTime<Utc>), ServiceError> {
+    // Nettoyage opportuniste des jetons expirés de l'utilisateur
+    diesel::delete(
+        confirmation_tokens::table
+            .filter(confirmation_tokens::user_id.eq(user_uuid))
+            .filter(confirmation_tokens::expires_at.lt(Utc::now())),
+    )
+    .execute(conn)
+    .await?;
+
+    let token = Uuid::new_v4().simple().to_string();
+    let expires_at = Utc::now() + ChronoDuration::minutes(CONFIRMATION_TTL_MINUTES);
+
+    diesel::insert_into(confirmation_tokens::table)
+        .values((
+            confirmation_tokens::token.eq(&token),
+            confirmation_tokens::user_id.eq(user_uuid),
+            confirmation_tokens::action.eq(action.code()),
+            confirmation_tokens::target_id.eq(target),
+            confirmation_tokens::expires_at.eq(expires_at),
+        ))
+        .execute(conn)
+        .await?;
+
+    Ok((token, expires_at))
+}
+
+// Consomme le jeton (usage unique) ; erreur s'il est inconnu, expiré ou lié à une autre opération
+pub async fn consume_token(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    action: DestructiveAction,
+    target: Option<Uuid>,
+    token: &str,
+) -> Result<(), ServiceError> {
+    let num_deleted = diesel::delete(
+        confirmation_tokens::table
+            .filter(confirmation_tokens::token.eq(token))
+            .filter(confirmation_tokens::user_id.eq(user_uuid))
+            .filter(confirmation_tokens::action.eq(action.code()))
+            .filter(confirmation_tokens::target_id.is_not_distinct_from(target))
+            .filter(confirmation_tokens::expires_at.ge(Utc::now())),
+    )
+    .execute(conn)
+    .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::BadRequest(
+            "Invalid or expired confirmation token. Repeat the request without a token to get a new one."
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// Premier appel : 409 avec le résumé de ce qui sera supprimé et le jeton à renvoyer
+pub async fn confirmation_required(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    action: DestructiveAction,
+    target: Option<Uuid>,
+    summary: serde_json::Value,
+) -> Result<HttpResponse, ServiceError> {
+    let (token, expires_at) = issue_token(conn, user_uuid, action, target).await?;
+
+    Ok(HttpResponse::Conflict().json(json!({
+        "status": "error",
+        "code": 409,
+        "message": "This operation is destructive. Repeat the request with confirmation_token to proceed.",
+        "action": action.code(),
+        "summary": summary,
+        "confirmation_token": token,
+        "expires_at": expires_at
+    })))
+}
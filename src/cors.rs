@@ -0,0 +1,56 @@
+// OptiTask/backend-api/src/cors.rs
+// Politiques CORS par groupe de routes. L'API de l'application n'accepte que les origines
+// du frontend, avec cookies/identifiants ; les routes publiques (badges, santé) sont lisibles
+// depuis n'importe quelle origine, sans identifiants, et leur preflight est mis en cache
+// plus longtemps.
+use crate::pagination;
+use crate::quotas;
+use actix_cors::Cors;
+use actix_web::http::header;
+
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    // Origines autorisées pour l'API (FRONTEND_URL_PROD, FRONTEND_URL_DEV)
+    pub allowed_origins: Vec<String>,
+    // Durée de cache des preflights de l'API (CORS_MAX_AGE_SECS)
+    pub max_age_secs: usize,
+    // Durée de cache des preflights des routes publiques (CORS_PUBLIC_MAX_AGE_SECS)
+    pub public_max_age_secs: usize,
+}
+
+// API authentifiée utilisée par le frontend
+pub fn api_policy(config: &CorsConfig) -> Cors {
+    let exposed_headers: Vec<String> = quotas::exposed_headers()
+        .into_iter()
+        .chain(
+            pagination::EXPOSED_HEADERS
+                .iter()
+                .map(|name| name.to_string()),
+        )
+        .collect();
+
+    config
+        .allowed_origins
+        .iter()
+        .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
+        .allowed_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"])
+        .allowed_headers(vec![
+            header::AUTHORIZATION,
+            header::ACCEPT,
+            header::CONTENT_TYPE,
+            header::HeaderName::from_static("x-demo-token"),
+        ])
+        .expose_headers(exposed_headers)
+        .supports_credentials()
+        .max_age(config.max_age_secs)
+}
+
+// Contenus publics intégrés ailleurs (README, wikis, pages de statut) : lecture seule
+pub fn public_policy(config: &CorsConfig) -> Cors {
+    Cors::default()
+        .allow_any_origin()
+        .send_wildcard()
+        .allowed_methods(vec!["GET", "HEAD", "OPTIONS"])
+        .allowed_headers(vec![header::ACCEPT])
+        .max_age(config.public_max_age_secs)
+}
@@ -0,0 +1,131 @@
+// OptiTask/backend-api/src/cost_limits.rs
+// Limite souple des endpoints coûteux (recherche, analytics, exports) : chaque appel
+// consomme un poids, et la dépense de l'utilisateur sur une fenêtre glissante ne peut
+// dépasser le budget. Au-delà : 429 COST_LIMIT_EXCEEDED avec le délai avant de réessayer.
+// Les appels CRUD ordinaires ne sont pas décomptés. Comptabilité en mémoire, par instance.
+use crate::error_handler::ServiceError;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+// Au-delà de ce nombre d'utilisateurs suivis, les fenêtres expirées sont purgées
+const MAX_TRACKED_USERS: usize = 10_000;
+
+// COST_LIMIT_BUDGET / COST_LIMIT_WINDOW_SECS (voir config.rs) ; budget 0 = désactivé
+#[derive(Debug, Clone)]
+pub struct CostLimitConfig {
+    pub budget: u32,
+    pub window_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CostClass {
+    // Recherche plein texte ou filtres JSON sur les tâches
+    Search,
+    // Agrégats de temps suivi et de tâches
+    Analytics,
+    // Instantané d'analytics enregistré
+    AnalyticsSnapshot,
+    // Appel au fournisseur LLM
+    AiSummary,
+    // Export de rapport (CSV, PDF...)
+    Export,
+    // Rendu d'un badge public, décompté par badge (voir badges.rs)
+    PublicBadge,
+}
+
+impl CostClass {
+    pub fn weight(self) -> u32 {
+        match self {
+            CostClass::PublicBadge => 1,
+            CostClass::Search => 2,
+            CostClass::Analytics => 3,
+            CostClass::AnalyticsSnapshot => 5,
+            CostClass::Export => 10,
+            CostClass::AiSummary => 10,
+        }
+    }
+}
+
+pub struct CostLimiter {
+    config: CostLimitConfig,
+    // Dépenses récentes par utilisateur, de la plus ancienne à la plus récente
+    spend: Mutex<HashMap<Uuid, VecDeque<(Instant, u32)>>>,
+}
+
+impl CostLimiter {
+    pub fn new(config: CostLimitConfig) -> CostLimiter {
+        CostLimiter {
+            config,
+            spend: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn window(&self) -> Duration {
+        Duration::from_secs(self.config.window_secs)
+    }
+
+    // Enregistre l'appel s'il tient dans le budget, sinon indique quand réessayer
+    pub fn charge(&self, user_uuid: Uuid, class: CostClass) -> Result<(), ServiceError> {
+        let budget = self.config.budget;
+        if budget == 0 {
+            return Ok(());
+        }
+        // Un appel plus lourd que le budget entier reste possible sur une fenêtre vide
+        let weight = class.weight().min(budget);
+        let now = Instant::now();
+        let window = self.window();
+
+        let mut spend = self.spend.lock().unwrap_or_else(|e| e.into_inner());
+        if spend.len() > MAX_TRACKED_USERS {
+            spend.retain(|_, charges| {
+                charges
+                    .back()
+                    .is_some_and(|(at, _)| now.duration_since(*at) < window)
+            });
+        }
+
+        let charges = spend.entry(user_uuid).or_default();
+        while charges
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) >= window)
+        {
+            charges.pop_front();
+        }
+
+        let spent: u32 = charges.iter().map(|(_, cost)| cost).sum();
+        if spent + weight <= budget {
+            charges.push_back((now, weight));
+            return Ok(());
+        }
+
+        // Attendre que les plus anciennes dépenses sortent de la fenêtre
+        let mut remaining = spent + weight - budget;
+        let mut retry_after = window;
+        for (at, cost) in charges.iter() {
+            retry_after = window.saturating_sub(now.duration_since(*at));
+            remaining = remaining.saturating_sub(*cost);
+            if remaining == 0 {
+                break;
+            }
+        }
+        let retry_after_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+
+        log::info!(
+            "Cost limit reached for user {} ({:?}, spent {}/{} over {}s)",
+            user_uuid,
+            class,
+            spent,
+            budget,
+            self.config.window_secs
+        );
+        Err(ServiceError::CostLimitExceeded(
+            format!(
+                "Too many expensive requests: {} of {} cost units used in the last {} seconds",
+                spent, budget, self.config.window_secs
+            ),
+            retry_after_secs.max(1),
+        ))
+    }
+}
@@ -0,0 +1,163 @@
+// OptiTask/backend-api/src/custom_fields.rs
+use crate::error_handler::ServiceError;
+use crate::ids::TaskId;
+use crate::models::{CustomFieldDefinition, TaskApiResponse, TaskCustomFieldValue};
+use crate::schema::{custom_field_definitions, task_custom_values};
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const CUSTOM_FIELD_TYPES: [&str; 4] = ["text", "number", "date", "select"];
+const MAX_TEXT_VALUE_LENGTH: usize = 2000;
+
+pub fn validate_field_type(field_type: &str) -> Result<(), ServiceError> {
+    if CUSTOM_FIELD_TYPES.contains(&field_type) {
+        Ok(())
+    } else {
+        Err(ServiceError::ValidationError(format!(
+            "Invalid custom field type: {}. Supported: {}",
+            field_type,
+            CUSTOM_FIELD_TYPES.join(", ")
+        )))
+    }
+}
+
+// Les options ne concernent que les champs "select", qui en exigent au moins une
+pub fn build_options(field_type: &str, options: Option<&[String]>) -> Result<Value, ServiceError> {
+    let cleaned: Vec<String> = options
+        .unwrap_or_default()
+        .iter()
+        .map(|option| option.trim().to_string())
+        .filter(|option| !option.is_empty())
+        .collect();
+
+    match field_type {
+        "select" if cleaned.is_empty() => Err(ServiceError::ValidationError(
+            "A select field requires at least one option".to_string(),
+        )),
+        "select" => Ok(Value::from(cleaned)),
+        _ if !cleaned.is_empty() => Err(ServiceError::ValidationError(format!(
+            "Options are only supported for select fields, not {}",
+            field_type
+        ))),
+        _ => Ok(Value::Array(Vec::new())),
+    }
+}
+
+// Convertit une valeur JSON en forme canonique texte stockée en base
+pub fn normalize_value(
+    definition: &CustomFieldDefinition,
+    raw: &Value,
+) -> Result<String, ServiceError> {
+    let invalid = || {
+        ServiceError::ValidationError(format!(
+            "Invalid value for {} field '{}'",
+            definition.field_type, definition.name
+        ))
+    };
+
+    match definition.field_type.as_str() {
+        "number" => {
+            let number = match raw {
+                Value::Number(n) => n.as_f64(),
+                Value::String(s) => s.trim().parse::<f64>().ok(),
+                _ => None,
+            }
+            .filter(|n| n.is_finite())
+            .ok_or_else(invalid)?;
+            Ok(number.to_string())
+        }
+        "date" => {
+            let text = raw.as_str().ok_or_else(invalid)?;
+            let date = NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").map_err(|_| invalid())?;
+            Ok(date.format("%Y-%m-%d").to_string())
+        }
+        "select" => {
+            let text = raw.as_str().ok_or_else(invalid)?.trim();
+            let allowed = definition
+                .options
+                .as_array()
+                .map(|options| options.iter().any(|option| option.as_str() == Some(text)))
+                .unwrap_or(false);
+            if allowed {
+                Ok(text.to_string())
+            } else {
+                Err(ServiceError::ValidationError(format!(
+                    "'{}' is not an option of field '{}'",
+                    text, definition.name
+                )))
+            }
+        }
+        _ => {
+            let text = raw.as_str().ok_or_else(invalid)?;
+            if text.chars().count() > MAX_TEXT_VALUE_LENGTH {
+                return Err(ServiceError::ValidationError(format!(
+                    "Value for field '{}' exceeds {} characters",
+                    definition.name, MAX_TEXT_VALUE_LENGTH
+                )));
+            }
+            Ok(text.to_string())
+        }
+    }
+}
+
+// Retype une valeur stockée selon le type du champ pour la réponse JSON
+pub fn value_to_json(field_type: &str, stored: &str) -> Value {
+    if field_type == "number" {
+        if let Some(number) = stored
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+        {
+            return Value::Number(number);
+        }
+    }
+    Value::String(stored.to_string())
+}
+
+// Peuple `custom_fields` pour un lot de tâches en une seule requête
+pub async fn attach_custom_fields(
+    conn: &mut AsyncPgConnection,
+    task_responses: &mut [TaskApiResponse],
+) -> Result<(), ServiceError> {
+    if task_responses.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<TaskId> = task_responses.iter().map(|t| t.id).collect();
+    let rows = task_custom_values::table
+        .inner_join(custom_field_definitions::table)
+        .filter(task_custom_values::task_id.eq_any(&task_ids))
+        .order(custom_field_definitions::name.asc())
+        .select((
+            task_custom_values::task_id,
+            task_custom_values::field_id,
+            custom_field_definitions::name,
+            custom_field_definitions::field_type,
+            task_custom_values::value,
+        ))
+        .load::<(TaskId, Uuid, String, String, String)>(conn)
+        .await?;
+
+    let mut values_by_task: HashMap<TaskId, Vec<TaskCustomFieldValue>> = HashMap::new();
+    for (task_uuid, field_uuid, name, field_type, stored) in rows {
+        values_by_task
+            .entry(task_uuid)
+            .or_default()
+            .push(TaskCustomFieldValue {
+                field_id: field_uuid,
+                value: value_to_json(&field_type, &stored),
+                name,
+                field_type,
+            });
+    }
+
+    for task_response in task_responses.iter_mut() {
+        task_response.custom_fields = values_by_task.remove(&task_response.id).unwrap_or_default();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+// OptiTask/backend-api/src/date_validation.rs
+// Cohérence des dates saisies : échéances plausibles, entrées de temps ordonnées, d'au plus
+// 24h et non datées dans le futur. Les refus sont des 422 portant un code stable et le
+// champ en cause, pour que les clients puissent les afficher à côté du bon champ.
+use crate::error_handler::ServiceError;
+use chrono::{DateTime, Duration, Months, NaiveDate, Utc};
+
+pub const DUE_DATE_OUT_OF_RANGE: &str = "DUE_DATE_OUT_OF_RANGE";
+pub const END_BEFORE_START: &str = "END_BEFORE_START";
+pub const DURATION_TOO_LONG: &str = "DURATION_TOO_LONG";
+pub const NEGATIVE_DURATION: &str = "NEGATIVE_DURATION";
+pub const ENTRY_IN_FUTURE: &str = "ENTRY_IN_FUTURE";
+
+// Écart maximal entre une échéance et aujourd'hui, dans les deux sens
+const MAX_DUE_DATE_YEARS: u32 = 10;
+const MAX_ENTRY_DURATION_SECS: i64 = 24 * 3600;
+// Avance tolérée sur l'horloge du serveur (latence, petites dérives)
+const FUTURE_TOLERANCE_SECS: i64 = 300;
+
+pub fn validate_due_date(due_date: NaiveDate, today: NaiveDate) -> Result<(), ServiceError> {
+    let span = Months::new(MAX_DUE_DATE_YEARS * 12);
+    let earliest = today.checked_sub_months(span).unwrap_or(NaiveDate::MIN);
+    let latest = today.checked_add_months(span).unwrap_or(NaiveDate::MAX);
+    if due_date < earliest || due_date > latest {
+        return Err(ServiceError::UnprocessableEntity(
+            DUE_DATE_OUT_OF_RANGE,
+            "due_date",
+            format!(
+                "due_date must be within {} years of today ({} to {})",
+                MAX_DUE_DATE_YEARS, earliest, latest
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// Entrée de temps telle qu'elle sera enregistrée (après fusion d'une mise à jour partielle).
+// `duration_seconds` explicite est contrôlé en plus de l'intervalle start/end.
+pub fn validate_time_range(
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    duration_seconds: Option<i32>,
+) -> Result<(), ServiceError> {
+    if let Some(end) = end {
+        if end < start {
+            return Err(ServiceError::UnprocessableEntity(
+                END_BEFORE_START,
+                "end_time",
+                "end_time cannot be before start_time".to_string(),
+            ));
+        }
+        if (end - start).num_seconds() > MAX_ENTRY_DURATION_SECS {
+            return Err(duration_too_long("end_time"));
+        }
+    }
+
+    match duration_seconds {
+        Some(seconds) if seconds < 0 => Err(ServiceError::UnprocessableEntity(
+            NEGATIVE_DURATION,
+            "duration_seconds",
+            "duration_seconds cannot be negative".to_string(),
+        )),
+        Some(seconds) if i64::from(seconds) > MAX_ENTRY_DURATION_SECS => {
+            Err(duration_too_long("duration_seconds"))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Une entrée ne peut ni commencer ni finir après `now` (à la tolérance près)
+pub fn validate_not_in_future(
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<(), ServiceError> {
+    let latest_allowed = now + Duration::seconds(FUTURE_TOLERANCE_SECS);
+    let (field, value) = match end {
+        Some(end) if end > latest_allowed => ("end_time", end),
+        _ => ("start_time", start),
+    };
+    if value > latest_allowed {
+        return Err(ServiceError::UnprocessableEntity(
+            ENTRY_IN_FUTURE,
+            field,
+            format!("{} cannot be in the future", field),
+        ));
+    }
+    Ok(())
+}
+
+fn duration_too_long(field: &'static str) -> ServiceError {
+    ServiceError::UnprocessableEntity(
+        DURATION_TOO_LONG,
+        field,
+        format!(
+            "A time entry cannot last more than {} hours",
+            MAX_ENTRY_DURATION_SECS / 3600
+        ),
+    )
+}
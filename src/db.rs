@@ -1,20 +1,24 @@
 // OptiTask/backend-api/src/db.rs
-use diesel_async::pooled_connection::bb8::Pool;
+use crate::config::DatabaseConfig;
+use diesel_async::pooled_connection::bb8::{Pool, PooledConnection};
 use diesel_async::pooled_connection::AsyncDieselConnectionManager;
 use diesel_async::AsyncPgConnection;
 use std::time::Duration;
 
 // Type alias pour notre pool
 pub type DbPool = Pool<AsyncPgConnection>;
+pub type DbConnection<'a> = PooledConnection<'a, AsyncPgConnection>;
 
-pub async fn create_pool(database_url: &str) -> Result<DbPool, Box<dyn std::error::Error>> {
+pub async fn create_pool(
+    database_config: &DatabaseConfig,
+) -> Result<DbPool, Box<dyn std::error::Error>> {
     // Configuration du gestionnaire de connexions
-    let config = AsyncDieselConnectionManager::<AsyncPgConnection>::new(database_url);
+    let config = AsyncDieselConnectionManager::<AsyncPgConnection>::new(&database_config.url);
 
     // Configuration du pool BB8
     let pool = Pool::builder()
-        .max_size(15) // Nombre maximum de connexions
-        .min_idle(Some(5)) // Nombre minimum de connexions inactives
+        .max_size(database_config.pool_max_size) // DATABASE_POOL_MAX_SIZE
+        .min_idle(Some(database_config.pool_min_idle)) // DATABASE_POOL_MIN_IDLE
         .max_lifetime(Some(Duration::from_secs(30 * 60))) // 30 minutes
         .idle_timeout(Some(Duration::from_secs(10 * 60))) // 10 minutes
         .connection_timeout(Duration::from_secs(30)) // 30 secondes pour obtenir une connexion
@@ -0,0 +1,125 @@
+// OptiTask/backend-api/src/demo.rs
+use crate::account;
+use crate::auth_utils::{constant_time_eq, AuthenticatedUser};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::onboarding;
+use crate::read_only_mode::ReadOnlyMode;
+use actix_web::{web, HttpRequest};
+use chrono::{Duration as ChronoDuration, Utc};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const DEMO_TOKEN_HEADER: &str = "X-Demo-Token";
+
+// Mode démo, actif si DEMO_AUTH_TOKEN est défini (voir config.rs)
+#[derive(Debug, Clone)]
+pub struct DemoConfig {
+    pub token: String,
+    pub user_id: Uuid,
+    // Heure UTC (0-23) à laquelle le jeu de données de démo est réinitialisé
+    pub reset_hour_utc: u32,
+}
+
+// Résout l'utilisateur de démo à partir du header X-Demo-Token.
+// None si le header est absent ; Err si le mode démo est désactivé ou le token invalide.
+pub fn authenticate(req: &HttpRequest) -> Option<Result<AuthenticatedUser, &'static str>> {
+    let header_value = req.headers().get(DEMO_TOKEN_HEADER)?;
+
+    let demo_config = req
+        .app_data::<web::Data<Option<DemoConfig>>>()
+        .and_then(|data| data.get_ref().clone());
+
+    let result = match (demo_config, header_value.to_str()) {
+        (Some(config), Ok(token)) if constant_time_eq(&config.token, token) => {
+            Ok(AuthenticatedUser { id: config.user_id })
+        }
+        (None, _) => Err("Demo mode is not enabled."),
+        _ => Err("Invalid demo token."),
+    };
+
+    Some(result)
+}
+
+// Efface puis recrée le jeu de données de démo dans une seule transaction
+pub async fn reset_demo_data(pool: &DbPool, demo_user_id: Uuid) -> Result<(), ServiceError> {
+    let mut conn = pool.get().await?;
+
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            account::wipe_user_data(conn, demo_user_id).await?;
+            onboarding::seed_sample_data(conn, demo_user_id).await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+// Durée jusqu'à la prochaine occurrence de l'heure de reset
+fn duration_until_next_reset(reset_hour_utc: u32) -> std::time::Duration {
+    let now = Utc::now();
+    let today_reset = now
+        .date_naive()
+        .and_hms_opt(reset_hour_utc, 0, 0)
+        .unwrap()
+        .and_utc();
+    let next_reset = if today_reset > now {
+        today_reset
+    } else {
+        today_reset + ChronoDuration::days(1)
+    };
+
+    (next_reset - now)
+        .to_std()
+        .unwrap_or(std::time::Duration::from_secs(60))
+}
+
+// Lance la tâche de fond : seed initial au démarrage, puis reset quotidien
+pub fn spawn_reset_job(pool: DbPool, read_only: Arc<ReadOnlyMode>, config: DemoConfig) {
+    actix_web::rt::spawn(async move {
+        if read_only.is_enabled() {
+            log::warn!("Demo mode: initial seed skipped (read-only mode)");
+        } else {
+            let mut conn = match pool.get().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    log::error!(
+                        "Demo mode: could not get a connection for initial seed: {:?}",
+                        e
+                    );
+                    return;
+                }
+            };
+            if let Err(e) = conn
+                .transaction::<_, ServiceError, _>(|conn| {
+                    async move { onboarding::seed_sample_data(conn, config.user_id).await }
+                        .scope_boxed()
+                })
+                .await
+            {
+                log::error!("Demo mode: initial seed failed: {}", e);
+            }
+        }
+
+        loop {
+            let wait = duration_until_next_reset(config.reset_hour_utc);
+            log::info!(
+                "Demo mode: next dataset reset in {} seconds",
+                wait.as_secs()
+            );
+            actix_web::rt::time::sleep(wait).await;
+            if read_only.is_enabled() {
+                log::warn!("Demo mode: dataset reset skipped (read-only mode)");
+                continue;
+            }
+
+            match reset_demo_data(&pool, config.user_id).await {
+                Ok(()) => log::info!("Demo mode: dataset reset for user {}", config.user_id),
+                Err(e) => log::error!("Demo mode: dataset reset failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,53 @@
+// OptiTask/backend-api/src/dry_run.rs
+// Simulation des opérations groupées (?dry_run=true) : imports, changements groupés, report
+// de planification, fusions. L'opération s'exécute réellement dans une transaction, avec
+// toutes ses validations et les contraintes de la base, puis la transaction est annulée au
+// lieu d'être validée : la réponse décrit exactement ce qui aurait été appliqué. Les effets
+// hors base (événements, synchronisation d'agenda) restent à la charge de l'appelant, qui
+// ne les déclenche que hors simulation.
+use crate::error_handler::ServiceError;
+use diesel_async::scoped_futures::ScopedBoxFuture;
+use diesel_async::{AsyncConnection, AsyncPgConnection, TransactionManager};
+use serde::Deserialize;
+
+type PgTransactionManager = <AsyncPgConnection as AsyncConnection>::TransactionManager;
+
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+pub struct DryRunQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// Comme AsyncConnection::transaction, mais annule une transaction réussie si `dry_run`
+pub async fn transaction<'a, R, F>(
+    conn: &mut AsyncPgConnection,
+    dry_run: bool,
+    callback: F,
+) -> Result<R, ServiceError>
+where
+    F: for<'r> FnOnce(
+            &'r mut AsyncPgConnection,
+        ) -> ScopedBoxFuture<'a, 'r, Result<R, ServiceError>>
+        + Send
+        + 'a,
+    R: Send + 'a,
+{
+    PgTransactionManager::begin_transaction(conn).await?;
+    match callback(&mut *conn).await {
+        Ok(value) if dry_run => {
+            PgTransactionManager::rollback_transaction(conn).await?;
+            Ok(value)
+        }
+        Ok(value) => {
+            PgTransactionManager::commit_transaction(conn).await?;
+            Ok(value)
+        }
+        Err(user_error) => {
+            // L'erreur d'origine prime sur un éventuel échec de l'annulation
+            if let Err(rollback_error) = PgTransactionManager::rollback_transaction(conn).await {
+                log::error!("Rollback after failed operation failed: {}", rollback_error);
+            }
+            Err(user_error)
+        }
+    }
+}
@@ -1,5 +1,6 @@
 // OptiTask/backend-api/src/error_handler.rs
-use actix_web::http::StatusCode;
+use crate::i18n;
+use actix_web::http::{header, StatusCode};
 use actix_web::{HttpResponse, ResponseError};
 use serde_json::json;
 use std::fmt;
@@ -8,6 +9,7 @@ use std::fmt;
 use diesel_async::pooled_connection::{bb8, PoolError};
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum ServiceError {
     InternalServerError(String),
     BadRequest(String),
@@ -17,12 +19,24 @@ pub enum ServiceError {
     PoolError(String),
     ValidationError(String),
     ConflictError(String),
+    // Conflit identifié par un code stable (ex: "WIP_LIMIT_EXCEEDED")
+    CodedConflict(&'static str, String),
+    // Valeur bien formée mais incohérente : (code stable, champ concerné, message)
+    UnprocessableEntity(&'static str, &'static str, String),
+    // Code de la permission manquante (ex: "project.write")
+    Forbidden(String),
+    // Quota d'appels dépassé (ex: envoi de feedback)
+    TooManyRequests(String),
+    // Budget de coût des endpoints coûteux dépassé ; secondes avant de réessayer
+    CostLimitExceeded(String, u64),
+    // Mode lecture seule global (maintenance) ; message facultatif fourni par l'administrateur
+    ReadOnlyMode(Option<String>),
 }
 
 impl ServiceError {
     fn from_pool_error(error: PoolError) -> ServiceError {
         log::error!("Database pool error: {}", error);
-        ServiceError::PoolError("Database connection pool error.".to_string())
+        ServiceError::PoolError(i18n::t("error.pool").to_string())
     }
 }
 
@@ -30,15 +44,15 @@ impl From<diesel::result::Error> for ServiceError {
     fn from(error: diesel::result::Error) -> ServiceError {
         match error {
             diesel::result::Error::NotFound => {
-                ServiceError::NotFound("The requested item was not found".to_string())
+                ServiceError::NotFound(i18n::t("error.not_found").to_string())
             }
             diesel::result::Error::DatabaseError(kind, info) => {
                 log::error!("Database error: {:?} - {}", kind, info.message());
-                ServiceError::DatabaseError("A database error occurred".to_string())
+                ServiceError::DatabaseError(i18n::t("error.database").to_string())
             }
             _ => {
                 log::error!("Database operation error: {}", error);
-                ServiceError::DatabaseError(format!("Database operation failed: {}", error))
+                ServiceError::DatabaseError(i18n::tf("error.database_operation", &[&error]))
             }
         }
     }
@@ -49,7 +63,7 @@ impl From<bb8::RunError> for ServiceError {
         match error {
             bb8::RunError::User(pool_error) => ServiceError::from(pool_error),
             bb8::RunError::TimedOut => {
-                ServiceError::PoolError("Database connection timed out".to_string())
+                ServiceError::PoolError(i18n::t("error.pool_timeout").to_string())
             }
         }
     }
@@ -66,7 +80,7 @@ impl From<PoolError> for ServiceError {
 impl From<serde_json::Error> for ServiceError {
     fn from(error: serde_json::Error) -> ServiceError {
         log::error!("JSON serialization/deserialization error: {}", error);
-        ServiceError::BadRequest("Invalid JSON format.".to_string())
+        ServiceError::BadRequest(i18n::t("error.invalid_json").to_string())
     }
 }
 
@@ -74,7 +88,7 @@ impl From<serde_json::Error> for ServiceError {
 impl From<uuid::Error> for ServiceError {
     fn from(error: uuid::Error) -> ServiceError {
         log::error!("UUID parsing error: {}", error);
-        ServiceError::BadRequest("Invalid UUID format.".to_string())
+        ServiceError::BadRequest(i18n::t("error.invalid_uuid").to_string())
     }
 }
 
@@ -82,7 +96,15 @@ impl From<uuid::Error> for ServiceError {
 impl From<std::num::ParseIntError> for ServiceError {
     fn from(error: std::num::ParseIntError) -> ServiceError {
         log::error!("Number parsing error: {}", error);
-        ServiceError::BadRequest("Invalid number format.".to_string())
+        ServiceError::BadRequest(i18n::t("error.invalid_number").to_string())
+    }
+}
+
+// Ajout pour les erreurs d'upload multipart
+impl From<actix_multipart::MultipartError> for ServiceError {
+    fn from(error: actix_multipart::MultipartError) -> ServiceError {
+        log::error!("Multipart payload error: {}", error);
+        ServiceError::BadRequest(i18n::t("error.invalid_multipart").to_string())
     }
 }
 
@@ -97,6 +119,24 @@ impl fmt::Display for ServiceError {
             ServiceError::PoolError(msg) => write!(f, "Pool Error: {}", msg),
             ServiceError::ValidationError(msg) => write!(f, "Validation Error: {}", msg),
             ServiceError::ConflictError(msg) => write!(f, "Conflict Error: {}", msg),
+            ServiceError::CodedConflict(code, msg) => {
+                write!(f, "Conflict Error ({}): {}", code, msg)
+            }
+            ServiceError::UnprocessableEntity(code, field, msg) => {
+                write!(f, "Unprocessable Entity ({}, {}): {}", code, field, msg)
+            }
+            ServiceError::Forbidden(permission) => {
+                write!(f, "Forbidden: missing permission {}", permission)
+            }
+            ServiceError::TooManyRequests(msg) => write!(f, "Too Many Requests: {}", msg),
+            ServiceError::CostLimitExceeded(msg, retry_after_secs) => {
+                write!(
+                    f,
+                    "Too Many Requests: {} (retry after {}s)",
+                    msg, retry_after_secs
+                )
+            }
+            ServiceError::ReadOnlyMode(_) => write!(f, "Service Unavailable: read-only mode"),
         }
     }
 }
@@ -112,6 +152,12 @@ impl ResponseError for ServiceError {
             ServiceError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             ServiceError::NotFound(_) => StatusCode::NOT_FOUND,
             ServiceError::ConflictError(_) => StatusCode::CONFLICT,
+            ServiceError::CodedConflict(_, _) => StatusCode::CONFLICT,
+            ServiceError::UnprocessableEntity(_, _, _) => StatusCode::UNPROCESSABLE_ENTITY,
+            ServiceError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ServiceError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            ServiceError::CostLimitExceeded(_, _) => StatusCode::TOO_MANY_REQUESTS,
+            ServiceError::ReadOnlyMode(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
@@ -123,9 +169,7 @@ impl ResponseError for ServiceError {
             // Pour les erreurs serveur, on envoie un message générique
             ServiceError::InternalServerError(_)
             | ServiceError::DatabaseError(_)
-            | ServiceError::PoolError(_) => {
-                "An internal server error occurred. Please try again later.".to_string()
-            }
+            | ServiceError::PoolError(_) => i18n::t("error.internal").to_string(),
             // Pour les erreurs client, on peut être plus spécifique
             _ => match self {
                 ServiceError::BadRequest(msg) => msg.clone(),
@@ -133,24 +177,55 @@ impl ResponseError for ServiceError {
                 ServiceError::Unauthorized(msg) => msg.clone(),
                 ServiceError::NotFound(msg) => msg.clone(),
                 ServiceError::ConflictError(msg) => msg.clone(),
-                _ => "An error occurred.".to_string(),
+                ServiceError::CodedConflict(_, msg) => msg.clone(),
+                ServiceError::UnprocessableEntity(_, _, msg) => msg.clone(),
+                ServiceError::TooManyRequests(msg) => msg.clone(),
+                ServiceError::CostLimitExceeded(msg, _) => msg.clone(),
+                ServiceError::ReadOnlyMode(message) => message
+                    .clone()
+                    .unwrap_or_else(|| i18n::t("error.read_only_mode").to_string()),
+                ServiceError::Forbidden(permission) => {
+                    i18n::tf("error.missing_permission", &[permission])
+                }
+                _ => i18n::t("error.generic").to_string(),
             },
         };
 
-        // Logging approprié selon le type d'erreur
-        if status_code.is_server_error() {
+        // Logging approprié selon le type d'erreur (le refus d'écriture en lecture seule est
+        // attendu pendant la maintenance)
+        if let ServiceError::ReadOnlyMode(_) = self {
+            log::debug!("Write rejected: read-only mode");
+        } else if status_code.is_server_error() {
             log::error!("Server error ({}): {}", status_code, self);
         } else if status_code.is_client_error() {
             log::warn!("Client error ({}): {}", status_code, self);
         }
 
-        // Construction de la réponse JSON
+        // Construction de la réponse JSON (message dans la langue négociée, voir i18n.rs)
         let mut response_body = json!({
             "status": "error",
             "code": status_code.as_u16(),
             "message": user_message
         });
 
+        if let ServiceError::Forbidden(permission) = self {
+            response_body["missing_permission"] = json!(permission);
+        }
+        if let ServiceError::CodedConflict(code, _) = self {
+            response_body["error_code"] = json!(code);
+        }
+        if let ServiceError::UnprocessableEntity(code, field, _) = self {
+            response_body["error_code"] = json!(code);
+            response_body["field"] = json!(field);
+        }
+        if let ServiceError::CostLimitExceeded(_, retry_after_secs) = self {
+            response_body["error_code"] = json!("COST_LIMIT_EXCEEDED");
+            response_body["retry_after_seconds"] = json!(retry_after_secs);
+        }
+        if let ServiceError::ReadOnlyMode(_) = self {
+            response_body["error_code"] = json!("READ_ONLY_MODE");
+        }
+
         // En mode debug, on peut ajouter plus de détails
         #[cfg(debug_assertions)]
         {
@@ -160,11 +235,16 @@ impl ResponseError for ServiceError {
             });
         }
 
-        HttpResponse::build(status_code).json(response_body)
+        let mut response = HttpResponse::build(status_code);
+        if let ServiceError::CostLimitExceeded(_, retry_after_secs) = self {
+            response.insert_header((header::RETRY_AFTER, retry_after_secs.to_string()));
+        }
+        response.json(response_body)
     }
 }
 
 // Fonctions utilitaires pour créer des erreurs communes
+#[allow(dead_code)]
 impl ServiceError {
     pub fn bad_request<T: Into<String>>(msg: T) -> Self {
         ServiceError::BadRequest(msg.into())
@@ -189,4 +269,8 @@ impl ServiceError {
     pub fn conflict<T: Into<String>>(msg: T) -> Self {
         ServiceError::ConflictError(msg.into())
     }
+
+    pub fn forbidden<T: Into<String>>(permission: T) -> Self {
+        ServiceError::Forbidden(permission.into())
+    }
 }
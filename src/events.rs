@@ -0,0 +1,250 @@
+// OptiTask/backend-api/src/events.rs
+// Événements métier (task_created, timer_stopped...) pour le pipeline d'analytics produit.
+// Les handlers publient sur un bus central ; une tâche de fond les regroupe par lots et les
+// envoie au collecteur configuré (EVENT_SINK) : NDJSON sur stdout, collecteur HTTP, ou
+// Kafka via son proxy REST. Publication non bloquante : si la file est pleine ou qu'aucun
+// collecteur n'est configuré, l'événement est ignoré. Aucun contenu saisi (titres,
+// descriptions) n'est transmis, seulement des identifiants et des propriétés techniques.
+use crate::ids::{ProjectId, TaskId};
+use crate::outbound;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::io::Write;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+pub const EVENT_TASK_CREATED: &str = "task_created";
+pub const EVENT_TASK_COMPLETED: &str = "task_completed";
+pub const EVENT_TASK_DELETED: &str = "task_deleted";
+pub const EVENT_LABEL_ADDED: &str = "label_added";
+pub const EVENT_PROJECT_CREATED: &str = "project_created";
+pub const EVENT_TIMER_STARTED: &str = "timer_started";
+pub const EVENT_TIMER_STOPPED: &str = "timer_stopped";
+pub const EVENT_TIME_BUDGET_THRESHOLD: &str = "time_budget_threshold_reached";
+
+// Événements en attente d'envoi ; au-delà, les nouveaux sont ignorés
+const EVENT_QUEUE_CAPACITY: usize = 10_000;
+const EVENT_BATCH_SIZE: usize = 100;
+
+// EVENT_SINK et variables associées (voir config.rs)
+#[derive(Debug, Clone)]
+pub enum EventSinkConfig {
+    Stdout,
+    Http {
+        url: String,
+        token: Option<String>,
+    },
+    KafkaRest {
+        url: String,
+        topic: String,
+        token: Option<String>,
+    },
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DomainEvent {
+    pub event_id: Uuid,
+    pub event_type: &'static str,
+    pub occurred_at: DateTime<Utc>,
+    pub user_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<TaskId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<ProjectId>,
+    pub properties: serde_json::Value,
+}
+
+impl DomainEvent {
+    pub fn new(event_type: &'static str, user_id: Uuid) -> DomainEvent {
+        DomainEvent {
+            event_id: Uuid::new_v4(),
+            event_type,
+            occurred_at: Utc::now(),
+            user_id,
+            task_id: None,
+            project_id: None,
+            properties: json!({}),
+        }
+    }
+
+    pub fn with_task(mut self, task_id: TaskId, project_id: Option<ProjectId>) -> DomainEvent {
+        self.task_id = Some(task_id);
+        self.project_id = project_id;
+        self
+    }
+
+    pub fn with_project(mut self, project_id: ProjectId) -> DomainEvent {
+        self.project_id = Some(project_id);
+        self
+    }
+
+    pub fn with_properties(mut self, properties: serde_json::Value) -> DomainEvent {
+        self.properties = properties;
+        self
+    }
+}
+
+// Destination des lots d'événements, choisie au démarrage
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    fn name(&self) -> String;
+
+    async fn publish(&self, batch: &[DomainEvent]) -> Result<(), String>;
+}
+
+// Une ligne JSON par événement sur la sortie standard (les logs partent sur stderr)
+pub struct StdoutSink;
+
+#[async_trait]
+impl EventSink for StdoutSink {
+    fn name(&self) -> String {
+        "stdout".to_string()
+    }
+
+    async fn publish(&self, batch: &[DomainEvent]) -> Result<(), String> {
+        let mut stdout = std::io::stdout().lock();
+        for event in batch {
+            serde_json::to_writer(&mut stdout, event).map_err(|e| e.to_string())?;
+            stdout.write_all(b"\n").map_err(|e| e.to_string())?;
+        }
+        stdout.flush().map_err(|e| e.to_string())
+    }
+}
+
+async fn post(
+    url: &str,
+    token: Option<&str>,
+    content_type: &str,
+    body: Vec<u8>,
+) -> Result<(), String> {
+    let mut request = outbound::client()
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, content_type)
+        .body(body);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = outbound::send(&outbound::EVENTS_POLICY, request)
+        .await
+        .map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("collector responded {}", response.status()))
+    }
+}
+
+// Collecteur HTTP : lot envoyé en NDJSON
+pub struct HttpSink {
+    url: String,
+    token: Option<String>,
+}
+
+#[async_trait]
+impl EventSink for HttpSink {
+    fn name(&self) -> String {
+        format!("http:{}", self.url)
+    }
+
+    async fn publish(&self, batch: &[DomainEvent]) -> Result<(), String> {
+        let mut body = Vec::new();
+        for event in batch {
+            serde_json::to_writer(&mut body, event).map_err(|e| e.to_string())?;
+            body.push(b'\n');
+        }
+        post(
+            &self.url,
+            self.token.as_deref(),
+            "application/x-ndjson",
+            body,
+        )
+        .await
+    }
+}
+
+// Kafka via le proxy REST (API v2) ; l'utilisateur sert de clé pour garder l'ordre par compte
+pub struct KafkaRestSink {
+    url: String,
+    topic: String,
+    token: Option<String>,
+}
+
+#[async_trait]
+impl EventSink for KafkaRestSink {
+    fn name(&self) -> String {
+        format!("kafka:{}", self.topic)
+    }
+
+    async fn publish(&self, batch: &[DomainEvent]) -> Result<(), String> {
+        let records: Vec<serde_json::Value> = batch
+            .iter()
+            .map(|event| json!({ "key": event.user_id, "value": event }))
+            .collect();
+        let body = serde_json::to_vec(&json!({ "records": records })).map_err(|e| e.to_string())?;
+        post(
+            &format!("{}/topics/{}", self.url.trim_end_matches('/'), self.topic),
+            self.token.as_deref(),
+            "application/vnd.kafka.json.v2+json",
+            body,
+        )
+        .await
+    }
+}
+
+pub fn sink_from_config(config: &EventSinkConfig) -> Arc<dyn EventSink> {
+    match config {
+        EventSinkConfig::Stdout => Arc::new(StdoutSink),
+        EventSinkConfig::Http { url, token } => Arc::new(HttpSink {
+            url: url.clone(),
+            token: token.clone(),
+        }),
+        EventSinkConfig::KafkaRest { url, topic, token } => Arc::new(KafkaRestSink {
+            url: url.clone(),
+            topic: topic.clone(),
+            token: token.clone(),
+        }),
+    }
+}
+
+static EVENT_BUS: OnceLock<mpsc::Sender<DomainEvent>> = OnceLock::new();
+
+// Publie un événement ; sans collecteur configuré, ne fait rien
+pub fn emit(event: DomainEvent) {
+    let Some(sender) = EVENT_BUS.get() else {
+        return;
+    };
+    if let Err(mpsc::error::TrySendError::Full(event)) = sender.try_send(event) {
+        log::warn!(
+            "Domain event queue is full, dropping {} event",
+            event.event_type
+        );
+    }
+}
+
+// Installe le bus et lance la tâche d'envoi par lots
+pub fn spawn_event_bus(sink: Arc<dyn EventSink>) {
+    let (sender, mut receiver) = mpsc::channel::<DomainEvent>(EVENT_QUEUE_CAPACITY);
+    if EVENT_BUS.set(sender).is_err() {
+        log::warn!("Domain event bus already started");
+        return;
+    }
+    log::info!("Domain events enabled ({})", sink.name());
+
+    actix_web::rt::spawn(async move {
+        let mut batch = Vec::with_capacity(EVENT_BATCH_SIZE);
+        while receiver.recv_many(&mut batch, EVENT_BATCH_SIZE).await > 0 {
+            if let Err(e) = sink.publish(&batch).await {
+                log::error!(
+                    "Could not publish {} domain event(s) to {}: {}",
+                    batch.len(),
+                    sink.name(),
+                    e
+                );
+            }
+            batch.clear();
+        }
+    });
+}
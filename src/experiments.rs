@@ -0,0 +1,158 @@
+// OptiTask/backend-api/src/experiments.rs
+// Expériences A/B : chaque utilisateur authentifié reçoit une variante tirée de façon
+// déterministe selon les poids (même seau stable que les drapeaux, voir feature_flags.rs).
+// La première affectation est enregistrée et reste acquise, même si les poids changent
+// ensuite ; les événements de conversion portent la variante de l'utilisateur.
+use crate::error_handler::ServiceError;
+use crate::feature_flags;
+use crate::models::{Experiment, ExperimentVariant};
+use crate::schema::{experiment_assignments, experiments};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+pub const MAX_VARIANTS: usize = 10;
+pub const MAX_VARIANT_WEIGHT: u32 = 1000;
+const MAX_VARIANT_NAME_CHARS: usize = 64;
+
+// Entre 2 et MAX_VARIANTS variantes aux noms uniques, poids entre 1 et MAX_VARIANT_WEIGHT
+pub fn validate_variants(variants: &[ExperimentVariant]) -> Result<(), ServiceError> {
+    if !(2..=MAX_VARIANTS).contains(&variants.len()) {
+        return Err(ServiceError::ValidationError(format!(
+            "An experiment needs between 2 and {} variants",
+            MAX_VARIANTS
+        )));
+    }
+    for (position, variant) in variants.iter().enumerate() {
+        if variant.name.trim().is_empty() || variant.name.chars().count() > MAX_VARIANT_NAME_CHARS {
+            return Err(ServiceError::ValidationError(format!(
+                "Variant names must contain between 1 and {} characters",
+                MAX_VARIANT_NAME_CHARS
+            )));
+        }
+        if !(1..=MAX_VARIANT_WEIGHT).contains(&variant.weight) {
+            return Err(ServiceError::ValidationError(format!(
+                "Variant weights must be between 1 and {}",
+                MAX_VARIANT_WEIGHT
+            )));
+        }
+        if variants[..position]
+            .iter()
+            .any(|other| other.name == variant.name)
+        {
+            return Err(ServiceError::ValidationError(format!(
+                "Duplicate variant '{}'",
+                variant.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn parse_variants(experiment: &Experiment) -> Vec<ExperimentVariant> {
+    serde_json::from_value(experiment.variants.clone()).unwrap_or_default()
+}
+
+// Variante tirée pour l'utilisateur, proportionnellement aux poids
+fn draw_variant(experiment: &Experiment, user_uuid: Uuid) -> Option<String> {
+    let variants = parse_variants(experiment);
+    let total_weight: u32 = variants.iter().map(|variant| variant.weight).sum();
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut bucket = feature_flags::stable_bucket(&experiment.key, user_uuid, total_weight);
+    for variant in variants {
+        if bucket < variant.weight {
+            return Some(variant.name);
+        }
+        bucket -= variant.weight;
+    }
+    None
+}
+
+// Variantes de l'utilisateur pour les expériences actives données ; les nouvelles
+// affectations sont enregistrées (première exposition)
+async fn assign(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    active_experiments: &[Experiment],
+) -> Result<BTreeMap<String, String>, ServiceError> {
+    let keys: Vec<&str> = active_experiments
+        .iter()
+        .map(|experiment| experiment.key.as_str())
+        .collect();
+    let mut stored: HashMap<String, String> = experiment_assignments::table
+        .filter(experiment_assignments::user_id.eq(user_uuid))
+        .filter(experiment_assignments::experiment_key.eq_any(&keys))
+        .select((
+            experiment_assignments::experiment_key,
+            experiment_assignments::variant,
+        ))
+        .load::<(String, String)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut assignments = BTreeMap::new();
+    for experiment in active_experiments {
+        let variant = match stored.remove(&experiment.key) {
+            Some(variant) => variant,
+            None => {
+                let Some(variant) = draw_variant(experiment, user_uuid) else {
+                    continue;
+                };
+                diesel::insert_into(experiment_assignments::table)
+                    .values((
+                        experiment_assignments::experiment_key.eq(&experiment.key),
+                        experiment_assignments::user_id.eq(user_uuid),
+                        experiment_assignments::variant.eq(&variant),
+                    ))
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .await?;
+                variant
+            }
+        };
+        assignments.insert(experiment.key.clone(), variant);
+    }
+    Ok(assignments)
+}
+
+// Variantes de l'utilisateur pour toutes les expériences actives
+pub async fn assignments_for(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<BTreeMap<String, String>, ServiceError> {
+    let active_experiments = experiments::table
+        .filter(experiments::is_active.eq(true))
+        .select(Experiment::as_select())
+        .load::<Experiment>(conn)
+        .await?;
+
+    assign(conn, user_uuid, &active_experiments).await
+}
+
+// Variante de l'utilisateur pour une expérience ; None si elle est inconnue ou arrêtée.
+// Les handlers s'en servent pour choisir le comportement à servir.
+pub async fn variant_for(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    experiment_key: &str,
+) -> Result<Option<String>, ServiceError> {
+    let active_experiment = experiments::table
+        .find(experiment_key)
+        .filter(experiments::is_active.eq(true))
+        .select(Experiment::as_select())
+        .first::<Experiment>(conn)
+        .await
+        .optional()?;
+    let Some(experiment) = active_experiment else {
+        return Ok(None);
+    };
+
+    Ok(assign(conn, user_uuid, std::slice::from_ref(&experiment))
+        .await?
+        .remove(experiment_key))
+}
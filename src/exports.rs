@@ -0,0 +1,301 @@
+// OptiTask/backend-api/src/exports.rs
+// Export imprimable d'une tâche ou d'un projet (GET /tasks/{id}/export, GET
+// /projects/{id}/export). Le document est rendu côté serveur en PDF (voir pdf.rs), avec les
+// intitulés dans la langue de la requête : détails de la tâche, champs personnalisés et
+// synthèse du temps suivi ; pour un projet, tâches ouvertes et terminées et temps par tâche.
+use crate::custom_fields;
+use crate::error_handler::ServiceError;
+use crate::i18n;
+use crate::ids::TaskId;
+use crate::models::{Project, Task, TaskApiResponse, DONE_TASK_STATUSES};
+use crate::pdf::{Font, PdfDocument};
+use crate::reports::format_duration;
+use crate::repository;
+use crate::schema::{projects, tasks, time_entries};
+use crate::time_budgets;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Pdf,
+}
+
+impl ExportFormat {
+    pub fn parse(raw: Option<&str>) -> Result<ExportFormat, ServiceError> {
+        match raw {
+            None | Some("pdf") => Ok(ExportFormat::Pdf),
+            Some(other) => Err(ServiceError::BadRequest(format!(
+                "Invalid export format: {}. Supported: pdf",
+                other
+            ))),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "application/pdf",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+// Synthèse du temps suivi sur une tâche, tous utilisateurs confondus, hors pauses
+struct TimeSummary {
+    tracked_seconds: i64,
+    entry_count: usize,
+    first_entry: Option<DateTime<Utc>>,
+    last_entry: Option<DateTime<Utc>>,
+    running: bool,
+}
+
+async fn load_time_summary(
+    conn: &mut AsyncPgConnection,
+    task_uuid: TaskId,
+) -> Result<TimeSummary, ServiceError> {
+    let entries = time_entries::table
+        .filter(time_entries::task_id.eq(task_uuid))
+        .filter(time_entries::is_break.eq(false))
+        .select((
+            time_entries::start_time,
+            time_entries::end_time,
+            time_entries::duration_seconds,
+        ))
+        .load::<(DateTime<Utc>, Option<DateTime<Utc>>, Option<i32>)>(conn)
+        .await?;
+    Ok(TimeSummary {
+        tracked_seconds: entries
+            .iter()
+            .filter_map(|(_, _, duration)| duration.map(i64::from))
+            .sum(),
+        entry_count: entries.len(),
+        first_entry: entries.iter().map(|(start, _, _)| *start).min(),
+        last_entry: entries
+            .iter()
+            .map(|(start, end, _)| end.unwrap_or(*start))
+            .max(),
+        running: entries.iter().any(|(_, end, _)| end.is_none()),
+    })
+}
+
+fn format_datetime(value: DateTime<Utc>) -> String {
+    value.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+fn page_label(page: usize, total: usize) -> String {
+    i18n::tf("export.page", &[&page, &total])
+}
+
+// `task` doit avoir été autorisée en lecture par l'appelant
+pub async fn render_task_export(
+    conn: &mut AsyncPgConnection,
+    task: Task,
+) -> Result<Vec<u8>, ServiceError> {
+    let project_name = match task.project_id {
+        Some(project_uuid) => projects::table
+            .find(project_uuid)
+            .select(projects::name)
+            .first::<String>(conn)
+            .await
+            .optional()?,
+        None => None,
+    };
+    let labels = repository::load_task_labels(conn, task.id).await?;
+    let time_summary = load_time_summary(conn, task.id).await?;
+
+    let mut task_response = TaskApiResponse::from(task);
+    custom_fields::attach_custom_fields(conn, std::slice::from_mut(&mut task_response)).await?;
+    time_budgets::attach_budget_usage(conn, std::slice::from_mut(&mut task_response)).await?;
+
+    let mut doc = PdfDocument::new(&task_response.title);
+    doc.title(&task_response.title);
+    doc.note(&format!(
+        "{} {}",
+        i18n::t("report.generated"),
+        format_datetime(Utc::now())
+    ));
+
+    doc.section(i18n::t("export.details"));
+    doc.field(i18n::t("export.status"), &task_response.status);
+    doc.field(i18n::t("export.stage"), &task_response.stage);
+    doc.field(
+        i18n::t("export.project"),
+        project_name.as_deref().unwrap_or(i18n::t("export.inbox")),
+    );
+    if let Some(due) = task_response.due_date {
+        doc.field(i18n::t("export.due_date"), &due.to_string());
+    }
+    if let Some(start) = task_response.scheduled_start {
+        let schedule = match task_response.scheduled_end {
+            Some(end) => format!("{} - {}", format_datetime(start), format_datetime(end)),
+            None => format_datetime(start),
+        };
+        doc.field(i18n::t("export.scheduled"), &schedule);
+    }
+    if !labels.is_empty() {
+        let label_names: Vec<&str> = labels.iter().map(|label| label.name.as_str()).collect();
+        doc.field(i18n::t("export.labels"), &label_names.join(", "));
+    }
+    doc.field(
+        i18n::t("export.created"),
+        &task_response.created_at.format("%Y-%m-%d").to_string(),
+    );
+
+    doc.section(i18n::t("export.description"));
+    match task_response
+        .description
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+    {
+        Some(description) => doc.paragraph(description),
+        None => doc.note(i18n::t("export.no_description")),
+    }
+
+    if !task_response.custom_fields.is_empty() {
+        doc.section(i18n::t("export.custom_fields"));
+        for field in &task_response.custom_fields {
+            let value = match &field.value {
+                serde_json::Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            doc.field(&field.name, &value);
+        }
+    }
+
+    doc.section(i18n::t("export.time_summary"));
+    doc.field(
+        i18n::t("export.tracked"),
+        &format_duration(time_summary.tracked_seconds),
+    );
+    if let Some(budget) = task_response.time_budget_seconds {
+        let used = task_response
+            .budget_used_pct
+            .map(|pct| format!(" ({}%)", pct))
+            .unwrap_or_default();
+        doc.field(
+            i18n::t("export.budget"),
+            &format!("{}{}", format_duration(i64::from(budget)), used),
+        );
+    }
+    doc.field(
+        i18n::t("export.entries"),
+        &time_summary.entry_count.to_string(),
+    );
+    if let (Some(first), Some(last)) = (time_summary.first_entry, time_summary.last_entry) {
+        doc.field(
+            i18n::t("export.tracked_between"),
+            &format!("{} - {}", format_datetime(first), format_datetime(last)),
+        );
+    }
+    if time_summary.running {
+        doc.note(i18n::t("export.timer_running"));
+    }
+
+    Ok(doc.finish(page_label))
+}
+
+// `project` doit avoir été autorisé en lecture par l'appelant ; un projet partagé est
+// exporté dans son ensemble, tous membres confondus
+pub async fn render_project_export(
+    conn: &mut AsyncPgConnection,
+    project: Project,
+) -> Result<Vec<u8>, ServiceError> {
+    let project_tasks = tasks::table
+        .filter(tasks::project_id.eq(project.id))
+        .order((tasks::due_date.asc().nulls_last(), tasks::created_at.asc()))
+        .select(Task::as_select())
+        .load::<Task>(conn)
+        .await?;
+    let task_ids: Vec<TaskId> = project_tasks.iter().map(|task| task.id).collect();
+    let tracked_by_task: HashMap<TaskId, i64> =
+        time_budgets::tracked_seconds_by_task(conn, &task_ids).await?;
+
+    let (completed, open): (Vec<&Task>, Vec<&Task>) = project_tasks
+        .iter()
+        .partition(|task| DONE_TASK_STATUSES.contains(&task.status.as_str()));
+
+    let mut doc = PdfDocument::new(&project.name);
+    doc.title(&project.name);
+    doc.note(&format!(
+        "{} {}",
+        i18n::t("report.generated"),
+        format_datetime(Utc::now())
+    ));
+
+    doc.section(&format!(
+        "{} ({})",
+        i18n::t("report.open_tasks"),
+        open.len()
+    ));
+    if open.is_empty() {
+        doc.note(i18n::t("report.no_open_tasks"));
+    }
+    for task in &open {
+        let line = match task.due_date {
+            Some(due) => format!(
+                "{} ({}, {} {})",
+                task.title,
+                task.status,
+                i18n::t("report.due"),
+                due
+            ),
+            None => format!("{} ({})", task.title, task.status),
+        };
+        doc.bullet(&line);
+    }
+
+    doc.section(&format!(
+        "{} ({})",
+        i18n::t("export.completed_tasks"),
+        completed.len()
+    ));
+    if completed.is_empty() {
+        doc.note(i18n::t("export.no_completed_tasks"));
+    }
+    for task in &completed {
+        doc.bullet(&task.title);
+    }
+
+    doc.section(i18n::t("report.time_per_task"));
+    let mut time_by_task: Vec<(&Task, i64)> = project_tasks
+        .iter()
+        .filter_map(|task| {
+            tracked_by_task
+                .get(&task.id)
+                .map(|tracked| (task, *tracked))
+        })
+        .filter(|(_, tracked)| *tracked > 0)
+        .collect();
+    time_by_task.sort_by_key(|(_, tracked)| std::cmp::Reverse(*tracked));
+    doc.row(i18n::t("report.task"), i18n::t("report.time"), Font::Bold);
+    for (task, tracked) in &time_by_task {
+        doc.row(&task.title, &format_duration(*tracked), Font::Regular);
+    }
+    let total_seconds: i64 = time_by_task.iter().map(|(_, tracked)| tracked).sum();
+    doc.space(4.0);
+    doc.row(
+        i18n::t("report.total"),
+        &format_duration(total_seconds),
+        Font::Bold,
+    );
+
+    Ok(doc.finish(page_label))
+}
+
+// Nom de fichier proposé au téléchargement
+pub fn export_filename(kind: &str, id: impl std::fmt::Display, format: ExportFormat) -> String {
+    format!("{}-{}.{}", kind, id, format.extension())
+}
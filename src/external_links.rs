@@ -0,0 +1,464 @@
+// OptiTask/backend-api/src/external_links.rs
+// Références externes typées sur une tâche (POST/GET/DELETE /tasks/{id}/links) : URL,
+// ticket GitHub, clé Jira ou Message-ID d'un e-mail. La référence est normalisée pour
+// éviter les doublons ("https://github.com/o/r/issues/1" et "o/r#1" désignent le même
+// ticket). Pour les URL et les tickets GitHub, le titre et l'icône de la page sont lus côté
+// serveur à l'ajout, via le client sortant (voir outbound.rs) ; un échec n'empêche pas
+// l'ajout. Les pages Jira et les messages demandent une authentification : pas d'aperçu.
+// Chaque saut (page de départ et redirections) passe par outbound::public_client.
+use crate::error_handler::ServiceError;
+use crate::outbound::{self, LINK_PREVIEW_POLICY};
+use chrono::{DateTime, Utc};
+
+pub const LINK_KIND_URL: &str = "url";
+pub const LINK_KIND_GITHUB_ISSUE: &str = "github_issue";
+pub const LINK_KIND_JIRA: &str = "jira";
+pub const LINK_KIND_EMAIL: &str = "email";
+pub const LINK_KINDS: [&str; 4] = [
+    LINK_KIND_URL,
+    LINK_KIND_GITHUB_ISSUE,
+    LINK_KIND_JIRA,
+    LINK_KIND_EMAIL,
+];
+
+pub const MAX_LINKS_PER_TASK: i64 = 100;
+const MAX_REFERENCE_CHARS: usize = 2048;
+const MAX_TITLE_CHARS: usize = 300;
+// Le titre et les icônes sont dans l'en-tête : inutile de lire au-delà
+const MAX_PREVIEW_BYTES: usize = 256 * 1024;
+const MAX_PREVIEW_REDIRECTS: usize = 5;
+
+// Référence normalisée et lien associé
+#[derive(Debug, PartialEq)]
+pub struct NormalizedLink {
+    pub kind: &'static str,
+    pub reference: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct LinkMetadata {
+    pub title: Option<String>,
+    pub favicon_url: Option<String>,
+    pub fetched_at: Option<DateTime<Utc>>,
+}
+
+fn parse_web_url(raw: &str, field: &str) -> Result<reqwest::Url, ServiceError> {
+    reqwest::Url::parse(raw.trim())
+        .ok()
+        .filter(|parsed| matches!(parsed.scheme(), "http" | "https") && parsed.has_host())
+        .ok_or_else(|| {
+            ServiceError::ValidationError(format!("{} must be an absolute http(s) URL", field))
+        })
+}
+
+fn is_github_name(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+// "owner/repo#123" ou https://github.com/owner/repo/(issues|pull)/123
+fn parse_github_issue(raw: &str) -> Option<(String, String, u64)> {
+    if let Ok(parsed) = reqwest::Url::parse(raw) {
+        if !matches!(parsed.host_str(), Some("github.com" | "www.github.com")) {
+            return None;
+        }
+        let segments: Vec<&str> = parsed.path_segments()?.filter(|s| !s.is_empty()).collect();
+        return match segments.as_slice() {
+            [owner, repo, "issues" | "pull", number]
+                if is_github_name(owner) && is_github_name(repo) =>
+            {
+                Some((owner.to_string(), repo.to_string(), number.parse().ok()?))
+            }
+            _ => None,
+        };
+    }
+    let (repository, number) = raw.split_once('#')?;
+    let (owner, repo) = repository.split_once('/')?;
+    if !is_github_name(owner) || !is_github_name(repo) {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string(), number.parse().ok()?))
+}
+
+// Clé Jira : projet en majuscules (lettre puis lettres, chiffres ou _) et numéro
+fn parse_jira_key(raw: &str) -> Option<String> {
+    let key = raw.to_ascii_uppercase();
+    let (project, number) = key.rsplit_once('-')?;
+    let mut project_chars = project.chars();
+    let valid_project = project_chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && project_chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    let valid_number = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+    (valid_project && valid_number).then_some(key)
+}
+
+pub fn normalize(
+    kind: &str,
+    reference: &str,
+    url: Option<&str>,
+) -> Result<NormalizedLink, ServiceError> {
+    let reference = reference.trim();
+    if reference.is_empty() || reference.chars().count() > MAX_REFERENCE_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "reference must contain between 1 and {} characters",
+            MAX_REFERENCE_CHARS
+        )));
+    }
+    let url = url.map(str::trim).filter(|url| !url.is_empty());
+
+    match kind {
+        LINK_KIND_URL | LINK_KIND_GITHUB_ISSUE if url.is_some() => {
+            Err(ServiceError::ValidationError(format!(
+                "url is derived from the reference for {} links",
+                kind
+            )))
+        }
+        LINK_KIND_URL => {
+            let parsed = parse_web_url(reference, "reference")?;
+            Ok(NormalizedLink {
+                kind: LINK_KIND_URL,
+                reference: parsed.to_string(),
+                url: Some(parsed.to_string()),
+            })
+        }
+        LINK_KIND_GITHUB_ISSUE => {
+            let (owner, repo, number) = parse_github_issue(reference).ok_or_else(|| {
+                ServiceError::ValidationError(
+                    "GitHub references must look like owner/repo#123 or a github.com issue URL"
+                        .to_string(),
+                )
+            })?;
+            Ok(NormalizedLink {
+                kind: LINK_KIND_GITHUB_ISSUE,
+                reference: format!("{}/{}#{}", owner, repo, number),
+                url: Some(format!(
+                    "https://github.com/{}/{}/issues/{}",
+                    owner, repo, number
+                )),
+            })
+        }
+        LINK_KIND_JIRA => {
+            // Une URL .../browse/KEY-123 fournit à la fois la clé et le lien
+            let (key, browse_url) = match reqwest::Url::parse(reference) {
+                Ok(parsed) => {
+                    let parsed = parse_web_url(parsed.as_str(), "reference")?;
+                    let key = parsed
+                        .path_segments()
+                        .and_then(|mut segments| segments.rfind(|s| !s.is_empty()))
+                        .and_then(parse_jira_key);
+                    (key, Some(parsed.to_string()))
+                }
+                Err(_) => (parse_jira_key(reference), None),
+            };
+            let key = key.ok_or_else(|| {
+                ServiceError::ValidationError(
+                    "Jira references must be an issue key such as PROJ-123".to_string(),
+                )
+            })?;
+            let url = match url {
+                Some(url) => Some(parse_web_url(url, "url")?.to_string()),
+                None => browse_url,
+            };
+            Ok(NormalizedLink {
+                kind: LINK_KIND_JIRA,
+                reference: key,
+                url,
+            })
+        }
+        LINK_KIND_EMAIL => {
+            let message_id = reference
+                .strip_prefix('<')
+                .and_then(|id| id.strip_suffix('>'))
+                .unwrap_or(reference);
+            let valid = message_id.contains('@')
+                && !message_id
+                    .chars()
+                    .any(|c| c.is_whitespace() || matches!(c, '<' | '>'));
+            if !valid {
+                return Err(ServiceError::ValidationError(
+                    "Email references must be a Message-ID such as <id@example.com>".to_string(),
+                ));
+            }
+            Ok(NormalizedLink {
+                kind: LINK_KIND_EMAIL,
+                reference: message_id.to_string(),
+                url: url
+                    .map(|url| parse_web_url(url, "url").map(|parsed| parsed.to_string()))
+                    .transpose()?,
+            })
+        }
+        other => Err(ServiceError::ValidationError(format!(
+            "Invalid link kind '{}'. Supported: {}",
+            other,
+            LINK_KINDS.join(", ")
+        ))),
+    }
+}
+
+// Seuls les liens publics ont un aperçu
+pub fn has_preview(link: &NormalizedLink) -> bool {
+    matches!(link.kind, LINK_KIND_URL | LINK_KIND_GITHUB_ISSUE)
+}
+
+// Suit les redirections à la main, en contrôlant l'adresse de chaque saut
+async fn fetch_public_page(url: reqwest::Url) -> Option<reqwest::Response> {
+    let mut current = url;
+    for _ in 0..=MAX_PREVIEW_REDIRECTS {
+        let Some(client) = outbound::public_client(&current).await else {
+            log::debug!("Skipping link preview for non-public host: {}", current);
+            return None;
+        };
+        let request = client
+            .get(current.clone())
+            .header(reqwest::header::ACCEPT, "text/html");
+        let response = match outbound::send_via(&client, &LINK_PREVIEW_POLICY, request).await {
+            Ok(response) => response,
+            Err(e) => {
+                log::debug!("Link preview for {} failed: {}", current, e);
+                return None;
+            }
+        };
+        if !response.status().is_redirection() {
+            return Some(response);
+        }
+        current = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(|location| current.join(location).ok())
+            .filter(|next| matches!(next.scheme(), "http" | "https"))?;
+    }
+    log::debug!(
+        "Link preview stopped after {} redirects",
+        MAX_PREVIEW_REDIRECTS
+    );
+    None
+}
+
+// Décode les entités HTML courantes
+fn decode_entities(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// Attributs d'une balise ouvrante ("<link rel=icon href='/x.png'>"), noms en minuscules
+fn tag_attributes(tag: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = tag
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .trim_end_matches('/');
+    // Nom de la balise
+    rest = rest.trim_start_matches(|c: char| !c.is_whitespace());
+    loop {
+        rest = rest.trim_start();
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        if name_end == 0 {
+            break;
+        }
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+        let Some(after_equals) = rest.strip_prefix('=') else {
+            attributes.push((name, String::new()));
+            continue;
+        };
+        rest = after_equals.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = rest[1..]
+                    .find(quote)
+                    .map(|end| end + 1)
+                    .unwrap_or(rest.len());
+                let value = &rest[1..end];
+                rest = rest.get(end + 1..).unwrap_or("");
+                value
+            }
+            _ => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let value = &rest[..end];
+                rest = &rest[end..];
+                value
+            }
+        };
+        attributes.push((name, decode_entities(value)));
+    }
+    attributes
+}
+
+// Balises ouvrantes `name` du document (recherche insensible à la casse)
+fn find_tags<'a>(html: &'a str, lowercase: &str, name: &str) -> Vec<&'a str> {
+    let needle = format!("<{}", name);
+    let mut tags = Vec::new();
+    let mut offset = 0;
+    while let Some(found) = lowercase[offset..].find(&needle) {
+        let start = offset + found;
+        let after_name = start + needle.len();
+        let Some(end) = lowercase[after_name..].find('>') else {
+            break;
+        };
+        let end = after_name + end + 1;
+        if lowercase[after_name..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_whitespace() || c == '>' || c == '/')
+        {
+            tags.push(&html[start..end]);
+        }
+        offset = end;
+    }
+    tags
+}
+
+fn clean_title(raw: &str) -> Option<String> {
+    let title = decode_entities(raw)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!title.is_empty()).then(|| title.chars().take(MAX_TITLE_CHARS).collect())
+}
+
+// Titre (<title>, sinon og:title) et icône (<link rel="icon">, sinon /favicon.ico)
+fn parse_metadata(html: &str, page_url: &reqwest::Url) -> (Option<String>, Option<String>) {
+    // Minuscules ASCII : les positions restent valables dans `html`
+    let lowercase = html.to_ascii_lowercase();
+
+    let title = lowercase
+        .find("<title")
+        .and_then(|start| lowercase[start..].find('>').map(|end| start + end + 1))
+        .and_then(|start| {
+            lowercase[start..]
+                .find("</title")
+                .map(|end| &html[start..start + end])
+        })
+        .and_then(clean_title)
+        .or_else(|| {
+            find_tags(html, &lowercase, "meta")
+                .into_iter()
+                .find_map(|tag| {
+                    let attributes = tag_attributes(tag);
+                    let is_og_title = attributes
+                        .iter()
+                        .any(|(name, value)| name == "property" && value == "og:title");
+                    is_og_title
+                        .then(|| {
+                            attributes
+                                .iter()
+                                .find(|(name, _)| name == "content")
+                                .and_then(|(_, content)| clean_title(content))
+                        })
+                        .flatten()
+                })
+        });
+
+    let favicon = find_tags(html, &lowercase, "link")
+        .into_iter()
+        .find_map(|tag| {
+            let attributes = tag_attributes(tag);
+            let is_icon = attributes.iter().any(|(name, value)| {
+                name == "rel"
+                    && value
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("icon"))
+            });
+            if !is_icon {
+                return None;
+            }
+            attributes
+                .iter()
+                .find(|(name, _)| name == "href")
+                .and_then(|(_, href)| page_url.join(href.trim()).ok())
+        })
+        .or_else(|| page_url.join("/favicon.ico").ok())
+        .filter(|icon| matches!(icon.scheme(), "http" | "https"))
+        .map(|icon| icon.to_string());
+
+    (title, favicon)
+}
+
+// Lit le titre et l'icône de la page ; renvoie des métadonnées vides en cas d'échec
+pub async fn fetch_metadata(url: &str) -> LinkMetadata {
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return LinkMetadata::default();
+    };
+    let mut response = match fetch_public_page(parsed).await {
+        Some(response) if response.status().is_success() => response,
+        Some(response) => {
+            log::debug!(
+                "Link preview for {} returned status {}",
+                url,
+                response.status()
+            );
+            return LinkMetadata::default();
+        }
+        None => return LinkMetadata::default(),
+    };
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.to_ascii_lowercase().contains("html"));
+    if !is_html {
+        return LinkMetadata::default();
+    }
+
+    let page_url = response.url().clone();
+    let mut body = Vec::new();
+    while body.len() < MAX_PREVIEW_BYTES {
+        match response.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            Ok(None) => break,
+            Err(e) => {
+                log::debug!("Link preview for {} failed while reading: {}", url, e);
+                break;
+            }
+        }
+    }
+    body.truncate(MAX_PREVIEW_BYTES);
+
+    let (title, favicon_url) = parse_metadata(&String::from_utf8_lossy(&body), &page_url);
+    LinkMetadata {
+        title,
+        favicon_url,
+        fetched_at: Some(Utc::now()),
+    }
+}
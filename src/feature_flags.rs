@@ -0,0 +1,128 @@
+// OptiTask/backend-api/src/feature_flags.rs
+// Évaluation des drapeaux de fonctionnalités pour un utilisateur, par ordre de priorité :
+// 1. override de l'utilisateur ;
+// 2. interrupteur global (enabled = false => inactif) ;
+// 3. déploiement progressif : l'utilisateur est actif si son seau (0..99), dérivé de
+//    façon stable de la clé du drapeau et de son identifiant, est sous rollout_percentage.
+// Un drapeau inconnu est inactif.
+use crate::error_handler::ServiceError;
+use crate::models::FeatureFlag;
+use crate::schema::{feature_flag_overrides, feature_flags};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use ring::digest::{digest, SHA256};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+const MAX_KEY_CHARS: usize = 64;
+
+// Clé de drapeau ou d'expérience en minuscules : lettres, chiffres, "_", "." et "-"
+// (même règle que les contraintes SQL)
+pub fn validate_key(key: &str) -> Result<(), ServiceError> {
+    let valid = !key.is_empty()
+        && key.len() <= MAX_KEY_CHARS
+        && key
+            .chars()
+            .next()
+            .is_some_and(|first| first.is_ascii_lowercase() || first.is_ascii_digit())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '.' | '-'));
+    if !valid {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid key '{}': use up to {} lowercase letters, digits, '_', '.' or '-'",
+            key, MAX_KEY_CHARS
+        )));
+    }
+    Ok(())
+}
+
+// Drapeaux consultés par les handlers
+pub const FLAG_AI_SUMMARY: &str = "ai_summary";
+
+// Seau stable (0..buckets) d'un utilisateur pour une clé donnée : un même utilisateur
+// ne bascule pas d'une requête à l'autre, et les seaux diffèrent d'une clé à l'autre.
+// Partagé avec l'affectation des expériences (voir experiments.rs).
+pub fn stable_bucket(key: &str, user_uuid: Uuid, buckets: u32) -> u32 {
+    let mut input = key.as_bytes().to_vec();
+    input.push(b':');
+    input.extend_from_slice(user_uuid.as_bytes());
+    let hash = digest(&SHA256, &input);
+    let prefix: [u8; 4] = hash.as_ref()[..4]
+        .try_into()
+        .expect("SHA-256 digest is 32 bytes");
+    u32::from_be_bytes(prefix) % buckets
+}
+
+fn evaluate(flag: &FeatureFlag, user_uuid: Uuid, user_override: Option<bool>) -> bool {
+    if let Some(forced) = user_override {
+        return forced;
+    }
+    flag.enabled && (stable_bucket(&flag.key, user_uuid, 100) as i32) < flag.rollout_percentage
+}
+
+// Tous les drapeaux évalués pour l'utilisateur
+pub async fn evaluate_all(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<BTreeMap<String, bool>, ServiceError> {
+    let flags = feature_flags::table
+        .select(FeatureFlag::as_select())
+        .load::<FeatureFlag>(conn)
+        .await?;
+    let overrides: HashMap<String, bool> = feature_flag_overrides::table
+        .filter(feature_flag_overrides::user_id.eq(user_uuid))
+        .select((
+            feature_flag_overrides::flag_key,
+            feature_flag_overrides::enabled,
+        ))
+        .load::<(String, bool)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(flags
+        .iter()
+        .map(|flag| {
+            let user_override = overrides.get(&flag.key).copied();
+            (flag.key.clone(), evaluate(flag, user_uuid, user_override))
+        })
+        .collect())
+}
+
+pub async fn is_enabled(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    flag_key: &str,
+) -> Result<bool, ServiceError> {
+    let Some(flag) = feature_flags::table
+        .find(flag_key)
+        .select(FeatureFlag::as_select())
+        .first::<FeatureFlag>(conn)
+        .await
+        .optional()?
+    else {
+        return Ok(false);
+    };
+    let user_override = feature_flag_overrides::table
+        .find((flag_key, user_uuid))
+        .select(feature_flag_overrides::enabled)
+        .first::<bool>(conn)
+        .await
+        .optional()?;
+
+    Ok(evaluate(&flag, user_uuid, user_override))
+}
+
+// Refuse l'accès (403, missing_permission = "feature.<clé>") si le drapeau est inactif
+pub async fn require_enabled(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    flag_key: &str,
+) -> Result<(), ServiceError> {
+    if is_enabled(conn, user_uuid, flag_key).await? {
+        Ok(())
+    } else {
+        Err(ServiceError::Forbidden(format!("feature.{}", flag_key)))
+    }
+}
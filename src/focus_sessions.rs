@@ -0,0 +1,224 @@
+// OptiTask/backend-api/src/focus_sessions.rs
+// Sessions de concentration (POST /focus-sessions) : l'utilisateur se consacre à un label
+// ou à un projet. Tant que la session est active, les entrées de temps créées sont marquées
+// focus_session_id, et démarrer un minuteur sur une tâche hors du périmètre est refusé
+// (409 TASK_OUTSIDE_FOCUS) sauf ?override=true. La fin de session fige un bilan : temps
+// suivi dans et hors du périmètre, temps par tâche et tâches terminées pendant la session.
+use crate::error_handler::ServiceError;
+use crate::ids::TaskId;
+use crate::models::{FocusSession, DONE_TASK_STATUSES};
+use crate::schema::{focus_sessions, task_labels, task_status_history, tasks, time_entries};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+pub const FOCUS_SESSION_ACTIVE: &str = "FOCUS_SESSION_ACTIVE";
+pub const TASK_OUTSIDE_FOCUS: &str = "TASK_OUTSIDE_FOCUS";
+
+#[derive(Serialize, Debug)]
+pub struct FocusTaskTime {
+    pub task_id: TaskId,
+    pub title: String,
+    pub tracked_seconds: i64,
+    // Tâche dans le périmètre de la session (sinon démarrée avec override)
+    pub in_focus: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FocusCompletedTask {
+    pub task_id: TaskId,
+    pub title: String,
+    pub completed_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FocusSessionSummary {
+    pub duration_seconds: i64,
+    pub tracked_seconds: i64,
+    pub focused_seconds: i64,
+    pub off_focus_seconds: i64,
+    // Part du temps suivi passée dans le périmètre, en pourcentage (None si rien n'a été suivi)
+    pub focus_pct: Option<i64>,
+    pub entry_count: usize,
+    pub tasks: Vec<FocusTaskTime>,
+    pub completed_tasks: Vec<FocusCompletedTask>,
+}
+
+pub async fn active_session(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<FocusSession>, ServiceError> {
+    focus_sessions::table
+        .filter(focus_sessions::user_id.eq(user_uuid))
+        .filter(focus_sessions::ended_at.is_null())
+        .select(FocusSession::as_select())
+        .first::<FocusSession>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// Identifiant à inscrire sur une entrée de temps créée maintenant
+pub async fn active_session_id(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<Uuid>, ServiceError> {
+    Ok(active_session(conn, user_uuid)
+        .await?
+        .map(|session| session.id))
+}
+
+// Tâches de `task_ids` qui relèvent du périmètre de la session
+async fn tasks_in_focus(
+    conn: &mut AsyncPgConnection,
+    session: &FocusSession,
+    task_ids: &[TaskId],
+) -> Result<HashSet<TaskId>, ServiceError> {
+    if task_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let in_focus = match (session.project_id, session.label_id) {
+        (Some(project_uuid), _) => {
+            tasks::table
+                .filter(tasks::id.eq_any(task_ids))
+                .filter(tasks::project_id.eq(project_uuid))
+                .select(tasks::id)
+                .load::<TaskId>(conn)
+                .await?
+        }
+        (None, Some(label_uuid)) => {
+            task_labels::table
+                .filter(task_labels::task_id.eq_any(task_ids))
+                .filter(task_labels::label_id.eq(label_uuid))
+                .select(task_labels::task_id)
+                .load::<TaskId>(conn)
+                .await?
+        }
+        (None, None) => Vec::new(),
+    };
+    Ok(in_focus.into_iter().collect())
+}
+
+// Refuse de démarrer un minuteur sur une tâche hors du périmètre de la session active
+pub async fn ensure_task_in_focus(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    task_uuid: TaskId,
+) -> Result<(), ServiceError> {
+    let Some(session) = active_session(conn, user_uuid).await? else {
+        return Ok(());
+    };
+    if tasks_in_focus(conn, &session, &[task_uuid])
+        .await?
+        .contains(&task_uuid)
+    {
+        return Ok(());
+    }
+    let scope = match (session.project_id, session.label_id) {
+        (Some(project_uuid), _) => format!("project {}", project_uuid),
+        (None, Some(label_uuid)) => format!("label {}", label_uuid),
+        (None, None) => "its scope".to_string(),
+    };
+    Err(ServiceError::CodedConflict(
+        TASK_OUTSIDE_FOCUS,
+        format!(
+            "A focus session on {} is active and this task is outside it. Retry with override=true to start the timer anyway.",
+            scope
+        ),
+    ))
+}
+
+// Bilan de la session close à `ended_at` ; un minuteur encore en cours compte jusqu'à la fin
+pub async fn build_summary(
+    conn: &mut AsyncPgConnection,
+    session: &FocusSession,
+    ended_at: DateTime<Utc>,
+) -> Result<FocusSessionSummary, ServiceError> {
+    let entries = time_entries::table
+        .inner_join(tasks::table)
+        .filter(time_entries::focus_session_id.eq(session.id))
+        .filter(time_entries::is_break.eq(false))
+        .select((
+            time_entries::task_id,
+            tasks::title,
+            time_entries::start_time,
+            time_entries::duration_seconds,
+        ))
+        .load::<(TaskId, String, DateTime<Utc>, Option<i32>)>(conn)
+        .await?;
+
+    let mut by_task: BTreeMap<TaskId, (String, i64)> = BTreeMap::new();
+    for (task_uuid, title, start_time, duration) in &entries {
+        let seconds = match duration {
+            Some(duration) => i64::from(*duration),
+            None => (ended_at - *start_time).num_seconds().max(0),
+        };
+        by_task.entry(*task_uuid).or_insert((title.clone(), 0)).1 += seconds;
+    }
+    let task_ids: Vec<TaskId> = by_task.keys().copied().collect();
+    let in_focus = tasks_in_focus(conn, session, &task_ids).await?;
+
+    let mut task_times: Vec<FocusTaskTime> = by_task
+        .into_iter()
+        .map(|(task_uuid, (title, tracked_seconds))| FocusTaskTime {
+            task_id: task_uuid,
+            title,
+            tracked_seconds,
+            in_focus: in_focus.contains(&task_uuid),
+        })
+        .collect();
+    task_times.sort_by_key(|task| std::cmp::Reverse(task.tracked_seconds));
+    let tracked_seconds: i64 = task_times.iter().map(|task| task.tracked_seconds).sum();
+    let focused_seconds: i64 = task_times
+        .iter()
+        .filter(|task| task.in_focus)
+        .map(|task| task.tracked_seconds)
+        .sum();
+
+    // Passages à un statut terminé pendant la session, sur les tâches du périmètre
+    let completions = task_status_history::table
+        .inner_join(tasks::table)
+        .filter(task_status_history::user_id.eq(session.user_id))
+        .filter(task_status_history::new_status.eq_any(DONE_TASK_STATUSES))
+        .filter(task_status_history::changed_at.ge(session.started_at))
+        .filter(task_status_history::changed_at.le(ended_at))
+        .order(task_status_history::changed_at.asc())
+        .select((
+            task_status_history::task_id,
+            tasks::title,
+            task_status_history::old_status,
+            task_status_history::changed_at,
+        ))
+        .load::<(TaskId, String, Option<String>, DateTime<Utc>)>(conn)
+        .await?;
+    let completed_ids: Vec<TaskId> = completions.iter().map(|row| row.0).collect();
+    let completed_in_focus = tasks_in_focus(conn, session, &completed_ids).await?;
+    let completed_tasks = completions
+        .into_iter()
+        .filter(|(task_uuid, _, old_status, _)| {
+            completed_in_focus.contains(task_uuid)
+                && !old_status
+                    .as_deref()
+                    .is_some_and(|old| DONE_TASK_STATUSES.contains(&old))
+        })
+        .map(|(task_uuid, title, _, completed_at)| FocusCompletedTask {
+            task_id: task_uuid,
+            title,
+            completed_at,
+        })
+        .collect();
+
+    Ok(FocusSessionSummary {
+        duration_seconds: (ended_at - session.started_at).num_seconds().max(0),
+        tracked_seconds,
+        focused_seconds,
+        off_focus_seconds: tracked_seconds - focused_seconds,
+        focus_pct: (tracked_seconds > 0).then(|| focused_seconds * 100 / tracked_seconds),
+        entry_count: entries.len(),
+        tasks: task_times,
+        completed_tasks,
+    })
+}
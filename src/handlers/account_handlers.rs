@@ -0,0 +1,151 @@
+// OptiTask/backend-api/src/handlers/account_handlers.rs
+use crate::account;
+use crate::auth_utils::AuthenticatedUser;
+use crate::confirmations::{self, DestructiveAction};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::schema::{account_deletion_requests, labels, projects, tasks, time_entries};
+use actix_web::{delete, get, post, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Deserialize, Debug)]
+pub struct ConfirmationQueryParams {
+    pub confirmation_token: Option<String>,
+}
+
+// === POST /account/delete-request ===
+// Programme la suppression après le délai de grâce ; le compte passe en lecture seule
+#[post("/delete-request")]
+pub async fn request_account_deletion_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let (request, created) = account::schedule_deletion(&mut conn, authenticated_user.id).await?;
+
+    if created {
+        Ok(HttpResponse::Created().json(request))
+    } else {
+        Ok(HttpResponse::Ok().json(request))
+    }
+}
+
+// === GET /account/delete-request ===
+#[get("/delete-request")]
+pub async fn get_account_deletion_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    match account::find_deletion_request(&mut conn, authenticated_user.id).await? {
+        Some(request) => Ok(HttpResponse::Ok().json(request)),
+        None => Err(ServiceError::NotFound(
+            "No account deletion is scheduled".to_string(),
+        )),
+    }
+}
+
+// === DELETE /account/delete-request ===
+// Annule la suppression programmée et rétablit l'accès en écriture
+#[delete("/delete-request")]
+pub async fn cancel_account_deletion_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    if account::cancel_deletion(&mut conn, authenticated_user.id).await? {
+        Ok(HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": "Account deletion cancelled"
+        })))
+    } else {
+        Err(ServiceError::NotFound(
+            "No account deletion is scheduled".to_string(),
+        ))
+    }
+}
+
+// === POST /account/wipe ===
+// Efface immédiatement toutes les données du compte, en deux temps :
+// sans jeton, renvoie le résumé et un jeton de confirmation
+#[post("/wipe")]
+pub async fn wipe_account_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<ConfirmationQueryParams>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let action = DestructiveAction::WipeAccount;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let Some(token) = &query.confirmation_token else {
+        let project_count = projects::table
+            .filter(projects::user_id.eq(user_uuid))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        let task_count = tasks::table
+            .filter(tasks::user_id.eq(user_uuid))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        let label_count = labels::table
+            .filter(labels::user_id.eq(user_uuid))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        let time_entry_count = time_entries::table
+            .filter(time_entries::user_id.eq(user_uuid))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+
+        return confirmations::confirmation_required(
+            &mut conn,
+            user_uuid,
+            action,
+            None,
+            json!({
+                "projects": project_count,
+                "tasks": task_count,
+                "labels": label_count,
+                "time_entries": time_entry_count
+            }),
+        )
+        .await;
+    };
+
+    confirmations::consume_token(&mut conn, user_uuid, action, None, token).await?;
+
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            account::wipe_user_data(conn, user_uuid).await?;
+            // Une suppression programmée n'a plus d'objet
+            diesel::delete(account_deletion_requests::table.find(user_uuid))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+
+    log::info!("Account data wiped on request for user {}", user_uuid);
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "All account data has been deleted"
+    })))
+}
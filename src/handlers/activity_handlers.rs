@@ -0,0 +1,34 @@
+// OptiTask/backend-api/src/handlers/activity_handlers.rs
+use crate::activity_feed;
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use actix_web::{get, web, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct ActivityQueryParams {
+    // Début de la période (défaut : 7 jours avant maintenant)
+    pub since: Option<DateTime<Utc>>,
+}
+
+// === GET /activity ===
+// Fil chronologique de la période, groupé par jour (revue hebdomadaire, digest)
+#[get("")]
+pub async fn get_activity_feed_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<ActivityQueryParams>,
+) -> Result<HttpResponse, ServiceError> {
+    let now = Utc::now();
+    let since = activity_feed::resolve_since(query.since, now)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let feed =
+        activity_feed::build_activity_feed(&mut conn, authenticated_user.id, since, now).await?;
+
+    Ok(HttpResponse::Ok().json(feed))
+}
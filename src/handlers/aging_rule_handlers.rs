@@ -0,0 +1,249 @@
+// OptiTask/backend-api/src/handlers/aging_rule_handlers.rs
+use crate::aging_rules;
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{
+    CreateTaskAgingRulePayload, NewTaskAgingRule, TaskAgingRule, UpdateTaskAgingRuleChangeset,
+    UpdateTaskAgingRulePayload,
+};
+use crate::permissions::{self, Permission};
+use crate::quotas;
+use crate::schema::task_aging_rules;
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_RULE_NAME_CHARS: usize = 100;
+const MAX_STATUS_CHARS: usize = 50;
+const MAX_LABEL_NAME_CHARS: usize = 50;
+const MAX_RULE_DAYS: i32 = 365;
+
+fn validate_text(field: &str, value: &str, max_chars: usize) -> Result<String, ServiceError> {
+    let value = value.trim();
+    if value.is_empty() || value.chars().count() > max_chars {
+        return Err(ServiceError::ValidationError(format!(
+            "{} must contain between 1 and {} characters",
+            field, max_chars
+        )));
+    }
+    Ok(value.to_string())
+}
+
+fn validate_max_days(max_days: i32) -> Result<i32, ServiceError> {
+    if !(1..=MAX_RULE_DAYS).contains(&max_days) {
+        return Err(ServiceError::ValidationError(format!(
+            "max_days must be between 1 and {}",
+            MAX_RULE_DAYS
+        )));
+    }
+    Ok(max_days)
+}
+
+// Une règle sans label ni notification n'aurait aucun effet
+fn ensure_has_action(add_label: Option<&str>, notify: bool) -> Result<(), ServiceError> {
+    if add_label.is_none() && !notify {
+        return Err(ServiceError::ValidationError(
+            "A rule must add a label, notify, or both".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn find_rule(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    rule_uuid: Uuid,
+) -> Result<TaskAgingRule, ServiceError> {
+    task_aging_rules::table
+        .filter(task_aging_rules::id.eq(rule_uuid))
+        .filter(task_aging_rules::user_id.eq(user_uuid))
+        .select(TaskAgingRule::as_select())
+        .first::<TaskAgingRule>(conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!("Aging rule with id {} not found", rule_uuid))
+        })
+}
+
+// === GET /aging-rules ===
+#[get("")]
+pub async fn list_aging_rules_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let rules = task_aging_rules::table
+        .filter(task_aging_rules::user_id.eq(authenticated_user.id))
+        .order(task_aging_rules::created_at.asc())
+        .select(TaskAgingRule::as_select())
+        .load::<TaskAgingRule>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(rules))
+}
+
+// === POST /aging-rules ===
+#[post("")]
+pub async fn create_aging_rule_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<CreateTaskAgingRulePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let add_label = payload
+        .add_label
+        .as_deref()
+        .map(|label_name| validate_text("add_label", label_name, MAX_LABEL_NAME_CHARS))
+        .transpose()?;
+    ensure_has_action(add_label.as_deref(), payload.notify)?;
+
+    let new_rule = NewTaskAgingRule {
+        user_id: user_uuid,
+        project_id: payload.project_id,
+        name: validate_text("name", &payload.name, MAX_RULE_NAME_CHARS)?,
+        status: validate_text("status", &payload.status, MAX_STATUS_CHARS)?,
+        max_days: validate_max_days(payload.max_days)?,
+        add_label,
+        notify: payload.notify,
+        is_active: payload.is_active,
+    };
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Une règle de projet modifie ses tâches : droit d'écriture requis
+    if let Some(project_uuid) = payload.project_id {
+        permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectWrite)
+            .await?;
+    }
+
+    let existing = task_aging_rules::table
+        .filter(task_aging_rules::user_id.eq(user_uuid))
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?;
+    if quotas::AGING_RULES.is_exceeded(existing) {
+        return Err(ServiceError::ValidationError(format!(
+            "At most {} aging rules can exist; delete an unused one first",
+            quotas::AGING_RULES.limit
+        )));
+    }
+
+    let rule = diesel::insert_into(task_aging_rules::table)
+        .values(&new_rule)
+        .returning(TaskAgingRule::as_returning())
+        .get_result::<TaskAgingRule>(&mut conn)
+        .await?;
+
+    Ok(quotas::AGING_RULES.created_response(StatusCode::CREATED, &rule, existing + 1))
+}
+
+// === PUT /aging-rules/{rule_id_path} ===
+#[put("/{rule_id_path}")]
+pub async fn update_aging_rule_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    rule_id_path: web::Path<Uuid>,
+    payload: web::Json<UpdateTaskAgingRulePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let rule_uuid = rule_id_path.into_inner();
+
+    let changes = UpdateTaskAgingRuleChangeset {
+        name: payload
+            .name
+            .as_deref()
+            .map(|name| validate_text("name", name, MAX_RULE_NAME_CHARS))
+            .transpose()?,
+        status: payload
+            .status
+            .as_deref()
+            .map(|status| validate_text("status", status, MAX_STATUS_CHARS))
+            .transpose()?,
+        max_days: payload.max_days.map(validate_max_days).transpose()?,
+        add_label: match &payload.add_label {
+            Some(Some(label_name)) => Some(Some(validate_text(
+                "add_label",
+                label_name,
+                MAX_LABEL_NAME_CHARS,
+            )?)),
+            Some(None) => Some(None),
+            None => None,
+        },
+        notify: payload.notify,
+        is_active: payload.is_active,
+    };
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let current = find_rule(&mut conn, authenticated_user.id, rule_uuid).await?;
+    let resulting_label = match &changes.add_label {
+        Some(label_name) => label_name.as_deref(),
+        None => current.add_label.as_deref(),
+    };
+    ensure_has_action(resulting_label, changes.notify.unwrap_or(current.notify))?;
+
+    let rule = diesel::update(task_aging_rules::table.find(rule_uuid))
+        .set(&changes)
+        .returning(TaskAgingRule::as_returning())
+        .get_result::<TaskAgingRule>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+// === DELETE /aging-rules/{rule_id_path} ===
+#[delete("/{rule_id_path}")]
+pub async fn delete_aging_rule_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    rule_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let rule_uuid = rule_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(
+        task_aging_rules::table
+            .filter(task_aging_rules::id.eq(rule_uuid))
+            .filter(task_aging_rules::user_id.eq(authenticated_user.id)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "Aging rule with id {} not found",
+            rule_uuid
+        )));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Aging rule deleted"
+    })))
+}
+
+// === POST /aging-rules/{rule_id_path}/dry-run ===
+// Tâches que la règle escaladerait maintenant, sans rien modifier
+#[post("/{rule_id_path}/dry-run")]
+pub async fn dry_run_aging_rule_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    rule_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let rule = find_rule(&mut conn, authenticated_user.id, rule_id_path.into_inner()).await?;
+    let evaluation = aging_rules::evaluate_rule(&mut conn, &rule, true).await?;
+
+    Ok(HttpResponse::Ok().json(evaluation))
+}
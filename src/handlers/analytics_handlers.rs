@@ -1,18 +1,40 @@
 // OptiTask/backend-api/src/handlers/analytics_handlers.rs
 
+use crate::analytics_export;
 use crate::auth_utils::AuthenticatedUser;
+use crate::cost_limits::{CostClass, CostLimiter};
 use crate::db::DbPool;
 use crate::error_handler::ServiceError;
-use crate::models::{AnalyticsQueryPeriod, ProductivityTrendPoint, TimeByProjectStat};
-use actix_web::{get, web, HttpResponse, Result as ActixResult};
-use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday}; // For date handling
+use crate::feature_flags;
+use crate::llm::LlmProvider;
+use crate::models::{
+    AiSummary, AiSummaryResponse, AnalyticsComparisonQuery, AnalyticsExportQuery,
+    AnalyticsQueryPeriod, AnalyticsSnapshot, AnalyticsSnapshotListQuery, FocusAnalyticsResponse,
+    InterruptionReasonStat, MetricDelta, NewAiSummary, NewAnalyticsSnapshot,
+    PeriodComparisonResponse, PeriodMetrics, ProductivityTrendPoint, ProjectTimeComparison,
+    TimeByProjectStat, DONE_TASK_STATUSES,
+};
+use crate::schema::{
+    ai_summaries, analytics_snapshots, pomodoro_interruptions, tasks, time_entries,
+};
+use crate::settings;
+use crate::sla;
+use crate::tracked_time;
+use actix_web::http::header;
+use actix_web::{get, post, web, HttpResponse, Result as ActixResult};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday}; // For date handling
+use diesel::prelude::*;
 use diesel::sql_query; // For executing raw SQL queries if necessary
 use diesel::sql_types::Uuid as DieselUuid;
-use diesel_async::RunQueryDsl; // Async traits // Import SQL types
+use diesel_async::{AsyncPgConnection, RunQueryDsl}; // Async traits // Import SQL types
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
-// Helper to determine start and end dates based on period
-fn calculate_date_range(
+// Helper to determine start and end dates based on period.
+// Weekly periods start on `week_start` (see settings::resolve_week_start).
+pub fn calculate_date_range(
     query_params: &AnalyticsQueryPeriod,
+    week_start: Weekday,
 ) -> Result<(NaiveDate, NaiveDate), ServiceError> {
     let today = Utc::now().date_naive();
 
@@ -27,15 +49,18 @@ fn calculate_date_range(
 
     match query_params.period.as_deref() {
         Some("this_week") => {
-            // Week starts Monday (iso_week)
-            let start_of_week = today
-                .week(Weekday::Mon)
-                .first_day();
-            let end_of_week = today
-                .week(Weekday::Mon)
-                .last_day();
+            let start_of_week = today.week(week_start).first_day();
+            let end_of_week = today.week(week_start).last_day();
             Ok((start_of_week, end_of_week))
         }
+        Some("last_week") => {
+            // Previous full week
+            let last_week_day = today - Duration::days(7);
+            Ok((
+                last_week_day.week(week_start).first_day(),
+                last_week_day.week(week_start).last_day(),
+            ))
+        }
         Some("last_7_days") => Ok((today - Duration::days(6), today)),
         Some("this_month") => {
             let start_of_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap();
@@ -51,62 +76,104 @@ fn calculate_date_range(
         Some("last_30_days") => Ok((today - Duration::days(29), today)),
         None => {
             // Default to "this week" if no period is provided
-            let start_of_week = today.week(Weekday::Mon).first_day();
-            let end_of_week = today.week(Weekday::Mon).last_day();
+            let start_of_week = today.week(week_start).first_day();
+            let end_of_week = today.week(week_start).last_day();
             Ok((start_of_week, end_of_week))
         }
         Some(other) => Err(ServiceError::BadRequest(format!(
-            "Invalid period specified: {}. Supported: this_week, last_7_days, this_month, last_30_days or provide start_date & end_date.",
+            "Invalid period specified: {}. Supported: this_week, last_week, last_7_days, this_month, last_30_days or provide start_date & end_date.",
             other
         ))),
     }
 }
 
+#[derive(QueryableByName)]
+struct TrackedTotal {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    total_duration_seconds: i64,
+}
+
+// Time tracked per project for a user over an inclusive date range (UTC days),
+// read from the daily_tracked_time rollup
+pub async fn load_time_by_project(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<TimeByProjectStat>, ServiceError> {
+    tracked_time::refresh_pending(conn, Some(user_uuid)).await?;
+
+    let query = sql_query(
+        "SELECT p.id as project_id, p.name as project_name, COALESCE(SUM(d.tracked_seconds), 0)::bigint as total_duration_seconds \
+         FROM daily_tracked_time d \
+         JOIN projects p ON d.project_id = p.id \
+         WHERE d.user_id = $1 AND d.day >= $2 AND d.day <= $3 \
+         GROUP BY p.id, p.name \
+         ORDER BY total_duration_seconds DESC"
+    )
+    .bind::<DieselUuid, _>(user_uuid)
+    .bind::<diesel::sql_types::Date, _>(start_date)
+    .bind::<diesel::sql_types::Date, _>(end_date);
+
+    log::debug!("Executing SQL for time_by_project: {:?}", query);
+
+    query
+        .load::<TimeByProjectStat>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Total tracked time (breaks excluded) for a user over an inclusive date range
+pub async fn load_tracked_seconds(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<i64, ServiceError> {
+    tracked_time::refresh_pending(conn, Some(user_uuid)).await?;
+
+    sql_query(
+        "SELECT COALESCE(SUM(tracked_seconds), 0)::bigint as total_duration_seconds \
+         FROM daily_tracked_time \
+         WHERE user_id = $1 AND day >= $2 AND day <= $3",
+    )
+    .bind::<DieselUuid, _>(user_uuid)
+    .bind::<diesel::sql_types::Date, _>(start_date)
+    .bind::<diesel::sql_types::Date, _>(end_date)
+    .get_result::<TrackedTotal>(conn)
+    .await
+    .map(|row| row.total_duration_seconds)
+    .map_err(ServiceError::from)
+}
+
 // === GET /analytics/time-by-project ===
 #[get("/time-by-project")]
 pub async fn get_time_by_project_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
     query_params: web::Query<AnalyticsQueryPeriod>,
 ) -> ActixResult<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Analytics)?;
     log::info!(
         "User {} fetching time_by_project with params: {:?}",
         user_uuid,
         query_params.0 // .0 to access web::Query data
     );
 
-    let (start_date, end_date) = calculate_date_range(&query_params.0)?;
-    // Include the entire end_date day
-    let start_datetime = Utc.from_utc_datetime(&start_date.and_hms_opt(0, 0, 0).unwrap()); // Convert to DateTime<Utc> if needed for TIMESTAMPTZ comparison
-    let end_datetime = Utc.from_utc_datetime(&end_date.and_hms_opt(23, 59, 59).unwrap());
-
     let mut conn = pool.get().await.map_err(ServiceError::from)?;
 
-    // Using sql_query for more flexibility with JOIN and GROUP BY
-    // Make sure column names match your DB and TimeByProjectStat
-    let query = sql_query(
-        "SELECT p.id as project_id, p.name as project_name, COALESCE(SUM(te.duration_seconds), 0) as total_duration_seconds \
-         FROM time_entries te \
-         JOIN tasks t ON te.task_id = t.id \
-         JOIN projects p ON t.project_id = p.id \
-         WHERE te.user_id = $1 AND t.project_id IS NOT NULL \
-         AND te.start_time >= $2 AND te.start_time <= $3 \
-         GROUP BY p.id, p.name \
-         ORDER BY total_duration_seconds DESC"
-    )
-    .bind::<DieselUuid, _>(user_uuid)
-    .bind::<diesel::sql_types::Timestamptz, _>(start_datetime) // Use Timestamptz if start_time is TIMESTAMPTZ
-    .bind::<diesel::sql_types::Timestamptz, _>(end_datetime); // Same
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, query_params.week_start.as_deref())
+            .await?;
+    let (start_date, end_date) = calculate_date_range(&query_params.0, week_start)?;
 
-    log::debug!("Executing SQL for time_by_project: {:?}", query);
-
-    let stats = query
-        .load::<TimeByProjectStat>(&mut conn)
+    let stats = load_time_by_project(&mut conn, user_uuid, start_date, end_date)
         .await
         .map_err(|e| {
             log::error!("Database error in get_time_by_project_handler: {:?}", e);
-            ServiceError::from(e)
+            e
         })?;
 
     Ok(HttpResponse::Ok().json(stats))
@@ -117,39 +184,38 @@ pub async fn get_time_by_project_handler(
 pub async fn get_productivity_trend_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
     query_params: web::Query<AnalyticsQueryPeriod>,
 ) -> ActixResult<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Analytics)?;
     log::info!(
         "User {} fetching productivity_trend with params: {:?}",
         user_uuid,
         query_params.0
     );
 
-    let (start_date_range, end_date_range) = calculate_date_range(&query_params.0)?;
-    // Include the entire end_date day
-    let start_datetime_range =
-        Utc.from_utc_datetime(&start_date_range.and_hms_opt(0, 0, 0).unwrap()); // Convert to DateTime<Utc> if needed for TIMESTAMPTZ comparison
-    let end_datetime_range =
-        Utc.from_utc_datetime(&end_date_range.and_hms_opt(23, 59, 59).unwrap());
-
     let mut conn = pool.get().await.map_err(ServiceError::from)?;
 
-    // Group by day. For TIMESTAMPTZ, we can use DATE(start_time AT TIME ZONE 'UTC')
-    // or a similar function depending on your DB and timezone.
-    // If start_time is just TIMESTAMP (without tz), DATE(start_time) suffices.
-    let query_str = "SELECT DATE(te.start_time AT TIME ZONE 'UTC') as date_point, \
-            COALESCE(SUM(te.duration_seconds), 0) as total_duration_seconds \
-     FROM time_entries te \
-     WHERE te.user_id = $1 \
-     AND te.start_time >= $2 AND te.start_time <= $3 \
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, query_params.week_start.as_deref())
+            .await?;
+    let (start_date_range, end_date_range) = calculate_date_range(&query_params.0, week_start)?;
+
+    tracked_time::refresh_pending(&mut conn, Some(user_uuid)).await?;
+
+    // Days are UTC days, as stored in the daily_tracked_time rollup
+    let query_str = "SELECT d.day as date_point, \
+            COALESCE(SUM(d.tracked_seconds), 0)::bigint as total_duration_seconds \
+     FROM daily_tracked_time d \
+     WHERE d.user_id = $1 AND d.day >= $2 AND d.day <= $3 \
      GROUP BY date_point \
      ORDER BY date_point ASC";
 
     let query = sql_query(query_str)
         .bind::<DieselUuid, _>(user_uuid)
-        .bind::<diesel::sql_types::Timestamptz, _>(start_datetime_range)
-        .bind::<diesel::sql_types::Timestamptz, _>(end_datetime_range);
+        .bind::<diesel::sql_types::Date, _>(start_date_range)
+        .bind::<diesel::sql_types::Date, _>(end_date_range);
 
     log::debug!("Executing SQL for productivity_trend: {:?}", query);
 
@@ -163,3 +229,538 @@ pub async fn get_productivity_trend_handler(
 
     Ok(HttpResponse::Ok().json(trend_points))
 }
+
+// === GET /analytics/export ===
+// Excel workbook with time by project, the daily trend and a per-task breakdown
+#[get("/export")]
+pub async fn export_analytics_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    query_params: web::Query<AnalyticsExportQuery>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let query = query_params.into_inner();
+    match query.format.as_deref() {
+        None | Some("xlsx") => {}
+        Some(other) => {
+            return Err(ServiceError::BadRequest(format!(
+                "Invalid export format: {}. Supported: xlsx",
+                other
+            )))
+        }
+    }
+    cost_limiter.charge(user_uuid, CostClass::Export)?;
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, query.week_start.as_deref()).await?;
+    let (start_date, end_date) = calculate_date_range(
+        &AnalyticsQueryPeriod {
+            period: query.period,
+            start_date: query.start_date,
+            end_date: query.end_date,
+            week_start: None,
+        },
+        week_start,
+    )?;
+    analytics_export::validate_export_range(start_date, end_date)?;
+
+    let workbook =
+        analytics_export::build_analytics_workbook(&mut conn, user_uuid, start_date, end_date)
+            .await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(analytics_export::XLSX_CONTENT_TYPE)
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"analytics-{}-{}.xlsx\"",
+                start_date, end_date
+            ),
+        ))
+        .streaming(analytics_export::stream_workbook(workbook)))
+}
+
+// Tracked time, completed tasks and per-project split for one period
+async fn load_period_metrics(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<PeriodMetrics, ServiceError> {
+    let (start_datetime, end_datetime) = period_bounds(start_date, end_date);
+
+    let tracked_seconds = load_tracked_seconds(conn, user_uuid, start_date, end_date).await?;
+
+    // Same completion criterion as the AI summary: done status, last updated in the period
+    let tasks_completed = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .filter(tasks::status.eq_any(DONE_TASK_STATUSES))
+        .filter(tasks::updated_at.ge(start_datetime))
+        .filter(tasks::updated_at.le(end_datetime))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+
+    let time_by_project = load_time_by_project(conn, user_uuid, start_date, end_date).await?;
+
+    Ok(PeriodMetrics {
+        start_date,
+        end_date,
+        tracked_seconds,
+        tasks_completed,
+        time_by_project,
+    })
+}
+
+// === GET /analytics/compare ===
+// Same metric set for two periods plus deltas, e.g. ?period=this_week&against=last_week
+#[get("/compare")]
+pub async fn compare_periods_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    query_params: web::Query<AnalyticsComparisonQuery>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Analytics)?;
+    let query_params = query_params.into_inner();
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, query_params.week_start.as_deref())
+            .await?;
+    let (start_date, end_date) = calculate_date_range(
+        &AnalyticsQueryPeriod {
+            period: query_params.period,
+            start_date: query_params.start_date,
+            end_date: query_params.end_date,
+            week_start: None,
+        },
+        week_start,
+    )?;
+    let (against_start, against_end) = if query_params.against.is_some()
+        || query_params.against_start_date.is_some()
+        || query_params.against_end_date.is_some()
+    {
+        calculate_date_range(
+            &AnalyticsQueryPeriod {
+                period: query_params.against,
+                start_date: query_params.against_start_date,
+                end_date: query_params.against_end_date,
+                week_start: None,
+            },
+            week_start,
+        )?
+    } else {
+        // Default: the window of the same length right before the current one
+        let length = end_date - start_date;
+        let previous_end = start_date - Duration::days(1);
+        (previous_end - length, previous_end)
+    };
+
+    let current = load_period_metrics(&mut conn, user_uuid, start_date, end_date).await?;
+    let previous = load_period_metrics(&mut conn, user_uuid, against_start, against_end).await?;
+
+    // Union of the projects present in either period, current ordering first
+    let mut time_by_project: Vec<ProjectTimeComparison> = current
+        .time_by_project
+        .iter()
+        .map(|stat| ProjectTimeComparison {
+            project_id: stat.project_id,
+            project_name: stat.project_name.clone(),
+            tracked_seconds: MetricDelta::new(
+                stat.total_duration_seconds,
+                previous
+                    .time_by_project
+                    .iter()
+                    .find(|prev| prev.project_id == stat.project_id)
+                    .map_or(0, |prev| prev.total_duration_seconds),
+            ),
+        })
+        .collect();
+    time_by_project.extend(
+        previous
+            .time_by_project
+            .iter()
+            .filter(|prev| {
+                !current
+                    .time_by_project
+                    .iter()
+                    .any(|stat| stat.project_id == prev.project_id)
+            })
+            .map(|prev| ProjectTimeComparison {
+                project_id: prev.project_id,
+                project_name: prev.project_name.clone(),
+                tracked_seconds: MetricDelta::new(0, prev.total_duration_seconds),
+            }),
+    );
+
+    Ok(HttpResponse::Ok().json(PeriodComparisonResponse {
+        tracked_seconds: MetricDelta::new(current.tracked_seconds, previous.tracked_seconds),
+        tasks_completed: MetricDelta::new(current.tasks_completed, previous.tasks_completed),
+        time_by_project,
+        current,
+        previous,
+    }))
+}
+
+// === GET /analytics/focus ===
+// Completed pomodoro sessions in the period and the interruptions logged during them
+#[get("/focus")]
+pub async fn get_focus_analytics_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    query_params: web::Query<AnalyticsQueryPeriod>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Analytics)?;
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, query_params.week_start.as_deref())
+            .await?;
+    let (start_date, end_date) = calculate_date_range(&query_params.0, week_start)?;
+    let (start_datetime, end_datetime) = period_bounds(start_date, end_date);
+
+    let sessions = time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::is_pomodoro_session.eq(true))
+        .filter(time_entries::end_time.is_not_null())
+        .filter(time_entries::start_time.ge(start_datetime))
+        .filter(time_entries::start_time.le(end_datetime))
+        .select((time_entries::id, time_entries::duration_seconds))
+        .load::<(Uuid, Option<i32>)>(&mut conn)
+        .await?;
+    let session_ids: Vec<Uuid> = sessions.iter().map(|(session_id, _)| *session_id).collect();
+    let focus_seconds = sessions
+        .iter()
+        .filter_map(|(_, duration)| *duration)
+        .map(i64::from)
+        .sum();
+
+    let interruptions = pomodoro_interruptions::table
+        .filter(pomodoro_interruptions::time_entry_id.eq_any(&session_ids))
+        .select((
+            pomodoro_interruptions::time_entry_id,
+            pomodoro_interruptions::reason,
+        ))
+        .load::<(Uuid, String)>(&mut conn)
+        .await?;
+
+    let mut counts_by_reason: HashMap<&str, i64> = HashMap::new();
+    let mut interrupted_sessions: HashSet<Uuid> = HashSet::new();
+    for (session_id, reason) in &interruptions {
+        *counts_by_reason.entry(reason.as_str()).or_default() += 1;
+        interrupted_sessions.insert(*session_id);
+    }
+    let mut interruptions_by_reason: Vec<InterruptionReasonStat> = counts_by_reason
+        .into_iter()
+        .map(|(reason, count)| InterruptionReasonStat {
+            reason: reason.to_string(),
+            count,
+        })
+        .collect();
+    interruptions_by_reason.sort_by(|a, b| b.count.cmp(&a.count).then(a.reason.cmp(&b.reason)));
+
+    let pomodoro_sessions = sessions.len() as i64;
+    let interruption_count = interruptions.len() as i64;
+    Ok(HttpResponse::Ok().json(FocusAnalyticsResponse {
+        start_date,
+        end_date,
+        pomodoro_sessions,
+        focus_seconds,
+        interruptions: interruption_count,
+        uninterrupted_sessions: pomodoro_sessions - interrupted_sessions.len() as i64,
+        interruptions_per_session: (pomodoro_sessions > 0).then(|| {
+            (interruption_count as f64 / pomodoro_sessions as f64 * 100.0).round() / 100.0
+        }),
+        interruptions_by_reason,
+    }))
+}
+
+// === POST /analytics/snapshots ===
+// Freezes the metrics of a finished period (e.g. ?period=last_week) so that later edits
+// to time entries or tasks do not change reports already used for payroll or invoicing
+#[post("/snapshots")]
+pub async fn create_analytics_snapshot_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    query_params: web::Query<AnalyticsQueryPeriod>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::AnalyticsSnapshot)?;
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, query_params.week_start.as_deref())
+            .await?;
+    let (start_date, end_date) = calculate_date_range(&query_params.0, week_start)?;
+    if end_date >= Utc::now().date_naive() {
+        return Err(ServiceError::ValidationError(format!(
+            "Only finished periods can be snapshotted (period ends {})",
+            end_date
+        )));
+    }
+
+    let metrics = load_period_metrics(&mut conn, user_uuid, start_date, end_date).await?;
+
+    // Snapshots are immutable: an existing one for the same period is never replaced
+    let snapshot = diesel::insert_into(analytics_snapshots::table)
+        .values(&NewAnalyticsSnapshot {
+            user_id: user_uuid,
+            period_start: start_date,
+            period_end: end_date,
+            metrics: serde_json::to_value(&metrics)?,
+        })
+        .on_conflict_do_nothing()
+        .returning(AnalyticsSnapshot::as_returning())
+        .get_result::<AnalyticsSnapshot>(&mut conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)?
+        .ok_or_else(|| {
+            ServiceError::ConflictError(format!(
+                "A snapshot already exists for {} to {}",
+                start_date, end_date
+            ))
+        })?;
+
+    Ok(HttpResponse::Created().json(snapshot))
+}
+
+// === GET /analytics/snapshots?from=&to= ===
+// Most recent periods first
+#[get("/snapshots")]
+pub async fn list_analytics_snapshots_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query_params: web::Query<AnalyticsSnapshotListQuery>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let mut snapshot_query = analytics_snapshots::table
+        .filter(analytics_snapshots::user_id.eq(authenticated_user.id))
+        .into_boxed();
+    if let Some(from) = query_params.from {
+        snapshot_query = snapshot_query.filter(analytics_snapshots::period_end.ge(from));
+    }
+    if let Some(to) = query_params.to {
+        snapshot_query = snapshot_query.filter(analytics_snapshots::period_start.le(to));
+    }
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let snapshots = snapshot_query
+        .order((
+            analytics_snapshots::period_start.desc(),
+            analytics_snapshots::period_end.desc(),
+        ))
+        .select(AnalyticsSnapshot::as_select())
+        .load::<AnalyticsSnapshot>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(snapshots))
+}
+
+// === GET /analytics/sla ===
+// SLA compliance per project/label target, for tasks created in the period
+#[get("/sla")]
+pub async fn get_sla_report_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    query_params: web::Query<AnalyticsQueryPeriod>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    cost_limiter.charge(authenticated_user.id, CostClass::Analytics)?;
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let week_start = settings::resolve_week_start(
+        &mut conn,
+        authenticated_user.id,
+        query_params.week_start.as_deref(),
+    )
+    .await?;
+    let (start_date, end_date) = calculate_date_range(&query_params.0, week_start)?;
+    let (start_datetime, end_datetime) = period_bounds(start_date, end_date);
+
+    let report = sla::compliance_report(
+        &mut conn,
+        authenticated_user.id,
+        start_datetime,
+        end_datetime,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+// === POST /analytics/sla/check ===
+// Runs the breach check now for the user's targets (the job runs hourly)
+#[post("/sla/check")]
+pub async fn check_sla_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let flagged = sla::check_breaches(&mut conn, Some(authenticated_user.id)).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "status": "success",
+        "message": format!("{} new SLA breach(es) flagged", flagged),
+        "flagged": flagged
+    })))
+}
+
+// Converts an inclusive date range to UTC instants covering whole days
+pub fn period_bounds(start_date: NaiveDate, end_date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    (
+        Utc.from_utc_datetime(&start_date.and_hms_opt(0, 0, 0).unwrap()),
+        Utc.from_utc_datetime(&end_date.and_hms_opt(23, 59, 59).unwrap()),
+    )
+}
+
+const AI_SUMMARY_SYSTEM_PROMPT: &str = "You are a productivity coach. Write a short, encouraging \
+weekly review (at most 150 words) of the user's work based only on the facts provided: \
+what was accomplished, where time went, and one concrete suggestion for next week.";
+
+// === POST /analytics/ai-summary ===
+#[post("/ai-summary")]
+pub async fn generate_ai_summary_handler(
+    pool: web::Data<DbPool>,
+    llm_provider: web::Data<dyn LlmProvider>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    query_params: web::Query<AnalyticsQueryPeriod>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::AiSummary)?;
+    log::info!(
+        "User {} requesting ai_summary with params: {:?}",
+        user_uuid,
+        query_params.0
+    );
+
+    let (start_date, end_date, completed_titles, project_stats, total_seconds) = {
+        let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+        feature_flags::require_enabled(&mut conn, user_uuid, feature_flags::FLAG_AI_SUMMARY)
+            .await?;
+
+        let week_start =
+            settings::resolve_week_start(&mut conn, user_uuid, query_params.week_start.as_deref())
+                .await?;
+        let (start_date, end_date) = calculate_date_range(&query_params.0, week_start)?;
+        let (start_datetime, end_datetime) = period_bounds(start_date, end_date);
+
+        // Return the cached summary if one was already generated for this period
+        let cached = ai_summaries::table
+            .filter(ai_summaries::user_id.eq(user_uuid))
+            .filter(ai_summaries::period_start.eq(start_date))
+            .filter(ai_summaries::period_end.eq(end_date))
+            .select(AiSummary::as_select())
+            .first::<AiSummary>(&mut conn)
+            .await
+            .optional()
+            .map_err(ServiceError::from)?;
+
+        if let Some(summary) = cached {
+            return Ok(HttpResponse::Ok().json(AiSummaryResponse {
+                summary,
+                cached: true,
+            }));
+        }
+
+        let completed_titles = tasks::table
+            .filter(tasks::user_id.eq(user_uuid))
+            .filter(tasks::status.eq_any(DONE_TASK_STATUSES))
+            .filter(tasks::updated_at.ge(start_datetime))
+            .filter(tasks::updated_at.le(end_datetime))
+            .order(tasks::updated_at.asc())
+            .select(tasks::title)
+            .load::<String>(&mut conn)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let project_stats =
+            load_time_by_project(&mut conn, user_uuid, start_date, end_date).await?;
+
+        let total_seconds =
+            load_tracked_seconds(&mut conn, user_uuid, start_date, end_date).await?;
+
+        (
+            start_date,
+            end_date,
+            completed_titles,
+            project_stats,
+            total_seconds,
+        )
+        // The connection is released here so it is not held during the LLM call
+    };
+
+    let mut facts = format!(
+        "Weekly review for {} to {}.\nTotal tracked time: {}.\n",
+        start_date,
+        end_date,
+        crate::reports::format_duration(total_seconds)
+    );
+    if completed_titles.is_empty() {
+        facts.push_str("No tasks were completed.\n");
+    } else {
+        facts.push_str(&format!("Completed tasks ({}):\n", completed_titles.len()));
+        for title in &completed_titles {
+            facts.push_str(&format!("- {}\n", title));
+        }
+    }
+    if !project_stats.is_empty() {
+        facts.push_str("Time by project:\n");
+        for stat in &project_stats {
+            facts.push_str(&format!(
+                "- {}: {}\n",
+                stat.project_name,
+                crate::reports::format_duration(stat.total_duration_seconds)
+            ));
+        }
+    }
+
+    let summary_text = llm_provider
+        .complete(AI_SUMMARY_SYSTEM_PROMPT, &facts)
+        .await?;
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    // A concurrent request may have stored a summary in the meantime: keep the first one
+    diesel::insert_into(ai_summaries::table)
+        .values(&NewAiSummary {
+            user_id: user_uuid,
+            period_start: start_date,
+            period_end: end_date,
+            summary: summary_text,
+            provider: llm_provider.name(),
+        })
+        .on_conflict_do_nothing()
+        .execute(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let summary = ai_summaries::table
+        .filter(ai_summaries::user_id.eq(user_uuid))
+        .filter(ai_summaries::period_start.eq(start_date))
+        .filter(ai_summaries::period_end.eq(end_date))
+        .select(AiSummary::as_select())
+        .first::<AiSummary>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Created().json(AiSummaryResponse {
+        summary,
+        cached: false,
+    }))
+}
@@ -0,0 +1,275 @@
+// OptiTask/backend-api/src/handlers/announcement_handlers.rs
+use crate::admin::AdminConfig;
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{
+    Announcement, AnnouncementApiResponse, AnnouncementListQuery, CreateAnnouncementPayload,
+    NewAnnouncement, UpdateAnnouncementChangeset, UpdateAnnouncementPayload, ANNOUNCEMENT_KINDS,
+};
+use crate::schema::{announcement_acks, announcements};
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_ANNOUNCEMENT_TITLE_CHARS: usize = 200;
+const MAX_ANNOUNCEMENT_BODY_CHARS: usize = 10_000;
+// Nombre d'annonces renvoyées aux clients
+const MAX_LISTED_ANNOUNCEMENTS: i64 = 50;
+
+fn validate_title(title: &str) -> Result<String, ServiceError> {
+    let title = title.trim();
+    if title.is_empty() || title.chars().count() > MAX_ANNOUNCEMENT_TITLE_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "title must contain between 1 and {} characters",
+            MAX_ANNOUNCEMENT_TITLE_CHARS
+        )));
+    }
+    Ok(title.to_string())
+}
+
+fn validate_body(body: &str) -> Result<String, ServiceError> {
+    let body = body.trim();
+    if body.is_empty() || body.chars().count() > MAX_ANNOUNCEMENT_BODY_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "body must contain between 1 and {} characters",
+            MAX_ANNOUNCEMENT_BODY_CHARS
+        )));
+    }
+    Ok(body.to_string())
+}
+
+fn validate_kind(kind: &str) -> Result<String, ServiceError> {
+    let normalized = kind.trim().to_ascii_lowercase();
+    if !ANNOUNCEMENT_KINDS.contains(&normalized.as_str()) {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid kind '{}'. Supported: {}",
+            kind,
+            ANNOUNCEMENT_KINDS.join(", ")
+        )));
+    }
+    Ok(normalized)
+}
+
+// === GET /announcements ===
+// Annonces publiées (les plus récentes en tête), avec la date de lecture de l'utilisateur.
+// ?since= ne renvoie que celles publiées après la date donnée.
+#[get("")]
+pub async fn list_announcements_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<AnnouncementListQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let mut announcement_query = announcements::table
+        .left_join(
+            announcement_acks::table.on(announcement_acks::announcement_id
+                .eq(announcements::id)
+                .and(announcement_acks::user_id.eq(authenticated_user.id))),
+        )
+        .filter(announcements::published_at.le(Utc::now()))
+        .into_boxed();
+    if let Some(since) = query.since {
+        announcement_query = announcement_query.filter(announcements::published_at.gt(since));
+    }
+
+    let announcement_list = announcement_query
+        .order(announcements::published_at.desc())
+        .limit(MAX_LISTED_ANNOUNCEMENTS)
+        .select((
+            Announcement::as_select(),
+            announcement_acks::acked_at.nullable(),
+        ))
+        .load::<(Announcement, Option<DateTime<Utc>>)>(&mut conn)
+        .await?
+        .into_iter()
+        .map(|(announcement, acked_at)| AnnouncementApiResponse {
+            announcement,
+            acked_at,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(announcement_list))
+}
+
+// === POST /announcements/{announcement_id_path}/ack ===
+// Marque l'annonce comme lue (idempotent : la première date de lecture est conservée)
+#[post("/{announcement_id_path}/ack")]
+pub async fn ack_announcement_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    announcement_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let announcement_uuid = announcement_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let announcement = announcements::table
+        .filter(announcements::id.eq(announcement_uuid))
+        .filter(announcements::published_at.le(Utc::now()))
+        .select(Announcement::as_select())
+        .first::<Announcement>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!(
+                "Announcement with id {} not found",
+                announcement_uuid
+            ))
+        })?;
+
+    diesel::insert_into(announcement_acks::table)
+        .values((
+            announcement_acks::announcement_id.eq(announcement_uuid),
+            announcement_acks::user_id.eq(authenticated_user.id),
+        ))
+        .on_conflict_do_nothing()
+        .execute(&mut conn)
+        .await?;
+
+    let acked_at = announcement_acks::table
+        .find((announcement_uuid, authenticated_user.id))
+        .select(announcement_acks::acked_at)
+        .first::<DateTime<Utc>>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(AnnouncementApiResponse {
+        announcement,
+        acked_at: Some(acked_at),
+    }))
+}
+
+// === GET /admin/announcements ===
+// Toutes les annonces, brouillons et publications programmées compris
+#[get("/announcements")]
+pub async fn admin_list_announcements_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let announcement_list = announcements::table
+        .order((
+            announcements::published_at.desc().nulls_first(),
+            announcements::created_at.desc(),
+        ))
+        .select(Announcement::as_select())
+        .load::<Announcement>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(announcement_list))
+}
+
+// === POST /admin/announcements ===
+#[post("/announcements")]
+pub async fn admin_create_announcement_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<CreateAnnouncementPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+
+    let new_announcement = NewAnnouncement {
+        title: validate_title(&payload.title)?,
+        body: validate_body(&payload.body)?,
+        kind: payload
+            .kind
+            .as_deref()
+            .map(validate_kind)
+            .transpose()?
+            .unwrap_or_else(|| ANNOUNCEMENT_KINDS[0].to_string()),
+        published_at: payload.published_at,
+        created_by: authenticated_user.id,
+    };
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let created = diesel::insert_into(announcements::table)
+        .values(&new_announcement)
+        .returning(Announcement::as_returning())
+        .get_result::<Announcement>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Created().json(created))
+}
+
+// === PUT /admin/announcements/{announcement_id_path} ===
+// Mise à jour partielle ; published_at: null repasse l'annonce en brouillon
+#[put("/announcements/{announcement_id_path}")]
+pub async fn admin_update_announcement_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    announcement_id_path: web::Path<Uuid>,
+    payload: web::Json<UpdateAnnouncementPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let announcement_uuid = announcement_id_path.into_inner();
+
+    let changes = UpdateAnnouncementChangeset {
+        title: payload.title.as_deref().map(validate_title).transpose()?,
+        body: payload.body.as_deref().map(validate_body).transpose()?,
+        kind: payload.kind.as_deref().map(validate_kind).transpose()?,
+        published_at: payload.published_at,
+    };
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let updated = diesel::update(announcements::table.find(announcement_uuid))
+        .set(&changes)
+        .returning(Announcement::as_returning())
+        .get_result::<Announcement>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!(
+                "Announcement with id {} not found",
+                announcement_uuid
+            ))
+        })?;
+
+    Ok(HttpResponse::Ok().json(updated))
+}
+
+// === DELETE /admin/announcements/{announcement_id_path} ===
+#[delete("/announcements/{announcement_id_path}")]
+pub async fn admin_delete_announcement_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    announcement_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let announcement_uuid = announcement_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(announcements::table.find(announcement_uuid))
+        .execute(&mut conn)
+        .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "Announcement with id {} not found",
+            announcement_uuid
+        )));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Announcement deleted"
+    })))
+}
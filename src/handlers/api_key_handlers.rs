@@ -0,0 +1,118 @@
+// OptiTask/backend-api/src/handlers/api_key_handlers.rs
+use crate::api_keys;
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{ApiKey, CreateApiKeyPayload, CreatedApiKey};
+use crate::quotas;
+use crate::schema::api_keys as api_keys_table;
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, post, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_API_KEY_NAME_CHARS: usize = 100;
+
+// === GET /settings/api-keys ===
+#[get("/api-keys")]
+pub async fn list_api_keys_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let keys = api_keys_table::table
+        .filter(api_keys_table::user_id.eq(authenticated_user.id))
+        .order(api_keys_table::created_at.desc())
+        .select(ApiKey::as_select())
+        .load::<ApiKey>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(keys))
+}
+
+// === POST /settings/api-keys ===
+// La clé générée n'est visible que dans cette réponse
+#[post("/api-keys")]
+pub async fn create_api_key_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<CreateApiKeyPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let name = payload.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_API_KEY_NAME_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "name must contain between 1 and {} characters",
+            MAX_API_KEY_NAME_CHARS
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let existing = api_keys_table::table
+        .filter(api_keys_table::user_id.eq(user_uuid))
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?;
+    if quotas::API_KEYS.is_exceeded(existing) {
+        return Err(ServiceError::ValidationError(format!(
+            "At most {} API keys can exist; revoke an unused one first",
+            quotas::API_KEYS.limit
+        )));
+    }
+
+    let key = api_keys::generate_api_key();
+    let api_key = diesel::insert_into(api_keys_table::table)
+        .values((
+            api_keys_table::user_id.eq(user_uuid),
+            api_keys_table::name.eq(&name),
+            api_keys_table::key_prefix.eq(api_keys::key_prefix(&key)),
+            api_keys_table::key_hash.eq(api_keys::hash_api_key(&key)),
+        ))
+        .returning(ApiKey::as_returning())
+        .get_result::<ApiKey>(&mut conn)
+        .await?;
+
+    Ok(quotas::API_KEYS.created_response(
+        StatusCode::CREATED,
+        &CreatedApiKey { api_key, key },
+        existing + 1,
+    ))
+}
+
+// === DELETE /settings/api-keys/{api_key_id_path} ===
+#[delete("/api-keys/{api_key_id_path}")]
+pub async fn revoke_api_key_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    api_key_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let api_key_uuid = api_key_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(
+        api_keys_table::table
+            .filter(api_keys_table::id.eq(api_key_uuid))
+            .filter(api_keys_table::user_id.eq(authenticated_user.id)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "API key with id {} not found",
+            api_key_uuid
+        )));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "API key revoked"
+    })))
+}
@@ -0,0 +1,121 @@
+// OptiTask/backend-api/src/handlers/app_password_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::caldav;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{AppPassword, CreateAppPasswordPayload, CreatedAppPassword};
+use crate::quotas;
+use crate::schema::app_passwords;
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, post, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_APP_PASSWORD_NAME_CHARS: usize = 100;
+
+// === GET /settings/app-passwords ===
+#[get("/app-passwords")]
+pub async fn list_app_passwords_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let passwords = app_passwords::table
+        .filter(app_passwords::user_id.eq(authenticated_user.id))
+        .order(app_passwords::created_at.desc())
+        .select(AppPassword::as_select())
+        .load::<AppPassword>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(passwords))
+}
+
+// === POST /settings/app-passwords ===
+// Le mot de passe généré n'est visible que dans cette réponse
+#[post("/app-passwords")]
+pub async fn create_app_password_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<CreateAppPasswordPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let name = payload.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_APP_PASSWORD_NAME_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "name must contain between 1 and {} characters",
+            MAX_APP_PASSWORD_NAME_CHARS
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let existing = app_passwords::table
+        .filter(app_passwords::user_id.eq(user_uuid))
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?;
+    if quotas::APP_PASSWORDS.is_exceeded(existing) {
+        return Err(ServiceError::ValidationError(format!(
+            "At most {} app passwords can exist; revoke an unused one first",
+            quotas::APP_PASSWORDS.limit
+        )));
+    }
+
+    let password = caldav::generate_app_password();
+    let app_password = diesel::insert_into(app_passwords::table)
+        .values((
+            app_passwords::user_id.eq(user_uuid),
+            app_passwords::name.eq(&name),
+            app_passwords::password_hash.eq(caldav::hash_app_password(&password)),
+        ))
+        .returning(AppPassword::as_returning())
+        .get_result::<AppPassword>(&mut conn)
+        .await?;
+
+    Ok(quotas::APP_PASSWORDS.created_response(
+        StatusCode::CREATED,
+        &CreatedAppPassword {
+            app_password,
+            username: user_uuid,
+            password,
+        },
+        existing + 1,
+    ))
+}
+
+// === DELETE /settings/app-passwords/{app_password_id_path} ===
+#[delete("/app-passwords/{app_password_id_path}")]
+pub async fn revoke_app_password_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    app_password_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let app_password_uuid = app_password_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(
+        app_passwords::table
+            .filter(app_passwords::id.eq(app_password_uuid))
+            .filter(app_passwords::user_id.eq(authenticated_user.id)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "App password with id {} not found",
+            app_password_uuid
+        )));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "App password revoked"
+    })))
+}
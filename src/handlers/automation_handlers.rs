@@ -0,0 +1,189 @@
+// OptiTask/backend-api/src/handlers/automation_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::automations;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{
+    AutomationConditions, AutomationRule, CreateAutomationRulePayload, NewAutomationRule,
+    UpdateAutomationRuleChangeset, UpdateAutomationRulePayload,
+};
+use crate::quotas;
+use crate::schema::automation_rules;
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_RULE_NAME_CHARS: usize = 100;
+
+fn validate_name(name: &str) -> Result<String, ServiceError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_RULE_NAME_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "name must contain between 1 and {} characters",
+            MAX_RULE_NAME_CHARS
+        )));
+    }
+    Ok(name.to_string())
+}
+
+// === GET /automations ===
+#[get("")]
+pub async fn list_automation_rules_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let rules = automation_rules::table
+        .filter(automation_rules::user_id.eq(authenticated_user.id))
+        .order(automation_rules::created_at.asc())
+        .select(AutomationRule::as_select())
+        .load::<AutomationRule>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(rules))
+}
+
+// === POST /automations ===
+#[post("")]
+pub async fn create_automation_rule_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<CreateAutomationRulePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let payload = payload.into_inner();
+    let rule_name = validate_name(&payload.name)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    automations::validate_rule(&mut conn, user_uuid, &payload.conditions, &payload.actions).await?;
+
+    let existing = automation_rules::table
+        .filter(automation_rules::user_id.eq(user_uuid))
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?;
+    if quotas::AUTOMATION_RULES.is_exceeded(existing) {
+        return Err(ServiceError::ValidationError(format!(
+            "At most {} automation rules can exist; delete an unused one first",
+            quotas::AUTOMATION_RULES.limit
+        )));
+    }
+
+    let rule = diesel::insert_into(automation_rules::table)
+        .values(&NewAutomationRule {
+            user_id: user_uuid,
+            name: rule_name,
+            trigger: payload.trigger.as_str().to_string(),
+            conditions: serde_json::to_value(&payload.conditions)?,
+            actions: serde_json::to_value(&payload.actions)?,
+            is_active: payload.is_active,
+        })
+        .returning(AutomationRule::as_returning())
+        .get_result::<AutomationRule>(&mut conn)
+        .await?;
+
+    Ok(quotas::AUTOMATION_RULES.created_response(StatusCode::CREATED, &rule, existing + 1))
+}
+
+// === PUT /automations/{rule_id_path} ===
+#[put("/{rule_id_path}")]
+pub async fn update_automation_rule_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    rule_id_path: web::Path<Uuid>,
+    payload: web::Json<UpdateAutomationRulePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let rule_uuid = rule_id_path.into_inner();
+    let payload = payload.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let current = automation_rules::table
+        .filter(automation_rules::id.eq(rule_uuid))
+        .filter(automation_rules::user_id.eq(user_uuid))
+        .select(AutomationRule::as_select())
+        .first::<AutomationRule>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!("Automation rule with id {} not found", rule_uuid))
+        })?;
+
+    // Les conditions et actions résultantes sont validées ensemble
+    if payload.conditions.is_some() || payload.actions.is_some() {
+        let conditions = match &payload.conditions {
+            Some(conditions) => conditions.clone(),
+            None => serde_json::from_value::<AutomationConditions>(current.conditions.clone())?,
+        };
+        let actions = match &payload.actions {
+            Some(actions) => actions.clone(),
+            None => serde_json::from_value(current.actions.clone())?,
+        };
+        automations::validate_rule(&mut conn, user_uuid, &conditions, &actions).await?;
+    }
+
+    let changes = UpdateAutomationRuleChangeset {
+        name: payload.name.as_deref().map(validate_name).transpose()?,
+        trigger: payload.trigger.map(|trigger| trigger.as_str().to_string()),
+        conditions: payload
+            .conditions
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?,
+        actions: payload
+            .actions
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?,
+        is_active: payload.is_active,
+    };
+
+    let rule = diesel::update(automation_rules::table.find(rule_uuid))
+        .set(&changes)
+        .returning(AutomationRule::as_returning())
+        .get_result::<AutomationRule>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+// === DELETE /automations/{rule_id_path} ===
+#[delete("/{rule_id_path}")]
+pub async fn delete_automation_rule_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    rule_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let rule_uuid = rule_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(
+        automation_rules::table
+            .filter(automation_rules::id.eq(rule_uuid))
+            .filter(automation_rules::user_id.eq(authenticated_user.id)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "Automation rule with id {} not found",
+            rule_uuid
+        )));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Automation rule deleted"
+    })))
+}
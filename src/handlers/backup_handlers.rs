@@ -0,0 +1,262 @@
+// OptiTask/backend-api/src/handlers/backup_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::backup_restore::{self, RestoreRequest};
+use crate::backups;
+use crate::cost_limits::{CostClass, CostLimiter};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::maintenance;
+use crate::models::{BackupConfig, BackupStatusResponse, ConfigureBackupPayload};
+use crate::schema::backup_configs;
+use actix_multipart::Multipart;
+use actix_web::{get, post, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use uuid::Uuid;
+
+const MAX_CREDENTIAL_CHARS: usize = 500;
+// Taille maximale acceptée pour l'archive à restaurer (20 Mo)
+const MAX_RESTORE_ARCHIVE_BYTES: usize = 20 * 1024 * 1024;
+
+#[derive(Deserialize, Debug)]
+pub struct RestoreQueryParams {
+    // replace ou merge
+    pub mode: String,
+}
+
+fn status_response(config: BackupConfig, encryption_key: Option<String>) -> BackupStatusResponse {
+    BackupStatusResponse {
+        next_run_at: backups::next_run_at(&config),
+        config,
+        encryption_key,
+    }
+}
+
+fn not_configured() -> ServiceError {
+    ServiceError::NotFound("No backup target has been configured".to_string())
+}
+
+// === POST /backups/configure ===
+// Crée ou remplace la cible. La clé de chiffrement, générée à la première configuration,
+// n'est renvoyée qu'à ce moment-là ; une reconfiguration la conserve.
+#[post("/configure")]
+pub async fn configure_backup_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<ConfigureBackupPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let payload = payload.into_inner();
+    let target_type = payload.target_type.trim().to_lowercase();
+    let endpoint_url = payload.endpoint_url.trim().to_string();
+    let bucket = payload
+        .bucket
+        .map(|bucket| bucket.trim().to_string())
+        .filter(|bucket| !bucket.is_empty());
+    let region = payload
+        .region
+        .map(|region| region.trim().to_string())
+        .filter(|region| !region.is_empty());
+    backups::validate_target(
+        &target_type,
+        &endpoint_url,
+        bucket.as_deref(),
+        region.as_deref(),
+    )?;
+    let path_prefix = backups::normalize_path_prefix(payload.path_prefix.as_deref())?;
+    let username = payload.username.trim().to_string();
+    if username.is_empty()
+        || username.chars().count() > MAX_CREDENTIAL_CHARS
+        || payload.secret.is_empty()
+        || payload.secret.chars().count() > MAX_CREDENTIAL_CHARS
+    {
+        return Err(ServiceError::ValidationError(format!(
+            "username and secret must contain between 1 and {} characters",
+            MAX_CREDENTIAL_CHARS
+        )));
+    }
+    let enabled = payload.enabled.unwrap_or(true);
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let (config, encryption_key) = match backups::load_config(&mut conn, user_uuid).await? {
+        Some(_) => {
+            let config = diesel::update(backup_configs::table.find(user_uuid))
+                .set((
+                    backup_configs::target_type.eq(&target_type),
+                    backup_configs::endpoint_url.eq(&endpoint_url),
+                    backup_configs::bucket.eq(&bucket),
+                    backup_configs::region.eq(&region),
+                    backup_configs::path_prefix.eq(&path_prefix),
+                    backup_configs::username.eq(&username),
+                    backup_configs::secret.eq(&payload.secret),
+                    backup_configs::enabled.eq(enabled),
+                    backup_configs::last_error.eq(None::<String>),
+                    backup_configs::updated_at.eq(Utc::now()),
+                ))
+                .returning(BackupConfig::as_returning())
+                .get_result::<BackupConfig>(&mut conn)
+                .await?;
+            (config, None)
+        }
+        None => {
+            let encryption_key = backups::generate_encryption_key()?;
+            let config = diesel::insert_into(backup_configs::table)
+                .values((
+                    backup_configs::user_id.eq(user_uuid),
+                    backup_configs::target_type.eq(&target_type),
+                    backup_configs::endpoint_url.eq(&endpoint_url),
+                    backup_configs::bucket.eq(&bucket),
+                    backup_configs::region.eq(&region),
+                    backup_configs::path_prefix.eq(&path_prefix),
+                    backup_configs::username.eq(&username),
+                    backup_configs::secret.eq(&payload.secret),
+                    backup_configs::encryption_key.eq(&encryption_key),
+                    backup_configs::enabled.eq(enabled),
+                ))
+                .returning(BackupConfig::as_returning())
+                .get_result::<BackupConfig>(&mut conn)
+                .await?;
+            (config, Some(encryption_key))
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(status_response(config, encryption_key)))
+}
+
+// === GET /backups/status ===
+#[get("/status")]
+pub async fn get_backup_status_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let config = backups::load_config(&mut conn, authenticated_user.id)
+        .await?
+        .ok_or_else(not_configured)?;
+
+    Ok(HttpResponse::Ok().json(status_response(config, None)))
+}
+
+// === POST /backups/run ===
+// Sauvegarde immédiate ; un échec d'envoi est rapporté dans last_error, comme la nuit
+#[post("/run")]
+pub async fn run_backup_handler(
+    pool: web::Data<DbPool>,
+    cost_limiter: web::Data<CostLimiter>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Export)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let config = backups::load_config(&mut conn, user_uuid)
+        .await?
+        .ok_or_else(not_configured)?;
+    let updated = backups::run_backup(&mut conn, &config).await?;
+
+    Ok(HttpResponse::Ok().json(status_response(updated, None)))
+}
+
+// Champ "file" du formulaire multipart
+async fn read_archive(mut payload: Multipart) -> Result<Vec<u8>, ServiceError> {
+    while let Some(mut field) = payload.try_next().await? {
+        let is_file = field
+            .content_disposition()
+            .and_then(|cd| cd.get_name())
+            .is_some_and(|name| name == "file");
+        if !is_file {
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.try_next().await? {
+            if bytes.len() + chunk.len() > MAX_RESTORE_ARCHIVE_BYTES {
+                return Err(ServiceError::BadRequest(format!(
+                    "The archive exceeds the maximum size of {} bytes",
+                    MAX_RESTORE_ARCHIVE_BYTES
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok(bytes);
+    }
+    Err(ServiceError::BadRequest(
+        "Missing multipart field 'file'".to_string(),
+    ))
+}
+
+// === POST /backups/restore?mode=replace|merge ===
+// Multipart : "file" = archive chiffrée produite par les sauvegardes, ou export JSON.
+// L'archive est validée ici, puis restaurée en arrière-plan : 202 avec l'opération à suivre
+// sur GET /backups/restore/{job_id}.
+#[post("/restore")]
+pub async fn restore_backup_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<RestoreQueryParams>,
+    payload: Multipart,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let mode = backup_restore::validate_mode(query.mode.trim())?;
+    let archive_bytes = read_archive(payload).await?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let config = backups::load_config(&mut conn, user_uuid).await?;
+    let archive = backup_restore::parse_archive(
+        &archive_bytes,
+        config.as_ref().map(|config| config.encryption_key.as_str()),
+        user_uuid,
+    )?;
+    let request = RestoreRequest {
+        mode: mode.to_string(),
+        archive,
+    };
+    let job = maintenance::enqueue_for_user(
+        &mut conn,
+        maintenance::JOB_RESTORE_BACKUP,
+        user_uuid,
+        serde_json::to_value(request)?,
+    )
+    .await?;
+    log::info!(
+        "User {} queued backup restore {} ({})",
+        user_uuid,
+        job.id,
+        mode
+    );
+
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+// === GET /backups/restore/{job_id_path} ===
+// Statut, avancement (progress) puis compte rendu (result) de la restauration
+#[get("/restore/{job_id_path}")]
+pub async fn get_restore_status_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    job_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let job = maintenance::find_user_job(
+        &mut conn,
+        job_id_path.into_inner(),
+        maintenance::JOB_RESTORE_BACKUP,
+        authenticated_user.id,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(job))
+}
@@ -0,0 +1,118 @@
+// OptiTask/backend-api/src/handlers/badge_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::badges::{self, BadgeRateLimiter};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::handlers::analytics_handlers::load_tracked_seconds;
+use crate::models::{PublicBadgeResponse, PublicBadgeToken};
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{delete, get, post, web, HttpResponse};
+use chrono::{Datelike, Utc};
+use serde_json::json;
+
+fn badge_response(badge: PublicBadgeToken) -> PublicBadgeResponse {
+    PublicBadgeResponse {
+        path: badges::badge_path(&badge.token),
+        created_at: badge.created_at,
+    }
+}
+
+// === GET /settings/badge ===
+#[get("/badge")]
+pub async fn get_badge_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let badge = badges::load_token(&mut conn, authenticated_user.id)
+        .await?
+        .ok_or_else(|| ServiceError::NotFound("No public badge has been created".to_string()))?;
+
+    Ok(HttpResponse::Ok().json(badge_response(badge)))
+}
+
+// === POST /settings/badge ===
+// Active le badge public ; renvoie le badge existant s'il l'est déjà
+#[post("/badge")]
+pub async fn create_badge_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let badge = badges::issue_token(&mut conn, authenticated_user.id, false).await?;
+
+    Ok(HttpResponse::Ok().json(badge_response(badge)))
+}
+
+// === POST /settings/badge/rotate ===
+#[post("/badge/rotate")]
+pub async fn rotate_badge_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let badge = badges::issue_token(&mut conn, authenticated_user.id, true).await?;
+
+    Ok(HttpResponse::Ok().json(badge_response(badge)))
+}
+
+// === DELETE /settings/badge ===
+#[delete("/badge")]
+pub async fn delete_badge_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    if badges::revoke_token(&mut conn, authenticated_user.id).await? == 0 {
+        return Err(ServiceError::NotFound(
+            "No public badge has been created".to_string(),
+        ));
+    }
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Public badge disabled"
+    })))
+}
+
+// === GET /public/badge/{token}.svg ===
+// Sans authentification : temps suivi du mois en cours (UTC) du propriétaire du jeton
+#[get("/badge/{token}.svg")]
+pub async fn public_badge_svg_handler(
+    pool: web::Data<DbPool>,
+    rate_limiter: web::Data<BadgeRateLimiter>,
+    token: web::Path<String>,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let owner_uuid = badges::find_owner(&mut conn, &token)
+        .await?
+        .ok_or_else(|| ServiceError::NotFound("Badge not found".to_string()))?;
+    rate_limiter.charge(owner_uuid)?;
+
+    let today = Utc::now().date_naive();
+    let month_start = today.with_day(1).unwrap_or(today);
+    let tracked_seconds = load_tracked_seconds(&mut conn, owner_uuid, month_start, today).await?;
+
+    let svg = badges::render_svg(&format!(
+        "{} focused this month",
+        badges::format_tracked_time(tracked_seconds)
+    ));
+
+    Ok(HttpResponse::Ok()
+        .content_type("image/svg+xml")
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(badges::BADGE_CACHE_MAX_AGE_SECS),
+        ]))
+        .body(svg))
+}
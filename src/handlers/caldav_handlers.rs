@@ -0,0 +1,520 @@
+// OptiTask/backend-api/src/handlers/caldav_handlers.rs
+// Collection CalDAV unique par utilisateur : /dav/{user_id}/tasks/{task_id}.ics
+use crate::account;
+use crate::caldav::{self, ParsedTodo};
+use crate::db::{DbConnection, DbPool};
+use crate::handlers::task_handlers;
+use crate::ids::TaskId;
+use crate::mentions;
+use crate::models::{NewTask, Task, DONE_TASK_STATUSES};
+use crate::schema::{task_labels, tasks};
+use crate::wip_limits;
+use actix_web::http::{header, StatusCode};
+use actix_web::{route, web, HttpRequest, HttpResponse, ResponseError};
+use chrono::{NaiveDateTime, Utc};
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+const DAV_CAPABILITIES: &str = "1, 3, calendar-access";
+const DAV_ALLOWED_METHODS: &str = "OPTIONS, GET, PUT, DELETE, PROPFIND, REPORT";
+const TASKS_COLLECTION_NAME: &str = "OptiTask";
+
+fn principal_href(user_uuid: Uuid) -> String {
+    format!("/dav/{}/", user_uuid)
+}
+
+fn collection_href(user_uuid: Uuid) -> String {
+    format!("/dav/{}/tasks/", user_uuid)
+}
+
+fn task_href(user_uuid: Uuid, task_uuid: TaskId) -> String {
+    format!("/dav/{}/tasks/{}.ics", user_uuid, task_uuid)
+}
+
+fn options_response() -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(("DAV", DAV_CAPABILITIES))
+        .insert_header((header::ALLOW, DAV_ALLOWED_METHODS))
+        .finish()
+}
+
+fn internal_error(e: impl std::fmt::Display) -> HttpResponse {
+    log::error!("CalDAV request failed: {}", e);
+    HttpResponse::InternalServerError().finish()
+}
+
+// Authentifie la requête et vérifie que l'URL désigne bien l'utilisateur authentifié
+async fn authorize<'a>(
+    req: &HttpRequest,
+    pool: &'a DbPool,
+    path_user: Option<Uuid>,
+) -> Result<(Uuid, DbConnection<'a>), HttpResponse> {
+    let user_uuid = caldav::authenticate(req, pool).await?;
+    if path_user.is_some_and(|path_user| path_user != user_uuid) {
+        return Err(HttpResponse::Forbidden().finish());
+    }
+    let conn = pool.get().await.map_err(internal_error)?;
+    Ok((user_uuid, conn))
+}
+
+// Compte dont la suppression est programmée : lecture seule, comme pour l'API (voir
+// account::read_only_guard, qui ne reconnaît pas l'authentification par mot de passe
+// d'application)
+async fn ensure_account_writable(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<(), HttpResponse> {
+    match account::find_deletion_request(conn, user_uuid).await {
+        Ok(None) => Ok(()),
+        Ok(Some(request)) => Err(HttpResponse::Forbidden().body(format!(
+            "Account is scheduled for deletion on {} and is read-only",
+            request.scheduled_for.format("%Y-%m-%d")
+        ))),
+        Err(e) => Err(internal_error(e)),
+    }
+}
+
+// "Depth: 0" uniquement ; 1 et infinity listent le contenu direct
+fn lists_children(req: &HttpRequest) -> bool {
+    req.headers()
+        .get("Depth")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim() != "0")
+        .unwrap_or(true)
+}
+
+fn principal_props(user_uuid: Uuid) -> String {
+    let href = caldav::xml_escape(&principal_href(user_uuid));
+    format!(
+        "<d:resourcetype><d:collection/><d:principal/></d:resourcetype>\
+         <d:current-user-principal><d:href>{href}</d:href></d:current-user-principal>\
+         <d:principal-URL><d:href>{href}</d:href></d:principal-URL>\
+         <c:calendar-home-set><d:href>{href}</d:href></c:calendar-home-set>",
+        href = href
+    )
+}
+
+async fn collection_props(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<String, HttpResponse> {
+    let (latest_update, task_count) = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .select((diesel::dsl::max(tasks::updated_at), count_star()))
+        .get_result::<(Option<NaiveDateTime>, i64)>(conn)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(format!(
+        "<d:resourcetype><d:collection/><c:calendar/></d:resourcetype>\
+         <d:displayname>{}</d:displayname>\
+         <c:supported-calendar-component-set><c:comp name=\"VTODO\"/></c:supported-calendar-component-set>\
+         <cs:getctag>{}</cs:getctag>\
+         <d:current-user-principal><d:href>{}</d:href></d:current-user-principal>",
+        TASKS_COLLECTION_NAME,
+        caldav::xml_escape(&caldav::collection_ctag(latest_update, task_count)),
+        caldav::xml_escape(&principal_href(user_uuid)),
+    ))
+}
+
+fn task_props(task: &Task, with_data: bool) -> String {
+    let mut props = format!(
+        "<d:getetag>{}</d:getetag>\
+         <d:getcontenttype>text/calendar; charset=utf-8; component=vtodo</d:getcontenttype>\
+         <d:resourcetype/>",
+        caldav::xml_escape(&caldav::task_etag(task))
+    );
+    if with_data {
+        props.push_str(&format!(
+            "<c:calendar-data>{}</c:calendar-data>",
+            caldav::xml_escape(&caldav::task_to_ical(task))
+        ));
+    }
+    props
+}
+
+async fn load_user_tasks(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Vec<Task>, HttpResponse> {
+    tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .order(tasks::created_at.asc())
+        .select(Task::as_select())
+        .load::<Task>(conn)
+        .await
+        .map_err(internal_error)
+}
+
+async fn find_user_task(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    task_uuid: TaskId,
+) -> Result<Option<Task>, HttpResponse> {
+    tasks::table
+        .filter(tasks::id.eq(task_uuid))
+        .filter(tasks::user_id.eq(user_uuid))
+        .select(Task::as_select())
+        .first::<Task>(conn)
+        .await
+        .optional()
+        .map_err(internal_error)
+}
+
+// "{task_id}.ics" ; les clients choisissent eux-mêmes le nom, qui doit être un UUID
+fn task_uuid_from_resource(resource: &str) -> Option<TaskId> {
+    resource
+        .strip_suffix(".ics")
+        .unwrap_or(resource)
+        .parse()
+        .ok()
+}
+
+// If-Match / If-None-Match : 412 si la condition n'est pas remplie
+fn check_preconditions(req: &HttpRequest, current_etag: Option<&str>) -> Result<(), HttpResponse> {
+    let header_value = |name: header::HeaderName| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+    if let Some(if_match) = header_value(header::IF_MATCH) {
+        let matches = match current_etag {
+            Some(etag) => if_match == "*" || if_match.split(',').any(|tag| tag.trim() == etag),
+            None => false,
+        };
+        if !matches {
+            return Err(HttpResponse::PreconditionFailed().finish());
+        }
+    }
+    if let Some(if_none_match) = header_value(header::IF_NONE_MATCH) {
+        let matches = match current_etag {
+            Some(etag) => {
+                if_none_match == "*" || if_none_match.split(',').any(|tag| tag.trim() == etag)
+            }
+            None => false,
+        };
+        if matches {
+            return Err(HttpResponse::PreconditionFailed().finish());
+        }
+    }
+    Ok(())
+}
+
+// === OPTIONS / PROPFIND /dav/ ===
+// Découverte : renvoie le principal de l'utilisateur authentifié
+#[route("/", method = "OPTIONS", method = "PROPFIND")]
+pub async fn dav_root_handler(req: HttpRequest, pool: web::Data<DbPool>) -> HttpResponse {
+    if req.method() == actix_web::http::Method::OPTIONS {
+        return options_response();
+    }
+    let user_uuid = match caldav::authenticate(&req, &pool).await {
+        Ok(user_uuid) => user_uuid,
+        Err(response) => return response,
+    };
+    caldav::multistatus(&[caldav::propstat_response(
+        "/dav/",
+        &principal_props(user_uuid),
+    )])
+}
+
+// === OPTIONS / PROPFIND /dav/{user_id}/ ===
+#[route("/{user_id_path}/", method = "OPTIONS", method = "PROPFIND")]
+pub async fn dav_principal_handler(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    user_id_path: web::Path<Uuid>,
+) -> HttpResponse {
+    if req.method() == actix_web::http::Method::OPTIONS {
+        return options_response();
+    }
+    let (user_uuid, mut conn) = match authorize(&req, &pool, Some(*user_id_path)).await {
+        Ok(authorized) => authorized,
+        Err(response) => return response,
+    };
+
+    let mut responses = vec![caldav::propstat_response(
+        &principal_href(user_uuid),
+        &principal_props(user_uuid),
+    )];
+    if lists_children(&req) {
+        match collection_props(&mut conn, user_uuid).await {
+            Ok(props) => responses.push(caldav::propstat_response(
+                &collection_href(user_uuid),
+                &props,
+            )),
+            Err(response) => return response,
+        }
+    }
+    caldav::multistatus(&responses)
+}
+
+// === OPTIONS / PROPFIND / REPORT /dav/{user_id}/tasks/ ===
+// REPORT : calendar-multiget (hrefs demandés) ou calendar-query (toutes les tâches)
+#[route(
+    "/{user_id_path}/tasks/",
+    method = "OPTIONS",
+    method = "PROPFIND",
+    method = "REPORT"
+)]
+pub async fn dav_collection_handler(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    user_id_path: web::Path<Uuid>,
+    body: web::Bytes,
+) -> HttpResponse {
+    if req.method() == actix_web::http::Method::OPTIONS {
+        return options_response();
+    }
+    let (user_uuid, mut conn) = match authorize(&req, &pool, Some(*user_id_path)).await {
+        Ok(authorized) => authorized,
+        Err(response) => return response,
+    };
+
+    if req.method().as_str() == "REPORT" {
+        let body = String::from_utf8_lossy(&body);
+        let user_tasks = match load_user_tasks(&mut conn, user_uuid).await {
+            Ok(user_tasks) => user_tasks,
+            Err(response) => return response,
+        };
+
+        let responses: Vec<String> = if body.contains("calendar-multiget") {
+            caldav::extract_hrefs(&body)
+                .into_iter()
+                .map(|href| {
+                    let resource = href.rsplit('/').next().unwrap_or_default();
+                    let found = task_uuid_from_resource(resource)
+                        .and_then(|task_uuid| user_tasks.iter().find(|t| t.id == task_uuid));
+                    match found {
+                        Some(task) => caldav::propstat_response(&href, &task_props(task, true)),
+                        None => caldav::not_found_response(&href),
+                    }
+                })
+                .collect()
+        } else {
+            user_tasks
+                .iter()
+                .map(|task| {
+                    caldav::propstat_response(
+                        &task_href(user_uuid, task.id),
+                        &task_props(task, true),
+                    )
+                })
+                .collect()
+        };
+        return caldav::multistatus(&responses);
+    }
+
+    let mut responses = match collection_props(&mut conn, user_uuid).await {
+        Ok(props) => vec![caldav::propstat_response(
+            &collection_href(user_uuid),
+            &props,
+        )],
+        Err(response) => return response,
+    };
+    if lists_children(&req) {
+        match load_user_tasks(&mut conn, user_uuid).await {
+            Ok(user_tasks) => responses.extend(user_tasks.iter().map(|task| {
+                caldav::propstat_response(&task_href(user_uuid, task.id), &task_props(task, false))
+            })),
+            Err(response) => return response,
+        }
+    }
+    caldav::multistatus(&responses)
+}
+
+async fn put_task(
+    conn: &mut AsyncPgConnection,
+    req: &HttpRequest,
+    user_uuid: Uuid,
+    task_uuid: TaskId,
+    existing: Option<Task>,
+    todo: ParsedTodo,
+) -> Result<HttpResponse, HttpResponse> {
+    if todo
+        .uid
+        .as_deref()
+        .is_some_and(|uid| uid.parse::<TaskId>().ok() != Some(task_uuid))
+    {
+        log::debug!(
+            "CalDAV UID {:?} differs from resource name {}",
+            todo.uid,
+            task_uuid
+        );
+    }
+    let summary = todo
+        .summary
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let (task, status) = match existing {
+        Some(current) => {
+            check_preconditions(req, Some(&caldav::task_etag(&current)))?;
+            let new_status =
+                caldav::task_status_from_vtodo(todo.status.as_deref(), Some(&current.status));
+            // Même règles que PUT /tasks/{id} : limite WIP de la colonne d'arrivée
+            if let (Some(project_uuid), true) = (current.project_id, new_status != current.status) {
+                wip_limits::ensure_capacity(conn, project_uuid, &new_status, Some(current.id))
+                    .await
+                    .map_err(|e| e.error_response())?;
+            }
+            let completes = !DONE_TASK_STATUSES.contains(&current.status.as_str())
+                && DONE_TASK_STATUSES.contains(&new_status.as_str());
+            let updated = diesel::update(tasks::table.find(current.id))
+                .set((
+                    tasks::title.eq(summary.unwrap_or(current.title)),
+                    tasks::description.eq(todo.description.filter(|d| !d.is_empty())),
+                    tasks::status.eq(new_status),
+                    tasks::due_date.eq(todo.due),
+                    tasks::updated_at.eq(Utc::now().naive_utc()),
+                ))
+                .returning(Task::as_returning())
+                .get_result::<Task>(conn)
+                .await
+                .map_err(internal_error)?;
+            // Tâche de suivi, règles task_completed et événement, comme via l'API
+            let updated = if completes {
+                let (completed, _, effects) = task_handlers::run_completion_hooks(conn, updated)
+                    .await
+                    .map_err(|e| e.error_response())?;
+                effects.dispatch();
+                completed
+            } else {
+                updated
+            };
+            (updated, StatusCode::NO_CONTENT)
+        }
+        None => {
+            check_preconditions(req, None)?;
+            // Un UUID déjà utilisé par la tâche d'un autre utilisateur est refusé
+            let taken = tasks::table
+                .find(task_uuid)
+                .count()
+                .get_result::<i64>(conn)
+                .await
+                .map_err(internal_error)?;
+            if taken > 0 {
+                return Err(HttpResponse::Forbidden().finish());
+            }
+
+            let new_task = NewTask {
+                user_id: user_uuid,
+                project_id: None,
+                title: summary.unwrap_or_else(|| "Untitled".to_string()),
+                description: todo.description.filter(|d| !d.is_empty()),
+                status: Some(caldav::task_status_from_vtodo(todo.status.as_deref(), None)),
+                due_date: todo.due,
+                order: None,
+                source: Some(serde_json::json!({ "type": "caldav" })),
+                context: None,
+                scheduled_start: None,
+                scheduled_end: None,
+                follow_up: None,
+                stage: None,
+                time_budget_seconds: None,
+            };
+            let created = diesel::insert_into(tasks::table)
+                .values((tasks::id.eq(task_uuid), &new_task))
+                .returning(Task::as_returning())
+                .get_result::<Task>(conn)
+                .await
+                .map_err(internal_error)?;
+            (created, StatusCode::CREATED)
+        }
+    };
+
+    // Références [[task:uuid]] de la description
+    mentions::sync_task_links(conn, user_uuid, task.id, task.description.as_deref())
+        .await
+        .map_err(internal_error)?;
+
+    Ok(HttpResponse::build(status)
+        .insert_header((header::ETAG, caldav::task_etag(&task)))
+        .finish())
+}
+
+// === OPTIONS / PROPFIND / GET / PUT / DELETE /dav/{user_id}/tasks/{task_id}.ics ===
+#[route(
+    "/{user_id_path}/tasks/{resource_path}",
+    method = "OPTIONS",
+    method = "PROPFIND",
+    method = "GET",
+    method = "PUT",
+    method = "DELETE"
+)]
+pub async fn dav_task_handler(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    path: web::Path<(Uuid, String)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    if req.method() == actix_web::http::Method::OPTIONS {
+        return options_response();
+    }
+    let (path_user, resource) = path.into_inner();
+    let (user_uuid, mut conn) = match authorize(&req, &pool, Some(path_user)).await {
+        Ok(authorized) => authorized,
+        Err(response) => return response,
+    };
+    let Some(task_uuid) = task_uuid_from_resource(&resource) else {
+        return HttpResponse::NotFound().finish();
+    };
+    if matches!(req.method().as_str(), "PUT" | "DELETE") {
+        if let Err(response) = ensure_account_writable(&mut conn, user_uuid).await {
+            return response;
+        }
+    }
+
+    let existing = match find_user_task(&mut conn, user_uuid, task_uuid).await {
+        Ok(existing) => existing,
+        Err(response) => return response,
+    };
+
+    let result =
+        match (req.method().as_str(), existing) {
+            ("GET", Some(task)) => Ok(HttpResponse::Ok()
+                .content_type("text/calendar; charset=utf-8")
+                .insert_header((header::ETAG, caldav::task_etag(&task)))
+                .body(caldav::task_to_ical(&task))),
+            ("PROPFIND", Some(task)) => Ok(caldav::multistatus(&[caldav::propstat_response(
+                &task_href(user_uuid, task.id),
+                &task_props(&task, false),
+            )])),
+            ("PUT", existing) => match caldav::parse_vtodo(&String::from_utf8_lossy(&body)) {
+                Some(todo) => put_task(&mut conn, &req, user_uuid, task_uuid, existing, todo).await,
+                None => Err(HttpResponse::UnsupportedMediaType()
+                    .body("Only VTODO components are supported")),
+            },
+            ("DELETE", Some(task)) => {
+                match check_preconditions(&req, Some(&caldav::task_etag(&task))) {
+                    Ok(()) => {
+                        let deleted = conn
+                            .transaction::<_, diesel::result::Error, _>(|conn| {
+                                async move {
+                                    diesel::delete(
+                                        task_labels::table.filter(task_labels::task_id.eq(task.id)),
+                                    )
+                                    .execute(conn)
+                                    .await?;
+                                    diesel::delete(tasks::table.find(task.id))
+                                        .execute(conn)
+                                        .await
+                                }
+                                .scope_boxed()
+                            })
+                            .await;
+                        deleted
+                            .map(|_| HttpResponse::NoContent().finish())
+                            .map_err(internal_error)
+                    }
+                    Err(response) => Err(response),
+                }
+            }
+            _ => Err(HttpResponse::NotFound().finish()),
+        };
+
+    result.unwrap_or_else(|response| response)
+}
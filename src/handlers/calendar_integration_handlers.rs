@@ -0,0 +1,499 @@
+// OptiTask/backend-api/src/handlers/calendar_integration_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::focus_sessions;
+use crate::ids::{ProjectId, TaskId};
+use crate::integrations::google_calendar::{self, GoogleCalendarConfig, PROVIDER_GOOGLE};
+use crate::models::{
+    AcceptCalendarSuggestionPayload, CalendarIntegrationStatus, CalendarProjectLink,
+    CalendarSuggestion, LinkCalendarProjectPayload, ListCalendarSuggestionsQuery, NewTask,
+    NewTimeEntry, TimeEntry, CALENDAR_SUGGESTION_ACCEPTED, CALENDAR_SUGGESTION_PENDING,
+    CALENDAR_SUGGESTION_REJECTED,
+};
+use crate::permissions::{self, Permission};
+use crate::schema::{
+    calendar_integrations, calendar_project_links, calendar_suggestions, projects, tasks,
+    time_entries,
+};
+use crate::time_budgets;
+use crate::timesheets::ensure_entry_unlocked;
+use actix_web::{delete, get, http::header, post, put, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_KEYWORD_CHARS: usize = 100;
+
+#[derive(Deserialize, Debug)]
+pub struct OAuthCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+fn enabled_config(
+    config: &web::Data<Option<GoogleCalendarConfig>>,
+) -> Result<&GoogleCalendarConfig, ServiceError> {
+    config.get_ref().as_ref().ok_or_else(|| {
+        ServiceError::NotFound("Google Calendar integration is not enabled".to_string())
+    })
+}
+
+// === GET /integrations/google-calendar ===
+#[get("")]
+pub async fn get_google_calendar_status_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<Option<GoogleCalendarConfig>>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    enabled_config(&config)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let integration = google_calendar::find_integration(&mut conn, authenticated_user.id).await?;
+
+    Ok(HttpResponse::Ok().json(CalendarIntegrationStatus {
+        provider: integration
+            .as_ref()
+            .map(|i| i.provider.clone())
+            .unwrap_or_else(|| PROVIDER_GOOGLE.to_string()),
+        connected: integration.is_some(),
+        calendar_id: integration.as_ref().map(|i| i.calendar_id.clone()),
+        last_synced_at: integration.as_ref().and_then(|i| i.last_synced_at),
+        connected_at: integration.as_ref().map(|i| i.created_at),
+    }))
+}
+
+// === GET /integrations/google-calendar/connect ===
+// Renvoie l'URL de consentement Google vers laquelle rediriger l'utilisateur
+#[get("/connect")]
+pub async fn connect_google_calendar_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<Option<GoogleCalendarConfig>>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    let config = enabled_config(&config)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let state = google_calendar::create_oauth_state(&mut conn, authenticated_user.id).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "authorization_url": config.authorization_url(&state)
+    })))
+}
+
+// === GET /integrations/google-calendar/callback?code=&state= ===
+// Appelé par le navigateur au retour de Google (sans en-tête d'authentification) :
+// l'utilisateur est identifié par le state.
+#[get("/callback")]
+pub async fn google_calendar_callback_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<Option<GoogleCalendarConfig>>,
+    query: web::Query<OAuthCallbackQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let config = enabled_config(&config)?;
+    let query = query.into_inner();
+
+    let outcome = match (query.error, query.code, query.state) {
+        (Some(error), _, _) => Err(ServiceError::BadRequest(format!(
+            "Google Calendar authorization was denied: {}",
+            error
+        ))),
+        (None, Some(code), Some(state)) => {
+            // Obtenir une connexion du pool
+            let mut conn = pool.get().await?;
+            google_calendar::complete_oauth(&mut conn, config, &state, &code).await
+        }
+        _ => Err(ServiceError::BadRequest(
+            "Missing code or state parameter".to_string(),
+        )),
+    };
+
+    match (&config.return_url, outcome) {
+        (Some(return_url), outcome) => {
+            let result = if outcome.is_ok() {
+                "connected"
+            } else {
+                "error"
+            };
+            let separator = if return_url.contains('?') { '&' } else { '?' };
+            Ok(HttpResponse::Found()
+                .insert_header((
+                    header::LOCATION,
+                    format!("{}{}google_calendar={}", return_url, separator, result),
+                ))
+                .finish())
+        }
+        (None, Ok(_)) => Ok(HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": "Google Calendar connected"
+        }))),
+        (None, Err(e)) => Err(e),
+    }
+}
+
+// === DELETE /integrations/google-calendar ===
+// Les suggestions déjà créées sont conservées
+#[delete("")]
+pub async fn disconnect_google_calendar_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(
+        calendar_integrations::table
+            .filter(calendar_integrations::user_id.eq(authenticated_user.id))
+            .filter(calendar_integrations::provider.eq(PROVIDER_GOOGLE)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(
+            "Google Calendar is not connected".to_string(),
+        ));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Google Calendar disconnected"
+    })))
+}
+
+// === POST /integrations/google-calendar/sync ===
+// Synchronisation immédiate, sans attendre la tâche périodique
+#[post("/sync")]
+pub async fn sync_google_calendar_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<Option<GoogleCalendarConfig>>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    let config = enabled_config(&config)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let integration = google_calendar::find_integration(&mut conn, authenticated_user.id)
+        .await?
+        .ok_or_else(|| ServiceError::NotFound("Google Calendar is not connected".to_string()))?;
+
+    let created = google_calendar::sync_integration(&mut conn, config, &integration).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": format!("{} new suggestion(s)", created),
+        "created": created
+    })))
+}
+
+// === GET /integrations/google-calendar/links ===
+#[get("/links")]
+pub async fn list_calendar_links_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let links = calendar_project_links::table
+        .filter(calendar_project_links::user_id.eq(authenticated_user.id))
+        .order(calendar_project_links::created_at.asc())
+        .select(CalendarProjectLink::as_select())
+        .load::<CalendarProjectLink>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(links))
+}
+
+// === PUT /integrations/google-calendar/links/{project_id_path} ===
+// Les événements dont le titre contient le mot-clé sont suggérés pour ce projet
+#[put("/links/{project_id_path}")]
+pub async fn link_calendar_project_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    project_id_path: web::Path<ProjectId>,
+    payload: web::Json<LinkCalendarProjectPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let project_uuid = project_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectWrite)
+        .await?;
+
+    let keyword = match payload.into_inner().keyword {
+        Some(keyword) => keyword,
+        None => {
+            projects::table
+                .find(project_uuid)
+                .select(projects::name)
+                .first::<String>(&mut conn)
+                .await?
+        }
+    };
+    let keyword = keyword.trim().to_string();
+    if keyword.is_empty() || keyword.chars().count() > MAX_KEYWORD_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "keyword must contain between 1 and {} characters",
+            MAX_KEYWORD_CHARS
+        )));
+    }
+
+    let link = diesel::insert_into(calendar_project_links::table)
+        .values((
+            calendar_project_links::user_id.eq(user_uuid),
+            calendar_project_links::project_id.eq(project_uuid),
+            calendar_project_links::keyword.eq(&keyword),
+        ))
+        .on_conflict((
+            calendar_project_links::user_id,
+            calendar_project_links::project_id,
+        ))
+        .do_update()
+        .set(calendar_project_links::keyword.eq(&keyword))
+        .returning(CalendarProjectLink::as_returning())
+        .get_result::<CalendarProjectLink>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(link))
+}
+
+// === DELETE /integrations/google-calendar/links/{project_id_path} ===
+#[delete("/links/{project_id_path}")]
+pub async fn unlink_calendar_project_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    project_id_path: web::Path<ProjectId>,
+) -> Result<HttpResponse, ServiceError> {
+    let project_uuid = project_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(
+        calendar_project_links::table
+            .filter(calendar_project_links::user_id.eq(authenticated_user.id))
+            .filter(calendar_project_links::project_id.eq(project_uuid)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "Project {} is not linked to the calendar",
+            project_uuid
+        )));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Calendar link removed"
+    })))
+}
+
+// === GET /integrations/google-calendar/suggestions?status= ===
+#[get("/suggestions")]
+pub async fn list_calendar_suggestions_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<ListCalendarSuggestionsQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let wanted_status = query
+        .into_inner()
+        .status
+        .unwrap_or_else(|| CALENDAR_SUGGESTION_PENDING.to_string());
+    if ![
+        CALENDAR_SUGGESTION_PENDING,
+        CALENDAR_SUGGESTION_ACCEPTED,
+        CALENDAR_SUGGESTION_REJECTED,
+    ]
+    .contains(&wanted_status.as_str())
+    {
+        return Err(ServiceError::ValidationError(
+            "status must be one of pending, accepted, rejected".to_string(),
+        ));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let suggestions = calendar_suggestions::table
+        .filter(calendar_suggestions::user_id.eq(authenticated_user.id))
+        .filter(calendar_suggestions::status.eq(&wanted_status))
+        .order(calendar_suggestions::start_time.desc())
+        .select(CalendarSuggestion::as_select())
+        .load::<CalendarSuggestion>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+async fn load_pending_suggestion(
+    conn: &mut diesel_async::AsyncPgConnection,
+    user_uuid: Uuid,
+    suggestion_uuid: Uuid,
+) -> Result<CalendarSuggestion, ServiceError> {
+    let suggestion = calendar_suggestions::table
+        .filter(calendar_suggestions::id.eq(suggestion_uuid))
+        .filter(calendar_suggestions::user_id.eq(user_uuid))
+        .select(CalendarSuggestion::as_select())
+        .for_update()
+        .first::<CalendarSuggestion>(conn)
+        .await
+        .optional()?
+        .ok_or_else(|| ServiceError::NotFound("Calendar suggestion not found".to_string()))?;
+
+    if suggestion.status != CALENDAR_SUGGESTION_PENDING {
+        return Err(ServiceError::ConflictError(format!(
+            "Suggestion already {}",
+            suggestion.status
+        )));
+    }
+    Ok(suggestion)
+}
+
+// === POST /integrations/google-calendar/suggestions/{suggestion_id_path}/accept ===
+// Crée l'entrée de temps (et la tâche si aucune n'est indiquée)
+#[post("/suggestions/{suggestion_id_path}/accept")]
+pub async fn accept_calendar_suggestion_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    suggestion_id_path: web::Path<Uuid>,
+    payload: Option<web::Json<AcceptCalendarSuggestionPayload>>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let suggestion_uuid = suggestion_id_path.into_inner();
+    let target_task = payload.and_then(|p| p.into_inner().task_id);
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let (suggestion, time_entry) = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let suggestion = load_pending_suggestion(conn, user_uuid, suggestion_uuid).await?;
+                ensure_entry_unlocked(conn, user_uuid, suggestion.start_time).await?;
+
+                let task_uuid = match target_task {
+                    Some(task_uuid) => {
+                        permissions::require_task(conn, user_uuid, task_uuid, Permission::TaskWrite)
+                            .await?
+                            .id
+                    }
+                    None => {
+                        permissions::require_project(
+                            conn,
+                            user_uuid,
+                            suggestion.project_id,
+                            Permission::ProjectWrite,
+                        )
+                        .await?;
+                        diesel::insert_into(tasks::table)
+                            .values(&NewTask {
+                                user_id: user_uuid,
+                                project_id: Some(suggestion.project_id),
+                                title: suggestion.title.clone(),
+                                description: None,
+                                status: None,
+                                due_date: None,
+                                order: None,
+                                context: None,
+                                scheduled_start: None,
+                                scheduled_end: None,
+                                follow_up: None,
+                                stage: None,
+                                time_budget_seconds: None,
+                                source: Some(json!({
+                                    "type": "calendar",
+                                    "provider": PROVIDER_GOOGLE,
+                                    "event_id": suggestion.external_event_id,
+                                })),
+                            })
+                            .returning(tasks::id)
+                            .get_result::<TaskId>(conn)
+                            .await?
+                    }
+                };
+
+                let focus_session_id = focus_sessions::active_session_id(conn, user_uuid).await?;
+                let time_entry = diesel::insert_into(time_entries::table)
+                    .values(&NewTimeEntry {
+                        user_id: user_uuid,
+                        task_id: task_uuid,
+                        start_time: suggestion.start_time,
+                        end_time: Some(suggestion.end_time),
+                        duration_seconds: Some(
+                            (suggestion.end_time - suggestion.start_time).num_seconds() as i32,
+                        ),
+                        is_pomodoro_session: None,
+                        is_break: None,
+                        clock_skew_seconds: None,
+                        suspect_clock: None,
+                        focus_session_id,
+                    })
+                    .get_result::<TimeEntry>(conn)
+                    .await?;
+
+                let suggestion = diesel::update(calendar_suggestions::table.find(suggestion.id))
+                    .set((
+                        calendar_suggestions::status.eq(CALENDAR_SUGGESTION_ACCEPTED),
+                        calendar_suggestions::time_entry_id.eq(time_entry.id),
+                    ))
+                    .returning(CalendarSuggestion::as_returning())
+                    .get_result::<CalendarSuggestion>(conn)
+                    .await?;
+
+                Ok((suggestion, time_entry))
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    // L'entrée créée compte dans le temps suivi de la tâche
+    time_budgets::check_time_budget(&mut conn, time_entry.task_id).await;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "suggestion": suggestion,
+        "time_entry": time_entry
+    })))
+}
+
+// === POST /integrations/google-calendar/suggestions/{suggestion_id_path}/reject ===
+#[post("/suggestions/{suggestion_id_path}/reject")]
+pub async fn reject_calendar_suggestion_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    suggestion_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let suggestion_uuid = suggestion_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let suggestion = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                load_pending_suggestion(conn, user_uuid, suggestion_uuid).await?;
+                diesel::update(calendar_suggestions::table.find(suggestion_uuid))
+                    .set(calendar_suggestions::status.eq(CALENDAR_SUGGESTION_REJECTED))
+                    .returning(CalendarSuggestion::as_returning())
+                    .get_result::<CalendarSuggestion>(conn)
+                    .await
+                    .map_err(ServiceError::from)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(suggestion))
+}
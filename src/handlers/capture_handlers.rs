@@ -0,0 +1,192 @@
+// OptiTask/backend-api/src/handlers/capture_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::{LabelId, ProjectId, TaskId};
+use crate::models::{Label, NewTask, NewTaskLabelAssociation, Task};
+use crate::permissions::{self, Permission};
+use crate::schema::{labels, task_labels, tasks};
+use crate::settings;
+use actix_web::{post, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const SOURCE_TYPE_BROWSER_EXTENSION: &str = "browser_extension";
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_SELECTION_CHARS: usize = 10_000;
+
+#[derive(Deserialize, Debug)]
+pub struct CapturePayload {
+    pub url: String,
+    pub page_title: Option<String>,
+    pub selected_text: Option<String>,
+    // Indications facultatives : ignorées si le projet n'est pas accessible
+    // ou si un label n'existe pas
+    pub project_id: Option<ProjectId>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+// Réponse volontairement réduite pour l'extension
+#[derive(Serialize, Debug)]
+pub struct CaptureResponse {
+    pub task_id: TaskId,
+    pub title: String,
+    pub project_id: Option<ProjectId>,
+    pub labels: Vec<String>,
+    pub ignored_hints: Vec<String>,
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn truncate_chars(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
+
+// === POST /capture ===
+// Titre : titre de la page, sinon première ligne de la sélection, sinon l'URL
+#[post("")]
+pub async fn capture_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<CapturePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let payload = payload.into_inner();
+
+    let url = payload.url.trim().to_string();
+    let is_web_url = reqwest::Url::parse(&url)
+        .map(|parsed| matches!(parsed.scheme(), "http" | "https"))
+        .unwrap_or(false);
+    if !is_web_url {
+        return Err(ServiceError::ValidationError(
+            "url must be an absolute http(s) URL".to_string(),
+        ));
+    }
+
+    let page_title = non_empty(&payload.page_title);
+    let selected_text =
+        non_empty(&payload.selected_text).map(|text| truncate_chars(text, MAX_SELECTION_CHARS));
+
+    let title = page_title
+        .or_else(|| selected_text.as_deref().and_then(|t| t.lines().next()))
+        .map(|t| truncate_chars(t.trim(), MAX_TITLE_CHARS))
+        .unwrap_or_else(|| truncate_chars(&url, MAX_TITLE_CHARS));
+    let description = match &selected_text {
+        Some(text) => format!("> {}\n\n{}", text.replace('\n', "\n> "), url),
+        None => url.clone(),
+    };
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let mut ignored_hints = Vec::new();
+
+    let project_uuid = match payload.project_id {
+        Some(project_uuid) => {
+            match permissions::require_project(
+                &mut conn,
+                user_uuid,
+                project_uuid,
+                Permission::ProjectWrite,
+            )
+            .await
+            {
+                Ok(_) => Some(project_uuid),
+                Err(ServiceError::NotFound(_)) | Err(ServiceError::Forbidden(_)) => {
+                    ignored_hints.push(format!("project:{}", project_uuid));
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        None => settings::default_project_for_new_task(&mut conn, user_uuid).await?,
+    };
+
+    let matched_labels: Vec<Label> = if payload.labels.is_empty() {
+        Vec::new()
+    } else {
+        let user_labels = labels::table
+            .filter(labels::user_id.eq(user_uuid))
+            .select(Label::as_select())
+            .load::<Label>(&mut conn)
+            .await?;
+
+        let mut matched: Vec<Label> = Vec::new();
+        for hint in &payload.labels {
+            let wanted = hint.trim().to_lowercase();
+            match user_labels.iter().find(|l| l.name.to_lowercase() == wanted) {
+                Some(label) if !matched.iter().any(|m| m.id == label.id) => {
+                    matched.push(label.clone())
+                }
+                Some(_) => {}
+                None => ignored_hints.push(format!("label:{}", hint.trim())),
+            }
+        }
+        matched
+    };
+
+    let new_task = NewTask {
+        user_id: user_uuid,
+        project_id: project_uuid,
+        title,
+        description: Some(description),
+        status: None,
+        due_date: None,
+        order: None,
+        context: None,
+        scheduled_start: None,
+        scheduled_end: None,
+        follow_up: None,
+        stage: None,
+        time_budget_seconds: None,
+        source: Some(json!({
+            "type": SOURCE_TYPE_BROWSER_EXTENSION,
+            "url": url,
+            "page_title": page_title,
+            "captured_at": Utc::now(),
+        })),
+    };
+
+    let label_ids: Vec<LabelId> = matched_labels.iter().map(|l| l.id).collect();
+    let task = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let task = diesel::insert_into(tasks::table)
+                    .values(&new_task)
+                    .get_result::<Task>(conn)
+                    .await?;
+
+                if !label_ids.is_empty() {
+                    let associations: Vec<NewTaskLabelAssociation> = label_ids
+                        .iter()
+                        .map(|label_uuid| NewTaskLabelAssociation {
+                            task_id: task.id,
+                            label_id: *label_uuid,
+                        })
+                        .collect();
+                    diesel::insert_into(task_labels::table)
+                        .values(&associations)
+                        .execute(conn)
+                        .await?;
+                }
+                Ok(task)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(HttpResponse::Created().json(CaptureResponse {
+        task_id: task.id,
+        title: task.title,
+        project_id: task.project_id,
+        labels: matched_labels.into_iter().map(|l| l.name).collect(),
+        ignored_hints,
+    }))
+}
@@ -0,0 +1,34 @@
+// OptiTask/backend-api/src/handlers/color_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::colors;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::ColorSuggestQuery;
+use actix_web::{get, web, HttpResponse};
+use serde_json::json;
+
+const DEFAULT_COLOR_COUNT: usize = 5;
+const MAX_COLOR_COUNT: usize = 12;
+
+// === GET /colors/suggest?count= ===
+// Couleurs lisibles les plus distinctes de celles des projets et labels de l'utilisateur
+#[get("/suggest")]
+pub async fn suggest_colors_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<ColorSuggestQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let count = query.count.unwrap_or(DEFAULT_COLOR_COUNT);
+    if !(1..=MAX_COLOR_COUNT).contains(&count) {
+        return Err(ServiceError::ValidationError(format!(
+            "count must be between 1 and {}",
+            MAX_COLOR_COUNT
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let used = colors::used_colors(&mut conn, authenticated_user.id).await?;
+    Ok(HttpResponse::Ok().json(json!({ "colors": colors::suggest(&used, count) })))
+}
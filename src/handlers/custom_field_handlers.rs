@@ -0,0 +1,317 @@
+// OptiTask/backend-api/src/handlers/custom_field_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::custom_fields;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::{ProjectId, TaskId};
+use crate::models::{
+    CreateCustomFieldPayload, CustomFieldDefinition, NewCustomFieldDefinition, NewTaskCustomValue,
+    SetTaskCustomValuePayload, TaskCustomFieldValue, UpdateCustomFieldChangeset,
+    UpdateCustomFieldPayload,
+};
+use crate::permissions::{self, Permission};
+use crate::schema::{custom_field_definitions, task_custom_values};
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use uuid::Uuid;
+
+async fn find_field_in_project(
+    conn: &mut AsyncPgConnection,
+    project_uuid: ProjectId,
+    field_uuid: Uuid,
+) -> Result<CustomFieldDefinition, ServiceError> {
+    custom_field_definitions::table
+        .filter(custom_field_definitions::id.eq(field_uuid))
+        .filter(custom_field_definitions::project_id.eq(project_uuid))
+        .select(CustomFieldDefinition::as_select())
+        .first::<CustomFieldDefinition>(conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!(
+                "Custom field with id {} not found in project {}",
+                field_uuid, project_uuid
+            ))
+        })
+}
+
+async fn ensure_field_name_available(
+    conn: &mut AsyncPgConnection,
+    project_uuid: ProjectId,
+    field_name: &str,
+    excluded_field: Option<Uuid>,
+) -> Result<(), ServiceError> {
+    let mut query = custom_field_definitions::table
+        .filter(custom_field_definitions::project_id.eq(project_uuid))
+        .filter(custom_field_definitions::name.eq(field_name))
+        .select(custom_field_definitions::id)
+        .into_boxed();
+    if let Some(field_uuid) = excluded_field {
+        query = query.filter(custom_field_definitions::id.ne(field_uuid));
+    }
+
+    let existing = query.first::<Uuid>(conn).await.optional()?;
+    if existing.is_some() {
+        return Err(ServiceError::ConflictError(format!(
+            "A custom field named '{}' already exists in this project",
+            field_name
+        )));
+    }
+    Ok(())
+}
+
+fn clean_field_name(raw: &str) -> Result<String, ServiceError> {
+    let field_name = raw.trim();
+    if field_name.is_empty() {
+        return Err(ServiceError::ValidationError(
+            "Custom field name cannot be empty".to_string(),
+        ));
+    }
+    Ok(field_name.to_string())
+}
+
+// === GET /projects/{project_id_path}/custom-fields ===
+#[get("/{project_id_path}/custom-fields")]
+pub async fn list_custom_fields_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    project_id_path: web::Path<ProjectId>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let project_uuid = project_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectRead)
+        .await?;
+
+    let field_list = custom_field_definitions::table
+        .filter(custom_field_definitions::project_id.eq(project_uuid))
+        .order(custom_field_definitions::name.asc())
+        .select(CustomFieldDefinition::as_select())
+        .load::<CustomFieldDefinition>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(field_list))
+}
+
+// === POST /projects/{project_id_path}/custom-fields ===
+#[post("/{project_id_path}/custom-fields")]
+pub async fn create_custom_field_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    project_id_path: web::Path<ProjectId>,
+    payload: web::Json<CreateCustomFieldPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let project_uuid = project_id_path.into_inner();
+
+    let field_name = clean_field_name(&payload.name)?;
+    custom_fields::validate_field_type(&payload.field_type)?;
+    let field_options =
+        custom_fields::build_options(&payload.field_type, payload.options.as_deref())?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectWrite)
+        .await?;
+    ensure_field_name_available(&mut conn, project_uuid, &field_name, None).await?;
+
+    let new_field = NewCustomFieldDefinition {
+        user_id: user_uuid,
+        project_id: project_uuid,
+        name: field_name,
+        field_type: payload.field_type.clone(),
+        options: field_options,
+    };
+
+    let field = diesel::insert_into(custom_field_definitions::table)
+        .values(&new_field)
+        .get_result::<CustomFieldDefinition>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Created().json(field))
+}
+
+// === PUT /projects/{project_id_path}/custom-fields/{field_id_path} ===
+#[put("/{project_id_path}/custom-fields/{field_id_path}")]
+pub async fn update_custom_field_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    path_params: web::Path<(ProjectId, Uuid)>,
+    payload: web::Json<UpdateCustomFieldPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let (project_uuid, field_uuid) = path_params.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectWrite)
+        .await?;
+    let field = find_field_in_project(&mut conn, project_uuid, field_uuid).await?;
+
+    let new_name = match &payload.name {
+        Some(raw) => {
+            let field_name = clean_field_name(raw)?;
+            ensure_field_name_available(&mut conn, project_uuid, &field_name, Some(field_uuid))
+                .await?;
+            Some(field_name)
+        }
+        None => None,
+    };
+    let new_options = match &payload.options {
+        Some(options) => Some(custom_fields::build_options(
+            &field.field_type,
+            Some(options),
+        )?),
+        None => None,
+    };
+
+    let field_changes = UpdateCustomFieldChangeset {
+        name: new_name,
+        options: new_options,
+        updated_at: Some(Utc::now().naive_utc()),
+    };
+
+    let updated_field = diesel::update(custom_field_definitions::table.find(field.id))
+        .set(&field_changes)
+        .get_result::<CustomFieldDefinition>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(updated_field))
+}
+
+// === DELETE /projects/{project_id_path}/custom-fields/{field_id_path} ===
+// Les valeurs associées sont supprimées en cascade
+#[delete("/{project_id_path}/custom-fields/{field_id_path}")]
+pub async fn delete_custom_field_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    path_params: web::Path<(ProjectId, Uuid)>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let (project_uuid, field_uuid) = path_params.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectWrite)
+        .await?;
+
+    let num_deleted = diesel::delete(
+        custom_field_definitions::table
+            .filter(custom_field_definitions::id.eq(field_uuid))
+            .filter(custom_field_definitions::project_id.eq(project_uuid)),
+    )
+    .execute(&mut conn)
+    .await
+    .map_err(ServiceError::from)?;
+
+    if num_deleted > 0 {
+        Ok(HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("Custom field with id {} deleted successfully", field_uuid)
+        })))
+    } else {
+        Err(ServiceError::NotFound(format!(
+            "Custom field with id {} not found in project {}",
+            field_uuid, project_uuid
+        )))
+    }
+}
+
+// === PUT /tasks/{task_id_path}/custom-fields/{field_id_path} ===
+// Définit la valeur d'un champ du projet de la tâche
+#[put("/{task_id_path}/custom-fields/{field_id_path}")]
+pub async fn set_task_custom_value_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    path_params: web::Path<(TaskId, Uuid)>,
+    payload: web::Json<SetTaskCustomValuePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let (task_uuid, field_uuid) = path_params.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let task =
+        permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskWrite).await?;
+
+    let Some(project_uuid) = task.project_id else {
+        return Err(ServiceError::BadRequest(
+            "Custom fields are only available on tasks that belong to a project".to_string(),
+        ));
+    };
+
+    let field = find_field_in_project(&mut conn, project_uuid, field_uuid).await?;
+    let stored_value = custom_fields::normalize_value(&field, &payload.value)?;
+
+    let new_value = NewTaskCustomValue {
+        task_id: task_uuid,
+        field_id: field.id,
+        value: stored_value.clone(),
+    };
+
+    diesel::insert_into(task_custom_values::table)
+        .values(&new_value)
+        .on_conflict((task_custom_values::task_id, task_custom_values::field_id))
+        .do_update()
+        .set(task_custom_values::value.eq(&stored_value))
+        .execute(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(TaskCustomFieldValue {
+        field_id: field.id,
+        value: custom_fields::value_to_json(&field.field_type, &stored_value),
+        name: field.name,
+        field_type: field.field_type,
+    }))
+}
+
+// === DELETE /tasks/{task_id_path}/custom-fields/{field_id_path} ===
+#[delete("/{task_id_path}/custom-fields/{field_id_path}")]
+pub async fn clear_task_custom_value_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    path_params: web::Path<(TaskId, Uuid)>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let (task_uuid, field_uuid) = path_params.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskWrite).await?;
+
+    let num_deleted = diesel::delete(
+        task_custom_values::table
+            .filter(task_custom_values::task_id.eq(task_uuid))
+            .filter(task_custom_values::field_id.eq(field_uuid)),
+    )
+    .execute(&mut conn)
+    .await
+    .map_err(ServiceError::from)?;
+
+    if num_deleted > 0 {
+        Ok(HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("Custom field {} cleared on task {}", field_uuid, task_uuid)
+        })))
+    } else {
+        Err(ServiceError::NotFound(format!(
+            "No value for custom field {} on task {}",
+            field_uuid, task_uuid
+        )))
+    }
+}
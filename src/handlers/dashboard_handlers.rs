@@ -0,0 +1,300 @@
+// OptiTask/backend-api/src/handlers/dashboard_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::handlers::analytics_handlers::{load_time_by_project, period_bounds};
+use crate::models::{
+    Task, TaskApiResponse, TimeByProjectStat, TimeEntry, DONE_TASK_STATUSES, TASK_STAGE_ACTIVE,
+    TASK_STAGE_BACKLOG,
+};
+use crate::repository;
+use crate::schema::{tasks, time_entries};
+use crate::settings;
+use actix_web::{get, web, HttpResponse};
+use chrono::{Duration, NaiveDate, Utc, Weekday};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+const TOP_PROJECTS_LIMIT: usize = 5;
+// Au-delà, la série est plafonnée (évite de scanner tout l'historique)
+const STREAK_LOOKBACK_DAYS: i64 = 365;
+
+#[derive(Deserialize, Debug)]
+pub struct DashboardQueryParams {
+    // Inclure les tâches reportées dans today_tasks
+    #[serde(default)]
+    pub include_snoozed: bool,
+    // Inclure les tâches du backlog dans today_tasks
+    #[serde(default)]
+    pub include_backlog: bool,
+    // Premier jour de la semaine ; à défaut, celui des préférences de l'utilisateur
+    pub week_start: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DailyTimeTotal {
+    pub date: NaiveDate,
+    pub total_duration_seconds: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WeekTimeTotals {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub total_duration_seconds: i64,
+    pub pomodoro_sessions: i64,
+    pub by_day: Vec<DailyTimeTotal>,
+}
+
+// Tâches sans projet (GET /tasks?view=inbox), à trier
+#[derive(Serialize, Debug)]
+pub struct InboxCounts {
+    pub open_tasks: i64,
+    pub total_tasks: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DashboardResponse {
+    pub date: NaiveDate,
+    pub today_tasks: Vec<TaskApiResponse>,
+    pub running_timer: Option<TimeEntry>,
+    pub week: WeekTimeTotals,
+    // Jours consécutifs (jusqu'à aujourd'hui) avec du temps suivi
+    pub streak_days: i64,
+    pub top_projects: Vec<TimeByProjectStat>,
+    pub inbox: InboxCounts,
+}
+
+// Tâches ouvertes dues aujourd'hui ou en retard, les tâches épinglées en tête ;
+// les tâches reportées et celles du backlog sont masquées sauf demande explicite,
+// les tâches archivées toujours
+async fn load_today_tasks(
+    pool: &DbPool,
+    user_uuid: Uuid,
+    today: NaiveDate,
+    include_snoozed: bool,
+    include_backlog: bool,
+) -> Result<Vec<TaskApiResponse>, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    let visible_stages: &[&str] = if include_backlog {
+        &[TASK_STAGE_ACTIVE, TASK_STAGE_BACKLOG]
+    } else {
+        &[TASK_STAGE_ACTIVE]
+    };
+    let mut task_query = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .filter(tasks::due_date.le(today))
+        .filter(tasks::status.ne_all(DONE_TASK_STATUSES))
+        .filter(tasks::stage.eq_any(visible_stages))
+        .into_boxed();
+    if !include_snoozed {
+        task_query = task_query.filter(
+            tasks::snoozed_until
+                .is_null()
+                .or(tasks::snoozed_until.le(Utc::now())),
+        );
+    }
+
+    let task_list = task_query
+        .order((
+            tasks::is_pinned.desc(),
+            tasks::due_date.asc(),
+            tasks::task_order.asc(),
+        ))
+        .select(Task::as_select())
+        .load::<Task>(&mut conn)
+        .await?;
+
+    let mut task_responses: Vec<TaskApiResponse> =
+        task_list.into_iter().map(TaskApiResponse::from).collect();
+    repository::attach_labels(&mut conn, &mut task_responses).await?;
+    Ok(task_responses)
+}
+
+async fn load_running_timer(
+    pool: &DbPool,
+    user_uuid: Uuid,
+) -> Result<Option<TimeEntry>, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::end_time.is_null())
+        .order(time_entries::start_time.desc())
+        .select(TimeEntry::as_select())
+        .first::<TimeEntry>(&mut conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+async fn load_week_totals(
+    pool: &DbPool,
+    user_uuid: Uuid,
+    today: NaiveDate,
+    week_start: Weekday,
+) -> Result<WeekTimeTotals, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    let start_date = today.week(week_start).first_day();
+    let end_date = today.week(week_start).last_day();
+    let (start_datetime, end_datetime) = period_bounds(start_date, end_date);
+
+    let entries = time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::is_break.eq(false))
+        .filter(time_entries::start_time.ge(start_datetime))
+        .filter(time_entries::start_time.le(end_datetime))
+        .select((
+            time_entries::start_time,
+            time_entries::duration_seconds,
+            time_entries::is_pomodoro_session,
+        ))
+        .load::<(chrono::DateTime<Utc>, Option<i32>, bool)>(&mut conn)
+        .await?;
+
+    let mut by_day: BTreeMap<NaiveDate, i64> = start_date
+        .iter_days()
+        .take_while(|day| *day <= end_date)
+        .map(|day| (day, 0))
+        .collect();
+    let mut pomodoro_sessions = 0;
+    for (start_time, duration, is_pomodoro) in &entries {
+        *by_day.entry(start_time.date_naive()).or_insert(0) += i64::from(duration.unwrap_or(0));
+        if *is_pomodoro {
+            pomodoro_sessions += 1;
+        }
+    }
+
+    Ok(WeekTimeTotals {
+        start_date,
+        end_date,
+        total_duration_seconds: by_day.values().sum(),
+        pomodoro_sessions,
+        by_day: by_day
+            .into_iter()
+            .map(|(date, total_duration_seconds)| DailyTimeTotal {
+                date,
+                total_duration_seconds,
+            })
+            .collect(),
+    })
+}
+
+// Une journée sans suivi aujourd'hui ne casse pas encore la série (elle part d'hier)
+async fn load_streak(
+    pool: &DbPool,
+    user_uuid: Uuid,
+    today: NaiveDate,
+) -> Result<i64, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    let (lookback_start, _) = period_bounds(today - Duration::days(STREAK_LOOKBACK_DAYS), today);
+    let start_times = time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::start_time.ge(lookback_start))
+        .select(time_entries::start_time)
+        .load::<chrono::DateTime<Utc>>(&mut conn)
+        .await?;
+
+    let active_days: HashSet<NaiveDate> = start_times.iter().map(|t| t.date_naive()).collect();
+
+    let mut day = if active_days.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+    let mut streak = 0;
+    while active_days.contains(&day) {
+        streak += 1;
+        day -= Duration::days(1);
+    }
+    Ok(streak)
+}
+
+async fn load_top_projects(
+    pool: &DbPool,
+    user_uuid: Uuid,
+    today: NaiveDate,
+    week_start: Weekday,
+) -> Result<Vec<TimeByProjectStat>, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    let mut stats = load_time_by_project(
+        &mut conn,
+        user_uuid,
+        today.week(week_start).first_day(),
+        today.week(week_start).last_day(),
+    )
+    .await?;
+    stats.truncate(TOP_PROJECTS_LIMIT);
+    Ok(stats)
+}
+
+async fn load_inbox_counts(pool: &DbPool, user_uuid: Uuid) -> Result<InboxCounts, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    let inbox_tasks = || {
+        tasks::table
+            .filter(tasks::user_id.eq(user_uuid))
+            .filter(tasks::project_id.is_null())
+    };
+    let total_tasks = inbox_tasks().count().get_result::<i64>(&mut conn).await?;
+    let open_tasks = inbox_tasks()
+        .filter(tasks::status.ne_all(DONE_TASK_STATUSES))
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?;
+
+    Ok(InboxCounts {
+        open_tasks,
+        total_tasks,
+    })
+}
+
+// === GET /dashboard ===
+// Agrège en une requête les données de la page d'accueil ; chaque bloc utilise
+// sa propre connexion du pool pour que les requêtes s'exécutent en parallèle.
+#[get("")]
+pub async fn get_dashboard_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<DashboardQueryParams>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let today = Utc::now().date_naive();
+
+    let week_start = {
+        let mut conn = pool.get().await?;
+        settings::resolve_week_start(&mut conn, user_uuid, query.week_start.as_deref()).await?
+    };
+
+    let (today_tasks, running_timer, week, streak_days, top_projects, inbox) = tokio::try_join!(
+        load_today_tasks(
+            &pool,
+            user_uuid,
+            today,
+            query.include_snoozed,
+            query.include_backlog,
+        ),
+        load_running_timer(&pool, user_uuid),
+        load_week_totals(&pool, user_uuid, today, week_start),
+        load_streak(&pool, user_uuid, today),
+        load_top_projects(&pool, user_uuid, today, week_start),
+        load_inbox_counts(&pool, user_uuid),
+    )?;
+
+    Ok(HttpResponse::Ok().json(DashboardResponse {
+        date: today,
+        today_tasks,
+        running_timer,
+        week,
+        streak_days,
+        top_projects,
+        inbox,
+    }))
+}
@@ -0,0 +1,240 @@
+// OptiTask/backend-api/src/handlers/device_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{
+    Device, RegisterDevicePayload, UpdateDeviceSyncStatePayload, DEVICE_PLATFORMS,
+};
+use crate::quotas;
+use crate::schema::devices;
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_DEVICE_NAME_CHARS: usize = 100;
+const MAX_PUSH_TOKEN_CHARS: usize = 4096;
+const MAX_SYNC_CURSOR_CHARS: usize = 1024;
+
+// === GET /devices ===
+// Appareils vus le plus récemment en tête
+#[get("")]
+pub async fn list_devices_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let device_list = devices::table
+        .filter(devices::user_id.eq(authenticated_user.id))
+        .order(devices::last_seen_at.desc())
+        .select(Device::as_select())
+        .load::<Device>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(device_list))
+}
+
+// === POST /devices/register ===
+// Enregistre un appareil, ou met à jour celui désigné par device_id (201 si créé, 200 sinon).
+// Un jeton push déjà associé à un autre appareil lui est retiré : le téléphone a changé
+// de compte ou d'installation, l'ancien appareil ne doit plus recevoir de notifications.
+#[post("/register")]
+pub async fn register_device_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<RegisterDevicePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let payload = payload.into_inner();
+
+    let device_name = payload.name.trim().to_string();
+    if device_name.is_empty() || device_name.chars().count() > MAX_DEVICE_NAME_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "name must contain between 1 and {} characters",
+            MAX_DEVICE_NAME_CHARS
+        )));
+    }
+    let platform = payload.platform.trim().to_ascii_lowercase();
+    if !DEVICE_PLATFORMS.contains(&platform.as_str()) {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid platform '{}'. Supported: {}",
+            payload.platform,
+            DEVICE_PLATFORMS.join(", ")
+        )));
+    }
+    let push_token = payload
+        .push_token
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty());
+    if push_token
+        .as_ref()
+        .is_some_and(|token| token.chars().count() > MAX_PUSH_TOKEN_CHARS)
+    {
+        return Err(ServiceError::ValidationError(format!(
+            "push_token cannot exceed {} characters",
+            MAX_PUSH_TOKEN_CHARS
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Appareils enregistrés après la création, None si l'appareil existait déjà
+    let (device, registered_count) = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let existing = match payload.device_id {
+                    Some(device_uuid) => devices::table
+                        .filter(devices::id.eq(device_uuid))
+                        .filter(devices::user_id.eq(user_uuid))
+                        .select(devices::id)
+                        .first::<Uuid>(conn)
+                        .await
+                        .optional()?,
+                    None => None,
+                };
+
+                if let Some(token) = &push_token {
+                    diesel::update(
+                        devices::table
+                            .filter(devices::push_token.eq(token))
+                            .filter(devices::id.ne(existing.unwrap_or_else(Uuid::nil))),
+                    )
+                    .set(devices::push_token.eq(None::<String>))
+                    .execute(conn)
+                    .await?;
+                }
+
+                match existing {
+                    Some(device_uuid) => {
+                        // Sans jeton dans la requête, le jeton enregistré est conservé
+                        let device = diesel::update(devices::table.find(device_uuid))
+                            .set((
+                                devices::name.eq(&device_name),
+                                devices::platform.eq(&platform),
+                                devices::last_seen_at.eq(Utc::now()),
+                                push_token
+                                    .as_ref()
+                                    .map(|token| devices::push_token.eq(token)),
+                            ))
+                            .returning(Device::as_returning())
+                            .get_result::<Device>(conn)
+                            .await?;
+                        Ok((device, None))
+                    }
+                    None => {
+                        let device_count = devices::table
+                            .filter(devices::user_id.eq(user_uuid))
+                            .count()
+                            .get_result::<i64>(conn)
+                            .await?;
+                        if quotas::DEVICES.is_exceeded(device_count) {
+                            return Err(ServiceError::ValidationError(format!(
+                                "At most {} devices can be registered; revoke an unused one first",
+                                quotas::DEVICES.limit
+                            )));
+                        }
+
+                        let device = diesel::insert_into(devices::table)
+                            .values((
+                                devices::user_id.eq(user_uuid),
+                                devices::name.eq(&device_name),
+                                devices::platform.eq(&platform),
+                                devices::push_token.eq(push_token),
+                            ))
+                            .returning(Device::as_returning())
+                            .get_result::<Device>(conn)
+                            .await?;
+                        Ok((device, Some(device_count + 1)))
+                    }
+                }
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    match registered_count {
+        Some(used) => Ok(quotas::DEVICES.created_response(StatusCode::CREATED, &device, used)),
+        None => Ok(HttpResponse::Ok().json(device)),
+    }
+}
+
+// === PUT /devices/{device_id_path}/sync-state ===
+// Enregistre le curseur de la dernière synchronisation réussie de l'appareil
+#[put("/{device_id_path}/sync-state")]
+pub async fn update_device_sync_state_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    device_id_path: web::Path<Uuid>,
+    payload: web::Json<UpdateDeviceSyncStatePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let device_uuid = device_id_path.into_inner();
+    let sync_cursor = payload.sync_cursor.trim();
+    if sync_cursor.is_empty() || sync_cursor.chars().count() > MAX_SYNC_CURSOR_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "sync_cursor must contain between 1 and {} characters",
+            MAX_SYNC_CURSOR_CHARS
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let now = Utc::now();
+    let device = diesel::update(
+        devices::table
+            .filter(devices::id.eq(device_uuid))
+            .filter(devices::user_id.eq(authenticated_user.id)),
+    )
+    .set((
+        devices::sync_cursor.eq(sync_cursor),
+        devices::last_synced_at.eq(now),
+        devices::last_seen_at.eq(now),
+    ))
+    .returning(Device::as_returning())
+    .get_result::<Device>(&mut conn)
+    .await
+    .optional()?
+    .ok_or_else(|| ServiceError::NotFound(format!("Device with id {} not found", device_uuid)))?;
+
+    Ok(HttpResponse::Ok().json(device))
+}
+
+// === DELETE /devices/{device_id_path} ===
+// Révoque l'appareil : il ne reçoit plus de notifications et son état de synchronisation est perdu
+#[delete("/{device_id_path}")]
+pub async fn revoke_device_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    device_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let device_uuid = device_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(
+        devices::table
+            .filter(devices::id.eq(device_uuid))
+            .filter(devices::user_id.eq(authenticated_user.id)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "Device with id {} not found",
+            device_uuid
+        )));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Device revoked"
+    })))
+}
@@ -0,0 +1,255 @@
+// OptiTask/backend-api/src/handlers/experiment_handlers.rs
+use crate::admin::AdminConfig;
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::experiments;
+use crate::feature_flags;
+use crate::models::{
+    Experiment, ExperimentEventPayload, ExperimentEventStat, ExperimentResultsResponse,
+    ExperimentVariantResults, UpsertExperimentPayload,
+};
+use crate::schema::{experiment_assignments, experiment_events, experiments as experiments_table};
+use actix_web::{get, post, put, web, HttpResponse};
+use diesel::dsl::{count_distinct, count_star};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+
+fn experiment_not_found(experiment_key: &str) -> ServiceError {
+    ServiceError::NotFound(format!("Experiment '{}' not found", experiment_key))
+}
+
+// === GET /experiments ===
+// Variantes de l'utilisateur courant pour les expériences actives : { "clé": "variante" }.
+// L'appel vaut exposition : les nouvelles affectations sont enregistrées.
+#[get("")]
+pub async fn get_my_experiments_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let assignments = experiments::assignments_for(&mut conn, authenticated_user.id).await?;
+
+    Ok(HttpResponse::Ok().json(assignments))
+}
+
+// === POST /experiments/{experiment_key}/event ===
+// Enregistre un événement de conversion avec la variante de l'utilisateur
+#[post("/{experiment_key}/event")]
+pub async fn record_experiment_event_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    experiment_key: web::Path<String>,
+    payload: web::Json<ExperimentEventPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let experiment_key = experiment_key.into_inner();
+    let event = payload.event.trim().to_ascii_lowercase();
+    feature_flags::validate_key(&event)?;
+    if payload.value.is_some_and(|value| !value.is_finite()) {
+        return Err(ServiceError::ValidationError(
+            "value must be a finite number".to_string(),
+        ));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let variant = experiments::variant_for(&mut conn, authenticated_user.id, &experiment_key)
+        .await?
+        .ok_or_else(|| experiment_not_found(&experiment_key))?;
+
+    diesel::insert_into(experiment_events::table)
+        .values((
+            experiment_events::experiment_key.eq(&experiment_key),
+            experiment_events::user_id.eq(authenticated_user.id),
+            experiment_events::variant.eq(&variant),
+            experiment_events::event.eq(&event),
+            experiment_events::value.eq(payload.value),
+        ))
+        .execute(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "experiment": experiment_key,
+        "variant": variant,
+        "event": event
+    })))
+}
+
+// === GET /admin/experiments ===
+#[get("/experiments")]
+pub async fn admin_list_experiments_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let experiment_list = experiments_table::table
+        .order(experiments_table::key.asc())
+        .select(Experiment::as_select())
+        .load::<Experiment>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(experiment_list))
+}
+
+// === PUT /admin/experiments/{experiment_key} ===
+// Crée l'expérience ou remplace sa configuration ; les affectations existantes sont
+// conservées (un utilisateur garde sa variante tant qu'elle existe)
+#[put("/experiments/{experiment_key}")]
+pub async fn admin_upsert_experiment_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    experiment_key: web::Path<String>,
+    payload: web::Json<UpsertExperimentPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let experiment_key = experiment_key.into_inner();
+    feature_flags::validate_key(&experiment_key)?;
+    experiments::validate_variants(&payload.variants)?;
+    let description = payload
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|description| !description.is_empty());
+    let variants = serde_json::to_value(&payload.variants)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let experiment = diesel::insert_into(experiments_table::table)
+        .values((
+            experiments_table::key.eq(&experiment_key),
+            experiments_table::description.eq(description),
+            experiments_table::variants.eq(&variants),
+            experiments_table::is_active.eq(payload.is_active),
+        ))
+        .on_conflict(experiments_table::key)
+        .do_update()
+        .set((
+            experiments_table::description.eq(excluded(experiments_table::description)),
+            experiments_table::variants.eq(excluded(experiments_table::variants)),
+            experiments_table::is_active.eq(excluded(experiments_table::is_active)),
+        ))
+        .returning(Experiment::as_returning())
+        .get_result::<Experiment>(&mut conn)
+        .await?;
+
+    // Les utilisateurs affectés à une variante retirée seront tirés à nouveau
+    let variant_names: Vec<String> = experiments::parse_variants(&experiment)
+        .into_iter()
+        .map(|variant| variant.name)
+        .collect();
+    diesel::delete(
+        experiment_assignments::table
+            .filter(experiment_assignments::experiment_key.eq(&experiment_key))
+            .filter(experiment_assignments::variant.ne_all(&variant_names)),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    Ok(HttpResponse::Ok().json(experiment))
+}
+
+// === GET /admin/experiments/{experiment_key}/results ===
+// Par variante : utilisateurs exposés, occurrences et utilisateurs distincts par événement
+#[get("/experiments/{experiment_key}/results")]
+pub async fn admin_experiment_results_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    experiment_key: web::Path<String>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let experiment_key = experiment_key.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let experiment = experiments_table::table
+        .find(&experiment_key)
+        .select(Experiment::as_select())
+        .first::<Experiment>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| experiment_not_found(&experiment_key))?;
+
+    let assigned_counts = experiment_assignments::table
+        .filter(experiment_assignments::experiment_key.eq(&experiment_key))
+        .group_by(experiment_assignments::variant)
+        .select((experiment_assignments::variant, count_star()))
+        .load::<(String, i64)>(&mut conn)
+        .await?;
+    let event_stats = experiment_events::table
+        .filter(experiment_events::experiment_key.eq(&experiment_key))
+        .group_by((experiment_events::variant, experiment_events::event))
+        .select((
+            experiment_events::variant,
+            experiment_events::event,
+            count_star(),
+            count_distinct(experiment_events::user_id),
+            diesel::dsl::sum(experiment_events::value),
+        ))
+        .order((experiment_events::variant, experiment_events::event))
+        .load::<(String, String, i64, i64, Option<f64>)>(&mut conn)
+        .await?;
+
+    // Variantes configurées d'abord, puis celles qui n'existent plus mais ont des données
+    let mut variant_names: Vec<String> = experiments::parse_variants(&experiment)
+        .into_iter()
+        .map(|variant| variant.name)
+        .collect();
+    for (variant, ..) in &event_stats {
+        if !variant_names.contains(variant) {
+            variant_names.push(variant.clone());
+        }
+    }
+    for (variant, _) in &assigned_counts {
+        if !variant_names.contains(variant) {
+            variant_names.push(variant.clone());
+        }
+    }
+
+    let variants = variant_names
+        .into_iter()
+        .map(|variant| {
+            let assigned_users = assigned_counts
+                .iter()
+                .find(|(name, _)| *name == variant)
+                .map_or(0, |(_, count)| *count);
+            let events = event_stats
+                .iter()
+                .filter(|(name, ..)| *name == variant)
+                .map(
+                    |(_, event, occurrences, unique_users, total_value)| ExperimentEventStat {
+                        event: event.clone(),
+                        occurrences: *occurrences,
+                        unique_users: *unique_users,
+                        conversion_rate: (assigned_users > 0)
+                            .then(|| *unique_users as f64 / assigned_users as f64),
+                        total_value: *total_value,
+                    },
+                )
+                .collect();
+            ExperimentVariantResults {
+                variant,
+                assigned_users,
+                events,
+            }
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ExperimentResultsResponse {
+        experiment,
+        variants,
+    }))
+}
@@ -0,0 +1,242 @@
+// OptiTask/backend-api/src/handlers/feature_flag_handlers.rs
+use crate::admin::AdminConfig;
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::feature_flags;
+use crate::models::{
+    FeatureFlag, FeatureFlagDetailResponse, FeatureFlagOverride, SetFeatureFlagOverridePayload,
+    UpsertFeatureFlagPayload,
+};
+use crate::schema::{feature_flag_overrides, feature_flags as feature_flags_table};
+use actix_web::{delete, get, put, web, HttpResponse};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+use uuid::Uuid;
+
+fn flag_not_found(flag_key: &str) -> ServiceError {
+    ServiceError::NotFound(format!("Feature flag '{}' not found", flag_key))
+}
+
+// === GET /flags ===
+// Drapeaux évalués pour l'utilisateur courant : { "clé": true/false }
+#[get("")]
+pub async fn get_my_flags_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let evaluated = feature_flags::evaluate_all(&mut conn, authenticated_user.id).await?;
+
+    Ok(HttpResponse::Ok().json(evaluated))
+}
+
+// === GET /admin/flags ===
+#[get("/flags")]
+pub async fn admin_list_flags_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let flag_list = feature_flags_table::table
+        .order(feature_flags_table::key.asc())
+        .select(FeatureFlag::as_select())
+        .load::<FeatureFlag>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(flag_list))
+}
+
+// === GET /admin/flags/{flag_key} ===
+// Le drapeau et ses overrides par utilisateur
+#[get("/flags/{flag_key}")]
+pub async fn admin_get_flag_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    flag_key: web::Path<String>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let flag_key = flag_key.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let flag = feature_flags_table::table
+        .find(&flag_key)
+        .select(FeatureFlag::as_select())
+        .first::<FeatureFlag>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| flag_not_found(&flag_key))?;
+    let overrides = feature_flag_overrides::table
+        .filter(feature_flag_overrides::flag_key.eq(&flag_key))
+        .order(feature_flag_overrides::created_at.asc())
+        .select(FeatureFlagOverride::as_select())
+        .load::<FeatureFlagOverride>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(FeatureFlagDetailResponse { flag, overrides }))
+}
+
+// === PUT /admin/flags/{flag_key} ===
+// Crée le drapeau ou remplace sa configuration ; les overrides sont conservés
+#[put("/flags/{flag_key}")]
+pub async fn admin_upsert_flag_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    flag_key: web::Path<String>,
+    payload: web::Json<UpsertFeatureFlagPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let flag_key = flag_key.into_inner();
+    feature_flags::validate_key(&flag_key)?;
+    if !(0..=100).contains(&payload.rollout_percentage) {
+        return Err(ServiceError::ValidationError(
+            "rollout_percentage must be between 0 and 100".to_string(),
+        ));
+    }
+    let description = payload
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|description| !description.is_empty());
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let flag = diesel::insert_into(feature_flags_table::table)
+        .values((
+            feature_flags_table::key.eq(&flag_key),
+            feature_flags_table::description.eq(description),
+            feature_flags_table::enabled.eq(payload.enabled),
+            feature_flags_table::rollout_percentage.eq(payload.rollout_percentage),
+        ))
+        .on_conflict(feature_flags_table::key)
+        .do_update()
+        .set((
+            feature_flags_table::description.eq(excluded(feature_flags_table::description)),
+            feature_flags_table::enabled.eq(excluded(feature_flags_table::enabled)),
+            feature_flags_table::rollout_percentage
+                .eq(excluded(feature_flags_table::rollout_percentage)),
+        ))
+        .returning(FeatureFlag::as_returning())
+        .get_result::<FeatureFlag>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(flag))
+}
+
+// === DELETE /admin/flags/{flag_key} ===
+// Supprime le drapeau et ses overrides ; il est ensuite évalué comme inactif
+#[delete("/flags/{flag_key}")]
+pub async fn admin_delete_flag_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    flag_key: web::Path<String>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let flag_key = flag_key.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(feature_flags_table::table.find(&flag_key))
+        .execute(&mut conn)
+        .await?;
+
+    if num_deleted == 0 {
+        return Err(flag_not_found(&flag_key));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": format!("Feature flag '{}' deleted", flag_key)
+    })))
+}
+
+// === PUT /admin/flags/{flag_key}/overrides/{user_id} ===
+// Impose la valeur du drapeau pour un utilisateur
+#[put("/flags/{flag_key}/overrides/{user_id}")]
+pub async fn admin_set_flag_override_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    path: web::Path<(String, Uuid)>,
+    payload: web::Json<SetFeatureFlagOverridePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let (flag_key, target_user_uuid) = path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let flag_exists = diesel::select(diesel::dsl::exists(
+        feature_flags_table::table.find(&flag_key),
+    ))
+    .get_result::<bool>(&mut conn)
+    .await?;
+    if !flag_exists {
+        return Err(flag_not_found(&flag_key));
+    }
+
+    let flag_override = diesel::insert_into(feature_flag_overrides::table)
+        .values((
+            feature_flag_overrides::flag_key.eq(&flag_key),
+            feature_flag_overrides::user_id.eq(target_user_uuid),
+            feature_flag_overrides::enabled.eq(payload.enabled),
+        ))
+        .on_conflict((
+            feature_flag_overrides::flag_key,
+            feature_flag_overrides::user_id,
+        ))
+        .do_update()
+        .set(feature_flag_overrides::enabled.eq(payload.enabled))
+        .returning(FeatureFlagOverride::as_returning())
+        .get_result::<FeatureFlagOverride>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(flag_override))
+}
+
+// === DELETE /admin/flags/{flag_key}/overrides/{user_id} ===
+// L'utilisateur retombe sur l'interrupteur global et le pourcentage
+#[delete("/flags/{flag_key}/overrides/{user_id}")]
+pub async fn admin_delete_flag_override_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    path: web::Path<(String, Uuid)>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let (flag_key, target_user_uuid) = path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted =
+        diesel::delete(feature_flag_overrides::table.find((&flag_key, target_user_uuid)))
+            .execute(&mut conn)
+            .await?;
+
+    if num_deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "No override of '{}' for user {}",
+            flag_key, target_user_uuid
+        )));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Override removed"
+    })))
+}
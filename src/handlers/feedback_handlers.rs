@@ -0,0 +1,188 @@
+// OptiTask/backend-api/src/handlers/feedback_handlers.rs
+use crate::admin::AdminConfig;
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{
+    CreateFeedbackPayload, Feedback, FeedbackListQuery, NewFeedback, PaginatedResponse,
+    FEEDBACK_CATEGORIES,
+};
+use crate::pagination;
+use crate::quotas;
+use crate::schema::feedback;
+use actix_web::http::StatusCode;
+use actix_web::{get, post, web, HttpResponse};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+const MAX_FEEDBACK_MESSAGE_CHARS: usize = 5000;
+const MAX_APP_VERSION_CHARS: usize = 50;
+// Taille maximale du diagnostic sérialisé (32 Ko)
+const MAX_DIAGNOSTICS_BYTES: usize = 32 * 1024;
+const DEFAULT_FEEDBACK_PER_PAGE: i64 = 20;
+const MAX_FEEDBACK_PER_PAGE: i64 = 100;
+
+fn validate_category(category: &str) -> Result<String, ServiceError> {
+    let normalized = category.trim().to_ascii_lowercase();
+    if !FEEDBACK_CATEGORIES.contains(&normalized.as_str()) {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid category '{}'. Supported: {}",
+            category,
+            FEEDBACK_CATEGORIES.join(", ")
+        )));
+    }
+    Ok(normalized)
+}
+
+// === POST /feedback ===
+// Enregistre un retour de l'utilisateur, dans la limite du quota horaire (quotas.rs)
+#[post("")]
+pub async fn create_feedback_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<CreateFeedbackPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let payload = payload.into_inner();
+
+    let category = validate_category(&payload.category)?;
+    let message = payload.message.trim().to_string();
+    if message.is_empty() || message.chars().count() > MAX_FEEDBACK_MESSAGE_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "message must contain between 1 and {} characters",
+            MAX_FEEDBACK_MESSAGE_CHARS
+        )));
+    }
+    let app_version = payload
+        .app_version
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty());
+    if app_version
+        .as_ref()
+        .is_some_and(|version| version.chars().count() > MAX_APP_VERSION_CHARS)
+    {
+        return Err(ServiceError::ValidationError(format!(
+            "app_version cannot exceed {} characters",
+            MAX_APP_VERSION_CHARS
+        )));
+    }
+    match &payload.diagnostics {
+        Some(serde_json::Value::Object(_)) | None => {}
+        Some(_) => {
+            return Err(ServiceError::ValidationError(
+                "diagnostics must be a JSON object".to_string(),
+            ))
+        }
+    }
+    if serde_json::to_vec(&payload.diagnostics)?.len() > MAX_DIAGNOSTICS_BYTES {
+        return Err(ServiceError::ValidationError(format!(
+            "diagnostics cannot exceed {} bytes",
+            MAX_DIAGNOSTICS_BYTES
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let recent_count = feedback::table
+        .filter(feedback::user_id.eq(user_uuid))
+        .filter(feedback::created_at.ge(Utc::now() - Duration::hours(1)))
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?;
+    if quotas::FEEDBACK_PER_HOUR.is_exceeded(recent_count) {
+        return Err(ServiceError::TooManyRequests(format!(
+            "At most {} feedback messages can be sent per hour",
+            quotas::FEEDBACK_PER_HOUR.limit
+        )));
+    }
+
+    let created = diesel::insert_into(feedback::table)
+        .values(&NewFeedback {
+            user_id: user_uuid,
+            category,
+            message,
+            app_version,
+            diagnostics: payload.diagnostics,
+        })
+        .returning(Feedback::as_returning())
+        .get_result::<Feedback>(&mut conn)
+        .await?;
+
+    Ok(quotas::FEEDBACK_PER_HOUR.created_response(StatusCode::CREATED, &created, recent_count + 1))
+}
+
+// === GET /feedback ===
+// Retours envoyés par l'utilisateur, les plus récents en tête
+#[get("")]
+pub async fn list_my_feedback_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let feedback_list = feedback::table
+        .filter(feedback::user_id.eq(authenticated_user.id))
+        .order(feedback::created_at.desc())
+        .select(Feedback::as_select())
+        .load::<Feedback>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(feedback_list))
+}
+
+// === GET /admin/feedback ===
+// Tous les retours, paginés et filtrables par catégorie et date (administrateurs uniquement)
+#[get("/feedback")]
+pub async fn admin_list_feedback_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<FeedbackListQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query
+        .per_page
+        .unwrap_or(DEFAULT_FEEDBACK_PER_PAGE)
+        .clamp(1, MAX_FEEDBACK_PER_PAGE);
+    let category = query
+        .category
+        .as_deref()
+        .map(validate_category)
+        .transpose()?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let filtered = || {
+        let mut filtered_query = feedback::table.into_boxed();
+        if let Some(category) = &category {
+            filtered_query = filtered_query.filter(feedback::category.eq(category.clone()));
+        }
+        if let Some(since) = query.since {
+            filtered_query = filtered_query.filter(feedback::created_at.ge(since));
+        }
+        filtered_query
+    };
+
+    let total_items = filtered().count().get_result::<i64>(&mut conn).await?;
+    let items = filtered()
+        .order(feedback::created_at.desc())
+        .limit(per_page)
+        .offset((page - 1) * per_page)
+        .select(Feedback::as_select())
+        .load::<Feedback>(&mut conn)
+        .await?;
+
+    Ok(pagination::paginated_response(PaginatedResponse {
+        items,
+        total_items,
+        total_pages: (total_items + per_page - 1) / per_page,
+        page,
+        per_page,
+    }))
+}
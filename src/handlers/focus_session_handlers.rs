@@ -0,0 +1,157 @@
+// OptiTask/backend-api/src/handlers/focus_session_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::focus_sessions::{self, FOCUS_SESSION_ACTIVE};
+use crate::models::{FocusSession, NewFocusSession, StartFocusSessionPayload};
+use crate::permissions::{self, Permission};
+use crate::schema::{focus_sessions as focus_sessions_table, labels};
+use actix_web::{get, post, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use uuid::Uuid;
+
+// === POST /focus-sessions ===
+// Démarre une session de concentration sur un label de l'utilisateur ou sur un projet
+// accessible. Une seule session active à la fois (409 FOCUS_SESSION_ACTIVE).
+#[post("")]
+pub async fn start_focus_session_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<StartFocusSessionPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let payload = payload.into_inner();
+    if payload.label_id.is_some() == payload.project_id.is_some() {
+        return Err(ServiceError::ValidationError(
+            "Exactly one of label_id or project_id must be provided".to_string(),
+        ));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    if let Some(label_uuid) = payload.label_id {
+        let owned = labels::table
+            .filter(labels::id.eq(label_uuid))
+            .filter(labels::user_id.eq(user_uuid))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        if owned == 0 {
+            return Err(ServiceError::NotFound(format!(
+                "Label with id {} not found or not owned by user",
+                label_uuid
+            )));
+        }
+    }
+    if let Some(project_uuid) = payload.project_id {
+        permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectRead)
+            .await?;
+    }
+
+    if let Some(active) = focus_sessions::active_session(&mut conn, user_uuid).await? {
+        return Err(ServiceError::CodedConflict(
+            FOCUS_SESSION_ACTIVE,
+            format!(
+                "Focus session {} is already active. End it before starting a new one.",
+                active.id
+            ),
+        ));
+    }
+
+    let session = diesel::insert_into(focus_sessions_table::table)
+        .values(&NewFocusSession {
+            user_id: user_uuid,
+            label_id: payload.label_id,
+            project_id: payload.project_id,
+        })
+        .returning(FocusSession::as_returning())
+        .get_result::<FocusSession>(&mut conn)
+        .await?;
+
+    log::info!("User {} started focus session {}", user_uuid, session.id);
+    Ok(HttpResponse::Created().json(session))
+}
+
+// === GET /focus-sessions/active ===
+// Session active de l'utilisateur, ou null
+#[get("/active")]
+pub async fn get_active_focus_session_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let session = focus_sessions::active_session(&mut conn, authenticated_user.id).await?;
+    Ok(HttpResponse::Ok().json(session))
+}
+
+// === POST /focus-sessions/{session_id_path}/end ===
+// Termine la session et renvoie son bilan, figé dans `summary`. Les minuteurs en cours ne
+// sont pas arrêtés ; ils comptent dans le bilan jusqu'à la fin de la session.
+#[post("/{session_id_path}/end")]
+pub async fn end_focus_session_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    session_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let session_uuid = session_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let ended = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let session = focus_sessions_table::table
+                    .filter(focus_sessions_table::id.eq(session_uuid))
+                    .filter(focus_sessions_table::user_id.eq(user_uuid))
+                    .for_update()
+                    .select(FocusSession::as_select())
+                    .first::<FocusSession>(conn)
+                    .await
+                    .optional()?
+                    .ok_or_else(|| {
+                        ServiceError::NotFound(format!(
+                            "Focus session with id {} not found",
+                            session_uuid
+                        ))
+                    })?;
+                if session.ended_at.is_some() {
+                    return Err(ServiceError::ConflictError(format!(
+                        "Focus session {} has already ended",
+                        session_uuid
+                    )));
+                }
+
+                let ended_at = Utc::now();
+                let summary = focus_sessions::build_summary(conn, &session, ended_at).await?;
+                let summary = serde_json::to_value(&summary).map_err(|e| {
+                    ServiceError::InternalServerError(format!(
+                        "Failed to serialize focus session summary: {}",
+                        e
+                    ))
+                })?;
+
+                diesel::update(focus_sessions_table::table.find(session.id))
+                    .set((
+                        focus_sessions_table::ended_at.eq(Some(ended_at)),
+                        focus_sessions_table::summary.eq(Some(summary)),
+                    ))
+                    .returning(FocusSession::as_returning())
+                    .get_result::<FocusSession>(conn)
+                    .await
+                    .map_err(ServiceError::from)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    log::info!("User {} ended focus session {}", user_uuid, ended.id);
+    Ok(HttpResponse::Ok().json(ended))
+}
@@ -0,0 +1,284 @@
+// OptiTask/backend-api/src/handlers/inbound_email_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::inbound_email::{self, InboundEmailConfig, InboundMessage};
+use crate::models::{InboundEmailAddress, InboundEmailAddressResponse};
+use crate::outbound;
+use crate::read_only_mode::ReadOnlyMode;
+use actix_multipart::Multipart;
+use actix_web::{get, post, web, HttpResponse};
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+// Taille maximale d'un champ texte du webhook Mailgun (512 Ko)
+const MAX_MAILGUN_FIELD_BYTES: usize = 512 * 1024;
+
+#[derive(Deserialize, Debug)]
+pub struct WebhookAuthQuery {
+    pub key: String,
+}
+
+fn enabled_config(
+    config: &web::Data<Option<InboundEmailConfig>>,
+) -> Result<&InboundEmailConfig, ServiceError> {
+    config
+        .get_ref()
+        .as_ref()
+        .ok_or_else(|| ServiceError::NotFound("Inbound email capture is not enabled".to_string()))
+}
+
+fn authorize_webhook(
+    config: &web::Data<Option<InboundEmailConfig>>,
+    query: &WebhookAuthQuery,
+) -> Result<InboundEmailConfig, ServiceError> {
+    let config = enabled_config(config)?;
+    if !config.verify_secret(&query.key) {
+        return Err(ServiceError::Unauthorized(
+            "Invalid inbound email webhook key".to_string(),
+        ));
+    }
+    Ok(config.clone())
+}
+
+fn address_response(
+    config: &InboundEmailConfig,
+    address: InboundEmailAddress,
+) -> InboundEmailAddressResponse {
+    InboundEmailAddressResponse {
+        address: config.address_for(&address.token),
+        created_at: address.created_at,
+    }
+}
+
+// Un destinataire inconnu n'est pas une erreur : répondre 200 évite les relances du fournisseur
+async fn process_message(
+    pool: &DbPool,
+    config: &InboundEmailConfig,
+    message: InboundMessage,
+) -> Result<HttpResponse, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    match inbound_email::create_task_from_message(&mut conn, config, &message).await? {
+        Some(task) => Ok(HttpResponse::Ok().json(json!({
+            "status": "success",
+            "task_id": task.id,
+            "ignored_attachments": message.attachment_count
+        }))),
+        None => {
+            log::warn!(
+                "Inbound email ignored: no known capture address among {:?}",
+                message.recipients
+            );
+            Ok(HttpResponse::Ok().json(json!({
+                "status": "ignored",
+                "message": "No matching capture address"
+            })))
+        }
+    }
+}
+
+// === GET /settings/inbound-email ===
+#[get("/inbound-email")]
+pub async fn get_inbound_email_address_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<Option<InboundEmailConfig>>,
+    read_only_mode: web::Data<ReadOnlyMode>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    let config = enabled_config(&config)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // La création à la première demande est une écriture
+    let address = match inbound_email::find_address(&mut conn, authenticated_user.id).await? {
+        Some(address) => address,
+        None => {
+            read_only_mode.ensure_writable()?;
+            inbound_email::load_or_create_address(&mut conn, authenticated_user.id).await?
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(address_response(config, address)))
+}
+
+// === POST /settings/inbound-email/rotate ===
+#[post("/inbound-email/rotate")]
+pub async fn rotate_inbound_email_address_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<Option<InboundEmailConfig>>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    let config = enabled_config(&config)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let address = inbound_email::rotate_address(&mut conn, authenticated_user.id).await?;
+
+    Ok(HttpResponse::Ok().json(address_response(config, address)))
+}
+
+// === POST /webhooks/email/mailgun?key=... ===
+// Route Mailgun "forward()" : formulaire multipart ; les pièces jointes sont ignorées
+#[post("/email/mailgun")]
+pub async fn mailgun_inbound_webhook_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<Option<InboundEmailConfig>>,
+    query: web::Query<WebhookAuthQuery>,
+    mut payload: Multipart,
+) -> Result<HttpResponse, ServiceError> {
+    let config = authorize_webhook(&config, &query)?;
+
+    let mut message = InboundMessage::default();
+    let mut body_plain: Option<String> = None;
+    let mut stripped_text: Option<String> = None;
+
+    while let Some(mut field) = payload
+        .try_next()
+        .await
+        .map_err(|e| ServiceError::BadRequest(format!("Invalid multipart payload: {}", e)))?
+    {
+        let field_name = field
+            .content_disposition()
+            .and_then(|cd| cd.get_name())
+            .map(str::to_string)
+            .unwrap_or_default();
+        let is_file = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .is_some();
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field
+            .try_next()
+            .await
+            .map_err(|e| ServiceError::BadRequest(format!("Invalid multipart payload: {}", e)))?
+        {
+            // Les fichiers sont consommés sans être conservés
+            if is_file {
+                continue;
+            }
+            if bytes.len() + chunk.len() > MAX_MAILGUN_FIELD_BYTES {
+                return Err(ServiceError::BadRequest(format!(
+                    "Multipart field '{}' exceeds the maximum size of {} bytes",
+                    field_name, MAX_MAILGUN_FIELD_BYTES
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        if is_file {
+            message.attachment_count += 1;
+            continue;
+        }
+
+        let value = String::from_utf8_lossy(&bytes).into_owned();
+        match field_name.as_str() {
+            "recipient" => message
+                .recipients
+                .extend(value.split(',').map(|r| r.trim().to_string())),
+            "sender" => message.sender = Some(value),
+            "subject" => message.subject = Some(value),
+            "body-plain" => body_plain = Some(value),
+            "stripped-text" => stripped_text = Some(value),
+            _ => {}
+        }
+    }
+
+    // Le texte sans citation ni signature est préféré au corps brut
+    message.body = stripped_text
+        .filter(|t| !t.trim().is_empty())
+        .or(body_plain);
+
+    process_message(&pool, &config, message).await
+}
+
+// === POST /webhooks/email/ses?key=... ===
+// Notification SNS d'une règle de réception SES (action SNS, encodage UTF-8)
+#[post("/email/ses")]
+pub async fn ses_inbound_webhook_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<Option<InboundEmailConfig>>,
+    query: web::Query<WebhookAuthQuery>,
+    body: String,
+) -> Result<HttpResponse, ServiceError> {
+    let config = authorize_webhook(&config, &query)?;
+
+    // SNS envoie du JSON avec un Content-Type text/plain
+    let envelope: Value = serde_json::from_str(&body)
+        .map_err(|e| ServiceError::BadRequest(format!("Invalid SNS payload: {}", e)))?;
+
+    match envelope["Type"].as_str() {
+        Some("SubscriptionConfirmation") => {
+            let subscribe_url = envelope["SubscribeURL"].as_str().unwrap_or_default();
+            let is_aws_url = reqwest::Url::parse(subscribe_url)
+                .ok()
+                .filter(|url| url.scheme() == "https")
+                .and_then(|url| url.host_str().map(str::to_string))
+                .is_some_and(|host| host.ends_with(".amazonaws.com"));
+            if !is_aws_url {
+                return Err(ServiceError::BadRequest(
+                    "SubscribeURL must be an https amazonaws.com URL".to_string(),
+                ));
+            }
+
+            outbound::send(&outbound::SNS_POLICY, outbound::client().get(subscribe_url))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|response| response.error_for_status().map_err(|e| e.to_string()))
+                .map_err(|e| {
+                    log::error!("Failed to confirm SNS subscription: {}", e);
+                    ServiceError::InternalServerError(
+                        "Failed to confirm SNS subscription".to_string(),
+                    )
+                })?;
+
+            log::info!("Confirmed SNS subscription for inbound email");
+            Ok(HttpResponse::Ok().json(json!({
+                "status": "success",
+                "message": "Subscription confirmed"
+            })))
+        }
+        Some("Notification") => {
+            let notification: Value = envelope["Message"]
+                .as_str()
+                .and_then(|raw| serde_json::from_str(raw).ok())
+                .ok_or_else(|| {
+                    ServiceError::BadRequest("SNS Message is not a SES notification".to_string())
+                })?;
+
+            let recipients = notification["receipt"]["recipients"]
+                .as_array()
+                .or_else(|| notification["mail"]["destination"].as_array())
+                .map(|list| {
+                    list.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            let (body, attachment_count) = notification["content"]
+                .as_str()
+                .map(inbound_email::parse_mime_message)
+                .unwrap_or((None, 0));
+
+            let message = InboundMessage {
+                recipients,
+                sender: notification["mail"]["source"].as_str().map(str::to_string),
+                subject: notification["mail"]["commonHeaders"]["subject"]
+                    .as_str()
+                    .map(str::to_string),
+                body,
+                attachment_count,
+            };
+
+            process_message(&pool, &config, message).await
+        }
+        _ => Ok(HttpResponse::Ok().json(json!({
+            "status": "ignored",
+            "message": "Unsupported SNS message type"
+        }))),
+    }
+}
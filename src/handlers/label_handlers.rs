@@ -1,17 +1,30 @@
 // OptiTask/backend-api/src/label_handlers.rs
 use crate::auth_utils::AuthenticatedUser;
+use crate::batch::{self, BatchItemResult, BatchReport};
+use crate::colors;
 use crate::db::DbPool;
 use crate::error_handler::ServiceError;
+use crate::icons;
+use crate::ids::LabelId;
 use crate::models::{
-    CreateLabelPayload, Label, NewLabel, UpdateLabelChangeset, UpdateLabelPayload,
+    BulkCreateLabelsPayload, BulkLabelResult, CreateLabelPayload, Label, NewLabel,
+    UpdateLabelChangeset, UpdateLabelPayload,
 };
 use crate::schema::labels::{self, dsl::*}; // dsl::* pour user_id, id etc.
+use crate::sla;
+use actix_web::http::StatusCode;
 use actix_web::{delete, get, post, put, web, HttpResponse};
 use chrono::Utc;
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl; // Import async version
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl}; // Import async version
 use serde_json::json;
-use uuid::Uuid;
+use std::collections::HashMap;
+
+const MAX_BULK_LABELS: usize = 100;
+const MAX_LABEL_NAME_CHARS: usize = 100;
+const BULK_OUTCOME_CREATED: &str = "created";
+const BULK_OUTCOME_EXISTING: &str = "existing";
 
 // === POST /labels ===
 #[post("")] // Relatif au scope "/labels" dans main.rs
@@ -22,15 +35,21 @@ pub async fn create_label_handler(
 ) -> Result<HttpResponse, ServiceError> {
     log::info!("Create label payload received: {:?}", payload);
 
-    let new_label_data = NewLabel {
+    let mut new_label_data = NewLabel {
         user_id: authenticated_user.id,
         name: payload.name.clone(),
         color: payload.color.clone(),
+        icon: icons::normalize_icon(payload.icon.as_deref())?,
+        sla_hours: sla::validate_sla_hours(payload.sla_hours)?,
     };
 
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
+    if new_label_data.color.is_none() {
+        new_label_data.color = Some(colors::assign_color(&mut conn, authenticated_user.id).await?);
+    }
+
     // Exécuter la requête de manière async
     let created_label = diesel::insert_into(labels::table)
         .values(&new_label_data)
@@ -42,6 +61,122 @@ pub async fn create_label_handler(
     Ok(HttpResponse::Created().json(created_label))
 }
 
+// === POST /labels/bulk ===
+// Upsert par nom (importeurs, saisie rapide "#nouveau-label") : un label existant dont le
+// nom correspond sans tenir compte de la casse est renvoyé tel quel, sinon il est créé.
+// Les résultats suivent l'ordre de la requête.
+#[post("/bulk")]
+pub async fn bulk_create_labels_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<BulkCreateLabelsPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let requested = payload.into_inner().labels;
+    if requested.is_empty() || requested.len() > MAX_BULK_LABELS {
+        return Err(ServiceError::ValidationError(format!(
+            "labels must contain between 1 and {} entries",
+            MAX_BULK_LABELS
+        )));
+    }
+
+    // Validation complète avant toute écriture
+    let mut new_labels = Vec::with_capacity(requested.len());
+    for entry in &requested {
+        let label_name = entry.name.trim();
+        if label_name.is_empty() || label_name.chars().count() > MAX_LABEL_NAME_CHARS {
+            return Err(ServiceError::ValidationError(format!(
+                "Label names must contain between 1 and {} characters",
+                MAX_LABEL_NAME_CHARS
+            )));
+        }
+        new_labels.push(NewLabel {
+            user_id: user_uuid,
+            name: label_name.to_string(),
+            color: entry.color.clone(),
+            icon: icons::normalize_icon(entry.icon.as_deref())?,
+            sla_hours: sla::validate_sla_hours(entry.sla_hours)?,
+        });
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let results = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                // Labels existants par nom en minuscules ; en cas de doublon, le plus ancien
+                let mut by_name: HashMap<String, Label> = HashMap::new();
+                let existing = labels
+                    .filter(user_id.eq(user_uuid))
+                    .order(created_at.asc())
+                    .select(Label::as_select())
+                    .load::<Label>(conn)
+                    .await?;
+                for label in existing {
+                    by_name.entry(label.name.to_lowercase()).or_insert(label);
+                }
+
+                let mut used_colors = colors::used_colors(conn, user_uuid).await?;
+                let mut results = Vec::with_capacity(new_labels.len());
+                for (index, mut new_label) in new_labels.into_iter().enumerate() {
+                    let key = new_label.name.to_lowercase();
+                    if let Some(label) = by_name.get(&key) {
+                        // Nom répété dans la requête : "created" seulement la première fois
+                        results.push(BatchItemResult::succeeded(
+                            index,
+                            StatusCode::OK,
+                            BULK_OUTCOME_EXISTING,
+                            BulkLabelResult {
+                                label: label.clone(),
+                                created: false,
+                            },
+                        ));
+                        continue;
+                    }
+                    if new_label.color.is_none() {
+                        new_label.color = Some(colors::next_color(&mut used_colors));
+                    }
+                    let label = diesel::insert_into(labels::table)
+                        .values(&new_label)
+                        .get_result::<Label>(conn)
+                        .await?;
+                    by_name.insert(key, label.clone());
+                    results.push(BatchItemResult::succeeded(
+                        index,
+                        StatusCode::CREATED,
+                        BULK_OUTCOME_CREATED,
+                        BulkLabelResult {
+                            label,
+                            created: true,
+                        },
+                    ));
+                }
+                Ok(results)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    let created_count = results
+        .iter()
+        .filter(|result| result.outcome == BULK_OUTCOME_CREATED)
+        .count();
+    log::info!(
+        "Bulk label upsert for user {}: {} created, {} existing",
+        user_uuid,
+        created_count,
+        results.len() - created_count
+    );
+
+    // Validation préalable et transaction unique : pas d'échec partiel possible
+    Ok(batch::respond(
+        StatusCode::OK,
+        json!({ "created_count": created_count }),
+        BatchReport::new(results),
+    ))
+}
+
 // === GET /labels ===
 #[get("")] // Relatif au scope "/labels" dans main.rs
 pub async fn list_labels_handler(
@@ -71,7 +206,7 @@ pub async fn list_labels_handler(
 pub async fn get_label_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    label_id_path: web::Path<Uuid>,
+    label_id_path: web::Path<LabelId>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let label_to_find_id = label_id_path.into_inner();
@@ -105,7 +240,7 @@ pub async fn get_label_handler(
 pub async fn update_label_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    label_id_path: web::Path<Uuid>,
+    label_id_path: web::Path<LabelId>,
     payload: web::Json<UpdateLabelPayload>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
@@ -120,6 +255,12 @@ pub async fn update_label_handler(
     let label_changes = UpdateLabelChangeset {
         name: payload.name.clone(),
         color: payload.color.clone(), // payload.color est Option<Option<String>>
+        icon: payload
+            .icon
+            .as_ref()
+            .map(|new_icon| icons::normalize_icon(new_icon.as_deref()))
+            .transpose()?,
+        sla_hours: payload.sla_hours.map(sla::validate_sla_hours).transpose()?,
         updated_at: Some(Utc::now().naive_utc()),
     };
 
@@ -151,7 +292,7 @@ pub async fn update_label_handler(
 pub async fn delete_label_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    label_id_path: web::Path<Uuid>,
+    label_id_path: web::Path<LabelId>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let label_to_delete_id = label_id_path.into_inner();
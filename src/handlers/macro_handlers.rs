@@ -0,0 +1,89 @@
+// OptiTask/backend-api/src/handlers/macro_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::custom_fields;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::macros;
+use crate::models::{MacroRunResponse, RunMacroPayload, TaskApiResponse};
+use crate::repository;
+use crate::time_budgets;
+use actix_web::{get, post, web, HttpResponse};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+
+// === GET /macros ===
+// Macros définies dans les préférences client (PUT /settings/client/macros)
+#[get("")]
+pub async fn list_macros_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let macro_list = macros::load_macros(&mut conn, authenticated_user.id).await?;
+
+    Ok(HttpResponse::Ok().json(macro_list))
+}
+
+// === POST /macros/{macro_id}/run ===
+// Exécute la macro sur la tâche donnée (ou celle du timer en cours) : toutes les étapes
+// réussissent ou aucune n'est appliquée
+#[post("/{macro_id}/run")]
+pub async fn run_macro_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    macro_id: web::Path<String>,
+    payload: Option<web::Json<RunMacroPayload>>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let macro_id = macro_id.into_inner();
+    let payload = payload.map(web::Json::into_inner).unwrap_or_default();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let quick_action = macros::load_macros(&mut conn, user_uuid)
+        .await?
+        .into_iter()
+        .find(|quick_action| quick_action.id == macro_id)
+        .ok_or_else(|| ServiceError::NotFound(format!("Macro '{}' not found", macro_id)))?;
+
+    let task_uuid = match payload.task_id {
+        Some(task_uuid) => task_uuid,
+        None => macros::running_timer_task(&mut conn, user_uuid)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::ValidationError(
+                    "task_id is required when no timer is running".to_string(),
+                )
+            })?,
+    };
+
+    let (task, time_entries, completion_effects) = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move { macros::run_macro(conn, user_uuid, &quick_action, task_uuid).await }
+                .scope_boxed()
+        })
+        .await?;
+
+    for effects in completion_effects {
+        effects.dispatch();
+    }
+
+    // Après le commit, comme pour POST /time-entries : une étape LogTime a pu entamer le budget
+    if !time_entries.is_empty() {
+        time_budgets::check_time_budget(&mut conn, task.id).await;
+    }
+
+    let mut task_response = TaskApiResponse::from(task);
+    task_response.labels = repository::load_task_labels(&mut conn, task_response.id).await?;
+    custom_fields::attach_custom_fields(&mut conn, std::slice::from_mut(&mut task_response))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(MacroRunResponse {
+        macro_id,
+        task: task_response,
+        time_entries,
+    }))
+}
@@ -0,0 +1,198 @@
+// OptiTask/backend-api/src/handlers/maintenance_handlers.rs
+use crate::admin::AdminConfig;
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::integrity;
+use crate::maintenance;
+use crate::models::MaintenanceJob;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::maintenance_jobs;
+use actix_web::{get, post, put, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Deserialize;
+use uuid::Uuid;
+
+const MAX_LISTED_JOBS: i64 = 50;
+
+#[derive(Deserialize, Debug)]
+pub struct MaintenanceJobQueryParams {
+    pub status: Option<String>,
+    pub kind: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SetReadOnlyPayload {
+    pub enabled: bool,
+    pub message: Option<String>,
+}
+
+// === GET /maintenance/integrity ===
+// Rapport d'intégrité des données de l'utilisateur
+#[get("/integrity")]
+pub async fn get_integrity_report_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let report = integrity::audit(&mut conn, Some(authenticated_user.id)).await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+// === POST /maintenance/repair ===
+// Applique les corrections sûres et renvoie l'audit résultant
+#[post("/repair")]
+pub async fn repair_integrity_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let summary = integrity::repair(&mut conn, authenticated_user.id).await?;
+    log::info!(
+        "User {} repaired data integrity issues: {:?}",
+        authenticated_user.id,
+        summary.repaired
+    );
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+// === POST /admin/maintenance/{kind} ===
+// Met en file une opération de maintenance (vacuum_analyze, rebuild_search_indexes,
+// purge_deleted_accounts, recompute_counters). 202 pour une nouvelle opération, 200 si la
+// même opération est déjà en attente ou en cours.
+#[post("/maintenance/{kind}")]
+pub async fn admin_enqueue_maintenance_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    kind: web::Path<String>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let kind = maintenance::validate_kind(&kind.into_inner())?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let (job, created) = maintenance::enqueue(&mut conn, kind, authenticated_user.id).await?;
+    if created {
+        log::info!(
+            "Admin {} queued maintenance job {} ({})",
+            authenticated_user.id,
+            job.id,
+            job.kind
+        );
+        Ok(HttpResponse::Accepted().json(job))
+    } else {
+        Ok(HttpResponse::Ok().json(job))
+    }
+}
+
+// === GET /admin/maintenance/jobs ===
+// Opérations les plus récentes, filtrables par statut et type
+#[get("/maintenance/jobs")]
+pub async fn admin_list_maintenance_jobs_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<MaintenanceJobQueryParams>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let query = query.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let mut jobs_query = maintenance_jobs::table
+        .order(maintenance_jobs::created_at.desc())
+        .limit(MAX_LISTED_JOBS)
+        .select(MaintenanceJob::as_select())
+        .into_boxed();
+    if let Some(status) = query.status {
+        jobs_query = jobs_query.filter(maintenance_jobs::status.eq(status));
+    }
+    if let Some(kind) = query.kind {
+        jobs_query = jobs_query.filter(maintenance_jobs::kind.eq(kind));
+    }
+    let job_list = jobs_query.load::<MaintenanceJob>(&mut conn).await?;
+
+    Ok(HttpResponse::Ok().json(job_list))
+}
+
+// === GET /admin/maintenance/jobs/{job_id} ===
+// Statut et compte rendu d'une opération
+#[get("/maintenance/jobs/{job_id}")]
+pub async fn admin_get_maintenance_job_handler(
+    pool: web::Data<DbPool>,
+    admin_config: web::Data<AdminConfig>,
+    authenticated_user: AuthenticatedUser,
+    job_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let job_uuid = job_id.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let job = maintenance_jobs::table
+        .find(job_uuid)
+        .select(MaintenanceJob::as_select())
+        .first::<MaintenanceJob>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!("Maintenance job with id {} not found", job_uuid))
+        })?;
+
+    Ok(HttpResponse::Ok().json(job))
+}
+
+// === GET /admin/read-only ===
+// État du mode lecture seule de l'instance
+#[get("/read-only")]
+pub async fn admin_get_read_only_handler(
+    admin_config: web::Data<AdminConfig>,
+    read_only_mode: web::Data<ReadOnlyMode>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+
+    Ok(HttpResponse::Ok().json(read_only_mode.status()))
+}
+
+// === PUT /admin/read-only ===
+// Active ou lève le mode lecture seule (sans accès à la base, utilisable pendant la
+// maintenance). Le message remplace celui renvoyé aux écritures refusées.
+#[put("/read-only")]
+pub async fn admin_set_read_only_handler(
+    admin_config: web::Data<AdminConfig>,
+    read_only_mode: web::Data<ReadOnlyMode>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<SetReadOnlyPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    admin_config.require_admin(&authenticated_user)?;
+    let payload = payload.into_inner();
+    let message = payload
+        .message
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+
+    let status = read_only_mode.set(payload.enabled, message, authenticated_user.id);
+    log::warn!(
+        "Admin {} {} read-only mode",
+        authenticated_user.id,
+        if status.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+
+    Ok(HttpResponse::Ok().json(status))
+}
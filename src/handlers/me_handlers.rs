@@ -0,0 +1,84 @@
+// OptiTask/backend-api/src/handlers/me_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::schema::{labels, projects, tasks, time_entries};
+use actix_web::{get, web, HttpResponse};
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Debug)]
+pub struct TaskUsage {
+    pub total: i64,
+    pub by_status: BTreeMap<String, i64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UsageResponse {
+    pub projects: i64,
+    pub tasks: TaskUsage,
+    pub labels: i64,
+    pub time_entries: i64,
+    pub total_tracked_seconds: i64,
+    // Aucune pièce jointe n'est stockée pour l'instant : toujours 0
+    pub attachment_storage_bytes: i64,
+}
+
+// === GET /me/usage ===
+#[get("/usage")]
+pub async fn get_usage_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let project_count = projects::table
+        .filter(projects::user_id.eq(user_uuid))
+        .select(count_star())
+        .get_result::<i64>(&mut conn)
+        .await?;
+
+    let tasks_by_status = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .group_by(tasks::status)
+        .select((tasks::status, count_star()))
+        .load::<(String, i64)>(&mut conn)
+        .await?;
+
+    let label_count = labels::table
+        .filter(labels::user_id.eq(user_uuid))
+        .select(count_star())
+        .get_result::<i64>(&mut conn)
+        .await?;
+
+    // Les pauses ne comptent ni comme entrées ni comme temps suivi
+    let (time_entry_count, tracked_seconds) = time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::is_break.eq(false))
+        .select((
+            count_star(),
+            diesel::dsl::sum(time_entries::duration_seconds),
+        ))
+        .get_result::<(i64, Option<i64>)>(&mut conn)
+        .await?;
+
+    let by_status: BTreeMap<String, i64> = tasks_by_status.into_iter().collect();
+
+    Ok(HttpResponse::Ok().json(UsageResponse {
+        projects: project_count,
+        tasks: TaskUsage {
+            total: by_status.values().sum(),
+            by_status,
+        },
+        labels: label_count,
+        time_entries: time_entry_count,
+        total_tracked_seconds: tracked_seconds.unwrap_or(0),
+        attachment_storage_bytes: 0,
+    }))
+}
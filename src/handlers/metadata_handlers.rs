@@ -0,0 +1,125 @@
+// OptiTask/backend-api/src/handlers/metadata_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::{ProjectId, TaskId};
+use crate::metadata::{self, MetadataConfig, PatchMetadataPayload};
+use crate::permissions::{self, Permission};
+use crate::schema::{projects, tasks};
+use actix_web::{patch, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde_json::{json, Value};
+
+// === PATCH /tasks/{task_id_path}/metadata ===
+// Les opérations sont appliquées sur la ligne verrouillée pour éviter les écritures perdues
+#[patch("/{task_id_path}/metadata")]
+pub async fn patch_task_metadata_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<MetadataConfig>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+    payload: web::Json<PatchMetadataPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let task_uuid = task_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_task(
+        &mut conn,
+        authenticated_user.id,
+        task_uuid,
+        Permission::TaskWrite,
+    )
+    .await?;
+
+    let payload = payload.into_inner();
+    let updated_metadata = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let current = tasks::table
+                    .find(task_uuid)
+                    .select(tasks::metadata)
+                    .for_update()
+                    .first::<Value>(conn)
+                    .await?;
+
+                let new_metadata =
+                    metadata::apply_operations(current, &payload.operations, &config)?;
+
+                diesel::update(tasks::table.find(task_uuid))
+                    .set((
+                        tasks::metadata.eq(&new_metadata),
+                        tasks::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .execute(conn)
+                    .await?;
+                Ok(new_metadata)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "task_id": task_uuid,
+        "metadata": updated_metadata
+    })))
+}
+
+// === PATCH /projects/{project_id_path}/metadata ===
+#[patch("/{project_id_path}/metadata")]
+pub async fn patch_project_metadata_handler(
+    pool: web::Data<DbPool>,
+    config: web::Data<MetadataConfig>,
+    authenticated_user: AuthenticatedUser,
+    project_id_path: web::Path<ProjectId>,
+    payload: web::Json<PatchMetadataPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let project_uuid = project_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(
+        &mut conn,
+        authenticated_user.id,
+        project_uuid,
+        Permission::ProjectWrite,
+    )
+    .await?;
+
+    let payload = payload.into_inner();
+    let updated_metadata = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let current = projects::table
+                    .find(project_uuid)
+                    .select(projects::metadata)
+                    .for_update()
+                    .first::<Value>(conn)
+                    .await?;
+
+                let new_metadata =
+                    metadata::apply_operations(current, &payload.operations, &config)?;
+
+                diesel::update(projects::table.find(project_uuid))
+                    .set((
+                        projects::metadata.eq(&new_metadata),
+                        projects::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .execute(conn)
+                    .await?;
+                Ok(new_metadata)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "project_id": project_uuid,
+        "metadata": updated_metadata
+    })))
+}
@@ -0,0 +1,44 @@
+// OptiTask/backend-api/src/handlers/metrics_handlers.rs
+use crate::config::AppConfig;
+use crate::error_handler::ServiceError;
+use crate::outbound;
+use actix_web::http::header;
+use actix_web::{get, web, HttpRequest, HttpResponse};
+
+// Comparaison en temps constant pour ne pas divulguer le jeton
+fn token_matches(expected: &str, candidate: &str) -> bool {
+    let expected = expected.as_bytes();
+    let candidate = candidate.as_bytes();
+    expected.len() == candidate.len()
+        && expected
+            .iter()
+            .zip(candidate)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+// === GET /metrics ===
+// Format texte Prometheus : état des disjoncteurs des appels sortants
+#[get("/metrics")]
+pub async fn metrics_handler(
+    req: HttpRequest,
+    app_config: web::Data<AppConfig>,
+) -> Result<HttpResponse, ServiceError> {
+    if let Some(expected) = &app_config.metrics_token {
+        let authorized = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|candidate| token_matches(expected, candidate.trim()));
+        if !authorized {
+            return Err(ServiceError::Unauthorized(
+                "Invalid or missing metrics token".to_string(),
+            ));
+        }
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(outbound::render_metrics()))
+}
@@ -1,7 +1,47 @@
 // OptiTask/backend-api/src/handlers/mod.rs
+pub mod account_handlers;
+pub mod activity_handlers;
+pub mod aging_rule_handlers;
+pub mod analytics_handlers;
+pub mod announcement_handlers;
+pub mod api_key_handlers;
+pub mod app_password_handlers;
+pub mod automation_handlers;
+pub mod backup_handlers;
+pub mod badge_handlers;
+pub mod caldav_handlers;
+pub mod calendar_integration_handlers;
+pub mod capture_handlers;
+pub mod color_handlers;
+pub mod custom_field_handlers;
+pub mod dashboard_handlers;
+pub mod device_handlers;
+pub mod experiment_handlers;
+pub mod feature_flag_handlers;
+pub mod feedback_handlers;
+pub mod focus_session_handlers;
+pub mod inbound_email_handlers;
 pub mod label_handlers;
+pub mod macro_handlers;
+pub mod maintenance_handlers;
+pub mod me_handlers;
+pub mod metadata_handlers;
+pub mod metrics_handlers;
+pub mod notification_handlers;
+pub mod onboarding_handlers;
+pub mod planning_handlers;
+pub mod polling_handlers;
+pub mod pomodoro_handlers;
 pub mod project_handlers;
+pub mod recent_handlers;
+pub mod settings_handlers;
+pub mod suggest_handlers;
+pub mod task_external_link_handlers;
 pub mod task_handlers;
+pub mod task_import_handlers;
 pub mod task_label_handlers;
+pub mod task_watcher_handlers;
+pub mod theme_handlers;
 pub mod time_entry_handlers;
-pub mod analytics_handlers;
+pub mod timesheet_handlers;
+pub mod workspace_handlers;
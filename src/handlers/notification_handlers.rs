@@ -0,0 +1,138 @@
+// OptiTask/backend-api/src/handlers/notification_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{Notification, PushDelivery};
+use crate::schema::{notifications, push_deliveries};
+use actix_web::{get, put, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_NOTIFICATIONS_PER_PAGE: i64 = 100;
+
+#[derive(Deserialize, Debug)]
+pub struct NotificationQueryParams {
+    #[serde(default)]
+    pub unread_only: bool,
+    pub limit: Option<i64>,
+}
+
+// === GET /notifications ===
+#[get("")]
+pub async fn list_notifications_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<NotificationQueryParams>,
+) -> Result<HttpResponse, ServiceError> {
+    let limit = query
+        .limit
+        .unwrap_or(50)
+        .clamp(1, MAX_NOTIFICATIONS_PER_PAGE);
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let mut query_builder = notifications::table
+        .filter(notifications::user_id.eq(authenticated_user.id))
+        .into_boxed();
+    if query.unread_only {
+        query_builder = query_builder.filter(notifications::read_at.is_null());
+    }
+
+    let notification_list = query_builder
+        .order(notifications::created_at.desc())
+        .limit(limit)
+        .select(Notification::as_select())
+        .load::<Notification>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(notification_list))
+}
+
+// === PUT /notifications/{notification_id_path}/read ===
+#[put("/{notification_id_path}/read")]
+pub async fn mark_notification_read_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    notification_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let notification_uuid = notification_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let notification = diesel::update(
+        notifications::table
+            .filter(notifications::id.eq(notification_uuid))
+            .filter(notifications::user_id.eq(authenticated_user.id)),
+    )
+    .set(notifications::read_at.eq(Some(Utc::now())))
+    .returning(Notification::as_returning())
+    .get_result::<Notification>(&mut conn)
+    .await
+    .optional()
+    .map_err(ServiceError::from)?;
+
+    match notification {
+        Some(n) => Ok(HttpResponse::Ok().json(n)),
+        None => Err(ServiceError::NotFound(format!(
+            "Notification with id {} not found",
+            notification_uuid
+        ))),
+    }
+}
+
+// === PUT /notifications/read-all ===
+#[put("/read-all")]
+pub async fn mark_all_notifications_read_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_updated = diesel::update(
+        notifications::table
+            .filter(notifications::user_id.eq(authenticated_user.id))
+            .filter(notifications::read_at.is_null()),
+    )
+    .set(notifications::read_at.eq(Some(Utc::now())))
+    .execute(&mut conn)
+    .await
+    .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": format!("{} notification(s) marked as read", num_updated)
+    })))
+}
+
+// === GET /notifications/{notification_id_path}/deliveries ===
+// Accusés d'envoi push de la notification, un par appareil
+#[get("/{notification_id_path}/deliveries")]
+pub async fn list_push_deliveries_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    notification_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let notification_uuid = notification_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let deliveries = push_deliveries::table
+        .filter(push_deliveries::notification_id.eq(notification_uuid))
+        .filter(push_deliveries::user_id.eq(authenticated_user.id))
+        .order(push_deliveries::created_at.asc())
+        .select(PushDelivery::as_select())
+        .load::<PushDelivery>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(deliveries))
+}
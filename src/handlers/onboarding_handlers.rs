@@ -0,0 +1,37 @@
+// OptiTask/backend-api/src/handlers/onboarding_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::onboarding;
+use actix_web::{post, web, HttpResponse, Result as ActixResult};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+use serde_json::json;
+
+// === POST /onboarding/seed ===
+// Crée un projet, quelques tâches avec labels et une entrée de temps d'exemple.
+// Idempotent : un second appel pour le même utilisateur ne crée rien.
+#[post("/seed")]
+pub async fn seed_onboarding_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    log::info!("User {} requesting onboarding sample data", user_uuid);
+
+    let mut conn = pool.get().await?;
+
+    let seed_result = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move { onboarding::seed_sample_data(conn, user_uuid).await }.scope_boxed()
+        })
+        .await?;
+
+    match seed_result {
+        Some(summary) => Ok(HttpResponse::Created().json(summary)),
+        None => Ok(HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": "Sample data has already been created for this user"
+        }))),
+    }
+}
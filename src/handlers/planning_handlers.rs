@@ -0,0 +1,176 @@
+// OptiTask/backend-api/src/handlers/planning_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::dry_run::{self, DryRunQuery};
+use crate::error_handler::ServiceError;
+use crate::integrations::{self, google_calendar::GoogleCalendarConfig};
+use crate::models::{
+    PlanningRolloverPayload, PlanningRolloverSummary, RolledOverTask, RolloverStrategy, Task,
+    DONE_TASK_STATUSES, TASK_STAGE_ACTIVE, TASK_STAGE_BACKLOG,
+};
+use crate::schema::tasks;
+use crate::settings;
+use actix_web::{post, web, HttpResponse};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::RunQueryDsl;
+
+// Nouvelles échéance et créneau d'une tâche de la semaine écoulée, None si rien ne change.
+// Seules les dates tombant dans la semaine écoulée sont touchées : une échéance lointaine
+// d'une tâche planifiée la semaine dernière reste en place.
+fn roll_over(
+    task: &Task,
+    strategy: RolloverStrategy,
+    from_start: NaiveDate,
+    to_start: NaiveDate,
+) -> Option<RolledOverTask> {
+    let week = Duration::days(7);
+    let in_previous_week = |date: NaiveDate| from_start <= date && date < to_start;
+    let due_in_week = task.due_date.is_some_and(in_previous_week);
+    let scheduled_in_week = task
+        .scheduled_start
+        .is_some_and(|start| in_previous_week(start.date_naive()));
+
+    let (due_date, scheduled_start, scheduled_end) = match strategy {
+        RolloverStrategy::KeepDueDate if scheduled_in_week => (
+            task.due_date,
+            task.scheduled_start.map(|start| start + week),
+            task.scheduled_end.map(|end| end + week),
+        ),
+        RolloverStrategy::ShiftWeek if due_in_week || scheduled_in_week => (
+            if due_in_week {
+                task.due_date.map(|due| due + week)
+            } else {
+                task.due_date
+            },
+            if scheduled_in_week {
+                task.scheduled_start.map(|start| start + week)
+            } else {
+                task.scheduled_start
+            },
+            if scheduled_in_week {
+                task.scheduled_end.map(|end| end + week)
+            } else {
+                task.scheduled_end
+            },
+        ),
+        RolloverStrategy::Backlog if due_in_week || scheduled_in_week => (
+            task.due_date.filter(|_| !due_in_week),
+            task.scheduled_start.filter(|_| !scheduled_in_week),
+            task.scheduled_end.filter(|_| !scheduled_in_week),
+        ),
+        _ => return None,
+    };
+
+    Some(RolledOverTask {
+        task_id: task.id,
+        title: task.title.clone(),
+        previous_due_date: task.due_date,
+        due_date,
+        previous_scheduled_start: task.scheduled_start,
+        scheduled_start,
+        scheduled_end,
+    })
+}
+
+// === POST /planning/rollover?dry_run= ===
+// Reporte sur la semaine en cours les tâches non terminées, échues ou planifiées la semaine
+// précédente, selon la stratégie choisie. Ne concerne que les tâches actives de l'utilisateur.
+#[post("/rollover")]
+pub async fn planning_rollover_handler(
+    pool: web::Data<DbPool>,
+    google_calendar: web::Data<Option<GoogleCalendarConfig>>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<DryRunQuery>,
+    payload: web::Json<PlanningRolloverPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let payload = payload.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, payload.week_start.as_deref()).await?;
+    let to_start = Utc::now().date_naive().week(week_start).first_day();
+    let from_start = to_start - Duration::days(7);
+    let from_instant: DateTime<Utc> = from_start.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let to_instant: DateTime<Utc> = to_start.and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+    let strategy = payload.strategy;
+    let dry_run = query.dry_run || payload.dry_run;
+    let rolled_over = dry_run::transaction(&mut conn, dry_run, |conn| {
+        async move {
+            let candidates = tasks::table
+                .filter(tasks::user_id.eq(user_uuid))
+                .filter(tasks::status.ne_all(DONE_TASK_STATUSES))
+                .filter(tasks::stage.eq(TASK_STAGE_ACTIVE))
+                .filter(
+                    tasks::due_date
+                        .ge(from_start)
+                        .and(tasks::due_date.lt(to_start))
+                        .or(tasks::scheduled_start
+                            .ge(from_instant)
+                            .and(tasks::scheduled_start.lt(to_instant))),
+                )
+                .order((tasks::due_date.asc().nulls_last(), tasks::created_at.asc()))
+                .select(Task::as_select())
+                .for_update()
+                .load::<Task>(conn)
+                .await?;
+
+            let rolled_over: Vec<RolledOverTask> = candidates
+                .iter()
+                .filter_map(|task| roll_over(task, strategy, from_start, to_start))
+                .collect();
+
+            let now = Utc::now().naive_utc();
+            for change in &rolled_over {
+                diesel::update(tasks::table.find(change.task_id))
+                    .set((
+                        tasks::due_date.eq(change.due_date),
+                        tasks::scheduled_start.eq(change.scheduled_start),
+                        tasks::scheduled_end.eq(change.scheduled_end),
+                        tasks::updated_at.eq(now),
+                    ))
+                    .execute(conn)
+                    .await?;
+            }
+            if strategy == RolloverStrategy::Backlog {
+                let moved_ids: Vec<_> = rolled_over.iter().map(|change| change.task_id).collect();
+                diesel::update(tasks::table.filter(tasks::id.eq_any(moved_ids)))
+                    .set(tasks::stage.eq(TASK_STAGE_BACKLOG))
+                    .execute(conn)
+                    .await?;
+            }
+            Ok(rolled_over)
+        }
+        .scope_boxed()
+    })
+    .await?;
+
+    // Les événements du calendrier suivent le nouveau créneau
+    if !dry_run {
+        for change in rolled_over
+            .iter()
+            .filter(|change| change.scheduled_start != change.previous_scheduled_start)
+        {
+            integrations::spawn_task_schedule_sync(
+                pool.get_ref().clone(),
+                google_calendar.get_ref().clone(),
+                user_uuid,
+                change.task_id,
+            );
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(PlanningRolloverSummary {
+        strategy,
+        dry_run,
+        from_week_start: from_start,
+        to_week_start: to_start,
+        moved_count: rolled_over.len(),
+        tasks: rolled_over,
+    }))
+}
@@ -0,0 +1,154 @@
+// OptiTask/backend-api/src/handlers/polling_handlers.rs
+// Déclencheurs par interrogation au format attendu par Zapier : tableau JSON du plus
+// récent au plus ancien, chaque élément portant un `id` stable (Zapier dédoublonne dessus).
+// Authentification par clé d'API (voir api_keys.rs), sans session ni webhook.
+use crate::api_keys;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::{ProjectId, TaskId};
+use crate::models::{PollingCompletedTask, Task, TaskApiResponse, DONE_TASK_STATUSES};
+use crate::repository;
+use crate::schema::{task_status_history, tasks};
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+const DEFAULT_POLLING_LIMIT: i64 = 50;
+const MAX_POLLING_LIMIT: i64 = 100;
+
+#[derive(Deserialize, Debug)]
+pub struct PollingQuery {
+    // Éléments strictement postérieurs à cette date
+    pub since: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+}
+
+impl PollingQuery {
+    fn limit(&self) -> Result<i64, ServiceError> {
+        match self.limit {
+            Some(limit) if !(1..=MAX_POLLING_LIMIT).contains(&limit) => {
+                Err(ServiceError::ValidationError(format!(
+                    "limit must be between 1 and {}",
+                    MAX_POLLING_LIMIT
+                )))
+            }
+            limit => Ok(limit.unwrap_or(DEFAULT_POLLING_LIMIT)),
+        }
+    }
+}
+
+// === GET /integrations/polling/me ===
+// Test de connexion de Zapier : valide la clé et renvoie l'utilisateur associé
+#[get("/me")]
+pub async fn polling_me_handler(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = api_keys::authenticate(&req, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(json!({ "id": user_uuid })))
+}
+
+// === GET /integrations/polling/new-tasks ===
+#[get("/new-tasks")]
+pub async fn polling_new_tasks_handler(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    query: web::Query<PollingQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let limit = query.limit()?;
+    let user_uuid = api_keys::authenticate(&req, &pool).await?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let mut query_builder = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .into_boxed();
+    if let Some(since) = query.since {
+        query_builder = query_builder.filter(tasks::created_at.gt(since.naive_utc()));
+    }
+    let new_tasks = query_builder
+        .order((tasks::created_at.desc(), tasks::id.desc()))
+        .limit(limit)
+        .select(Task::as_select())
+        .load::<Task>(&mut conn)
+        .await?;
+
+    let mut task_responses: Vec<TaskApiResponse> =
+        new_tasks.into_iter().map(TaskApiResponse::from).collect();
+    repository::attach_labels(&mut conn, &mut task_responses).await?;
+    Ok(HttpResponse::Ok().json(task_responses))
+}
+
+// === GET /integrations/polling/new-completed-tasks ===
+// Un élément par passage à un statut terminé : `id` est celui de l'entrée d'historique,
+// si bien qu'une tâche rouverte puis terminée à nouveau redéclenche le Zap
+#[get("/new-completed-tasks")]
+pub async fn polling_new_completed_tasks_handler(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    query: web::Query<PollingQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let limit = query.limit()?;
+    let user_uuid = api_keys::authenticate(&req, &pool).await?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let mut query_builder = task_status_history::table
+        .inner_join(tasks::table)
+        .filter(tasks::user_id.eq(user_uuid))
+        .filter(task_status_history::new_status.eq_any(DONE_TASK_STATUSES))
+        // Les changements de projet d'une tâche déjà terminée ne sont pas des complétions
+        .filter(
+            task_status_history::old_status
+                .is_null()
+                .or(task_status_history::old_status.ne_all(DONE_TASK_STATUSES)),
+        )
+        .into_boxed();
+    if let Some(since) = query.since {
+        query_builder = query_builder.filter(task_status_history::changed_at.gt(since));
+    }
+    let completed = query_builder
+        .order((
+            task_status_history::changed_at.desc(),
+            task_status_history::id.desc(),
+        ))
+        .limit(limit)
+        .select((
+            task_status_history::id,
+            tasks::id,
+            tasks::project_id,
+            tasks::title,
+            task_status_history::new_status,
+            task_status_history::changed_at,
+        ))
+        .load::<(
+            Uuid,
+            TaskId,
+            Option<ProjectId>,
+            String,
+            String,
+            DateTime<Utc>,
+        )>(&mut conn)
+        .await?
+        .into_iter()
+        .map(
+            |(id, task_id, project_id, title, status, completed_at)| PollingCompletedTask {
+                id,
+                task_id,
+                project_id,
+                title,
+                status,
+                completed_at,
+            },
+        )
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(completed))
+}
@@ -0,0 +1,99 @@
+// OptiTask/backend-api/src/handlers/pomodoro_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::TimeEntryId;
+use crate::models::{
+    CreatePomodoroInterruptionPayload, NewPomodoroInterruption, PomodoroInterruption, TimeEntry,
+    INTERRUPTION_REASONS,
+};
+use crate::schema::{pomodoro_interruptions, time_entries};
+use actix_web::{post, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+const MAX_INTERRUPTION_NOTE_CHARS: usize = 500;
+
+fn validate_reason(reason: &str) -> Result<String, ServiceError> {
+    let normalized = reason.trim().to_ascii_lowercase();
+    if !INTERRUPTION_REASONS.contains(&normalized.as_str()) {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid reason '{}'. Supported: {}",
+            reason,
+            INTERRUPTION_REASONS.join(", ")
+        )));
+    }
+    Ok(normalized)
+}
+
+// === POST /pomodoro/{session_id_path}/interruption ===
+// Note une interruption pendant une session pomodoro de l'utilisateur (en cours ou terminée).
+// interrupted_at doit tomber dans la session.
+#[post("/{session_id_path}/interruption")]
+pub async fn record_interruption_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    session_id_path: web::Path<TimeEntryId>,
+    payload: web::Json<CreatePomodoroInterruptionPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let session_uuid = session_id_path.into_inner();
+    let payload = payload.into_inner();
+
+    let reason = validate_reason(&payload.reason)?;
+    let note = payload
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|note| note.chars().count() > MAX_INTERRUPTION_NOTE_CHARS)
+    {
+        return Err(ServiceError::ValidationError(format!(
+            "note cannot exceed {} characters",
+            MAX_INTERRUPTION_NOTE_CHARS
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let session = time_entries::table
+        .filter(time_entries::id.eq(session_uuid))
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::is_pomodoro_session.eq(true))
+        .select(TimeEntry::as_select())
+        .first::<TimeEntry>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!(
+                "Pomodoro session with id {} not found",
+                session_uuid
+            ))
+        })?;
+
+    let now = Utc::now();
+    let interrupted_at = payload.interrupted_at.unwrap_or(now);
+    let session_end = session.end_time.unwrap_or(now);
+    if interrupted_at < session.start_time || interrupted_at > session_end {
+        return Err(ServiceError::ValidationError(
+            "interrupted_at must fall within the pomodoro session".to_string(),
+        ));
+    }
+
+    let created = diesel::insert_into(pomodoro_interruptions::table)
+        .values(&NewPomodoroInterruption {
+            time_entry_id: session.id,
+            user_id: user_uuid,
+            reason,
+            note,
+            interrupted_at,
+        })
+        .returning(PomodoroInterruption::as_returning())
+        .get_result::<PomodoroInterruption>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Created().json(created))
+}
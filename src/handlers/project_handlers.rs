@@ -1,17 +1,127 @@
 // OptiTask/backend-api/src/project_handlers.rs
 use crate::auth_utils::AuthenticatedUser;
+use crate::colors;
+use crate::confirmations::{self, DestructiveAction};
+use crate::cost_limits::{CostClass, CostLimiter};
+use crate::currency::normalize_currency;
 use crate::db::DbPool;
+use crate::dry_run::{self, DryRunQuery};
 use crate::error_handler::ServiceError;
+use crate::events::{self, DomainEvent};
+use crate::exports::{self, ExportFormat, ExportQuery};
+use crate::handlers::analytics_handlers::calculate_date_range;
+use crate::icons;
+use crate::ids::{ProjectId, TaskId};
 use crate::models::{
-    CreateProjectPayload, NewProject, Project, UpdateProjectChangeset, UpdateProjectPayload,
+    AnalyticsQueryPeriod, CreateProjectPayload, NewProject, Project, Task, TaskApiResponse,
+    UpdateProjectChangeset, UpdateProjectPayload, DONE_TASK_STATUSES, TASK_STAGE_ACTIVE,
+    TASK_STAGE_BACKLOG,
 };
+use crate::permissions::{self, Permission};
+use crate::project_completion;
+use crate::project_merge;
+use crate::reports::{self, ProjectReport, ReportFormat, ReportTask};
+use crate::repository;
 use crate::schema::projects::{self, dsl::*};
+use crate::schema::{task_status_history, tasks, time_entries};
+use crate::settings;
+use crate::sla;
+use crate::themes;
+use crate::wip_limits;
+use actix_web::http::header;
 use actix_web::{delete, get, post, put, web, HttpResponse};
-use chrono::Utc;
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, TimeZone, Utc};
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl; // Import async version
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl}; // Import async version
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use uuid::Uuid;
+use std::collections::HashMap;
+
+// Paramètres de suppression : delete_tasks supprime aussi les tâches du projet
+// (opération confirmée en deux temps), sinon elles sont simplement détachées
+#[derive(Deserialize, Debug)]
+pub struct DeleteProjectQueryParams {
+    #[serde(default)]
+    pub delete_tasks: bool,
+    pub confirmation_token: Option<String>,
+}
+
+// Le board n'affiche que les tâches actives, sauf ?include_backlog=true
+#[derive(Deserialize, Debug)]
+pub struct BoardQueryParams {
+    #[serde(default)]
+    pub include_backlog: bool,
+}
+
+// Colonne du board : tâches d'un statut et limite WIP éventuelle
+#[derive(Serialize, Debug)]
+pub struct BoardColumn {
+    pub status: String,
+    pub wip_limit: Option<i64>,
+    pub task_count: usize,
+    // La limite est atteinte (ou dépassée via override) : plus de tâche sans override
+    pub is_full: bool,
+    pub tasks: Vec<TaskApiResponse>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ProjectBoardResponse {
+    pub project_id: ProjectId,
+    pub columns: Vec<BoardColumn>,
+}
+
+// Ordre des colonnes connues ; les autres statuts suivent par ordre alphabétique
+const BOARD_STATUS_ORDER: [&str; 7] = [
+    "todo",
+    "pending",
+    "inprogress",
+    "in_progress",
+    "review",
+    "done",
+    "completed",
+];
+
+// Période du burndown (par défaut les 14 derniers jours)
+#[derive(Deserialize, Debug)]
+pub struct BurndownQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+// État du projet à la fin d'une journée
+#[derive(Serialize, Debug)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    pub total_tasks: usize,
+    pub completed_tasks: usize,
+    pub remaining_tasks: usize,
+    // Somme des estimations (metadata.estimate_hours) des tâches restantes
+    pub remaining_estimated_hours: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ProjectBurndownResponse {
+    pub project_id: ProjectId,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub points: Vec<BurndownPoint>,
+}
+
+const DEFAULT_BURNDOWN_DAYS: i64 = 14;
+const MAX_BURNDOWN_DAYS: i64 = 366;
+// Clé de metadata portant l'estimation d'une tâche, en heures
+const ESTIMATE_METADATA_KEY: &str = "estimate_hours";
+
+// Paramètres du rapport de projet : format + période (mêmes règles que les analytics)
+#[derive(Deserialize, Debug)]
+pub struct ProjectReportQuery {
+    pub format: Option<String>,
+    pub period: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub week_start: Option<String>,
+}
 
 #[post("")]
 pub async fn create_project_handler(
@@ -19,15 +129,45 @@ pub async fn create_project_handler(
     authenticated_user: AuthenticatedUser,
     payload: web::Json<CreateProjectPayload>,
 ) -> Result<HttpResponse, ServiceError> {
-    let new_project_data = NewProject {
+    let mut new_project_data = NewProject {
         user_id: authenticated_user.id,
         name: payload.name.clone(),
         color: payload.color.clone(),
+        workspace_id: payload.workspace_id,
+        icon: icons::normalize_icon(payload.icon.as_deref())?,
+        wip_limits: payload
+            .wip_limits
+            .as_ref()
+            .map(wip_limits::build_limits)
+            .transpose()?,
+        sla_hours: sla::validate_sla_hours(payload.sla_hours)?,
+        currency: normalize_currency(payload.currency.as_deref())?,
+        theme: payload
+            .theme
+            .as_ref()
+            .map(themes::build_theme)
+            .transpose()?,
     };
 
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
+    // Rattacher à un espace partagé exige d'en être membre éditeur
+    if let Some(workspace_uuid) = payload.workspace_id {
+        permissions::require_workspace(
+            &mut conn,
+            authenticated_user.id,
+            workspace_uuid,
+            Permission::WorkspaceAddProjects,
+        )
+        .await?;
+    }
+
+    if new_project_data.color.is_none() {
+        new_project_data.color =
+            Some(colors::assign_color(&mut conn, authenticated_user.id).await?);
+    }
+
     // Exécuter la requête de manière async
     let project = diesel::insert_into(projects::table)
         .values(&new_project_data)
@@ -35,6 +175,12 @@ pub async fn create_project_handler(
         .await
         .map_err(ServiceError::from)?;
 
+    events::emit(
+        DomainEvent::new(events::EVENT_PROJECT_CREATED, authenticated_user.id)
+            .with_project(project.id)
+            .with_properties(json!({ "shared": project.workspace_id.is_some() })),
+    );
+
     Ok(HttpResponse::Created().json(project))
 }
 
@@ -42,16 +188,25 @@ pub async fn create_project_handler(
 pub async fn list_projects_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
+    query: web::Query<ListProjectsQueryParams>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
 
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
+    // Projets personnels et projets des espaces partagés dont l'utilisateur est membre
+    let visible_project_ids = permissions::accessible_project_ids(&mut conn, user_uuid).await?;
+
     // Exécuter la requête de manière async
-    let project_list = projects
-        .filter(user_id.eq(user_uuid))
+    let mut project_query = projects
+        .filter(id.eq_any(&visible_project_ids))
         .select(Project::as_select())
+        .into_boxed();
+    if !query.include_archived {
+        project_query = project_query.filter(archived_at.is_null());
+    }
+    let project_list = project_query
         .load::<Project>(&mut conn)
         .await
         .map_err(ServiceError::from)?;
@@ -63,7 +218,7 @@ pub async fn list_projects_handler(
 pub async fn get_project_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    project_id_path: web::Path<Uuid>,
+    project_id_path: web::Path<ProjectId>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let project_to_find_id = project_id_path.into_inner();
@@ -71,30 +226,30 @@ pub async fn get_project_handler(
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
+    permissions::require_project(
+        &mut conn,
+        user_uuid,
+        project_to_find_id,
+        Permission::ProjectRead,
+    )
+    .await?;
+
     // Exécuter la requête de manière async
-    let project_option = projects
-        .filter(user_id.eq(user_uuid))
-        .filter(id.eq(project_to_find_id))
+    let project = projects
+        .find(project_to_find_id)
         .select(Project::as_select())
         .first::<Project>(&mut conn)
         .await
-        .optional()
         .map_err(ServiceError::from)?;
 
-    match project_option {
-        Some(project) => Ok(HttpResponse::Ok().json(project)),
-        None => Err(ServiceError::NotFound(format!(
-            "Project with id {} not found or not owned by user",
-            project_to_find_id
-        ))),
-    }
+    Ok(HttpResponse::Ok().json(project))
 }
 
 #[put("/{project_id_path}")]
 pub async fn update_project_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    project_id_path: web::Path<Uuid>,
+    project_id_path: web::Path<ProjectId>,
     payload: web::Json<UpdateProjectPayload>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
@@ -103,22 +258,64 @@ pub async fn update_project_handler(
     let project_changes = UpdateProjectChangeset {
         name: payload.name.clone(),
         color: payload.color.clone(),
+        workspace_id: payload.workspace_id,
+        icon: payload
+            .icon
+            .as_ref()
+            .map(|new_icon| icons::normalize_icon(new_icon.as_deref()))
+            .transpose()?,
+        wip_limits: payload
+            .wip_limits
+            .as_ref()
+            .map(wip_limits::build_limits)
+            .transpose()?,
+        sla_hours: payload.sla_hours.map(sla::validate_sla_hours).transpose()?,
+        currency: payload
+            .currency
+            .as_ref()
+            .map(|new_currency| normalize_currency(new_currency.as_deref()))
+            .transpose()?,
+        theme: payload
+            .theme
+            .as_ref()
+            .map(themes::build_theme)
+            .transpose()?,
         updated_at: Some(Utc::now().naive_utc()),
     };
 
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
-    // Exécuter la requête de manière async
-    let updated_project = diesel::update(
-        projects
-            .filter(id.eq(project_to_update_id))
-            .filter(user_id.eq(user_uuid)),
+    // Changer l'espace de rattachement est réservé au propriétaire du projet
+    let required_permission = if payload.workspace_id.is_some() {
+        Permission::ProjectManage
+    } else {
+        Permission::ProjectWrite
+    };
+    permissions::require_project(
+        &mut conn,
+        user_uuid,
+        project_to_update_id,
+        required_permission,
     )
-    .set(&project_changes)
-    .get_result::<Project>(&mut conn)
-    .await
-    .map_err(ServiceError::from)?;
+    .await?;
+
+    if let Some(Some(workspace_uuid)) = payload.workspace_id {
+        permissions::require_workspace(
+            &mut conn,
+            user_uuid,
+            workspace_uuid,
+            Permission::WorkspaceAddProjects,
+        )
+        .await?;
+    }
+
+    // Exécuter la requête de manière async
+    let updated_project = diesel::update(projects.find(project_to_update_id))
+        .set(&project_changes)
+        .get_result::<Project>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
 
     Ok(HttpResponse::Ok().json(updated_project))
 }
@@ -127,7 +324,8 @@ pub async fn update_project_handler(
 pub async fn delete_project_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    project_id_path: web::Path<Uuid>,
+    project_id_path: web::Path<ProjectId>,
+    query: web::Query<DeleteProjectQueryParams>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let project_to_delete_id = project_id_path.into_inner();
@@ -135,15 +333,72 @@ pub async fn delete_project_handler(
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
-    // Exécuter la requête de manière async
-    let num_deleted = diesel::delete(
-        projects
-            .filter(user_id.eq(user_uuid))
-            .filter(id.eq(project_to_delete_id)),
+    permissions::require_project(
+        &mut conn,
+        user_uuid,
+        project_to_delete_id,
+        Permission::ProjectManage,
     )
-    .execute(&mut conn)
-    .await
-    .map_err(ServiceError::from)?;
+    .await?;
+
+    if query.delete_tasks {
+        let action = DestructiveAction::DeleteProjectWithTasks;
+        match &query.confirmation_token {
+            Some(token) => {
+                confirmations::consume_token(
+                    &mut conn,
+                    user_uuid,
+                    action,
+                    Some(project_to_delete_id.into()),
+                    token,
+                )
+                .await?
+            }
+            None => {
+                let project_tasks = tasks::table
+                    .filter(tasks::project_id.eq(project_to_delete_id))
+                    .select(tasks::id);
+                let task_count = project_tasks.count().get_result::<i64>(&mut conn).await?;
+                let time_entry_count = time_entries::table
+                    .filter(time_entries::task_id.eq_any(project_tasks))
+                    .count()
+                    .get_result::<i64>(&mut conn)
+                    .await?;
+
+                return confirmations::confirmation_required(
+                    &mut conn,
+                    user_uuid,
+                    action,
+                    Some(project_to_delete_id.into()),
+                    json!({
+                        "project_id": project_to_delete_id,
+                        "tasks": task_count,
+                        "time_entries": time_entry_count
+                    }),
+                )
+                .await;
+            }
+        }
+    }
+
+    let delete_tasks = query.delete_tasks;
+    let num_deleted = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                // Les labels et le temps suivi des tâches sont supprimés en cascade
+                if delete_tasks {
+                    diesel::delete(tasks::table.filter(tasks::project_id.eq(project_to_delete_id)))
+                        .execute(conn)
+                        .await?;
+                }
+                diesel::delete(projects.find(project_to_delete_id))
+                    .execute(conn)
+                    .await
+                    .map_err(ServiceError::from)
+            }
+            .scope_boxed()
+        })
+        .await?;
 
     if num_deleted > 0 {
         Ok(HttpResponse::Ok().json(json!({
@@ -157,3 +412,563 @@ pub async fn delete_project_handler(
         )))
     }
 }
+
+// Clôture de projet : statut donné aux tâches ouvertes (completed par défaut, ou cancelled)
+#[derive(Deserialize, Debug)]
+pub struct CompleteProjectQueryParams {
+    pub resolution: Option<String>,
+}
+
+// Les projets archivés (terminés) ne sont listés qu'avec ?include_archived=true
+#[derive(Deserialize, Debug)]
+pub struct ListProjectsQueryParams {
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+// === GET /projects/duplicates ===
+// Projets visibles portant le même nom (casse et espaces ignorés), candidats à une fusion
+#[get("/duplicates")]
+pub async fn list_duplicate_projects_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let mut conn = pool.get().await?;
+
+    let visible_project_ids = permissions::accessible_project_ids(&mut conn, user_uuid).await?;
+    let project_list = projects
+        .filter(id.eq_any(&visible_project_ids))
+        .select(Project::as_select())
+        .load::<Project>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(project_merge::find_duplicates(project_list)))
+}
+
+// === POST /projects/{source}/merge-into/{target}?dry_run= ===
+// Déplace tâches, champs personnalisés, règles et liens d'agenda du projet source vers la
+// cible, puis supprime la source. Les deux projets doivent être gérés par l'utilisateur.
+#[post("/{source_project_id}/merge-into/{target_project_id}")]
+pub async fn merge_project_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    path: web::Path<(ProjectId, ProjectId)>,
+    query: web::Query<DryRunQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let (source_project_id, target_project_id) = path.into_inner();
+    let dry_run = query.dry_run;
+
+    let mut conn = pool.get().await?;
+
+    for project_uuid in [source_project_id, target_project_id] {
+        permissions::require_project(
+            &mut conn,
+            user_uuid,
+            project_uuid,
+            Permission::ProjectManage,
+        )
+        .await?;
+    }
+
+    let mut summary = dry_run::transaction(&mut conn, dry_run, |conn| {
+        async move {
+            // Verrouille les deux projets pour éviter deux fusions croisées simultanées
+            let mut locked = projects
+                .filter(id.eq_any([source_project_id, target_project_id]))
+                .select(Project::as_select())
+                .for_update()
+                .load::<Project>(conn)
+                .await?;
+            let source_position = locked
+                .iter()
+                .position(|project| project.id == source_project_id)
+                .ok_or_else(|| {
+                    ServiceError::NotFound(format!(
+                        "Project with id {} not found",
+                        source_project_id
+                    ))
+                })?;
+            let source = locked.swap_remove(source_position);
+            let target = locked
+                .into_iter()
+                .find(|project| project.id == target_project_id)
+                .ok_or_else(|| {
+                    ServiceError::NotFound(format!(
+                        "Project with id {} not found",
+                        target_project_id
+                    ))
+                })?;
+
+            project_merge::merge_projects(conn, &source, &target).await
+        }
+        .scope_boxed()
+    })
+    .await?;
+    summary.dry_run = dry_run;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+// === POST /projects/{project_id_path}/complete?resolution= ===
+// Clôture le projet en une transaction : tâches ouvertes terminées (ou annulées avec
+// resolution=cancelled), minuteurs en cours arrêtés, projet archivé
+#[post("/{project_id_path}/complete")]
+pub async fn complete_project_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    project_id_path: web::Path<ProjectId>,
+    query: web::Query<CompleteProjectQueryParams>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let project_to_complete_id = project_id_path.into_inner();
+    let resolution = match query.resolution.as_deref() {
+        Some(value) => project_completion::parse_resolution(value)?,
+        None => project_completion::RESOLUTION_COMPLETED,
+    };
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(
+        &mut conn,
+        user_uuid,
+        project_to_complete_id,
+        Permission::ProjectManage,
+    )
+    .await?;
+
+    let summary = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                // Verrou : deux clôtures simultanées ne traitent pas deux fois les tâches
+                let project = projects
+                    .find(project_to_complete_id)
+                    .select(Project::as_select())
+                    .for_update()
+                    .first::<Project>(conn)
+                    .await?;
+                project_completion::complete_project(conn, &project, resolution).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    for timer in &summary.stopped_timers {
+        events::emit(
+            DomainEvent::new(events::EVENT_TIMER_STOPPED, timer.user_id)
+                .with_task(timer.task_id, Some(summary.project_id))
+                .with_properties(json!({
+                    "time_entry_id": timer.time_entry_id,
+                    "duration_seconds": timer.duration_seconds,
+                    "project_completed": true,
+                })),
+        );
+    }
+    if resolution == project_completion::RESOLUTION_COMPLETED {
+        for closed_task_id in &summary.closed_task_ids {
+            events::emit(
+                DomainEvent::new(events::EVENT_TASK_COMPLETED, user_uuid)
+                    .with_task(*closed_task_id, Some(summary.project_id))
+                    .with_properties(json!({
+                        "status": resolution,
+                        "project_completed": true,
+                    })),
+            );
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+// === GET /projects/{project_id_path}/board ===
+// Tâches actives du projet groupées par statut, avec les limites WIP de chaque colonne
+#[get("/{project_id_path}/board")]
+pub async fn get_project_board_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    project_id_path: web::Path<ProjectId>,
+    query: web::Query<BoardQueryParams>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let board_project_id = project_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(
+        &mut conn,
+        user_uuid,
+        board_project_id,
+        Permission::ProjectRead,
+    )
+    .await?;
+
+    let project = projects
+        .find(board_project_id)
+        .select(Project::as_select())
+        .first::<Project>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let visible_stages: &[&str] = if query.include_backlog {
+        &[TASK_STAGE_ACTIVE, TASK_STAGE_BACKLOG]
+    } else {
+        &[TASK_STAGE_ACTIVE]
+    };
+    let project_tasks = tasks::table
+        .filter(tasks::project_id.eq(project.id))
+        .filter(tasks::stage.eq_any(visible_stages))
+        .order((
+            tasks::is_pinned.desc(),
+            tasks::task_order.asc().nulls_last(),
+            tasks::created_at.asc(),
+        ))
+        .select(Task::as_select())
+        .load::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let mut task_responses: Vec<TaskApiResponse> = project_tasks
+        .into_iter()
+        .map(TaskApiResponse::from)
+        .collect();
+    repository::attach_labels(&mut conn, &mut task_responses).await?;
+
+    // Une colonne par statut présent ou limité
+    let limits = wip_limits::parse_limits(&project.wip_limits);
+    let mut by_status: HashMap<String, Vec<TaskApiResponse>> = limits
+        .keys()
+        .map(|limited_status| (limited_status.clone(), Vec::new()))
+        .collect();
+    for task in task_responses {
+        by_status.entry(task.status.clone()).or_default().push(task);
+    }
+
+    let mut columns: Vec<BoardColumn> = by_status
+        .into_iter()
+        .map(|(column_status, column_tasks)| {
+            let wip_limit = limits.get(&column_status).copied();
+            BoardColumn {
+                is_full: wip_limit.is_some_and(|limit| column_tasks.len() as i64 >= limit),
+                task_count: column_tasks.len(),
+                wip_limit,
+                status: column_status,
+                tasks: column_tasks,
+            }
+        })
+        .collect();
+    columns.sort_by(|a, b| {
+        let rank = |column_status: &str| {
+            BOARD_STATUS_ORDER
+                .iter()
+                .position(|known| *known == column_status)
+                .unwrap_or(BOARD_STATUS_ORDER.len())
+        };
+        rank(&a.status)
+            .cmp(&rank(&b.status))
+            .then_with(|| a.status.cmp(&b.status))
+    });
+
+    Ok(HttpResponse::Ok().json(ProjectBoardResponse {
+        project_id: project.id,
+        columns,
+    }))
+}
+
+// === GET /projects/{project_id_path}/burndown ===
+// Séries quotidiennes (tâches restantes, terminées, périmètre total, heures estimées
+// restantes) reconstruites depuis task_status_history
+#[get("/{project_id_path}/burndown")]
+pub async fn get_project_burndown_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    project_id_path: web::Path<ProjectId>,
+    query: web::Query<BurndownQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Analytics)?;
+    let burndown_project_id = project_id_path.into_inner();
+
+    let to_date = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from_date = query
+        .from
+        .unwrap_or(to_date - ChronoDuration::days(DEFAULT_BURNDOWN_DAYS - 1));
+    if from_date > to_date {
+        return Err(ServiceError::BadRequest(
+            "from cannot be after to".to_string(),
+        ));
+    }
+    if (to_date - from_date).num_days() >= MAX_BURNDOWN_DAYS {
+        return Err(ServiceError::ValidationError(format!(
+            "The burndown range cannot exceed {} days",
+            MAX_BURNDOWN_DAYS
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(
+        &mut conn,
+        user_uuid,
+        burndown_project_id,
+        Permission::ProjectRead,
+    )
+    .await?;
+
+    // Tâches ayant appartenu au projet à un moment de leur historique
+    let history_task_ids = task_status_history::table
+        .filter(task_status_history::project_id.eq(burndown_project_id))
+        .select(task_status_history::task_id)
+        .distinct()
+        .load::<TaskId>(&mut conn)
+        .await?;
+
+    let range_end = Utc.from_utc_datetime(&to_date.and_hms_opt(23, 59, 59).unwrap());
+    let history = task_status_history::table
+        .filter(task_status_history::task_id.eq_any(&history_task_ids))
+        .filter(task_status_history::changed_at.le(range_end))
+        .order(task_status_history::changed_at.asc())
+        .select((
+            task_status_history::task_id,
+            task_status_history::project_id,
+            task_status_history::new_status,
+            task_status_history::changed_at,
+        ))
+        .load::<(TaskId, Option<ProjectId>, String, DateTime<Utc>)>(&mut conn)
+        .await?;
+
+    let estimates: HashMap<TaskId, f64> = tasks::table
+        .filter(tasks::id.eq_any(&history_task_ids))
+        .select((tasks::id, tasks::metadata))
+        .load::<(TaskId, serde_json::Value)>(&mut conn)
+        .await?
+        .into_iter()
+        .filter_map(|(task_uuid, task_metadata)| {
+            task_metadata
+                .get(ESTIMATE_METADATA_KEY)
+                .and_then(|estimate| estimate.as_f64())
+                .filter(|hours| *hours > 0.0)
+                .map(|hours| (task_uuid, hours))
+        })
+        .collect();
+
+    // Rejoue l'historique jour par jour : (projet, statut) de chaque tâche en fin de journée
+    let mut task_states: HashMap<TaskId, (Option<ProjectId>, String)> = HashMap::new();
+    let mut history_iter = history.into_iter().peekable();
+    let mut points = Vec::new();
+    for day in from_date.iter_days().take_while(|day| *day <= to_date) {
+        let day_end = Utc.from_utc_datetime(&day.and_hms_opt(23, 59, 59).unwrap());
+        while let Some((task_uuid, task_project, new_status, _)) =
+            history_iter.next_if(|entry| entry.3 <= day_end)
+        {
+            task_states.insert(task_uuid, (task_project, new_status));
+        }
+
+        let mut point = BurndownPoint {
+            date: day,
+            total_tasks: 0,
+            completed_tasks: 0,
+            remaining_tasks: 0,
+            remaining_estimated_hours: 0.0,
+        };
+        for (task_uuid, (task_project, task_status)) in &task_states {
+            if *task_project != Some(burndown_project_id) {
+                continue;
+            }
+            point.total_tasks += 1;
+            if DONE_TASK_STATUSES.contains(&task_status.as_str()) {
+                point.completed_tasks += 1;
+            } else {
+                point.remaining_tasks += 1;
+                point.remaining_estimated_hours += estimates.get(task_uuid).copied().unwrap_or(0.0);
+            }
+        }
+        points.push(point);
+    }
+
+    Ok(HttpResponse::Ok().json(ProjectBurndownResponse {
+        project_id: burndown_project_id,
+        from: from_date,
+        to: to_date,
+        points,
+    }))
+}
+
+// === GET /projects/{project_id_path}/report ===
+// Rapport de statut (tâches ouvertes, terminées sur la période, temps par tâche)
+// au format markdown (par défaut) ou html.
+#[get("/{project_id_path}/report")]
+pub async fn get_project_report_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    project_id_path: web::Path<ProjectId>,
+    query: web::Query<ProjectReportQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Export)?;
+    let project_to_report_id = project_id_path.into_inner();
+    let query = query.into_inner();
+
+    let format = ReportFormat::parse(query.format.as_deref())?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, query.week_start.as_deref()).await?;
+    let (start_date, end_date) = calculate_date_range(
+        &AnalyticsQueryPeriod {
+            period: query.period,
+            start_date: query.start_date,
+            end_date: query.end_date,
+            week_start: None,
+        },
+        week_start,
+    )?;
+    let start_datetime = Utc.from_utc_datetime(&start_date.and_hms_opt(0, 0, 0).unwrap());
+    let end_datetime = Utc.from_utc_datetime(&end_date.and_hms_opt(23, 59, 59).unwrap());
+
+    permissions::require_project(
+        &mut conn,
+        user_uuid,
+        project_to_report_id,
+        Permission::ProjectRead,
+    )
+    .await?;
+
+    let project = projects
+        .find(project_to_report_id)
+        .select(Project::as_select())
+        .first::<Project>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    // Un projet partagé est rapporté dans son ensemble, tous membres confondus
+    let project_tasks = tasks::table
+        .filter(tasks::project_id.eq(project.id))
+        .order((tasks::due_date.asc().nulls_last(), tasks::created_at.asc()))
+        .select(Task::as_select())
+        .load::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    // Temps suivi par tâche sur la période
+    let tracked_by_task: HashMap<TaskId, i64> = time_entries::table
+        .inner_join(tasks::table)
+        .filter(tasks::project_id.eq(project.id))
+        .filter(time_entries::is_break.eq(false))
+        .filter(time_entries::start_time.ge(start_datetime))
+        .filter(time_entries::start_time.le(end_datetime))
+        .group_by(time_entries::task_id)
+        .select((
+            time_entries::task_id,
+            diesel::dsl::sum(time_entries::duration_seconds),
+        ))
+        .load::<(TaskId, Option<i64>)>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?
+        .into_iter()
+        .map(|(tracked_task_id, seconds)| (tracked_task_id, seconds.unwrap_or(0)))
+        .collect();
+
+    let period_start_naive = start_datetime.naive_utc();
+    let period_end_naive = end_datetime.naive_utc();
+    let to_report_task = |task: &Task| {
+        let tracked_seconds = tracked_by_task.get(&task.id).copied().unwrap_or(0);
+        ReportTask {
+            title: task.title.clone(),
+            status: task.status.clone(),
+            due_date: task.due_date.map(|d| d.to_string()),
+            tracked_seconds,
+            tracked: reports::format_duration(tracked_seconds),
+        }
+    };
+
+    let open_tasks = project_tasks
+        .iter()
+        .filter(|task| !DONE_TASK_STATUSES.contains(&task.status.as_str()))
+        .map(to_report_task)
+        .collect();
+    let completed_tasks = project_tasks
+        .iter()
+        .filter(|task| DONE_TASK_STATUSES.contains(&task.status.as_str()))
+        .filter(|task| task.updated_at >= period_start_naive && task.updated_at <= period_end_naive)
+        .map(to_report_task)
+        .collect();
+    let mut time_by_task: Vec<ReportTask> = project_tasks
+        .iter()
+        .filter(|task| tracked_by_task.contains_key(&task.id))
+        .map(to_report_task)
+        .collect();
+    time_by_task.sort_by_key(|task| std::cmp::Reverse(task.tracked_seconds));
+
+    let total_seconds: i64 = tracked_by_task.values().sum();
+    let report = ProjectReport {
+        project_name: project.name,
+        period_start: start_date.to_string(),
+        period_end: end_date.to_string(),
+        generated_at: Utc::now().format("%Y-%m-%d %H:%M UTC").to_string(),
+        open_tasks,
+        completed_tasks,
+        time_by_task,
+        total_tracked: reports::format_duration(total_seconds),
+    };
+
+    let rendered = reports::render_project_report(&report, format)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .body(rendered))
+}
+
+// === GET /projects/{project_id_path}/export ===
+// Document imprimable du projet (tâches ouvertes et terminées, temps par tâche), en PDF.
+#[get("/{project_id_path}/export")]
+pub async fn export_project_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    project_id_path: web::Path<ProjectId>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Export)?;
+    let project_to_export_id = project_id_path.into_inner();
+    let format = ExportFormat::parse(query.format.as_deref())?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_project(
+        &mut conn,
+        user_uuid,
+        project_to_export_id,
+        Permission::ProjectRead,
+    )
+    .await?;
+
+    let project = projects
+        .find(project_to_export_id)
+        .select(Project::as_select())
+        .first::<Project>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let document = exports::render_project_export(&mut conn, project).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}\"",
+                exports::export_filename("project", project_to_export_id, format)
+            ),
+        ))
+        .body(document))
+}
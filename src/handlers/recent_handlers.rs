@@ -0,0 +1,162 @@
+// OptiTask/backend-api/src/handlers/recent_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::{ProjectId, TaskId};
+use crate::models::{
+    RecentItem, RecentQuery, RecentView, RecordRecentViewPayload, RECENT_ENTITY_PROJECT,
+    RECENT_ENTITY_TASK, RECENT_ENTITY_TYPES,
+};
+use crate::permissions::{self, Permission};
+use crate::recents::{self, MAX_RECENT_VIEWS};
+use crate::schema::{projects, recent_views, tasks};
+use actix_web::{get, post, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const DEFAULT_RECENT_LIMIT: i64 = 20;
+const MAX_RECENT_LIMIT: i64 = 50;
+
+// === POST /recent ===
+// Enregistre la consultation d'une tâche ou d'un projet accessible
+#[post("")]
+pub async fn record_recent_view_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<RecordRecentViewPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let entity_type = payload.entity_type.trim();
+    if !RECENT_ENTITY_TYPES.contains(&entity_type) {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid entity_type '{}'. Supported: {}",
+            entity_type,
+            RECENT_ENTITY_TYPES.join(", ")
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    if entity_type == RECENT_ENTITY_TASK {
+        permissions::require_task(
+            &mut conn,
+            user_uuid,
+            TaskId::from(payload.entity_id),
+            Permission::TaskRead,
+        )
+        .await?;
+    } else {
+        permissions::require_project(
+            &mut conn,
+            user_uuid,
+            ProjectId::from(payload.entity_id),
+            Permission::ProjectRead,
+        )
+        .await?;
+    }
+
+    recents::record_view(&mut conn, user_uuid, entity_type, payload.entity_id).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "View recorded"
+    })))
+}
+
+// === GET /recent?limit= ===
+// Éléments triés par score amorti ; ceux supprimés ou devenus inaccessibles sont omis
+#[get("")]
+pub async fn list_recent_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<RecentQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let limit = query.limit.unwrap_or(DEFAULT_RECENT_LIMIT);
+    if !(1..=MAX_RECENT_LIMIT).contains(&limit) {
+        return Err(ServiceError::ValidationError(format!(
+            "limit must be between 1 and {}",
+            MAX_RECENT_LIMIT
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let views = recent_views::table
+        .filter(recent_views::user_id.eq(user_uuid))
+        .order(recent_views::last_viewed_at.desc())
+        .limit(MAX_RECENT_VIEWS)
+        .select(RecentView::as_select())
+        .load::<RecentView>(&mut conn)
+        .await?;
+
+    let ids_of = |wanted: &str| -> Vec<Uuid> {
+        views
+            .iter()
+            .filter(|view| view.entity_type == wanted)
+            .map(|view| view.entity_id)
+            .collect()
+    };
+    let task_ids = ids_of(RECENT_ENTITY_TASK);
+    let project_ids = ids_of(RECENT_ENTITY_PROJECT);
+
+    let accessible_projects = permissions::accessible_project_ids(&mut conn, user_uuid).await?;
+
+    // (titre, projet) par tâche encore accessible
+    let task_titles: HashMap<Uuid, (String, Option<ProjectId>)> = tasks::table
+        .filter(tasks::id.eq_any(&task_ids))
+        .filter(
+            tasks::user_id
+                .eq(user_uuid)
+                .or(tasks::project_id.eq_any(&accessible_projects)),
+        )
+        .select((tasks::id, tasks::title, tasks::project_id))
+        .load::<(Uuid, String, Option<ProjectId>)>(&mut conn)
+        .await?
+        .into_iter()
+        .map(|(task_uuid, title, project_uuid)| (task_uuid, (title, project_uuid)))
+        .collect();
+    let project_names: HashMap<Uuid, String> = projects::table
+        .filter(projects::id.eq_any(&project_ids))
+        .filter(projects::id.eq_any(&accessible_projects))
+        .select((projects::id, projects::name))
+        .load::<(Uuid, String)>(&mut conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let now = Utc::now();
+    let mut items: Vec<RecentItem> = views
+        .into_iter()
+        .filter_map(|view| {
+            let (title, project_id) = if view.entity_type == RECENT_ENTITY_TASK {
+                task_titles.get(&view.entity_id).cloned()?
+            } else {
+                (project_names.get(&view.entity_id).cloned()?, None)
+            };
+            Some(RecentItem {
+                score: recents::decayed_score(view.score, view.last_viewed_at, now),
+                entity_type: view.entity_type,
+                entity_id: view.entity_id,
+                title,
+                project_id,
+                view_count: view.view_count,
+                last_viewed_at: view.last_viewed_at,
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.last_viewed_at.cmp(&a.last_viewed_at))
+    });
+    items.truncate(limit as usize);
+
+    Ok(HttpResponse::Ok().json(json!({ "items": items })))
+}
@@ -0,0 +1,242 @@
+// OptiTask/backend-api/src/handlers/settings_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::macros;
+use crate::models::{
+    ClientPreferences, PutClientPreferencesPayload, UpdateUserSettingsChangeset,
+    UpdateUserSettingsPayload, UserSettings,
+};
+use crate::permissions::{self, Permission};
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::{client_preferences, user_settings};
+use crate::settings;
+use crate::timer_auto_stop::TIMER_AUTO_STOP_MODES;
+use actix_web::{delete, get, put, web, HttpResponse};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+
+// === GET /settings ===
+#[get("")]
+pub async fn get_settings_handler(
+    pool: web::Data<DbPool>,
+    read_only_mode: web::Data<ReadOnlyMode>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // La création de la ligne par défaut est une écriture
+    let user_settings = match settings::find_settings(&mut conn, authenticated_user.id).await? {
+        Some(user_settings) => user_settings,
+        None => {
+            read_only_mode.ensure_writable()?;
+            settings::load_or_create_settings(&mut conn, authenticated_user.id).await?
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(user_settings))
+}
+
+// === PUT /settings ===
+// Mise à jour partielle : seuls les champs présents sont modifiés
+#[put("")]
+pub async fn update_settings_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<UpdateUserSettingsPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+
+    // Stocké sous sa forme canonique ("Sunday" => "sunday")
+    let week_start = payload
+        .week_start
+        .as_deref()
+        .map(settings::parse_week_start)
+        .transpose()?
+        .map(|weekday| settings::week_start_name(weekday).to_string());
+
+    // Les deux bornes des heures calmes sont définies ou effacées ensemble
+    if payload.quiet_hours_start.map(|start| start.is_some())
+        != payload.quiet_hours_end.map(|end| end.is_some())
+    {
+        return Err(ServiceError::ValidationError(
+            "quiet_hours_start and quiet_hours_end must be set or cleared together".to_string(),
+        ));
+    }
+    if let Some(offset) = payload.utc_offset_minutes {
+        if offset.abs() > settings::MAX_UTC_OFFSET_MINUTES {
+            return Err(ServiceError::ValidationError(format!(
+                "utc_offset_minutes must be between -{} and {}",
+                settings::MAX_UTC_OFFSET_MINUTES,
+                settings::MAX_UTC_OFFSET_MINUTES
+            )));
+        }
+    }
+    if let Some(batch_minutes) = payload.push_batch_minutes {
+        if !(0..=settings::MAX_PUSH_BATCH_MINUTES).contains(&batch_minutes) {
+            return Err(ServiceError::ValidationError(format!(
+                "push_batch_minutes must be between 0 and {}",
+                settings::MAX_PUSH_BATCH_MINUTES
+            )));
+        }
+    }
+
+    if let Some(mode) = payload.timer_auto_stop_mode.as_deref() {
+        if !TIMER_AUTO_STOP_MODES.contains(&mode) {
+            return Err(ServiceError::ValidationError(format!(
+                "Invalid timer_auto_stop_mode '{}'. Supported: {}",
+                mode,
+                TIMER_AUTO_STOP_MODES.join(", ")
+            )));
+        }
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Le projet par défaut doit accepter de nouvelles tâches de l'utilisateur
+    if let Some(Some(project_uuid)) = payload.default_project_id {
+        permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectWrite)
+            .await?;
+    }
+
+    let settings_changes = UpdateUserSettingsChangeset {
+        notify_watched_status_changes: payload.notify_watched_status_changes,
+        notify_watched_comments: payload.notify_watched_comments,
+        updated_at: Some(Utc::now().naive_utc()),
+        week_start,
+        time_locked_before: payload.time_locked_before,
+        push_due_reminders: payload.push_due_reminders,
+        push_timer_nudges: payload.push_timer_nudges,
+        push_mentions: payload.push_mentions,
+        quiet_hours_start: payload.quiet_hours_start,
+        quiet_hours_end: payload.quiet_hours_end,
+        utc_offset_minutes: payload.utc_offset_minutes,
+        push_batch_minutes: payload.push_batch_minutes,
+        nudge_planned_tasks: payload.nudge_planned_tasks,
+        nudge_no_time_tracked: payload.nudge_no_time_tracked,
+        nudge_check_time: payload.nudge_check_time,
+        default_project_id: payload.default_project_id,
+        leaderboard_opt_out: payload.leaderboard_opt_out,
+        timer_auto_stop_time: payload.timer_auto_stop_time,
+        timer_auto_stop_mode: payload.timer_auto_stop_mode.clone(),
+    };
+
+    settings::load_or_create_settings(&mut conn, user_uuid).await?;
+
+    let updated_settings = diesel::update(user_settings::table.find(user_uuid))
+        .set(&settings_changes)
+        .get_result::<UserSettings>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(updated_settings))
+}
+
+fn client_preferences_not_found(namespace: &str) -> ServiceError {
+    ServiceError::NotFound(format!(
+        "No client preferences stored under '{}'",
+        namespace
+    ))
+}
+
+// === GET /settings/client ===
+// Tous les documents de préférences client de l'utilisateur
+#[get("/client")]
+pub async fn list_client_preferences_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let preference_list = client_preferences::table
+        .filter(client_preferences::user_id.eq(authenticated_user.id))
+        .order(client_preferences::namespace.asc())
+        .select(ClientPreferences::as_select())
+        .load::<ClientPreferences>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(preference_list))
+}
+
+// === GET /settings/client/{namespace} ===
+#[get("/client/{namespace}")]
+pub async fn get_client_preferences_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    namespace: web::Path<String>,
+) -> Result<HttpResponse, ServiceError> {
+    let namespace = namespace.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let preferences =
+        settings::load_client_preferences(&mut conn, authenticated_user.id, &namespace)
+            .await?
+            .ok_or_else(|| client_preferences_not_found(&namespace))?;
+
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+// === PUT /settings/client/{namespace} ===
+// Remplace le document ; expected_updated_at active la détection de conflit
+#[put("/client/{namespace}")]
+pub async fn put_client_preferences_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    namespace: web::Path<String>,
+    payload: web::Json<PutClientPreferencesPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let namespace = namespace.into_inner();
+    settings::validate_namespace(&namespace)?;
+    settings::validate_client_preferences(&payload.data)?;
+    // Les macros sont exécutées côté serveur : leur définition doit être valide
+    if namespace == macros::MACROS_NAMESPACE {
+        macros::parse_document(&payload.data)?;
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let saved = settings::save_client_preferences(
+        &mut conn,
+        authenticated_user.id,
+        &namespace,
+        &payload.data,
+        payload.expected_updated_at,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(saved))
+}
+
+// === DELETE /settings/client/{namespace} ===
+#[delete("/client/{namespace}")]
+pub async fn delete_client_preferences_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    namespace: web::Path<String>,
+) -> Result<HttpResponse, ServiceError> {
+    let namespace = namespace.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted =
+        diesel::delete(client_preferences::table.find((authenticated_user.id, &namespace)))
+            .execute(&mut conn)
+            .await?;
+
+    if num_deleted == 0 {
+        return Err(client_preferences_not_found(&namespace));
+    }
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": format!("Client preferences '{}' deleted", namespace)
+    })))
+}
@@ -0,0 +1,159 @@
+// OptiTask/backend-api/src/handlers/suggest_handlers.rs
+// Suggestions de saisie du quick-switcher : correspondance par préfixe de mot sur les
+// titres et noms (index trigrammes), quelques résultats par type. Volontairement plus
+// léger que la recherche : pas de filtres, pas de pagination, pas de décompte de coût.
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{
+    LabelSuggestion, ProjectSuggestion, SuggestQuery, SuggestResponse, TaskSuggestion,
+    SUGGEST_TYPES, SUGGEST_TYPE_LABELS, SUGGEST_TYPE_PROJECTS, SUGGEST_TYPE_TASKS,
+    TASK_STAGE_ARCHIVE,
+};
+use crate::permissions;
+use crate::schema::{labels, projects, tasks};
+use actix_web::{get, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+const SUGGESTIONS_PER_TYPE: i64 = 5;
+const MAX_SUGGEST_QUERY_CHARS: usize = 100;
+
+// Échappe les jokers de LIKE (\ est le caractère d'échappement par défaut)
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn parse_types(types: Option<&str>) -> Result<Vec<&str>, ServiceError> {
+    let Some(types) = types.map(str::trim).filter(|types| !types.is_empty()) else {
+        return Ok(SUGGEST_TYPES.to_vec());
+    };
+    types
+        .split(',')
+        .map(str::trim)
+        .filter(|requested| !requested.is_empty())
+        .map(|requested| {
+            SUGGEST_TYPES
+                .iter()
+                .copied()
+                .find(|known| *known == requested)
+                .ok_or_else(|| {
+                    ServiceError::ValidationError(format!(
+                        "Invalid type '{}'. Supported: {}",
+                        requested,
+                        SUGGEST_TYPES.join(", ")
+                    ))
+                })
+        })
+        .collect()
+}
+
+// === GET /suggest?q=&types=tasks,projects,labels ===
+// Les résultats commençant par la saisie passent avant ceux dont un mot la commence
+#[get("")]
+pub async fn suggest_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<SuggestQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let typed = query.q.trim();
+    if typed.is_empty() || typed.chars().count() > MAX_SUGGEST_QUERY_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "q must contain between 1 and {} characters",
+            MAX_SUGGEST_QUERY_CHARS
+        )));
+    }
+    let types = parse_types(query.types.as_deref())?;
+
+    let escaped = escape_like(typed);
+    let starts_with = format!("{}%", escaped);
+    let word_starts_with = format!("% {}%", escaped);
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let needs_projects =
+        types.contains(&SUGGEST_TYPE_TASKS) || types.contains(&SUGGEST_TYPE_PROJECTS);
+    let project_ids = if needs_projects {
+        permissions::accessible_project_ids(&mut conn, user_uuid).await?
+    } else {
+        Vec::new()
+    };
+
+    let mut response = SuggestResponse {
+        query: typed.to_string(),
+        tasks: None,
+        projects: None,
+        labels: None,
+    };
+
+    if types.contains(&SUGGEST_TYPE_TASKS) {
+        response.tasks = Some(
+            tasks::table
+                .filter(
+                    tasks::user_id
+                        .eq(user_uuid)
+                        .or(tasks::project_id.eq_any(&project_ids)),
+                )
+                .filter(tasks::stage.ne(TASK_STAGE_ARCHIVE))
+                .filter(
+                    tasks::title
+                        .ilike(&starts_with)
+                        .or(tasks::title.ilike(&word_starts_with)),
+                )
+                .order((
+                    tasks::title.not_ilike(&starts_with),
+                    tasks::updated_at.desc(),
+                ))
+                .limit(SUGGESTIONS_PER_TYPE)
+                .select((tasks::id, tasks::title, tasks::project_id, tasks::status))
+                .load::<TaskSuggestion>(&mut conn)
+                .await?,
+        );
+    }
+
+    if types.contains(&SUGGEST_TYPE_PROJECTS) {
+        response.projects = Some(
+            projects::table
+                .filter(projects::id.eq_any(&project_ids))
+                .filter(
+                    projects::name
+                        .ilike(&starts_with)
+                        .or(projects::name.ilike(&word_starts_with)),
+                )
+                .order((projects::name.not_ilike(&starts_with), projects::name))
+                .limit(SUGGESTIONS_PER_TYPE)
+                .select((
+                    projects::id,
+                    projects::name,
+                    projects::color,
+                    projects::icon,
+                ))
+                .load::<ProjectSuggestion>(&mut conn)
+                .await?,
+        );
+    }
+
+    if types.contains(&SUGGEST_TYPE_LABELS) {
+        response.labels = Some(
+            labels::table
+                .filter(labels::user_id.eq(user_uuid))
+                .filter(
+                    labels::name
+                        .ilike(&starts_with)
+                        .or(labels::name.ilike(&word_starts_with)),
+                )
+                .order((labels::name.not_ilike(&starts_with), labels::name))
+                .limit(SUGGESTIONS_PER_TYPE)
+                .select((labels::id, labels::name, labels::color))
+                .load::<LabelSuggestion>(&mut conn)
+                .await?,
+        );
+    }
+
+    Ok(HttpResponse::Ok().json(response))
+}
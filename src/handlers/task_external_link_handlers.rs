@@ -0,0 +1,154 @@
+// OptiTask/backend-api/src/handlers/task_external_link_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::external_links::{self, LinkMetadata, MAX_LINKS_PER_TASK};
+use crate::ids::TaskId;
+use crate::models::{CreateTaskExternalLinkPayload, NewTaskExternalLink, TaskExternalLink};
+use crate::permissions::{self, Permission};
+use crate::schema::task_external_links;
+use actix_web::{delete, get, post, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+use uuid::Uuid;
+
+// === POST /tasks/{task_id_path}/links ===
+// Attache une référence externe à la tâche ; le titre et l'icône des pages publiques sont lus
+// à l'ajout (voir external_links.rs)
+#[post("/{task_id_path}/links")]
+pub async fn add_task_external_link_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+    payload: web::Json<CreateTaskExternalLinkPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let task_uuid = task_id_path.into_inner();
+    let payload = payload.into_inner();
+
+    let link = external_links::normalize(
+        payload.kind.trim(),
+        &payload.reference,
+        payload.url.as_deref(),
+    )?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskWrite).await?;
+
+    let existing = task_external_links::table
+        .filter(task_external_links::task_id.eq(task_uuid))
+        .select((task_external_links::kind, task_external_links::reference))
+        .load::<(String, String)>(&mut conn)
+        .await?;
+    if existing
+        .iter()
+        .any(|(kind, reference)| kind == link.kind && *reference == link.reference)
+    {
+        return Err(ServiceError::ConflictError(format!(
+            "Task {} already links to {} {}",
+            task_uuid, link.kind, link.reference
+        )));
+    }
+    if existing.len() as i64 >= MAX_LINKS_PER_TASK {
+        return Err(ServiceError::ValidationError(format!(
+            "A task cannot have more than {} links",
+            MAX_LINKS_PER_TASK
+        )));
+    }
+
+    let metadata = match &link.url {
+        Some(url) if external_links::has_preview(&link) => {
+            external_links::fetch_metadata(url).await
+        }
+        _ => LinkMetadata::default(),
+    };
+
+    let created = diesel::insert_into(task_external_links::table)
+        .values(&NewTaskExternalLink {
+            task_id: task_uuid,
+            user_id: user_uuid,
+            kind: link.kind.to_string(),
+            reference: link.reference,
+            url: link.url,
+            title: metadata.title,
+            favicon_url: metadata.favicon_url,
+            metadata_fetched_at: metadata.fetched_at,
+        })
+        .returning(TaskExternalLink::as_returning())
+        .get_result::<TaskExternalLink>(&mut conn)
+        .await?;
+
+    log::info!(
+        "User {} linked {} {} to task {}",
+        user_uuid,
+        created.kind,
+        created.reference,
+        task_uuid
+    );
+    Ok(HttpResponse::Created().json(created))
+}
+
+// === GET /tasks/{task_id_path}/links ===
+#[get("/{task_id_path}/links")]
+pub async fn list_task_external_links_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let task_uuid = task_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskRead).await?;
+
+    let links = task_external_links::table
+        .filter(task_external_links::task_id.eq(task_uuid))
+        .order(task_external_links::created_at.asc())
+        .select(TaskExternalLink::as_select())
+        .load::<TaskExternalLink>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(links))
+}
+
+// === DELETE /tasks/{task_id_path}/links/{link_id_path} ===
+#[delete("/{task_id_path}/links/{link_id_path}")]
+pub async fn remove_task_external_link_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    path_params: web::Path<(TaskId, Uuid)>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let (task_uuid, link_uuid) = path_params.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskWrite).await?;
+
+    let deleted = diesel::delete(
+        task_external_links::table
+            .filter(task_external_links::id.eq(link_uuid))
+            .filter(task_external_links::task_id.eq(task_uuid)),
+    )
+    .execute(&mut conn)
+    .await?;
+    if deleted == 0 {
+        return Err(ServiceError::NotFound(format!(
+            "Link with id {} not found on task {}",
+            link_uuid, task_uuid
+        )));
+    }
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Link removed from task",
+        "task_id": task_uuid,
+        "link_id": link_uuid
+    })))
+}
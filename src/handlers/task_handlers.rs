@@ -1,17 +1,44 @@
 // OptiTask/backend-api/src/task_handlers.rs
 use crate::auth_utils::AuthenticatedUser;
+use crate::automations::{self, AutomationEvent};
+use crate::batch::{self, BatchItemResult, BatchReport};
+use crate::cost_limits::{CostClass, CostLimiter};
+use crate::custom_fields;
+use crate::date_validation;
 use crate::db::DbPool;
+use crate::dry_run::{self, DryRunQuery};
 use crate::error_handler::ServiceError;
+use crate::events::{self, DomainEvent};
+use crate::exports::{self, ExportFormat, ExportQuery};
+use crate::ids::{ProjectId, TaskId, TimeEntryId};
+use crate::integrations::{self, google_calendar::GoogleCalendarConfig};
+use crate::mentions;
 use crate::models::{
-    CreateTaskPayload, Label, NewTask, PaginatedResponse, Task, TaskApiResponse,
-     UpdateTaskChangeset, UpdateTaskPayload,
+    AutomationTrigger, BulkTaskRef, BulkTaskStagePayload, BulkTaskStatusPayload, CreateTaskPayload,
+    CustomFieldDefinition, FollowUpConfig, NewTask, PaginatedResponse, PomodoroInterruption,
+    PomodoroSession, SnoozeTaskPayload, Task, TaskApiResponse, TaskContext, TaskDuplicateCandidate,
+    TaskPomodoroHistoryResponse, UpdateTaskChangeset, UpdateTaskPayload, DONE_TASK_STATUSES,
+    TASK_STAGES, TASK_STAGE_ACTIVE, TASK_STAGE_BACKLOG,
 };
+use crate::notifications::{self, TaskActivity};
+use crate::pagination;
+use crate::permissions::{self, Permission};
+use crate::repository;
 use crate::schema::tasks::dsl::*;
-use crate::schema::{labels, task_labels, tasks};
-use actix_web::{delete, get, post, put, web, HttpResponse};
-use chrono::Utc;
+use crate::schema::{
+    custom_field_definitions, pomodoro_interruptions, task_custom_values, task_labels, task_links,
+    tasks, time_entries,
+};
+use crate::settings;
+use crate::time_budgets;
+use crate::wip_limits;
+use actix_web::http::{header, StatusCode};
+use actix_web::{delete, get, post, put, web, HttpResponse, ResponseError};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel::sql_types::{Array, Float4, Text, Uuid as DieselUuid};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
@@ -19,40 +46,371 @@ use uuid::Uuid;
 // Struct pour les paramètres de requête de filtrage des tâches
 #[derive(Deserialize, Debug)]
 pub struct TaskQueryParams {
-    pub project_id: Option<Uuid>,
+    pub project_id: Option<ProjectId>,
     pub status: Option<String>,
+    // Filtre sur un champ personnalisé : custom_field_id + custom_field_value (forme canonique)
+    pub custom_field_id: Option<Uuid>,
+    pub custom_field_value: Option<String>,
+    // Filtres sur le contexte (ex: ?context.source=mobile)
+    #[serde(rename = "context.source")]
+    pub context_source: Option<String>,
+    #[serde(rename = "context.device")]
+    pub context_device: Option<String>,
+    #[serde(rename = "context.location_name")]
+    pub context_location_name: Option<String>,
+    // Inclure les tâches reportées dont le report n'est pas encore échu
+    #[serde(default)]
+    pub include_snoozed: bool,
+    // Filtrer par horizon de planification (backlog, active, archive)
+    pub stage: Option<String>,
+    // Données supplémentaires, séparées par des virgules (voir TASK_EXPANSIONS)
+    pub expand: Option<String>,
+    // Vue prédéfinie : "inbox" = tâches de l'utilisateur sans projet
+    pub view: Option<String>,
     pub page: Option<i64>,
     pub per_page: Option<i64>,
 }
 
+// Valeurs acceptées par ?view=
+const TASK_VIEW_INBOX: &str = "inbox";
+
+// Paramètres de requête pour la lecture d'une tâche
+#[derive(Deserialize, Debug)]
+pub struct GetTaskQueryParams {
+    pub expand: Option<String>,
+}
+
+// Valeurs acceptées par ?expand=
+const EXPAND_POMODOROS: &str = "pomodoros";
+const TASK_EXPANSIONS: &[&str] = &[EXPAND_POMODOROS];
+
+// Vérifie ?expand= et indique si le nombre de pomodoros est demandé
+fn expands_pomodoros(expand: Option<&str>) -> Result<bool, ServiceError> {
+    let mut pomodoros = false;
+    for expansion in expand
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|expansion| !expansion.is_empty())
+    {
+        if !TASK_EXPANSIONS.contains(&expansion) {
+            return Err(ServiceError::ValidationError(format!(
+                "Invalid expand '{}'. Supported: {}",
+                expansion,
+                TASK_EXPANSIONS.join(", ")
+            )));
+        }
+        pomodoros |= expansion == EXPAND_POMODOROS;
+    }
+    Ok(pomodoros)
+}
+
+// Paramètres de requête pour la création de tâche
+#[derive(Deserialize, Debug)]
+pub struct CreateTaskQueryParams {
+    #[serde(default)]
+    pub check_duplicates: bool,
+    // Ignorer la limite WIP de la colonne cible
+    #[serde(rename = "override", default)]
+    pub override_wip_limit: bool,
+}
+
+// Paramètres de requête pour la mise à jour de tâche
+#[derive(Deserialize, Debug)]
+pub struct UpdateTaskQueryParams {
+    #[serde(rename = "override", default)]
+    pub override_wip_limit: bool,
+}
+
+// Paramètres du changement de statut groupé ; dry_run valide tout puis annule
+#[derive(Deserialize, Debug)]
+pub struct BulkTaskStatusQueryParams {
+    #[serde(rename = "override", default)]
+    pub override_wip_limit: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// Longueur maximale de chaque valeur de contexte
+const MAX_CONTEXT_VALUE_CHARS: usize = 100;
+
+// Nettoie le contexte reçu ; None s'il ne contient aucune valeur
+fn context_to_json(raw_context: TaskContext) -> Result<Option<serde_json::Value>, ServiceError> {
+    let clean = |value: Option<String>, key: &str| -> Result<Option<String>, ServiceError> {
+        let value = value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        match value {
+            Some(v) if v.chars().count() > MAX_CONTEXT_VALUE_CHARS => {
+                Err(ServiceError::ValidationError(format!(
+                    "context.{} cannot exceed {} characters",
+                    key, MAX_CONTEXT_VALUE_CHARS
+                )))
+            }
+            other => Ok(other),
+        }
+    };
+
+    let cleaned = TaskContext {
+        location_name: clean(raw_context.location_name, "location_name")?,
+        device: clean(raw_context.device, "device")?,
+        source: clean(raw_context.source, "source")?,
+    };
+    if cleaned.location_name.is_none() && cleaned.device.is_none() && cleaned.source.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(json!(cleaned)))
+}
+
+// Valide la tâche de suivi configurée sur la tâche
+fn follow_up_to_json(follow_up_config: FollowUpConfig) -> Result<serde_json::Value, ServiceError> {
+    automations::validate_follow_up(&follow_up_config.title, follow_up_config.due_in_days)?;
+    Ok(json!(FollowUpConfig {
+        title: follow_up_config.title.trim().to_string(),
+        due_in_days: follow_up_config.due_in_days,
+    }))
+}
+
+// Seuil de similarité trigramme (0..1) au-delà duquel une tâche ouverte est un doublon probable
+const DUPLICATE_SIMILARITY_THRESHOLD: f32 = 0.5;
+const MAX_DUPLICATE_CANDIDATES: i64 = 5;
+
+// Nombre maximum de tâches épinglées par utilisateur
+const MAX_PINNED_TASKS: i64 = 10;
+
+// Report maximal d'une tâche
+const MAX_SNOOZE_DAYS: i64 = 365;
+
+// Revue des tâches dormantes : ancienneté par défaut et maximale (en jours)
+const DEFAULT_STALE_DAYS: i64 = 30;
+const MAX_STALE_DAYS: i64 = 365;
+
+#[derive(Deserialize, Debug)]
+pub struct StaleTasksQueryParams {
+    pub days: Option<i64>,
+}
+
+// Nombre maximum de tâches par promotion, rétrogradation ou changement de statut groupé
+const MAX_BULK_STAGE_TASKS: usize = 200;
+
+// Changement de statut groupé : résultat par tâche et codes de refus
+const BULK_STATUS_REJECTED: &str = "BULK_STATUS_REJECTED";
+const BULK_TASK_NOT_FOUND: &str = "TASK_NOT_FOUND";
+const BULK_TASK_FORBIDDEN: &str = "FORBIDDEN";
+const BULK_OUTCOME_UPDATED: &str = "updated";
+const BULK_OUTCOME_UNCHANGED: &str = "unchanged";
+const BULK_OUTCOME_REJECTED: &str = "rejected";
+// Tâche acceptée, mais la transaction a été annulée à cause d'une autre
+const BULK_OUTCOME_NOT_APPLIED: &str = "not_applied";
+
+// Promotion et rétrogradation : tâches déplacées ou laissées en place
+const BULK_OUTCOME_MOVED: &str = "moved";
+const BULK_OUTCOME_SKIPPED: &str = "skipped";
+const BULK_TASK_NOT_IN_STAGE: &str = "TASK_NOT_IN_STAGE";
+
+// Recherche les tâches ouvertes de l'utilisateur dont le titre ressemble au titre donné
+async fn find_duplicate_candidates(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    candidate_title: &str,
+) -> Result<Vec<TaskDuplicateCandidate>, ServiceError> {
+    // L'opérateur % exploite l'index GIN trigramme, le seuil explicite affine le résultat
+    diesel::sql_query(
+        "SELECT id, title, status, project_id, similarity(title, $2) AS similarity \
+         FROM tasks \
+         WHERE user_id = $1 AND status <> ALL($3) \
+         AND title % $2 AND similarity(title, $2) >= $4 \
+         ORDER BY similarity DESC \
+         LIMIT $5",
+    )
+    .bind::<DieselUuid, _>(user_uuid)
+    .bind::<Text, _>(candidate_title)
+    .bind::<Array<Text>, _>(DONE_TASK_STATUSES.to_vec())
+    .bind::<Float4, _>(DUPLICATE_SIMILARITY_THRESHOLD)
+    .bind::<diesel::sql_types::BigInt, _>(MAX_DUPLICATE_CANDIDATES)
+    .load::<TaskDuplicateCandidate>(conn)
+    .await
+    .map_err(ServiceError::from)
+}
+
+// Les deux bornes du créneau vont ensemble et la fin suit le début
+fn validate_schedule(
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Result<(), ServiceError> {
+    match (start, end) {
+        (None, None) => Ok(()),
+        (Some(start), Some(end)) if end > start => Ok(()),
+        (Some(_), Some(_)) => Err(ServiceError::ValidationError(
+            "scheduled_end must be after scheduled_start".to_string(),
+        )),
+        _ => Err(ServiceError::ValidationError(
+            "scheduled_start and scheduled_end must be set together".to_string(),
+        )),
+    }
+}
+
+// Horizon de planification (voir TASK_STAGES), normalisé en minuscules
+pub fn parse_task_stage(value: &str) -> Result<String, ServiceError> {
+    let normalized = value.trim().to_ascii_lowercase();
+    if !TASK_STAGES.contains(&normalized.as_str()) {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid stage '{}'. Supported: {}",
+            value,
+            TASK_STAGES.join(", ")
+        )));
+    }
+    Ok(normalized)
+}
+
 #[post("")]
 pub async fn create_task_handler(
     pool: web::Data<DbPool>,
+    google_calendar: web::Data<Option<GoogleCalendarConfig>>,
     authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    query: web::Query<CreateTaskQueryParams>,
     payload: web::Json<CreateTaskPayload>,
 ) -> Result<HttpResponse, ServiceError> {
+    validate_schedule(payload.scheduled_start, payload.scheduled_end)?;
+    if let Some(new_due_date) = payload.due_date {
+        date_validation::validate_due_date(new_due_date, Utc::now().date_naive())?;
+    }
+    let new_task_stage = payload.stage.as_deref().map(parse_task_stage).transpose()?;
+    if let Some(budget_seconds) = payload.time_budget_seconds {
+        time_budgets::validate_time_budget(budget_seconds)?;
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Sans project_id : projet par défaut des préférences, sinon la tâche va dans l'Inbox
+    let new_task_project_id = match payload.project_id {
+        Some(project_uuid) => Some(project_uuid),
+        None => settings::default_project_for_new_task(&mut conn, authenticated_user.id).await?,
+    };
+
     let new_task_data = NewTask {
         user_id: authenticated_user.id,
-        project_id: payload.project_id,
+        project_id: new_task_project_id,
         title: payload.title.clone(),
         description: payload.description.clone(),
         status: payload.status.clone(),
         due_date: payload.due_date,
         order: payload.order,
+        source: None,
+        context: payload
+            .context
+            .clone()
+            .map(context_to_json)
+            .transpose()?
+            .flatten(),
+        scheduled_start: payload.scheduled_start,
+        scheduled_end: payload.scheduled_end,
+        follow_up: payload
+            .follow_up
+            .clone()
+            .map(follow_up_to_json)
+            .transpose()?,
+        stage: new_task_stage,
+        time_budget_seconds: payload.time_budget_seconds,
     };
 
-    // Obtenir une connexion du pool
-    let mut conn = pool.get().await?;
+    // Créer dans un projet (personnel ou partagé) exige le droit d'écriture
+    if let Some(project_uuid) = new_task_project_id {
+        permissions::require_project(
+            &mut conn,
+            authenticated_user.id,
+            project_uuid,
+            Permission::ProjectWrite,
+        )
+        .await?;
+        if !query.override_wip_limit {
+            let target_status = payload
+                .status
+                .as_deref()
+                .unwrap_or(wip_limits::DEFAULT_TASK_STATUS);
+            wip_limits::ensure_capacity(&mut conn, project_uuid, target_status, None).await?;
+        }
+    }
+
+    // Détection de doublons optionnelle : 409 avec les candidats au lieu de créer
+    if query.check_duplicates {
+        cost_limiter.charge(authenticated_user.id, CostClass::Search)?;
+        let candidates =
+            find_duplicate_candidates(&mut conn, authenticated_user.id, &payload.title).await?;
+        if !candidates.is_empty() {
+            log::info!(
+                "Task creation for user {} blocked: {} similar open task(s) for '{}'",
+                authenticated_user.id,
+                candidates.len(),
+                payload.title
+            );
+            return Ok(HttpResponse::Conflict().json(json!({
+                "status": "error",
+                "code": 409,
+                "message": "Similar open tasks already exist. Retry without check_duplicates to create anyway.",
+                "candidates": candidates
+            })));
+        }
+    }
 
     // Exécuter la requête de manière async
-    let task = diesel::insert_into(tasks::table)
+    let mut task = diesel::insert_into(tasks::table)
         .values(&new_task_data)
         .get_result::<Task>(&mut conn)
         .await
         .map_err(ServiceError::from)?;
 
+    // Références [[task:uuid]] de la description
+    if task.description.is_some() {
+        mentions::sync_task_links(
+            &mut conn,
+            authenticated_user.id,
+            task.id,
+            task.description.as_deref(),
+        )
+        .await?;
+    }
+
+    // Règles d'automatisation ; recharger la tâche si une action l'a modifiée
+    let automation_outcome = automations::run_automations(
+        &mut conn,
+        AutomationEvent::for_task(AutomationTrigger::TaskCreated, task.id),
+    )
+    .await;
+    if automation_outcome.applied > 0 {
+        task = tasks.find(task.id).first::<Task>(&mut conn).await?;
+    }
+
+    events::emit(
+        DomainEvent::new(events::EVENT_TASK_CREATED, authenticated_user.id)
+            .with_task(task.id, task.project_id)
+            .with_properties(json!({
+                "status": task.status,
+                "stage": task.stage,
+                "has_due_date": task.due_date.is_some(),
+                "is_scheduled": task.scheduled_start.is_some(),
+            })),
+    );
+
+    if task.scheduled_start.is_some() {
+        integrations::spawn_task_schedule_sync(
+            pool.get_ref().clone(),
+            google_calendar.get_ref().clone(),
+            task.user_id,
+            task.id,
+        );
+    }
+
     // Convertir en TaskApiResponse (sans labels pour l'instant)
-    let task_response = TaskApiResponse::from(task);
+    let mut task_response = TaskApiResponse::from(task);
+    time_budgets::attach_budget_usage(&mut conn, std::slice::from_mut(&mut task_response)).await?;
+    task_response.follow_up_tasks = automation_outcome
+        .follow_up_tasks
+        .into_iter()
+        .map(TaskApiResponse::from)
+        .collect();
 
     Ok(HttpResponse::Created().json(task_response))
 }
@@ -61,28 +419,68 @@ pub async fn create_task_handler(
 pub async fn list_tasks_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
     query: web::Query<TaskQueryParams>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
+    let with_pomodoros = expands_pomodoros(query.expand.as_deref())?;
+
+    // Les filtres sur le contexte (JSONB) et les champs personnalisés sont des recherches
+    // coûteuses, décomptées du budget ; la liste simple ne l'est pas
+    let is_search = query.custom_field_id.is_some()
+        || query.context_source.is_some()
+        || query.context_device.is_some()
+        || query.context_location_name.is_some();
+    if is_search {
+        cost_limiter.charge(user_uuid, CostClass::Search)?;
+    }
 
     // Paramètres de pagination
     let page = query.page.unwrap_or(1);
     let per_page = query.per_page.unwrap_or(10);
     let offset = (page - 1) * per_page;
 
-    // Obtenir une connexion du pool
-    let mut conn = pool.get().await?;
+    // Deux connexions : le total et la page sont chargés en parallèle
+    let (mut conn, mut count_conn) = repository::get_connection_pair(&pool).await?;
 
     // Construire la requête de base pour compter le total
-    let mut count_query = tasks.filter(user_id.eq(user_uuid)).into_boxed();
+    let mut count_query = tasks.into_boxed();
 
     // Construire la requête principale
-    let mut query_builder = tasks.filter(user_id.eq(user_uuid)).into_boxed();
+    let mut query_builder = tasks.into_boxed();
+
+    let inbox_view = match query.view.as_deref() {
+        None => false,
+        Some(TASK_VIEW_INBOX) => true,
+        Some(other) => {
+            return Err(ServiceError::ValidationError(format!(
+                "Invalid view '{}'. Supported: {}",
+                other, TASK_VIEW_INBOX
+            )))
+        }
+    };
+    if inbox_view && query.project_id.is_some() {
+        return Err(ServiceError::ValidationError(
+            "view=inbox cannot be combined with project_id".to_string(),
+        ));
+    }
 
-    // Filtrer par projet si spécifié
+    // Filtrer par projet si spécifié : toutes les tâches du projet s'il est accessible
+    // (projet partagé), sinon uniquement les tâches de l'utilisateur
     if let Some(project_uuid) = query.project_id {
+        permissions::require_project(&mut conn, user_uuid, project_uuid, Permission::ProjectRead)
+            .await?;
         query_builder = query_builder.filter(project_id.eq(project_uuid));
         count_query = count_query.filter(project_id.eq(project_uuid));
+    } else {
+        query_builder = query_builder.filter(user_id.eq(user_uuid));
+        count_query = count_query.filter(user_id.eq(user_uuid));
+    }
+
+    // Inbox : tâches créées sans projet (ni projet par défaut)
+    if inbox_view {
+        query_builder = query_builder.filter(project_id.is_null());
+        count_query = count_query.filter(project_id.is_null());
     }
 
     // Filtrer par statut si spécifié
@@ -91,39 +489,101 @@ pub async fn list_tasks_handler(
         count_query = count_query.filter(status.eq(task_status));
     }
 
-    // Compter le total d'éléments
-    let total_items = count_query
-        .count()
-        .get_result::<i64>(&mut conn)
-        .await
-        .map_err(ServiceError::from)?;
+    // Masquer les tâches reportées, sauf si ?include_snoozed=true
+    if !query.include_snoozed {
+        let now = Utc::now();
+        query_builder = query_builder.filter(snoozed_until.is_null().or(snoozed_until.le(now)));
+        count_query = count_query.filter(snoozed_until.is_null().or(snoozed_until.le(now)));
+    }
 
-    // Exécuter la requête principale avec pagination
-    let task_list = query_builder
-        .order(tasks::created_at.desc())
-        .limit(per_page)
-        .offset(offset)
-        .select(Task::as_select())
-        .load::<Task>(&mut conn)
-        .await
-        .map_err(ServiceError::from)?;
+    // Filtrer par horizon de planification si spécifié
+    if let Some(requested_stage) = &query.stage {
+        let requested_stage = parse_task_stage(requested_stage)?;
+        query_builder = query_builder.filter(stage.eq(requested_stage.clone()));
+        count_query = count_query.filter(stage.eq(requested_stage));
+    }
 
-    // Convertir les tâches en TaskApiResponse et récupérer les labels
-    let mut task_responses = Vec::new();
+    // Filtrer par contexte : toutes les clés demandées doivent correspondre
+    let mut context_filter = serde_json::Map::new();
+    for (key, value) in [
+        ("source", &query.context_source),
+        ("device", &query.context_device),
+        ("location_name", &query.context_location_name),
+    ] {
+        if let Some(value) = value {
+            context_filter.insert(key.to_string(), json!(value));
+        }
+    }
+    if !context_filter.is_empty() {
+        let context_filter = serde_json::Value::Object(context_filter);
+        query_builder = query_builder.filter(context.contains(context_filter.clone()));
+        count_query = count_query.filter(context.contains(context_filter));
+    }
 
-    for task in task_list {
-        // Récupérer les labels pour cette tâche
-        let task_labels_list = task_labels::table
-            .filter(task_labels::task_id.eq(task.id))
-            .inner_join(labels::table.on(labels::id.eq(task_labels::label_id)))
-            .select(Label::as_select())
-            .load::<Label>(&mut conn)
-            .await
-            .map_err(ServiceError::from)?;
+    // Filtrer par valeur de champ personnalisé si spécifié
+    match (query.custom_field_id, &query.custom_field_value) {
+        (Some(field_uuid), Some(raw_value)) => {
+            let field = custom_field_definitions::table
+                .filter(custom_field_definitions::id.eq(field_uuid))
+                .select(CustomFieldDefinition::as_select())
+                .first::<CustomFieldDefinition>(&mut conn)
+                .await
+                .optional()
+                .map_err(ServiceError::from)?
+                .ok_or_else(|| {
+                    ServiceError::NotFound(format!("Custom field with id {} not found", field_uuid))
+                })?;
+            permissions::require_project(
+                &mut conn,
+                user_uuid,
+                field.project_id,
+                Permission::ProjectRead,
+            )
+            .await?;
+            // Normaliser comme à l'écriture ("3" et "3.0" désignent le même nombre)
+            let stored_value = custom_fields::normalize_value(
+                &field,
+                &serde_json::Value::String(raw_value.clone()),
+            )?;
 
-        let mut task_response = TaskApiResponse::from(task);
-        task_response.labels = task_labels_list;
-        task_responses.push(task_response);
+            let matching_tasks = || {
+                task_custom_values::table
+                    .filter(task_custom_values::field_id.eq(field_uuid))
+                    .filter(task_custom_values::value.eq(stored_value.clone()))
+                    .select(task_custom_values::task_id)
+            };
+            query_builder = query_builder.filter(id.eq_any(matching_tasks()));
+            count_query = count_query.filter(id.eq_any(matching_tasks()));
+        }
+        (None, None) => {}
+        _ => {
+            return Err(ServiceError::BadRequest(
+                "custom_field_id and custom_field_value must be provided together".to_string(),
+            ))
+        }
+    }
+
+    // Compter le total d'éléments et exécuter la requête principale avec pagination
+    let (total_items, task_list) = tokio::try_join!(
+        count_query.count().get_result::<i64>(&mut count_conn),
+        query_builder
+            .order((tasks::is_pinned.desc(), tasks::created_at.desc()))
+            .limit(per_page)
+            .offset(offset)
+            .select(Task::as_select())
+            .load::<Task>(&mut conn),
+    )
+    .map_err(ServiceError::from)?;
+    drop(count_conn);
+
+    // Convertir les tâches en TaskApiResponse et récupérer les labels
+    let mut task_responses: Vec<TaskApiResponse> =
+        task_list.into_iter().map(TaskApiResponse::from).collect();
+    repository::attach_labels(&mut conn, &mut task_responses).await?;
+    custom_fields::attach_custom_fields(&mut conn, &mut task_responses).await?;
+    time_budgets::attach_budget_usage(&mut conn, &mut task_responses).await?;
+    if with_pomodoros {
+        repository::attach_pomodoro_counts(&mut conn, user_uuid, &mut task_responses).await?;
     }
 
     let total_pages = (total_items + per_page - 1) / per_page;
@@ -136,108 +596,500 @@ pub async fn list_tasks_handler(
         per_page,
     };
 
-    Ok(HttpResponse::Ok().json(paginated_response))
+    Ok(pagination::paginated_response(paginated_response))
+}
+
+// === GET /tasks/stale ===
+// Revue hebdomadaire : tâches ouvertes ni modifiées ni suivies (time entries) depuis N jours,
+// les plus anciennes d'abord. Les tâches reportées en cours sont exclues.
+#[get("/stale")]
+pub async fn list_stale_tasks_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<StaleTasksQueryParams>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let days = query.days.unwrap_or(DEFAULT_STALE_DAYS);
+    if !(1..=MAX_STALE_DAYS).contains(&days) {
+        return Err(ServiceError::ValidationError(format!(
+            "days must be between 1 and {}",
+            MAX_STALE_DAYS
+        )));
+    }
+    let now = Utc::now();
+    let cutoff = now - ChronoDuration::days(days);
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Tâches avec du temps suivi depuis la date limite (timer en cours compris)
+    let recently_tracked = time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(
+            time_entries::end_time
+                .is_null()
+                .or(time_entries::end_time.ge(cutoff)),
+        )
+        .select(time_entries::task_id)
+        .distinct();
+
+    let stale_tasks = tasks
+        .filter(user_id.eq(user_uuid))
+        .filter(status.ne_all(DONE_TASK_STATUSES))
+        .filter(updated_at.lt(cutoff.naive_utc()))
+        .filter(snoozed_until.is_null().or(snoozed_until.le(now)))
+        .filter(diesel::dsl::not(id.eq_any(recently_tracked)))
+        .order(updated_at.asc())
+        .select(Task::as_select())
+        .load::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let mut task_responses: Vec<TaskApiResponse> =
+        stale_tasks.into_iter().map(TaskApiResponse::from).collect();
+    repository::attach_labels(&mut conn, &mut task_responses).await?;
+    custom_fields::attach_custom_fields(&mut conn, &mut task_responses).await?;
+
+    Ok(HttpResponse::Ok().json(task_responses))
 }
 
 #[get("/{task_id_path}")]
 pub async fn get_task_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    task_id_path: web::Path<Uuid>,
+    task_id_path: web::Path<TaskId>,
+    query: web::Query<GetTaskQueryParams>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let task_to_find_id = task_id_path.into_inner();
+    let with_pomodoros = expands_pomodoros(query.expand.as_deref())?;
+
+    // La tâche et ses labels sont chargés en parallèle sur deux connexions
+    let (mut conn, mut labels_conn) = repository::get_connection_pair(&pool).await?;
+
+    let (task, task_labels_list) = tokio::try_join!(
+        permissions::require_task(&mut conn, user_uuid, task_to_find_id, Permission::TaskRead),
+        repository::load_task_labels(&mut labels_conn, task_to_find_id),
+    )?;
+    drop(labels_conn);
+
+    let mut task_response = TaskApiResponse::from(task);
+    task_response.labels = task_labels_list;
+    custom_fields::attach_custom_fields(&mut conn, std::slice::from_mut(&mut task_response))
+        .await?;
+    time_budgets::attach_budget_usage(&mut conn, std::slice::from_mut(&mut task_response)).await?;
+    if with_pomodoros {
+        repository::attach_pomodoro_counts(
+            &mut conn,
+            user_uuid,
+            std::slice::from_mut(&mut task_response),
+        )
+        .await?;
+    }
+
+    Ok(HttpResponse::Ok().json(task_response))
+}
+
+// === GET /tasks/{task_id_path}/export ===
+// Document imprimable de la tâche (détails, champs personnalisés, temps suivi), en PDF.
+#[get("/{task_id_path}/export")]
+pub async fn export_task_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    task_id_path: web::Path<TaskId>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    cost_limiter.charge(user_uuid, CostClass::Export)?;
+    let task_to_export_id = task_id_path.into_inner();
+    let format = ExportFormat::parse(query.format.as_deref())?;
 
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
-    // Exécuter la requête de manière async
-    let task_option = tasks
-        .filter(user_id.eq(user_uuid))
-        .filter(id.eq(task_to_find_id))
-        .select(Task::as_select())
-        .first::<Task>(&mut conn)
+    let task = permissions::require_task(
+        &mut conn,
+        user_uuid,
+        task_to_export_id,
+        Permission::TaskRead,
+    )
+    .await?;
+    let document = exports::render_task_export(&mut conn, task).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}\"",
+                exports::export_filename("task", task_to_export_id, format)
+            ),
+        ))
+        .body(document))
+}
+
+// === GET /tasks/{task_id_path}/pomodoros ===
+// Pomodoros terminés de l'utilisateur sur la tâche, du plus récent au plus ancien
+#[get("/{task_id_path}/pomodoros")]
+pub async fn list_task_pomodoros_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let target_task_id = task_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Vérifier que la tâche est accessible à l'utilisateur
+    permissions::require_task(&mut conn, user_uuid, target_task_id, Permission::TaskRead).await?;
+
+    let sessions = time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::task_id.eq(target_task_id))
+        .filter(time_entries::is_pomodoro_session.eq(true))
+        .filter(time_entries::end_time.is_not_null())
+        .order(time_entries::start_time.desc())
+        .select(PomodoroSession::as_select())
+        .load::<PomodoroSession>(&mut conn)
         .await
-        .optional()
         .map_err(ServiceError::from)?;
 
-    match task_option {
-        Some(task) => {
-            // Récupérer les labels pour cette tâche
-            let task_labels_list = task_labels::table
-                .filter(task_labels::task_id.eq(task.id))
-                .inner_join(labels::table.on(labels::id.eq(task_labels::label_id)))
-                .select(Label::as_select())
-                .load::<Label>(&mut conn)
-                .await
-                .map_err(ServiceError::from)?;
+    let total_seconds = sessions
+        .iter()
+        .filter_map(|session| session.duration_seconds)
+        .map(i64::from)
+        .sum();
+    let session_ids: Vec<TimeEntryId> = sessions.iter().map(|session| session.id).collect();
+    let interruptions = pomodoro_interruptions::table
+        .filter(pomodoro_interruptions::time_entry_id.eq_any(&session_ids))
+        .order(pomodoro_interruptions::interrupted_at.asc())
+        .select(PomodoroInterruption::as_select())
+        .load::<PomodoroInterruption>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(TaskPomodoroHistoryResponse {
+        task_id: target_task_id,
+        count: sessions.len() as i64,
+        total_seconds,
+        sessions,
+        interruptions,
+    }))
+}
 
-            let mut task_response = TaskApiResponse::from(task);
-            task_response.labels = task_labels_list;
+// Suites externes d'une complétion (webhooks des règles, événement task_completed) : à
+// déclencher une fois la complétion validée, donc après le commit si l'appelant tient
+// une transaction
+#[must_use]
+pub struct CompletionEffects {
+    webhooks: Vec<automations::PendingWebhook>,
+    event: DomainEvent,
+}
 
-            Ok(HttpResponse::Ok().json(task_response))
+impl CompletionEffects {
+    pub fn dispatch(self) {
+        for webhook in self.webhooks {
+            webhook.spawn();
         }
-        None => Err(ServiceError::NotFound(format!(
-            "Task with id {} not found or not owned by user",
-            task_to_find_id
-        ))),
+        events::emit(self.event);
+    }
+}
+
+// Tâche passée à un statut terminé : crée la tâche de suivi configurée puis exécute
+// les règles task_completed. Renvoie la tâche (rechargée si une règle l'a modifiée),
+// les tâches de suivi créées et les suites externes, que l'appelant déclenche.
+pub async fn run_completion_hooks(
+    conn: &mut AsyncPgConnection,
+    completed_task: Task,
+) -> Result<(Task, Vec<TaskApiResponse>, CompletionEffects), ServiceError> {
+    let mut follow_up_tasks = Vec::new();
+    let follow_up_config = completed_task
+        .follow_up
+        .clone()
+        .and_then(|value| serde_json::from_value::<FollowUpConfig>(value).ok());
+    if let Some(follow_up_config) = follow_up_config {
+        follow_up_tasks.push(
+            automations::create_follow_up_task(
+                conn,
+                &completed_task,
+                &follow_up_config.title,
+                follow_up_config.due_in_days,
+                json!({ "type": "follow_up", "task_id": completed_task.id }),
+            )
+            .await?,
+        );
     }
+
+    let automation_outcome = automations::run_automations_deferred(
+        conn,
+        AutomationEvent::for_task(AutomationTrigger::TaskCompleted, completed_task.id),
+    )
+    .await;
+    follow_up_tasks.extend(automation_outcome.follow_up_tasks);
+    let effects = CompletionEffects {
+        webhooks: automation_outcome.webhooks,
+        event: DomainEvent::new(events::EVENT_TASK_COMPLETED, completed_task.user_id)
+            .with_task(completed_task.id, completed_task.project_id)
+            .with_properties(json!({
+                "status": completed_task.status,
+                "age_seconds": (Utc::now().naive_utc() - completed_task.created_at).num_seconds(),
+                "follow_up_tasks": follow_up_tasks.len(),
+            })),
+    };
+    let completed_task = if automation_outcome.applied > 0 {
+        tasks.find(completed_task.id).first::<Task>(conn).await?
+    } else {
+        completed_task
+    };
+
+    Ok((
+        completed_task,
+        follow_up_tasks
+            .into_iter()
+            .map(TaskApiResponse::from)
+            .collect(),
+        effects,
+    ))
 }
 
 #[put("/{task_id_path}")]
 pub async fn update_task_handler(
     pool: web::Data<DbPool>,
+    google_calendar: web::Data<Option<GoogleCalendarConfig>>,
     authenticated_user: AuthenticatedUser,
-    task_id_path: web::Path<Uuid>,
+    task_id_path: web::Path<TaskId>,
+    query: web::Query<UpdateTaskQueryParams>,
     payload: web::Json<UpdateTaskPayload>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let task_to_update_id = task_id_path.into_inner();
 
+    // Seule la nouvelle échéance est contrôlée : une ancienne valeur ne bloque pas l'édition
+    if let Some(Some(new_due_date)) = payload.due_date {
+        date_validation::validate_due_date(new_due_date, Utc::now().date_naive())?;
+    }
+    if let Some(Some(budget_seconds)) = payload.time_budget_seconds {
+        time_budgets::validate_time_budget(budget_seconds)?;
+    }
+
     let task_changes = UpdateTaskChangeset {
-        project_id: payload.project_id.clone(),
+        project_id: payload.project_id,
         title: payload.title.clone(),
         description: payload.description.clone(),
         status: payload.status.clone(),
-        due_date: payload.due_date.clone(),
-        order: payload.order.clone(),
+        due_date: payload.due_date,
+        order: payload.order,
+        context: match payload.context.clone() {
+            Some(Some(new_context)) => Some(context_to_json(new_context)?),
+            Some(None) => Some(None),
+            None => None,
+        },
+        scheduled_start: payload.scheduled_start,
+        scheduled_end: payload.scheduled_end,
+        follow_up: match payload.follow_up.clone() {
+            Some(Some(follow_up_config)) => Some(Some(follow_up_to_json(follow_up_config)?)),
+            Some(None) => Some(None),
+            None => None,
+        },
+        stage: payload.stage.as_deref().map(parse_task_stage).transpose()?,
+        time_budget_seconds: payload.time_budget_seconds,
+        // Nouveau budget : les seuils sont réévalués depuis zéro
+        time_budget_alerted_pct: payload.time_budget_seconds.map(|_| 0),
         updated_at: Some(Utc::now().naive_utc()),
     };
 
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
-    // Exécuter la requête de manière async
-    let updated_task = diesel::update(
-        tasks
-            .filter(id.eq(task_to_update_id))
-            .filter(user_id.eq(user_uuid)),
+    let current_task = permissions::require_task(
+        &mut conn,
+        user_uuid,
+        task_to_update_id,
+        Permission::TaskWrite,
     )
-    .set(&task_changes)
-    .get_result::<Task>(&mut conn)
-    .await
-    .map_err(ServiceError::from)?;
+    .await?;
 
-    // Récupérer les labels pour la tâche mise à jour
-    let task_labels_list = task_labels::table
-        .filter(task_labels::task_id.eq(updated_task.id))
-        .inner_join(labels::table.on(labels::id.eq(task_labels::label_id)))
-        .select(Label::as_select())
-        .load::<Label>(&mut conn)
+    // Créneau résultant de la mise à jour partielle
+    validate_schedule(
+        payload
+            .scheduled_start
+            .unwrap_or(current_task.scheduled_start),
+        payload.scheduled_end.unwrap_or(current_task.scheduled_end),
+    )?;
+
+    // Déplacer la tâche vers un autre projet exige d'y avoir le droit d'écriture
+    if let Some(Some(target_project)) = payload.project_id {
+        if current_task.project_id != Some(target_project) {
+            permissions::require_project(
+                &mut conn,
+                user_uuid,
+                target_project,
+                Permission::ProjectWrite,
+            )
+            .await?;
+        }
+    }
+
+    // Entrer dans une colonne (projet + statut) pleine exige ?override=true
+    let target_project = payload.project_id.unwrap_or(current_task.project_id);
+    let target_status = payload.status.as_deref().unwrap_or(&current_task.status);
+    let enters_column =
+        target_project != current_task.project_id || target_status != current_task.status;
+    if let (Some(project_uuid), true, false) =
+        (target_project, enters_column, query.override_wip_limit)
+    {
+        wip_limits::ensure_capacity(
+            &mut conn,
+            project_uuid,
+            target_status,
+            Some(current_task.id),
+        )
+        .await?;
+    }
+
+    // Statut précédent, pour notifier les observateurs d'un changement
+    let previous_status = payload.status.as_ref().map(|_| current_task.status.clone());
+
+    // Exécuter la requête de manière async
+    let mut updated_task = diesel::update(tasks.find(task_to_update_id))
+        .set(&task_changes)
+        .get_result::<Task>(&mut conn)
         .await
         .map_err(ServiceError::from)?;
 
+    if let Some(old_status) = previous_status.filter(|old| *old != updated_task.status) {
+        notifications::notify_task_watchers(
+            &mut conn,
+            TaskActivity::status_changed(
+                user_uuid,
+                updated_task.id,
+                &updated_task.title,
+                &old_status,
+                &updated_task.status,
+            ),
+        )
+        .await?;
+    }
+
+    // Un budget réduit peut être déjà entamé au-delà d'un seuil
+    if payload.time_budget_seconds.is_some() {
+        time_budgets::check_time_budget(&mut conn, updated_task.id).await;
+    }
+
+    // Les champs personnalisés sont propres au projet : retirer ceux de l'ancien projet
+    if payload.project_id.is_some() {
+        let current_project_fields = custom_field_definitions::table
+            .filter(
+                custom_field_definitions::project_id
+                    .nullable()
+                    .eq(updated_task.project_id),
+            )
+            .select(custom_field_definitions::id);
+        diesel::delete(
+            task_custom_values::table
+                .filter(task_custom_values::task_id.eq(updated_task.id))
+                .filter(task_custom_values::field_id.ne_all(current_project_fields)),
+        )
+        .execute(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+    }
+
+    // Resynchroniser les références [[task:uuid]] si la description a changé
+    if payload.description.is_some() {
+        mentions::sync_task_links(
+            &mut conn,
+            user_uuid,
+            updated_task.id,
+            updated_task.description.as_deref(),
+        )
+        .await?;
+    }
+
+    // L'événement du calendrier reprend le titre, la description et le créneau
+    let touches_calendar_event = payload.scheduled_start.is_some()
+        || payload.scheduled_end.is_some()
+        || (updated_task.scheduled_start.is_some()
+            && (payload.title.is_some() || payload.description.is_some()));
+    if touches_calendar_event {
+        integrations::spawn_task_schedule_sync(
+            pool.get_ref().clone(),
+            google_calendar.get_ref().clone(),
+            updated_task.user_id,
+            updated_task.id,
+        );
+    }
+
+    // Passage à un statut terminé : tâche de suivi et règles task_completed
+    let mut follow_up_tasks = Vec::new();
+    if !DONE_TASK_STATUSES.contains(&current_task.status.as_str())
+        && DONE_TASK_STATUSES.contains(&updated_task.status.as_str())
+    {
+        let effects;
+        (updated_task, follow_up_tasks, effects) =
+            run_completion_hooks(&mut conn, updated_task).await?;
+        effects.dispatch();
+    }
+
+    // Récupérer les labels pour la tâche mise à jour
+    let task_labels_list = repository::load_task_labels(&mut conn, updated_task.id).await?;
+
     let mut task_response = TaskApiResponse::from(updated_task);
     task_response.labels = task_labels_list;
+    task_response.follow_up_tasks = follow_up_tasks;
+    custom_fields::attach_custom_fields(&mut conn, std::slice::from_mut(&mut task_response))
+        .await?;
+    time_budgets::attach_budget_usage(&mut conn, std::slice::from_mut(&mut task_response)).await?;
 
     Ok(HttpResponse::Ok().json(task_response))
 }
 
+// === GET /tasks/{task_id_path}/backlinks ===
+// Tâches dont la description référence cette tâche via [[task:uuid]]
+#[get("/{task_id_path}/backlinks")]
+pub async fn list_task_backlinks_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let target_task_id = task_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Vérifier que la tâche est accessible à l'utilisateur
+    permissions::require_task(&mut conn, user_uuid, target_task_id, Permission::TaskRead).await?;
+
+    let referencing_tasks = task_links::table
+        .inner_join(tasks::table.on(tasks::id.eq(task_links::source_task_id)))
+        .filter(task_links::target_task_id.eq(target_task_id))
+        .filter(tasks::user_id.eq(user_uuid))
+        .order(tasks::updated_at.desc())
+        .select(Task::as_select())
+        .load::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let mut task_responses: Vec<TaskApiResponse> = referencing_tasks
+        .into_iter()
+        .map(TaskApiResponse::from)
+        .collect();
+    repository::attach_labels(&mut conn, &mut task_responses).await?;
+    custom_fields::attach_custom_fields(&mut conn, &mut task_responses).await?;
+
+    Ok(HttpResponse::Ok().json(task_responses))
+}
+
 #[delete("/{task_id_path}")]
 pub async fn delete_task_handler(
     pool: web::Data<DbPool>,
+    google_calendar: web::Data<Option<GoogleCalendarConfig>>,
     authenticated_user: AuthenticatedUser,
-    task_id_path: web::Path<Uuid>,
+    task_id_path: web::Path<TaskId>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let task_to_delete_id = task_id_path.into_inner();
@@ -245,6 +1097,14 @@ pub async fn delete_task_handler(
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
+    let task_to_delete = permissions::require_task(
+        &mut conn,
+        user_uuid,
+        task_to_delete_id,
+        Permission::TaskWrite,
+    )
+    .await?;
+
     // D'abord, supprimer les associations de labels
     diesel::delete(task_labels::table.filter(task_labels::task_id.eq(task_to_delete_id)))
         .execute(&mut conn)
@@ -252,16 +1112,26 @@ pub async fn delete_task_handler(
         .map_err(ServiceError::from)?;
 
     // Ensuite, supprimer la tâche
-    let num_deleted = diesel::delete(
-        tasks
-            .filter(user_id.eq(user_uuid))
-            .filter(id.eq(task_to_delete_id)),
-    )
-    .execute(&mut conn)
-    .await
-    .map_err(ServiceError::from)?;
+    let num_deleted = diesel::delete(tasks.find(task_to_delete_id))
+        .execute(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
 
     if num_deleted > 0 {
+        events::emit(
+            DomainEvent::new(events::EVENT_TASK_DELETED, user_uuid)
+                .with_task(task_to_delete_id, task_to_delete.project_id)
+                .with_properties(json!({ "status": task_to_delete.status })),
+        );
+        // La tâche n'existe plus : l'événement associé est supprimé du calendrier
+        if task_to_delete.scheduled_start.is_some() {
+            integrations::spawn_task_schedule_sync(
+                pool.get_ref().clone(),
+                google_calendar.get_ref().clone(),
+                task_to_delete.user_id,
+                task_to_delete_id,
+            );
+        }
         Ok(HttpResponse::Ok().json(json!({
             "status": "success",
             "message": format!("Task with id {} deleted successfully", task_to_delete_id)
@@ -278,7 +1148,7 @@ pub async fn delete_task_handler(
 pub async fn toggle_task_completion_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    task_id_path: web::Path<Uuid>,
+    task_id_path: web::Path<TaskId>,
 ) -> Result<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let task_to_toggle_id = task_id_path.into_inner();
@@ -287,24 +1157,13 @@ pub async fn toggle_task_completion_handler(
     let mut conn = pool.get().await?;
 
     // D'abord, récupérer la tâche pour connaître son statut actuel
-    let current_task = tasks
-        .filter(user_id.eq(user_uuid))
-        .filter(id.eq(task_to_toggle_id))
-        .select(Task::as_select())
-        .first::<Task>(&mut conn)
-        .await
-        .optional()
-        .map_err(ServiceError::from)?;
-
-    let task = match current_task {
-        Some(t) => t,
-        None => {
-            return Err(ServiceError::NotFound(format!(
-                "Task with id {} not found or not owned by user",
-                task_to_toggle_id
-            )))
-        }
-    };
+    let task = permissions::require_task(
+        &mut conn,
+        user_uuid,
+        task_to_toggle_id,
+        Permission::TaskWrite,
+    )
+    .await?;
 
     // Déterminer le nouveau statut
     let new_status = if task.status == "completed" {
@@ -320,31 +1179,616 @@ pub async fn toggle_task_completion_handler(
         status: Some(new_status),
         due_date: None,
         order: None,
+        context: None,
+        scheduled_start: None,
+        scheduled_end: None,
+        follow_up: None,
+        stage: None,
+        time_budget_seconds: None,
+        time_budget_alerted_pct: None,
         updated_at: Some(Utc::now().naive_utc()),
     };
 
     // Mettre à jour la tâche
-    let updated_task = diesel::update(
-        tasks
-            .filter(id.eq(task_to_toggle_id))
-            .filter(user_id.eq(user_uuid)),
+    let mut updated_task = diesel::update(tasks.find(task_to_toggle_id))
+        .set(&task_changes)
+        .get_result::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    notifications::notify_task_watchers(
+        &mut conn,
+        TaskActivity::status_changed(
+            user_uuid,
+            updated_task.id,
+            &updated_task.title,
+            &task.status,
+            &updated_task.status,
+        ),
     )
-    .set(&task_changes)
-    .get_result::<Task>(&mut conn)
-    .await
-    .map_err(ServiceError::from)?;
+    .await?;
+
+    let mut follow_up_tasks = Vec::new();
+    if updated_task.status == "completed" {
+        let effects;
+        (updated_task, follow_up_tasks, effects) =
+            run_completion_hooks(&mut conn, updated_task).await?;
+        effects.dispatch();
+    }
 
     // Récupérer les labels pour la tâche mise à jour
-    let task_labels_list = task_labels::table
-        .filter(task_labels::task_id.eq(updated_task.id))
-        .inner_join(labels::table.on(labels::id.eq(task_labels::label_id)))
-        .select(Label::as_select())
-        .load::<Label>(&mut conn)
+    let task_labels_list = repository::load_task_labels(&mut conn, updated_task.id).await?;
+
+    let mut task_response = TaskApiResponse::from(updated_task);
+    task_response.labels = task_labels_list;
+    task_response.follow_up_tasks = follow_up_tasks;
+    custom_fields::attach_custom_fields(&mut conn, std::slice::from_mut(&mut task_response))
+        .await?;
+    time_budgets::attach_budget_usage(&mut conn, std::slice::from_mut(&mut task_response)).await?;
+
+    Ok(HttpResponse::Ok().json(task_response))
+}
+
+// Refuse d'épingler une tâche de plus quand le propriétaire a atteint la limite ; la tâche
+// elle-même n'est pas comptée (également appelé par les automatisations)
+pub async fn ensure_pin_capacity(
+    conn: &mut AsyncPgConnection,
+    owner_uuid: Uuid,
+    task_uuid: TaskId,
+) -> Result<(), ServiceError> {
+    let pinned_count = tasks
+        .filter(user_id.eq(owner_uuid))
+        .filter(is_pinned.eq(true))
+        .filter(id.ne(task_uuid))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+    if pinned_count >= MAX_PINNED_TASKS {
+        return Err(ServiceError::ConflictError(format!(
+            "At most {} tasks can be pinned. Unpin a task before pinning another one.",
+            MAX_PINNED_TASKS
+        )));
+    }
+    Ok(())
+}
+
+// Épingle ou désépingle une tâche ; la limite s'applique aux tâches du propriétaire
+async fn set_task_pinned(
+    pool: &DbPool,
+    user_uuid: Uuid,
+    task_uuid: TaskId,
+    pinned: bool,
+) -> Result<HttpResponse, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    let task =
+        permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskWrite).await?;
+
+    if pinned && !task.is_pinned {
+        ensure_pin_capacity(&mut conn, task.user_id, task.id).await?;
+    }
+
+    let updated_task = diesel::update(tasks.find(task_uuid))
+        .set((is_pinned.eq(pinned), updated_at.eq(Utc::now().naive_utc())))
+        .get_result::<Task>(&mut conn)
         .await
         .map_err(ServiceError::from)?;
 
     let mut task_response = TaskApiResponse::from(updated_task);
-    task_response.labels = task_labels_list;
+    task_response.labels = repository::load_task_labels(&mut conn, task_response.id).await?;
+    custom_fields::attach_custom_fields(&mut conn, std::slice::from_mut(&mut task_response))
+        .await?;
+    time_budgets::attach_budget_usage(&mut conn, std::slice::from_mut(&mut task_response)).await?;
+
+    Ok(HttpResponse::Ok().json(task_response))
+}
+
+// === POST /tasks/{task_id_path}/pin ===
+#[post("/{task_id_path}/pin")]
+pub async fn pin_task_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    set_task_pinned(
+        &pool,
+        authenticated_user.id,
+        task_id_path.into_inner(),
+        true,
+    )
+    .await
+}
+
+// === POST /tasks/{task_id_path}/unpin ===
+#[post("/{task_id_path}/unpin")]
+pub async fn unpin_task_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    set_task_pinned(
+        &pool,
+        authenticated_user.id,
+        task_id_path.into_inner(),
+        false,
+    )
+    .await
+}
+
+// Date de fin du report : exactement un de until / duration_minutes, dans le futur
+fn resolve_snooze_until(payload: &SnoozeTaskPayload) -> Result<DateTime<Utc>, ServiceError> {
+    let now = Utc::now();
+    let until = match (payload.until, payload.duration_minutes) {
+        (Some(until), None) => until,
+        // Borné pour éviter un débordement ; une durée trop longue est rejetée plus bas
+        (None, Some(minutes)) if minutes > 0 => {
+            now + ChronoDuration::minutes(minutes.min((MAX_SNOOZE_DAYS + 1) * 24 * 60))
+        }
+        (None, Some(_)) => {
+            return Err(ServiceError::ValidationError(
+                "duration_minutes must be positive".to_string(),
+            ))
+        }
+        _ => {
+            return Err(ServiceError::ValidationError(
+                "Provide exactly one of until or duration_minutes".to_string(),
+            ))
+        }
+    };
+
+    if until <= now {
+        return Err(ServiceError::ValidationError(
+            "Snooze end must be in the future".to_string(),
+        ));
+    }
+    if until > now + ChronoDuration::days(MAX_SNOOZE_DAYS) {
+        return Err(ServiceError::ValidationError(format!(
+            "A task cannot be snoozed for more than {} days",
+            MAX_SNOOZE_DAYS
+        )));
+    }
+    Ok(until)
+}
+
+async fn set_task_snoozed_until(
+    pool: &DbPool,
+    user_uuid: Uuid,
+    task_uuid: TaskId,
+    until: Option<DateTime<Utc>>,
+) -> Result<HttpResponse, ServiceError> {
+    let mut conn = pool.get().await?;
+
+    permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskWrite).await?;
+
+    let updated_task = diesel::update(tasks.find(task_uuid))
+        .set((
+            snoozed_until.eq(until),
+            updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .get_result::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let mut task_response = TaskApiResponse::from(updated_task);
+    task_response.labels = repository::load_task_labels(&mut conn, task_response.id).await?;
+    custom_fields::attach_custom_fields(&mut conn, std::slice::from_mut(&mut task_response))
+        .await?;
+    time_budgets::attach_budget_usage(&mut conn, std::slice::from_mut(&mut task_response)).await?;
 
     Ok(HttpResponse::Ok().json(task_response))
 }
+
+// === POST /tasks/{task_id_path}/snooze ===
+// Masque la tâche des listes par défaut et de la vue du jour jusqu'à la date indiquée
+#[post("/{task_id_path}/snooze")]
+pub async fn snooze_task_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+    payload: web::Json<SnoozeTaskPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let until = resolve_snooze_until(&payload)?;
+    set_task_snoozed_until(
+        &pool,
+        authenticated_user.id,
+        task_id_path.into_inner(),
+        Some(until),
+    )
+    .await
+}
+
+// === POST /tasks/{task_id_path}/unsnooze ===
+#[post("/{task_id_path}/unsnooze")]
+pub async fn unsnooze_task_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    set_task_snoozed_until(
+        &pool,
+        authenticated_user.id,
+        task_id_path.into_inner(),
+        None,
+    )
+    .await
+}
+
+// === POST /tasks/{task_id_path}/still-relevant ===
+// Marque la tâche comme revue : elle sort de la file des tâches dormantes
+#[post("/{task_id_path}/still-relevant")]
+pub async fn mark_task_still_relevant_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let task_uuid = task_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskWrite).await?;
+
+    let touched_task = diesel::update(tasks.find(task_uuid))
+        .set(updated_at.eq(Utc::now().naive_utc()))
+        .get_result::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let mut task_response = TaskApiResponse::from(touched_task);
+    task_response.labels = repository::load_task_labels(&mut conn, task_response.id).await?;
+    custom_fields::attach_custom_fields(&mut conn, std::slice::from_mut(&mut task_response))
+        .await?;
+    time_budgets::attach_budget_usage(&mut conn, std::slice::from_mut(&mut task_response)).await?;
+
+    Ok(HttpResponse::Ok().json(task_response))
+}
+
+// Passe les tâches désignées de from_stage à to_stage ; celles dans un autre horizon sont ignorées
+// et signalées (réponse 207 si d'autres ont été déplacées, voir batch.rs)
+async fn move_tasks_between_stages(
+    pool: &DbPool,
+    user_uuid: Uuid,
+    task_uuids: Vec<TaskId>,
+    from_stage: &str,
+    to_stage: &str,
+    dry_run: bool,
+) -> Result<HttpResponse, ServiceError> {
+    if task_uuids.is_empty() || task_uuids.len() > MAX_BULK_STAGE_TASKS {
+        return Err(ServiceError::ValidationError(format!(
+            "task_ids must contain between 1 and {} tasks",
+            MAX_BULK_STAGE_TASKS
+        )));
+    }
+
+    let mut conn = pool.get().await?;
+
+    for task_uuid in &task_uuids {
+        permissions::require_task(&mut conn, user_uuid, *task_uuid, Permission::TaskWrite).await?;
+    }
+
+    let stage_filter_ids = &task_uuids;
+    let moved_ids = dry_run::transaction(&mut conn, dry_run, |conn| {
+        async move {
+            let moved_ids = diesel::update(
+                tasks
+                    .filter(id.eq_any(stage_filter_ids))
+                    .filter(stage.eq(from_stage)),
+            )
+            .set((stage.eq(to_stage), updated_at.eq(Utc::now().naive_utc())))
+            .returning(id)
+            .get_results::<TaskId>(conn)
+            .await?;
+            Ok(moved_ids)
+        }
+        .scope_boxed()
+    })
+    .await?;
+
+    // Une tâche déjà dans le stage cible (ou dans un autre) n'est pas déplacée
+    let results = task_uuids
+        .into_iter()
+        .enumerate()
+        .map(|(index, task_uuid)| {
+            let entity = BulkTaskRef {
+                task_id: task_uuid,
+                previous_status: None,
+            };
+            if moved_ids.contains(&task_uuid) {
+                BatchItemResult::succeeded(index, StatusCode::OK, BULK_OUTCOME_MOVED, entity)
+            } else {
+                BatchItemResult::failed(
+                    index,
+                    StatusCode::CONFLICT,
+                    BULK_OUTCOME_SKIPPED,
+                    BULK_TASK_NOT_IN_STAGE,
+                    format!("Task is not in the '{}' stage", from_stage),
+                    Some(entity),
+                )
+            }
+        })
+        .collect();
+
+    Ok(batch::respond(
+        StatusCode::OK,
+        json!({ "stage": to_stage, "dry_run": dry_run }),
+        BatchReport::new(results),
+    ))
+}
+
+// === POST /tasks/promote?dry_run= ===
+// Fait passer des tâches du backlog au board actif
+#[post("/promote")]
+pub async fn promote_tasks_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<DryRunQuery>,
+    payload: web::Json<BulkTaskStagePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    move_tasks_between_stages(
+        &pool,
+        authenticated_user.id,
+        payload.into_inner().task_ids,
+        TASK_STAGE_BACKLOG,
+        TASK_STAGE_ACTIVE,
+        query.dry_run,
+    )
+    .await
+}
+
+// === POST /tasks/demote?dry_run= ===
+// Renvoie des tâches actives au backlog
+#[post("/demote")]
+pub async fn demote_tasks_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<DryRunQuery>,
+    payload: web::Json<BulkTaskStagePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    move_tasks_between_stages(
+        &pool,
+        authenticated_user.id,
+        payload.into_inner().task_ids,
+        TASK_STAGE_ACTIVE,
+        TASK_STAGE_BACKLOG,
+        query.dry_run,
+    )
+    .await
+}
+
+// Motif d'un refus lors d'un changement de statut groupé ; les autres erreurs interrompent
+// la requête
+fn bulk_status_rejection(
+    index: usize,
+    task_uuid: TaskId,
+    previous_status: Option<String>,
+    error: ServiceError,
+) -> Result<BatchItemResult<BulkTaskRef>, ServiceError> {
+    let status_code = error.status_code();
+    let (code, message) = match error {
+        ServiceError::NotFound(message) => (BULK_TASK_NOT_FOUND, message),
+        ServiceError::Forbidden(permission) => (
+            BULK_TASK_FORBIDDEN,
+            format!("Missing permission {}", permission),
+        ),
+        ServiceError::CodedConflict(code, message) => (code, message),
+        other => return Err(other),
+    };
+    Ok(BatchItemResult::failed(
+        index,
+        status_code,
+        BULK_OUTCOME_REJECTED,
+        code,
+        message,
+        Some(BulkTaskRef {
+            task_id: task_uuid,
+            previous_status,
+        }),
+    ))
+}
+
+// === POST /tasks/status?override=&dry_run= ===
+// Passe toutes les tâches désignées au même statut, dans une seule transaction : si une
+// tâche est refusée (introuvable, droits, limite WIP), aucune n'est modifiée et la réponse
+// 409 détaille le résultat de chaque tâche
+#[post("/status")]
+pub async fn bulk_update_task_status_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<BulkTaskStatusQueryParams>,
+    payload: web::Json<BulkTaskStatusPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let BulkTaskStatusPayload {
+        task_ids: requested_ids,
+        status: new_status,
+    } = payload.into_inner();
+
+    let new_status = new_status.trim().to_string();
+    if new_status.is_empty() {
+        return Err(ServiceError::ValidationError(
+            "status cannot be empty".to_string(),
+        ));
+    }
+    let mut task_uuids: Vec<TaskId> = Vec::with_capacity(requested_ids.len());
+    for task_uuid in requested_ids {
+        if !task_uuids.contains(&task_uuid) {
+            task_uuids.push(task_uuid);
+        }
+    }
+    if task_uuids.is_empty() || task_uuids.len() > MAX_BULK_STAGE_TASKS {
+        return Err(ServiceError::ValidationError(format!(
+            "task_ids must contain between 1 and {} tasks",
+            MAX_BULK_STAGE_TASKS
+        )));
+    }
+    let override_wip_limit = query.override_wip_limit;
+    let dry_run = query.dry_run;
+
+    // Résultats remplis dans la transaction, conservés même si elle est annulée
+    let mut results: Vec<BatchItemResult<BulkTaskRef>> = Vec::with_capacity(task_uuids.len());
+    let results_ref = &mut results;
+    let target_status = new_status.as_str();
+
+    let transaction_result = {
+        // Obtenir une connexion du pool
+        let mut conn = pool.get().await?;
+
+        dry_run::transaction(&mut conn, dry_run, |conn| {
+            async move {
+                // 1. Contrôles et mise à jour, tâche par tâche : les tâches déjà passées au
+                // nouveau statut comptent dans la limite WIP des suivantes
+                let mut changed: Vec<(Task, String)> = Vec::new();
+                for (index, task_uuid) in task_uuids.iter().enumerate() {
+                    let task = match permissions::require_task(
+                        conn,
+                        user_uuid,
+                        *task_uuid,
+                        Permission::TaskWrite,
+                    )
+                    .await
+                    {
+                        Ok(task) => task,
+                        Err(e) => {
+                            results_ref.push(bulk_status_rejection(index, *task_uuid, None, e)?);
+                            continue;
+                        }
+                    };
+
+                    if task.status == target_status {
+                        results_ref.push(BatchItemResult::succeeded(
+                            index,
+                            StatusCode::OK,
+                            BULK_OUTCOME_UNCHANGED,
+                            BulkTaskRef {
+                                task_id: task.id,
+                                previous_status: Some(task.status),
+                            },
+                        ));
+                        continue;
+                    }
+
+                    if let (Some(project_uuid), false) = (task.project_id, override_wip_limit) {
+                        if let Err(e) = wip_limits::ensure_capacity(
+                            conn,
+                            project_uuid,
+                            target_status,
+                            Some(task.id),
+                        )
+                        .await
+                        {
+                            results_ref.push(bulk_status_rejection(
+                                index,
+                                task.id,
+                                Some(task.status),
+                                e,
+                            )?);
+                            continue;
+                        }
+                    }
+
+                    let updated_task = diesel::update(tasks.find(task.id))
+                        .set((
+                            status.eq(target_status),
+                            updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .get_result::<Task>(conn)
+                        .await?;
+                    results_ref.push(BatchItemResult::succeeded(
+                        index,
+                        StatusCode::OK,
+                        BULK_OUTCOME_UPDATED,
+                        BulkTaskRef {
+                            task_id: task.id,
+                            previous_status: Some(task.status.clone()),
+                        },
+                    ));
+                    changed.push((updated_task, task.status));
+                }
+
+                if results_ref.iter().any(|result| !result.is_success()) {
+                    return Err(ServiceError::CodedConflict(
+                        BULK_STATUS_REJECTED,
+                        "Some tasks cannot take this status; no task was changed".to_string(),
+                    ));
+                }
+
+                // 2. Suites du changement, une fois toutes les tâches acceptées ; une
+                // simulation s'arrête là (les suites publient des événements)
+                if dry_run {
+                    return Ok(Vec::new());
+                }
+                let mut completion_effects = Vec::new();
+                for (updated_task, old_status) in changed {
+                    notifications::notify_task_watchers(
+                        conn,
+                        TaskActivity::status_changed(
+                            user_uuid,
+                            updated_task.id,
+                            &updated_task.title,
+                            &old_status,
+                            &updated_task.status,
+                        ),
+                    )
+                    .await?;
+                    if !DONE_TASK_STATUSES.contains(&old_status.as_str())
+                        && DONE_TASK_STATUSES.contains(&updated_task.status.as_str())
+                    {
+                        let (_, _, effects) = run_completion_hooks(conn, updated_task).await?;
+                        completion_effects.push(effects);
+                    }
+                }
+                Ok(completion_effects)
+            }
+            .scope_boxed()
+        })
+        .await
+    };
+
+    match transaction_result {
+        Ok(completion_effects) => {
+            // Webhooks et événements une fois le commit passé
+            for effects in completion_effects {
+                effects.dispatch();
+            }
+            let updated_count = results
+                .iter()
+                .filter(|result| result.outcome == BULK_OUTCOME_UPDATED)
+                .count();
+            Ok(batch::respond(
+                StatusCode::OK,
+                json!({
+                    "status": new_status,
+                    "dry_run": dry_run,
+                    "updated_count": updated_count,
+                }),
+                BatchReport::new(results),
+            ))
+        }
+        Err(ServiceError::CodedConflict(BULK_STATUS_REJECTED, message)) => {
+            // Transaction annulée : les tâches acceptées ne sont pas modifiées non plus
+            for result in results
+                .iter_mut()
+                .filter(|result| result.outcome == BULK_OUTCOME_UPDATED)
+            {
+                result.status = StatusCode::FAILED_DEPENDENCY.as_u16();
+                result.outcome = BULK_OUTCOME_NOT_APPLIED;
+                result.error_code = Some(BULK_STATUS_REJECTED);
+                result.message = Some("Not applied because another task was rejected".to_string());
+            }
+            // Tout-ou-rien : aucune tâche n'a changé, la réponse reste un 409
+            Ok(batch::respond_with_status(
+                StatusCode::CONFLICT,
+                json!({
+                    "status": "error",
+                    "code": 409,
+                    "error_code": BULK_STATUS_REJECTED,
+                    "message": message,
+                    "dry_run": dry_run,
+                }),
+                BatchReport::new(results),
+            ))
+        }
+        Err(e) => Err(e),
+    }
+}
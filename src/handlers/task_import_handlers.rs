@@ -0,0 +1,492 @@
+// OptiTask/backend-api/src/handlers/task_import_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::batch::{self, BatchItemResult, BatchReport};
+use crate::db::DbPool;
+use crate::dry_run::{self, DryRunQuery};
+use crate::error_handler::ServiceError;
+use crate::ids::{LabelId, ProjectId, TaskId};
+use crate::mentions;
+use crate::models::{Label, NewLabel, NewProject, NewTask, NewTaskLabelAssociation, Project, Task};
+use crate::schema::{labels, projects, task_labels, tasks};
+use actix_multipart::Multipart;
+use actix_web::http::StatusCode;
+use actix_web::{post, web, HttpResponse, Result as ActixResult};
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// Taille maximale acceptée pour le fichier CSV (5 Mo)
+const MAX_IMPORT_FILE_BYTES: usize = 5 * 1024 * 1024;
+// Taille maximale acceptée pour le JSON de mapping
+const MAX_MAPPING_BYTES: usize = 16 * 1024;
+
+fn default_due_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+fn default_label_separator() -> String {
+    ",".to_string()
+}
+
+// Mapping entre les colonnes du CSV (noms d'en-têtes) et les champs d'une tâche
+#[derive(Deserialize, Debug)]
+pub struct ImportColumnMapping {
+    pub title: String,
+    pub description: Option<String>,
+    pub project: Option<String>,
+    pub labels: Option<String>,
+    pub status: Option<String>,
+    pub due_date: Option<String>,
+    // Format chrono (strftime), ex: "%d/%m/%Y"
+    #[serde(default = "default_due_date_format")]
+    pub due_date_format: String,
+    #[serde(default = "default_label_separator")]
+    pub label_separator: String,
+}
+
+const IMPORT_OUTCOME_IMPORTED: &str = "imported";
+// Ligne valide d'une simulation (dry_run)
+const IMPORT_OUTCOME_VALID: &str = "valid";
+const IMPORT_OUTCOME_ERROR: &str = "error";
+const INVALID_IMPORT_ROW: &str = "INVALID_IMPORT_ROW";
+
+// Ligne du CSV, entité d'un résultat d'import (voir batch.rs)
+#[derive(Serialize, Debug)]
+pub struct ImportRowReport {
+    pub row: u64,
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<TaskId>,
+    // Toutes les erreurs de la ligne (le message du résultat les réunit)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+// Champs propres à l'import, à côté des résultats par ligne
+#[derive(Serialize, Debug)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub created_projects: Vec<String>,
+    pub created_labels: Vec<String>,
+}
+
+// Ligne validée, prête à être insérée
+#[derive(Debug)]
+struct ParsedRow {
+    row: u64,
+    title: String,
+    description: Option<String>,
+    project_name: Option<String>,
+    label_names: Vec<String>,
+    status: Option<String>,
+    due_date: Option<NaiveDate>,
+}
+
+// Index des colonnes du CSV pour chaque champ mappé
+struct ColumnIndexes {
+    title: usize,
+    description: Option<usize>,
+    project: Option<usize>,
+    labels: Option<usize>,
+    status: Option<usize>,
+    due_date: Option<usize>,
+}
+
+fn resolve_columns(
+    headers: &csv::StringRecord,
+    mapping: &ImportColumnMapping,
+) -> Result<ColumnIndexes, ServiceError> {
+    let find = |column: &str| -> Result<usize, ServiceError> {
+        headers
+            .iter()
+            .position(|h| h.trim() == column.trim())
+            .ok_or_else(|| {
+                ServiceError::BadRequest(format!(
+                    "Mapped column '{}' not found in CSV header",
+                    column
+                ))
+            })
+    };
+    let find_opt = |column: &Option<String>| -> Result<Option<usize>, ServiceError> {
+        column.as_deref().map(find).transpose()
+    };
+
+    Ok(ColumnIndexes {
+        title: find(&mapping.title)?,
+        description: find_opt(&mapping.description)?,
+        project: find_opt(&mapping.project)?,
+        labels: find_opt(&mapping.labels)?,
+        status: find_opt(&mapping.status)?,
+        due_date: find_opt(&mapping.due_date)?,
+    })
+}
+
+// Valeur d'une cellule, None si absente ou vide
+fn cell(record: &csv::StringRecord, index: Option<usize>) -> Option<String> {
+    index
+        .and_then(|i| record.get(i))
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn parse_row(
+    record: &csv::StringRecord,
+    row: u64,
+    columns: &ColumnIndexes,
+    mapping: &ImportColumnMapping,
+) -> Result<ParsedRow, Vec<String>> {
+    let mut errors = Vec::new();
+
+    let title = cell(record, Some(columns.title));
+    if title.is_none() {
+        errors.push("Title is empty".to_string());
+    }
+
+    let due_date = match cell(record, columns.due_date) {
+        Some(raw) => match NaiveDate::parse_from_str(&raw, &mapping.due_date_format) {
+            Ok(date) => Some(date),
+            Err(_) => {
+                errors.push(format!(
+                    "Due date '{}' does not match format '{}'",
+                    raw, mapping.due_date_format
+                ));
+                None
+            }
+        },
+        None => None,
+    };
+
+    let label_names: Vec<String> = cell(record, columns.labels)
+        .map(|raw| {
+            raw.split(mapping.label_separator.as_str())
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(ParsedRow {
+        row,
+        title: title.unwrap_or_default(),
+        description: cell(record, columns.description),
+        project_name: cell(record, columns.project),
+        label_names,
+        status: cell(record, columns.status),
+        due_date,
+    })
+}
+
+// Lit les champs "file" et "mapping" du formulaire multipart
+async fn read_multipart(
+    mut payload: Multipart,
+) -> Result<(Vec<u8>, ImportColumnMapping), ServiceError> {
+    let mut file_bytes: Option<Vec<u8>> = None;
+    let mut mapping_bytes: Option<Vec<u8>> = None;
+
+    while let Some(mut field) = payload.try_next().await? {
+        let field_name = field
+            .content_disposition()
+            .and_then(|cd| cd.get_name())
+            .map(str::to_string);
+
+        let max_bytes = match field_name.as_deref() {
+            Some("file") => MAX_IMPORT_FILE_BYTES,
+            Some("mapping") => MAX_MAPPING_BYTES,
+            _ => continue,
+        };
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.try_next().await? {
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(ServiceError::BadRequest(format!(
+                    "Multipart field '{}' exceeds the maximum size of {} bytes",
+                    field_name.unwrap_or_default(),
+                    max_bytes
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        match field_name.as_deref() {
+            Some("file") => file_bytes = Some(bytes),
+            _ => mapping_bytes = Some(bytes),
+        }
+    }
+
+    let file_bytes = file_bytes
+        .ok_or_else(|| ServiceError::BadRequest("Missing multipart field 'file'".to_string()))?;
+    let mapping_bytes = mapping_bytes
+        .ok_or_else(|| ServiceError::BadRequest("Missing multipart field 'mapping'".to_string()))?;
+    let mapping = serde_json::from_slice::<ImportColumnMapping>(&mapping_bytes)?;
+
+    Ok((file_bytes, mapping))
+}
+
+// Insère les lignes valides, en créant les projets et labels manquants
+async fn import_rows(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    parsed_rows: &[ParsedRow],
+) -> Result<(Vec<String>, Vec<String>, HashMap<u64, TaskId>), ServiceError> {
+    // Projets et labels existants, indexés par nom en minuscules
+    let mut project_ids: HashMap<String, ProjectId> = projects::table
+        .filter(projects::user_id.eq(user_uuid))
+        .select(Project::as_select())
+        .load::<Project>(conn)
+        .await?
+        .into_iter()
+        .map(|p| (p.name.to_lowercase(), p.id))
+        .collect();
+    let mut label_ids: HashMap<String, LabelId> = labels::table
+        .filter(labels::user_id.eq(user_uuid))
+        .select(Label::as_select())
+        .load::<Label>(conn)
+        .await?
+        .into_iter()
+        .map(|l| (l.name.to_lowercase(), l.id))
+        .collect();
+
+    let mut created_projects = Vec::new();
+    let mut created_labels = Vec::new();
+    let mut task_ids = HashMap::new();
+
+    for parsed in parsed_rows {
+        if let Some(project_name) = &parsed.project_name {
+            let key = project_name.to_lowercase();
+            if let Entry::Vacant(slot) = project_ids.entry(key) {
+                let new_id = diesel::insert_into(projects::table)
+                    .values(&NewProject {
+                        user_id: user_uuid,
+                        name: project_name.clone(),
+                        color: None,
+                        workspace_id: None,
+                        icon: None,
+                        wip_limits: None,
+                        sla_hours: None,
+                        currency: None,
+                        theme: None,
+                    })
+                    .get_result::<Project>(conn)
+                    .await?
+                    .id;
+                slot.insert(new_id);
+                created_projects.push(project_name.clone());
+            }
+        }
+
+        for label_name in &parsed.label_names {
+            let key = label_name.to_lowercase();
+            if let Entry::Vacant(slot) = label_ids.entry(key) {
+                let new_id = diesel::insert_into(labels::table)
+                    .values(&NewLabel {
+                        user_id: user_uuid,
+                        name: label_name.clone(),
+                        color: None,
+                        icon: None,
+                        sla_hours: None,
+                    })
+                    .get_result::<Label>(conn)
+                    .await?
+                    .id;
+                slot.insert(new_id);
+                created_labels.push(label_name.clone());
+            }
+        }
+
+        let task = diesel::insert_into(tasks::table)
+            .values(&NewTask {
+                user_id: user_uuid,
+                project_id: parsed
+                    .project_name
+                    .as_ref()
+                    .and_then(|name| project_ids.get(&name.to_lowercase()).copied()),
+                title: parsed.title.clone(),
+                description: parsed.description.clone(),
+                status: parsed.status.clone(),
+                due_date: parsed.due_date,
+                order: None,
+                context: None,
+                scheduled_start: None,
+                scheduled_end: None,
+                follow_up: None,
+                stage: None,
+                time_budget_seconds: None,
+                source: None,
+            })
+            .get_result::<Task>(conn)
+            .await?;
+
+        let mut associated_label_ids: Vec<LabelId> = parsed
+            .label_names
+            .iter()
+            .filter_map(|name| label_ids.get(&name.to_lowercase()).copied())
+            .collect();
+        associated_label_ids.sort();
+        associated_label_ids.dedup();
+
+        let associations: Vec<NewTaskLabelAssociation> = associated_label_ids
+            .into_iter()
+            .map(|label_id| NewTaskLabelAssociation {
+                task_id: task.id,
+                label_id,
+            })
+            .collect();
+
+        if !associations.is_empty() {
+            diesel::insert_into(task_labels::table)
+                .values(&associations)
+                .execute(conn)
+                .await?;
+        }
+
+        if task.description.is_some() {
+            mentions::sync_task_links(conn, user_uuid, task.id, task.description.as_deref())
+                .await?;
+        }
+
+        task_ids.insert(parsed.row, task.id);
+    }
+
+    Ok((created_projects, created_labels, task_ids))
+}
+
+// === POST /tasks/import?dry_run= ===
+// Multipart : "file" (CSV avec en-têtes) + "mapping" (JSON ImportColumnMapping).
+// Les lignes invalides sont ignorées et rapportées ; ?dry_run=true importe puis annule
+// la transaction (contraintes de la base comprises) et ne renvoie que le rapport.
+#[post("/import")]
+pub async fn import_tasks_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<DryRunQuery>,
+    payload: Multipart,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let dry_run = query.dry_run;
+
+    let (file_bytes, mapping) = read_multipart(payload).await?;
+    log::info!(
+        "User {} importing tasks from CSV ({} bytes, dry_run={}) with mapping: {:?}",
+        user_uuid,
+        file_bytes.len(),
+        dry_run,
+        mapping
+    );
+
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(file_bytes.as_slice());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| ServiceError::BadRequest(format!("Could not read CSV header: {}", e)))?
+        .clone();
+    let columns = resolve_columns(&headers, &mapping)?;
+
+    let mut row_reports = Vec::new();
+    let mut parsed_rows = Vec::new();
+
+    for record_result in reader.records() {
+        match record_result {
+            Ok(record) => {
+                let row = record.position().map(|p| p.line()).unwrap_or_default();
+                match parse_row(&record, row, &columns, &mapping) {
+                    Ok(parsed) => parsed_rows.push(parsed),
+                    Err(errors) => row_reports.push(ImportRowReport {
+                        row,
+                        title: cell(&record, Some(columns.title)),
+                        task_id: None,
+                        errors,
+                    }),
+                }
+            }
+            Err(e) => row_reports.push(ImportRowReport {
+                row: e.position().map(|p| p.line()).unwrap_or_default(),
+                title: None,
+                task_id: None,
+                errors: vec![format!("Malformed CSV row: {}", e)],
+            }),
+        }
+    }
+
+    // Toutes les insertions dans une seule transaction (la connexion est libérée à la fin du bloc)
+    let (created_projects, created_labels, task_ids) = {
+        let mut conn = pool.get().await?;
+        let rows_to_import = &parsed_rows;
+        dry_run::transaction(&mut conn, dry_run, |conn| {
+            async move { import_rows(conn, user_uuid, rows_to_import).await }.scope_boxed()
+        })
+        .await?
+    };
+
+    row_reports.extend(parsed_rows.into_iter().map(|parsed| ImportRowReport {
+        row: parsed.row,
+        // En simulation, les identifiants n'ont pas survécu à l'annulation
+        task_id: task_ids.get(&parsed.row).copied().filter(|_| !dry_run),
+        title: Some(parsed.title),
+        errors: Vec::new(),
+    }));
+    row_reports.sort_by_key(|report| report.row);
+
+    let report = ImportReport {
+        dry_run,
+        total_rows: row_reports.len(),
+        created_projects,
+        created_labels,
+    };
+    let results = BatchReport::new(
+        row_reports
+            .into_iter()
+            .enumerate()
+            .map(|(index, row_report)| {
+                if row_report.errors.is_empty() {
+                    let (status, outcome) = if dry_run {
+                        (StatusCode::OK, IMPORT_OUTCOME_VALID)
+                    } else {
+                        (StatusCode::CREATED, IMPORT_OUTCOME_IMPORTED)
+                    };
+                    BatchItemResult::succeeded(index, status, outcome, row_report)
+                } else {
+                    BatchItemResult::failed(
+                        index,
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        IMPORT_OUTCOME_ERROR,
+                        INVALID_IMPORT_ROW,
+                        row_report.errors.join("; "),
+                        Some(row_report),
+                    )
+                }
+            })
+            .collect(),
+    );
+
+    log::info!(
+        "CSV import for user {}: {} rows, {} valid, {} failed (dry_run={})",
+        user_uuid,
+        report.total_rows,
+        results.succeeded,
+        results.failed,
+        dry_run
+    );
+
+    // 201 si des tâches ont été créées, 207 si des lignes ont échoué à côté
+    let success_status = if dry_run || results.succeeded == 0 {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+    Ok(batch::respond(success_status, report, results))
+}
@@ -1,8 +1,12 @@
 use crate::auth_utils::AuthenticatedUser;
+use crate::automations::{self, AutomationEvent};
 use crate::db::DbPool;
 use crate::error_handler::ServiceError;
-use crate::models::{Label, NewTaskLabelAssociation}; // TaskLabel pour la suppression, Label pour le listage
-use crate::schema::{labels, task_labels, tasks}; // tasks est nécessaire pour vérifier la propriété de la tâche
+use crate::events::{self, DomainEvent};
+use crate::ids::{LabelId, TaskId};
+use crate::models::{AutomationTrigger, Label, NewTaskLabelAssociation}; // TaskLabel pour la suppression, Label pour le listage
+use crate::permissions::{self, Permission};
+use crate::schema::{labels, task_labels};
 use actix_web::{delete, get, post, web, HttpResponse, Result as ActixResult};
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl; // Import async version
@@ -13,7 +17,7 @@ use uuid::Uuid;
 // DTO pour le payload de POST /tasks/{taskId}/labels
 #[derive(Deserialize, Debug)]
 pub struct AddLabelToTaskPayload {
-    pub label_id: Uuid,
+    pub label_id: LabelId,
 }
 
 // === POST /tasks/{task_id_path}/labels ===
@@ -22,7 +26,7 @@ pub struct AddLabelToTaskPayload {
 pub async fn add_label_to_task_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    path_params: web::Path<(Uuid,)>, // web::Path attend un tuple pour un seul paramètre, ou une struct
+    path_params: web::Path<(TaskId,)>, // web::Path attend un tuple pour un seul paramètre, ou une struct
     payload: web::Json<AddLabelToTaskPayload>,
 ) -> ActixResult<HttpResponse, ServiceError> {
     let (task_id_from_path,) = path_params.into_inner(); // Extrait l'UUID du tuple
@@ -39,22 +43,14 @@ pub async fn add_label_to_task_handler(
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
-    // 1. Vérifier que la tâche appartient à l'utilisateur
-    let _task_check = tasks::table
-        .filter(tasks::id.eq(task_id_from_path))
-        .filter(tasks::user_id.eq(user_uuid))
-        .select(tasks::id)
-        .first::<Uuid>(&mut conn)
-        .await
-        .optional()
-        .map_err(ServiceError::from)?;
-
-    if _task_check.is_none() {
-        return Err(ServiceError::NotFound(format!(
-            "Task with id {} not found or not owned by user",
-            task_id_from_path
-        )));
-    }
+    // 1. Vérifier que l'utilisateur peut modifier la tâche
+    permissions::require_task(
+        &mut conn,
+        user_uuid,
+        task_id_from_path,
+        Permission::TaskWrite,
+    )
+    .await?;
 
     // 2. Vérifier que le label appartient à l'utilisateur (ou est public, si vous avez cette notion)
     let _label_check = labels::table
@@ -104,6 +100,23 @@ pub async fn add_label_to_task_handler(
         .await
         .map_err(ServiceError::from)?;
 
+    // Règles d'automatisation label_added
+    automations::run_automations(
+        &mut conn,
+        AutomationEvent {
+            trigger: AutomationTrigger::LabelAdded,
+            task_id: task_id_from_path,
+            label_id: Some(label_to_add_id),
+        },
+    )
+    .await;
+
+    events::emit(
+        DomainEvent::new(events::EVENT_LABEL_ADDED, user_uuid)
+            .with_task(task_id_from_path, None)
+            .with_properties(json!({ "label_id": label_to_add_id })),
+    );
+
     Ok(HttpResponse::Created().json(json!({
         "status": "success",
         "message": "Label added to task successfully",
@@ -118,7 +131,7 @@ pub async fn add_label_to_task_handler(
 pub async fn list_labels_for_task_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    path_params: web::Path<(Uuid,)>,
+    path_params: web::Path<(TaskId,)>,
 ) -> ActixResult<HttpResponse, ServiceError> {
     let (task_id_from_path,) = path_params.into_inner();
     let user_uuid = authenticated_user.id;
@@ -132,22 +145,14 @@ pub async fn list_labels_for_task_handler(
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
-    // 1. Vérifier que la tâche appartient à l'utilisateur
-    let _task_check = tasks::table
-        .filter(tasks::id.eq(task_id_from_path))
-        .filter(tasks::user_id.eq(user_uuid))
-        .select(tasks::id)
-        .first::<Uuid>(&mut conn)
-        .await
-        .optional()
-        .map_err(ServiceError::from)?;
-
-    if _task_check.is_none() {
-        return Err(ServiceError::NotFound(format!(
-            "Task with id {} not found or not owned by user",
-            task_id_from_path
-        )));
-    }
+    // 1. Vérifier que l'utilisateur peut lire la tâche
+    permissions::require_task(
+        &mut conn,
+        user_uuid,
+        task_id_from_path,
+        Permission::TaskRead,
+    )
+    .await?;
 
     // 2. Récupérer les labels associés
     // Utilise une jointure implicite ou explicite
@@ -168,7 +173,7 @@ pub async fn list_labels_for_task_handler(
 pub async fn remove_label_from_task_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    path_params: web::Path<(Uuid, Uuid)>, // Tuple pour task_id et label_id
+    path_params: web::Path<(TaskId, LabelId)>, // Tuple pour task_id et label_id
 ) -> ActixResult<HttpResponse, ServiceError> {
     let (task_id_from_path, label_id_to_remove) = path_params.into_inner();
     let user_uuid = authenticated_user.id;
@@ -183,24 +188,16 @@ pub async fn remove_label_from_task_handler(
     // Obtenir une connexion du pool
     let mut conn = pool.get().await?;
 
-    // 1. Vérifier que la tâche appartient à l'utilisateur (important pour la sécurité)
-    // Ceci empêche un utilisateur de manipuler les labels d'une tâche qui ne lui appartient pas
+    // 1. Vérifier que l'utilisateur peut modifier la tâche (important pour la sécurité)
+    // Ceci empêche un utilisateur de manipuler les labels d'une tâche qui ne lui est pas accessible
     // même s'il connaît l'ID de la tâche et du label.
-    let _task_owner_check = tasks::table
-        .filter(tasks::id.eq(task_id_from_path))
-        .filter(tasks::user_id.eq(user_uuid))
-        .select(tasks::id)
-        .first::<Uuid>(&mut conn)
-        .await
-        .optional()
-        .map_err(ServiceError::from)?;
-
-    if _task_owner_check.is_none() {
-        return Err(ServiceError::NotFound(format!(
-            "Task with id {} not found or not owned by user",
-            task_id_from_path
-        )));
-    }
+    permissions::require_task(
+        &mut conn,
+        user_uuid,
+        task_id_from_path,
+        Permission::TaskWrite,
+    )
+    .await?;
 
     // 2. Supprimer l'association
     let num_deleted = diesel::delete(
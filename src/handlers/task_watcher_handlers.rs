@@ -0,0 +1,86 @@
+// OptiTask/backend-api/src/handlers/task_watcher_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::TaskId;
+use crate::permissions::{self, Permission};
+use crate::schema::task_watchers;
+use actix_web::{delete, post, web, HttpResponse};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+
+// === POST /tasks/{task_id_path}/watch ===
+// L'utilisateur courant suit l'activité de la tâche (idempotent)
+#[post("/{task_id_path}/watch")]
+pub async fn watch_task_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let task_uuid = task_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Suivre une tâche ne demande que de pouvoir la lire
+    permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskRead).await?;
+
+    let inserted = diesel::insert_into(task_watchers::table)
+        .values((
+            task_watchers::task_id.eq(task_uuid),
+            task_watchers::user_id.eq(user_uuid),
+        ))
+        .on_conflict_do_nothing()
+        .execute(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let message = if inserted > 0 {
+        "Task is now watched"
+    } else {
+        "Task already watched"
+    };
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": message,
+        "task_id": task_uuid
+    })))
+}
+
+// === DELETE /tasks/{task_id_path}/watch ===
+#[delete("/{task_id_path}/watch")]
+pub async fn unwatch_task_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    task_id_path: web::Path<TaskId>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let task_uuid = task_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let num_deleted = diesel::delete(
+        task_watchers::table
+            .filter(task_watchers::task_id.eq(task_uuid))
+            .filter(task_watchers::user_id.eq(user_uuid)),
+    )
+    .execute(&mut conn)
+    .await
+    .map_err(ServiceError::from)?;
+
+    if num_deleted > 0 {
+        Ok(HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": "Task is no longer watched",
+            "task_id": task_uuid
+        })))
+    } else {
+        Err(ServiceError::NotFound(format!(
+            "Task {} is not watched by user",
+            task_uuid
+        )))
+    }
+}
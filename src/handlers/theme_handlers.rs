@@ -0,0 +1,15 @@
+// OptiTask/backend-api/src/handlers/theme_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::error_handler::ServiceError;
+use crate::themes::THEME_PRESETS;
+use actix_web::{get, HttpResponse};
+use serde_json::json;
+
+// === GET /themes/presets ===
+// Thèmes prédéfinis applicables aux projets (champ theme.preset)
+#[get("/presets")]
+pub async fn list_theme_presets_handler(
+    _authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    Ok(HttpResponse::Ok().json(json!({ "presets": THEME_PRESETS })))
+}
@@ -1,21 +1,42 @@
 use crate::auth_utils::AuthenticatedUser;
+use crate::automations::{self, AutomationEvent};
+use crate::clock_skew::{self, ClockAssessment}; // Client clock skew detection
+use crate::date_validation; // Date sanity checks (structured 422s)
 use crate::db::DbPool;
 use crate::error_handler::ServiceError;
+use crate::events::{self, DomainEvent}; // Analytics domain events
+use crate::focus_sessions; // Active focus session tagging and scope guard
+use crate::ids::{TaskId, TimeEntryId};
+use crate::integrity::COMPUTED_DURATION_SQL; // end_time - start_time in whole seconds
 use crate::models::{
-    CreateTimeEntryPayload, NewTimeEntry, TimeEntry, UpdateTimeEntryChangeset,
-    UpdateTimeEntryPayload,
-};
-use crate::schema::{
-    tasks,                        // Import tasks for ownership verification
-    time_entries::{self, dsl::*}, // dsl::* for filters etc.
+    AutomationTrigger, CreateTimeEntryPayload, IdleAction, NewTimeEntry, TimeEntry,
+    TrimIdlePayload, UpdateTimeEntryChangeset, UpdateTimeEntryPayload,
 };
+use crate::permissions::{self, Permission}; // Task access verification
+use crate::schema::time_entries::{self, dsl::*}; // dsl::* for filters etc.
+use crate::settings::MAX_UTC_OFFSET_MINUTES; // Real-world UTC offsets lie within ±14h
+use crate::time_budgets; // Per-task time budget alerts
+use crate::timer_recovery::TIMER_NOT_RUNNING; // Stale timers are closed in the background
+use crate::timesheets::{ensure_entry_unlocked, ENTRY_UNLOCKED_SQL}; // Submitted weeks are read-only
 use actix_web::{delete, get, post, put, web, HttpResponse, Result as ActixResult};
-use chrono::{NaiveDateTime, Utc}; // Utc for Utc::now()
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, Utc}; // Utc for Utc::now()
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl; // Async traits
+use diesel::sql_types::{Nullable, Timestamptz, Uuid as DieselUuid};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl}; // Async traits
+use serde::Serialize;
 use serde_json::json; // For custom JSON responses
+use std::collections::{BTreeMap, BTreeSet};
 use uuid::Uuid;
 
+// Query parameters for POST /time-entries
+#[derive(serde::Deserialize, Debug)]
+pub struct CreateTimeEntryQuery {
+    // Start a timer outside the active focus session's scope
+    #[serde(rename = "override", default)]
+    pub override_focus: bool,
+}
+
 // DTO for listing query parameters
 #[derive(serde::Deserialize, Debug)]
 pub struct ListTimeEntriesQuery {
@@ -26,12 +47,69 @@ pub struct ListTimeEntriesQuery {
                                           // pub per_page: Option<i64>,
 }
 
+// Query parameters for the per-day listing (local days, inclusive bounds)
+#[derive(serde::Deserialize, Debug)]
+pub struct DailyTimeEntriesQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    // Client UTC offset in minutes (e.g. 120 for UTC+2), used to compute local days
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+}
+
+// Entries of one local day with subtotals
+#[derive(Serialize, Debug)]
+pub struct DailyTimeEntries {
+    pub date: NaiveDate,
+    pub total_duration_seconds: i64,
+    pub entry_count: usize,
+    pub first_activity_at: DateTime<Utc>,
+    pub last_activity_at: DateTime<Utc>,
+    pub entries: Vec<TimeEntry>,
+}
+
+// Maximum span of the per-day listing
+const MAX_DAILY_RANGE_DAYS: i64 = 93;
+// Idle intervals shorter than this are kept as tracked time
+const MIN_IDLE_SECONDS: i64 = 60;
+
+// Query parameters for POST /time-entries/recompute-durations
+#[derive(serde::Deserialize, Debug)]
+pub struct RecomputeDurationsQuery {
+    // all | task (requires task_id) | range (requires from and to, inclusive UTC days)
+    pub scope: String,
+    pub task_id: Option<TaskId>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+// Entries updated per statement, to keep locks and transaction size bounded
+const RECOMPUTE_BATCH_SIZE: i64 = 500;
+
+// Task of an entry whose duration was recomputed
+#[derive(QueryableByName)]
+struct RecomputedEntry {
+    #[diesel(sql_type = DieselUuid)]
+    recomputed_task_id: TaskId,
+}
+
+// Result of an idle reconciliation
+#[derive(Serialize, Debug)]
+pub struct TrimIdleResponse {
+    pub entry: TimeEntry,
+    // Part of the entry after the idle interval, when it was cut from the middle
+    pub continuation: Option<TimeEntry>,
+    pub break_entry: Option<TimeEntry>,
+    pub trimmed_seconds: i64,
+}
+
 // === POST /time-entries ===
 #[post("")] // Relative to "/time-entries" scope in main.rs
 pub async fn create_time_entry_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
     payload: web::Json<CreateTimeEntryPayload>,
+    query: web::Query<CreateTimeEntryQuery>,
 ) -> ActixResult<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id; // Uuid is Copy
 
@@ -41,42 +119,60 @@ pub async fn create_time_entry_handler(
         payload.0 // Access internal data of web::Json for logging
     );
 
+    // Compare the client's clock with ours: plausible skew is corrected, the rest is flagged
+    clock_skew::validate_client_offset(payload.client_offset)?;
+    let clock = clock_skew::assess(payload.client_time, payload.client_offset, Utc::now());
+    let entry_start = clock.correct(payload.start_time);
+    let entry_end = payload.end_time.map(|end| clock.correct(end));
+    if clock != ClockAssessment::Trusted {
+        log::warn!(
+            "User {} submitted a time entry with a client clock skew of {:?}s ({:?})",
+            user_uuid,
+            clock.skew_seconds(),
+            clock
+        );
+    }
+
     let mut conn = pool.get().await.map_err(ServiceError::from)?;
 
-    // 1. Verify that the associated task belongs to the user
-    let _task_exists = tasks::table
-        .filter(tasks::id.eq(payload.task_id))
-        .filter(tasks::user_id.eq(user_uuid))
-        .select(tasks::id)
-        .first::<Uuid>(&mut conn)
-        .await
-        .map_err(|db_err| {
-            // More fine-grained error handling for NotFound
-            match db_err {
-                diesel::result::Error::NotFound => ServiceError::NotFound(format!(
-                    "Task with id {} not found or not owned by user",
-                    payload.task_id
-                )),
-                _ => ServiceError::from(db_err),
-            }
-        })?;
+    // 1. Verify that the user can work on the associated task (own or shared project)
+    permissions::require_task(&mut conn, user_uuid, payload.task_id, Permission::TaskWrite).await?;
+
+    // During a focus session, timers only start on tasks within its scope (409 otherwise)
+    if entry_end.is_none() && !query.override_focus {
+        focus_sessions::ensure_task_in_focus(&mut conn, user_uuid, payload.task_id).await?;
+    }
+    let active_focus_session = focus_sessions::active_session_id(&mut conn, user_uuid).await?;
+
+    // The target week must not belong to a submitted or approved timesheet
+    ensure_entry_unlocked(&mut conn, user_uuid, entry_start).await?;
 
     // 2. Calculate duration_seconds if end_time is provided and duration_seconds is not
     let mut final_duration_seconds = payload.duration_seconds;
-    if let Some(end) = payload.end_time {
-        if final_duration_seconds.is_none() && end > payload.start_time {
-            final_duration_seconds = Some((end - payload.start_time).num_seconds() as i32);
+    if let Some(end) = entry_end {
+        if final_duration_seconds.is_none() && end > entry_start {
+            final_duration_seconds = Some((end - entry_start).num_seconds() as i32);
         }
     }
 
+    date_validation::validate_time_range(entry_start, entry_end, final_duration_seconds)?;
+    // A suspect client clock is kept and flagged rather than rejected (see clock_skew.rs)
+    if !clock.is_suspect() {
+        date_validation::validate_not_in_future(entry_start, entry_end, Utc::now())?;
+    }
+
     let new_time_entry_data = NewTimeEntry {
         user_id: user_uuid,
         task_id: payload.task_id,
-        start_time: payload.start_time,
-        end_time: payload.end_time,
+        start_time: entry_start,
+        end_time: entry_end,
         duration_seconds: final_duration_seconds,
         is_pomodoro_session: payload.is_pomodoro_session, // NewTimeEntry.is_pomodoro_session is Option<bool>
-                                                          // DB has DEFAULT FALSE, so None here is ok.
+        // DB has DEFAULT FALSE, so None here is ok.
+        is_break: None,
+        clock_skew_seconds: clock.skew_seconds(),
+        suspect_clock: Some(clock.is_suspect()),
+        focus_session_id: active_focus_session, // Tagged with the active focus session, if any
     };
 
     // 3. Insert
@@ -87,6 +183,23 @@ pub async fn create_time_entry_handler(
         .map_err(ServiceError::from)?;
 
     log::info!("Time entry created successfully: {:?}", created_entry);
+
+    if created_entry.duration_seconds.is_some() {
+        time_budgets::check_time_budget(&mut conn, created_entry.task_id).await;
+    }
+
+    // An entry without end_time is a running timer
+    if created_entry.end_time.is_none() {
+        events::emit(
+            DomainEvent::new(events::EVENT_TIMER_STARTED, user_uuid)
+                .with_task(created_entry.task_id, None)
+                .with_properties(json!({
+                    "time_entry_id": created_entry.id,
+                    "is_pomodoro_session": created_entry.is_pomodoro_session,
+                })),
+        );
+    }
+
     Ok(HttpResponse::Created().json(created_entry))
 }
 
@@ -131,12 +244,98 @@ pub async fn list_time_entries_handler(
     Ok(HttpResponse::Ok().json(entries))
 }
 
+// === GET /time-entries/daily?from=&to= ===
+// Entries grouped by local day; days without entries are omitted
+#[get("/daily")]
+pub async fn list_daily_time_entries_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query_params: web::Query<DailyTimeEntriesQuery>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let query_options = query_params.into_inner();
+
+    if query_options.to < query_options.from {
+        return Err(ServiceError::ValidationError(
+            "to must be on or after from".to_string(),
+        ));
+    }
+    if (query_options.to - query_options.from).num_days() >= MAX_DAILY_RANGE_DAYS {
+        return Err(ServiceError::ValidationError(format!(
+            "The range cannot exceed {} days",
+            MAX_DAILY_RANGE_DAYS
+        )));
+    }
+    // Range-checked before converting to seconds: the raw value could overflow the multiplication
+    let offset = Some(query_options.utc_offset_minutes)
+        .filter(|minutes| (-MAX_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(minutes))
+        .and_then(|minutes| FixedOffset::east_opt(minutes * 60))
+        .ok_or_else(|| {
+            ServiceError::ValidationError(format!(
+                "utc_offset_minutes must be between -{} and {}",
+                MAX_UTC_OFFSET_MINUTES, MAX_UTC_OFFSET_MINUTES
+            ))
+        })?;
+
+    // Local midnight bounds converted to UTC
+    let local_bound = |day: NaiveDate| {
+        day.and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(offset)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let range_start = local_bound(query_options.from);
+    let range_end = local_bound(query_options.to + Duration::days(1));
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let entries = time_entries
+        .filter(user_id.eq(user_uuid))
+        .filter(start_time.ge(range_start))
+        .filter(start_time.lt(range_end))
+        .order(start_time.asc())
+        .select(TimeEntry::as_select())
+        .load::<TimeEntry>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let mut days: BTreeMap<NaiveDate, Vec<TimeEntry>> = BTreeMap::new();
+    for entry in entries {
+        let local_day = entry.start_time.with_timezone(&offset).date_naive();
+        days.entry(local_day).or_default().push(entry);
+    }
+
+    let daily: Vec<DailyTimeEntries> = days
+        .into_iter()
+        .map(|(date, day_entries)| DailyTimeEntries {
+            date,
+            total_duration_seconds: day_entries
+                .iter()
+                .filter(|e| !e.is_break)
+                .map(|e| i64::from(e.duration_seconds.unwrap_or(0)))
+                .sum(),
+            entry_count: day_entries.len(),
+            // Entries are sorted by start_time, so the first one opens the day
+            first_activity_at: day_entries[0].start_time,
+            last_activity_at: day_entries
+                .iter()
+                .map(|e| e.end_time.unwrap_or(e.start_time))
+                .max()
+                .unwrap_or(day_entries[0].start_time),
+            entries: day_entries,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(daily))
+}
+
 // === GET /time-entries/{entry_id_path} ===
 #[get("/{entry_id_path}")]
 pub async fn get_time_entry_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    entry_id_path: web::Path<Uuid>,
+    entry_id_path: web::Path<TimeEntryId>,
 ) -> ActixResult<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let entry_to_find_id = entry_id_path.into_inner();
@@ -171,7 +370,7 @@ pub async fn get_time_entry_handler(
 pub async fn update_time_entry_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    entry_id_path: web::Path<Uuid>,
+    entry_id_path: web::Path<TimeEntryId>,
     payload: web::Json<UpdateTimeEntryPayload>,
 ) -> ActixResult<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
@@ -186,11 +385,12 @@ pub async fn update_time_entry_handler(
     let mut conn = pool.get().await.map_err(ServiceError::from)?;
 
     // First, fetch the current start_time for duration calculation
-    let current_entry_start_time_naive = time_entries
+    // (end_time tells whether this update stops a running timer)
+    let (current_entry_start_time_naive, current_entry_end_time, current_task_id) = time_entries
         .filter(id.eq(entry_to_update_id))
         .filter(user_id.eq(user_uuid))
-        .select(start_time)
-        .first::<NaiveDateTime>(&mut conn)
+        .select((start_time, end_time, task_id))
+        .first::<(NaiveDateTime, Option<NaiveDateTime>, TaskId)>(&mut conn)
         .await
         .map_err(|db_err| match db_err {
             // More fine-grained handling of NotFound
@@ -201,7 +401,24 @@ pub async fn update_time_entry_handler(
             _ => ServiceError::from(db_err),
         })?;
 
-    let mut changeset_duration = payload.duration_seconds.clone(); // payload.duration_seconds is Option<Option<i32>>
+    // Both the current week and the target week (if start_time moves) must be unlocked
+    ensure_entry_unlocked(
+        &mut conn,
+        user_uuid,
+        current_entry_start_time_naive.and_utc(),
+    )
+    .await?;
+    if let Some(new_start_time) = payload.start_time {
+        ensure_entry_unlocked(&mut conn, user_uuid, new_start_time).await?;
+    }
+
+    // Moving the entry to another task requires write access on that task
+    if let Some(new_task_uuid) = payload.task_id {
+        permissions::require_task(&mut conn, user_uuid, new_task_uuid, Permission::TaskWrite)
+            .await?;
+    }
+
+    let mut changeset_duration = payload.duration_seconds; // payload.duration_seconds is Option<Option<i32>>
 
     // Conversion for comparison and duration calculation
     if let Some(Some(end_t_utc)) = payload.end_time {
@@ -217,12 +434,30 @@ pub async fn update_time_entry_handler(
         }
     }
 
+    // Validate the entry as it will be stored once the partial update is applied
+    let merged_start = payload
+        .start_time
+        .unwrap_or_else(|| current_entry_start_time_naive.and_utc());
+    let merged_end = payload
+        .end_time
+        .unwrap_or_else(|| current_entry_end_time.map(|end| end.and_utc()));
+    date_validation::validate_time_range(merged_start, merged_end, changeset_duration.flatten())?;
+    if payload.start_time.is_some() || payload.end_time.is_some() {
+        date_validation::validate_not_in_future(merged_start, merged_end, Utc::now())?;
+    }
+
+    let previous_task_id = payload.task_id.map(|_| current_task_id);
+
     let entry_changes = UpdateTimeEntryChangeset {
+        task_id: payload.task_id,
         start_time: payload.start_time, // payload.start_time is Option<DateTime<Utc>>
-        end_time: payload.end_time.clone(),
+        end_time: payload.end_time,
         duration_seconds: changeset_duration,
         is_pomodoro_session: payload.is_pomodoro_session,
         updated_at: Some(Utc::now().naive_utc()),
+        // Times edited by the user supersede the suspect client clock
+        suspect_clock: (payload.start_time.is_some() || payload.end_time.is_some())
+            .then_some(false),
     };
 
     log::info!(
@@ -231,16 +466,43 @@ pub async fn update_time_entry_handler(
         entry_changes
     );
 
+    // start_time pins the statement to the entry's monthly partition
     let updated_entry = diesel::update(
         time_entries
             .filter(id.eq(entry_to_update_id))
-            .filter(user_id.eq(user_uuid)),
+            .filter(user_id.eq(user_uuid))
+            .filter(start_time.eq(current_entry_start_time_naive)),
     )
     .set(&entry_changes)
     .get_result::<TimeEntry>(&mut conn)
     .await
     .map_err(ServiceError::from)?;
 
+    // Stopping a running timer fires the timer_stopped automation rules
+    if current_entry_end_time.is_none() && updated_entry.end_time.is_some() {
+        automations::run_automations(
+            &mut conn,
+            AutomationEvent::for_task(AutomationTrigger::TimerStopped, updated_entry.task_id),
+        )
+        .await;
+
+        events::emit(
+            DomainEvent::new(events::EVENT_TIMER_STOPPED, user_uuid)
+                .with_task(updated_entry.task_id, None)
+                .with_properties(json!({
+                    "time_entry_id": updated_entry.id,
+                    "duration_seconds": updated_entry.duration_seconds,
+                    "is_pomodoro_session": updated_entry.is_pomodoro_session,
+                })),
+        );
+    }
+
+    // Tracked time changed on the entry's task, and on the previous one if it was moved
+    time_budgets::check_time_budget(&mut conn, updated_entry.task_id).await;
+    if let Some(previous_task) = previous_task_id.filter(|task| *task != updated_entry.task_id) {
+        time_budgets::check_time_budget(&mut conn, previous_task).await;
+    }
+
     Ok(HttpResponse::Ok().json(updated_entry))
 }
 
@@ -249,7 +511,7 @@ pub async fn update_time_entry_handler(
 pub async fn delete_time_entry_handler(
     pool: web::Data<DbPool>,
     authenticated_user: AuthenticatedUser,
-    entry_id_path: web::Path<Uuid>,
+    entry_id_path: web::Path<TimeEntryId>,
 ) -> ActixResult<HttpResponse, ServiceError> {
     let user_uuid = authenticated_user.id;
     let entry_to_delete_id = entry_id_path.into_inner();
@@ -261,16 +523,37 @@ pub async fn delete_time_entry_handler(
 
     let mut conn = pool.get().await.map_err(ServiceError::from)?;
 
+    // Entries of a submitted or approved week cannot be deleted
+    let entry_to_delete = time_entries
+        .filter(user_id.eq(user_uuid))
+        .filter(id.eq(entry_to_delete_id))
+        .select((start_time, task_id))
+        .first::<(DateTime<Utc>, TaskId)>(&mut conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)?;
+    let Some((entry_start, entry_task_id)) = entry_to_delete else {
+        return Err(ServiceError::NotFound(format!(
+            "TimeEntry with id {} not found or not owned by user to delete",
+            entry_to_delete_id
+        )));
+    };
+    ensure_entry_unlocked(&mut conn, user_uuid, entry_start).await?;
+
+    // start_time pins the statement to the entry's monthly partition
     let num_deleted = diesel::delete(
         time_entries
             .filter(user_id.eq(user_uuid))
-            .filter(id.eq(entry_to_delete_id)),
+            .filter(id.eq(entry_to_delete_id))
+            .filter(start_time.eq(entry_start)),
     )
     .execute(&mut conn)
     .await
     .map_err(ServiceError::from)?;
 
     if num_deleted > 0 {
+        // Removed time may bring the task back under an alert threshold
+        time_budgets::check_time_budget(&mut conn, entry_task_id).await;
         Ok(HttpResponse::Ok().json(json!({
             "status": "success",
             "message": format!("TimeEntry with id {} deleted successfully", entry_to_delete_id)
@@ -282,3 +565,355 @@ pub async fn delete_time_entry_handler(
         )))
     }
 }
+
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> i32 {
+    (to - from).num_seconds() as i32
+}
+
+// === POST /time-entries/recompute-durations ===
+// Recalculates duration_seconds from start/end for finished entries whose stored
+// duration is missing or wrong (e.g. imported data). Entries of locked weeks are skipped.
+#[post("/recompute-durations")]
+pub async fn recompute_durations_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    query: web::Query<RecomputeDurationsQuery>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+
+    let (task_filter, range_start, range_end) = match query.scope.as_str() {
+        "all" => (None, None, None),
+        "task" => {
+            let task_uuid = query.task_id.ok_or_else(|| {
+                ServiceError::BadRequest("task_id is required for scope=task".to_string())
+            })?;
+            (Some(task_uuid), None, None)
+        }
+        "range" => {
+            let (Some(from), Some(to)) = (query.from, query.to) else {
+                return Err(ServiceError::BadRequest(
+                    "from and to are required for scope=range".to_string(),
+                ));
+            };
+            if from > to {
+                return Err(ServiceError::BadRequest(
+                    "from cannot be after to".to_string(),
+                ));
+            }
+            (
+                None,
+                Some(from.and_hms_opt(0, 0, 0).unwrap().and_utc()),
+                Some(to.and_hms_opt(23, 59, 59).unwrap().and_utc()),
+            )
+        }
+        other => {
+            return Err(ServiceError::BadRequest(format!(
+                "Invalid scope: {}. Supported: all, task, range",
+                other
+            )))
+        }
+    };
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    if let Some(task_uuid) = task_filter {
+        permissions::require_task(&mut conn, user_uuid, task_uuid, Permission::TaskRead).await?;
+    }
+
+    // time_entries is partitioned by month on start_time: the range is only spelled out when
+    // present, on both the subquery and the updated table, so that unrelated months are pruned
+    let range_condition = |alias: &str| match range_start {
+        Some(_) => format!(
+            "AND {alias}.start_time >= $3 AND {alias}.start_time <= $4",
+            alias = alias
+        ),
+        None => "AND $3::timestamptz IS NULL AND $4::timestamptz IS NULL".to_string(),
+    };
+
+    // Each batch only picks entries that still differ, so the loop ends once all are fixed
+    let batch_sql = format!(
+        "UPDATE time_entries target SET duration_seconds = {computed}, updated_at = NOW() \
+         WHERE target.id IN (SELECT te.id FROM time_entries te \
+         WHERE te.user_id = $1 \
+         AND te.end_time IS NOT NULL AND te.end_time >= te.start_time \
+         AND te.duration_seconds IS DISTINCT FROM {computed} \
+         AND ($2::uuid IS NULL OR te.task_id = $2) \
+         {inner_range} \
+         AND {unlocked} \
+         LIMIT {batch}) \
+         AND target.user_id = $1 {outer_range} \
+         RETURNING target.task_id AS recomputed_task_id",
+        computed = COMPUTED_DURATION_SQL,
+        inner_range = range_condition("te"),
+        outer_range = range_condition("target"),
+        unlocked = ENTRY_UNLOCKED_SQL,
+        batch = RECOMPUTE_BATCH_SIZE
+    );
+
+    let mut updated = 0;
+    let mut batches = 0;
+    let mut affected_tasks = BTreeSet::new();
+    loop {
+        let recomputed = diesel::sql_query(&batch_sql)
+            .bind::<DieselUuid, _>(user_uuid)
+            .bind::<Nullable<DieselUuid>, _>(task_filter)
+            .bind::<Nullable<Timestamptz>, _>(range_start)
+            .bind::<Nullable<Timestamptz>, _>(range_end)
+            .load::<RecomputedEntry>(&mut conn)
+            .await
+            .map_err(ServiceError::from)?;
+        if recomputed.is_empty() {
+            break;
+        }
+        updated += recomputed.len();
+        batches += 1;
+        affected_tasks.extend(recomputed.into_iter().map(|entry| entry.recomputed_task_id));
+    }
+
+    // Corrected durations change tracked time, so budget alerts are re-evaluated per task
+    for task_uuid in affected_tasks {
+        time_budgets::check_time_budget(&mut conn, task_uuid).await;
+    }
+
+    log::info!(
+        "User {} recomputed {} time entry duration(s) in {} batch(es) (scope: {})",
+        user_uuid,
+        updated,
+        batches,
+        query.scope
+    );
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": format!("{} time entry duration(s) recomputed", updated),
+        "updated": updated,
+        "batches": batches
+    })))
+}
+
+// === POST /time-entries/{entry_id_path}/trim-idle ===
+// Removes an idle interval reported by a client. The interval is clamped to the entry
+// (a running entry ends "now"): at the start or end it shrinks the entry, in the middle
+// it splits it in two. With action "break" the interval is kept as a break entry.
+#[post("/{entry_id_path}/trim-idle")]
+pub async fn trim_idle_time_entry_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    entry_id_path: web::Path<TimeEntryId>,
+    payload: web::Json<TrimIdlePayload>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let entry_uuid = entry_id_path.into_inner();
+    let payload = payload.into_inner();
+
+    if payload.idle_end <= payload.idle_start {
+        return Err(ServiceError::ValidationError(
+            "idle_end must be after idle_start".to_string(),
+        ));
+    }
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let response = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let entry = time_entries
+                    .filter(id.eq(entry_uuid))
+                    .filter(user_id.eq(user_uuid))
+                    .select(TimeEntry::as_select())
+                    .for_update()
+                    .first::<TimeEntry>(conn)
+                    .await
+                    .optional()?
+                    .ok_or_else(|| {
+                        ServiceError::NotFound(format!(
+                            "TimeEntry with id {} not found or not owned by user",
+                            entry_uuid
+                        ))
+                    })?;
+
+                if entry.is_break {
+                    return Err(ServiceError::ValidationError(
+                        "Idle time cannot be trimmed from a break entry".to_string(),
+                    ));
+                }
+                ensure_entry_unlocked(conn, user_uuid, entry.start_time).await?;
+
+                let now = Utc::now();
+                let entry_end = entry.end_time.unwrap_or(now);
+                let idle_from = payload.idle_start.max(entry.start_time);
+                let idle_to = payload.idle_end.min(entry_end);
+                if idle_to <= idle_from {
+                    return Err(ServiceError::ValidationError(
+                        "The idle interval does not overlap the time entry".to_string(),
+                    ));
+                }
+
+                let trimmed_seconds = (idle_to - idle_from).num_seconds();
+                if trimmed_seconds < MIN_IDLE_SECONDS {
+                    return Ok(TrimIdleResponse {
+                        entry,
+                        continuation: None,
+                        break_entry: None,
+                        trimmed_seconds: 0,
+                    });
+                }
+                if idle_from == entry.start_time && idle_to == entry_end {
+                    return Err(ServiceError::ValidationError(
+                        "The idle interval covers the whole time entry; delete it instead"
+                            .to_string(),
+                    ));
+                }
+                // The remaining part may start in the following (possibly locked) week
+                ensure_entry_unlocked(conn, user_uuid, idle_to).await?;
+
+                let (updated_entry, continuation) = if idle_from == entry.start_time {
+                    // Idle at the start: the entry begins when activity resumed
+                    let updated_entry = diesel::update(time_entries.find(entry.id))
+                        .set((
+                            start_time.eq(idle_to),
+                            duration_seconds
+                                .eq(entry.end_time.map(|end| seconds_between(idle_to, end))),
+                            updated_at.eq(now.naive_utc()),
+                        ))
+                        .get_result::<TimeEntry>(conn)
+                        .await?;
+                    (updated_entry, None)
+                } else {
+                    // Idle at the end or in the middle: the entry stops when idle began
+                    let updated_entry = diesel::update(time_entries.find(entry.id))
+                        .set((
+                            end_time.eq(Some(idle_from)),
+                            duration_seconds.eq(Some(seconds_between(entry.start_time, idle_from))),
+                            updated_at.eq(now.naive_utc()),
+                        ))
+                        .get_result::<TimeEntry>(conn)
+                        .await?;
+
+                    // Activity after the idle interval becomes a new entry (still running if the original was)
+                    let continuation = if idle_to < entry_end {
+                        let remaining = diesel::insert_into(time_entries::table)
+                            .values(&NewTimeEntry {
+                                user_id: user_uuid,
+                                task_id: entry.task_id,
+                                start_time: idle_to,
+                                end_time: entry.end_time,
+                                duration_seconds: entry
+                                    .end_time
+                                    .map(|end| seconds_between(idle_to, end)),
+                                is_pomodoro_session: Some(entry.is_pomodoro_session),
+                                is_break: None,
+                                clock_skew_seconds: entry.clock_skew_seconds,
+                                suspect_clock: Some(entry.suspect_clock),
+                                focus_session_id: entry.focus_session_id,
+                            })
+                            .get_result::<TimeEntry>(conn)
+                            .await?;
+                        Some(remaining)
+                    } else {
+                        None
+                    };
+                    (updated_entry, continuation)
+                };
+
+                let break_entry = match payload.action {
+                    IdleAction::Discard => None,
+                    IdleAction::Break => Some(
+                        diesel::insert_into(time_entries::table)
+                            .values(&NewTimeEntry {
+                                user_id: user_uuid,
+                                task_id: entry.task_id,
+                                start_time: idle_from,
+                                end_time: Some(idle_to),
+                                duration_seconds: Some(seconds_between(idle_from, idle_to)),
+                                is_pomodoro_session: Some(false),
+                                is_break: Some(true),
+                                clock_skew_seconds: entry.clock_skew_seconds,
+                                suspect_clock: Some(entry.suspect_clock),
+                                focus_session_id: entry.focus_session_id,
+                            })
+                            .get_result::<TimeEntry>(conn)
+                            .await?,
+                    ),
+                };
+
+                Ok(TrimIdleResponse {
+                    entry: updated_entry,
+                    continuation,
+                    break_entry,
+                    trimmed_seconds,
+                })
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    if response.trimmed_seconds > 0 {
+        time_budgets::check_time_budget(&mut conn, response.entry.task_id).await;
+    }
+
+    log::info!(
+        "User {} trimmed {}s of idle time from time_entry {}",
+        user_uuid,
+        response.trimmed_seconds,
+        entry_uuid
+    );
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+// === POST /time-entries/{entry_id_path}/heartbeat ===
+// Called every minute by clients while a timer runs. Entries whose heartbeat goes stale
+// are closed at their last heartbeat by the timer recovery job (see timer_recovery.rs);
+// a client resuming after that gets 409 TIMER_NOT_RUNNING with the entry still readable.
+#[post("/{entry_id_path}/heartbeat")]
+pub async fn heartbeat_time_entry_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    entry_id_path: web::Path<TimeEntryId>,
+) -> ActixResult<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let entry_uuid = entry_id_path.into_inner();
+
+    let mut conn = pool.get().await.map_err(ServiceError::from)?;
+
+    let updated_entry = diesel::update(
+        time_entries
+            .filter(id.eq(entry_uuid))
+            .filter(user_id.eq(user_uuid))
+            .filter(end_time.is_null()),
+    )
+    .set(last_heartbeat_at.eq(Some(Utc::now())))
+    .get_result::<TimeEntry>(&mut conn)
+    .await
+    .optional()
+    .map_err(ServiceError::from)?;
+
+    if let Some(entry) = updated_entry {
+        return Ok(HttpResponse::Ok().json(entry));
+    }
+
+    // Distinguish a stopped (possibly auto-stopped) entry from a missing one
+    let stopped_entry = time_entries
+        .filter(id.eq(entry_uuid))
+        .filter(user_id.eq(user_uuid))
+        .select(TimeEntry::as_select())
+        .first::<TimeEntry>(&mut conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)?;
+
+    match stopped_entry {
+        Some(entry) => Err(ServiceError::CodedConflict(
+            TIMER_NOT_RUNNING,
+            if entry.auto_stopped {
+                format!("TimeEntry {} was stopped automatically", entry_uuid)
+            } else {
+                format!("TimeEntry {} is not running", entry_uuid)
+            },
+        )),
+        None => Err(ServiceError::NotFound(format!(
+            "TimeEntry with id {} not found or not owned by user",
+            entry_uuid
+        ))),
+    }
+}
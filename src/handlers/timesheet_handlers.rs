@@ -0,0 +1,294 @@
+// OptiTask/backend-api/src/handlers/timesheet_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{
+    NewNotification, ReviewTimesheetPayload, SubmitTimesheetPayload, Timesheet,
+    TIMESHEET_STATUS_APPROVED, TIMESHEET_STATUS_DRAFT, TIMESHEET_STATUS_SUBMITTED,
+};
+use crate::notifications::KIND_TIMESHEET_REVIEWED;
+use crate::schema::{notifications, time_entries, timesheets};
+use actix_web::{get, post, web, HttpResponse};
+use chrono::{Datelike, Duration as ChronoDuration, Utc, Weekday};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde_json::json;
+use uuid::Uuid;
+
+const MAX_REVIEW_NOTE_CHARS: usize = 1000;
+
+// === GET /timesheets ===
+#[get("")]
+pub async fn list_timesheets_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let sheets = timesheets::table
+        .filter(timesheets::user_id.eq(authenticated_user.id))
+        .order(timesheets::week_start.desc())
+        .select(Timesheet::as_select())
+        .load::<Timesheet>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(sheets))
+}
+
+// === GET /timesheets/pending-review ===
+// Feuilles soumises que l'appelant peut valider
+#[get("/pending-review")]
+pub async fn list_pending_timesheets_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let reviewable =
+        crate::timesheets::reviewable_user_ids(&mut conn, authenticated_user.id).await?;
+    if reviewable.is_empty() {
+        return Ok(HttpResponse::Ok().json(Vec::<Timesheet>::new()));
+    }
+
+    let sheets = timesheets::table
+        .filter(timesheets::user_id.eq_any(&reviewable))
+        .filter(timesheets::status.eq(TIMESHEET_STATUS_SUBMITTED))
+        .order(timesheets::submitted_at.asc())
+        .select(Timesheet::as_select())
+        .load::<Timesheet>(&mut conn)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(sheets))
+}
+
+// === POST /timesheets/submit ===
+// Crée (ou reprend un brouillon) puis soumet la semaine ; ses entrées deviennent non modifiables
+#[post("/submit")]
+pub async fn submit_timesheet_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<SubmitTimesheetPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let week_start = payload.week_start;
+
+    if week_start.weekday() != Weekday::Mon {
+        return Err(ServiceError::ValidationError(
+            "week_start must be a Monday".to_string(),
+        ));
+    }
+    if week_start > Utc::now().date_naive() {
+        return Err(ServiceError::ValidationError(
+            "A future week cannot be submitted".to_string(),
+        ));
+    }
+
+    let range_start = week_start.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let range_end = range_start + ChronoDuration::days(7);
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let timesheet = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let existing_status = timesheets::table
+                    .filter(timesheets::user_id.eq(user_uuid))
+                    .filter(timesheets::week_start.eq(week_start))
+                    .select(timesheets::status)
+                    .for_update()
+                    .first::<String>(conn)
+                    .await
+                    .optional()?;
+                if let Some(status) = existing_status.filter(|s| s != TIMESHEET_STATUS_DRAFT) {
+                    return Err(ServiceError::ConflictError(format!(
+                        "The timesheet for the week of {} is already {}",
+                        week_start, status
+                    )));
+                }
+
+                // Une entrée en cours n'a pas de durée définitive
+                let running_entries = time_entries::table
+                    .filter(time_entries::user_id.eq(user_uuid))
+                    .filter(time_entries::start_time.ge(range_start))
+                    .filter(time_entries::start_time.lt(range_end))
+                    .filter(time_entries::end_time.is_null())
+                    .count()
+                    .get_result::<i64>(conn)
+                    .await?;
+                if running_entries > 0 {
+                    return Err(ServiceError::ValidationError(
+                        "Stop the running time entry before submitting this week".to_string(),
+                    ));
+                }
+
+                let total_seconds = time_entries::table
+                    .filter(time_entries::user_id.eq(user_uuid))
+                    .filter(time_entries::start_time.ge(range_start))
+                    .filter(time_entries::start_time.lt(range_end))
+                    .filter(time_entries::is_break.eq(false))
+                    .select(diesel::dsl::sum(time_entries::duration_seconds))
+                    .get_result::<Option<i64>>(conn)
+                    .await?
+                    .unwrap_or(0);
+
+                let now = Utc::now();
+                diesel::insert_into(timesheets::table)
+                    .values((
+                        timesheets::user_id.eq(user_uuid),
+                        timesheets::week_start.eq(week_start),
+                        timesheets::status.eq(TIMESHEET_STATUS_SUBMITTED),
+                        timesheets::total_duration_seconds.eq(total_seconds),
+                        timesheets::submitted_at.eq(now),
+                    ))
+                    .on_conflict((timesheets::user_id, timesheets::week_start))
+                    .do_update()
+                    .set((
+                        timesheets::status.eq(TIMESHEET_STATUS_SUBMITTED),
+                        timesheets::total_duration_seconds.eq(total_seconds),
+                        timesheets::submitted_at.eq(now),
+                    ))
+                    .returning(Timesheet::as_returning())
+                    .get_result::<Timesheet>(conn)
+                    .await
+                    .map_err(ServiceError::from)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(timesheet))
+}
+
+// Validation ou rejet : seul un propriétaire d'un espace commun peut relire la feuille
+async fn review_timesheet(
+    pool: &DbPool,
+    reviewer_uuid: Uuid,
+    timesheet_uuid: Uuid,
+    note: Option<String>,
+    approve: bool,
+) -> Result<Timesheet, ServiceError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_REVIEW_NOTE_CHARS)
+    {
+        return Err(ServiceError::ValidationError(format!(
+            "note cannot exceed {} characters",
+            MAX_REVIEW_NOTE_CHARS
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            let current = timesheets::table
+                .find(timesheet_uuid)
+                .select(Timesheet::as_select())
+                .for_update()
+                .first::<Timesheet>(conn)
+                .await
+                .optional()?
+                .ok_or_else(|| ServiceError::NotFound("Timesheet not found".to_string()))?;
+
+            if !crate::timesheets::can_review(conn, reviewer_uuid, current.user_id).await? {
+                // Ne pas révéler l'existence de la feuille à un tiers
+                return Err(ServiceError::NotFound("Timesheet not found".to_string()));
+            }
+            if current.status != TIMESHEET_STATUS_SUBMITTED {
+                return Err(ServiceError::ConflictError(format!(
+                    "Only submitted timesheets can be reviewed (current status: {})",
+                    current.status
+                )));
+            }
+
+            // Un rejet renvoie la feuille en brouillon et déverrouille ses entrées
+            let new_status = if approve {
+                TIMESHEET_STATUS_APPROVED
+            } else {
+                TIMESHEET_STATUS_DRAFT
+            };
+
+            let updated = diesel::update(timesheets::table.find(timesheet_uuid))
+                .set((
+                    timesheets::status.eq(new_status),
+                    timesheets::reviewed_by.eq(reviewer_uuid),
+                    timesheets::reviewed_at.eq(Utc::now()),
+                    timesheets::review_note.eq(&note),
+                ))
+                .returning(Timesheet::as_returning())
+                .get_result::<Timesheet>(conn)
+                .await?;
+
+            let verdict = if approve { "approved" } else { "rejected" };
+            diesel::insert_into(notifications::table)
+                .values(&NewNotification {
+                    user_id: updated.user_id,
+                    task_id: None,
+                    actor_id: Some(reviewer_uuid),
+                    kind: KIND_TIMESHEET_REVIEWED.to_string(),
+                    message: format!(
+                        "Your timesheet for the week of {} was {}",
+                        updated.week_start, verdict
+                    ),
+                    payload: json!({
+                        "timesheet_id": updated.id,
+                        "week_start": updated.week_start,
+                        "verdict": verdict,
+                        "note": note,
+                    }),
+                })
+                .execute(conn)
+                .await?;
+
+            Ok(updated)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+// === POST /timesheets/{timesheet_id_path}/approve ===
+#[post("/{timesheet_id_path}/approve")]
+pub async fn approve_timesheet_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    timesheet_id_path: web::Path<Uuid>,
+    payload: Option<web::Json<ReviewTimesheetPayload>>,
+) -> Result<HttpResponse, ServiceError> {
+    let note = payload.and_then(|p| p.into_inner().note);
+    let timesheet = review_timesheet(
+        &pool,
+        authenticated_user.id,
+        timesheet_id_path.into_inner(),
+        note,
+        true,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(timesheet))
+}
+
+// === POST /timesheets/{timesheet_id_path}/reject ===
+#[post("/{timesheet_id_path}/reject")]
+pub async fn reject_timesheet_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    timesheet_id_path: web::Path<Uuid>,
+    payload: Option<web::Json<ReviewTimesheetPayload>>,
+) -> Result<HttpResponse, ServiceError> {
+    let note = payload.and_then(|p| p.into_inner().note);
+    let timesheet = review_timesheet(
+        &pool,
+        authenticated_user.id,
+        timesheet_id_path.into_inner(),
+        note,
+        false,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(timesheet))
+}
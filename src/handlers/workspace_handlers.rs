@@ -0,0 +1,552 @@
+// OptiTask/backend-api/src/handlers/workspace_handlers.rs
+use crate::auth_utils::AuthenticatedUser;
+use crate::cost_limits::{CostClass, CostLimiter};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::handlers::analytics_handlers::{calculate_date_range, period_bounds};
+use crate::models::{
+    AddWorkspaceMemberPayload, AnalyticsQueryPeriod, CreateWorkspacePayload, LeaderboardEntry,
+    MemberStandup, NewWorkspace, StandupTask, Task, UpdateWorkspaceTimeLockPayload, Workspace,
+    WorkspaceDetailResponse, WorkspaceLeaderboardResponse, WorkspaceMember,
+    WorkspaceStandupResponse, DONE_TASK_STATUSES, LEADERBOARD_METRICS,
+    LEADERBOARD_METRIC_FOCUS_TIME, WORKSPACE_ROLES,
+};
+use crate::permissions::{self, Permission};
+use crate::schema::{projects, tasks, time_entries, user_settings, workspace_members, workspaces};
+use crate::settings;
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+// Paramètres du classement : mesure et période (mêmes valeurs que /analytics)
+#[derive(Deserialize, Debug)]
+pub struct LeaderboardQuery {
+    pub metric: Option<String>,
+    pub period: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub week_start: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StandupQuery {
+    // Jour du standup (YYYY-MM-DD), aujourd'hui par défaut
+    pub date: Option<NaiveDate>,
+}
+
+impl From<Task> for StandupTask {
+    fn from(task: Task) -> Self {
+        StandupTask {
+            id: task.id,
+            title: task.title,
+            status: task.status,
+            project_id: task.project_id,
+            due_date: task.due_date,
+        }
+    }
+}
+
+// === POST /workspaces ===
+// Le créateur devient propriétaire et premier membre
+#[post("")]
+pub async fn create_workspace_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    payload: web::Json<CreateWorkspacePayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let workspace_name = payload.name.trim().to_string();
+    if workspace_name.is_empty() {
+        return Err(ServiceError::ValidationError(
+            "Workspace name cannot be empty".to_string(),
+        ));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let workspace = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let workspace = diesel::insert_into(workspaces::table)
+                    .values(&NewWorkspace {
+                        owner_id: user_uuid,
+                        name: workspace_name,
+                    })
+                    .get_result::<Workspace>(conn)
+                    .await?;
+
+                diesel::insert_into(workspace_members::table)
+                    .values((
+                        workspace_members::workspace_id.eq(workspace.id),
+                        workspace_members::user_id.eq(user_uuid),
+                        workspace_members::role.eq("owner"),
+                    ))
+                    .execute(conn)
+                    .await?;
+
+                Ok(workspace)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(HttpResponse::Created().json(workspace))
+}
+
+// === GET /workspaces ===
+#[get("")]
+pub async fn list_workspaces_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+) -> Result<HttpResponse, ServiceError> {
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    let workspace_list = workspaces::table
+        .inner_join(workspace_members::table)
+        .filter(workspace_members::user_id.eq(authenticated_user.id))
+        .order(workspaces::name.asc())
+        .select(Workspace::as_select())
+        .load::<Workspace>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(workspace_list))
+}
+
+// === GET /workspaces/{workspace_id_path} ===
+#[get("/{workspace_id_path}")]
+pub async fn get_workspace_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    workspace_id_path: web::Path<Uuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let workspace_uuid = workspace_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_workspace(
+        &mut conn,
+        authenticated_user.id,
+        workspace_uuid,
+        Permission::WorkspaceRead,
+    )
+    .await?;
+
+    let workspace = workspaces::table
+        .find(workspace_uuid)
+        .select(Workspace::as_select())
+        .first::<Workspace>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let members = workspace_members::table
+        .filter(workspace_members::workspace_id.eq(workspace_uuid))
+        .order(workspace_members::joined_at.asc())
+        .select(WorkspaceMember::as_select())
+        .load::<WorkspaceMember>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(WorkspaceDetailResponse { workspace, members }))
+}
+
+// === POST /workspaces/{workspace_id_path}/members ===
+// Réservé au propriétaire ; ajoute ou met à jour le rôle d'un membre
+#[post("/{workspace_id_path}/members")]
+pub async fn add_workspace_member_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    workspace_id_path: web::Path<Uuid>,
+    payload: web::Json<AddWorkspaceMemberPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let workspace_uuid = workspace_id_path.into_inner();
+    let member_role = payload.role.clone().unwrap_or_else(|| "editor".to_string());
+
+    if !WORKSPACE_ROLES.contains(&member_role.as_str()) || member_role == "owner" {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid member role: {}. Supported: editor, viewer",
+            member_role
+        )));
+    }
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_workspace(
+        &mut conn,
+        authenticated_user.id,
+        workspace_uuid,
+        Permission::WorkspaceManageMembers,
+    )
+    .await?;
+    if payload.user_id == authenticated_user.id {
+        return Err(ServiceError::BadRequest(
+            "The workspace owner cannot change their own role".to_string(),
+        ));
+    }
+
+    let member = diesel::insert_into(workspace_members::table)
+        .values((
+            workspace_members::workspace_id.eq(workspace_uuid),
+            workspace_members::user_id.eq(payload.user_id),
+            workspace_members::role.eq(&member_role),
+        ))
+        .on_conflict((workspace_members::workspace_id, workspace_members::user_id))
+        .do_update()
+        .set(workspace_members::role.eq(&member_role))
+        .get_result::<WorkspaceMember>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(member))
+}
+
+// === PUT /workspaces/{workspace_id_path}/time-lock ===
+// Réservé au propriétaire : clôture comptable de l'espace. Les entrées de temps des
+// membres antérieures à cette date ne peuvent plus être créées, modifiées ni supprimées.
+#[put("/{workspace_id_path}/time-lock")]
+pub async fn update_workspace_time_lock_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    workspace_id_path: web::Path<Uuid>,
+    payload: web::Json<UpdateWorkspaceTimeLockPayload>,
+) -> Result<HttpResponse, ServiceError> {
+    let workspace_uuid = workspace_id_path.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_workspace(
+        &mut conn,
+        authenticated_user.id,
+        workspace_uuid,
+        Permission::WorkspaceManageMembers,
+    )
+    .await?;
+
+    let workspace = diesel::update(workspaces::table.find(workspace_uuid))
+        .set(workspaces::time_locked_before.eq(payload.locked_before))
+        .returning(Workspace::as_returning())
+        .get_result::<Workspace>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    Ok(HttpResponse::Ok().json(workspace))
+}
+
+// === DELETE /workspaces/{workspace_id_path}/members/{member_id_path} ===
+// Le propriétaire retire un membre, un membre peut quitter l'espace
+#[delete("/{workspace_id_path}/members/{member_id_path}")]
+pub async fn remove_workspace_member_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    path_params: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ServiceError> {
+    let (workspace_uuid, member_uuid) = path_params.into_inner();
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    // Quitter l'espace ne demande que d'en être membre
+    let required_permission = if member_uuid == authenticated_user.id {
+        Permission::WorkspaceRead
+    } else {
+        Permission::WorkspaceManageMembers
+    };
+    permissions::require_workspace(
+        &mut conn,
+        authenticated_user.id,
+        workspace_uuid,
+        required_permission,
+    )
+    .await?;
+
+    let num_deleted = diesel::delete(
+        workspace_members::table
+            .filter(workspace_members::workspace_id.eq(workspace_uuid))
+            .filter(workspace_members::user_id.eq(member_uuid))
+            .filter(workspace_members::role.ne("owner")),
+    )
+    .execute(&mut conn)
+    .await
+    .map_err(ServiceError::from)?;
+
+    if num_deleted > 0 {
+        Ok(HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("Member {} removed from workspace", member_uuid)
+        })))
+    } else {
+        Err(ServiceError::NotFound(format!(
+            "Member {} not found in workspace (the owner cannot be removed)",
+            member_uuid
+        )))
+    }
+}
+
+// === GET /workspaces/{workspace_id_path}/standup?date= ===
+// Par membre : tâches terminées la veille, temps suivi la veille et tâches prévues
+// le jour même, limités aux projets rattachés à l'espace.
+#[get("/{workspace_id_path}/standup")]
+pub async fn get_workspace_standup_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    workspace_id_path: web::Path<Uuid>,
+    query: web::Query<StandupQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let workspace_uuid = workspace_id_path.into_inner();
+    let standup_date = query.date.unwrap_or_else(|| Utc::now().date_naive());
+    let yesterday = standup_date - Duration::days(1);
+    let (yesterday_start, yesterday_end) = period_bounds(yesterday, yesterday);
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_workspace(
+        &mut conn,
+        authenticated_user.id,
+        workspace_uuid,
+        Permission::WorkspaceRead,
+    )
+    .await?;
+
+    let members = workspace_members::table
+        .filter(workspace_members::workspace_id.eq(workspace_uuid))
+        .order(workspace_members::joined_at.asc())
+        .select(WorkspaceMember::as_select())
+        .load::<WorkspaceMember>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+    let member_ids: Vec<Uuid> = members.iter().map(|m| m.user_id).collect();
+
+    let workspace_projects = projects::table
+        .filter(projects::workspace_id.eq(workspace_uuid))
+        .select(projects::id.nullable());
+
+    // Sans date de complétion, une tâche terminée est datée par sa dernière mise à jour
+    let completed_tasks = tasks::table
+        .filter(tasks::project_id.eq_any(workspace_projects))
+        .filter(tasks::user_id.eq_any(&member_ids))
+        .filter(tasks::status.eq_any(DONE_TASK_STATUSES))
+        .filter(tasks::updated_at.ge(yesterday_start.naive_utc()))
+        .filter(tasks::updated_at.le(yesterday_end.naive_utc()))
+        .order(tasks::updated_at.asc())
+        .select(Task::as_select())
+        .load::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let planned_tasks = tasks::table
+        .filter(tasks::project_id.eq_any(workspace_projects))
+        .filter(tasks::user_id.eq_any(&member_ids))
+        .filter(tasks::status.ne_all(DONE_TASK_STATUSES))
+        .filter(tasks::due_date.le(standup_date))
+        .order((tasks::due_date.asc(), tasks::task_order.asc()))
+        .select(Task::as_select())
+        .load::<Task>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?;
+
+    let tracked_by_member: HashMap<Uuid, i64> = time_entries::table
+        .inner_join(tasks::table)
+        .filter(tasks::project_id.eq_any(workspace_projects))
+        .filter(time_entries::user_id.eq_any(&member_ids))
+        .filter(time_entries::is_break.eq(false))
+        .filter(time_entries::start_time.ge(yesterday_start))
+        .filter(time_entries::start_time.le(yesterday_end))
+        .group_by(time_entries::user_id)
+        .select((
+            time_entries::user_id,
+            diesel::dsl::sum(time_entries::duration_seconds),
+        ))
+        .load::<(Uuid, Option<i64>)>(&mut conn)
+        .await
+        .map_err(ServiceError::from)?
+        .into_iter()
+        .map(|(member_uuid, seconds)| (member_uuid, seconds.unwrap_or(0)))
+        .collect();
+
+    let mut completed_by_member: HashMap<Uuid, Vec<StandupTask>> = HashMap::new();
+    for task in completed_tasks {
+        completed_by_member
+            .entry(task.user_id)
+            .or_default()
+            .push(StandupTask::from(task));
+    }
+    let mut planned_by_member: HashMap<Uuid, Vec<StandupTask>> = HashMap::new();
+    for task in planned_tasks {
+        planned_by_member
+            .entry(task.user_id)
+            .or_default()
+            .push(StandupTask::from(task));
+    }
+
+    let member_standups = members
+        .into_iter()
+        .map(|member| MemberStandup {
+            completed_yesterday: completed_by_member
+                .remove(&member.user_id)
+                .unwrap_or_default(),
+            tracked_seconds_yesterday: tracked_by_member.get(&member.user_id).copied().unwrap_or(0),
+            planned_today: planned_by_member
+                .remove(&member.user_id)
+                .unwrap_or_default(),
+            user_id: member.user_id,
+            role: member.role,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(WorkspaceStandupResponse {
+        workspace_id: workspace_uuid,
+        date: standup_date,
+        yesterday,
+        members: member_standups,
+    }))
+}
+
+// === GET /workspaces/{workspace_id_path}/leaderboard?metric=&period= ===
+// Classement amical des membres sur les projets de l'espace : temps de focus (sessions
+// pomodoro terminées) ou tâches terminées sur la période. Les membres ayant choisi
+// leaderboard_opt_out sont exclus du calcul, pas seulement masqués.
+#[get("/{workspace_id_path}/leaderboard")]
+pub async fn get_workspace_leaderboard_handler(
+    pool: web::Data<DbPool>,
+    authenticated_user: AuthenticatedUser,
+    cost_limiter: web::Data<CostLimiter>,
+    workspace_id_path: web::Path<Uuid>,
+    query: web::Query<LeaderboardQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_uuid = authenticated_user.id;
+    let workspace_uuid = workspace_id_path.into_inner();
+    let query = query.into_inner();
+
+    let metric = query
+        .metric
+        .as_deref()
+        .unwrap_or(LEADERBOARD_METRIC_FOCUS_TIME);
+    if !LEADERBOARD_METRICS.contains(&metric) {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid metric '{}'. Supported: {}",
+            metric,
+            LEADERBOARD_METRICS.join(", ")
+        )));
+    }
+    cost_limiter.charge(user_uuid, CostClass::Analytics)?;
+
+    // Obtenir une connexion du pool
+    let mut conn = pool.get().await?;
+
+    permissions::require_workspace(
+        &mut conn,
+        user_uuid,
+        workspace_uuid,
+        Permission::WorkspaceRead,
+    )
+    .await?;
+
+    let week_start =
+        settings::resolve_week_start(&mut conn, user_uuid, query.week_start.as_deref()).await?;
+    let (start_date, end_date) = calculate_date_range(
+        &AnalyticsQueryPeriod {
+            period: query.period,
+            start_date: query.start_date,
+            end_date: query.end_date,
+            week_start: None,
+        },
+        week_start,
+    )?;
+    let (start_datetime, end_datetime) = period_bounds(start_date, end_date);
+
+    let member_ids = workspace_members::table
+        .filter(workspace_members::workspace_id.eq(workspace_uuid))
+        .select(workspace_members::user_id)
+        .load::<Uuid>(&mut conn)
+        .await?;
+    let opted_out: HashSet<Uuid> = user_settings::table
+        .filter(user_settings::user_id.eq_any(&member_ids))
+        .filter(user_settings::leaderboard_opt_out.eq(true))
+        .select(user_settings::user_id)
+        .load::<Uuid>(&mut conn)
+        .await?
+        .into_iter()
+        .collect();
+    let participant_ids: Vec<Uuid> = member_ids
+        .into_iter()
+        .filter(|member_uuid| !opted_out.contains(member_uuid))
+        .collect();
+
+    let workspace_projects = projects::table
+        .filter(projects::workspace_id.eq(workspace_uuid))
+        .select(projects::id.nullable());
+
+    let values: HashMap<Uuid, i64> = if metric == LEADERBOARD_METRIC_FOCUS_TIME {
+        time_entries::table
+            .inner_join(tasks::table)
+            .filter(tasks::project_id.eq_any(workspace_projects))
+            .filter(time_entries::user_id.eq_any(&participant_ids))
+            .filter(time_entries::is_pomodoro_session.eq(true))
+            .filter(time_entries::end_time.is_not_null())
+            .filter(time_entries::start_time.ge(start_datetime))
+            .filter(time_entries::start_time.le(end_datetime))
+            .group_by(time_entries::user_id)
+            .select((
+                time_entries::user_id,
+                diesel::dsl::sum(time_entries::duration_seconds),
+            ))
+            .load::<(Uuid, Option<i64>)>(&mut conn)
+            .await?
+            .into_iter()
+            .map(|(member_uuid, seconds)| (member_uuid, seconds.unwrap_or(0)))
+            .collect()
+    } else {
+        // Même critère que le standup : tâche terminée datée par sa dernière mise à jour
+        tasks::table
+            .filter(tasks::project_id.eq_any(workspace_projects))
+            .filter(tasks::user_id.eq_any(&participant_ids))
+            .filter(tasks::status.eq_any(DONE_TASK_STATUSES))
+            .filter(tasks::updated_at.ge(start_datetime.naive_utc()))
+            .filter(tasks::updated_at.le(end_datetime.naive_utc()))
+            .group_by(tasks::user_id)
+            .select((tasks::user_id, diesel::dsl::count_star()))
+            .load::<(Uuid, i64)>(&mut conn)
+            .await?
+            .into_iter()
+            .collect()
+    };
+
+    let mut ranked: Vec<(Uuid, i64)> = participant_ids
+        .into_iter()
+        .map(|member_uuid| (member_uuid, values.get(&member_uuid).copied().unwrap_or(0)))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut entries: Vec<LeaderboardEntry> = Vec::with_capacity(ranked.len());
+    for (position, (member_uuid, value)) in ranked.into_iter().enumerate() {
+        let rank = match entries.last() {
+            Some(previous) if previous.value == value => previous.rank,
+            _ => position as i64 + 1,
+        };
+        entries.push(LeaderboardEntry {
+            rank,
+            user_id: member_uuid,
+            value,
+        });
+    }
+
+    Ok(HttpResponse::Ok().json(WorkspaceLeaderboardResponse {
+        workspace_id: workspace_uuid,
+        metric: metric.to_string(),
+        start_date,
+        end_date,
+        entries,
+    }))
+}
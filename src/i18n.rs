@@ -0,0 +1,325 @@
+// OptiTask/backend-api/src/i18n.rs
+// Localisation des textes générés par le serveur (erreurs, rapports). La langue est
+// négociée par requête à partir de l'en-tête Accept-Language et conservée dans une
+// variable locale à la tâche ; hors requête (tâches de fond), l'anglais s'applique.
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderValue, ACCEPT_LANGUAGE, CONTENT_LANGUAGE};
+use actix_web::middleware::Next;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        }
+    }
+
+    // Étiquette de langue principale ("fr-CA" => fr)
+    fn from_tag(tag: &str) -> Option<Locale> {
+        let primary = tag.split(['-', '_']).next()?.trim();
+        match primary.to_ascii_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "fr" => Some(Locale::Fr),
+            _ => None,
+        }
+    }
+
+    // Langue supportée préférée d'après Accept-Language (poids q compris)
+    pub fn negotiate(accept_language: &str) -> Locale {
+        let mut candidates: Vec<(f32, &str)> = accept_language
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let tag = parts.next()?.trim();
+                let weight = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+                (!tag.is_empty() && weight > 0.0).then_some((weight, tag))
+            })
+            .collect();
+        // Tri stable : à poids égal, l'ordre de l'en-tête est conservé
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+        candidates
+            .into_iter()
+            .find_map(|(_, tag)| Locale::from_tag(tag))
+            .unwrap_or_default()
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN_MESSAGES,
+            Locale::Fr => FR_MESSAGES,
+        }
+    }
+}
+
+// Catalogues de messages : clé => texte, "{}" étant remplacé par les arguments dans l'ordre
+const EN_MESSAGES: &[(&str, &str)] = &[
+    (
+        "error.internal",
+        "An internal server error occurred. Please try again later.",
+    ),
+    ("error.generic", "An error occurred."),
+    ("error.not_found", "The requested item was not found"),
+    ("error.database", "A database error occurred"),
+    ("error.database_operation", "Database operation failed: {}"),
+    ("error.pool", "Database connection pool error."),
+    ("error.pool_timeout", "Database connection timed out"),
+    ("error.invalid_json", "Invalid JSON format."),
+    ("error.invalid_uuid", "Invalid UUID format."),
+    ("error.invalid_number", "Invalid number format."),
+    ("error.invalid_multipart", "Invalid multipart payload."),
+    ("error.missing_permission", "Missing permission: {}"),
+    (
+        "error.read_only_mode",
+        "The service is in read-only mode for maintenance. Changes are temporarily disabled.",
+    ),
+    (
+        "error.workspace_not_found",
+        "Workspace with id {} not found or user is not a member",
+    ),
+    (
+        "error.project_not_found",
+        "Project with id {} not found or not accessible",
+    ),
+    (
+        "error.task_not_found",
+        "Task with id {} not found or not accessible",
+    ),
+    ("report.title", "Status report"),
+    ("report.period", "Period"),
+    ("report.generated", "Generated"),
+    ("report.open_tasks", "Open tasks"),
+    ("report.no_open_tasks", "No open tasks."),
+    ("report.due", "due"),
+    ("report.completed", "Completed this period"),
+    (
+        "report.nothing_completed",
+        "Nothing completed in this period.",
+    ),
+    ("report.time_per_task", "Time spent per task"),
+    ("report.task", "Task"),
+    ("report.time", "Time"),
+    ("report.total", "Total"),
+    ("export.page", "Page {} of {}"),
+    ("export.details", "Details"),
+    ("export.status", "Status"),
+    ("export.stage", "Stage"),
+    ("export.project", "Project"),
+    ("export.inbox", "Inbox"),
+    ("export.due_date", "Due date"),
+    ("export.scheduled", "Scheduled"),
+    ("export.labels", "Labels"),
+    ("export.created", "Created"),
+    ("export.description", "Description"),
+    ("export.no_description", "No description."),
+    ("export.custom_fields", "Custom fields"),
+    ("export.time_summary", "Time summary"),
+    ("export.tracked", "Time tracked"),
+    ("export.budget", "Time budget"),
+    ("export.entries", "Time entries"),
+    ("export.tracked_between", "Tracked between"),
+    (
+        "export.timer_running",
+        "A timer is currently running on this task.",
+    ),
+    ("export.completed_tasks", "Completed tasks"),
+    ("export.no_completed_tasks", "No completed tasks."),
+    ("analytics_export.sheet_time_by_project", "Time by project"),
+    ("analytics_export.sheet_daily_trend", "Daily trend"),
+    ("analytics_export.sheet_tasks", "Tasks"),
+    ("analytics_export.project", "Project"),
+    ("analytics_export.task", "Task"),
+    ("analytics_export.status", "Status"),
+    ("analytics_export.date", "Date"),
+    ("analytics_export.hours", "Hours"),
+    ("analytics_export.share", "Share (%)"),
+    ("analytics_export.entries", "Time entries"),
+    ("analytics_export.tasks_completed", "Tasks completed"),
+    ("analytics_export.inbox", "Inbox"),
+    ("analytics_export.total", "Total"),
+];
+
+const FR_MESSAGES: &[(&str, &str)] = &[
+    (
+        "error.internal",
+        "Une erreur interne est survenue. Veuillez réessayer plus tard.",
+    ),
+    ("error.generic", "Une erreur est survenue."),
+    ("error.not_found", "L'élément demandé est introuvable"),
+    (
+        "error.database",
+        "Une erreur de base de données est survenue",
+    ),
+    (
+        "error.database_operation",
+        "L'opération en base de données a échoué : {}",
+    ),
+    (
+        "error.pool",
+        "Erreur du pool de connexions à la base de données.",
+    ),
+    (
+        "error.pool_timeout",
+        "Délai de connexion à la base de données dépassé",
+    ),
+    ("error.invalid_json", "Format JSON invalide."),
+    ("error.invalid_uuid", "Format d'UUID invalide."),
+    ("error.invalid_number", "Format de nombre invalide."),
+    ("error.invalid_multipart", "Contenu multipart invalide."),
+    ("error.missing_permission", "Permission manquante : {}"),
+    (
+        "error.read_only_mode",
+        "Le service est en lecture seule pour maintenance. Les modifications sont temporairement désactivées.",
+    ),
+    (
+        "error.workspace_not_found",
+        "Espace {} introuvable ou l'utilisateur n'en est pas membre",
+    ),
+    (
+        "error.project_not_found",
+        "Projet {} introuvable ou inaccessible",
+    ),
+    (
+        "error.task_not_found",
+        "Tâche {} introuvable ou inaccessible",
+    ),
+    ("report.title", "Rapport d'avancement"),
+    ("report.period", "Période"),
+    ("report.generated", "Généré le"),
+    ("report.open_tasks", "Tâches ouvertes"),
+    ("report.no_open_tasks", "Aucune tâche ouverte."),
+    ("report.due", "échéance"),
+    ("report.completed", "Terminées sur la période"),
+    (
+        "report.nothing_completed",
+        "Rien n'a été terminé sur la période.",
+    ),
+    ("report.time_per_task", "Temps passé par tâche"),
+    ("report.task", "Tâche"),
+    ("report.time", "Temps"),
+    ("report.total", "Total"),
+    ("export.page", "Page {} sur {}"),
+    ("export.details", "Détails"),
+    ("export.status", "Statut"),
+    ("export.stage", "Horizon"),
+    ("export.project", "Projet"),
+    ("export.inbox", "Boîte de réception"),
+    ("export.due_date", "Échéance"),
+    ("export.scheduled", "Planifiée"),
+    ("export.labels", "Labels"),
+    ("export.created", "Créée le"),
+    ("export.description", "Description"),
+    ("export.no_description", "Aucune description."),
+    ("export.custom_fields", "Champs personnalisés"),
+    ("export.time_summary", "Temps suivi"),
+    ("export.tracked", "Temps suivi"),
+    ("export.budget", "Budget de temps"),
+    ("export.entries", "Entrées de temps"),
+    ("export.tracked_between", "Suivi entre"),
+    (
+        "export.timer_running",
+        "Un minuteur est en cours sur cette tâche.",
+    ),
+    ("export.completed_tasks", "Tâches terminées"),
+    ("export.no_completed_tasks", "Aucune tâche terminée."),
+    ("analytics_export.sheet_time_by_project", "Temps par projet"),
+    ("analytics_export.sheet_daily_trend", "Tendance quotidienne"),
+    ("analytics_export.sheet_tasks", "Tâches"),
+    ("analytics_export.project", "Projet"),
+    ("analytics_export.task", "Tâche"),
+    ("analytics_export.status", "Statut"),
+    ("analytics_export.date", "Date"),
+    ("analytics_export.hours", "Heures"),
+    ("analytics_export.share", "Part (%)"),
+    ("analytics_export.entries", "Entrées de temps"),
+    ("analytics_export.tasks_completed", "Tâches terminées"),
+    ("analytics_export.inbox", "Boîte de réception"),
+    ("analytics_export.total", "Total"),
+];
+
+tokio::task_local! {
+    static REQUEST_LOCALE: Locale;
+}
+
+// Langue de la requête en cours (anglais hors requête)
+pub fn current_locale() -> Locale {
+    REQUEST_LOCALE
+        .try_with(|locale| *locale)
+        .unwrap_or_default()
+}
+
+// Texte d'une clé dans la langue donnée, avec repli sur l'anglais puis sur la clé
+pub fn translate(locale: Locale, key: &'static str) -> &'static str {
+    let lookup = |catalog: &'static [(&'static str, &'static str)]| {
+        catalog
+            .iter()
+            .find(|(candidate, _)| *candidate == key)
+            .map(|(_, text)| *text)
+    };
+    lookup(locale.catalog())
+        .or_else(|| lookup(EN_MESSAGES))
+        .unwrap_or(key)
+}
+
+// Texte d'une clé dans la langue de la requête en cours
+pub fn t(key: &'static str) -> &'static str {
+    translate(current_locale(), key)
+}
+
+// Comme t, en substituant les "{}" du message par les arguments
+pub fn tf(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut segments = t(key).split("{}");
+    let mut message = segments.next().unwrap_or_default().to_string();
+    for segment in segments {
+        if let Some(arg) = args.next() {
+            message.push_str(&arg.to_string());
+        }
+        message.push_str(segment);
+    }
+    message
+}
+
+// Toutes les clés d'un préfixe ("report."), sans le préfixe, pour les templates
+pub fn messages_with_prefix(locale: Locale, prefix: &str) -> BTreeMap<&'static str, &'static str> {
+    EN_MESSAGES
+        .iter()
+        .filter_map(|(key, _)| {
+            key.strip_prefix(prefix)
+                .map(|short_key| (short_key, translate(locale, key)))
+        })
+        .collect()
+}
+
+// Middleware : négocie la langue de la requête et l'annonce via Content-Language
+pub async fn locale_middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let locale = req
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(Locale::negotiate)
+        .unwrap_or_default();
+
+    let mut response = REQUEST_LOCALE
+        .scope(locale, next.call(req))
+        .await?
+        .map_into_boxed_body();
+    response
+        .headers_mut()
+        .insert(CONTENT_LANGUAGE, HeaderValue::from_static(locale.code()));
+    Ok(response)
+}
@@ -0,0 +1,67 @@
+// OptiTask/backend-api/src/icons.rs
+// Icônes des projets et labels : un emoji (séquences ZWJ, drapeaux, keycaps compris)
+// ou un identifiant d'icône du frontend (ex: "folder", "lucide:briefcase", "mdi-home")
+use crate::error_handler::ServiceError;
+
+const MAX_ICON_LENGTH: usize = 64;
+// Un emoji composé (famille, drapeau de région…) dépasse rarement une dizaine de code points
+const MAX_EMOJI_CODE_POINTS: usize = 16;
+
+fn is_pictographic(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF // symboles, pictogrammes, émoticônes, transports, drapeaux
+            | 0x2600..=0x27BF // symboles divers et dingbats
+            | 0x2300..=0x23FF // symboles techniques (⌚, ⏰…)
+            | 0x2B00..=0x2BFF // flèches et formes (⭐, ⬛…)
+            | 0x2190..=0x21FF // flèches
+            | 0x3030 | 0x303D | 0x3297 | 0x3299
+            | 0x00A9 | 0x00AE | 0x203C | 0x2049 | 0x2122 | 0x2139
+    )
+}
+
+// Caractères autorisés uniquement en complément d'un pictogramme
+fn is_emoji_component(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x200D // zero width joiner
+            | 0xFE0E | 0xFE0F // sélecteurs de variation
+            | 0x20E3 // keycap
+            | 0xE0020..=0xE007F // tags (drapeaux de sous-régions)
+    ) || c.is_ascii_digit()
+        || c == '#'
+        || c == '*'
+}
+
+pub fn is_emoji(icon: &str) -> bool {
+    let code_points = icon.chars().count();
+    code_points <= MAX_EMOJI_CODE_POINTS
+        && icon
+            .chars()
+            .all(|c| is_pictographic(c) || is_emoji_component(c))
+        && (icon.chars().any(is_pictographic) || icon.contains('\u{20E3}'))
+}
+
+// Identifiant en minuscules : segments alphanumériques séparés par '-', '_' ou ':'
+fn is_icon_identifier(icon: &str) -> bool {
+    let separators = ['-', '_', ':'];
+    icon.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || separators.contains(&c))
+        && icon
+            .split(|c| separators.contains(&c))
+            .all(|segment| !segment.is_empty())
+}
+
+// Normalise l'icône reçue ; une chaîne vide équivaut à l'absence d'icône
+pub fn normalize_icon(icon: Option<&str>) -> Result<Option<String>, ServiceError> {
+    let Some(icon) = icon.map(str::trim).filter(|icon| !icon.is_empty()) else {
+        return Ok(None);
+    };
+    if icon.len() > MAX_ICON_LENGTH || !(is_emoji(icon) || is_icon_identifier(icon)) {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid icon '{}': expected a single emoji or an icon identifier such as \"folder\" or \"lucide:briefcase\" (max {} characters)",
+            icon, MAX_ICON_LENGTH
+        )));
+    }
+    Ok(Some(icon.to_string()))
+}
@@ -0,0 +1,338 @@
+// OptiTask/backend-api/src/inbound_email.rs
+// Capture de tâches par email : chaque utilisateur dispose d'une adresse secrète
+// (add+<token>@domaine) ; les webhooks Mailgun / SES (via SNS) créent la tâche.
+use crate::auth_utils;
+use crate::error_handler::ServiceError;
+use crate::models::{InboundEmailAddress, NewTask, Task};
+use crate::schema::{inbound_email_addresses, tasks};
+use crate::settings;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use uuid::Uuid;
+
+const ADDRESS_PREFIX: &str = "add";
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_DESCRIPTION_CHARS: usize = 10_000;
+const SOURCE_TYPE_EMAIL: &str = "email";
+
+// Active si INBOUND_EMAIL_WEBHOOK_SECRET est défini (voir config.rs)
+#[derive(Debug, Clone)]
+pub struct InboundEmailConfig {
+    pub domain: String,
+    // Secret partagé passé en `?key=` par le fournisseur dans l'URL du webhook
+    pub webhook_secret: String,
+}
+
+impl InboundEmailConfig {
+    pub fn address_for(&self, token: &str) -> String {
+        format!("{}+{}@{}", ADDRESS_PREFIX, token, self.domain)
+    }
+
+    pub fn verify_secret(&self, candidate: &str) -> bool {
+        auth_utils::constant_time_eq(&self.webhook_secret, candidate)
+    }
+
+    // Extrait le token d'une adresse "add+<token>@domaine", éventuellement
+    // au format "Nom <add+token@domaine>"
+    pub fn token_from_address(&self, raw_address: &str) -> Option<String> {
+        let address = match (raw_address.find('<'), raw_address.rfind('>')) {
+            (Some(start), Some(end)) if start < end => &raw_address[start + 1..end],
+            _ => raw_address,
+        };
+        let (local_part, domain) = address.trim().rsplit_once('@')?;
+        if !domain.eq_ignore_ascii_case(&self.domain) {
+            return None;
+        }
+        let (prefix, token) = local_part.split_once('+')?;
+        if !prefix.eq_ignore_ascii_case(ADDRESS_PREFIX) || token.is_empty() {
+            return None;
+        }
+        Some(token.to_lowercase())
+    }
+}
+
+// Message normalisé, quel que soit le fournisseur
+#[derive(Debug, Default)]
+pub struct InboundMessage {
+    pub recipients: Vec<String>,
+    pub sender: Option<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub attachment_count: usize,
+}
+
+fn generate_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+pub async fn find_address(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<InboundEmailAddress>, ServiceError> {
+    inbound_email_addresses::table
+        .find(user_uuid)
+        .select(InboundEmailAddress::as_select())
+        .first::<InboundEmailAddress>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// Charge l'adresse de l'utilisateur, en la créant à la première demande
+pub async fn load_or_create_address(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<InboundEmailAddress, ServiceError> {
+    diesel::insert_into(inbound_email_addresses::table)
+        .values((
+            inbound_email_addresses::user_id.eq(user_uuid),
+            inbound_email_addresses::token.eq(generate_token()),
+        ))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .await?;
+
+    inbound_email_addresses::table
+        .find(user_uuid)
+        .select(InboundEmailAddress::as_select())
+        .first::<InboundEmailAddress>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Remplace le token : l'ancienne adresse cesse immédiatement de fonctionner
+pub async fn rotate_address(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<InboundEmailAddress, ServiceError> {
+    let new_token = generate_token();
+
+    diesel::insert_into(inbound_email_addresses::table)
+        .values((
+            inbound_email_addresses::user_id.eq(user_uuid),
+            inbound_email_addresses::token.eq(&new_token),
+        ))
+        .on_conflict(inbound_email_addresses::user_id)
+        .do_update()
+        .set((
+            inbound_email_addresses::token.eq(&new_token),
+            inbound_email_addresses::created_at.eq(diesel::dsl::now),
+        ))
+        .returning(InboundEmailAddress::as_returning())
+        .get_result::<InboundEmailAddress>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+fn truncate_chars(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
+
+// Crée la tâche pour le premier destinataire correspondant à une adresse connue.
+// None si aucun destinataire ne correspond (message ignoré).
+pub async fn create_task_from_message(
+    conn: &mut AsyncPgConnection,
+    config: &InboundEmailConfig,
+    message: &InboundMessage,
+) -> Result<Option<Task>, ServiceError> {
+    let tokens: Vec<String> = message
+        .recipients
+        .iter()
+        .filter_map(|recipient| config.token_from_address(recipient))
+        .collect();
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+
+    let owner = inbound_email_addresses::table
+        .filter(inbound_email_addresses::token.eq_any(&tokens))
+        .select(inbound_email_addresses::user_id)
+        .first::<Uuid>(conn)
+        .await
+        .optional()?;
+    let Some(owner_uuid) = owner else {
+        return Ok(None);
+    };
+
+    let title = message
+        .subject
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| truncate_chars(s, MAX_TITLE_CHARS))
+        .unwrap_or_else(|| "(no subject)".to_string());
+    let description = message
+        .body
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .map(|b| truncate_chars(b, MAX_DESCRIPTION_CHARS));
+
+    // Pas de stockage de pièces jointes pour l'instant : elles sont ignorées
+    if message.attachment_count > 0 {
+        log::info!(
+            "Inbound email for user {}: {} attachment(s) ignored",
+            owner_uuid,
+            message.attachment_count
+        );
+    }
+
+    // Projet par défaut de l'utilisateur, sinon l'Inbox
+    let project_uuid = settings::default_project_for_new_task(conn, owner_uuid).await?;
+
+    let new_task = NewTask {
+        user_id: owner_uuid,
+        project_id: project_uuid,
+        title,
+        description,
+        status: None,
+        due_date: None,
+        order: None,
+        context: None,
+        scheduled_start: None,
+        scheduled_end: None,
+        follow_up: None,
+        stage: None,
+        time_budget_seconds: None,
+        source: Some(json!({
+            "type": SOURCE_TYPE_EMAIL,
+            "sender": message.sender,
+            "attachment_count": message.attachment_count
+        })),
+    };
+
+    let task = diesel::insert_into(tasks::table)
+        .values(&new_task)
+        .get_result::<Task>(conn)
+        .await?;
+
+    log::info!(
+        "Created task {} for user {} from inbound email (sender: {})",
+        task.id,
+        owner_uuid,
+        message.sender.as_deref().unwrap_or("unknown")
+    );
+    Ok(Some(task))
+}
+
+// --- Lecture minimale d'un message MIME brut (contenu SES) ---
+
+fn split_headers(raw: &str) -> (&str, &str) {
+    raw.split_once("\r\n\r\n")
+        .or_else(|| raw.split_once("\n\n"))
+        .unwrap_or((raw, ""))
+}
+
+// Valeur d'un en-tête, lignes de continuation comprises
+fn header_value(headers: &str, name: &str) -> Option<String> {
+    let mut value: Option<String> = None;
+    for line in headers.lines() {
+        if let Some(current) = value.as_mut() {
+            if line.starts_with(' ') || line.starts_with('\t') {
+                current.push(' ');
+                current.push_str(line.trim());
+                continue;
+            }
+            break;
+        }
+        if let Some((key, rest)) = line.split_once(':') {
+            if key.trim().eq_ignore_ascii_case(name) {
+                value = Some(rest.trim().to_string());
+            }
+        }
+    }
+    value
+}
+
+fn header_param(header: &str, param: &str) -> Option<String> {
+    header.split(';').skip(1).find_map(|part| {
+        let (key, value) = part.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(param)
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn decode_quoted_printable(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            // Saut de ligne "doux"
+            if bytes[i + 1..].starts_with(b"\r\n") {
+                i += 3;
+                continue;
+            }
+            if bytes[i + 1..].starts_with(b"\n") {
+                i += 2;
+                continue;
+            }
+            if let Some(hex) = input.get(i + 1..i + 3) {
+                if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// Corps texte d'une partie, si son encodage est pris en charge (7bit, 8bit, quoted-printable)
+fn decode_text_part(headers: &str, body: &str) -> Option<String> {
+    let encoding = header_value(headers, "Content-Transfer-Encoding")
+        .unwrap_or_else(|| "7bit".to_string())
+        .to_lowercase();
+    match encoding.as_str() {
+        "7bit" | "8bit" | "binary" => Some(body.to_string()),
+        "quoted-printable" => Some(decode_quoted_printable(body)),
+        _ => None,
+    }
+}
+
+// Renvoie le premier corps text/plain et le nombre de pièces jointes
+pub fn parse_mime_message(raw: &str) -> (Option<String>, usize) {
+    let (headers, body) = split_headers(raw);
+    let content_type = header_value(headers, "Content-Type")
+        .unwrap_or_else(|| "text/plain".to_string())
+        .to_lowercase();
+
+    if !content_type.starts_with("multipart/") {
+        let is_attachment = header_value(headers, "Content-Disposition")
+            .is_some_and(|d| d.to_lowercase().starts_with("attachment"));
+        return if is_attachment {
+            (None, 1)
+        } else if content_type.starts_with("text/plain") {
+            (decode_text_part(headers, body), 0)
+        } else {
+            (None, 0)
+        };
+    }
+
+    let Some(boundary) =
+        header_value(headers, "Content-Type").and_then(|ct| header_param(&ct, "boundary"))
+    else {
+        return (None, 0);
+    };
+
+    let delimiter = format!("--{}", boundary);
+    let mut text_body: Option<String> = None;
+    let mut attachment_count = 0;
+    for part in body.split(delimiter.as_str()).skip(1) {
+        if part.starts_with("--") {
+            break;
+        }
+        let (part_body, part_attachments) =
+            parse_mime_message(part.trim_start_matches(['\r', '\n']));
+        attachment_count += part_attachments;
+        if text_body.is_none() {
+            text_body = part_body;
+        }
+    }
+    (text_body, attachment_count)
+}
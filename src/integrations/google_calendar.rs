@@ -0,0 +1,629 @@
+// OptiTask/backend-api/src/integrations/google_calendar.rs
+// Connexion OAuth à Google Agenda et import périodique des événements récents
+// sous forme de suggestions d'entrées de temps.
+use super::{suggest_time_entries, CalendarEvent};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::TaskId;
+use crate::models::CalendarIntegration;
+use crate::outbound;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::{calendar_event_mappings, calendar_integrations, calendar_oauth_states, tasks};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const PROVIDER_GOOGLE: &str = "google";
+
+const AUTHORIZATION_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const CALENDAR_API_URL: &str = "https://www.googleapis.com/calendar/v3";
+// Lecture et écriture des événements (l'écriture sert à la synchronisation sortante)
+const OAUTH_SCOPE: &str = "https://www.googleapis.com/auth/calendar.events";
+const OAUTH_STATE_TTL_MINUTES: i64 = 10;
+// Marge avant expiration à partir de laquelle le jeton d'accès est renouvelé
+const TOKEN_REFRESH_MARGIN_SECS: i64 = 60;
+const SYNC_LOOKBACK_DAYS: i64 = 7;
+const SYNC_JOB_INTERVAL_SECS: u64 = 1800;
+// Propriété privée posée sur les événements créés pour une tâche planifiée
+const TASK_ID_PROPERTY: &str = "optitask_task_id";
+
+#[derive(Debug, Clone)]
+pub struct GoogleCalendarConfig {
+    pub client_id: String,
+    client_secret: String,
+    pub redirect_uri: String,
+    // Page du frontend vers laquelle rediriger après le consentement (facultative)
+    pub return_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: i64,
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EventsPage {
+    #[serde(default)]
+    items: Vec<GoogleEvent>,
+    #[serde(rename = "nextPageToken")]
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GoogleEvent {
+    id: String,
+    summary: Option<String>,
+    status: Option<String>,
+    start: Option<GoogleEventTime>,
+    end: Option<GoogleEventTime>,
+    #[serde(rename = "extendedProperties")]
+    extended_properties: Option<GoogleExtendedProperties>,
+}
+
+#[derive(Deserialize)]
+struct GoogleExtendedProperties {
+    #[serde(default)]
+    private: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct CreatedEvent {
+    id: String,
+}
+
+// Les événements "journée entière" n'ont qu'une date et sont ignorés
+#[derive(Deserialize)]
+struct GoogleEventTime {
+    #[serde(rename = "dateTime")]
+    date_time: Option<DateTime<Utc>>,
+}
+
+fn provider_error(context: &str, e: impl std::fmt::Display) -> ServiceError {
+    log::error!("Google Calendar {} failed: {}", context, e);
+    ServiceError::InternalServerError(format!("Google Calendar {} failed", context))
+}
+
+// .../calendars/{calendar_id}/events[/{event_id}], segments encodés
+fn events_url(calendar_id: &str, event_id: Option<&str>) -> Result<reqwest::Url, ServiceError> {
+    let mut url =
+        reqwest::Url::parse(CALENDAR_API_URL).map_err(|e| provider_error("events request", e))?;
+    {
+        let mut segments = url
+            .path_segments_mut()
+            .map_err(|_| provider_error("events request", "invalid base URL"))?;
+        segments.extend(["calendars", calendar_id, "events"]);
+        if let Some(event_id) = event_id {
+            segments.push(event_id);
+        }
+    }
+    Ok(url)
+}
+
+// Un événement supprimé côté calendrier renvoie 404 ou 410
+fn is_gone(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE
+}
+
+impl GoogleCalendarConfig {
+    // Construit au démarrage par config.rs, si les trois variables OAuth sont définies
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+        return_url: Option<String>,
+    ) -> GoogleCalendarConfig {
+        GoogleCalendarConfig {
+            client_id,
+            client_secret,
+            redirect_uri,
+            return_url,
+        }
+    }
+
+    pub fn authorization_url(&self, state: &str) -> String {
+        // access_type=offline + prompt=consent : garantit l'obtention d'un refresh token
+        reqwest::Url::parse_with_params(
+            AUTHORIZATION_URL,
+            &[
+                ("client_id", self.client_id.as_str()),
+                ("redirect_uri", self.redirect_uri.as_str()),
+                ("response_type", "code"),
+                ("scope", OAUTH_SCOPE),
+                ("access_type", "offline"),
+                ("prompt", "consent"),
+                ("state", state),
+            ],
+        )
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| AUTHORIZATION_URL.to_string())
+    }
+
+    async fn token_request(&self, form: &[(&str, &str)]) -> Result<TokenResponse, ServiceError> {
+        let response = outbound::send(
+            &outbound::CALENDAR_POLICY,
+            outbound::client().post(TOKEN_URL).form(form),
+        )
+        .await
+        .map_err(|e| provider_error("token request", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            log::warn!("Google token endpoint returned {}: {}", status, body);
+            return Err(ServiceError::BadRequest(
+                "Google Calendar authorization failed or was revoked. Connect the calendar again."
+                    .to_string(),
+            ));
+        }
+
+        response
+            .json::<TokenResponse>()
+            .await
+            .map_err(|e| provider_error("token response", e))
+    }
+
+    async fn exchange_code(&self, code: &str) -> Result<TokenResponse, ServiceError> {
+        self.token_request(&[
+            ("code", code),
+            ("client_id", &self.client_id),
+            ("client_secret", &self.client_secret),
+            ("redirect_uri", &self.redirect_uri),
+            ("grant_type", "authorization_code"),
+        ])
+        .await
+    }
+
+    async fn refresh(&self, refresh_token: &str) -> Result<TokenResponse, ServiceError> {
+        self.token_request(&[
+            ("refresh_token", refresh_token),
+            ("client_id", &self.client_id),
+            ("client_secret", &self.client_secret),
+            ("grant_type", "refresh_token"),
+        ])
+        .await
+    }
+
+    // Événements à occurrence unique (récurrences dépliées) démarrant dans l'intervalle
+    async fn list_events(
+        &self,
+        access_token: &str,
+        calendar_id: &str,
+        time_min: DateTime<Utc>,
+        time_max: DateTime<Utc>,
+    ) -> Result<Vec<CalendarEvent>, ServiceError> {
+        let url = events_url(calendar_id, None)?;
+
+        let mut events = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut request = outbound::client()
+                .get(url.clone())
+                .bearer_auth(access_token)
+                .query(&[
+                    ("timeMin", time_min.to_rfc3339()),
+                    ("timeMax", time_max.to_rfc3339()),
+                    ("singleEvents", "true".to_string()),
+                    ("orderBy", "startTime".to_string()),
+                    ("maxResults", "250".to_string()),
+                ]);
+            if let Some(token) = &page_token {
+                request = request.query(&[("pageToken", token)]);
+            }
+
+            let response = outbound::send(&outbound::CALENDAR_POLICY, request)
+                .await
+                .map_err(|e| provider_error("events request", e))?;
+            if !response.status().is_success() {
+                return Err(provider_error(
+                    "events request",
+                    format!("status {}", response.status()),
+                ));
+            }
+            let page = response
+                .json::<EventsPage>()
+                .await
+                .map_err(|e| provider_error("events response", e))?;
+
+            events.extend(page.items.into_iter().filter_map(|event| {
+                if event.status.as_deref() == Some("cancelled") {
+                    return None;
+                }
+                // Nos propres créneaux de tâches ne deviennent pas des suggestions
+                let pushed_by_us = event
+                    .extended_properties
+                    .as_ref()
+                    .is_some_and(|props| props.private.contains_key(TASK_ID_PROPERTY));
+                if pushed_by_us {
+                    return None;
+                }
+                let start = event.start.and_then(|t| t.date_time)?;
+                let end = event.end.and_then(|t| t.date_time)?;
+                Some(CalendarEvent {
+                    external_id: event.id,
+                    title: event
+                        .summary
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())?,
+                    start,
+                    end,
+                })
+            }));
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(events)
+    }
+
+    // Met à jour l'événement existant ou en crée un nouveau ; renvoie son identifiant
+    async fn put_event(
+        &self,
+        access_token: &str,
+        calendar_id: &str,
+        existing_event_id: Option<&str>,
+        body: &serde_json::Value,
+    ) -> Result<String, ServiceError> {
+        if let Some(event_id) = existing_event_id {
+            let request = outbound::client()
+                .put(events_url(calendar_id, Some(event_id))?)
+                .bearer_auth(access_token)
+                .json(body);
+            let response = outbound::send(&outbound::CALENDAR_POLICY, request)
+                .await
+                .map_err(|e| provider_error("event update", e))?;
+            if response.status().is_success() {
+                return Ok(event_id.to_string());
+            }
+            if !is_gone(response.status()) {
+                return Err(provider_error(
+                    "event update",
+                    format!("status {}", response.status()),
+                ));
+            }
+            // Supprimé dans le calendrier entre-temps : le recréer
+        }
+
+        let request = outbound::client()
+            .post(events_url(calendar_id, None)?)
+            .bearer_auth(access_token)
+            .json(body);
+        let response = outbound::send(&outbound::CALENDAR_POLICY, request)
+            .await
+            .map_err(|e| provider_error("event creation", e))?;
+        if !response.status().is_success() {
+            return Err(provider_error(
+                "event creation",
+                format!("status {}", response.status()),
+            ));
+        }
+        response
+            .json::<CreatedEvent>()
+            .await
+            .map(|event| event.id)
+            .map_err(|e| provider_error("event creation response", e))
+    }
+
+    async fn delete_event(
+        &self,
+        access_token: &str,
+        calendar_id: &str,
+        event_id: &str,
+    ) -> Result<(), ServiceError> {
+        let request = outbound::client()
+            .delete(events_url(calendar_id, Some(event_id))?)
+            .bearer_auth(access_token);
+        let response = outbound::send(&outbound::CALENDAR_POLICY, request)
+            .await
+            .map_err(|e| provider_error("event deletion", e))?;
+        if response.status().is_success() || is_gone(response.status()) {
+            Ok(())
+        } else {
+            Err(provider_error(
+                "event deletion",
+                format!("status {}", response.status()),
+            ))
+        }
+    }
+}
+
+// Démarre le flux OAuth : le state lie le retour de Google à l'utilisateur
+pub async fn create_oauth_state(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<String, ServiceError> {
+    diesel::delete(
+        calendar_oauth_states::table.filter(calendar_oauth_states::expires_at.lt(Utc::now())),
+    )
+    .execute(conn)
+    .await?;
+
+    let state = Uuid::new_v4().simple().to_string();
+    diesel::insert_into(calendar_oauth_states::table)
+        .values((
+            calendar_oauth_states::state.eq(&state),
+            calendar_oauth_states::user_id.eq(user_uuid),
+            calendar_oauth_states::expires_at
+                .eq(Utc::now() + ChronoDuration::minutes(OAUTH_STATE_TTL_MINUTES)),
+        ))
+        .execute(conn)
+        .await?;
+    Ok(state)
+}
+
+// Retour de Google : consomme le state, échange le code et enregistre les jetons
+pub async fn complete_oauth(
+    conn: &mut AsyncPgConnection,
+    config: &GoogleCalendarConfig,
+    state: &str,
+    code: &str,
+) -> Result<Uuid, ServiceError> {
+    let user_uuid = diesel::delete(
+        calendar_oauth_states::table
+            .filter(calendar_oauth_states::state.eq(state))
+            .filter(calendar_oauth_states::expires_at.ge(Utc::now())),
+    )
+    .returning(calendar_oauth_states::user_id)
+    .get_result::<Uuid>(conn)
+    .await
+    .optional()?
+    .ok_or_else(|| {
+        ServiceError::BadRequest(
+            "Invalid or expired OAuth state. Start the connection again.".to_string(),
+        )
+    })?;
+
+    let tokens = config.exchange_code(code).await?;
+    let expires_at = Utc::now() + ChronoDuration::seconds(tokens.expires_in);
+
+    let integration_uuid = diesel::insert_into(calendar_integrations::table)
+        .values((
+            calendar_integrations::user_id.eq(user_uuid),
+            calendar_integrations::provider.eq(PROVIDER_GOOGLE),
+            calendar_integrations::access_token.eq(&tokens.access_token),
+            calendar_integrations::refresh_token.eq(&tokens.refresh_token),
+            calendar_integrations::token_expires_at.eq(expires_at),
+        ))
+        .on_conflict((
+            calendar_integrations::user_id,
+            calendar_integrations::provider,
+        ))
+        .do_update()
+        .set((
+            calendar_integrations::access_token.eq(&tokens.access_token),
+            calendar_integrations::token_expires_at.eq(expires_at),
+        ))
+        .returning(calendar_integrations::id)
+        .get_result::<Uuid>(conn)
+        .await?;
+
+    // Google ne renvoie pas toujours de refresh token lors d'une reconnexion : garder l'ancien
+    if let Some(refresh_token) = &tokens.refresh_token {
+        diesel::update(calendar_integrations::table.find(integration_uuid))
+            .set(calendar_integrations::refresh_token.eq(refresh_token))
+            .execute(conn)
+            .await?;
+    }
+
+    log::info!("Google Calendar connected for user {}", user_uuid);
+    Ok(user_uuid)
+}
+
+pub async fn find_integration(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<CalendarIntegration>, ServiceError> {
+    calendar_integrations::table
+        .filter(calendar_integrations::user_id.eq(user_uuid))
+        .filter(calendar_integrations::provider.eq(PROVIDER_GOOGLE))
+        .select(CalendarIntegration::as_select())
+        .first::<CalendarIntegration>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// Jeton d'accès valide, renouvelé via le refresh token s'il expire bientôt
+pub async fn access_token(
+    conn: &mut AsyncPgConnection,
+    config: &GoogleCalendarConfig,
+    integration: &CalendarIntegration,
+) -> Result<String, ServiceError> {
+    if integration.token_expires_at
+        > Utc::now() + ChronoDuration::seconds(TOKEN_REFRESH_MARGIN_SECS)
+    {
+        return Ok(integration.access_token.clone());
+    }
+
+    let refresh_token = integration.refresh_token.as_deref().ok_or_else(|| {
+        ServiceError::BadRequest(
+            "Google Calendar access expired. Connect the calendar again.".to_string(),
+        )
+    })?;
+    let tokens = config.refresh(refresh_token).await?;
+    let expires_at = Utc::now() + ChronoDuration::seconds(tokens.expires_in);
+
+    diesel::update(calendar_integrations::table.find(integration.id))
+        .set((
+            calendar_integrations::access_token.eq(&tokens.access_token),
+            calendar_integrations::token_expires_at.eq(expires_at),
+        ))
+        .execute(conn)
+        .await?;
+    Ok(tokens.access_token)
+}
+
+// Importe les événements des derniers jours et crée les suggestions correspondantes
+pub async fn sync_integration(
+    conn: &mut AsyncPgConnection,
+    config: &GoogleCalendarConfig,
+    integration: &CalendarIntegration,
+) -> Result<usize, ServiceError> {
+    let token = access_token(conn, config, integration).await?;
+    let now = Utc::now();
+    let events = config
+        .list_events(
+            &token,
+            &integration.calendar_id,
+            now - ChronoDuration::days(SYNC_LOOKBACK_DAYS),
+            now,
+        )
+        .await?;
+
+    let created = suggest_time_entries(conn, integration.user_id, &events).await?;
+
+    diesel::update(calendar_integrations::table.find(integration.id))
+        .set(calendar_integrations::last_synced_at.eq(now))
+        .execute(conn)
+        .await?;
+
+    log::debug!(
+        "Google Calendar sync for user {}: {} event(s), {} new suggestion(s)",
+        integration.user_id,
+        events.len(),
+        created
+    );
+    Ok(created)
+}
+
+// Aligne l'événement du calendrier sur le créneau de la tâche : création ou mise à jour
+// si la tâche est planifiée, suppression si elle ne l'est plus ou n'existe plus.
+pub async fn push_task_schedule(
+    conn: &mut AsyncPgConnection,
+    config: &GoogleCalendarConfig,
+    owner_uuid: Uuid,
+    task_uuid: TaskId,
+) -> Result<(), ServiceError> {
+    let Some(integration) = find_integration(conn, owner_uuid).await? else {
+        return Ok(());
+    };
+
+    let scheduled_task = tasks::table
+        .find(task_uuid)
+        .select((
+            tasks::title,
+            tasks::description,
+            tasks::scheduled_start,
+            tasks::scheduled_end,
+        ))
+        .first::<(
+            String,
+            Option<String>,
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+        )>(conn)
+        .await
+        .optional()?;
+
+    let mapped_event_id = calendar_event_mappings::table
+        .filter(calendar_event_mappings::integration_id.eq(integration.id))
+        .filter(calendar_event_mappings::task_id.eq(task_uuid))
+        .select(calendar_event_mappings::external_event_id)
+        .first::<String>(conn)
+        .await
+        .optional()?;
+
+    match scheduled_task {
+        Some((title, description, Some(start), Some(end))) => {
+            let token = access_token(conn, config, &integration).await?;
+            let body = json!({
+                "summary": title,
+                "description": description,
+                "start": { "dateTime": start.to_rfc3339() },
+                "end": { "dateTime": end.to_rfc3339() },
+                "extendedProperties": { "private": { TASK_ID_PROPERTY: task_uuid.to_string() } },
+            });
+            let event_id = config
+                .put_event(
+                    &token,
+                    &integration.calendar_id,
+                    mapped_event_id.as_deref(),
+                    &body,
+                )
+                .await?;
+
+            diesel::insert_into(calendar_event_mappings::table)
+                .values((
+                    calendar_event_mappings::integration_id.eq(integration.id),
+                    calendar_event_mappings::task_id.eq(task_uuid),
+                    calendar_event_mappings::external_event_id.eq(&event_id),
+                ))
+                .on_conflict((
+                    calendar_event_mappings::integration_id,
+                    calendar_event_mappings::task_id,
+                ))
+                .do_update()
+                .set((
+                    calendar_event_mappings::external_event_id.eq(&event_id),
+                    calendar_event_mappings::synced_at.eq(Utc::now()),
+                ))
+                .execute(conn)
+                .await?;
+        }
+        _ => {
+            if let Some(event_id) = mapped_event_id {
+                let token = access_token(conn, config, &integration).await?;
+                config
+                    .delete_event(&token, &integration.calendar_id, &event_id)
+                    .await?;
+                diesel::delete(
+                    calendar_event_mappings::table
+                        .filter(calendar_event_mappings::integration_id.eq(integration.id))
+                        .filter(calendar_event_mappings::task_id.eq(task_uuid)),
+                )
+                .execute(conn)
+                .await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_sync(pool: &DbPool, config: &GoogleCalendarConfig) -> Result<(), ServiceError> {
+    let integrations = {
+        let mut conn = pool.get().await?;
+        calendar_integrations::table
+            .filter(calendar_integrations::provider.eq(PROVIDER_GOOGLE))
+            .select(CalendarIntegration::as_select())
+            .load::<CalendarIntegration>(&mut conn)
+            .await?
+    };
+
+    for integration in integrations {
+        let mut conn = pool.get().await?;
+        if let Err(e) = sync_integration(&mut conn, config, &integration).await {
+            log::warn!(
+                "Google Calendar sync failed for user {}: {}",
+                integration.user_id,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+// Lance la synchronisation périodique de toutes les intégrations Google
+pub fn spawn_sync_job(pool: DbPool, read_only: Arc<ReadOnlyMode>, config: GoogleCalendarConfig) {
+    actix_web::rt::spawn(async move {
+        let mut interval =
+            actix_web::rt::time::interval(std::time::Duration::from_secs(SYNC_JOB_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            if let Err(e) = run_sync(&pool, &config).await {
+                log::error!("Google Calendar sync job failed: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,119 @@
+// OptiTask/backend-api/src/integrations/mod.rs
+// Intégrations calendrier : les fournisseurs normalisent leurs événements en
+// `CalendarEvent`, la création des suggestions est commune à tous.
+pub mod google_calendar;
+
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::{ProjectId, TaskId};
+use crate::models::NewCalendarSuggestion;
+use crate::schema::{calendar_project_links, calendar_suggestions};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use google_calendar::GoogleCalendarConfig;
+use uuid::Uuid;
+
+const MAX_SUGGESTION_TITLE_CHARS: usize = 200;
+
+// Événement normalisé, quel que soit le fournisseur
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub external_id: String,
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+// Crée une suggestion pour chaque événement terminé dont le titre contient le mot-clé
+// d'un projet lié (le plus long l'emporte). Un événement déjà connu, quel que soit
+// son statut, n'est jamais suggéré à nouveau.
+pub async fn suggest_time_entries(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    events: &[CalendarEvent],
+) -> Result<usize, ServiceError> {
+    let mut links = calendar_project_links::table
+        .filter(calendar_project_links::user_id.eq(user_uuid))
+        .select((
+            calendar_project_links::project_id,
+            calendar_project_links::keyword,
+        ))
+        .load::<(ProjectId, String)>(conn)
+        .await?;
+    if links.is_empty() {
+        return Ok(0);
+    }
+    links.sort_by_key(|(_, keyword)| std::cmp::Reverse(keyword.chars().count()));
+    let links: Vec<(ProjectId, String)> = links
+        .into_iter()
+        .map(|(project_uuid, keyword)| (project_uuid, keyword.to_lowercase()))
+        .collect();
+
+    let now = Utc::now();
+    let suggestions: Vec<NewCalendarSuggestion> = events
+        .iter()
+        .filter(|event| event.end <= now && event.end > event.start)
+        .filter_map(|event| {
+            let title = event.title.to_lowercase();
+            links
+                .iter()
+                .find(|(_, keyword)| title.contains(keyword.as_str()))
+                .map(|(project_uuid, _)| NewCalendarSuggestion {
+                    user_id: user_uuid,
+                    project_id: *project_uuid,
+                    external_event_id: event.external_id.clone(),
+                    title: event
+                        .title
+                        .chars()
+                        .take(MAX_SUGGESTION_TITLE_CHARS)
+                        .collect(),
+                    start_time: event.start,
+                    end_time: event.end,
+                })
+        })
+        .collect();
+
+    if suggestions.is_empty() {
+        return Ok(0);
+    }
+
+    diesel::insert_into(calendar_suggestions::table)
+        .values(&suggestions)
+        .on_conflict((
+            calendar_suggestions::user_id,
+            calendar_suggestions::external_event_id,
+        ))
+        .do_nothing()
+        .execute(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Répercute le créneau d'une tâche sur le calendrier de son propriétaire, en arrière-plan :
+// une erreur du fournisseur ne doit pas faire échouer la modification de la tâche.
+pub fn spawn_task_schedule_sync(
+    pool: DbPool,
+    google_calendar: Option<GoogleCalendarConfig>,
+    owner_uuid: Uuid,
+    task_uuid: TaskId,
+) {
+    let Some(config) = google_calendar else {
+        return;
+    };
+    actix_web::rt::spawn(async move {
+        let result = async {
+            let mut conn = pool.get().await?;
+            google_calendar::push_task_schedule(&mut conn, &config, owner_uuid, task_uuid).await
+        }
+        .await;
+        if let Err(e) = result {
+            log::warn!(
+                "Calendar push failed for task {} of user {}: {}",
+                task_uuid,
+                owner_uuid,
+                e
+            );
+        }
+    });
+}
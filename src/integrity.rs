@@ -0,0 +1,264 @@
+// OptiTask/backend-api/src/integrity.rs
+// Audit d'intégrité des données : associations orphelines, entrées de temps rattachées
+// à des tâches supprimées, durées négatives ou incohérentes avec start/end, horloges client
+// invraisemblables (suspect_clock). Les
+// corrections sûres sont appliquées dans une transaction ; les entrées des semaines
+// soumises ou validées ne sont jamais modifiées.
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::timesheets::ENTRY_UNLOCKED_SQL;
+use chrono::{DateTime, Utc};
+use diesel::sql_types::{BigInt, Nullable, Uuid as DieselUuid};
+use diesel::{sql_query, QueryableByName};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Fréquence de l'audit global (résultats journalisés)
+const INTEGRITY_JOB_INTERVAL_SECS: u64 = 86400;
+const MAX_SAMPLE_IDS: i64 = 20;
+
+// Durée calculée à partir de start/end, arrondie à la seconde inférieure comme dans les handlers
+pub const COMPUTED_DURATION_SQL: &str = "FLOOR(EXTRACT(EPOCH FROM (end_time - start_time)))::int";
+
+struct IntegrityCheck {
+    name: &'static str,
+    description: &'static str,
+    // Identifiant échantillonné dans le rapport
+    id_column: &'static str,
+    // FROM ... WHERE ..., $1 = utilisateur (NULL pour tous)
+    from_where: &'static str,
+    // Correction appliquée aux lignes de from_where (None : revue manuelle)
+    repair: Option<fn(&str) -> String>,
+}
+
+const CHECKS: [IntegrityCheck; 6] = [
+    IntegrityCheck {
+        name: "orphaned_task_labels",
+        description: "Label associations whose task or label no longer exists",
+        id_column: "tl.task_id",
+        from_where: "FROM task_labels tl \
+             LEFT JOIN tasks t ON t.id = tl.task_id \
+             LEFT JOIN labels l ON l.id = tl.label_id \
+             WHERE (t.id IS NULL OR l.id IS NULL) \
+             AND ($1::uuid IS NULL OR t.user_id = $1 OR l.user_id = $1)",
+        repair: Some(|from_where| {
+            format!(
+                "DELETE FROM task_labels WHERE (task_id, label_id) IN \
+                 (SELECT tl.task_id, tl.label_id {})",
+                from_where
+            )
+        }),
+    },
+    IntegrityCheck {
+        name: "orphaned_time_entries",
+        description: "Time entries pointing at a deleted task",
+        id_column: "te.id",
+        from_where: "FROM time_entries te \
+             WHERE NOT EXISTS (SELECT 1 FROM tasks t WHERE t.id = te.task_id) \
+             AND ($1::uuid IS NULL OR te.user_id = $1)",
+        repair: Some(|from_where| {
+            format!(
+                "DELETE FROM time_entries WHERE id IN (SELECT te.id {} AND {})",
+                from_where, ENTRY_UNLOCKED_SQL
+            )
+        }),
+    },
+    IntegrityCheck {
+        name: "negative_durations",
+        description: "Time entries with a negative duration",
+        id_column: "te.id",
+        from_where: "FROM time_entries te \
+             WHERE te.duration_seconds < 0 \
+             AND ($1::uuid IS NULL OR te.user_id = $1)",
+        // Recalculée si start/end le permettent, sinon effacée
+        repair: Some(|from_where| {
+            format!(
+                "UPDATE time_entries SET duration_seconds = \
+                 CASE WHEN end_time IS NOT NULL AND end_time >= start_time THEN {} ELSE NULL END, \
+                 updated_at = NOW() \
+                 WHERE id IN (SELECT te.id {} AND {})",
+                COMPUTED_DURATION_SQL, from_where, ENTRY_UNLOCKED_SQL
+            )
+        }),
+    },
+    IntegrityCheck {
+        name: "inconsistent_durations",
+        description: "Finished time entries whose duration differs from end - start (or is missing)",
+        id_column: "te.id",
+        from_where: "FROM time_entries te \
+             WHERE te.end_time IS NOT NULL AND te.end_time >= te.start_time \
+             AND (te.duration_seconds IS NULL OR (te.duration_seconds >= 0 \
+             AND ABS(te.duration_seconds - EXTRACT(EPOCH FROM (te.end_time - te.start_time))) > 1)) \
+             AND ($1::uuid IS NULL OR te.user_id = $1)",
+        repair: Some(|from_where| {
+            format!(
+                "UPDATE time_entries SET duration_seconds = {}, updated_at = NOW() \
+                 WHERE id IN (SELECT te.id {} AND {})",
+                COMPUTED_DURATION_SQL, from_where, ENTRY_UNLOCKED_SQL
+            )
+        }),
+    },
+    IntegrityCheck {
+        name: "end_before_start",
+        description: "Time entries ending before they start (manual review required)",
+        id_column: "te.id",
+        from_where: "FROM time_entries te \
+             WHERE te.end_time < te.start_time \
+             AND ($1::uuid IS NULL OR te.user_id = $1)",
+        repair: None,
+    },
+    IntegrityCheck {
+        name: "suspect_clock",
+        description: "Time entries submitted with an implausible client clock (manual review required)",
+        id_column: "te.id",
+        from_where: "FROM time_entries te \
+             WHERE te.suspect_clock \
+             AND ($1::uuid IS NULL OR te.user_id = $1)",
+        repair: None,
+    },
+];
+
+#[derive(QueryableByName)]
+struct CountRow {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+#[derive(QueryableByName)]
+struct IdRow {
+    #[diesel(sql_type = DieselUuid)]
+    id: Uuid,
+}
+
+#[derive(Serialize, Debug)]
+pub struct IntegrityIssue {
+    pub check: &'static str,
+    pub description: &'static str,
+    pub count: i64,
+    pub sample_ids: Vec<Uuid>,
+    pub repairable: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct IntegrityReport {
+    pub checked_at: DateTime<Utc>,
+    pub total_issues: i64,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RepairSummary {
+    // Lignes corrigées par vérification
+    pub repaired: BTreeMap<&'static str, usize>,
+    // Audit après correction (restent les cas verrouillés ou à revoir manuellement)
+    pub report: IntegrityReport,
+}
+
+// Audite les données d'un utilisateur, ou de tous si owner vaut None
+pub async fn audit(
+    conn: &mut AsyncPgConnection,
+    owner: Option<Uuid>,
+) -> Result<IntegrityReport, ServiceError> {
+    let mut issues = Vec::new();
+    for check in &CHECKS {
+        let count = sql_query(format!("SELECT COUNT(*) AS count {}", check.from_where))
+            .bind::<Nullable<DieselUuid>, _>(owner)
+            .get_result::<CountRow>(conn)
+            .await?
+            .count;
+        let sample_ids = if count > 0 {
+            sql_query(format!(
+                "SELECT DISTINCT {} AS id {} LIMIT {}",
+                check.id_column, check.from_where, MAX_SAMPLE_IDS
+            ))
+            .bind::<Nullable<DieselUuid>, _>(owner)
+            .load::<IdRow>(conn)
+            .await?
+            .into_iter()
+            .map(|row| row.id)
+            .collect()
+        } else {
+            Vec::new()
+        };
+        issues.push(IntegrityIssue {
+            check: check.name,
+            description: check.description,
+            count,
+            sample_ids,
+            repairable: check.repair.is_some(),
+        });
+    }
+
+    Ok(IntegrityReport {
+        checked_at: Utc::now(),
+        total_issues: issues.iter().map(|issue| issue.count).sum(),
+        issues,
+    })
+}
+
+// Applique les corrections sûres aux données de l'utilisateur, en une transaction
+pub async fn repair(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<RepairSummary, ServiceError> {
+    let repaired = conn
+        .transaction::<_, ServiceError, _>(|conn| {
+            async move {
+                let mut repaired = BTreeMap::new();
+                for check in &CHECKS {
+                    let Some(build_repair) = check.repair else {
+                        continue;
+                    };
+                    let affected = sql_query(build_repair(check.from_where))
+                        .bind::<Nullable<DieselUuid>, _>(Some(user_uuid))
+                        .execute(conn)
+                        .await?;
+                    repaired.insert(check.name, affected);
+                }
+                Ok(repaired)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(RepairSummary {
+        repaired,
+        report: audit(conn, Some(user_uuid)).await?,
+    })
+}
+
+// Lance l'audit global périodique ; les problèmes détectés sont journalisés
+pub fn spawn_integrity_job(pool: DbPool, read_only: Arc<ReadOnlyMode>) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(
+            INTEGRITY_JOB_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            let result = match pool.get().await {
+                Ok(mut conn) => audit(&mut conn, None).await,
+                Err(e) => Err(ServiceError::from(e)),
+            };
+            match result {
+                Ok(report) => {
+                    for issue in report.issues.iter().filter(|issue| issue.count > 0) {
+                        log::warn!(
+                            "Integrity check {} found {} issue(s)",
+                            issue.check,
+                            issue.count
+                        );
+                    }
+                }
+                Err(e) => log::error!("Integrity audit job failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,143 @@
+// OptiTask/backend-api/src/llm.rs
+use crate::config::LlmConfig;
+use crate::error_handler::ServiceError;
+use crate::outbound;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+
+// Fournisseur de génération de texte. Les handlers dépendent de ce trait uniquement,
+// le fournisseur concret est choisi au démarrage via les variables d'environnement.
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    // Nom stocké avec les contenus générés (ex: "openai-compatible:gpt-4o-mini")
+    fn name(&self) -> String;
+
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+    ) -> Result<String, ServiceError>;
+}
+
+// Fournisseur compatible avec l'API OpenAI "chat completions"
+// (OpenAI, Mistral, Ollama, vLLM, ...)
+pub struct OpenAiCompatibleProvider {
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatCompletionChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionChoice {
+    message: ChatCompletionMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionMessage {
+    content: String,
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiCompatibleProvider {
+    fn name(&self) -> String {
+        format!("openai-compatible:{}", self.model)
+    }
+
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+    ) -> Result<String, ServiceError> {
+        let url = format!("{}/chat/completions", self.base_url.trim_end_matches('/'));
+        let mut request = outbound::client().post(&url).json(&json!({
+            "model": self.model,
+            "messages": [
+                { "role": "system", "content": system_prompt },
+                { "role": "user", "content": user_prompt }
+            ]
+        }));
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = outbound::send(&outbound::LLM_POLICY, request)
+            .await
+            .map_err(|e| {
+                log::error!("LLM request to {} failed: {}", url, e);
+                ServiceError::InternalServerError("LLM provider request failed".to_string())
+            })?;
+
+        if !response.status().is_success() {
+            log::error!("LLM provider returned status {}", response.status());
+            return Err(ServiceError::InternalServerError(
+                "LLM provider returned an error".to_string(),
+            ));
+        }
+
+        let completion = response
+            .json::<ChatCompletionResponse>()
+            .await
+            .map_err(|e| {
+                log::error!("Invalid LLM provider response: {}", e);
+                ServiceError::InternalServerError("Invalid LLM provider response".to_string())
+            })?;
+
+        completion
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content.trim().to_string())
+            .filter(|content| !content.is_empty())
+            .ok_or_else(|| {
+                ServiceError::InternalServerError("LLM provider returned no content".to_string())
+            })
+    }
+}
+
+// Fournisseur hors-ligne utilisé quand aucun LLM n'est configuré :
+// renvoie les faits fournis dans le prompt utilisateur sous forme de texte brut.
+pub struct OfflineProvider;
+
+#[async_trait]
+impl LlmProvider for OfflineProvider {
+    fn name(&self) -> String {
+        "offline".to_string()
+    }
+
+    async fn complete(
+        &self,
+        _system_prompt: &str,
+        user_prompt: &str,
+    ) -> Result<String, ServiceError> {
+        Ok(user_prompt.trim().to_string())
+    }
+}
+
+// LLM_API_URL active le fournisseur HTTP ; LLM_API_KEY et LLM_MODEL sont optionnels
+pub fn provider_from_config(config: Option<&LlmConfig>) -> Arc<dyn LlmProvider> {
+    match config {
+        Some(config) => {
+            log::info!(
+                "LLM provider configured: {} ({})",
+                config.api_url,
+                config.model
+            );
+            Arc::new(OpenAiCompatibleProvider {
+                base_url: config.api_url.clone(),
+                api_key: config.api_key.clone(),
+                model: config.model.clone(),
+            })
+        }
+        None => {
+            log::info!("LLM_API_URL not set, using offline summary provider");
+            Arc::new(OfflineProvider)
+        }
+    }
+}
@@ -0,0 +1,246 @@
+// OptiTask/backend-api/src/macros.rs
+// Macros d'actions rapides : des suites d'opérations existantes (saisie de temps, statut,
+// horizon) déclenchées d'un raccourci. Les définitions vivent dans les préférences client,
+// espace de noms "macros" ; leur exécution se fait côté serveur, en une transaction.
+use crate::error_handler::ServiceError;
+use crate::feature_flags;
+use crate::focus_sessions;
+use crate::handlers::task_handlers::{parse_task_stage, run_completion_hooks, CompletionEffects};
+use crate::ids::TaskId;
+use crate::models::{
+    MacroDocument, MacroStep, NewTimeEntry, QuickActionMacro, Task, TimeEntry, DONE_TASK_STATUSES,
+};
+use crate::notifications::{self, TaskActivity};
+use crate::permissions::{self, Permission};
+use crate::schema::{tasks, time_entries};
+use crate::settings;
+use crate::timesheets::ensure_entry_unlocked;
+use crate::wip_limits;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+pub const MACROS_NAMESPACE: &str = "macros";
+
+const MAX_MACROS: usize = 50;
+const MAX_STEPS_PER_MACRO: usize = 10;
+const MAX_MACRO_NAME_CHARS: usize = 100;
+const MAX_SHORTCUT_CHARS: usize = 50;
+const MAX_STATUS_CHARS: usize = 50;
+// Durée maximale d'une saisie de temps (24 h)
+const MAX_LOG_MINUTES: i64 = 24 * 60;
+
+fn validate_step(step: &MacroStep) -> Result<(), ServiceError> {
+    match step {
+        MacroStep::LogTime { minutes, .. } => {
+            if !(1..=MAX_LOG_MINUTES).contains(minutes) {
+                return Err(ServiceError::ValidationError(format!(
+                    "log_time minutes must be between 1 and {}",
+                    MAX_LOG_MINUTES
+                )));
+            }
+        }
+        MacroStep::SetStatus { status } => {
+            if status.trim().is_empty() || status.chars().count() > MAX_STATUS_CHARS {
+                return Err(ServiceError::ValidationError(format!(
+                    "set_status expects a status of 1 to {} characters",
+                    MAX_STATUS_CHARS
+                )));
+            }
+        }
+        MacroStep::SetStage { stage } => {
+            parse_task_stage(stage)?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_macro(quick_action: &QuickActionMacro) -> Result<(), ServiceError> {
+    feature_flags::validate_key(&quick_action.id)?;
+    let name = quick_action.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_MACRO_NAME_CHARS {
+        return Err(ServiceError::ValidationError(format!(
+            "Macro names must contain between 1 and {} characters",
+            MAX_MACRO_NAME_CHARS
+        )));
+    }
+    if quick_action
+        .shortcut
+        .as_ref()
+        .is_some_and(|shortcut| shortcut.chars().count() > MAX_SHORTCUT_CHARS)
+    {
+        return Err(ServiceError::ValidationError(format!(
+            "shortcut cannot exceed {} characters",
+            MAX_SHORTCUT_CHARS
+        )));
+    }
+    if quick_action.steps.is_empty() || quick_action.steps.len() > MAX_STEPS_PER_MACRO {
+        return Err(ServiceError::ValidationError(format!(
+            "Macro '{}' must have between 1 and {} steps",
+            quick_action.id, MAX_STEPS_PER_MACRO
+        )));
+    }
+    quick_action.steps.iter().try_for_each(validate_step)
+}
+
+// Valide le document de l'espace de noms "macros" (appelé avant tout enregistrement)
+pub fn parse_document(data: &serde_json::Value) -> Result<MacroDocument, ServiceError> {
+    let document = serde_json::from_value::<MacroDocument>(data.clone())
+        .map_err(|e| ServiceError::ValidationError(format!("Invalid macro definitions: {}", e)))?;
+    if document.macros.len() > MAX_MACROS {
+        return Err(ServiceError::ValidationError(format!(
+            "At most {} macros can be defined",
+            MAX_MACROS
+        )));
+    }
+    for (position, quick_action) in document.macros.iter().enumerate() {
+        validate_macro(quick_action)?;
+        if document.macros[..position]
+            .iter()
+            .any(|other| other.id == quick_action.id)
+        {
+            return Err(ServiceError::ValidationError(format!(
+                "Duplicate macro id '{}'",
+                quick_action.id
+            )));
+        }
+    }
+    Ok(document)
+}
+
+// Macros définies par l'utilisateur (liste vide s'il n'en a enregistré aucune)
+pub async fn load_macros(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Vec<QuickActionMacro>, ServiceError> {
+    let stored = settings::load_client_preferences(conn, user_uuid, MACROS_NAMESPACE).await?;
+    Ok(stored
+        .and_then(|preferences| serde_json::from_value::<MacroDocument>(preferences.data).ok())
+        .unwrap_or_default()
+        .macros)
+}
+
+// Tâche du timer en cours de l'utilisateur
+pub async fn running_timer_task(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<TaskId>, ServiceError> {
+    time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::end_time.is_null())
+        .filter(time_entries::is_break.eq(false))
+        .order(time_entries::start_time.desc())
+        .select(time_entries::task_id)
+        .first::<TaskId>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+async fn set_status(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    task: Task,
+    new_status: &str,
+    completion_effects: &mut Vec<CompletionEffects>,
+) -> Result<Task, ServiceError> {
+    if task.status == new_status {
+        return Ok(task);
+    }
+    if let Some(project_uuid) = task.project_id {
+        wip_limits::ensure_capacity(conn, project_uuid, new_status, Some(task.id)).await?;
+    }
+
+    let updated_task = diesel::update(tasks::table.find(task.id))
+        .set((
+            tasks::status.eq(new_status),
+            tasks::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .get_result::<Task>(conn)
+        .await?;
+    notifications::notify_task_watchers(
+        conn,
+        TaskActivity::status_changed(
+            user_uuid,
+            updated_task.id,
+            &updated_task.title,
+            &task.status,
+            &updated_task.status,
+        ),
+    )
+    .await?;
+
+    if !DONE_TASK_STATUSES.contains(&task.status.as_str())
+        && DONE_TASK_STATUSES.contains(&updated_task.status.as_str())
+    {
+        let (completed_task, _, effects) = run_completion_hooks(conn, updated_task).await?;
+        completion_effects.push(effects);
+        return Ok(completed_task);
+    }
+    Ok(updated_task)
+}
+
+// Exécute les étapes dans l'ordre sur la tâche ; l'appelant fournit la transaction.
+// Renvoie la tâche finale, les entrées de temps créées et les suites des complétions,
+// à déclencher après le commit.
+pub async fn run_macro(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    quick_action: &QuickActionMacro,
+    task_uuid: TaskId,
+) -> Result<(Task, Vec<TimeEntry>, Vec<CompletionEffects>), ServiceError> {
+    let mut task =
+        permissions::require_task(conn, user_uuid, task_uuid, Permission::TaskWrite).await?;
+    let mut created_entries = Vec::new();
+    let mut completion_effects = Vec::new();
+
+    for step in &quick_action.steps {
+        validate_step(step)?;
+        match step {
+            MacroStep::LogTime { minutes, pomodoro } => {
+                let end = Utc::now();
+                let start = end - Duration::minutes(*minutes);
+                ensure_entry_unlocked(conn, user_uuid, start).await?;
+                let focus_session_id = focus_sessions::active_session_id(conn, user_uuid).await?;
+                let entry = diesel::insert_into(time_entries::table)
+                    .values(&NewTimeEntry {
+                        user_id: user_uuid,
+                        task_id: task.id,
+                        start_time: start,
+                        end_time: Some(end),
+                        duration_seconds: Some((*minutes * 60) as i32),
+                        is_pomodoro_session: Some(*pomodoro),
+                        is_break: None,
+                        clock_skew_seconds: None,
+                        suspect_clock: None,
+                        focus_session_id,
+                    })
+                    .get_result::<TimeEntry>(conn)
+                    .await?;
+                created_entries.push(entry);
+            }
+            MacroStep::SetStatus { status } => {
+                task = set_status(
+                    conn,
+                    user_uuid,
+                    task,
+                    status.trim(),
+                    &mut completion_effects,
+                )
+                .await?;
+            }
+            MacroStep::SetStage { stage } => {
+                task = diesel::update(tasks::table.find(task.id))
+                    .set((
+                        tasks::stage.eq(parse_task_stage(stage)?),
+                        tasks::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<Task>(conn)
+                    .await?;
+            }
+        }
+    }
+
+    Ok((task, created_entries, completion_effects))
+}
@@ -1,25 +1,94 @@
 // OptiTask/backend-api/src/main.rs
+mod account;
+mod activity_feed;
+mod admin;
+mod aging_rules;
+mod analytics_export;
+mod api_keys;
 mod auth_utils;
+mod automations;
+mod backup_restore;
+mod backups;
+mod badges;
+mod batch;
+mod caldav;
+mod clock_skew;
+mod colors;
+mod config;
+mod confirmations;
+mod cors;
+mod cost_limits;
+mod currency;
+mod custom_fields;
+mod date_validation;
 mod db;
+mod demo;
+mod dry_run;
 mod error_handler;
+mod events;
+mod experiments;
+mod exports;
+mod external_links;
+mod feature_flags;
+mod focus_sessions;
 mod handlers;
+mod i18n;
+mod icons;
+mod ids;
+mod inbound_email;
+mod integrations;
+mod integrity;
+mod llm;
+mod macros;
+mod maintenance;
+mod mentions;
+mod metadata;
 mod models;
+mod notifications;
+mod nudges;
+mod onboarding;
+mod outbound;
+mod pagination;
+mod pdf;
+mod permissions;
+mod project_completion;
+mod project_merge;
+mod push;
+mod quotas;
+mod read_only_mode;
+mod recents;
+mod reports;
+mod repository;
 pub mod schema;
+mod schema_check;
+mod settings;
+mod sla;
+mod themes;
+mod time_budgets;
+mod timer_auto_stop;
+mod timer_recovery;
+mod timesheets;
+mod tracked_time;
+mod wip_limits;
 
-use actix_cors::Cors;
-use actix_web::{http::header, middleware::Logger, web, App, HttpResponse, HttpServer};
+use actix_web::{
+    middleware::{from_fn, Logger},
+    web, App, HttpResponse, HttpServer,
+};
 use db::DbPool;
-use std::env;
+use std::sync::Arc;
 
 // Health check handler avec async
 async fn health_check_handler(
     pool: web::Data<DbPool>,
+    read_only_mode: web::Data<read_only_mode::ReadOnlyMode>,
 ) -> Result<HttpResponse, error_handler::ServiceError> {
     // Test de connexion au pool
     match pool.get().await {
         Ok(_conn) => Ok(HttpResponse::Ok().json(serde_json::json!({
             "status": "healthy",
-            "message": "Backend is running and DB pool accessible"
+            "message": "Backend is running and DB pool accessible",
+            "read_only": read_only_mode.status().enabled
         }))),
         Err(e) => {
             log::error!("Failed to get connection from pool: {:?}", e);
@@ -46,92 +115,468 @@ async fn main() -> std::io::Result<()> {
         }
     }
 
-    // Récupérer DATABASE_URL
-    let database_url = env::var("DATABASE_URL")
-        .expect("DATABASE_URL must be set in environment variables or .env file");
+    // Lire et valider la configuration (arrêt immédiat si une variable est invalide)
+    let app_config = config::AppConfig::from_env()?;
 
     // Créer le pool de connexions async
-    let pool = db::create_pool(&database_url)
+    let pool = db::create_pool(&app_config.database)
         .await
         .expect("Failed to create database connection pool.");
 
+    // Le schéma réel doit correspondre à schema.rs avant de servir la moindre requête
+    schema_check::verify_schema(&pool, app_config.schema_check).await?;
+
     log::info!("🚀 OptiTask Backend Service starting...");
 
-    // Configuration des URLs pour CORS
-    let frontend_url_prod = env::var("FRONTEND_URL_PROD")
-        .unwrap_or_else(|_| "https://opti-task-six.vercel.app".to_string());
+    // Mode lecture seule global (maintenance de la base), modifiable via /admin/read-only,
+    // partagé avec les tâches de fond
+    let read_only_mode = Arc::new(read_only_mode::ReadOnlyMode::new(&app_config.read_only));
+    if app_config.read_only.enabled {
+        log::warn!("Starting in read-only mode: write requests will be rejected");
+    }
+
+    // Mode démo (optionnel) : jeu de données isolé réinitialisé chaque nuit
+    let demo_config = app_config.demo.clone();
+    if let Some(config) = &demo_config {
+        log::info!("Demo mode enabled for user {}", config.user_id);
+        demo::spawn_reset_job(pool.clone(), read_only_mode.clone(), config.clone());
+    }
+
+    // Capture de tâches par email (optionnelle)
+    let inbound_email_config = app_config.inbound_email.clone();
+    if let Some(config) = &inbound_email_config {
+        log::info!("Inbound email capture enabled for domain {}", config.domain);
+    }
+
+    // Import des événements Google Agenda (optionnel)
+    let google_calendar_config = app_config.google_calendar.clone();
+    if let Some(config) = &google_calendar_config {
+        log::info!("Google Calendar integration enabled ({})", config.client_id);
+        integrations::google_calendar::spawn_sync_job(
+            pool.clone(),
+            read_only_mode.clone(),
+            config.clone(),
+        );
+    }
+
+    // Administrateurs de l'instance (routes /admin)
+    let admin_config = web::Data::new(app_config.admin.clone());
+
+    // Limite de taille des métadonnées libres
+    let metadata_config = web::Data::new(app_config.metadata.clone());
+
+    // Budget de coût des endpoints coûteux (recherche, analytics, exports)
+    let cost_limiter = web::Data::new(cost_limits::CostLimiter::new(
+        app_config.cost_limits.clone(),
+    ));
+
+    // Limite de rendu des badges publics, par badge
+    let badge_rate_limiter = web::Data::new(badges::BadgeRateLimiter::new());
+
+    // Suppressions de compte arrivées à échéance
+    account::spawn_deletion_job(pool.clone(), read_only_mode.clone());
+
+    // Règles d'escalade des tâches qui stagnent
+    aging_rules::spawn_aging_job(pool.clone(), read_only_mode.clone());
+
+    // Dépassements des délais cibles (SLA) des projets et labels
+    sla::spawn_sla_job(pool.clone(), read_only_mode.clone());
 
-    let frontend_url_dev =
-        env::var("FRONTEND_URL_DEV").unwrap_or_else(|_| "http://localhost:3000".to_string());
+    // Relances sur le rythme de travail (préférences nudge_*)
+    nudges::spawn_nudge_job(pool.clone(), read_only_mode.clone());
 
-    // Port et host configuration
-    let host = env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = env::var("PORT")
-        .unwrap_or_else(|_| "8080".to_string())
-        .parse::<u16>()
-        .expect("PORT must be a valid number");
+    // Audit d'intégrité quotidien (journalisé)
+    integrity::spawn_integrity_job(pool.clone(), read_only_mode.clone());
+
+    // Opérations de maintenance mises en file depuis /admin/maintenance,
+    // et recalcul continu du temps suivi agrégé (analytics)
+    maintenance::spawn_maintenance_job(pool.clone(), read_only_mode.clone());
+
+    // Événements métier vers le pipeline d'analytics (optionnel)
+    if let Some(config) = &app_config.events {
+        events::spawn_event_bus(events::sink_from_config(config));
+    }
+
+    // Minuteurs restés "en cours" après un crash du client (heartbeat expiré)
+    timer_recovery::spawn_timer_recovery_job(
+        pool.clone(),
+        read_only_mode.clone(),
+        app_config.timer_heartbeat_timeout_minutes,
+    );
+
+    // Arrêt quotidien des minuteurs oubliés (préférence timer_auto_stop_time)
+    timer_auto_stop::spawn_timer_auto_stop_job(pool.clone(), read_only_mode.clone());
+
+    // Sauvegardes nocturnes chiffrées vers le stockage des utilisateurs
+    backups::spawn_backup_job(pool.clone(), read_only_mode.clone());
+
+    // Notifications push vers les appareils enregistrés (optionnelles)
+    if let Some(provider) = push::provider_from_config(&app_config)? {
+        push::spawn_push_job(pool.clone(), read_only_mode.clone(), provider);
+    }
+
+    // Fournisseur LLM pour les fonctionnalités de résumé
+    let llm_provider = web::Data::from(llm::provider_from_config(app_config.llm.as_ref()));
+
+    let host = app_config.server.host.clone();
+    let port = app_config.server.port;
+    let cors_config = app_config.cors.clone();
+    let app_config = web::Data::new(app_config);
+    let read_only_mode = web::Data::from(read_only_mode);
 
     log::info!("Server will start at http://{}:{}", host, port);
 
     // Démarrer le serveur HTTP
     HttpServer::new(move || {
-        // Configuration CORS
-        let mut cors = Cors::default()
-            .allowed_origin(&frontend_url_prod)
-            .allowed_origin(&frontend_url_dev)
-            .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
-            .allowed_headers(vec![
-                header::AUTHORIZATION,
-                header::ACCEPT,
-                header::CONTENT_TYPE,
-            ])
-            .supports_credentials()
-            .max_age(3600);
-
         App::new()
+            .wrap(from_fn(i18n::locale_middleware))
             .wrap(Logger::default())
-            .wrap(cors)
             .app_data(web::Data::new(pool.clone()))
-            .service(web::resource("/health").route(web::get().to(health_check_handler)))
-            .service(
-                web::scope("/projects")
-                    .service(handlers::project_handlers::create_project_handler)
-                    .service(handlers::project_handlers::list_projects_handler)
-                    .service(handlers::project_handlers::get_project_handler)
-                    .service(handlers::project_handlers::update_project_handler)
-                    .service(handlers::project_handlers::delete_project_handler),
-            )
-            .service(
-                web::scope("/tasks")
-                    .service(handlers::task_handlers::create_task_handler)
-                    .service(handlers::task_handlers::list_tasks_handler)
-                    .service(handlers::task_handlers::get_task_handler)
-                    .service(handlers::task_handlers::update_task_handler)
-                    .service(handlers::task_handlers::delete_task_handler)
-                    .service(handlers::task_label_handlers::add_label_to_task_handler)
-                    .service(handlers::task_label_handlers::list_labels_for_task_handler)
-                    .service(handlers::task_label_handlers::remove_label_from_task_handler),
-            )
+            .app_data(web::Data::new(demo_config.clone()))
+            .app_data(web::Data::new(inbound_email_config.clone()))
+            .app_data(web::Data::new(google_calendar_config.clone()))
+            .app_data(llm_provider.clone())
+            .app_data(metadata_config.clone())
+            .app_data(cost_limiter.clone())
+            .app_data(badge_rate_limiter.clone())
+            .app_data(admin_config.clone())
+            .app_data(read_only_mode.clone())
+            .app_data(app_config.clone())
+            // Routes publiques, lisibles depuis n'importe quelle origine
             .service(
-                web::scope("/labels")
-                    .service(handlers::label_handlers::create_label_handler)
-                    .service(handlers::label_handlers::list_labels_handler)
-                    .service(handlers::label_handlers::get_label_handler)
-                    .service(handlers::label_handlers::update_label_handler)
-                    .service(handlers::label_handlers::delete_label_handler),
+                web::resource("/health")
+                    .wrap(cors::public_policy(&cors_config))
+                    .route(web::get().to(health_check_handler)),
             )
             .service(
-                web::scope("/time-entries")
-                    .service(handlers::time_entry_handlers::create_time_entry_handler)
-                    .service(handlers::time_entry_handlers::list_time_entries_handler)
-                    .service(handlers::time_entry_handlers::get_time_entry_handler)
-                    .service(handlers::time_entry_handlers::update_time_entry_handler)
-                    .service(handlers::time_entry_handlers::delete_time_entry_handler),
+                web::scope("/public")
+                    .wrap(cors::public_policy(&cors_config))
+                    .service(handlers::badge_handlers::public_badge_svg_handler),
             )
+            // API de l'application : origines du frontend uniquement. Le CORS enveloppe la
+            // garde lecture seule pour que ses refus restent lisibles par le navigateur.
             .service(
-                web::scope("/analytics")
-                    .service(handlers::analytics_handlers::get_time_by_project_handler)
-                    .service(handlers::analytics_handlers::get_productivity_trend_handler),
+                web::scope("")
+                    .wrap(from_fn(account::read_only_guard))
+                    .wrap(from_fn(read_only_mode::read_only_mode_guard))
+                    .wrap(cors::api_policy(&cors_config))
+                .service(handlers::metrics_handlers::metrics_handler)
+                .service(
+                    web::scope("/projects")
+                        .service(handlers::project_handlers::create_project_handler)
+                        .service(handlers::project_handlers::list_projects_handler)
+                        .service(handlers::project_handlers::list_duplicate_projects_handler)
+                        .service(handlers::project_handlers::get_project_handler)
+                        .service(handlers::project_handlers::get_project_report_handler)
+                        .service(handlers::project_handlers::export_project_handler)
+                        .service(handlers::project_handlers::get_project_board_handler)
+                        .service(handlers::project_handlers::get_project_burndown_handler)
+                        .service(handlers::project_handlers::merge_project_handler)
+                        .service(handlers::project_handlers::complete_project_handler)
+                        .service(handlers::custom_field_handlers::list_custom_fields_handler)
+                        .service(handlers::custom_field_handlers::create_custom_field_handler)
+                        .service(handlers::custom_field_handlers::update_custom_field_handler)
+                        .service(handlers::custom_field_handlers::delete_custom_field_handler)
+                        .service(handlers::metadata_handlers::patch_project_metadata_handler)
+                        .service(handlers::project_handlers::update_project_handler)
+                        .service(handlers::project_handlers::delete_project_handler),
+                )
+                .service(
+                    web::scope("/tasks")
+                        .service(handlers::task_import_handlers::import_tasks_handler)
+                        .service(handlers::task_handlers::create_task_handler)
+                        .service(handlers::task_handlers::list_tasks_handler)
+                        .service(handlers::task_handlers::list_stale_tasks_handler)
+                        .service(handlers::task_handlers::promote_tasks_handler)
+                        .service(handlers::task_handlers::demote_tasks_handler)
+                        .service(handlers::task_handlers::bulk_update_task_status_handler)
+                        .service(handlers::task_handlers::get_task_handler)
+                        .service(handlers::task_handlers::update_task_handler)
+                        .service(handlers::task_handlers::delete_task_handler)
+                        .service(handlers::task_handlers::list_task_backlinks_handler)
+                        .service(handlers::task_handlers::export_task_handler)
+                        .service(handlers::task_handlers::list_task_pomodoros_handler)
+                        .service(handlers::task_handlers::pin_task_handler)
+                        .service(handlers::task_handlers::unpin_task_handler)
+                        .service(handlers::task_handlers::snooze_task_handler)
+                        .service(handlers::task_handlers::unsnooze_task_handler)
+                        .service(handlers::task_handlers::mark_task_still_relevant_handler)
+                        .service(handlers::custom_field_handlers::set_task_custom_value_handler)
+                        .service(handlers::custom_field_handlers::clear_task_custom_value_handler)
+                        .service(handlers::metadata_handlers::patch_task_metadata_handler)
+                        .service(handlers::task_watcher_handlers::watch_task_handler)
+                        .service(handlers::task_watcher_handlers::unwatch_task_handler)
+                        .service(handlers::task_label_handlers::add_label_to_task_handler)
+                        .service(handlers::task_label_handlers::list_labels_for_task_handler)
+                        .service(handlers::task_label_handlers::remove_label_from_task_handler)
+                        .service(
+                            handlers::task_external_link_handlers::add_task_external_link_handler,
+                        )
+                        .service(
+                            handlers::task_external_link_handlers::list_task_external_links_handler,
+                        )
+                        .service(
+                            handlers::task_external_link_handlers::remove_task_external_link_handler,
+                        ),
+                )
+                .service(web::scope("/capture").service(handlers::capture_handlers::capture_handler))
+                .service(
+                    web::scope("/labels")
+                        .service(handlers::label_handlers::create_label_handler)
+                        .service(handlers::label_handlers::bulk_create_labels_handler)
+                        .service(handlers::label_handlers::list_labels_handler)
+                        .service(handlers::label_handlers::get_label_handler)
+                        .service(handlers::label_handlers::update_label_handler)
+                        .service(handlers::label_handlers::delete_label_handler),
+                )
+                .service(
+                    web::scope("/time-entries")
+                        .service(handlers::time_entry_handlers::create_time_entry_handler)
+                        .service(handlers::time_entry_handlers::list_time_entries_handler)
+                        .service(handlers::time_entry_handlers::list_daily_time_entries_handler)
+                        .service(handlers::time_entry_handlers::recompute_durations_handler)
+                        .service(handlers::time_entry_handlers::get_time_entry_handler)
+                        .service(handlers::time_entry_handlers::update_time_entry_handler)
+                        .service(handlers::time_entry_handlers::delete_time_entry_handler)
+                        .service(handlers::time_entry_handlers::trim_idle_time_entry_handler)
+                        .service(handlers::time_entry_handlers::heartbeat_time_entry_handler),
+                )
+                .service(
+                    web::scope("/timesheets")
+                        .service(handlers::timesheet_handlers::list_timesheets_handler)
+                        .service(handlers::timesheet_handlers::list_pending_timesheets_handler)
+                        .service(handlers::timesheet_handlers::submit_timesheet_handler)
+                        .service(handlers::timesheet_handlers::approve_timesheet_handler)
+                        .service(handlers::timesheet_handlers::reject_timesheet_handler),
+                )
+                .service(
+                    web::scope("/aging-rules")
+                        .service(handlers::aging_rule_handlers::list_aging_rules_handler)
+                        .service(handlers::aging_rule_handlers::create_aging_rule_handler)
+                        .service(handlers::aging_rule_handlers::update_aging_rule_handler)
+                        .service(handlers::aging_rule_handlers::delete_aging_rule_handler)
+                        .service(handlers::aging_rule_handlers::dry_run_aging_rule_handler),
+                )
+                .service(
+                    web::scope("/maintenance")
+                        .service(handlers::maintenance_handlers::get_integrity_report_handler)
+                        .service(handlers::maintenance_handlers::repair_integrity_handler),
+                )
+                .service(
+                    web::scope("/automations")
+                        .service(handlers::automation_handlers::list_automation_rules_handler)
+                        .service(handlers::automation_handlers::create_automation_rule_handler)
+                        .service(handlers::automation_handlers::update_automation_rule_handler)
+                        .service(handlers::automation_handlers::delete_automation_rule_handler),
+                )
+                .service(
+                    web::scope("/analytics")
+                        .service(handlers::analytics_handlers::get_time_by_project_handler)
+                        .service(handlers::analytics_handlers::get_productivity_trend_handler)
+                        .service(handlers::analytics_handlers::export_analytics_handler)
+                        .service(handlers::analytics_handlers::generate_ai_summary_handler)
+                        .service(handlers::analytics_handlers::compare_periods_handler)
+                        .service(handlers::analytics_handlers::get_focus_analytics_handler)
+                        .service(handlers::analytics_handlers::create_analytics_snapshot_handler)
+                        .service(handlers::analytics_handlers::list_analytics_snapshots_handler)
+                        .service(handlers::analytics_handlers::get_sla_report_handler)
+                        .service(handlers::analytics_handlers::check_sla_handler),
+                )
+                .service(
+                    web::scope("/dashboard")
+                        .service(handlers::dashboard_handlers::get_dashboard_handler),
+                )
+                .service(
+                    web::scope("/account")
+                        .service(handlers::account_handlers::request_account_deletion_handler)
+                        .service(handlers::account_handlers::get_account_deletion_handler)
+                        .service(handlers::account_handlers::cancel_account_deletion_handler)
+                        .service(handlers::account_handlers::wipe_account_handler),
+                )
+                .service(web::scope("/me").service(handlers::me_handlers::get_usage_handler))
+                .service(
+                    web::scope("/activity")
+                        .service(handlers::activity_handlers::get_activity_feed_handler),
+                )
+                .service(
+                    web::scope("/onboarding")
+                        .service(handlers::onboarding_handlers::seed_onboarding_handler),
+                )
+                .service(
+                    web::scope("/workspaces")
+                        .service(handlers::workspace_handlers::create_workspace_handler)
+                        .service(handlers::workspace_handlers::list_workspaces_handler)
+                        .service(handlers::workspace_handlers::get_workspace_handler)
+                        .service(handlers::workspace_handlers::get_workspace_standup_handler)
+                        .service(handlers::workspace_handlers::get_workspace_leaderboard_handler)
+                        .service(handlers::workspace_handlers::update_workspace_time_lock_handler)
+                        .service(handlers::workspace_handlers::add_workspace_member_handler)
+                        .service(handlers::workspace_handlers::remove_workspace_member_handler),
+                )
+                .service(
+                    web::scope("/settings")
+                        .service(handlers::settings_handlers::get_settings_handler)
+                        .service(handlers::settings_handlers::update_settings_handler)
+                        .service(handlers::settings_handlers::list_client_preferences_handler)
+                        .service(handlers::settings_handlers::get_client_preferences_handler)
+                        .service(handlers::settings_handlers::put_client_preferences_handler)
+                        .service(handlers::settings_handlers::delete_client_preferences_handler)
+                        .service(handlers::inbound_email_handlers::get_inbound_email_address_handler)
+                        .service(handlers::inbound_email_handlers::rotate_inbound_email_address_handler)
+                        .service(handlers::app_password_handlers::list_app_passwords_handler)
+                        .service(handlers::app_password_handlers::create_app_password_handler)
+                        .service(handlers::app_password_handlers::revoke_app_password_handler)
+                        .service(handlers::api_key_handlers::list_api_keys_handler)
+                        .service(handlers::api_key_handlers::create_api_key_handler)
+                        .service(handlers::api_key_handlers::revoke_api_key_handler)
+                        .service(handlers::badge_handlers::get_badge_handler)
+                        .service(handlers::badge_handlers::create_badge_handler)
+                        .service(handlers::badge_handlers::rotate_badge_handler)
+                        .service(handlers::badge_handlers::delete_badge_handler),
+                )
+                .service(
+                    web::scope("/backups")
+                        .service(handlers::backup_handlers::configure_backup_handler)
+                        .service(handlers::backup_handlers::get_backup_status_handler)
+                        .service(handlers::backup_handlers::run_backup_handler)
+                        .service(handlers::backup_handlers::restore_backup_handler)
+                        .service(handlers::backup_handlers::get_restore_status_handler),
+                )
+                .service(
+                    web::scope("/recent")
+                        .service(handlers::recent_handlers::record_recent_view_handler)
+                        .service(handlers::recent_handlers::list_recent_handler),
+                )
+                .service(
+                    web::scope("/colors").service(handlers::color_handlers::suggest_colors_handler),
+                )
+                .service(web::scope("/suggest").service(handlers::suggest_handlers::suggest_handler))
+                .service(
+                    web::scope("/themes").service(handlers::theme_handlers::list_theme_presets_handler),
+                )
+                .service(
+                    web::scope("/dav")
+                        .service(handlers::caldav_handlers::dav_root_handler)
+                        .service(handlers::caldav_handlers::dav_principal_handler)
+                        .service(handlers::caldav_handlers::dav_collection_handler)
+                        .service(handlers::caldav_handlers::dav_task_handler),
+                )
+                .service(
+                    web::scope("/integrations/polling")
+                        .service(handlers::polling_handlers::polling_me_handler)
+                        .service(handlers::polling_handlers::polling_new_tasks_handler)
+                        .service(handlers::polling_handlers::polling_new_completed_tasks_handler),
+                )
+                .service(
+                    web::scope("/integrations/google-calendar")
+                        .service(
+                            handlers::calendar_integration_handlers::get_google_calendar_status_handler,
+                        )
+                        .service(
+                            handlers::calendar_integration_handlers::disconnect_google_calendar_handler,
+                        )
+                        .service(
+                            handlers::calendar_integration_handlers::connect_google_calendar_handler,
+                        )
+                        .service(
+                            handlers::calendar_integration_handlers::google_calendar_callback_handler,
+                        )
+                        .service(handlers::calendar_integration_handlers::sync_google_calendar_handler)
+                        .service(handlers::calendar_integration_handlers::list_calendar_links_handler)
+                        .service(handlers::calendar_integration_handlers::link_calendar_project_handler)
+                        .service(
+                            handlers::calendar_integration_handlers::unlink_calendar_project_handler,
+                        )
+                        .service(
+                            handlers::calendar_integration_handlers::list_calendar_suggestions_handler,
+                        )
+                        .service(
+                            handlers::calendar_integration_handlers::accept_calendar_suggestion_handler,
+                        )
+                        .service(
+                            handlers::calendar_integration_handlers::reject_calendar_suggestion_handler,
+                        ),
+                )
+                .service(
+                    web::scope("/webhooks")
+                        .service(handlers::inbound_email_handlers::mailgun_inbound_webhook_handler)
+                        .service(handlers::inbound_email_handlers::ses_inbound_webhook_handler),
+                )
+                .service(
+                    web::scope("/feedback")
+                        .service(handlers::feedback_handlers::create_feedback_handler)
+                        .service(handlers::feedback_handlers::list_my_feedback_handler),
+                )
+                .service(
+                    web::scope("/admin")
+                        .service(handlers::feedback_handlers::admin_list_feedback_handler)
+                        .service(handlers::announcement_handlers::admin_list_announcements_handler)
+                        .service(handlers::announcement_handlers::admin_create_announcement_handler)
+                        .service(handlers::announcement_handlers::admin_update_announcement_handler)
+                        .service(handlers::announcement_handlers::admin_delete_announcement_handler)
+                        .service(handlers::feature_flag_handlers::admin_list_flags_handler)
+                        .service(handlers::feature_flag_handlers::admin_get_flag_handler)
+                        .service(handlers::feature_flag_handlers::admin_upsert_flag_handler)
+                        .service(handlers::feature_flag_handlers::admin_delete_flag_handler)
+                        .service(handlers::feature_flag_handlers::admin_set_flag_override_handler)
+                        .service(handlers::feature_flag_handlers::admin_delete_flag_override_handler)
+                        .service(handlers::experiment_handlers::admin_list_experiments_handler)
+                        .service(handlers::experiment_handlers::admin_upsert_experiment_handler)
+                        .service(handlers::experiment_handlers::admin_experiment_results_handler)
+                        .service(handlers::maintenance_handlers::admin_list_maintenance_jobs_handler)
+                        .service(handlers::maintenance_handlers::admin_get_maintenance_job_handler)
+                        .service(handlers::maintenance_handlers::admin_enqueue_maintenance_handler)
+                        .service(handlers::maintenance_handlers::admin_get_read_only_handler)
+                        .service(handlers::maintenance_handlers::admin_set_read_only_handler),
+                )
+                .service(
+                    web::scope("/flags").service(handlers::feature_flag_handlers::get_my_flags_handler),
+                )
+                .service(
+                    web::scope("/experiments")
+                        .service(handlers::experiment_handlers::get_my_experiments_handler)
+                        .service(handlers::experiment_handlers::record_experiment_event_handler),
+                )
+                .service(
+                    web::scope("/announcements")
+                        .service(handlers::announcement_handlers::list_announcements_handler)
+                        .service(handlers::announcement_handlers::ack_announcement_handler),
+                )
+                .service(
+                    web::scope("/macros")
+                        .service(handlers::macro_handlers::list_macros_handler)
+                        .service(handlers::macro_handlers::run_macro_handler),
+                )
+                .service(
+                    web::scope("/pomodoro")
+                        .service(handlers::pomodoro_handlers::record_interruption_handler),
+                )
+                .service(
+                    web::scope("/focus-sessions")
+                        .service(handlers::focus_session_handlers::start_focus_session_handler)
+                        .service(
+                            handlers::focus_session_handlers::get_active_focus_session_handler,
+                        )
+                        .service(handlers::focus_session_handlers::end_focus_session_handler),
+                )
+                .service(
+                    web::scope("/planning")
+                        .service(handlers::planning_handlers::planning_rollover_handler),
+                )
+                .service(
+                    web::scope("/devices")
+                        .service(handlers::device_handlers::list_devices_handler)
+                        .service(handlers::device_handlers::register_device_handler)
+                        .service(handlers::device_handlers::update_device_sync_state_handler)
+                        .service(handlers::device_handlers::revoke_device_handler),
+                )
+                .service(
+                    web::scope("/notifications")
+                        .service(handlers::notification_handlers::list_notifications_handler)
+                        .service(handlers::notification_handlers::mark_all_notifications_read_handler)
+                        .service(handlers::notification_handlers::mark_notification_read_handler)
+                        .service(handlers::notification_handlers::list_push_deliveries_handler),
+                )
             )
     })
     .bind(format!("{}:{}", host, port))?
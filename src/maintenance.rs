@@ -0,0 +1,536 @@
+// OptiTask/backend-api/src/maintenance.rs
+// Opérations de maintenance déclenchées depuis /admin/maintenance : elles sont mises en file
+// (table maintenance_jobs) puis exécutées une à une par une tâche de fond, qui enregistre
+// leur statut et leur compte rendu. Évite de passer par psql pour l'exploitation courante.
+// La même file exécute les opérations longues demandées par les utilisateurs (restauration).
+use crate::account;
+use crate::backup_restore;
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::MaintenanceJob;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::maintenance_jobs;
+use crate::tracked_time;
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Bool, Date, Nullable, Text, Timestamptz};
+use diesel::{sql_query, QueryableByName};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl, SimpleAsyncConnection};
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const JOB_VACUUM_ANALYZE: &str = "vacuum_analyze";
+pub const JOB_REBUILD_SEARCH_INDEXES: &str = "rebuild_search_indexes";
+pub const JOB_PURGE_DELETED_ACCOUNTS: &str = "purge_deleted_accounts";
+pub const JOB_RECOMPUTE_COUNTERS: &str = "recompute_counters";
+pub const JOB_REBUILD_TRACKED_TIME: &str = "rebuild_tracked_time";
+pub const JOB_ENSURE_PARTITIONS: &str = "ensure_partitions";
+pub const JOB_KINDS: [&str; 6] = [
+    JOB_VACUUM_ANALYZE,
+    JOB_REBUILD_SEARCH_INDEXES,
+    JOB_PURGE_DELETED_ACCOUNTS,
+    JOB_RECOMPUTE_COUNTERS,
+    JOB_REBUILD_TRACKED_TIME,
+    JOB_ENSURE_PARTITIONS,
+];
+
+// Demandée par un utilisateur (POST /backups/restore), absente de JOB_KINDS
+pub const JOB_RESTORE_BACKUP: &str = "restore_backup";
+
+pub const JOB_STATUS_QUEUED: &str = "queued";
+pub const JOB_STATUS_RUNNING: &str = "running";
+pub const JOB_STATUS_SUCCEEDED: &str = "succeeded";
+pub const JOB_STATUS_FAILED: &str = "failed";
+
+// Fréquence de recherche des opérations en file
+const MAINTENANCE_JOB_INTERVAL_SECS: u64 = 10;
+// Au-delà, une opération "running" est considérée comme interrompue (redémarrage du serveur)
+const STALE_JOB_MINUTES: i64 = 60;
+// Seuils des recommandations VACUUM : lignes mortes en nombre et en proportion
+const VACUUM_MIN_DEAD_TUPLES: i64 = 1000;
+const VACUUM_DEAD_RATIO: f64 = 0.2;
+
+// Partitions mensuelles de time_entries préparées à l'avance (mois courant inclus)
+const PARTITION_MONTHS_AHEAD: u32 = 3;
+
+// Index utilisés par la recherche de tâches (trigrammes sur le titre, contexte JSON)
+const SEARCH_INDEXES: [&str; 2] = ["idx_tasks_title_trgm", "idx_tasks_context"];
+
+// Compteurs dénormalisés : nom et requête de recalcul (ne touche que les lignes divergentes)
+const COUNTERS: [(&str, &str); 2] = [
+    (
+        "timesheets.total_duration_seconds",
+        "UPDATE timesheets ts SET total_duration_seconds = totals.total \
+         FROM (SELECT ts2.id, COALESCE(SUM(te.duration_seconds), 0)::bigint AS total \
+         FROM timesheets ts2 \
+         LEFT JOIN time_entries te ON te.user_id = ts2.user_id AND te.is_break = FALSE \
+         AND te.start_time >= (ts2.week_start::timestamp AT TIME ZONE 'UTC') \
+         AND te.start_time < ((ts2.week_start + 7)::timestamp AT TIME ZONE 'UTC') \
+         WHERE ts2.status IN ('submitted', 'approved') \
+         GROUP BY ts2.id) totals \
+         WHERE ts.id = totals.id AND ts.total_duration_seconds <> totals.total",
+    ),
+    (
+        "projects.task_counts",
+        "UPDATE projects p SET open_task_count = counts.open_count, \
+         completed_task_count = counts.completed_count \
+         FROM (SELECT p2.id, \
+         (COUNT(t.id) FILTER (WHERE t.status NOT IN ('completed', 'done')))::int AS open_count, \
+         (COUNT(t.id) FILTER (WHERE t.status IN ('completed', 'done')))::int AS completed_count \
+         FROM projects p2 LEFT JOIN tasks t ON t.project_id = p2.id \
+         GROUP BY p2.id) counts \
+         WHERE p.id = counts.id \
+         AND (p.open_task_count <> counts.open_count \
+         OR p.completed_task_count <> counts.completed_count)",
+    ),
+];
+
+pub fn validate_kind(kind: &str) -> Result<&'static str, ServiceError> {
+    JOB_KINDS
+        .iter()
+        .find(|known| **known == kind)
+        .copied()
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!(
+                "Unknown maintenance operation '{}'. Supported: {}",
+                kind,
+                JOB_KINDS.join(", ")
+            ))
+        })
+}
+
+// Met l'opération en file ; si la même opération est déjà en attente ou en cours, elle est
+// renvoyée telle quelle (second élément à false)
+pub async fn enqueue(
+    conn: &mut AsyncPgConnection,
+    kind: &'static str,
+    requested_by: Uuid,
+) -> Result<(MaintenanceJob, bool), ServiceError> {
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            let pending = maintenance_jobs::table
+                .filter(maintenance_jobs::kind.eq(kind))
+                .filter(maintenance_jobs::status.eq_any([JOB_STATUS_QUEUED, JOB_STATUS_RUNNING]))
+                .select(MaintenanceJob::as_select())
+                .for_update()
+                .first::<MaintenanceJob>(conn)
+                .await
+                .optional()?;
+            if let Some(job) = pending {
+                return Ok((job, false));
+            }
+
+            let job = diesel::insert_into(maintenance_jobs::table)
+                .values((
+                    maintenance_jobs::kind.eq(kind),
+                    maintenance_jobs::requested_by.eq(requested_by),
+                ))
+                .returning(MaintenanceJob::as_returning())
+                .get_result::<MaintenanceJob>(conn)
+                .await?;
+            Ok((job, true))
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+// Met en file une opération demandée par un utilisateur, avec ses données d'entrée ;
+// une seule opération de chaque type peut être en attente ou en cours par utilisateur
+pub async fn enqueue_for_user(
+    conn: &mut AsyncPgConnection,
+    kind: &'static str,
+    user_uuid: Uuid,
+    payload: serde_json::Value,
+) -> Result<MaintenanceJob, ServiceError> {
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            let pending = maintenance_jobs::table
+                .filter(maintenance_jobs::kind.eq(kind))
+                .filter(maintenance_jobs::requested_by.eq(user_uuid))
+                .filter(maintenance_jobs::status.eq_any([JOB_STATUS_QUEUED, JOB_STATUS_RUNNING]))
+                .select(maintenance_jobs::id)
+                .for_update()
+                .first::<Uuid>(conn)
+                .await
+                .optional()?;
+            if let Some(job_id) = pending {
+                return Err(ServiceError::ConflictError(format!(
+                    "Operation {} is already queued or running ({})",
+                    kind, job_id
+                )));
+            }
+
+            diesel::insert_into(maintenance_jobs::table)
+                .values((
+                    maintenance_jobs::kind.eq(kind),
+                    maintenance_jobs::requested_by.eq(user_uuid),
+                    maintenance_jobs::payload.eq(payload),
+                ))
+                .returning(MaintenanceJob::as_returning())
+                .get_result::<MaintenanceJob>(conn)
+                .await
+                .map_err(ServiceError::from)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+// Opération d'un utilisateur, pour le suivi de son avancement
+pub async fn find_user_job(
+    conn: &mut AsyncPgConnection,
+    job_uuid: Uuid,
+    kind: &str,
+    user_uuid: Uuid,
+) -> Result<MaintenanceJob, ServiceError> {
+    maintenance_jobs::table
+        .filter(maintenance_jobs::id.eq(job_uuid))
+        .filter(maintenance_jobs::kind.eq(kind))
+        .filter(maintenance_jobs::requested_by.eq(user_uuid))
+        .select(MaintenanceJob::as_select())
+        .first::<MaintenanceJob>(conn)
+        .await
+        .optional()?
+        .ok_or_else(|| ServiceError::NotFound(format!("Job with id {} not found", job_uuid)))
+}
+
+pub async fn load_payload(
+    conn: &mut AsyncPgConnection,
+    job_uuid: Uuid,
+) -> Result<Option<serde_json::Value>, ServiceError> {
+    maintenance_jobs::table
+        .find(job_uuid)
+        .select(maintenance_jobs::payload)
+        .first::<Option<serde_json::Value>>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Enregistre l'avancement sur une connexion à part : visible pendant la transaction de l'opération
+pub async fn record_progress(pool: &DbPool, job_uuid: Uuid, progress: serde_json::Value) {
+    let result = match pool.get().await {
+        Ok(mut conn) => diesel::update(maintenance_jobs::table.find(job_uuid))
+            .set(maintenance_jobs::progress.eq(progress))
+            .execute(&mut conn)
+            .await
+            .map_err(ServiceError::from),
+        Err(e) => Err(ServiceError::from(e)),
+    };
+    if let Err(e) = result {
+        log::warn!("Could not record progress of job {}: {}", job_uuid, e);
+    }
+}
+
+#[derive(QueryableByName)]
+struct TableStatsRow {
+    #[diesel(sql_type = Text)]
+    table_name: String,
+    #[diesel(sql_type = BigInt)]
+    live_tuples: i64,
+    #[diesel(sql_type = BigInt)]
+    dead_tuples: i64,
+    #[diesel(sql_type = Nullable<Timestamptz>)]
+    last_vacuumed_at: Option<DateTime<Utc>>,
+    #[diesel(sql_type = Nullable<Timestamptz>)]
+    last_analyzed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+struct TableHint {
+    table: String,
+    live_tuples: i64,
+    dead_tuples: i64,
+    last_vacuumed_at: Option<DateTime<Utc>>,
+    last_analyzed_at: Option<DateTime<Utc>>,
+    // "vacuum_analyze" ou "analyze"
+    action: &'static str,
+    error: Option<String>,
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// VACUUM et REINDEX CONCURRENTLY refusent les transactions : exécutés hors transaction.
+// VACUUM (ANALYZE) des tables chargées en lignes mortes, ANALYZE de celles jamais analysées.
+// Les autres tables sont laissées à l'autovacuum.
+async fn run_vacuum_analyze(
+    conn: &mut AsyncPgConnection,
+) -> Result<serde_json::Value, ServiceError> {
+    let stats = sql_query(
+        "SELECT relname::text AS table_name, n_live_tup AS live_tuples, n_dead_tup AS dead_tuples, \
+         GREATEST(last_vacuum, last_autovacuum) AS last_vacuumed_at, \
+         GREATEST(last_analyze, last_autoanalyze) AS last_analyzed_at \
+         FROM pg_stat_user_tables WHERE schemaname = 'public' ORDER BY relname",
+    )
+    .load::<TableStatsRow>(conn)
+    .await?;
+
+    let mut hints = Vec::new();
+    for row in stats {
+        let dead_ratio = row.dead_tuples as f64 / (row.live_tuples + row.dead_tuples).max(1) as f64;
+        let action = if row.dead_tuples >= VACUUM_MIN_DEAD_TUPLES && dead_ratio >= VACUUM_DEAD_RATIO
+        {
+            "vacuum_analyze"
+        } else if row.last_analyzed_at.is_none() && row.live_tuples > 0 {
+            "analyze"
+        } else {
+            continue;
+        };
+        let statement = match action {
+            "vacuum_analyze" => format!("VACUUM (ANALYZE) {}", quote_identifier(&row.table_name)),
+            _ => format!("ANALYZE {}", quote_identifier(&row.table_name)),
+        };
+        // Un échec (droits insuffisants...) n'empêche pas de traiter les autres tables
+        let error = conn
+            .batch_execute(&statement)
+            .await
+            .err()
+            .map(|e| e.to_string());
+        hints.push(TableHint {
+            table: row.table_name,
+            live_tuples: row.live_tuples,
+            dead_tuples: row.dead_tuples,
+            last_vacuumed_at: row.last_vacuumed_at,
+            last_analyzed_at: row.last_analyzed_at,
+            action,
+            error,
+        });
+    }
+
+    Ok(json!({ "tables": hints }))
+}
+
+// REINDEX CONCURRENTLY : la recherche reste disponible pendant la reconstruction
+async fn run_rebuild_search_indexes(
+    conn: &mut AsyncPgConnection,
+) -> Result<serde_json::Value, ServiceError> {
+    let mut rebuilt = Vec::new();
+    for index in SEARCH_INDEXES {
+        conn.batch_execute(&format!(
+            "REINDEX INDEX CONCURRENTLY {}",
+            quote_identifier(index)
+        ))
+        .await?;
+        rebuilt.push(index);
+    }
+    Ok(json!({ "rebuilt_indexes": rebuilt }))
+}
+
+async fn run_recompute_counters(
+    conn: &mut AsyncPgConnection,
+) -> Result<serde_json::Value, ServiceError> {
+    let mut corrected = serde_json::Map::new();
+    for (name, statement) in COUNTERS {
+        let affected = sql_query(statement).execute(conn).await?;
+        corrected.insert(name.to_string(), json!(affected));
+    }
+    Ok(json!({ "corrected_rows": corrected }))
+}
+
+#[derive(QueryableByName)]
+struct PartitionCreated {
+    #[diesel(sql_type = Bool)]
+    created: bool,
+}
+
+// Crée les partitions mensuelles de time_entries manquantes, du mois courant à
+// PARTITION_MONTHS_AHEAD mois (voir ensure_time_entries_partition dans les migrations)
+pub async fn ensure_time_entry_partitions(
+    conn: &mut AsyncPgConnection,
+) -> Result<Vec<NaiveDate>, ServiceError> {
+    let today = Utc::now().date_naive();
+    let current_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap();
+
+    let mut created = Vec::new();
+    for ahead in 0..=PARTITION_MONTHS_AHEAD {
+        let month_start = current_month + Months::new(ahead);
+        let partition = sql_query("SELECT ensure_time_entries_partition($1) AS created")
+            .bind::<Date, _>(month_start)
+            .get_result::<PartitionCreated>(conn)
+            .await?;
+        if partition.created {
+            created.push(month_start);
+        }
+    }
+    Ok(created)
+}
+
+async fn run_job(pool: &DbPool, job: &MaintenanceJob) -> Result<serde_json::Value, ServiceError> {
+    let kind = job.kind.as_str();
+    if kind == JOB_PURGE_DELETED_ACCOUNTS {
+        let deleted = account::run_due_deletions(pool).await?;
+        return Ok(json!({ "deleted_accounts": deleted }));
+    }
+    if kind == JOB_RESTORE_BACKUP {
+        return backup_restore::run_restore_job(pool, job).await;
+    }
+
+    let mut conn = pool.get().await?;
+    match kind {
+        JOB_VACUUM_ANALYZE => run_vacuum_analyze(&mut conn).await,
+        JOB_REBUILD_SEARCH_INDEXES => run_rebuild_search_indexes(&mut conn).await,
+        JOB_RECOMPUTE_COUNTERS => run_recompute_counters(&mut conn).await,
+        JOB_ENSURE_PARTITIONS => {
+            let created = ensure_time_entry_partitions(&mut conn).await?;
+            Ok(json!({ "created_partitions": created }))
+        }
+        JOB_REBUILD_TRACKED_TIME => {
+            let rows = tracked_time::rebuild_all(&mut conn).await?;
+            Ok(json!({ "daily_rows": rows }))
+        }
+        _ => Err(ServiceError::ValidationError(format!(
+            "Unknown maintenance operation '{}'",
+            kind
+        ))),
+    }
+}
+
+// Réserve la plus ancienne opération en file (SKIP LOCKED : plusieurs instances possibles)
+async fn claim_next_job(
+    conn: &mut AsyncPgConnection,
+) -> Result<Option<MaintenanceJob>, ServiceError> {
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            let next_id = maintenance_jobs::table
+                .filter(maintenance_jobs::status.eq(JOB_STATUS_QUEUED))
+                .order(maintenance_jobs::created_at.asc())
+                .select(maintenance_jobs::id)
+                .for_update()
+                .skip_locked()
+                .first::<Uuid>(conn)
+                .await
+                .optional()?;
+            let Some(job_id) = next_id else {
+                return Ok(None);
+            };
+
+            diesel::update(maintenance_jobs::table.find(job_id))
+                .set((
+                    maintenance_jobs::status.eq(JOB_STATUS_RUNNING),
+                    maintenance_jobs::started_at.eq(Utc::now()),
+                ))
+                .returning(MaintenanceJob::as_returning())
+                .get_result::<MaintenanceJob>(conn)
+                .await
+                .map(Some)
+                .map_err(ServiceError::from)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+async fn fail_stale_jobs(conn: &mut AsyncPgConnection) -> Result<usize, ServiceError> {
+    diesel::update(
+        maintenance_jobs::table
+            .filter(maintenance_jobs::status.eq(JOB_STATUS_RUNNING))
+            .filter(
+                maintenance_jobs::started_at.lt(Utc::now() - Duration::minutes(STALE_JOB_MINUTES)),
+            ),
+    )
+    .set((
+        maintenance_jobs::status.eq(JOB_STATUS_FAILED),
+        maintenance_jobs::error.eq("Interrupted before completion"),
+        maintenance_jobs::finished_at.eq(Utc::now()),
+        maintenance_jobs::payload.eq(None::<serde_json::Value>),
+    ))
+    .execute(conn)
+    .await
+    .map_err(ServiceError::from)
+}
+
+// Exécute les opérations en file jusqu'à épuisement ; renvoie le nombre d'opérations traitées
+async fn process_queue(pool: &DbPool) -> Result<usize, ServiceError> {
+    let mut processed = 0;
+    loop {
+        let job = {
+            let mut conn = pool.get().await?;
+            fail_stale_jobs(&mut conn).await?;
+            claim_next_job(&mut conn).await?
+        };
+        let Some(job) = job else {
+            return Ok(processed);
+        };
+
+        log::info!("Maintenance job {} ({}) started", job.id, job.kind);
+        let (status, result, error) = match run_job(pool, &job).await {
+            Ok(result) => (JOB_STATUS_SUCCEEDED, Some(result), None),
+            Err(e) => {
+                log::error!("Maintenance job {} ({}) failed: {}", job.id, job.kind, e);
+                (JOB_STATUS_FAILED, None, Some(e.to_string()))
+            }
+        };
+
+        let mut conn = pool.get().await?;
+        diesel::update(maintenance_jobs::table.find(job.id))
+            .set((
+                maintenance_jobs::status.eq(status),
+                maintenance_jobs::result.eq(result),
+                maintenance_jobs::error.eq(error),
+                maintenance_jobs::finished_at.eq(Utc::now()),
+                // Les données d'entrée (sauvegarde restaurée...) ne sont pas conservées
+                maintenance_jobs::payload.eq(None::<serde_json::Value>),
+            ))
+            .execute(&mut conn)
+            .await?;
+        log::info!("Maintenance job {} ({}) {}", job.id, job.kind, status);
+        processed += 1;
+    }
+}
+
+// Recalcule les jours de temps suivi modifiés depuis le passage précédent
+async fn refresh_tracked_time(pool: &DbPool) -> Result<(), ServiceError> {
+    let mut conn = pool.get().await?;
+    let refreshed = tracked_time::refresh_pending(&mut conn, None).await?;
+    if refreshed > 0 {
+        log::debug!("Refreshed {} day(s) of tracked time", refreshed);
+    }
+    Ok(())
+}
+
+async fn ensure_partitions(pool: &DbPool) -> Result<(), ServiceError> {
+    let mut conn = pool.get().await?;
+    for month_start in ensure_time_entry_partitions(&mut conn).await? {
+        log::info!(
+            "Created time_entries partition for {}",
+            month_start.format("%Y-%m")
+        );
+    }
+    Ok(())
+}
+
+// Lance la tâche de fond qui exécute les opérations de maintenance en file,
+// prépare les partitions mensuelles de time_entries et tient à jour le temps suivi agrégé
+pub fn spawn_maintenance_job(pool: DbPool, read_only: Arc<ReadOnlyMode>) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(
+            MAINTENANCE_JOB_INTERVAL_SECS,
+        ));
+        // Jour de la dernière vérification des partitions (une fois par jour suffit)
+        let mut partitions_checked_on: Option<NaiveDate> = None;
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            let today = Utc::now().date_naive();
+            if partitions_checked_on != Some(today) {
+                match ensure_partitions(&pool).await {
+                    Ok(()) => partitions_checked_on = Some(today),
+                    Err(e) => log::error!("Time entry partition check failed: {}", e),
+                }
+            }
+            if let Err(e) = process_queue(&pool).await {
+                log::error!("Maintenance job runner failed: {}", e);
+            }
+            if let Err(e) = refresh_tracked_time(&pool).await {
+                log::error!("Tracked time refresh failed: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,84 @@
+// OptiTask/backend-api/src/mentions.rs
+use crate::error_handler::ServiceError;
+use crate::ids::TaskId;
+use crate::schema::{task_links, tasks};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+const MENTION_PREFIX: &str = "[[task:";
+const MENTION_SUFFIX: &str = "]]";
+
+// Extrait les UUID des références [[task:<uuid>]] d'un texte (sans doublons, dans l'ordre)
+pub fn extract_task_mentions(text: &str) -> Vec<TaskId> {
+    let mut mentioned = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(MENTION_PREFIX) {
+        rest = &rest[start + MENTION_PREFIX.len()..];
+        let Some(end) = rest.find(MENTION_SUFFIX) else {
+            break;
+        };
+        if let Ok(task_uuid) = rest[..end].trim().parse::<TaskId>() {
+            if !mentioned.contains(&task_uuid) {
+                mentioned.push(task_uuid);
+            }
+        }
+        rest = &rest[end + MENTION_SUFFIX.len()..];
+    }
+
+    mentioned
+}
+
+// Remplace les liens sortants d'une tâche par ceux trouvés dans son texte.
+// Seules les tâches existantes appartenant au même utilisateur sont liées.
+pub async fn sync_task_links(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    source_task_id: TaskId,
+    text: Option<&str>,
+) -> Result<usize, ServiceError> {
+    let mentioned: Vec<TaskId> = text
+        .map(extract_task_mentions)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|target| *target != source_task_id)
+        .collect();
+
+    diesel::delete(task_links::table.filter(task_links::source_task_id.eq(source_task_id)))
+        .execute(conn)
+        .await?;
+
+    if mentioned.is_empty() {
+        return Ok(0);
+    }
+
+    let valid_targets = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .filter(tasks::id.eq_any(&mentioned))
+        .select(tasks::id)
+        .load::<TaskId>(conn)
+        .await?;
+
+    let new_links: Vec<_> = valid_targets
+        .iter()
+        .map(|target| {
+            (
+                task_links::source_task_id.eq(source_task_id),
+                task_links::target_task_id.eq(*target),
+            )
+        })
+        .collect();
+
+    if new_links.is_empty() {
+        return Ok(0);
+    }
+
+    let inserted = diesel::insert_into(task_links::table)
+        .values(&new_links)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .await?;
+
+    Ok(inserted)
+}
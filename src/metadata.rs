@@ -0,0 +1,197 @@
+// OptiTask/backend-api/src/metadata.rs
+// Métadonnées libres (JSONB) des tâches et projets, modifiées par opérations
+// "set" / "remove" adressées par JSON Pointer (RFC 6901).
+use crate::error_handler::ServiceError;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+const MAX_OPERATIONS_PER_PATCH: usize = 100;
+
+// METADATA_MAX_BYTES, 16 Ko par défaut (voir config.rs)
+#[derive(Debug, Clone)]
+pub struct MetadataConfig {
+    pub max_bytes: usize,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum MetadataOperation {
+    Set { path: String, value: Value },
+    Remove { path: String },
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PatchMetadataPayload {
+    pub operations: Vec<MetadataOperation>,
+}
+
+fn parse_pointer(path: &str) -> Result<Vec<String>, ServiceError> {
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = path.strip_prefix('/') else {
+        return Err(ServiceError::ValidationError(format!(
+            "Invalid JSON Pointer '{}': must be empty or start with '/'",
+            path
+        )));
+    };
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn array_index(token: &str, len: usize, path: &str) -> Result<usize, ServiceError> {
+    token
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index < len)
+        .ok_or_else(|| {
+            ServiceError::ValidationError(format!(
+                "Array index '{}' out of range in '{}'",
+                token, path
+            ))
+        })
+}
+
+// Crée les objets intermédiaires manquants ; "-" ajoute en fin de tableau
+fn set_at(
+    target: &mut Value,
+    tokens: &[String],
+    value: Value,
+    path: &str,
+) -> Result<(), ServiceError> {
+    let Some((last, parents)) = tokens.split_last() else {
+        if !value.is_object() {
+            return Err(ServiceError::ValidationError(
+                "Metadata root must be a JSON object".to_string(),
+            ));
+        }
+        *target = value;
+        return Ok(());
+    };
+
+    let mut current = target;
+    for token in parents {
+        current = match current {
+            Value::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(Map::new())),
+            Value::Array(items) => {
+                let index = array_index(token, items.len(), path)?;
+                &mut items[index]
+            }
+            _ => {
+                return Err(ServiceError::ValidationError(format!(
+                    "Cannot traverse a scalar value at '{}' in '{}'",
+                    token, path
+                )))
+            }
+        };
+    }
+
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+        }
+        Value::Array(items) if last == "-" => items.push(value),
+        Value::Array(items) => {
+            let index = array_index(last, items.len(), path)?;
+            items[index] = value;
+        }
+        _ => {
+            return Err(ServiceError::ValidationError(format!(
+                "Cannot set a key on a scalar value in '{}'",
+                path
+            )))
+        }
+    }
+    Ok(())
+}
+
+// Supprimer un chemin absent ne fait rien (opération idempotente)
+fn remove_at(target: &mut Value, tokens: &[String], path: &str) -> Result<(), ServiceError> {
+    let Some((last, parents)) = tokens.split_last() else {
+        return Err(ServiceError::ValidationError(
+            "The metadata root cannot be removed".to_string(),
+        ));
+    };
+
+    let mut current = target;
+    for token in parents {
+        let next = match current {
+            Value::Object(map) => map.get_mut(token),
+            Value::Array(items) => token.parse::<usize>().ok().and_then(|i| items.get_mut(i)),
+            _ => None,
+        };
+        match next {
+            Some(value) => current = value,
+            None => return Ok(()),
+        }
+    }
+
+    match current {
+        Value::Object(map) => {
+            map.remove(last);
+        }
+        Value::Array(items) => {
+            if let Some(index) = last.parse::<usize>().ok().filter(|i| *i < items.len()) {
+                items.remove(index);
+            }
+        }
+        _ => {
+            log::debug!(
+                "Ignoring metadata removal below a scalar value in '{}'",
+                path
+            );
+        }
+    }
+    Ok(())
+}
+
+// Applique les opérations dans l'ordre puis vérifie la taille du document obtenu
+pub fn apply_operations(
+    current: Value,
+    operations: &[MetadataOperation],
+    config: &MetadataConfig,
+) -> Result<Value, ServiceError> {
+    if operations.is_empty() {
+        return Err(ServiceError::ValidationError(
+            "At least one metadata operation is required".to_string(),
+        ));
+    }
+    if operations.len() > MAX_OPERATIONS_PER_PATCH {
+        return Err(ServiceError::ValidationError(format!(
+            "A metadata patch cannot contain more than {} operations",
+            MAX_OPERATIONS_PER_PATCH
+        )));
+    }
+
+    let mut document = if current.is_object() {
+        current
+    } else {
+        Value::Object(Map::new())
+    };
+
+    for operation in operations {
+        match operation {
+            MetadataOperation::Set { path, value } => {
+                set_at(&mut document, &parse_pointer(path)?, value.clone(), path)?
+            }
+            MetadataOperation::Remove { path } => {
+                remove_at(&mut document, &parse_pointer(path)?, path)?
+            }
+        }
+    }
+
+    let size = serde_json::to_vec(&document)
+        .map(|bytes| bytes.len())
+        .unwrap_or(usize::MAX);
+    if size > config.max_bytes {
+        return Err(ServiceError::ValidationError(format!(
+            "Metadata size ({} bytes) exceeds the limit of {} bytes",
+            size, config.max_bytes
+        )));
+    }
+    Ok(document)
+}
@@ -1,7 +1,17 @@
-use crate::schema::{labels, projects, task_labels, tasks, time_entries};
-use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use crate::ids::{LabelId, ProjectId, TaskId, TimeEntryId};
+use crate::schema::{
+    account_deletion_requests, ai_summaries, analytics_snapshots, announcements, api_keys,
+    app_passwords, automation_rules, backup_configs, calendar_integrations, calendar_project_links,
+    calendar_suggestions, client_preferences, custom_field_definitions, devices, experiments,
+    feature_flag_overrides, feature_flags, feedback, focus_sessions, inbound_email_addresses,
+    labels, maintenance_jobs, notifications, pomodoro_interruptions, projects, public_badge_tokens,
+    push_deliveries, recent_views, task_aging_rules, task_custom_values, task_external_links,
+    task_labels, tasks, time_entries, timesheets, user_settings, workspace_members, workspaces,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use diesel::prelude::*;
 use serde::{Deserialize, Deserializer, Serialize}; // Deserializer est nécessaire pour deserialize_with
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 use diesel::sql_types::BigInt; // Pour les sommes de durées
@@ -32,6 +42,19 @@ where
     }
 }
 
+// Pour Option<Option<TaskId>>, Option<Option<ProjectId>>... (identifiants typés, voir ids.rs)
+fn deserialize_opt_opt_id<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    match Option::<T>::deserialize(deserializer) {
+        Ok(Some(id)) => Ok(Some(Some(id))),
+        Ok(None) => Ok(Some(None)),
+        Err(e) => Err(e),
+    }
+}
+
 // Pour Option<Option<NaiveDate>>
 fn deserialize_opt_opt_naivedate<'de, D>(
     deserializer: D,
@@ -46,6 +69,20 @@ where
     }
 }
 
+// Pour Option<Option<NaiveTime>>
+fn deserialize_opt_opt_naivetime<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<NaiveTime>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<NaiveTime>::deserialize(deserializer) {
+        Ok(Some(t)) => Ok(Some(Some(t))),
+        Ok(None) => Ok(Some(None)),
+        Err(e) => Err(e),
+    }
+}
+
 // Pour Option<Option<i32>>
 fn deserialize_opt_opt_i32<'de, D>(deserializer: D) -> Result<Option<Option<i32>>, D::Error>
 where
@@ -74,17 +111,69 @@ where
     }
 }
 
+// Pour Option<Option<TaskContext>>
+fn deserialize_opt_opt_task_context<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<TaskContext>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<TaskContext>::deserialize(deserializer) {
+        Ok(Some(c)) => Ok(Some(Some(c))),
+        Ok(None) => Ok(Some(None)),
+        Err(e) => Err(e),
+    }
+}
+
+// Pour Option<Option<FollowUpConfig>>
+fn deserialize_opt_opt_follow_up<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<FollowUpConfig>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<FollowUpConfig>::deserialize(deserializer) {
+        Ok(Some(c)) => Ok(Some(Some(c))),
+        Ok(None) => Ok(Some(None)),
+        Err(e) => Err(e),
+    }
+}
+
 // --- Project Model ---
 #[derive(Queryable, Selectable, Identifiable, Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[diesel(table_name = projects)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Project {
-    pub id: Uuid,
+    pub id: ProjectId,
     pub user_id: Uuid,
     pub name: String,
     pub color: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    // Espace partagé auquel le projet est rattaché (None = projet personnel)
+    pub workspace_id: Option<Uuid>,
+    pub metadata: serde_json::Value,
+    // Emoji ou identifiant d'icône choisi par l'utilisateur
+    pub icon: Option<String>,
+    // Limites WIP par statut (voir wip_limits.rs)
+    pub wip_limits: serde_json::Value,
+    // Délai cible de complétion des tâches, en heures (voir sla.rs)
+    pub sla_hours: Option<i32>,
+    // Code ISO 4217 des montants du projet (voir currency.rs)
+    pub currency: Option<String>,
+    // Compteurs tenus à jour par trigger sur tasks (statuts de DONE_TASK_STATUSES = terminées)
+    pub open_task_count: i32,
+    pub completed_task_count: i32,
+    // Thème visuel (voir themes.rs) ; absent des sauvegardes antérieures
+    #[serde(default = "default_project_theme")]
+    pub theme: serde_json::Value,
+    // Projet terminé et archivé (voir project_completion.rs) ; None = actif
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+fn default_project_theme() -> serde_json::Value {
+    serde_json::json!({})
 }
 
 #[derive(Insertable, Deserialize, Debug)]
@@ -93,6 +182,12 @@ pub struct NewProject {
     pub user_id: Uuid,
     pub name: String,
     pub color: Option<String>,
+    pub workspace_id: Option<Uuid>,
+    pub icon: Option<String>,
+    pub wip_limits: Option<serde_json::Value>,
+    pub sla_hours: Option<i32>,
+    pub currency: Option<String>,
+    pub theme: Option<serde_json::Value>,
 }
 
 #[derive(AsChangeset, Debug)]
@@ -100,9 +195,25 @@ pub struct NewProject {
 pub struct UpdateProjectChangeset {
     pub name: Option<String>,
     pub color: Option<Option<String>>,
+    pub workspace_id: Option<Option<Uuid>>,
+    pub icon: Option<Option<String>>,
+    pub wip_limits: Option<serde_json::Value>,
+    pub sla_hours: Option<Option<i32>>,
+    pub currency: Option<Option<String>>,
+    pub theme: Option<serde_json::Value>,
     pub updated_at: Option<NaiveDateTime>,
 }
 
+// Statuts de tâche considérés comme terminés ("completed" via toggle, "done" côté board).
+// Repris dans le trigger des compteurs de projets (update_project_task_counts).
+pub const DONE_TASK_STATUSES: [&str; 2] = ["completed", "done"];
+
+// Horizons de planification d'une tâche, indépendants du statut
+pub const TASK_STAGE_BACKLOG: &str = "backlog";
+pub const TASK_STAGE_ACTIVE: &str = "active";
+pub const TASK_STAGE_ARCHIVE: &str = "archive";
+pub const TASK_STAGES: [&str; 3] = [TASK_STAGE_BACKLOG, TASK_STAGE_ACTIVE, TASK_STAGE_ARCHIVE];
+
 // --- Task Model (Diesel Queryable) ---
 // Cette struct est pour interagir avec la DB. Elle ne contiendra pas directement les labels.
 #[derive(
@@ -112,9 +223,9 @@ pub struct UpdateProjectChangeset {
 #[diesel(belongs_to(Project, foreign_key = project_id))]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Task {
-    pub id: Uuid,
+    pub id: TaskId,
     pub user_id: Uuid,
-    pub project_id: Option<Uuid>,
+    pub project_id: Option<ProjectId>,
     pub title: String,
     pub description: Option<String>,
     pub status: String,
@@ -123,6 +234,18 @@ pub struct Task {
     pub order: Option<i32>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub source: Option<serde_json::Value>,
+    pub context: Option<serde_json::Value>,
+    pub metadata: serde_json::Value,
+    pub scheduled_start: Option<DateTime<Utc>>,
+    pub scheduled_end: Option<DateTime<Utc>>,
+    pub is_pinned: bool,
+    pub snoozed_until: Option<DateTime<Utc>>,
+    pub follow_up: Option<serde_json::Value>,
+    pub stage: String,
+    pub time_budget_seconds: Option<i32>,
+    // Dernier seuil d'alerte de budget notifié (voir time_budgets.rs)
+    pub time_budget_alerted_pct: i16,
 }
 
 // === NOUVELLE STRUCT POUR LA RÉPONSE API DE TÂCHE ===
@@ -130,9 +253,9 @@ pub struct Task {
 #[derive(Serialize, Deserialize, Debug, Clone)] // Ajouter Deserialize pour la cohérence si besoin
 pub struct TaskApiResponse {
     // Champs de la tâche (copiés de la struct Task)
-    pub id: Uuid,
+    pub id: TaskId,
     pub user_id: Uuid,
-    pub project_id: Option<Uuid>,
+    pub project_id: Option<ProjectId>,
     pub title: String,
     pub description: Option<String>,
     pub status: String,
@@ -141,8 +264,39 @@ pub struct TaskApiResponse {
     pub task_order: Option<i32>, // Utiliser un nom de champ différent de Task.order pour éviter confusion
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    // Origine de la tâche (ex: {"type": "browser_extension", "url": ...})
+    pub source: Option<serde_json::Value>,
+    // Contexte saisi par le client (voir TaskContext)
+    pub context: Option<serde_json::Value>,
+    // Clés libres des intégrations (PATCH /tasks/{id}/metadata)
+    pub metadata: serde_json::Value,
+    // Créneau planifié, synchronisé avec le calendrier connecté
+    pub scheduled_start: Option<DateTime<Utc>>,
+    pub scheduled_end: Option<DateTime<Utc>>,
+    // Épinglée en tête des listes
+    pub is_pinned: bool,
+    // Masquée des listes par défaut jusqu'à cette date
+    pub snoozed_until: Option<DateTime<Utc>>,
+    // Tâche de suivi à créer à la complétion (voir FollowUpConfig)
+    pub follow_up: Option<serde_json::Value>,
+    // Horizon de planification : backlog, active ou archive (voir TASK_STAGES)
+    pub stage: String,
     // Labels associés
     pub labels: Vec<Label>,
+    // Valeurs des champs personnalisés du projet
+    #[serde(default)]
+    pub custom_fields: Vec<TaskCustomFieldValue>,
+    // Tâches de suivi créées par cette requête (création ou complétion)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub follow_up_tasks: Vec<TaskApiResponse>,
+    // Pomodoros terminés de l'utilisateur sur la tâche (?expand=pomodoros)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pomodoro_count: Option<i64>,
+    // Budget de temps en secondes, et part consommée par le temps suivi (en %)
+    #[serde(default)]
+    pub time_budget_seconds: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_used_pct: Option<f64>,
 }
 
 // Helper pour convertir une Task DB en TaskApiResponse (sans labels au début)
@@ -160,7 +314,21 @@ impl From<Task> for TaskApiResponse {
             task_order: task_db.order, // Mapper depuis Task.order
             created_at: task_db.created_at,
             updated_at: task_db.updated_at,
+            source: task_db.source,
+            context: task_db.context,
+            metadata: task_db.metadata,
+            scheduled_start: task_db.scheduled_start,
+            scheduled_end: task_db.scheduled_end,
+            is_pinned: task_db.is_pinned,
+            snoozed_until: task_db.snoozed_until,
+            follow_up: task_db.follow_up,
+            stage: task_db.stage,
             labels: Vec::new(), // Initialisé vide, sera peuplé dans le handler
+            custom_fields: Vec::new(),
+            follow_up_tasks: Vec::new(),
+            pomodoro_count: None,
+            time_budget_seconds: task_db.time_budget_seconds,
+            budget_used_pct: None,
         }
     }
 }
@@ -169,25 +337,41 @@ impl From<Task> for TaskApiResponse {
 #[diesel(table_name = tasks)]
 pub struct NewTask {
     pub user_id: Uuid,
-    pub project_id: Option<Uuid>,
+    pub project_id: Option<ProjectId>,
     pub title: String,
     pub description: Option<String>,
     pub status: Option<String>,
     pub due_date: Option<NaiveDate>,
     #[diesel(column_name = task_order)]
     pub order: Option<i32>,
+    pub source: Option<serde_json::Value>,
+    pub context: Option<serde_json::Value>,
+    pub scheduled_start: Option<DateTime<Utc>>,
+    pub scheduled_end: Option<DateTime<Utc>>,
+    pub follow_up: Option<serde_json::Value>,
+    // None = stage par défaut de la table (active)
+    pub stage: Option<String>,
+    pub time_budget_seconds: Option<i32>,
 }
 
 #[derive(AsChangeset, Debug)]
 #[diesel(table_name = tasks)]
 pub struct UpdateTaskChangeset {
-    pub project_id: Option<Option<Uuid>>,
+    pub project_id: Option<Option<ProjectId>>,
     pub title: Option<String>,
     pub description: Option<Option<String>>,
     pub status: Option<String>,
     pub due_date: Option<Option<NaiveDate>>,
     #[diesel(column_name = task_order)]
     pub order: Option<Option<i32>>,
+    pub context: Option<Option<serde_json::Value>>,
+    pub scheduled_start: Option<Option<DateTime<Utc>>>,
+    pub scheduled_end: Option<Option<DateTime<Utc>>>,
+    pub follow_up: Option<Option<serde_json::Value>>,
+    pub stage: Option<String>,
+    pub time_budget_seconds: Option<Option<i32>>,
+    // Remis à 0 quand le budget change
+    pub time_budget_alerted_pct: Option<i16>,
     pub updated_at: Option<NaiveDateTime>,
 }
 
@@ -196,12 +380,15 @@ pub struct UpdateTaskChangeset {
 #[diesel(table_name = labels)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Label {
-    pub id: Uuid,
+    pub id: LabelId,
     pub user_id: Uuid,
     pub name: String,
     pub color: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub icon: Option<String>,
+    // Délai cible de complétion des tâches portant ce label, en heures
+    pub sla_hours: Option<i32>,
 }
 
 #[derive(Insertable, Deserialize, Debug)]
@@ -210,6 +397,8 @@ pub struct NewLabel {
     pub user_id: Uuid,
     pub name: String,
     pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sla_hours: Option<i32>,
 }
 
 #[derive(AsChangeset, Debug)]
@@ -217,6 +406,8 @@ pub struct NewLabel {
 pub struct UpdateLabelChangeset {
     pub name: Option<String>,
     pub color: Option<Option<String>>,
+    pub icon: Option<Option<String>>,
+    pub sla_hours: Option<Option<i32>>,
     pub updated_at: Option<NaiveDateTime>,
 }
 
@@ -237,15 +428,15 @@ pub struct UpdateLabelChangeset {
 #[diesel(belongs_to(Label))]
 #[diesel(primary_key(task_id, label_id))]
 pub struct TaskLabel {
-    pub task_id: Uuid,
-    pub label_id: Uuid,
+    pub task_id: TaskId,
+    pub label_id: LabelId,
 }
 
 #[derive(Insertable, Deserialize, Debug)]
 #[diesel(table_name = task_labels)]
 pub struct NewTaskLabelAssociation {
-    pub task_id: Uuid,
-    pub label_id: Uuid,
+    pub task_id: TaskId,
+    pub label_id: LabelId,
 }
 
 // --- TimeEntry Model ---
@@ -263,36 +454,193 @@ pub struct NewTaskLabelAssociation {
 #[diesel(table_name = time_entries)]
 #[diesel(belongs_to(Task))]
 pub struct TimeEntry {
-    pub id: Uuid,
+    pub id: TimeEntryId,
     pub user_id: Uuid,
-    pub task_id: Uuid,
+    pub task_id: TaskId,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
     pub duration_seconds: Option<i32>,
     pub is_pomodoro_session: bool,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    // Pause détachée d'une entrée, exclue des totaux
+    pub is_break: bool,
+    // Écart d'horloge du client à la création (voir clock_skew.rs)
+    pub clock_skew_seconds: Option<i32>,
+    pub suspect_clock: bool,
+    // Dernier signal de vie du minuteur (voir timer_recovery.rs)
+    #[serde(default)]
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+    // Arrêtée d'office : faute de signal (timer_recovery.rs) ou à l'heure d'arrêt quotidienne
+    // (timer_auto_stop.rs)
+    #[serde(default)]
+    pub auto_stopped: bool,
+    // Session de concentration active à la création (voir focus_sessions.rs)
+    #[serde(default)]
+    pub focus_session_id: Option<Uuid>,
 }
 
 #[derive(Insertable, Deserialize, Debug)]
 #[diesel(table_name = time_entries)]
 pub struct NewTimeEntry {
     pub user_id: Uuid,
-    pub task_id: Uuid,
+    pub task_id: TaskId,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
     pub duration_seconds: Option<i32>,
     pub is_pomodoro_session: Option<bool>,
+    pub is_break: Option<bool>,
+    pub clock_skew_seconds: Option<i32>,
+    pub suspect_clock: Option<bool>,
+    pub focus_session_id: Option<Uuid>,
 }
 
 #[derive(AsChangeset, Debug)]
 #[diesel(table_name = time_entries)]
 pub struct UpdateTimeEntryChangeset {
+    pub task_id: Option<TaskId>,
     pub start_time: Option<DateTime<Utc>>,
     pub end_time: Option<Option<DateTime<Utc>>>,
     pub duration_seconds: Option<Option<i32>>,
     pub is_pomodoro_session: Option<bool>,
     pub updated_at: Option<NaiveDateTime>,
+    // Horaires corrigés à la main : l'entrée n'est plus suspecte
+    pub suspect_clock: Option<bool>,
+}
+
+// Session pomodoro terminée (time entry marquée is_pomodoro_session)
+#[derive(Queryable, Selectable, Serialize, Debug)]
+#[diesel(table_name = time_entries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PomodoroSession {
+    pub id: TimeEntryId,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i32>,
+}
+
+// Historique des pomodoros d'une tâche (GET /tasks/{id}/pomodoros)
+#[derive(Serialize, Debug)]
+pub struct TaskPomodoroHistoryResponse {
+    pub task_id: TaskId,
+    pub count: i64,
+    pub total_seconds: i64,
+    pub sessions: Vec<PomodoroSession>,
+    // Interruptions notées pendant ces sessions (time_entry_id = id de la session)
+    pub interruptions: Vec<PomodoroInterruption>,
+}
+
+// --- Pomodoro Interruption Models ---
+pub const INTERRUPTION_REASONS: [&str; 6] = [
+    "notification",
+    "colleague",
+    "meeting",
+    "call",
+    "self",
+    "other",
+];
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = pomodoro_interruptions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PomodoroInterruption {
+    pub id: Uuid,
+    pub time_entry_id: TimeEntryId,
+    pub user_id: Uuid,
+    pub reason: String,
+    pub note: Option<String>,
+    pub interrupted_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = pomodoro_interruptions)]
+pub struct NewPomodoroInterruption {
+    pub time_entry_id: TimeEntryId,
+    pub user_id: Uuid,
+    pub reason: String,
+    pub note: Option<String>,
+    pub interrupted_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreatePomodoroInterruptionPayload {
+    pub reason: String,
+    pub note: Option<String>,
+    // Maintenant par défaut
+    pub interrupted_at: Option<DateTime<Utc>>,
+}
+
+// --- Focus Session Models ---
+// Session de concentration liée à un label ou à un projet (exactement l'un des deux)
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = focus_sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FocusSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub label_id: Option<LabelId>,
+    pub project_id: Option<ProjectId>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    // Bilan figé à la fin de la session (voir focus_sessions.rs)
+    pub summary: Option<serde_json::Value>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = focus_sessions)]
+pub struct NewFocusSession {
+    pub user_id: Uuid,
+    pub label_id: Option<LabelId>,
+    pub project_id: Option<ProjectId>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StartFocusSessionPayload {
+    pub label_id: Option<LabelId>,
+    pub project_id: Option<ProjectId>,
+}
+
+// --- Task External Link Models ---
+// Référence externe typée sur une tâche (voir external_links.rs)
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = task_external_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TaskExternalLink {
+    pub id: Uuid,
+    pub task_id: TaskId,
+    pub user_id: Uuid,
+    pub kind: String,
+    // Forme normalisée : URL, "owner/repo#123", clé Jira ou Message-ID sans chevrons
+    pub reference: String,
+    pub url: Option<String>,
+    // Métadonnées lues sur la page à l'ajout (None si indisponibles)
+    pub title: Option<String>,
+    pub favicon_url: Option<String>,
+    pub metadata_fetched_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = task_external_links)]
+pub struct NewTaskExternalLink {
+    pub task_id: TaskId,
+    pub user_id: Uuid,
+    pub kind: String,
+    pub reference: String,
+    pub url: Option<String>,
+    pub title: Option<String>,
+    pub favicon_url: Option<String>,
+    pub metadata_fetched_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateTaskExternalLinkPayload {
+    // url | github_issue | jira | email
+    pub kind: String,
+    pub reference: String,
+    // Lien vers le ticket Jira ou le message ; déduit de la référence pour les autres types
+    pub url: Option<String>,
 }
 
 // --- PAYLOAD DTOs ---
@@ -301,6 +649,12 @@ pub struct UpdateTimeEntryChangeset {
 pub struct CreateProjectPayload {
     pub name: String,
     pub color: Option<String>,
+    pub workspace_id: Option<Uuid>,
+    pub icon: Option<String>,
+    pub wip_limits: Option<BTreeMap<String, i64>>,
+    pub sla_hours: Option<i32>,
+    pub currency: Option<String>,
+    pub theme: Option<ProjectThemePayload>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -308,22 +662,60 @@ pub struct UpdateProjectPayload {
     pub name: Option<String>,
     #[serde(deserialize_with = "deserialize_opt_opt_string", default)]
     pub color: Option<Option<String>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_uuid", default)]
+    pub workspace_id: Option<Option<Uuid>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_string", default)]
+    pub icon: Option<Option<String>>,
+    // Remplace l'ensemble des limites WIP ({} pour les retirer)
+    pub wip_limits: Option<BTreeMap<String, i64>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_i32", default)]
+    pub sla_hours: Option<Option<i32>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_string", default)]
+    pub currency: Option<Option<String>>,
+    // Remplace l'ensemble du thème ({} pour le retirer)
+    pub theme: Option<ProjectThemePayload>,
+}
+
+// Thème d'un projet ; chaque champ est facultatif
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectThemePayload {
+    // Clé d'un preset de GET /themes/presets
+    pub preset: Option<String>,
+    pub accent_color: Option<String>,
+    pub cover_image_url: Option<String>,
+    pub emoji: Option<String>,
+}
+
+// Thème prédéfini proposé par GET /themes/presets
+#[derive(Serialize, Debug)]
+pub struct ThemePreset {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub accent_color: &'static str,
+    pub emoji: &'static str,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct CreateTaskPayload {
-    pub project_id: Option<Uuid>,
+    pub project_id: Option<ProjectId>,
     pub title: String,
     pub description: Option<String>,
     pub status: Option<String>,
     pub due_date: Option<NaiveDate>,
     pub order: Option<i32>,
+    pub context: Option<TaskContext>,
+    pub scheduled_start: Option<DateTime<Utc>>,
+    pub scheduled_end: Option<DateTime<Utc>>,
+    pub follow_up: Option<FollowUpConfig>,
+    pub stage: Option<String>,
+    pub time_budget_seconds: Option<i32>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct UpdateTaskPayload {
-    #[serde(deserialize_with = "deserialize_opt_opt_uuid", default)]
-    pub project_id: Option<Option<Uuid>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_id", default)]
+    pub project_id: Option<Option<ProjectId>>,
     pub title: Option<String>,
     #[serde(deserialize_with = "deserialize_opt_opt_string", default)]
     pub description: Option<Option<String>>,
@@ -332,12 +724,91 @@ pub struct UpdateTaskPayload {
     pub due_date: Option<Option<NaiveDate>>,
     #[serde(deserialize_with = "deserialize_opt_opt_i32", default)]
     pub order: Option<Option<i32>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_task_context", default)]
+    pub context: Option<Option<TaskContext>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_datetime_utc", default)]
+    pub scheduled_start: Option<Option<DateTime<Utc>>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_datetime_utc", default)]
+    pub scheduled_end: Option<Option<DateTime<Utc>>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_follow_up", default)]
+    pub follow_up: Option<Option<FollowUpConfig>>,
+    pub stage: Option<String>,
+    #[serde(deserialize_with = "deserialize_opt_opt_i32", default)]
+    pub time_budget_seconds: Option<Option<i32>>,
+}
+
+// Promotion ou rétrogradation groupée (POST /tasks/promote, /tasks/demote)
+#[derive(Deserialize, Debug)]
+pub struct BulkTaskStagePayload {
+    pub task_ids: Vec<TaskId>,
+}
+
+// Changement de statut groupé (POST /tasks/status), appliqué à toutes les tâches ou à aucune
+#[derive(Deserialize, Debug)]
+pub struct BulkTaskStatusPayload {
+    pub task_ids: Vec<TaskId>,
+    pub status: String,
+}
+
+// Entité d'un résultat d'opération groupée sur les tâches (voir batch.rs)
+#[derive(Serialize, Debug)]
+pub struct BulkTaskRef {
+    pub task_id: TaskId,
+    // Statut avant un changement de statut groupé
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_status: Option<String>,
+}
+
+// Tâche de suivi créée quand la tâche passe à un statut terminé.
+// title peut contenir {title}, remplacé par le titre de la tâche d'origine.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FollowUpConfig {
+    pub title: String,
+    // Échéance à N jours de la complétion
+    pub due_in_days: Option<i64>,
+}
+
+// Report d'une tâche : une date précise (until) ou une durée en minutes
+#[derive(Deserialize, Debug)]
+pub struct SnoozeTaskPayload {
+    pub until: Option<DateTime<Utc>>,
+    pub duration_minutes: Option<i64>,
+}
+
+// Contexte d'une tâche, stocké en JSONB (clés absentes omises)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TaskContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    // Application d'origine (ex: "mobile", "web")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct CreateLabelPayload {
     pub name: String,
     pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sla_hours: Option<i32>,
+}
+
+// Création en lot : un label existant du même nom (casse ignorée) est réutilisé
+#[derive(Deserialize, Debug)]
+pub struct BulkCreateLabelsPayload {
+    pub labels: Vec<CreateLabelPayload>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BulkLabelResult {
+    #[serde(flatten)]
+    pub label: Label,
+    // false si le label existait déjà
+    pub created: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -345,19 +816,29 @@ pub struct UpdateLabelPayload {
     pub name: Option<String>,
     #[serde(deserialize_with = "deserialize_opt_opt_string", default)]
     pub color: Option<Option<String>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_string", default)]
+    pub icon: Option<Option<String>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_i32", default)]
+    pub sla_hours: Option<Option<i32>>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct CreateTimeEntryPayload {
-    pub task_id: Uuid,
+    pub task_id: TaskId,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
     pub duration_seconds: Option<i32>,
     pub is_pomodoro_session: Option<bool>,
+    // Heure de l'horloge du client à l'envoi et son décalage UTC en minutes ;
+    // permettent de détecter une horloge déréglée (voir clock_skew.rs)
+    pub client_time: Option<DateTime<Utc>>,
+    pub client_offset: Option<i32>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct UpdateTimeEntryPayload {
+    // Réaffecte l'entrée à une autre tâche (accessible en écriture)
+    pub task_id: Option<TaskId>,
     pub start_time: Option<DateTime<Utc>>, // Pourrait être Option<Option<NaiveDateTime>> si on veut le mettre à NULL
     #[serde(deserialize_with = "deserialize_opt_opt_datetime_utc", default)]
     pub end_time: Option<Option<DateTime<Utc>>>,
@@ -366,17 +847,35 @@ pub struct UpdateTimeEntryPayload {
     pub is_pomodoro_session: Option<bool>, // Boolean ne peut pas vraiment être "absent vs null", juste true/false/absent
 }
 
+// Action appliquée à l'intervalle d'inactivité signalé par le client
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IdleAction {
+    Discard,
+    Break,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TrimIdlePayload {
+    pub idle_start: DateTime<Utc>,
+    pub idle_end: DateTime<Utc>,
+    pub action: IdleAction,
+}
+
 // --- Pagination DTOs ---
 #[derive(Deserialize, Debug)]
+#[allow(dead_code)]
 pub struct PaginationParams {
     #[serde(default = "default_page")]
     pub page: i64,
     #[serde(default = "default_per_page")]
     pub per_page: i64,
 }
+#[allow(dead_code)]
 fn default_page() -> i64 {
     1
 }
+#[allow(dead_code)]
 fn default_per_page() -> i64 {
     10
 }
@@ -395,7 +894,7 @@ pub struct PaginatedResponse<T> {
 #[diesel(check_for_backend(diesel::pg::Pg))] // Nécessaire pour QueryableByName avec un backend spécifique
 pub struct TimeByProjectStat {
     #[diesel(sql_type = diesel::sql_types::Uuid)] // Spécifier le type SQL pour QueryableByName
-    pub project_id: Uuid,
+    pub project_id: ProjectId,
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub project_name: String,
     // Diesel sum sur i32 retourne i64 (BigInt). Optionnel si certains projets n'ont pas de temps.
@@ -413,6 +912,22 @@ pub struct ProductivityTrendPoint {
     pub total_duration_seconds: i64,
 }
 
+// Tâche ouverte similaire retournée par la détection de doublons (similarité trigramme)
+#[derive(QueryableByName, Serialize, Debug, Clone)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TaskDuplicateCandidate {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub id: TaskId,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub title: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub status: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Uuid>)]
+    pub project_id: Option<ProjectId>,
+    #[diesel(sql_type = diesel::sql_types::Float4)]
+    pub similarity: f32,
+}
+
 // DTO pour les paramètres de requête des analytics
 #[derive(Deserialize, Debug)]
 pub struct AnalyticsQueryPeriod {
@@ -420,4 +935,1465 @@ pub struct AnalyticsQueryPeriod {
     pub period: Option<String>,
     pub start_date: Option<NaiveDate>, // YYYY-MM-DD
     pub end_date: Option<NaiveDate>,   // YYYY-MM-DD
+    // Premier jour de la semaine ; à défaut, celui des préférences de l'utilisateur
+    pub week_start: Option<String>,
+}
+
+// Paramètres de GET /analytics/export : format du fichier + période (mêmes règles que les
+// autres analytics)
+#[derive(Deserialize, Debug)]
+pub struct AnalyticsExportQuery {
+    pub format: Option<String>,
+    pub period: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub week_start: Option<String>,
+}
+
+// Paramètres de GET /analytics/compare : période courante et période de référence.
+// Sans référence, la période de même durée qui précède la période courante est utilisée.
+#[derive(Deserialize, Debug)]
+pub struct AnalyticsComparisonQuery {
+    pub period: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub against: Option<String>,
+    pub against_start_date: Option<NaiveDate>,
+    pub against_end_date: Option<NaiveDate>,
+    pub week_start: Option<String>,
+}
+
+// Métriques d'une période
+#[derive(Serialize, Debug)]
+pub struct PeriodMetrics {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub tracked_seconds: i64,
+    pub tasks_completed: i64,
+    pub time_by_project: Vec<TimeByProjectStat>,
+}
+
+// Écart entre la période courante et la référence ; percent est None si la référence vaut 0
+#[derive(Serialize, Debug)]
+pub struct MetricDelta {
+    pub current: i64,
+    pub previous: i64,
+    pub delta: i64,
+    pub percent_change: Option<f64>,
+}
+
+impl MetricDelta {
+    pub fn new(current: i64, previous: i64) -> Self {
+        MetricDelta {
+            current,
+            previous,
+            delta: current - previous,
+            percent_change: (previous != 0)
+                .then(|| ((current - previous) as f64 / previous as f64 * 1000.0).round() / 10.0),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ProjectTimeComparison {
+    pub project_id: ProjectId,
+    pub project_name: String,
+    pub tracked_seconds: MetricDelta,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PeriodComparisonResponse {
+    pub current: PeriodMetrics,
+    pub previous: PeriodMetrics,
+    pub tracked_seconds: MetricDelta,
+    pub tasks_completed: MetricDelta,
+    pub time_by_project: Vec<ProjectTimeComparison>,
+}
+
+// Concentration sur une période (GET /analytics/focus)
+#[derive(Serialize, Debug)]
+pub struct FocusAnalyticsResponse {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub pomodoro_sessions: i64,
+    pub focus_seconds: i64,
+    pub interruptions: i64,
+    // Sessions terminées sans aucune interruption
+    pub uninterrupted_sessions: i64,
+    pub interruptions_per_session: Option<f64>,
+    pub interruptions_by_reason: Vec<InterruptionReasonStat>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct InterruptionReasonStat {
+    pub reason: String,
+    pub count: i64,
+}
+
+// --- Analytics Snapshot Model ---
+// metrics contient un PeriodMetrics sérialisé au moment de la capture
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = analytics_snapshots)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AnalyticsSnapshot {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub metrics: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = analytics_snapshots)]
+pub struct NewAnalyticsSnapshot {
+    pub user_id: Uuid,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub metrics: serde_json::Value,
+}
+
+// Filtre de GET /analytics/snapshots : snapshots dont la période chevauche [from, to]
+#[derive(Deserialize, Debug)]
+pub struct AnalyticsSnapshotListQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+// --- AI Summary Model ---
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = ai_summaries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AiSummary {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub summary: String,
+    pub provider: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = ai_summaries)]
+pub struct NewAiSummary {
+    pub user_id: Uuid,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub summary: String,
+    pub provider: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AiSummaryResponse {
+    #[serde(flatten)]
+    pub summary: AiSummary,
+    // true si le résumé provient du cache pour cette période
+    pub cached: bool,
+}
+
+// --- Custom Field Models ---
+// Types supportés : "text", "number", "date" (YYYY-MM-DD), "select" (valeur parmi options)
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = custom_field_definitions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CustomFieldDefinition {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub project_id: ProjectId,
+    pub name: String,
+    pub field_type: String,
+    pub options: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = custom_field_definitions)]
+pub struct NewCustomFieldDefinition {
+    pub user_id: Uuid,
+    pub project_id: ProjectId,
+    pub name: String,
+    pub field_type: String,
+    pub options: serde_json::Value,
+}
+
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = custom_field_definitions)]
+pub struct UpdateCustomFieldChangeset {
+    pub name: Option<String>,
+    pub options: Option<serde_json::Value>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = task_custom_values)]
+pub struct NewTaskCustomValue {
+    pub task_id: TaskId,
+    pub field_id: Uuid,
+    pub value: String,
+}
+
+// Valeur d'un champ personnalisé telle qu'exposée dans TaskApiResponse
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TaskCustomFieldValue {
+    pub field_id: Uuid,
+    pub name: String,
+    pub field_type: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateCustomFieldPayload {
+    pub name: String,
+    pub field_type: String,
+    pub options: Option<Vec<String>>,
+}
+
+// Le type d'un champ n'est pas modifiable une fois créé
+#[derive(Deserialize, Debug)]
+pub struct UpdateCustomFieldPayload {
+    pub name: Option<String>,
+    pub options: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SetTaskCustomValuePayload {
+    pub value: serde_json::Value,
+}
+
+// --- User Settings Model ---
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = user_settings)]
+#[diesel(primary_key(user_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserSettings {
+    pub user_id: Uuid,
+    pub notify_watched_status_changes: bool,
+    pub notify_watched_comments: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    // "monday", "sunday" ou "saturday"
+    pub week_start: String,
+    // Clôture comptable personnelle (voir timesheets::ensure_entry_unlocked)
+    pub time_locked_before: Option<NaiveDate>,
+    // Catégories de notifications push (voir push.rs)
+    pub push_due_reminders: bool,
+    pub push_timer_nudges: bool,
+    pub push_mentions: bool,
+    // Heures calmes en heure locale (utc_offset_minutes), None = désactivées
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+    pub utc_offset_minutes: i32,
+    pub push_batch_minutes: i32,
+    // Relances sur le rythme de travail (voir nudges.rs), évaluées à nudge_check_time
+    pub nudge_planned_tasks: bool,
+    pub nudge_no_time_tracked: bool,
+    pub nudge_check_time: NaiveTime,
+    // Projet des tâches créées sans project_id (None = Inbox)
+    pub default_project_id: Option<ProjectId>,
+    // Exclu des classements des espaces partagés
+    pub leaderboard_opt_out: bool,
+    // Arrêt quotidien des minuteurs oubliés (voir timer_auto_stop.rs), None = désactivé
+    pub timer_auto_stop_time: Option<NaiveTime>,
+    // "cap" ou "split"
+    pub timer_auto_stop_mode: String,
+}
+
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = user_settings)]
+pub struct UpdateUserSettingsChangeset {
+    pub notify_watched_status_changes: Option<bool>,
+    pub notify_watched_comments: Option<bool>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub week_start: Option<String>,
+    pub time_locked_before: Option<Option<NaiveDate>>,
+    pub push_due_reminders: Option<bool>,
+    pub push_timer_nudges: Option<bool>,
+    pub push_mentions: Option<bool>,
+    pub quiet_hours_start: Option<Option<NaiveTime>>,
+    pub quiet_hours_end: Option<Option<NaiveTime>>,
+    pub utc_offset_minutes: Option<i32>,
+    pub push_batch_minutes: Option<i32>,
+    pub nudge_planned_tasks: Option<bool>,
+    pub nudge_no_time_tracked: Option<bool>,
+    pub nudge_check_time: Option<NaiveTime>,
+    pub default_project_id: Option<Option<ProjectId>>,
+    pub leaderboard_opt_out: Option<bool>,
+    pub timer_auto_stop_time: Option<Option<NaiveTime>>,
+    pub timer_auto_stop_mode: Option<String>,
+}
+
+// Préférences d'interface d'un client, par espace de noms (PUT /settings/client/{namespace})
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = client_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ClientPreferences {
+    pub user_id: Uuid,
+    pub namespace: String,
+    pub data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PutClientPreferencesPayload {
+    pub data: serde_json::Value,
+    // updated_at lu par le client ; 409 si le document a changé depuis.
+    // Absent : la dernière écriture l'emporte.
+    pub expected_updated_at: Option<DateTime<Utc>>,
+}
+
+// --- Macro Models ---
+// Actions rapides nommées, stockées dans les préférences client (espace de noms "macros")
+// et exécutées côté serveur sur une tâche (POST /macros/{id}/run)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum MacroStep {
+    // Entrée de temps de `minutes` se terminant à l'exécution
+    LogTime {
+        minutes: i64,
+        #[serde(default)]
+        pomodoro: bool,
+    },
+    SetStatus {
+        status: String,
+    },
+    SetStage {
+        stage: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct QuickActionMacro {
+    pub id: String,
+    pub name: String,
+    // Raccourci clavier, interprété par le client uniquement
+    pub shortcut: Option<String>,
+    pub steps: Vec<MacroStep>,
+}
+
+// Contenu de l'espace de noms "macros"
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct MacroDocument {
+    pub macros: Vec<QuickActionMacro>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct RunMacroPayload {
+    // Tâche ciblée ; à défaut, celle du timer en cours
+    pub task_id: Option<TaskId>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MacroRunResponse {
+    pub macro_id: String,
+    pub task: TaskApiResponse,
+    pub time_entries: Vec<TimeEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateUserSettingsPayload {
+    pub notify_watched_status_changes: Option<bool>,
+    pub notify_watched_comments: Option<bool>,
+    pub week_start: Option<String>,
+    // null lève la clôture
+    #[serde(deserialize_with = "deserialize_opt_opt_naivedate", default)]
+    pub time_locked_before: Option<Option<NaiveDate>>,
+    pub push_due_reminders: Option<bool>,
+    pub push_timer_nudges: Option<bool>,
+    pub push_mentions: Option<bool>,
+    // "22:00:00" ; les deux bornes vont ensemble, null sur les deux désactive les heures calmes
+    #[serde(deserialize_with = "deserialize_opt_opt_naivetime", default)]
+    pub quiet_hours_start: Option<Option<NaiveTime>>,
+    #[serde(deserialize_with = "deserialize_opt_opt_naivetime", default)]
+    pub quiet_hours_end: Option<Option<NaiveTime>>,
+    pub utc_offset_minutes: Option<i32>,
+    pub push_batch_minutes: Option<i32>,
+    pub nudge_planned_tasks: Option<bool>,
+    pub nudge_no_time_tracked: Option<bool>,
+    // Heure locale, ex: "15:00:00"
+    pub nudge_check_time: Option<NaiveTime>,
+    // null : les tâches créées sans projet restent dans l'Inbox
+    #[serde(deserialize_with = "deserialize_opt_opt_id", default)]
+    pub default_project_id: Option<Option<ProjectId>>,
+    pub leaderboard_opt_out: Option<bool>,
+    // Heure locale, ex: "03:00:00" ; null désactive l'arrêt quotidien des minuteurs
+    #[serde(deserialize_with = "deserialize_opt_opt_naivetime", default)]
+    pub timer_auto_stop_time: Option<Option<NaiveTime>>,
+    // "cap" ou "split"
+    pub timer_auto_stop_mode: Option<String>,
+}
+
+// --- Notification Model ---
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = notifications)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub task_id: Option<TaskId>,
+    pub actor_id: Option<Uuid>,
+    pub kind: String,
+    pub message: String,
+    pub payload: serde_json::Value,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = notifications)]
+pub struct NewNotification {
+    pub user_id: Uuid,
+    pub task_id: Option<TaskId>,
+    pub actor_id: Option<Uuid>,
+    pub kind: String,
+    pub message: String,
+    pub payload: serde_json::Value,
+}
+
+// --- Workspace Models ---
+// Rôles des membres d'un espace partagé
+pub const WORKSPACE_ROLES: [&str; 3] = ["owner", "editor", "viewer"];
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = workspaces)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Workspace {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    // Clôture comptable de l'espace, opposable à ses membres hors propriétaire
+    pub time_locked_before: Option<NaiveDate>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = workspaces)]
+pub struct NewWorkspace {
+    pub owner_id: Uuid,
+    pub name: String,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Debug, Clone)]
+#[diesel(table_name = workspace_members)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WorkspaceMember {
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub role: String,
+    pub joined_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateWorkspacePayload {
+    pub name: String,
+}
+
+// null lève la clôture
+#[derive(Deserialize, Debug)]
+pub struct UpdateWorkspaceTimeLockPayload {
+    pub locked_before: Option<NaiveDate>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AddWorkspaceMemberPayload {
+    pub user_id: Uuid,
+    pub role: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WorkspaceDetailResponse {
+    #[serde(flatten)]
+    pub workspace: Workspace,
+    pub members: Vec<WorkspaceMember>,
+}
+
+// --- Standup DTOs ---
+#[derive(Serialize, Debug)]
+pub struct StandupTask {
+    pub id: TaskId,
+    pub title: String,
+    pub status: String,
+    pub project_id: Option<ProjectId>,
+    pub due_date: Option<NaiveDate>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MemberStandup {
+    pub user_id: Uuid,
+    pub role: String,
+    pub completed_yesterday: Vec<StandupTask>,
+    pub tracked_seconds_yesterday: i64,
+    pub planned_today: Vec<StandupTask>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WorkspaceStandupResponse {
+    pub workspace_id: Uuid,
+    pub date: NaiveDate,
+    pub yesterday: NaiveDate,
+    pub members: Vec<MemberStandup>,
+}
+
+// Mesures disponibles pour le classement d'un espace
+pub const LEADERBOARD_METRIC_FOCUS_TIME: &str = "focus_time";
+pub const LEADERBOARD_METRIC_TASKS_COMPLETED: &str = "tasks_completed";
+pub const LEADERBOARD_METRICS: [&str; 2] = [
+    LEADERBOARD_METRIC_FOCUS_TIME,
+    LEADERBOARD_METRIC_TASKS_COMPLETED,
+];
+
+#[derive(Serialize, Debug)]
+pub struct LeaderboardEntry {
+    // Les ex aequo partagent le même rang (1, 1, 3...)
+    pub rank: i64,
+    pub user_id: Uuid,
+    // Secondes de pomodoro (focus_time) ou nombre de tâches (tasks_completed)
+    pub value: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WorkspaceLeaderboardResponse {
+    pub workspace_id: Uuid,
+    pub metric: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    // Membres ayant choisi leaderboard_opt_out absents, y compris l'appelant
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+// --- Inbound Email Models ---
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = inbound_email_addresses)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct InboundEmailAddress {
+    pub token: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Debug)]
+pub struct InboundEmailAddressResponse {
+    pub address: String,
+    pub created_at: NaiveDateTime,
+}
+
+// --- Public Badge Models ---
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = public_badge_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PublicBadgeToken {
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PublicBadgeResponse {
+    // Chemin public de l'image, à préfixer par l'URL de l'API
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// --- Account Deletion Models ---
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = account_deletion_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AccountDeletionRequest {
+    pub user_id: Uuid,
+    pub requested_at: DateTime<Utc>,
+    pub scheduled_for: DateTime<Utc>,
+}
+
+// --- Timesheet Models ---
+pub const TIMESHEET_STATUS_DRAFT: &str = "draft";
+pub const TIMESHEET_STATUS_SUBMITTED: &str = "submitted";
+pub const TIMESHEET_STATUS_APPROVED: &str = "approved";
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = timesheets)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Timesheet {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub week_start: NaiveDate,
+    pub status: String,
+    pub total_duration_seconds: i64,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_note: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SubmitTimesheetPayload {
+    // Lundi de la semaine soumise
+    pub week_start: NaiveDate,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReviewTimesheetPayload {
+    pub note: Option<String>,
+}
+
+// --- Calendar Integration Models ---
+pub const CALENDAR_SUGGESTION_PENDING: &str = "pending";
+pub const CALENDAR_SUGGESTION_ACCEPTED: &str = "accepted";
+pub const CALENDAR_SUGGESTION_REJECTED: &str = "rejected";
+
+// Jetons OAuth : jamais sérialisés vers le client
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name = calendar_integrations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CalendarIntegration {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub token_expires_at: DateTime<Utc>,
+    pub calendar_id: String,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CalendarIntegrationStatus {
+    pub provider: String,
+    pub connected: bool,
+    pub calendar_id: Option<String>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub connected_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = calendar_project_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CalendarProjectLink {
+    pub project_id: ProjectId,
+    pub keyword: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LinkCalendarProjectPayload {
+    // Par défaut : le nom du projet
+    pub keyword: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = calendar_suggestions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CalendarSuggestion {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub project_id: ProjectId,
+    pub external_event_id: String,
+    pub title: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub status: String,
+    pub time_entry_id: Option<TimeEntryId>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = calendar_suggestions)]
+pub struct NewCalendarSuggestion {
+    pub user_id: Uuid,
+    pub project_id: ProjectId,
+    pub external_event_id: String,
+    pub title: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ListCalendarSuggestionsQuery {
+    // pending (défaut), accepted, rejected
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AcceptCalendarSuggestionPayload {
+    // Tâche existante ; sinon une tâche est créée dans le projet lié avec le titre de l'événement
+    pub task_id: Option<TaskId>,
+}
+
+// --- App Password Models ---
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = app_passwords)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AppPassword {
+    pub id: Uuid,
+    pub name: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateAppPasswordPayload {
+    pub name: String,
+}
+
+// Réponse de création : le mot de passe en clair n'est renvoyé qu'une fois
+#[derive(Serialize, Debug)]
+pub struct CreatedAppPassword {
+    #[serde(flatten)]
+    pub app_password: AppPassword,
+    pub username: Uuid,
+    pub password: String,
+}
+
+// --- API Key Models ---
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = api_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    // Début de la clé, pour la reconnaître dans la liste
+    pub key_prefix: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateApiKeyPayload {
+    pub name: String,
+}
+
+// Réponse de création : la clé en clair n'est renvoyée qu'une fois
+#[derive(Serialize, Debug)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+// Élément d'un déclencheur par interrogation (Zapier) : `id` identifie l'événement et
+// sert au dédoublonnage côté Zapier
+#[derive(Serialize, Debug)]
+pub struct PollingCompletedTask {
+    pub id: Uuid,
+    pub task_id: TaskId,
+    pub project_id: Option<ProjectId>,
+    pub title: String,
+    pub status: String,
+    pub completed_at: DateTime<Utc>,
+}
+
+// --- Recent View Models ---
+pub const RECENT_ENTITY_TASK: &str = "task";
+pub const RECENT_ENTITY_PROJECT: &str = "project";
+pub const RECENT_ENTITY_TYPES: [&str; 2] = [RECENT_ENTITY_TASK, RECENT_ENTITY_PROJECT];
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = recent_views)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RecentView {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub view_count: i32,
+    // Score à la date de last_viewed_at ; amorti à la lecture (voir recents.rs)
+    pub score: f64,
+    pub last_viewed_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RecordRecentViewPayload {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RecentQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RecentItem {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    // Titre de la tâche ou nom du projet
+    pub title: String,
+    // Projet de la tâche (None pour un projet)
+    pub project_id: Option<ProjectId>,
+    pub view_count: i32,
+    pub last_viewed_at: DateTime<Utc>,
+    pub score: f64,
+}
+
+// --- Suggest Models ---
+// Types interrogeables par GET /suggest
+pub const SUGGEST_TYPE_TASKS: &str = "tasks";
+pub const SUGGEST_TYPE_PROJECTS: &str = "projects";
+pub const SUGGEST_TYPE_LABELS: &str = "labels";
+pub const SUGGEST_TYPES: [&str; 3] = [
+    SUGGEST_TYPE_TASKS,
+    SUGGEST_TYPE_PROJECTS,
+    SUGGEST_TYPE_LABELS,
+];
+
+#[derive(Deserialize, Debug)]
+pub struct ColorSuggestQuery {
+    pub count: Option<usize>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SuggestQuery {
+    pub q: String,
+    // Liste séparée par des virgules ; tous les types par défaut
+    pub types: Option<String>,
+}
+
+#[derive(Queryable, Serialize, Debug)]
+pub struct TaskSuggestion {
+    pub id: TaskId,
+    pub title: String,
+    pub project_id: Option<ProjectId>,
+    pub status: String,
+}
+
+#[derive(Queryable, Serialize, Debug)]
+pub struct ProjectSuggestion {
+    pub id: ProjectId,
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+}
+
+#[derive(Queryable, Serialize, Debug)]
+pub struct LabelSuggestion {
+    pub id: LabelId,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+// Seuls les types demandés sont présents
+#[derive(Serialize, Debug)]
+pub struct SuggestResponse {
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<Vec<TaskSuggestion>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projects: Option<Vec<ProjectSuggestion>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<LabelSuggestion>>,
+}
+
+// --- Backup Models ---
+pub const BACKUP_TARGET_WEBDAV: &str = "webdav";
+pub const BACKUP_TARGET_S3: &str = "s3";
+pub const BACKUP_TARGETS: [&str; 2] = [BACKUP_TARGET_WEBDAV, BACKUP_TARGET_S3];
+
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = backup_configs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BackupConfig {
+    pub user_id: Uuid,
+    pub target_type: String,
+    pub endpoint_url: String,
+    // S3 uniquement
+    pub bucket: Option<String>,
+    pub region: Option<String>,
+    pub path_prefix: String,
+    // Identifiant WebDAV ou access key S3
+    pub username: String,
+    // Mot de passe WebDAV ou secret key S3 : jamais renvoyé au client
+    #[serde(skip_serializing)]
+    pub secret: String,
+    // Clé AES-256 en base64, montrée une seule fois à la configuration
+    #[serde(skip_serializing)]
+    pub encryption_key: String,
+    pub enabled: bool,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_object_key: Option<String>,
+    pub last_size_bytes: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ConfigureBackupPayload {
+    pub target_type: String,
+    pub endpoint_url: String,
+    pub bucket: Option<String>,
+    pub region: Option<String>,
+    pub path_prefix: Option<String>,
+    pub username: String,
+    pub secret: String,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BackupStatusResponse {
+    #[serde(flatten)]
+    pub config: BackupConfig,
+    // Prochaine sauvegarde automatique (None si désactivée)
+    pub next_run_at: Option<DateTime<Utc>>,
+    // Uniquement à la première configuration : nécessaire pour déchiffrer les sauvegardes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption_key: Option<String>,
+}
+
+// --- Device Models ---
+pub const DEVICE_PLATFORMS: [&str; 4] = ["ios", "android", "web", "desktop"];
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = devices)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Device {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub platform: String,
+    // Jamais renvoyé au client
+    #[serde(skip_serializing)]
+    pub push_token: Option<String>,
+    pub sync_cursor: Option<String>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub last_seen_at: DateTime<Utc>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+// Sans device_id (ou s'il est inconnu), un nouvel appareil est créé
+#[derive(Deserialize, Debug)]
+pub struct RegisterDevicePayload {
+    pub device_id: Option<Uuid>,
+    pub name: String,
+    pub platform: String,
+    pub push_token: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateDeviceSyncStatePayload {
+    pub sync_cursor: String,
+}
+
+// --- Push Delivery Models ---
+// Accusé d'envoi d'une notification vers un appareil : "sent", "failed" ou "invalid_token"
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = push_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PushDelivery {
+    pub id: Uuid,
+    pub notification_id: Uuid,
+    pub device_id: Option<Uuid>,
+    pub provider: String,
+    pub status: String,
+    pub provider_message_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = push_deliveries)]
+pub struct NewPushDelivery {
+    pub notification_id: Uuid,
+    pub device_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub status: &'static str,
+    pub provider_message_id: Option<String>,
+    pub error: Option<String>,
+}
+
+// --- Task Aging Rule Models ---
+// Escalade d'une tâche restée trop longtemps dans un statut (voir aging_rules.rs)
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = task_aging_rules)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TaskAgingRule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    // None = toutes les tâches de l'utilisateur
+    pub project_id: Option<ProjectId>,
+    pub name: String,
+    pub status: String,
+    pub max_days: i32,
+    // Label ajouté à la tâche (créé s'il n'existe pas)
+    pub add_label: Option<String>,
+    pub notify: bool,
+    pub is_active: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = task_aging_rules)]
+pub struct NewTaskAgingRule {
+    pub user_id: Uuid,
+    pub project_id: Option<ProjectId>,
+    pub name: String,
+    pub status: String,
+    pub max_days: i32,
+    pub add_label: Option<String>,
+    pub notify: bool,
+    pub is_active: bool,
+}
+
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = task_aging_rules)]
+pub struct UpdateTaskAgingRuleChangeset {
+    pub name: Option<String>,
+    pub status: Option<String>,
+    pub max_days: Option<i32>,
+    pub add_label: Option<Option<String>>,
+    pub notify: Option<bool>,
+    pub is_active: Option<bool>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateTaskAgingRulePayload {
+    pub project_id: Option<ProjectId>,
+    pub name: String,
+    pub status: String,
+    pub max_days: i32,
+    pub add_label: Option<String>,
+    #[serde(default = "default_true")]
+    pub notify: bool,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateTaskAgingRulePayload {
+    pub name: Option<String>,
+    pub status: Option<String>,
+    pub max_days: Option<i32>,
+    #[serde(deserialize_with = "deserialize_opt_opt_string", default)]
+    pub add_label: Option<Option<String>>,
+    pub notify: Option<bool>,
+    pub is_active: Option<bool>,
+}
+
+// --- Automation Models ---
+// Événements déclencheurs des règles d'automatisation (voir automations.rs)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationTrigger {
+    TaskCreated,
+    TaskCompleted,
+    LabelAdded,
+    TimerStopped,
+}
+
+impl AutomationTrigger {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AutomationTrigger::TaskCreated => "task_created",
+            AutomationTrigger::TaskCompleted => "task_completed",
+            AutomationTrigger::LabelAdded => "label_added",
+            AutomationTrigger::TimerStopped => "timer_stopped",
+        }
+    }
+}
+
+// Conditions cumulatives ; priority est lue dans metadata.priority de la tâche
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AutomationConditions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<ProjectId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_id: Option<LabelId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+}
+
+// Champs modifiables par l'action set_field
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationField {
+    // value : statut (texte)
+    Status,
+    // value : nombre de jours à partir d'aujourd'hui, ou null pour retirer l'échéance
+    DueDate,
+    // value : booléen
+    IsPinned,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum AutomationAction {
+    SetField {
+        field: AutomationField,
+        value: serde_json::Value,
+    },
+    AddLabel {
+        label_id: LabelId,
+    },
+    // title peut contenir {title}, remplacé par le titre de la tâche d'origine
+    CreateFollowUpTask {
+        title: String,
+        due_in_days: Option<i64>,
+    },
+    CallWebhook {
+        url: String,
+    },
+}
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = automation_rules)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AutomationRule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub trigger: String,
+    pub conditions: serde_json::Value,
+    pub actions: serde_json::Value,
+    pub is_active: bool,
+    pub last_triggered_at: Option<DateTime<Utc>>,
+    // Dernière erreur d'exécution (None si la dernière exécution a réussi)
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = automation_rules)]
+pub struct NewAutomationRule {
+    pub user_id: Uuid,
+    pub name: String,
+    pub trigger: String,
+    pub conditions: serde_json::Value,
+    pub actions: serde_json::Value,
+    pub is_active: bool,
+}
+
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = automation_rules)]
+pub struct UpdateAutomationRuleChangeset {
+    pub name: Option<String>,
+    pub trigger: Option<String>,
+    pub conditions: Option<serde_json::Value>,
+    pub actions: Option<serde_json::Value>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateAutomationRulePayload {
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    #[serde(default)]
+    pub conditions: AutomationConditions,
+    pub actions: Vec<AutomationAction>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateAutomationRulePayload {
+    pub name: Option<String>,
+    pub trigger: Option<AutomationTrigger>,
+    pub conditions: Option<AutomationConditions>,
+    pub actions: Option<Vec<AutomationAction>>,
+    pub is_active: Option<bool>,
+}
+
+// --- Planning Models ---
+// Sort des tâches non terminées de la semaine écoulée (POST /planning/rollover)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloverStrategy {
+    // Le créneau passe à cette semaine, l'échéance reste (la tâche apparaît en retard)
+    KeepDueDate,
+    // Échéance et créneau décalés de 7 jours
+    ShiftWeek,
+    // Échéance et créneau retirés, la tâche retourne au backlog
+    Backlog,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PlanningRolloverPayload {
+    pub strategy: RolloverStrategy,
+    // Premier jour de la semaine (monday/sunday/saturday) ; préférence enregistrée par défaut
+    pub week_start: Option<String>,
+    // Calcule le résumé sans rien modifier (équivaut à ?dry_run=true)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RolledOverTask {
+    pub task_id: TaskId,
+    pub title: String,
+    pub previous_due_date: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub previous_scheduled_start: Option<DateTime<Utc>>,
+    pub scheduled_start: Option<DateTime<Utc>>,
+    pub scheduled_end: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PlanningRolloverSummary {
+    pub strategy: RolloverStrategy,
+    pub dry_run: bool,
+    pub from_week_start: NaiveDate,
+    pub to_week_start: NaiveDate,
+    pub moved_count: usize,
+    pub tasks: Vec<RolledOverTask>,
+}
+
+// --- Feedback Models ---
+pub const FEEDBACK_CATEGORIES: [&str; 4] = ["bug", "feature_request", "praise", "other"];
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = feedback)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Feedback {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub category: String,
+    pub message: String,
+    pub app_version: Option<String>,
+    pub diagnostics: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = feedback)]
+pub struct NewFeedback {
+    pub user_id: Uuid,
+    pub category: String,
+    pub message: String,
+    pub app_version: Option<String>,
+    pub diagnostics: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateFeedbackPayload {
+    pub category: String,
+    pub message: String,
+    pub app_version: Option<String>,
+    // Objet JSON libre (modèle d'appareil, version de l'OS, logs récents...)
+    pub diagnostics: Option<serde_json::Value>,
+}
+
+// Filtres de GET /admin/feedback
+#[derive(Deserialize, Debug)]
+pub struct FeedbackListQuery {
+    pub category: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+// --- Announcement Models ---
+pub const ANNOUNCEMENT_KINDS: [&str; 4] = ["feature", "improvement", "fix", "notice"];
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = announcements)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Announcement {
+    pub id: Uuid,
+    pub title: String,
+    pub body: String,
+    pub kind: String,
+    // None = brouillon, visible des administrateurs uniquement
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = announcements)]
+pub struct NewAnnouncement {
+    pub title: String,
+    pub body: String,
+    pub kind: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_by: Uuid,
+}
+
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = announcements)]
+pub struct UpdateAnnouncementChangeset {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub kind: Option<String>,
+    pub published_at: Option<Option<DateTime<Utc>>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateAnnouncementPayload {
+    pub title: String,
+    pub body: String,
+    pub kind: Option<String>,
+    // Absent = brouillon ; une date future programme la publication
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateAnnouncementPayload {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub kind: Option<String>,
+    // null repasse l'annonce en brouillon
+    #[serde(deserialize_with = "deserialize_opt_opt_datetime_utc", default)]
+    pub published_at: Option<Option<DateTime<Utc>>>,
+}
+
+// Annonces publiées après cette date (GET /announcements?since=)
+#[derive(Deserialize, Debug)]
+pub struct AnnouncementListQuery {
+    pub since: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AnnouncementApiResponse {
+    #[serde(flatten)]
+    pub announcement: Announcement,
+    // Date de lecture par l'utilisateur courant (None = non lue)
+    pub acked_at: Option<DateTime<Utc>>,
+}
+
+// --- Feature Flag Models ---
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = feature_flags)]
+#[diesel(primary_key(key))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FeatureFlag {
+    pub key: String,
+    pub description: Option<String>,
+    // Interrupteur global : désactivé, seuls les overrides activent le drapeau
+    pub enabled: bool,
+    // Part des utilisateurs (0..100) pour qui le drapeau est actif
+    pub rollout_percentage: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = feature_flag_overrides)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FeatureFlagOverride {
+    pub flag_key: String,
+    pub user_id: Uuid,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+// Création ou remplacement d'un drapeau (PUT /admin/flags/{key})
+#[derive(Deserialize, Debug)]
+pub struct UpsertFeatureFlagPayload {
+    pub description: Option<String>,
+    pub enabled: bool,
+    #[serde(default)]
+    pub rollout_percentage: i32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SetFeatureFlagOverridePayload {
+    pub enabled: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FeatureFlagDetailResponse {
+    #[serde(flatten)]
+    pub flag: FeatureFlag,
+    pub overrides: Vec<FeatureFlagOverride>,
+}
+
+// --- Experiment Models ---
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = experiments)]
+#[diesel(primary_key(key))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Experiment {
+    pub key: String,
+    pub description: Option<String>,
+    // Liste de ExperimentVariant
+    pub variants: serde_json::Value,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentVariant {
+    pub name: String,
+    // Poids relatif dans l'affectation (1 par défaut)
+    #[serde(default = "default_variant_weight")]
+    pub weight: u32,
+}
+
+fn default_variant_weight() -> u32 {
+    1
+}
+
+// Création ou remplacement d'une expérience (PUT /admin/experiments/{key})
+#[derive(Deserialize, Debug)]
+pub struct UpsertExperimentPayload {
+    pub description: Option<String>,
+    pub variants: Vec<ExperimentVariant>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExperimentEventPayload {
+    pub event: String,
+    // Valeur numérique facultative (durée, nombre de tâches...)
+    pub value: Option<f64>,
+}
+
+// Résultats d'une variante : utilisateurs exposés et événements
+#[derive(Serialize, Debug)]
+pub struct ExperimentVariantResults {
+    pub variant: String,
+    pub assigned_users: i64,
+    pub events: Vec<ExperimentEventStat>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExperimentEventStat {
+    pub event: String,
+    pub occurrences: i64,
+    pub unique_users: i64,
+    // Part des utilisateurs exposés ayant déclenché l'événement
+    pub conversion_rate: Option<f64>,
+    pub total_value: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExperimentResultsResponse {
+    pub experiment: Experiment,
+    pub variants: Vec<ExperimentVariantResults>,
+}
+
+// Opération de maintenance en file (POST /admin/maintenance/{kind})
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = maintenance_jobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MaintenanceJob {
+    pub id: Uuid,
+    pub kind: String,
+    // queued, running, succeeded ou failed
+    pub status: String,
+    pub requested_by: Uuid,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    // Avancement des opérations longues (restauration de sauvegarde)
+    pub progress: Option<serde_json::Value>,
 }
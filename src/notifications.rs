@@ -0,0 +1,109 @@
+// OptiTask/backend-api/src/notifications.rs
+use crate::error_handler::ServiceError;
+use crate::ids::TaskId;
+use crate::models::NewNotification;
+use crate::schema::{notifications, task_watchers, user_settings};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use uuid::Uuid;
+
+pub const KIND_TASK_STATUS_CHANGED: &str = "task_status_changed";
+pub const KIND_TASK_COMMENT: &str = "task_comment";
+pub const KIND_ACCOUNT_DELETION_SCHEDULED: &str = "account_deletion_scheduled";
+pub const KIND_ACCOUNT_DELETED: &str = "account_deleted";
+pub const KIND_TIMESHEET_REVIEWED: &str = "timesheet_reviewed";
+pub const KIND_TASK_AGING_ESCALATION: &str = "task_aging_escalation";
+pub const KIND_TASK_SLA_BREACH: &str = "task_sla_breach";
+pub const KIND_TASK_DUE_REMINDER: &str = "task_due_reminder";
+pub const KIND_TIMER_NUDGE: &str = "timer_nudge";
+pub const KIND_PLANNED_TASKS_NUDGE: &str = "planned_tasks_nudge";
+pub const KIND_NO_TIME_TRACKED_NUDGE: &str = "no_time_tracked_nudge";
+pub const KIND_TIMER_AUTO_STOPPED: &str = "timer_auto_stopped";
+pub const KIND_TASK_TIME_BUDGET: &str = "task_time_budget";
+
+// Événement d'activité sur une tâche, diffusé aux observateurs
+pub struct TaskActivity<'a> {
+    pub actor_id: Uuid,
+    pub task_id: TaskId,
+    pub task_title: &'a str,
+    pub kind: &'static str,
+    pub message: String,
+    pub payload: serde_json::Value,
+}
+
+impl<'a> TaskActivity<'a> {
+    pub fn status_changed(
+        actor_id: Uuid,
+        task_id: TaskId,
+        task_title: &'a str,
+        old_status: &str,
+        new_status: &str,
+    ) -> Self {
+        TaskActivity {
+            actor_id,
+            task_id,
+            task_title,
+            kind: KIND_TASK_STATUS_CHANGED,
+            message: format!(
+                "'{}' moved from {} to {}",
+                task_title, old_status, new_status
+            ),
+            payload: json!({ "old_status": old_status, "new_status": new_status }),
+        }
+    }
+}
+
+// Crée une notification pour chaque observateur de la tâche (hors auteur de l'action)
+// dont les préférences acceptent ce type d'événement. Sans ligne de préférences, tout est actif.
+pub async fn notify_task_watchers(
+    conn: &mut AsyncPgConnection,
+    activity: TaskActivity<'_>,
+) -> Result<usize, ServiceError> {
+    let watcher_prefs = task_watchers::table
+        .left_join(user_settings::table.on(user_settings::user_id.eq(task_watchers::user_id)))
+        .filter(task_watchers::task_id.eq(activity.task_id))
+        .filter(task_watchers::user_id.ne(activity.actor_id))
+        .select((
+            task_watchers::user_id,
+            user_settings::notify_watched_status_changes.nullable(),
+            user_settings::notify_watched_comments.nullable(),
+        ))
+        .load::<(Uuid, Option<bool>, Option<bool>)>(conn)
+        .await?;
+
+    let recipients: Vec<NewNotification> = watcher_prefs
+        .into_iter()
+        .filter(|(_, status_pref, comment_pref)| match activity.kind {
+            KIND_TASK_STATUS_CHANGED => status_pref.unwrap_or(true),
+            KIND_TASK_COMMENT => comment_pref.unwrap_or(true),
+            _ => true,
+        })
+        .map(|(watcher_id, _, _)| NewNotification {
+            user_id: watcher_id,
+            task_id: Some(activity.task_id),
+            actor_id: Some(activity.actor_id),
+            kind: activity.kind.to_string(),
+            message: activity.message.clone(),
+            payload: activity.payload.clone(),
+        })
+        .collect();
+
+    if recipients.is_empty() {
+        return Ok(0);
+    }
+
+    log::debug!(
+        "Notifying {} watcher(s) of {} on task {} ({})",
+        recipients.len(),
+        activity.kind,
+        activity.task_id,
+        activity.task_title
+    );
+
+    diesel::insert_into(notifications::table)
+        .values(&recipients)
+        .execute(conn)
+        .await
+        .map_err(ServiceError::from)
+}
@@ -0,0 +1,230 @@
+// OptiTask/backend-api/src/nudges.rs
+// Relances sur le rythme de travail : "5 tâches prévues aujourd'hui, aucune terminée"
+// ou "aucun temps suivi aujourd'hui". Chaque utilisateur choisit ses relances et l'heure
+// locale de l'évaluation dans ses préférences ; une relance de chaque type au plus par
+// journée locale, remise comme une notification ordinaire (et donc en push si configuré).
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::models::{NewNotification, DONE_TASK_STATUSES, TASK_STAGE_BACKLOG};
+use crate::notifications::{KIND_NO_TIME_TRACKED_NUDGE, KIND_PLANNED_TASKS_NUDGE};
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::{notifications, tasks, time_entries, user_settings};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use diesel::dsl::exists;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const NUDGE_JOB_INTERVAL_SECS: u64 = 300;
+
+// Préférences de relance d'un utilisateur ayant activé au moins une relance
+struct NudgePreferences {
+    user_id: Uuid,
+    planned_tasks: bool,
+    no_time_tracked: bool,
+    check_time: NaiveTime,
+    utc_offset_minutes: i32,
+}
+
+// Journée locale de l'utilisateur et ses bornes en UTC
+struct LocalDay {
+    date: NaiveDate,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+impl LocalDay {
+    fn at(now: DateTime<Utc>, utc_offset_minutes: i32) -> (LocalDay, NaiveTime) {
+        let offset = Duration::minutes(i64::from(utc_offset_minutes));
+        let local_now = (now + offset).naive_utc();
+        let date = local_now.date();
+        let start = date.and_time(NaiveTime::MIN).and_utc() - offset;
+        (
+            LocalDay {
+                date,
+                start,
+                end: start + Duration::days(1),
+            },
+            local_now.time(),
+        )
+    }
+}
+
+async fn already_nudged(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    kind: &str,
+    day: &LocalDay,
+) -> Result<bool, ServiceError> {
+    diesel::select(exists(
+        notifications::table
+            .filter(notifications::user_id.eq(user_uuid))
+            .filter(notifications::kind.eq(kind))
+            .filter(notifications::created_at.ge(day.start)),
+    ))
+    .get_result::<bool>(conn)
+    .await
+    .map_err(ServiceError::from)
+}
+
+// Tâches prévues pour la journée (échéance ou créneau), hors backlog :
+// relance si moins de la moitié sont terminées
+async fn planned_tasks_nudge(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    day: &LocalDay,
+) -> Result<Option<NewNotification>, ServiceError> {
+    let planned_statuses = tasks::table
+        .filter(tasks::user_id.eq(user_uuid))
+        .filter(tasks::stage.ne(TASK_STAGE_BACKLOG))
+        .filter(
+            tasks::due_date.eq(day.date).or(tasks::scheduled_start
+                .ge(day.start)
+                .and(tasks::scheduled_start.lt(day.end))),
+        )
+        .select(tasks::status)
+        .load::<String>(conn)
+        .await?;
+
+    let planned = planned_statuses.len();
+    let completed = planned_statuses
+        .iter()
+        .filter(|task_status| DONE_TASK_STATUSES.contains(&task_status.as_str()))
+        .count();
+    if planned == 0 || completed * 2 >= planned {
+        return Ok(None);
+    }
+
+    Ok(Some(NewNotification {
+        user_id: user_uuid,
+        task_id: None,
+        actor_id: None,
+        kind: KIND_PLANNED_TASKS_NUDGE.to_string(),
+        message: format!(
+            "You planned {} task{} today and completed {} so far",
+            planned,
+            if planned == 1 { "" } else { "s" },
+            completed
+        ),
+        payload: json!({ "date": day.date, "planned": planned, "completed": completed }),
+    }))
+}
+
+async fn no_time_tracked_nudge(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    day: &LocalDay,
+) -> Result<Option<NewNotification>, ServiceError> {
+    let tracked_today = diesel::select(exists(
+        time_entries::table
+            .filter(time_entries::user_id.eq(user_uuid))
+            .filter(time_entries::is_break.eq(false))
+            .filter(time_entries::start_time.lt(day.end))
+            .filter(
+                time_entries::end_time
+                    .is_null()
+                    .or(time_entries::end_time.gt(day.start)),
+            ),
+    ))
+    .get_result::<bool>(conn)
+    .await?;
+    if tracked_today {
+        return Ok(None);
+    }
+
+    Ok(Some(NewNotification {
+        user_id: user_uuid,
+        task_id: None,
+        actor_id: None,
+        kind: KIND_NO_TIME_TRACKED_NUDGE.to_string(),
+        message: "No time tracked yet today".to_string(),
+        payload: json!({ "date": day.date }),
+    }))
+}
+
+// Évalue les relances des utilisateurs dont l'heure d'évaluation locale est passée.
+// Renvoie le nombre de notifications créées.
+pub async fn evaluate_nudges(conn: &mut AsyncPgConnection) -> Result<usize, ServiceError> {
+    let now = Utc::now();
+
+    let subscribers = user_settings::table
+        .filter(
+            user_settings::nudge_planned_tasks
+                .eq(true)
+                .or(user_settings::nudge_no_time_tracked.eq(true)),
+        )
+        .select((
+            user_settings::user_id,
+            user_settings::nudge_planned_tasks,
+            user_settings::nudge_no_time_tracked,
+            user_settings::nudge_check_time,
+            user_settings::utc_offset_minutes,
+        ))
+        .load::<(Uuid, bool, bool, NaiveTime, i32)>(conn)
+        .await?
+        .into_iter()
+        .map(
+            |(user_id, planned_tasks, no_time_tracked, check_time, utc_offset_minutes)| {
+                NudgePreferences {
+                    user_id,
+                    planned_tasks,
+                    no_time_tracked,
+                    check_time,
+                    utc_offset_minutes,
+                }
+            },
+        );
+
+    let mut nudges = Vec::new();
+    for preferences in subscribers {
+        let (day, local_time) = LocalDay::at(now, preferences.utc_offset_minutes);
+        if local_time < preferences.check_time {
+            continue;
+        }
+
+        if preferences.planned_tasks
+            && !already_nudged(conn, preferences.user_id, KIND_PLANNED_TASKS_NUDGE, &day).await?
+        {
+            nudges.extend(planned_tasks_nudge(conn, preferences.user_id, &day).await?);
+        }
+        if preferences.no_time_tracked
+            && !already_nudged(conn, preferences.user_id, KIND_NO_TIME_TRACKED_NUDGE, &day).await?
+        {
+            nudges.extend(no_time_tracked_nudge(conn, preferences.user_id, &day).await?);
+        }
+    }
+
+    if nudges.is_empty() {
+        return Ok(0);
+    }
+    diesel::insert_into(notifications::table)
+        .values(&nudges)
+        .execute(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Lance la tâche de fond d'évaluation des relances
+pub fn spawn_nudge_job(pool: DbPool, read_only: Arc<ReadOnlyMode>) {
+    actix_web::rt::spawn(async move {
+        let mut interval =
+            actix_web::rt::time::interval(std::time::Duration::from_secs(NUDGE_JOB_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            let result = match pool.get().await {
+                Ok(mut conn) => evaluate_nudges(&mut conn).await,
+                Err(e) => Err(ServiceError::from(e)),
+            };
+            match result {
+                Ok(0) => {}
+                Ok(created) => log::info!("Nudge job created {} notification(s)", created),
+                Err(e) => log::error!("Nudge job failed: {}", e),
+            }
+        }
+    });
+}
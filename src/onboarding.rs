@@ -0,0 +1,222 @@
+// OptiTask/backend-api/src/onboarding.rs
+use crate::error_handler::ServiceError;
+use crate::models::{
+    Label, NewLabel, NewProject, NewTask, NewTaskLabelAssociation, NewTimeEntry, Project, Task,
+    TaskApiResponse, TimeEntry,
+};
+use crate::schema::{labels, projects, task_labels, tasks, time_entries, user_onboarding};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use uuid::Uuid;
+
+// Données d'exemple : (nom, couleur) des labels
+const SAMPLE_LABELS: [(&str, &str); 2] = [("Important", "#EF4444"), ("Quick win", "#10B981")];
+
+// Données d'exemple pour une tâche
+struct SampleTask {
+    title: &'static str,
+    description: &'static str,
+    status: &'static str,
+    due_in_days: Option<i64>,
+    label_indexes: &'static [usize],
+}
+
+const SAMPLE_TASKS: [SampleTask; 4] = [
+    SampleTask {
+        title: "Explore your first project",
+        description: "Projects group related tasks. Rename this one or create your own.",
+        status: "completed",
+        due_in_days: None,
+        label_indexes: &[1],
+    },
+    SampleTask {
+        title: "Track time on a task",
+        description: "Start a timer or a pomodoro session to see it in your analytics.",
+        status: "in_progress",
+        due_in_days: Some(0),
+        label_indexes: &[0],
+    },
+    SampleTask {
+        title: "Organize tasks with labels",
+        description: "Labels work across projects. Try filtering by one.",
+        status: "todo",
+        due_in_days: Some(2),
+        label_indexes: &[0, 1],
+    },
+    SampleTask {
+        title: "Plan your week",
+        description: "Set due dates so upcoming work shows up on your dashboard.",
+        status: "todo",
+        due_in_days: Some(6),
+        label_indexes: &[],
+    },
+];
+
+// Résumé des données créées lors du seed
+#[derive(Serialize, Debug)]
+pub struct SeedSummary {
+    pub project: Project,
+    pub tasks: Vec<TaskApiResponse>,
+    pub time_entry: TimeEntry,
+}
+
+// Crée le jeu de données d'exemple pour un utilisateur.
+// Doit être appelée à l'intérieur d'une transaction : la réservation dans `user_onboarding`
+// et les insertions sont ainsi atomiques. Retourne None si l'utilisateur a déjà été initialisé.
+pub async fn seed_sample_data(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<SeedSummary>, ServiceError> {
+    // 1. Réserver le seed pour cet utilisateur (idempotence)
+    let claimed = diesel::insert_into(user_onboarding::table)
+        .values(user_onboarding::user_id.eq(user_uuid))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .await?;
+
+    if claimed == 0 {
+        log::info!("Sample data already seeded for user {}", user_uuid);
+        return Ok(None);
+    }
+
+    // 2. Projet d'exemple
+    let project = diesel::insert_into(projects::table)
+        .values(&NewProject {
+            user_id: user_uuid,
+            name: "Getting Started".to_string(),
+            color: Some("#6366F1".to_string()),
+            workspace_id: None,
+            icon: Some("🚀".to_string()),
+            wip_limits: None,
+            sla_hours: None,
+            currency: None,
+            theme: None,
+        })
+        .get_result::<Project>(conn)
+        .await?;
+
+    diesel::update(user_onboarding::table.find(user_uuid))
+        .set(user_onboarding::project_id.eq(project.id))
+        .execute(conn)
+        .await?;
+
+    // 3. Labels d'exemple (réutilise ceux qui existent déjà avec le même nom)
+    let new_labels: Vec<NewLabel> = SAMPLE_LABELS
+        .iter()
+        .map(|(label_name, label_color)| NewLabel {
+            user_id: user_uuid,
+            name: label_name.to_string(),
+            color: Some(label_color.to_string()),
+            icon: None,
+            sla_hours: None,
+        })
+        .collect();
+
+    diesel::insert_into(labels::table)
+        .values(&new_labels)
+        .on_conflict((labels::user_id, labels::name))
+        .do_nothing()
+        .execute(conn)
+        .await?;
+
+    let label_names: Vec<&str> = SAMPLE_LABELS.iter().map(|(n, _)| *n).collect();
+    let seeded_labels = labels::table
+        .filter(labels::user_id.eq(user_uuid))
+        .filter(labels::name.eq_any(&label_names))
+        .select(Label::as_select())
+        .load::<Label>(conn)
+        .await?;
+
+    // 4. Tâches d'exemple et associations de labels
+    let today = Utc::now().date_naive();
+    let mut task_responses = Vec::new();
+
+    for (position, sample) in SAMPLE_TASKS.iter().enumerate() {
+        let task = diesel::insert_into(tasks::table)
+            .values(&NewTask {
+                user_id: user_uuid,
+                project_id: Some(project.id),
+                title: sample.title.to_string(),
+                description: Some(sample.description.to_string()),
+                status: Some(sample.status.to_string()),
+                due_date: sample.due_in_days.map(|days| today + Duration::days(days)),
+                order: Some(position as i32),
+                context: None,
+                scheduled_start: None,
+                scheduled_end: None,
+                follow_up: None,
+                stage: None,
+                time_budget_seconds: None,
+                source: None,
+            })
+            .get_result::<Task>(conn)
+            .await?;
+
+        let task_label_list: Vec<Label> = sample
+            .label_indexes
+            .iter()
+            .filter_map(|index| {
+                let label_name = SAMPLE_LABELS[*index].0;
+                seeded_labels.iter().find(|l| l.name == label_name).cloned()
+            })
+            .collect();
+
+        let associations: Vec<NewTaskLabelAssociation> = task_label_list
+            .iter()
+            .map(|label| NewTaskLabelAssociation {
+                task_id: task.id,
+                label_id: label.id,
+            })
+            .collect();
+
+        if !associations.is_empty() {
+            diesel::insert_into(task_labels::table)
+                .values(&associations)
+                .execute(conn)
+                .await?;
+        }
+
+        let mut task_response = TaskApiResponse::from(task);
+        task_response.labels = task_label_list;
+        task_responses.push(task_response);
+    }
+
+    // 5. Une session pomodoro terminée sur la tâche "en cours"
+    let tracked_task_id = task_responses
+        .iter()
+        .find(|t| t.status == "in_progress")
+        .map(|t| t.id)
+        .ok_or_else(|| ServiceError::internal_error("Sample task to track time on is missing"))?;
+
+    let session_end = Utc::now() - Duration::minutes(10);
+    let session_start = session_end - Duration::minutes(25);
+    let time_entry = diesel::insert_into(time_entries::table)
+        .values(&NewTimeEntry {
+            user_id: user_uuid,
+            task_id: tracked_task_id,
+            start_time: session_start,
+            end_time: Some(session_end),
+            duration_seconds: Some((session_end - session_start).num_seconds() as i32),
+            is_pomodoro_session: Some(true),
+            is_break: None,
+            clock_skew_seconds: None,
+            suspect_clock: None,
+            focus_session_id: None,
+        })
+        .get_result::<TimeEntry>(conn)
+        .await?;
+
+    log::info!(
+        "Sample data seeded for user {} (project {})",
+        user_uuid,
+        project.id
+    );
+
+    Ok(Some(SeedSummary {
+        project,
+        tasks: task_responses,
+        time_entry,
+    }))
+}
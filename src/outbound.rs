@@ -0,0 +1,391 @@
+// OptiTask/backend-api/src/outbound.rs
+// Appels HTTP sortants (webhooks, intégrations, LLM, push, sauvegardes...) : un client
+// partagé, un délai et un nombre de nouvelles tentatives propres à chaque appelant, et un
+// disjoncteur par destination (hôte). Après plusieurs échecs consécutifs, la destination
+// est coupée pendant un délai de refroidissement : les appels échouent immédiatement au
+// lieu d'attendre un tiers lent. Un seul appel d'essai est ensuite laissé passer.
+// L'état des disjoncteurs est exposé au format Prometheus par GET /metrics.
+// Les destinations choisies par les utilisateurs (webhooks, stockage des sauvegardes, aperçus
+// de liens) passent par public_client : l'hôte est résolu une seule fois, refusé s'il pointe
+// vers le réseau interne, puis contacté à cette adresse précise, sans suivre de redirection.
+// Un DNS qui change de réponse entre le contrôle et la connexion (DNS rebinding) est sans effet.
+use actix_web::web;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Write;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT_SECS: u64 = 5;
+// Échecs consécutifs qui ouvrent le disjoncteur, et durée de coupure
+const FAILURE_THRESHOLD: u32 = 5;
+const OPEN_COOLDOWN_SECS: u64 = 30;
+const RETRY_BASE_DELAY_MS: u64 = 250;
+// Les webhooks ont des destinations choisies par les utilisateurs : on borne le suivi
+const MAX_TRACKED_DESTINATIONS: usize = 1000;
+
+// Délai par tentative et nouvelles tentatives, par type d'appel
+#[derive(Debug, Clone, Copy)]
+pub struct OutboundPolicy {
+    pub caller: &'static str,
+    pub timeout: Duration,
+    pub max_retries: u32,
+}
+
+pub const WEBHOOK_POLICY: OutboundPolicy = OutboundPolicy {
+    caller: "webhook",
+    timeout: Duration::from_secs(10),
+    max_retries: 2,
+};
+// Appelé pendant la requête de l'utilisateur : pas de nouvelle tentative
+pub const LLM_POLICY: OutboundPolicy = OutboundPolicy {
+    caller: "llm",
+    timeout: Duration::from_secs(60),
+    max_retries: 0,
+};
+pub const PUSH_POLICY: OutboundPolicy = OutboundPolicy {
+    caller: "push",
+    timeout: Duration::from_secs(15),
+    max_retries: 1,
+};
+pub const CALENDAR_POLICY: OutboundPolicy = OutboundPolicy {
+    caller: "google_calendar",
+    timeout: Duration::from_secs(30),
+    max_retries: 1,
+};
+pub const BACKUP_POLICY: OutboundPolicy = OutboundPolicy {
+    caller: "backup",
+    timeout: Duration::from_secs(120),
+    max_retries: 2,
+};
+pub const EVENTS_POLICY: OutboundPolicy = OutboundPolicy {
+    caller: "events",
+    timeout: Duration::from_secs(10),
+    max_retries: 1,
+};
+pub const SNS_POLICY: OutboundPolicy = OutboundPolicy {
+    caller: "sns",
+    timeout: Duration::from_secs(10),
+    max_retries: 1,
+};
+// Aperçu des liens externes, pendant la requête de l'utilisateur : court et sans nouvelle tentative
+pub const LINK_PREVIEW_POLICY: OutboundPolicy = OutboundPolicy {
+    caller: "link_preview",
+    timeout: Duration::from_secs(5),
+    max_retries: 0,
+};
+
+#[derive(Debug)]
+pub enum OutboundError {
+    // Disjoncteur ouvert pour cette destination : l'appel n'a pas été tenté
+    CircuitOpen(String),
+    Request(reqwest::Error),
+}
+
+impl fmt::Display for OutboundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OutboundError::CircuitOpen(destination) => {
+                write!(f, "circuit open for {}", destination)
+            }
+            OutboundError::Request(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for OutboundError {}
+
+// Client unique : pool de connexions partagé, délai de connexion commun
+pub fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+            .build()
+            .expect("Failed to build outbound HTTP client")
+    })
+}
+
+// Adresses internes : une destination choisie par un utilisateur ne doit pas servir à
+// sonder le réseau du serveur
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                // 100.64.0.0/10 (CGNAT)
+                || (v4.octets()[0] == 100 && (v4.octets()[1] & 0xc0) == 64))
+        }
+        IpAddr::V6(v6) => {
+            let first_segment = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                // fc00::/7 (adresses locales uniques), fe80::/10 (lien local)
+                || (first_segment & 0xfe00) == 0xfc00
+                || (first_segment & 0xffc0) == 0xfe80
+                || v6.to_ipv4_mapped().is_some_and(|v4| !is_public_ip(IpAddr::V4(v4))))
+        }
+    }
+}
+
+// Adresse à contacter pour cette URL, si toutes les adresses de l'hôte sont publiques
+async fn resolve_public_address(url: &reqwest::Url) -> Option<SocketAddr> {
+    let host = url.host_str()?.to_string();
+    let port = url.port_or_known_default().unwrap_or(443);
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    match web::block(move || (host.as_str(), port).to_socket_addrs()).await {
+        Ok(Ok(addresses)) => {
+            let addresses: Vec<SocketAddr> = addresses.collect();
+            if addresses.iter().all(|address| is_public_ip(address.ip())) {
+                addresses.first().copied()
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+// Client d'un seul appel vers `url`, épinglé sur une adresse publique contrôlée, sans proxy
+// ni redirection automatique ; None si l'hôte ne résout pas vers des adresses publiques.
+// À utiliser avec send_via, pour conserver délai, nouvelles tentatives et disjoncteur.
+pub async fn public_client(url: &reqwest::Url) -> Option<reqwest::Client> {
+    let address = resolve_public_address(url).await?;
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+        .redirect(reqwest::redirect::Policy::none())
+        .no_proxy();
+    // Une adresse IP littérale n'est pas résolue : rien à épingler
+    if let Some(domain) = url.domain() {
+        builder = builder.resolve(domain, address);
+    }
+    builder.build().ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BreakerState {
+    Closed,
+    Open { until: Instant },
+    // Refroidissement écoulé, un appel d'essai est en cours
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    state: BreakerState,
+    consecutive_failures: u32,
+    successes: u64,
+    failures: u64,
+    rejected: u64,
+}
+
+impl Breaker {
+    fn new() -> Breaker {
+        Breaker {
+            state: BreakerState::Closed,
+            consecutive_failures: 0,
+            successes: 0,
+            failures: 0,
+            rejected: 0,
+        }
+    }
+}
+
+fn breakers() -> &'static Mutex<HashMap<String, Breaker>> {
+    static BREAKERS: OnceLock<Mutex<HashMap<String, Breaker>>> = OnceLock::new();
+    BREAKERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Hôte et port explicite éventuel
+fn destination(url: &reqwest::Url) -> String {
+    match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => "unknown".to_string(),
+    }
+}
+
+// Refuse l'appel si le disjoncteur est ouvert ; laisse passer l'appel d'essai
+fn try_acquire(destination: &str) -> bool {
+    let mut breakers = breakers().lock().unwrap();
+    if !breakers.contains_key(destination) && breakers.len() >= MAX_TRACKED_DESTINATIONS {
+        // Oublie les destinations saines : elles repartiraient de zéro de toute façon
+        breakers.retain(|_, breaker| {
+            breaker.state != BreakerState::Closed || breaker.consecutive_failures > 0
+        });
+    }
+    let breaker = breakers
+        .entry(destination.to_string())
+        .or_insert_with(Breaker::new);
+
+    match breaker.state {
+        BreakerState::Closed => true,
+        BreakerState::Open { until } if Instant::now() >= until => {
+            breaker.state = BreakerState::HalfOpen;
+            true
+        }
+        BreakerState::Open { .. } | BreakerState::HalfOpen => {
+            breaker.rejected += 1;
+            false
+        }
+    }
+}
+
+fn record(destination: &str, success: bool) {
+    let mut breakers = breakers().lock().unwrap();
+    let Some(breaker) = breakers.get_mut(destination) else {
+        return;
+    };
+    if success {
+        breaker.successes += 1;
+        breaker.consecutive_failures = 0;
+        breaker.state = BreakerState::Closed;
+        return;
+    }
+
+    breaker.failures += 1;
+    breaker.consecutive_failures += 1;
+    let reopen = breaker.state == BreakerState::HalfOpen
+        || breaker.consecutive_failures >= FAILURE_THRESHOLD;
+    if reopen && !matches!(breaker.state, BreakerState::Open { .. }) {
+        log::warn!(
+            "Circuit opened for {} after {} consecutive failure(s)",
+            destination,
+            breaker.consecutive_failures
+        );
+        breaker.state = BreakerState::Open {
+            until: Instant::now() + Duration::from_secs(OPEN_COOLDOWN_SECS),
+        };
+    }
+}
+
+// Erreurs du côté du tiers : comptent comme échecs et justifient une nouvelle tentative
+fn is_server_failure(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+fn is_idempotent(method: &reqwest::Method) -> bool {
+    matches!(
+        *method,
+        reqwest::Method::GET
+            | reqwest::Method::HEAD
+            | reqwest::Method::PUT
+            | reqwest::Method::DELETE
+    )
+}
+
+// Envoie la requête selon la politique de l'appelant. Les réponses 4xx sont renvoyées
+// telles quelles (le tiers répond) ; une 5xx persistante aussi, après les nouvelles tentatives.
+// Une requête non idempotente n'est retentée que si la connexion n'a pas pu s'établir.
+pub async fn send(
+    policy: &OutboundPolicy,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, OutboundError> {
+    send_via(client(), policy, request).await
+}
+
+// Comme send, avec un client propre à l'appelant (résolution ou redirections particulières)
+pub async fn send_via(
+    client: &reqwest::Client,
+    policy: &OutboundPolicy,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, OutboundError> {
+    let mut request = request
+        .timeout(policy.timeout)
+        .build()
+        .map_err(OutboundError::Request)?;
+    let destination = destination(request.url());
+    let idempotent = is_idempotent(request.method());
+
+    let mut attempt = 0;
+    loop {
+        if !try_acquire(&destination) {
+            return Err(OutboundError::CircuitOpen(destination));
+        }
+        // Les corps en flux ne se copient pas : pas de nouvelle tentative dans ce cas
+        let retry_copy = if attempt < policy.max_retries {
+            request.try_clone()
+        } else {
+            None
+        };
+
+        let result = client.execute(request).await;
+        let (success, retryable) = match &result {
+            Ok(response) => {
+                let failed = is_server_failure(response.status());
+                (!failed, failed && idempotent)
+            }
+            Err(e) => (false, idempotent || e.is_connect()),
+        };
+        record(&destination, success);
+
+        match retry_copy {
+            Some(copy) if retryable => {
+                log::warn!(
+                    "Outbound {} call to {} failed (attempt {}), retrying",
+                    policy.caller,
+                    destination,
+                    attempt + 1
+                );
+                actix_web::rt::time::sleep(Duration::from_millis(RETRY_BASE_DELAY_MS << attempt))
+                    .await;
+                request = copy;
+                attempt += 1;
+            }
+            _ => return result.map_err(OutboundError::Request),
+        }
+    }
+}
+
+// Métriques Prometheus des disjoncteurs (format texte)
+pub fn render_metrics() -> String {
+    let breakers = breakers().lock().unwrap();
+    let mut destinations: Vec<&String> = breakers.keys().collect();
+    destinations.sort();
+
+    let mut out = String::new();
+    out.push_str(
+        "# HELP optitask_outbound_circuit_state Circuit breaker state per destination (0 closed, 1 half-open, 2 open).\n\
+         # TYPE optitask_outbound_circuit_state gauge\n",
+    );
+    for destination in &destinations {
+        let state = match breakers[*destination].state {
+            BreakerState::Closed => 0,
+            BreakerState::HalfOpen => 1,
+            BreakerState::Open { .. } => 2,
+        };
+        let _ = writeln!(
+            out,
+            "optitask_outbound_circuit_state{{destination=\"{}\"}} {}",
+            destination, state
+        );
+    }
+
+    out.push_str(
+        "# HELP optitask_outbound_requests_total Outbound HTTP calls per destination and outcome.\n\
+         # TYPE optitask_outbound_requests_total counter\n",
+    );
+    for destination in &destinations {
+        let breaker = &breakers[*destination];
+        for (outcome, count) in [
+            ("success", breaker.successes),
+            ("failure", breaker.failures),
+            ("rejected", breaker.rejected),
+        ] {
+            let _ = writeln!(
+                out,
+                "optitask_outbound_requests_total{{destination=\"{}\",outcome=\"{}\"}} {}",
+                destination, outcome, count
+            );
+        }
+    }
+    out
+}
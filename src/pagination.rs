@@ -0,0 +1,28 @@
+// OptiTask/backend-api/src/pagination.rs
+// Réponses paginées : le corps reste un PaginatedResponse, et les mêmes informations sont
+// répétées en en-têtes pour les clients qui paginent sans lire le corps (tableaux, exports).
+use crate::models::PaginatedResponse;
+use actix_web::HttpResponse;
+use serde::Serialize;
+
+pub const TOTAL_COUNT_HEADER: &str = "X-Total-Count";
+pub const TOTAL_PAGES_HEADER: &str = "X-Total-Pages";
+pub const PAGE_HEADER: &str = "X-Page";
+pub const PER_PAGE_HEADER: &str = "X-Per-Page";
+
+// En-têtes lisibles par le frontend (CORS)
+pub const EXPOSED_HEADERS: [&str; 4] = [
+    TOTAL_COUNT_HEADER,
+    TOTAL_PAGES_HEADER,
+    PAGE_HEADER,
+    PER_PAGE_HEADER,
+];
+
+pub fn paginated_response<T: Serialize>(body: PaginatedResponse<T>) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header((TOTAL_COUNT_HEADER, body.total_items.to_string()))
+        .insert_header((TOTAL_PAGES_HEADER, body.total_pages.to_string()))
+        .insert_header((PAGE_HEADER, body.page.to_string()))
+        .insert_header((PER_PAGE_HEADER, body.per_page.to_string()))
+        .json(body)
+}
@@ -0,0 +1,390 @@
+// OptiTask/backend-api/src/pdf.rs
+// Écriture minimale de documents PDF 1.4 imprimables, sans dépendance : une colonne de texte
+// en A4, polices standard Helvetica (régulière, grasse, oblique) en WinAnsiEncoding, retour
+// à la ligne et sauts de page automatiques, numéro de page en pied. Les caractères hors
+// WinAnsi sont remplacés par "?". Les largeurs de glyphes sont approchées par classe de
+// caractères, avec une marge suffisante pour ne jamais déborder de la colonne.
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+// Hauteur réservée au pied de page sous la marge basse
+const FOOTER_HEIGHT: f32 = 20.0;
+const LINE_SPACING: f32 = 1.35;
+
+const TITLE_SIZE: f32 = 18.0;
+const SECTION_SIZE: f32 = 13.0;
+const BODY_SIZE: f32 = 10.5;
+const SMALL_SIZE: f32 = 8.5;
+// Largeur de la colonne des intitulés dans field()
+const LABEL_WIDTH: f32 = 130.0;
+const BULLET_INDENT: f32 = 14.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Font {
+    Regular,
+    Bold,
+    Oblique,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Oblique => "F3",
+        }
+    }
+}
+
+pub struct PdfDocument {
+    title: String,
+    // Flux de contenu des pages terminées
+    pages: Vec<String>,
+    current: String,
+    // Ligne de base de la prochaine ligne, depuis le bas de la page
+    cursor_y: f32,
+}
+
+// Code WinAnsi (Windows-1252) d'un caractère, "?" s'il n'y en a pas
+fn win_ansi(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{A0}'..='\u{FF}' => c as u32 as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8A,
+        '‹' => 0x8B,
+        'Œ' => 0x8C,
+        'Ž' => 0x8E,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9A,
+        '›' => 0x9B,
+        'œ' => 0x9C,
+        'ž' => 0x9E,
+        'Ÿ' => 0x9F,
+        '\t' => b' ',
+        _ => b'?',
+    }
+}
+
+// Chaîne littérale PDF "(...)", octets non ASCII en octal
+fn pdf_string(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('(');
+    for byte in text.chars().map(win_ansi) {
+        match byte {
+            b'(' | b')' | b'\\' => {
+                literal.push('\\');
+                literal.push(byte as char);
+            }
+            0x20..=0x7E => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    literal.push(')');
+    literal
+}
+
+// Largeur approchée d'un caractère, en millièmes de la taille de police (métriques Helvetica)
+fn char_width(c: char, font: Font) -> f32 {
+    let width = match c {
+        'i' | 'j' | 'l' | '\'' | '|' | '.' | ',' | ':' | ';' | '!' => 250.0,
+        'f' | 't' | 'r' | 'I' | ' ' | '(' | ')' | '[' | ']' | '-' | '/' => 333.0,
+        'm' | 'M' | 'W' | 'w' | '@' | '%' | 'Œ' | 'œ' | '—' => 889.0,
+        'A'..='Z' | 'À'..='Ý' | '&' | '…' => 722.0,
+        _ => 556.0,
+    };
+    if font == Font::Bold {
+        width * 1.06
+    } else {
+        width
+    }
+}
+
+pub fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    text.chars().map(|c| char_width(c, font)).sum::<f32>() * size / 1000.0
+}
+
+// Découpe un paragraphe en lignes tenant dans `max_width`, mot par mot ; un mot trop long
+// est coupé au caractère
+fn wrap(text: &str, font: Font, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", line, word)
+        };
+        if text_width(&candidate, font, size) <= max_width {
+            line = candidate;
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        for c in word.chars() {
+            line.push(c);
+            if text_width(&line, font, size) > max_width && line.chars().count() > 1 {
+                let overflow = line.pop().unwrap_or(c);
+                lines.push(std::mem::take(&mut line));
+                line.push(overflow);
+            }
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+impl PdfDocument {
+    pub fn new(title: &str) -> Self {
+        PdfDocument {
+            title: title.to_string(),
+            pages: Vec::new(),
+            current: String::new(),
+            cursor_y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn content_width(&self) -> f32 {
+        PAGE_WIDTH - 2.0 * MARGIN
+    }
+
+    // Passe à la page suivante si `height` ne tient plus au-dessus du pied de page
+    fn ensure_space(&mut self, height: f32) {
+        if self.cursor_y - height < MARGIN + FOOTER_HEIGHT {
+            self.pages.push(std::mem::take(&mut self.current));
+            self.cursor_y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn draw_text(&mut self, x: f32, y: f32, text: &str, font: Font, size: f32) {
+        self.current.push_str(&format!(
+            "BT /{} {} Tf {:.2} {:.2} Td {} Tj ET\n",
+            font.resource(),
+            size,
+            x,
+            y,
+            pdf_string(text)
+        ));
+    }
+
+    // Lignes déjà découpées, à partir de `x`, avec saut de page entre deux lignes si besoin
+    fn draw_lines(&mut self, x: f32, lines: &[String], font: Font, size: f32) {
+        let line_height = size * LINE_SPACING;
+        for line in lines {
+            self.ensure_space(line_height);
+            self.cursor_y -= line_height;
+            let y = self.cursor_y;
+            self.draw_text(x, y, line, font, size);
+        }
+    }
+
+    pub fn space(&mut self, height: f32) {
+        self.cursor_y -= height;
+    }
+
+    pub fn title(&mut self, text: &str) {
+        let lines = wrap(text, Font::Bold, TITLE_SIZE, self.content_width());
+        self.draw_lines(MARGIN, &lines, Font::Bold, TITLE_SIZE);
+        self.space(6.0);
+    }
+
+    // Titre de section souligné d'un filet ; garde au moins une ligne de texte sous le titre
+    pub fn section(&mut self, text: &str) {
+        self.space(10.0);
+        self.ensure_space(SECTION_SIZE * LINE_SPACING + BODY_SIZE * LINE_SPACING + 4.0);
+        let lines = wrap(text, Font::Bold, SECTION_SIZE, self.content_width());
+        self.draw_lines(MARGIN, &lines, Font::Bold, SECTION_SIZE);
+        let rule_y = self.cursor_y - 4.0;
+        self.current.push_str(&format!(
+            "0.75 G 0.5 w {:.2} {:.2} m {:.2} {:.2} l S 0 G\n",
+            MARGIN,
+            rule_y,
+            PAGE_WIDTH - MARGIN,
+            rule_y
+        ));
+        self.space(6.0);
+    }
+
+    // Paragraphes séparés par les retours à la ligne du texte
+    pub fn paragraph(&mut self, text: &str) {
+        self.styled_paragraph(text, Font::Regular);
+    }
+
+    // Texte secondaire (métadonnées, mentions "aucun élément")
+    pub fn note(&mut self, text: &str) {
+        self.styled_paragraph(text, Font::Oblique);
+    }
+
+    fn styled_paragraph(&mut self, text: &str, font: Font) {
+        for paragraph in text.lines() {
+            let lines = wrap(paragraph, font, BODY_SIZE, self.content_width());
+            self.draw_lines(MARGIN, &lines, font, BODY_SIZE);
+        }
+    }
+
+    // Intitulé en gras dans une colonne fixe, valeur à droite
+    pub fn field(&mut self, label: &str, value: &str) {
+        let value_x = MARGIN + LABEL_WIDTH;
+        let value_lines = wrap(
+            value,
+            Font::Regular,
+            BODY_SIZE,
+            self.content_width() - LABEL_WIDTH,
+        );
+        self.ensure_space(BODY_SIZE * LINE_SPACING);
+        let label_y = self.cursor_y - BODY_SIZE * LINE_SPACING;
+        self.draw_text(MARGIN, label_y, label, Font::Bold, BODY_SIZE);
+        self.draw_lines(value_x, &value_lines, Font::Regular, BODY_SIZE);
+    }
+
+    pub fn bullet(&mut self, text: &str) {
+        let lines = wrap(
+            text,
+            Font::Regular,
+            BODY_SIZE,
+            self.content_width() - BULLET_INDENT,
+        );
+        self.ensure_space(BODY_SIZE * LINE_SPACING);
+        let bullet_y = self.cursor_y - BODY_SIZE * LINE_SPACING;
+        self.draw_text(MARGIN, bullet_y, "•", Font::Regular, BODY_SIZE);
+        self.draw_lines(MARGIN + BULLET_INDENT, &lines, Font::Regular, BODY_SIZE);
+    }
+
+    // Ligne de tableau à deux colonnes, la seconde alignée à droite
+    pub fn row(&mut self, left: &str, right: &str, font: Font) {
+        let right_width = text_width(right, font, BODY_SIZE);
+        let left_lines = wrap(
+            left,
+            font,
+            BODY_SIZE,
+            self.content_width() - right_width - 12.0,
+        );
+        self.ensure_space(BODY_SIZE * LINE_SPACING);
+        let right_y = self.cursor_y - BODY_SIZE * LINE_SPACING;
+        self.draw_text(
+            PAGE_WIDTH - MARGIN - right_width,
+            right_y,
+            right,
+            font,
+            BODY_SIZE,
+        );
+        self.draw_lines(MARGIN, &left_lines, font, BODY_SIZE);
+    }
+
+    // Assemble le fichier ; `page_label(n, total)` donne le texte du pied de page
+    pub fn finish(mut self, page_label: impl Fn(usize, usize) -> String) -> Vec<u8> {
+        self.pages.push(std::mem::take(&mut self.current));
+        let page_count = self.pages.len();
+
+        // Objets fixes : 1 catalogue, 2 arbre des pages, 3-5 polices, 6 informations ;
+        // puis pour chaque page, l'objet page et son flux de contenu
+        let first_page_object = 7;
+        let mut objects: Vec<Vec<u8>> = Vec::with_capacity(6 + 2 * page_count);
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        let kids: Vec<String> = (0..page_count)
+            .map(|index| format!("{} 0 R", first_page_object + 2 * index))
+            .collect();
+        objects.push(
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                page_count
+            )
+            .into_bytes(),
+        );
+        for base_font in ["Helvetica", "Helvetica-Bold", "Helvetica-Oblique"] {
+            objects.push(
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                    base_font
+                )
+                .into_bytes(),
+            );
+        }
+        objects.push(
+            format!(
+                "<< /Title {} /Producer (OptiTask) >>",
+                pdf_string(&self.title)
+            )
+            .into_bytes(),
+        );
+
+        for (index, content) in self.pages.iter().enumerate() {
+            let footer = page_label(index + 1, page_count);
+            let footer_x = PAGE_WIDTH - MARGIN - text_width(&footer, Font::Regular, SMALL_SIZE);
+            let stream = format!(
+                "{}0.45 g BT /F1 {} Tf {:.2} {:.2} Td {} Tj ET 0 g\n",
+                content,
+                SMALL_SIZE,
+                footer_x,
+                MARGIN - SMALL_SIZE,
+                pdf_string(&footer)
+            );
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> \
+                     /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    first_page_object + 2 * index + 1
+                )
+                .into_bytes(),
+            );
+            objects.push(
+                format!(
+                    "<< /Length {} >>\nstream\n{}endstream",
+                    stream.len(),
+                    stream
+                )
+                .into_bytes(),
+            );
+        }
+
+        // Commentaire binaire en tête : signale aux outils de transfert un fichier binaire
+        let mut output = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(output.len());
+            output.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            output.extend_from_slice(object);
+            output.extend_from_slice(b"\nendobj\n");
+        }
+        let xref_offset = output.len();
+        output.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+        );
+        for offset in offsets {
+            output.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        output.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 6 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        output
+    }
+}
@@ -0,0 +1,201 @@
+// OptiTask/backend-api/src/permissions.rs
+// Résolution du rôle de l'appelant sur un espace, un projet ou une tâche, et garde
+// utilisée par les handlers de ressources potentiellement partagées.
+use crate::error_handler::ServiceError;
+use crate::i18n;
+use crate::ids::{ProjectId, TaskId};
+use crate::models::Task;
+use crate::schema::{projects, tasks, workspace_members};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+// Ordonnés du moins au plus privilégié
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Viewer,
+    Editor,
+    Owner,
+}
+
+impl Role {
+    pub fn parse(raw: &str) -> Option<Role> {
+        match raw {
+            "owner" => Some(Role::Owner),
+            "editor" => Some(Role::Editor),
+            "viewer" => Some(Role::Viewer),
+            _ => None,
+        }
+    }
+
+    pub fn allows(self, permission: Permission) -> bool {
+        self >= permission.minimum_role()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Permission {
+    WorkspaceRead,
+    WorkspaceAddProjects,
+    WorkspaceManageMembers,
+    ProjectRead,
+    ProjectWrite,
+    ProjectManage,
+    TaskRead,
+    TaskWrite,
+}
+
+impl Permission {
+    // Code renvoyé dans les réponses 403 (`missing_permission`)
+    pub fn code(self) -> &'static str {
+        match self {
+            Permission::WorkspaceRead => "workspace.read",
+            Permission::WorkspaceAddProjects => "workspace.add_projects",
+            Permission::WorkspaceManageMembers => "workspace.manage_members",
+            Permission::ProjectRead => "project.read",
+            Permission::ProjectWrite => "project.write",
+            Permission::ProjectManage => "project.manage",
+            Permission::TaskRead => "task.read",
+            Permission::TaskWrite => "task.write",
+        }
+    }
+
+    fn minimum_role(self) -> Role {
+        match self {
+            Permission::WorkspaceRead | Permission::ProjectRead | Permission::TaskRead => {
+                Role::Viewer
+            }
+            Permission::WorkspaceAddProjects | Permission::ProjectWrite | Permission::TaskWrite => {
+                Role::Editor
+            }
+            Permission::WorkspaceManageMembers | Permission::ProjectManage => Role::Owner,
+        }
+    }
+}
+
+fn ensure_allowed(role: Role, permission: Permission) -> Result<Role, ServiceError> {
+    if role.allows(permission) {
+        Ok(role)
+    } else {
+        Err(ServiceError::Forbidden(permission.code().to_string()))
+    }
+}
+
+pub async fn workspace_role(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    workspace_uuid: Uuid,
+) -> Result<Option<Role>, ServiceError> {
+    let raw_role = workspace_members::table
+        .filter(workspace_members::workspace_id.eq(workspace_uuid))
+        .filter(workspace_members::user_id.eq(user_uuid))
+        .select(workspace_members::role)
+        .first::<String>(conn)
+        .await
+        .optional()?;
+
+    Ok(raw_role.as_deref().and_then(Role::parse))
+}
+
+// Le créateur du projet en est propriétaire ; les membres de son espace héritent de leur rôle
+pub async fn project_role(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    project_uuid: ProjectId,
+) -> Result<Option<Role>, ServiceError> {
+    let project = projects::table
+        .find(project_uuid)
+        .select((projects::user_id, projects::workspace_id))
+        .first::<(Uuid, Option<Uuid>)>(conn)
+        .await
+        .optional()?;
+
+    match project {
+        None => Ok(None),
+        Some((owner_uuid, _)) if owner_uuid == user_uuid => Ok(Some(Role::Owner)),
+        Some((_, Some(workspace_uuid))) => workspace_role(conn, user_uuid, workspace_uuid).await,
+        Some((_, None)) => Ok(None),
+    }
+}
+
+// Sans rôle, la ressource est traitée comme inexistante (404) pour ne pas révéler son existence
+pub async fn require_workspace(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    workspace_uuid: Uuid,
+    permission: Permission,
+) -> Result<Role, ServiceError> {
+    let role = workspace_role(conn, user_uuid, workspace_uuid)
+        .await?
+        .ok_or_else(|| {
+            ServiceError::NotFound(i18n::tf("error.workspace_not_found", &[&workspace_uuid]))
+        })?;
+    ensure_allowed(role, permission)
+}
+
+pub async fn require_project(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    project_uuid: ProjectId,
+    permission: Permission,
+) -> Result<Role, ServiceError> {
+    let role = project_role(conn, user_uuid, project_uuid)
+        .await?
+        .ok_or_else(|| {
+            ServiceError::NotFound(i18n::tf("error.project_not_found", &[&project_uuid]))
+        })?;
+    ensure_allowed(role, permission)
+}
+
+// Charge la tâche si l'appelant dispose de la permission demandée
+pub async fn require_task(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    task_uuid: TaskId,
+    permission: Permission,
+) -> Result<Task, ServiceError> {
+    let not_found = || ServiceError::NotFound(i18n::tf("error.task_not_found", &[&task_uuid]));
+
+    let task = tasks::table
+        .find(task_uuid)
+        .select(Task::as_select())
+        .first::<Task>(conn)
+        .await
+        .optional()?
+        .ok_or_else(not_found)?;
+
+    let role = if task.user_id == user_uuid {
+        Role::Owner
+    } else {
+        match task.project_id {
+            Some(project_uuid) => project_role(conn, user_uuid, project_uuid)
+                .await?
+                .ok_or_else(not_found)?,
+            None => return Err(not_found()),
+        }
+    };
+
+    ensure_allowed(role, permission)?;
+    Ok(task)
+}
+
+// Projets visibles par l'utilisateur : les siens et ceux des espaces dont il est membre
+pub async fn accessible_project_ids(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Vec<ProjectId>, ServiceError> {
+    let member_workspaces = workspace_members::table
+        .filter(workspace_members::user_id.eq(user_uuid))
+        .select(workspace_members::workspace_id.nullable());
+
+    projects::table
+        .filter(
+            projects::user_id
+                .eq(user_uuid)
+                .or(projects::workspace_id.eq_any(member_workspaces)),
+        )
+        .select(projects::id)
+        .load::<ProjectId>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
@@ -0,0 +1,149 @@
+// OptiTask/backend-api/src/project_completion.rs
+// Clôture d'un projet terminé : les tâches encore ouvertes passent à "completed" (ou
+// "cancelled"), les minuteurs en cours sur ces tâches sont arrêtés, puis le projet est
+// archivé. L'appelant fournit la transaction ; les compteurs de tâches et l'historique des
+// statuts suivent via les triggers sur tasks.
+use crate::error_handler::ServiceError;
+use crate::ids::{ProjectId, TaskId, TimeEntryId};
+use crate::models::{Project, TimeEntry, DONE_TASK_STATUSES};
+use crate::schema::{projects, tasks, time_entries};
+use crate::timesheets::ensure_entry_unlocked;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use uuid::Uuid;
+
+pub const RESOLUTION_COMPLETED: &str = "completed";
+pub const RESOLUTION_CANCELLED: &str = "cancelled";
+pub const PROJECT_RESOLUTIONS: [&str; 2] = [RESOLUTION_COMPLETED, RESOLUTION_CANCELLED];
+
+pub const PROJECT_ARCHIVED: &str = "PROJECT_ARCHIVED";
+
+#[derive(Serialize, Debug)]
+pub struct ProjectCompletionSummary {
+    pub project_id: ProjectId,
+    // Statut donné aux tâches ouvertes
+    pub resolution: &'static str,
+    pub tasks_closed: usize,
+    pub closed_task_ids: Vec<TaskId>,
+    // Tâches déjà terminées ou annulées, laissées telles quelles
+    pub tasks_already_closed: i64,
+    pub stopped_timers: Vec<StoppedTimer>,
+    pub archived_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StoppedTimer {
+    pub time_entry_id: TimeEntryId,
+    pub task_id: TaskId,
+    pub user_id: Uuid,
+    pub duration_seconds: i32,
+}
+
+pub fn parse_resolution(value: &str) -> Result<&'static str, ServiceError> {
+    let normalized = value.trim().to_ascii_lowercase();
+    PROJECT_RESOLUTIONS
+        .iter()
+        .find(|known| **known == normalized)
+        .copied()
+        .ok_or_else(|| {
+            ServiceError::ValidationError(format!(
+                "Invalid resolution '{}'. Supported: {}",
+                value,
+                PROJECT_RESOLUTIONS.join(", ")
+            ))
+        })
+}
+
+fn closed_statuses() -> Vec<&'static str> {
+    let mut closed = DONE_TASK_STATUSES.to_vec();
+    closed.push(RESOLUTION_CANCELLED);
+    closed
+}
+
+// `project` doit avoir été verrouillé (FOR UPDATE) par l'appelant
+pub async fn complete_project(
+    conn: &mut AsyncPgConnection,
+    project: &Project,
+    resolution: &'static str,
+) -> Result<ProjectCompletionSummary, ServiceError> {
+    if project.archived_at.is_some() {
+        return Err(ServiceError::CodedConflict(
+            PROJECT_ARCHIVED,
+            format!("Project '{}' is already archived", project.name),
+        ));
+    }
+    let now = Utc::now();
+
+    // Les minuteurs sont arrêtés avant la clôture des tâches : une semaine verrouillée
+    // (feuille de temps soumise) annule toute l'opération
+    let running_entries = time_entries::table
+        .inner_join(tasks::table)
+        .filter(tasks::project_id.eq(project.id))
+        .filter(time_entries::end_time.is_null())
+        .select(TimeEntry::as_select())
+        .load::<TimeEntry>(conn)
+        .await?;
+    let mut stopped_timers = Vec::with_capacity(running_entries.len());
+    for entry in &running_entries {
+        ensure_entry_unlocked(conn, entry.user_id, entry.start_time).await?;
+        let stopped_at = now.max(entry.start_time);
+        let duration_seconds = (stopped_at - entry.start_time).num_seconds() as i32;
+        diesel::update(
+            time_entries::table
+                .filter(time_entries::id.eq(entry.id))
+                .filter(time_entries::start_time.eq(entry.start_time)),
+        )
+        .set((
+            time_entries::end_time.eq(Some(stopped_at)),
+            time_entries::duration_seconds.eq(Some(duration_seconds)),
+            time_entries::updated_at.eq(now.naive_utc()),
+        ))
+        .execute(conn)
+        .await?;
+        stopped_timers.push(StoppedTimer {
+            time_entry_id: entry.id,
+            task_id: entry.task_id,
+            user_id: entry.user_id,
+            duration_seconds,
+        });
+    }
+
+    let closed_task_ids = diesel::update(
+        tasks::table
+            .filter(tasks::project_id.eq(project.id))
+            .filter(tasks::status.ne_all(closed_statuses())),
+    )
+    .set((
+        tasks::status.eq(resolution),
+        tasks::updated_at.eq(now.naive_utc()),
+    ))
+    .returning(tasks::id)
+    .get_results::<TaskId>(conn)
+    .await?;
+    let tasks_already_closed = tasks::table
+        .filter(tasks::project_id.eq(project.id))
+        .filter(tasks::id.ne_all(&closed_task_ids))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+
+    diesel::update(projects::table.find(project.id))
+        .set((
+            projects::archived_at.eq(Some(now)),
+            projects::updated_at.eq(now.naive_utc()),
+        ))
+        .execute(conn)
+        .await?;
+
+    Ok(ProjectCompletionSummary {
+        project_id: project.id,
+        resolution,
+        tasks_closed: closed_task_ids.len(),
+        closed_task_ids,
+        tasks_already_closed,
+        stopped_timers,
+        archived_at: now,
+    })
+}
@@ -0,0 +1,283 @@
+// OptiTask/backend-api/src/project_merge.rs
+// Détection et fusion des projets en double (imports successifs, "Personal" recréé...).
+// La fusion déplace vers la cible tout ce qui est rattaché au projet source, puis le
+// supprime ; l'appelant fournit la transaction. Les compteurs de tâches et le temps suivi
+// agrégé suivent via les triggers sur tasks.
+use crate::error_handler::ServiceError;
+use crate::ids::ProjectId;
+use crate::models::Project;
+use crate::schema::{
+    calendar_project_links, calendar_suggestions, custom_field_definitions, focus_sessions,
+    projects, task_aging_rules, task_custom_values, task_status_history, tasks, user_onboarding,
+    user_settings,
+};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Serialize, Debug)]
+pub struct ProjectDuplicateGroup {
+    // Nom normalisé commun (casse et espaces ignorés)
+    pub normalized_name: String,
+    // Du plus ancien au plus récent : le premier est la cible naturelle d'une fusion
+    pub projects: Vec<Project>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct ProjectMergeSummary {
+    pub source_project_id: ProjectId,
+    pub target_project_id: ProjectId,
+    pub dry_run: bool,
+    pub tasks_moved: i64,
+    // Champs personnalisés déplacés tels quels vers la cible
+    pub custom_fields_moved: i64,
+    // Champs fusionnés avec un champ de même nom et de même type de la cible
+    pub custom_fields_merged: i64,
+    // Champs déplacés mais renommés (même nom, type différent)
+    pub custom_fields_renamed: Vec<String>,
+    pub aging_rules_moved: i64,
+    pub calendar_links_moved: i64,
+    pub calendar_suggestions_moved: i64,
+}
+
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// Regroupe les projets portant le même nom ; seuls les groupes d'au moins deux sont renvoyés
+pub fn find_duplicates(project_list: Vec<Project>) -> Vec<ProjectDuplicateGroup> {
+    let mut groups: BTreeMap<String, Vec<Project>> = BTreeMap::new();
+    for project in project_list {
+        groups
+            .entry(normalize_name(&project.name))
+            .or_default()
+            .push(project);
+    }
+    groups
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .map(|(normalized_name, mut group)| {
+            group.sort_by_key(|project| project.created_at);
+            ProjectDuplicateGroup {
+                normalized_name,
+                projects: group,
+            }
+        })
+        .collect()
+}
+
+// Les deux projets doivent relever du même espace (ou être deux projets personnels du même
+// propriétaire) : la fusion ne doit pas changer qui voit les tâches
+pub fn ensure_mergeable(source: &Project, target: &Project) -> Result<(), ServiceError> {
+    if source.id == target.id {
+        return Err(ServiceError::ValidationError(
+            "A project cannot be merged into itself".to_string(),
+        ));
+    }
+    let same_scope = match (source.workspace_id, target.workspace_id) {
+        (Some(source_workspace), Some(target_workspace)) => source_workspace == target_workspace,
+        (None, None) => source.user_id == target.user_id,
+        _ => false,
+    };
+    if !same_scope {
+        return Err(ServiceError::ValidationError(
+            "Projects can only be merged within the same workspace".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+struct FieldDefinition {
+    id: Uuid,
+    name: String,
+    field_type: String,
+    options: serde_json::Value,
+}
+
+async fn load_fields(
+    conn: &mut AsyncPgConnection,
+    project_uuid: ProjectId,
+) -> Result<Vec<FieldDefinition>, ServiceError> {
+    Ok(custom_field_definitions::table
+        .filter(custom_field_definitions::project_id.eq(project_uuid))
+        .select((
+            custom_field_definitions::id,
+            custom_field_definitions::name,
+            custom_field_definitions::field_type,
+            custom_field_definitions::options,
+        ))
+        .load::<(Uuid, String, String, serde_json::Value)>(conn)
+        .await?
+        .into_iter()
+        .map(|(id, name, field_type, options)| FieldDefinition {
+            id,
+            name,
+            field_type,
+            options,
+        })
+        .collect())
+}
+
+// Options d'un champ "select" fusionné : celles de la cible, puis les nouvelles de la source
+fn merged_options(target: &serde_json::Value, source: &serde_json::Value) -> serde_json::Value {
+    let mut options = target.as_array().cloned().unwrap_or_default();
+    for option in source.as_array().into_iter().flatten() {
+        if !options.contains(option) {
+            options.push(option.clone());
+        }
+    }
+    serde_json::Value::Array(options)
+}
+
+// Fusionne source dans target. La simulation (dry_run) passe par dry_run::transaction :
+// la fusion est appliquée puis annulée.
+pub async fn merge_projects(
+    conn: &mut AsyncPgConnection,
+    source: &Project,
+    target: &Project,
+) -> Result<ProjectMergeSummary, ServiceError> {
+    ensure_mergeable(source, target)?;
+
+    let mut summary = ProjectMergeSummary {
+        source_project_id: source.id,
+        target_project_id: target.id,
+        ..Default::default()
+    };
+
+    summary.tasks_moved = tasks::table
+        .filter(tasks::project_id.eq(source.id))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+    summary.aging_rules_moved = task_aging_rules::table
+        .filter(task_aging_rules::project_id.eq(source.id))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+    summary.calendar_links_moved = calendar_project_links::table
+        .filter(calendar_project_links::project_id.eq(source.id))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+    summary.calendar_suggestions_moved = calendar_suggestions::table
+        .filter(calendar_suggestions::project_id.eq(source.id))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+
+    let target_fields = load_fields(conn, target.id).await?;
+    let source_fields = load_fields(conn, source.id).await?;
+    for field in &source_fields {
+        let homonym = target_fields.iter().find(|other| other.name == field.name);
+        match homonym {
+            Some(other) if other.field_type == field.field_type => {
+                summary.custom_fields_merged += 1;
+                // Les valeurs des tâches déplacées passent sur le champ de la cible
+                diesel::update(
+                    task_custom_values::table.filter(task_custom_values::field_id.eq(field.id)),
+                )
+                .set(task_custom_values::field_id.eq(other.id))
+                .execute(conn)
+                .await?;
+                diesel::update(custom_field_definitions::table.find(other.id))
+                    .set(
+                        custom_field_definitions::options
+                            .eq(merged_options(&other.options, &field.options)),
+                    )
+                    .execute(conn)
+                    .await?;
+                diesel::delete(custom_field_definitions::table.find(field.id))
+                    .execute(conn)
+                    .await?;
+            }
+            Some(_) => {
+                let renamed = format!("{} ({})", field.name, source.name);
+                summary.custom_fields_moved += 1;
+                summary.custom_fields_renamed.push(renamed.clone());
+                diesel::update(custom_field_definitions::table.find(field.id))
+                    .set((
+                        custom_field_definitions::project_id.eq(target.id),
+                        custom_field_definitions::name.eq(renamed),
+                    ))
+                    .execute(conn)
+                    .await?;
+            }
+            None => {
+                summary.custom_fields_moved += 1;
+                diesel::update(custom_field_definitions::table.find(field.id))
+                    .set(custom_field_definitions::project_id.eq(target.id))
+                    .execute(conn)
+                    .await?;
+            }
+        }
+    }
+
+    diesel::update(tasks::table.filter(tasks::project_id.eq(source.id)))
+        .set(tasks::project_id.eq(target.id))
+        .execute(conn)
+        .await?;
+    // L'historique est réattribué pour que les rapports de la cible couvrent tout le passé
+    diesel::update(
+        task_status_history::table.filter(task_status_history::project_id.eq(source.id)),
+    )
+    .set(task_status_history::project_id.eq(target.id))
+    .execute(conn)
+    .await?;
+    // Les sessions de focus partiraient en cascade avec la source
+    diesel::update(focus_sessions::table.filter(focus_sessions::project_id.eq(source.id)))
+        .set(focus_sessions::project_id.eq(target.id))
+        .execute(conn)
+        .await?;
+    diesel::update(task_aging_rules::table.filter(task_aging_rules::project_id.eq(source.id)))
+        .set(task_aging_rules::project_id.eq(target.id))
+        .execute(conn)
+        .await?;
+    diesel::update(
+        calendar_suggestions::table.filter(calendar_suggestions::project_id.eq(source.id)),
+    )
+    .set(calendar_suggestions::project_id.eq(target.id))
+    .execute(conn)
+    .await?;
+    diesel::update(user_onboarding::table.filter(user_onboarding::project_id.eq(source.id)))
+        .set(user_onboarding::project_id.eq(target.id))
+        .execute(conn)
+        .await?;
+    // Sinon la suppression de la source renverrait les nouvelles tâches dans l'Inbox
+    diesel::update(user_settings::table.filter(user_settings::default_project_id.eq(source.id)))
+        .set(user_settings::default_project_id.eq(target.id))
+        .execute(conn)
+        .await?;
+
+    // Mots-clés d'agenda : ceux déjà liés à la cible sont conservés une seule fois
+    let links = calendar_project_links::table
+        .filter(calendar_project_links::project_id.eq(source.id))
+        .select((
+            calendar_project_links::user_id,
+            calendar_project_links::keyword,
+        ))
+        .load::<(Uuid, String)>(conn)
+        .await?;
+    for (link_user, keyword) in links {
+        diesel::insert_into(calendar_project_links::table)
+            .values((
+                calendar_project_links::user_id.eq(link_user),
+                calendar_project_links::project_id.eq(target.id),
+                calendar_project_links::keyword.eq(keyword),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+    }
+
+    // Les liens d'agenda restants et les définitions éventuelles partent en cascade
+    diesel::delete(projects::table.find(source.id))
+        .execute(conn)
+        .await?;
+
+    Ok(summary)
+}
@@ -0,0 +1,700 @@
+// OptiTask/backend-api/src/push.rs
+// Notifications push : le dispatcher envoie les notifications récentes vers les appareils
+// enregistrés (voir device_handlers), en respectant les heures calmes et en regroupant
+// les rafales, et conserve un accusé par envoi. Les rappels d'échéance
+// et les relances de minuteur sont créés ici comme des notifications ordinaires.
+use crate::config::{AppConfig, ConfigError};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::handlers::analytics_handlers::period_bounds;
+use crate::ids::{TaskId, TimeEntryId};
+use crate::models::{NewNotification, NewPushDelivery, UserSettings, DONE_TASK_STATUSES};
+use crate::notifications::{
+    KIND_NO_TIME_TRACKED_NUDGE, KIND_PLANNED_TASKS_NUDGE, KIND_TASK_COMMENT,
+    KIND_TASK_DUE_REMINDER, KIND_TASK_STATUS_CHANGED, KIND_TIMER_NUDGE,
+};
+use crate::outbound;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::{devices, notifications, push_deliveries, tasks, time_entries, user_settings};
+use async_trait::async_trait;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use chrono::{DateTime, Duration, NaiveTime, Timelike, Utc};
+use diesel::dsl::{exists, not};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use ring::rand::SystemRandom;
+use ring::signature::{RsaKeyPair, RSA_PKCS1_SHA256};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+const PUSH_JOB_INTERVAL_SECS: u64 = 60;
+// Au-delà, une notification n'est plus envoyée ; les plus récentes, retenues par les
+// heures calmes ou une indisponibilité, partent en un résumé
+const PUSH_MAX_AGE_HOURS: i64 = 24;
+// Valeur par défaut de user_settings.push_batch_minutes
+const DEFAULT_PUSH_BATCH_MINUTES: i32 = 2;
+const SUMMARY_PREVIEW_LINES: usize = 3;
+
+// Notification réservée pour l'envoi : id, tâche, type, message, date de création
+type PendingPush = (Uuid, Option<Uuid>, String, String, DateTime<Utc>);
+// Type transmis à l'application pour un envoi regroupé
+const KIND_PUSH_SUMMARY: &str = "summary";
+// Les rappels d'échéance partent à partir de cette heure (UTC)
+const DUE_REMINDER_HOUR_UTC: u32 = 8;
+const TIMER_NUDGE_AFTER_HOURS: i64 = 3;
+
+pub const DELIVERY_SENT: &str = "sent";
+pub const DELIVERY_FAILED: &str = "failed";
+pub const DELIVERY_INVALID_TOKEN: &str = "invalid_token";
+
+// Types de notifications envoyés en push ; les autres restent dans l'application
+const PUSHED_KINDS: [&str; 6] = [
+    KIND_TASK_DUE_REMINDER,
+    KIND_TIMER_NUDGE,
+    KIND_TASK_STATUS_CHANGED,
+    KIND_TASK_COMMENT,
+    KIND_PLANNED_TASKS_NUDGE,
+    KIND_NO_TIME_TRACKED_NUDGE,
+];
+
+// Catégories désactivables dans les préférences (push_due_reminders, ...)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PushCategory {
+    DueReminders,
+    TimerNudges,
+    // Activité des autres sur les tâches observées
+    Mentions,
+    // Relances sur le rythme de travail, déjà choisies via nudge_* (voir nudges.rs)
+    WorkNudges,
+}
+
+impl PushCategory {
+    pub fn of_kind(kind: &str) -> Option<PushCategory> {
+        match kind {
+            KIND_TASK_DUE_REMINDER => Some(PushCategory::DueReminders),
+            KIND_TIMER_NUDGE => Some(PushCategory::TimerNudges),
+            KIND_TASK_STATUS_CHANGED | KIND_TASK_COMMENT => Some(PushCategory::Mentions),
+            KIND_PLANNED_TASKS_NUDGE | KIND_NO_TIME_TRACKED_NUDGE => Some(PushCategory::WorkNudges),
+            _ => None,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            PushCategory::DueReminders => "Due today",
+            PushCategory::TimerNudges => "Timer still running",
+            PushCategory::Mentions => "Task activity",
+            PushCategory::WorkNudges => "Daily check-in",
+        }
+    }
+}
+
+pub struct PushMessage {
+    pub title: String,
+    pub body: String,
+    // Transmis tel quel à l'application (identifiants de notification et de tâche)
+    pub data: BTreeMap<String, String>,
+}
+
+pub enum PushOutcome {
+    // Identifiant attribué par le fournisseur, s'il en renvoie un
+    Delivered(Option<String>),
+    // Jeton expiré ou application désinstallée : le jeton est retiré de l'appareil
+    InvalidToken,
+}
+
+// Fournisseur d'envoi. Le dispatcher dépend de ce trait uniquement,
+// le fournisseur concret est choisi au démarrage via les variables d'environnement.
+#[async_trait]
+pub trait PushProvider: Send + Sync {
+    // Nom stocké dans les accusés d'envoi (ex: "fcm:my-project")
+    fn name(&self) -> String;
+
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<PushOutcome, ServiceError>;
+}
+
+const FCM_API_URL: &str = "https://fcm.googleapis.com";
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+
+// Firebase Cloud Messaging (API HTTP v1). Les appareils iOS sont joints via le relais
+// APNs de FCM : l'application enregistre son jeton FCM, pas un jeton APNs brut.
+pub struct FcmProvider {
+    project_id: String,
+    client_email: String,
+    token_uri: String,
+    signing_key: RsaKeyPair,
+    // Jeton OAuth du compte de service et son expiration
+    access_token: Mutex<Option<(String, DateTime<Utc>)>>,
+}
+
+#[derive(Deserialize)]
+struct ServiceAccountKey {
+    project_id: String,
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Deserialize)]
+struct OAuthTokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+#[derive(Deserialize)]
+struct FcmSendResponse {
+    name: String,
+}
+
+impl FcmProvider {
+    // FCM_SERVICE_ACCOUNT_FILE : clé JSON d'un compte de service du projet Firebase
+    pub fn from_service_account_file(path: &str) -> Result<FcmProvider, ConfigError> {
+        let invalid = |reason: String| ConfigError::Invalid("FCM_SERVICE_ACCOUNT_FILE", reason);
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| invalid(format!("cannot read '{}' ({})", path, e)))?;
+        let key: ServiceAccountKey = serde_json::from_str(&raw)
+            .map_err(|e| invalid(format!("'{}' is not a service account key ({})", path, e)))?;
+
+        // Clé PKCS#8 au format PEM
+        let der = STANDARD
+            .decode(
+                key.private_key
+                    .lines()
+                    .filter(|line| !line.starts_with("-----"))
+                    .collect::<String>(),
+            )
+            .map_err(|_| invalid("invalid private_key encoding".to_string()))?;
+        let signing_key =
+            RsaKeyPair::from_pkcs8(&der).map_err(|_| invalid("invalid private_key".to_string()))?;
+
+        Ok(FcmProvider {
+            project_id: key.project_id,
+            client_email: key.client_email,
+            token_uri: key.token_uri,
+            signing_key,
+            access_token: Mutex::new(None),
+        })
+    }
+
+    // Assertion JWT (RS256) échangée contre un jeton d'accès OAuth
+    fn signed_assertion(&self, now: DateTime<Utc>) -> Result<String, ServiceError> {
+        let header = URL_SAFE_NO_PAD.encode(json!({ "alg": "RS256", "typ": "JWT" }).to_string());
+        let claims = URL_SAFE_NO_PAD.encode(
+            json!({
+                "iss": self.client_email,
+                "scope": FCM_SCOPE,
+                "aud": self.token_uri,
+                "iat": now.timestamp(),
+                "exp": (now + Duration::hours(1)).timestamp(),
+            })
+            .to_string(),
+        );
+        let signing_input = format!("{}.{}", header, claims);
+
+        let mut signature = vec![0; self.signing_key.public().modulus_len()];
+        self.signing_key
+            .sign(
+                &RSA_PKCS1_SHA256,
+                &SystemRandom::new(),
+                signing_input.as_bytes(),
+                &mut signature,
+            )
+            .map_err(|_| {
+                ServiceError::InternalServerError("Failed to sign FCM assertion".to_string())
+            })?;
+
+        Ok(format!(
+            "{}.{}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+
+    async fn bearer_token(&self) -> Result<String, ServiceError> {
+        let now = Utc::now();
+        if let Some((token, expires_at)) = self.access_token.lock().unwrap().clone() {
+            if expires_at > now + Duration::minutes(1) {
+                return Ok(token);
+            }
+        }
+
+        let request = outbound::client().post(&self.token_uri).form(&[
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", self.signed_assertion(now)?.as_str()),
+        ]);
+        let response = outbound::send(&outbound::PUSH_POLICY, request)
+            .await
+            .map_err(|e| {
+                log::error!("FCM token request failed: {}", e);
+                ServiceError::InternalServerError("FCM token request failed".to_string())
+            })?;
+        if !response.status().is_success() {
+            log::error!("FCM token endpoint returned status {}", response.status());
+            return Err(ServiceError::InternalServerError(
+                "FCM token endpoint returned an error".to_string(),
+            ));
+        }
+        let token = response.json::<OAuthTokenResponse>().await.map_err(|e| {
+            log::error!("Invalid FCM token response: {}", e);
+            ServiceError::InternalServerError("Invalid FCM token response".to_string())
+        })?;
+
+        *self.access_token.lock().unwrap() = Some((
+            token.access_token.clone(),
+            now + Duration::seconds(token.expires_in),
+        ));
+        Ok(token.access_token)
+    }
+}
+
+#[async_trait]
+impl PushProvider for FcmProvider {
+    fn name(&self) -> String {
+        format!("fcm:{}", self.project_id)
+    }
+
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<PushOutcome, ServiceError> {
+        let url = format!(
+            "{}/v1/projects/{}/messages:send",
+            FCM_API_URL, self.project_id
+        );
+        let request = outbound::client()
+            .post(&url)
+            .bearer_auth(self.bearer_token().await?)
+            .json(&json!({
+                "message": {
+                    "token": token,
+                    "notification": { "title": message.title, "body": message.body },
+                    "data": message.data,
+                    "apns": { "payload": { "aps": { "sound": "default" } } }
+                }
+            }));
+        let response = outbound::send(&outbound::PUSH_POLICY, request)
+            .await
+            .map_err(|e| {
+                log::error!("FCM send request failed: {}", e);
+                ServiceError::InternalServerError("FCM send request failed".to_string())
+            })?;
+
+        let status = response.status();
+        if status.is_success() {
+            let sent = response.json::<FcmSendResponse>().await.map_err(|e| {
+                log::error!("Invalid FCM send response: {}", e);
+                ServiceError::InternalServerError("Invalid FCM send response".to_string())
+            })?;
+            return Ok(PushOutcome::Delivered(Some(sent.name)));
+        }
+
+        let error_body = response.text().await.unwrap_or_default();
+        if status == reqwest::StatusCode::NOT_FOUND || error_body.contains("UNREGISTERED") {
+            return Ok(PushOutcome::InvalidToken);
+        }
+        log::warn!("FCM returned status {}: {}", status, error_body);
+        Err(ServiceError::InternalServerError(format!(
+            "FCM returned status {}",
+            status
+        )))
+    }
+}
+
+// None si aucun fournisseur n'est configuré : les notifications restent dans l'application
+pub fn provider_from_config(
+    config: &AppConfig,
+) -> Result<Option<Arc<dyn PushProvider>>, ConfigError> {
+    let Some(path) = &config.fcm_service_account_file else {
+        return Ok(None);
+    };
+    let provider = FcmProvider::from_service_account_file(path)?;
+    log::info!("Push notifications enabled ({})", provider.name());
+    Ok(Some(Arc::new(provider)))
+}
+
+// Préférences d'envoi d'un utilisateur (valeurs par défaut sans ligne de préférences)
+struct PushPreferences {
+    due_reminders: bool,
+    timer_nudges: bool,
+    mentions: bool,
+    quiet_hours: Option<(NaiveTime, NaiveTime)>,
+    utc_offset_minutes: i32,
+    batch_minutes: i32,
+}
+
+impl PushPreferences {
+    async fn load(
+        conn: &mut AsyncPgConnection,
+        user_uuid: Uuid,
+    ) -> Result<PushPreferences, ServiceError> {
+        let stored = user_settings::table
+            .find(user_uuid)
+            .select(UserSettings::as_select())
+            .first::<UserSettings>(conn)
+            .await
+            .optional()?;
+
+        Ok(match stored {
+            None => PushPreferences {
+                due_reminders: true,
+                timer_nudges: true,
+                mentions: true,
+                quiet_hours: None,
+                utc_offset_minutes: 0,
+                batch_minutes: DEFAULT_PUSH_BATCH_MINUTES,
+            },
+            Some(stored) => PushPreferences {
+                due_reminders: stored.push_due_reminders,
+                timer_nudges: stored.push_timer_nudges,
+                mentions: stored.push_mentions,
+                quiet_hours: stored.quiet_hours_start.zip(stored.quiet_hours_end),
+                utc_offset_minutes: stored.utc_offset_minutes,
+                batch_minutes: stored.push_batch_minutes,
+            },
+        })
+    }
+
+    fn allows(&self, category: PushCategory) -> bool {
+        match category {
+            PushCategory::DueReminders => self.due_reminders,
+            PushCategory::TimerNudges => self.timer_nudges,
+            PushCategory::Mentions => self.mentions,
+            PushCategory::WorkNudges => true,
+        }
+    }
+
+    // La plage peut passer minuit (22:00 → 07:00) ; début = fin équivaut à aucune plage
+    fn in_quiet_hours(&self, now: DateTime<Utc>) -> bool {
+        let Some((start, end)) = self.quiet_hours else {
+            return false;
+        };
+        let local_time = (now + Duration::minutes(i64::from(self.utc_offset_minutes))).time();
+        if start <= end {
+            start <= local_time && local_time < end
+        } else {
+            local_time >= start || local_time < end
+        }
+    }
+}
+
+// Rappel pour chaque tâche ouverte due aujourd'hui (une fois par jour et par tâche),
+// limité aux utilisateurs joignables qui n'ont pas désactivé cette catégorie
+pub async fn create_due_reminders(conn: &mut AsyncPgConnection) -> Result<usize, ServiceError> {
+    let now = Utc::now();
+    if now.hour() < DUE_REMINDER_HOUR_UTC {
+        return Ok(0);
+    }
+    let today = now.date_naive();
+    let (day_start, _) = period_bounds(today, today);
+
+    let due_tasks = tasks::table
+        .filter(tasks::due_date.eq(today))
+        .filter(tasks::status.ne_all(DONE_TASK_STATUSES))
+        .filter(
+            tasks::snoozed_until
+                .is_null()
+                .or(tasks::snoozed_until.le(now)),
+        )
+        .filter(exists(
+            devices::table
+                .filter(devices::user_id.eq(tasks::user_id))
+                .filter(devices::push_token.is_not_null()),
+        ))
+        .filter(not(exists(
+            user_settings::table
+                .filter(user_settings::user_id.eq(tasks::user_id))
+                .filter(user_settings::push_due_reminders.eq(false)),
+        )))
+        .filter(not(exists(
+            notifications::table
+                .filter(notifications::task_id.eq(tasks::id.nullable()))
+                .filter(notifications::kind.eq(KIND_TASK_DUE_REMINDER))
+                .filter(notifications::created_at.ge(day_start)),
+        )))
+        .select((tasks::id, tasks::user_id, tasks::title))
+        .load::<(TaskId, Uuid, String)>(conn)
+        .await?;
+
+    if due_tasks.is_empty() {
+        return Ok(0);
+    }
+
+    let reminders: Vec<NewNotification> = due_tasks
+        .into_iter()
+        .map(|(task_uuid, owner_uuid, title)| NewNotification {
+            user_id: owner_uuid,
+            task_id: Some(task_uuid),
+            actor_id: None,
+            kind: KIND_TASK_DUE_REMINDER.to_string(),
+            message: format!("'{}' is due today", title),
+            payload: json!({ "due_date": today }),
+        })
+        .collect();
+
+    diesel::insert_into(notifications::table)
+        .values(&reminders)
+        .execute(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Relance unique pour chaque minuteur lancé depuis plus de TIMER_NUDGE_AFTER_HOURS
+pub async fn create_timer_nudges(conn: &mut AsyncPgConnection) -> Result<usize, ServiceError> {
+    let now = Utc::now();
+
+    let running_entries = time_entries::table
+        .inner_join(tasks::table)
+        .filter(time_entries::end_time.is_null())
+        .filter(time_entries::start_time.le(now - Duration::hours(TIMER_NUDGE_AFTER_HOURS)))
+        .filter(exists(
+            devices::table
+                .filter(devices::user_id.eq(time_entries::user_id))
+                .filter(devices::push_token.is_not_null()),
+        ))
+        .filter(not(exists(
+            user_settings::table
+                .filter(user_settings::user_id.eq(time_entries::user_id))
+                .filter(user_settings::push_timer_nudges.eq(false)),
+        )))
+        .filter(not(exists(
+            notifications::table
+                .filter(notifications::user_id.eq(time_entries::user_id))
+                .filter(notifications::task_id.eq(time_entries::task_id.nullable()))
+                .filter(notifications::kind.eq(KIND_TIMER_NUDGE))
+                .filter(notifications::created_at.ge(time_entries::start_time)),
+        )))
+        .select((
+            time_entries::id,
+            time_entries::user_id,
+            time_entries::task_id,
+            time_entries::start_time,
+            tasks::title,
+        ))
+        .load::<(TimeEntryId, Uuid, TaskId, DateTime<Utc>, String)>(conn)
+        .await?;
+
+    if running_entries.is_empty() {
+        return Ok(0);
+    }
+
+    let nudges: Vec<NewNotification> = running_entries
+        .into_iter()
+        .map(
+            |(entry_uuid, owner_uuid, task_uuid, start_time, title)| NewNotification {
+                user_id: owner_uuid,
+                task_id: Some(task_uuid),
+                actor_id: None,
+                kind: KIND_TIMER_NUDGE.to_string(),
+                message: format!(
+                    "Timer running for {}h on '{}' — still working?",
+                    (now - start_time).num_hours(),
+                    title
+                ),
+                payload: json!({ "time_entry_id": entry_uuid, "started_at": start_time }),
+            },
+        )
+        .collect();
+
+    diesel::insert_into(notifications::table)
+        .values(&nudges)
+        .execute(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Envoie les notifications en attente vers les appareils de leur destinataire et
+// enregistre un accusé par notification et par appareil. Pour chaque utilisateur :
+// - rien n'est envoyé pendant ses heures calmes, les notifications attendent la fin de la plage ;
+// - l'envoi attend que la plus ancienne notification en attente ait push_batch_minutes,
+//   puis toutes celles accumulées partent ensemble (un résumé s'il y en a plusieurs).
+// Renvoie le nombre d'envois réussis.
+pub async fn dispatch_pending(
+    conn: &mut AsyncPgConnection,
+    provider: &dyn PushProvider,
+) -> Result<usize, ServiceError> {
+    let now = Utc::now();
+    let oldest_allowed = now - Duration::hours(PUSH_MAX_AGE_HOURS);
+
+    let pending_users = notifications::table
+        .filter(notifications::pushed_at.is_null())
+        .filter(notifications::created_at.ge(oldest_allowed))
+        .filter(notifications::kind.eq_any(PUSHED_KINDS))
+        .group_by(notifications::user_id)
+        .select((
+            notifications::user_id,
+            diesel::dsl::min(notifications::created_at),
+        ))
+        .load::<(Uuid, Option<DateTime<Utc>>)>(conn)
+        .await?;
+
+    let provider_name = provider.name();
+    let mut sent = 0;
+    for (recipient_uuid, oldest_pending) in pending_users {
+        let preferences = PushPreferences::load(conn, recipient_uuid).await?;
+        if preferences.in_quiet_hours(now) {
+            continue;
+        }
+        let batch_window = Duration::minutes(i64::from(preferences.batch_minutes));
+        if oldest_pending.is_some_and(|oldest| oldest > now - batch_window) {
+            continue;
+        }
+
+        // Marquées avant l'envoi : une autre instance ne renverra pas les mêmes notifications
+        let claimed = diesel::update(
+            notifications::table
+                .filter(notifications::user_id.eq(recipient_uuid))
+                .filter(notifications::pushed_at.is_null())
+                .filter(notifications::created_at.ge(oldest_allowed))
+                .filter(notifications::kind.eq_any(PUSHED_KINDS)),
+        )
+        .set(notifications::pushed_at.eq(now))
+        .returning((
+            notifications::id,
+            notifications::task_id,
+            notifications::kind,
+            notifications::message,
+            notifications::created_at,
+        ))
+        .get_results::<PendingPush>(conn)
+        .await?;
+
+        let mut to_send: Vec<PendingPush> = claimed
+            .into_iter()
+            .filter(|(_, _, kind, _, _)| {
+                PushCategory::of_kind(kind).is_some_and(|category| preferences.allows(category))
+            })
+            .collect();
+        if to_send.is_empty() {
+            continue;
+        }
+        to_send.sort_by_key(|(_, _, _, _, created_at)| *created_at);
+
+        let targets = devices::table
+            .filter(devices::user_id.eq(recipient_uuid))
+            .filter(devices::push_token.is_not_null())
+            .select((devices::id, devices::push_token.assume_not_null()))
+            .load::<(Uuid, String)>(conn)
+            .await?;
+        if targets.is_empty() {
+            continue;
+        }
+
+        let push_message =
+            if let [(notification_uuid, task_uuid, kind, message, _)] = to_send.as_slice() {
+                let mut data = BTreeMap::from([
+                    ("notification_id".to_string(), notification_uuid.to_string()),
+                    ("kind".to_string(), kind.clone()),
+                ]);
+                if let Some(task_uuid) = task_uuid {
+                    data.insert("task_id".to_string(), task_uuid.to_string());
+                }
+                PushMessage {
+                    title: PushCategory::of_kind(kind)
+                        .map_or("OptiTask", PushCategory::title)
+                        .to_string(),
+                    body: message.clone(),
+                    data,
+                }
+            } else {
+                summary_message(&to_send)
+            };
+
+        for (device_uuid, token) in targets {
+            let (status, provider_message_id, error) =
+                match provider.send(&token, &push_message).await {
+                    Ok(PushOutcome::Delivered(message_id)) => (DELIVERY_SENT, message_id, None),
+                    Ok(PushOutcome::InvalidToken) => {
+                        diesel::update(devices::table.find(device_uuid))
+                            .set(devices::push_token.eq(None::<String>))
+                            .execute(conn)
+                            .await?;
+                        (DELIVERY_INVALID_TOKEN, None, None)
+                    }
+                    Err(e) => (DELIVERY_FAILED, None, Some(e.to_string())),
+                };
+            if status == DELIVERY_SENT {
+                sent += 1;
+            }
+
+            // Un résumé vaut accusé pour chacune des notifications regroupées
+            let receipts: Vec<NewPushDelivery> = to_send
+                .iter()
+                .map(|(notification_uuid, _, _, _, _)| NewPushDelivery {
+                    notification_id: *notification_uuid,
+                    device_id: Some(device_uuid),
+                    user_id: recipient_uuid,
+                    provider: provider_name.clone(),
+                    status,
+                    provider_message_id: provider_message_id.clone(),
+                    error: error.clone(),
+                })
+                .collect();
+            diesel::insert_into(push_deliveries::table)
+                .values(&receipts)
+                .execute(conn)
+                .await?;
+        }
+    }
+    Ok(sent)
+}
+
+// Une seule notification pour un lot : le nombre d'événements et les premiers messages
+fn summary_message(batch: &[PendingPush]) -> PushMessage {
+    let mut body: Vec<String> = batch
+        .iter()
+        .take(SUMMARY_PREVIEW_LINES)
+        .map(|(_, _, _, message, _)| message.clone())
+        .collect();
+    if batch.len() > SUMMARY_PREVIEW_LINES {
+        body.push(format!("and {} more", batch.len() - SUMMARY_PREVIEW_LINES));
+    }
+
+    PushMessage {
+        title: format!("{} new notifications", batch.len()),
+        body: body.join("\n"),
+        data: BTreeMap::from([
+            ("kind".to_string(), KIND_PUSH_SUMMARY.to_string()),
+            ("count".to_string(), batch.len().to_string()),
+            (
+                "notification_ids".to_string(),
+                batch
+                    .iter()
+                    .map(|(notification_uuid, _, _, _, _)| notification_uuid.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        ]),
+    }
+}
+
+// Lance la tâche de fond : création des rappels puis envoi des notifications en attente
+pub fn spawn_push_job(pool: DbPool, read_only: Arc<ReadOnlyMode>, provider: Arc<dyn PushProvider>) {
+    actix_web::rt::spawn(async move {
+        let mut interval =
+            actix_web::rt::time::interval(std::time::Duration::from_secs(PUSH_JOB_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            let result = async {
+                let mut conn = pool.get().await?;
+                let created =
+                    create_due_reminders(&mut conn).await? + create_timer_nudges(&mut conn).await?;
+                let sent = dispatch_pending(&mut conn, provider.as_ref()).await?;
+                Ok::<_, ServiceError>((created, sent))
+            }
+            .await;
+            match result {
+                Ok((0, 0)) => {}
+                Ok((created, sent)) => log::info!(
+                    "Push job created {} reminder(s) and sent {} push notification(s)",
+                    created,
+                    sent
+                ),
+                Err(e) => log::error!("Push job failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,146 @@
+// OptiTask/backend-api/src/quotas.rs
+// Quotas par utilisateur des ressources créées (règles, clés, appareils...). Au-delà de
+// la limite, la création est refusée ; en approchant, les réponses de création portent
+// les en-têtes X-Quota-Remaining-<quota> / X-Quota-Limit-<quota> et un tableau `warnings`
+// pour que les clients préviennent l'utilisateur avant le refus.
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use serde::Serialize;
+
+pub const QUOTA_NEARLY_REACHED: &str = "QUOTA_NEARLY_REACHED";
+pub const QUOTA_REACHED: &str = "QUOTA_REACHED";
+// Avertissement quand il reste au plus ce pourcentage du quota
+const WARNING_THRESHOLD_PERCENT: i64 = 20;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Quota {
+    // Suffixe des en-têtes et identifiant dans `warnings`
+    pub name: &'static str,
+    // Désignation dans les messages
+    pub label: &'static str,
+    pub limit: i64,
+}
+
+pub const AUTOMATION_RULES: Quota = Quota {
+    name: "Automation-Rules",
+    label: "automation rules",
+    limit: 50,
+};
+pub const AGING_RULES: Quota = Quota {
+    name: "Aging-Rules",
+    label: "aging rules",
+    limit: 50,
+};
+pub const APP_PASSWORDS: Quota = Quota {
+    name: "App-Passwords",
+    label: "app passwords",
+    limit: 20,
+};
+pub const API_KEYS: Quota = Quota {
+    name: "Api-Keys",
+    label: "API keys",
+    limit: 20,
+};
+pub const DEVICES: Quota = Quota {
+    name: "Devices",
+    label: "devices",
+    limit: 50,
+};
+// Sur une heure glissante
+pub const FEEDBACK_PER_HOUR: Quota = Quota {
+    name: "Feedback-Hourly",
+    label: "feedback messages per hour",
+    limit: 5,
+};
+
+const ALL_QUOTAS: [Quota; 6] = [
+    AUTOMATION_RULES,
+    AGING_RULES,
+    APP_PASSWORDS,
+    API_KEYS,
+    DEVICES,
+    FEEDBACK_PER_HOUR,
+];
+
+fn remaining_header(quota: &Quota) -> String {
+    format!("X-Quota-Remaining-{}", quota.name)
+}
+
+fn limit_header(quota: &Quota) -> String {
+    format!("X-Quota-Limit-{}", quota.name)
+}
+
+// En-têtes lisibles par le frontend (CORS)
+pub fn exposed_headers() -> Vec<String> {
+    ALL_QUOTAS
+        .iter()
+        .flat_map(|quota| [remaining_header(quota), limit_header(quota)])
+        .collect()
+}
+
+#[derive(Serialize, Debug)]
+pub struct QuotaWarning {
+    pub code: &'static str,
+    pub quota: &'static str,
+    pub limit: i64,
+    pub remaining: i64,
+    pub message: String,
+}
+
+impl Quota {
+    pub fn is_exceeded(&self, used: i64) -> bool {
+        used >= self.limit
+    }
+
+    // `used` inclut la ressource qui vient d'être créée
+    pub fn warning(&self, used: i64) -> Option<QuotaWarning> {
+        let remaining = (self.limit - used).max(0);
+        if remaining * 100 > self.limit * WARNING_THRESHOLD_PERCENT {
+            return None;
+        }
+        let (code, message) = if remaining == 0 {
+            (
+                QUOTA_REACHED,
+                format!(
+                    "Limit of {} {} reached; further creations will be refused",
+                    self.limit, self.label
+                ),
+            )
+        } else {
+            (
+                QUOTA_NEARLY_REACHED,
+                format!("{} of {} {} remaining", remaining, self.limit, self.label),
+            )
+        };
+        Some(QuotaWarning {
+            code,
+            quota: self.name,
+            limit: self.limit,
+            remaining,
+            message,
+        })
+    }
+
+    // Réponse de création : en-têtes du quota et `warnings` ajouté au corps (objet JSON)
+    pub fn created_response<T: Serialize>(
+        &self,
+        status: StatusCode,
+        body: &T,
+        used: i64,
+    ) -> HttpResponse {
+        let mut json = serde_json::to_value(body).unwrap_or_default();
+        if let (Some(warning), Some(object)) = (self.warning(used), json.as_object_mut()) {
+            object.insert(
+                "warnings".to_string(),
+                serde_json::to_value([warning]).unwrap_or_default(),
+            );
+        }
+        HttpResponse::build(status)
+            .insert_header((
+                remaining_header(self),
+                (self.limit - used).max(0).to_string(),
+            ))
+            .insert_header((limit_header(self), self.limit.to_string()))
+            .json(json)
+    }
+}
@@ -0,0 +1,122 @@
+// OptiTask/backend-api/src/read_only_mode.rs
+// Mode lecture seule global, pour les fenêtres de maintenance de la base : les lectures
+// restent servies, toute requête d'écriture est refusée par 503 READ_ONLY_MODE. Le mode est
+// activé au démarrage par READ_ONLY_MODE (voir config.rs) ou à chaud via PUT
+// /admin/read-only. L'état est tenu en mémoire, sans accès à la base, et vaut pour
+// l'instance qui l'a reçu : un déploiement à plusieurs instances passe par la variable.
+use crate::error_handler::ServiceError;
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::{web, ResponseError};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+// Route de bascule, toujours accessible pour pouvoir lever le mode
+pub const READ_ONLY_ADMIN_PATH: &str = "/admin/read-only";
+
+// READ_ONLY_MODE / READ_ONLY_MESSAGE (voir config.rs)
+#[derive(Debug, Clone, Default)]
+pub struct ReadOnlyConfig {
+    pub enabled: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ReadOnlyStatus {
+    pub enabled: bool,
+    // Message affiché aux clients dont l'écriture est refusée
+    pub message: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    // Administrateur à l'origine du dernier changement (None : configuration au démarrage)
+    pub changed_by: Option<Uuid>,
+}
+
+pub struct ReadOnlyMode {
+    status: RwLock<ReadOnlyStatus>,
+}
+
+impl ReadOnlyMode {
+    pub fn new(config: &ReadOnlyConfig) -> ReadOnlyMode {
+        ReadOnlyMode {
+            status: RwLock::new(ReadOnlyStatus {
+                enabled: config.enabled,
+                message: config.message.clone(),
+                since: config.enabled.then(Utc::now),
+                changed_by: None,
+            }),
+        }
+    }
+
+    pub fn status(&self) -> ReadOnlyStatus {
+        self.status
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    // Consulté aussi par les tâches de fond, qui sautent leurs passages tant que le mode
+    // est actif : une maintenance de la base ne doit recevoir aucune écriture
+    pub fn is_enabled(&self) -> bool {
+        self.status
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .enabled
+    }
+
+    // Pour les écritures qui échappent au middleware (création paresseuse lors d'un GET)
+    pub fn ensure_writable(&self) -> Result<(), ServiceError> {
+        let status = self.status();
+        if status.enabled {
+            return Err(ServiceError::ReadOnlyMode(status.message));
+        }
+        Ok(())
+    }
+
+    // Active ou lève le mode ; `since` n'est remis à jour qu'au changement d'état
+    pub fn set(&self, enabled: bool, message: Option<String>, admin_uuid: Uuid) -> ReadOnlyStatus {
+        let mut status = self
+            .status
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if status.enabled != enabled {
+            status.since = enabled.then(Utc::now);
+        }
+        status.enabled = enabled;
+        status.message = message;
+        status.changed_by = Some(admin_uuid);
+        status.clone()
+    }
+}
+
+// Méthodes de lecture, y compris celles de WebDAV (PROPFIND, REPORT) utilisées par CalDAV
+pub fn is_read_method(method: &Method) -> bool {
+    matches!(
+        method.as_str(),
+        "GET" | "HEAD" | "OPTIONS" | "PROPFIND" | "REPORT"
+    )
+}
+
+// Middleware : refuse les écritures tant que le mode est actif
+pub async fn read_only_mode_guard(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let is_read = is_read_method(req.method());
+    if !is_read && req.path() != READ_ONLY_ADMIN_PATH {
+        if let Some(mode) = req.app_data::<web::Data<ReadOnlyMode>>() {
+            let status = mode.status();
+            if status.enabled {
+                let error = ServiceError::ReadOnlyMode(status.message);
+                return Ok(req.into_response(error.error_response()));
+            }
+        }
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_boxed_body)
+}
@@ -0,0 +1,71 @@
+// OptiTask/backend-api/src/recents.rs
+// Éléments récemment consultés : chaque consultation ajoute 1 au score de l'élément,
+// score qui perd la moitié de sa valeur tous les trois jours. Un élément souvent ouvert
+// reste en tête plus longtemps qu'un élément ouvert une seule fois.
+use crate::error_handler::ServiceError;
+use crate::schema::recent_views;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::Double;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+const SCORE_HALF_LIFE_SECS: f64 = 3.0 * 24.0 * 3600.0;
+// Éléments conservés par utilisateur ; les plus anciens sont oubliés
+pub const MAX_RECENT_VIEWS: i64 = 100;
+
+// Score à l'instant `now` d'un score enregistré à `recorded_at`
+pub fn decayed_score(score: f64, recorded_at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let elapsed_secs = (now - recorded_at).num_seconds().max(0) as f64;
+    score * 0.5_f64.powf(elapsed_secs / SCORE_HALF_LIFE_SECS)
+}
+
+// Enregistre la consultation (une ligne par élément) puis oublie les plus anciens
+pub async fn record_view(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    entity_type: &str,
+    entity_uuid: Uuid,
+) -> Result<(), ServiceError> {
+    diesel::insert_into(recent_views::table)
+        .values((
+            recent_views::user_id.eq(user_uuid),
+            recent_views::entity_type.eq(entity_type),
+            recent_views::entity_id.eq(entity_uuid),
+        ))
+        .on_conflict((
+            recent_views::user_id,
+            recent_views::entity_type,
+            recent_views::entity_id,
+        ))
+        .do_update()
+        .set((
+            recent_views::view_count.eq(recent_views::view_count + 1),
+            recent_views::score.eq(diesel::dsl::sql::<Double>(&format!(
+                "recent_views.score * power(0.5, EXTRACT(EPOCH FROM (NOW() - recent_views.last_viewed_at)) / {}) + 1",
+                SCORE_HALF_LIFE_SECS
+            ))),
+            recent_views::last_viewed_at.eq(Utc::now()),
+        ))
+        .execute(conn)
+        .await?;
+
+    let stale_cutoff = recent_views::table
+        .filter(recent_views::user_id.eq(user_uuid))
+        .order(recent_views::last_viewed_at.desc())
+        .offset(MAX_RECENT_VIEWS)
+        .select(recent_views::last_viewed_at)
+        .first::<DateTime<Utc>>(conn)
+        .await
+        .optional()?;
+    if let Some(cutoff) = stale_cutoff {
+        diesel::delete(
+            recent_views::table
+                .filter(recent_views::user_id.eq(user_uuid))
+                .filter(recent_views::last_viewed_at.le(cutoff)),
+        )
+        .execute(conn)
+        .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,159 @@
+// OptiTask/backend-api/src/reports.rs
+use crate::error_handler::ServiceError;
+use crate::i18n;
+use minijinja::{context, Environment, Value};
+use serde::Serialize;
+
+const PROJECT_REPORT_MARKDOWN: &str = r#"# {{ project_name }} — {{ t.title }}
+
+_{{ t.period }}: {{ period_start }} → {{ period_end }} · {{ t.generated }} {{ generated_at }}_
+
+## {{ t.open_tasks }} ({{ open_tasks | length }})
+{% for task in open_tasks %}
+- [ ] {{ task.title }} ({{ task.status }}{% if task.due_date %}, {{ t.due }} {{ task.due_date }}{% endif %})
+{%- else %}
+_{{ t.no_open_tasks }}_
+{%- endfor %}
+
+## {{ t.completed }} ({{ completed_tasks | length }})
+{% for task in completed_tasks %}
+- [x] {{ task.title }}
+{%- else %}
+_{{ t.nothing_completed }}_
+{%- endfor %}
+
+## {{ t.time_per_task }}
+
+| {{ t.task }} | {{ t.time }} |
+|------|------|
+{% for task in time_by_task -%}
+| {{ task.title }} | {{ task.tracked }} |
+{% endfor -%}
+| **{{ t.total }}** | **{{ total_tracked }}** |
+"#;
+
+const PROJECT_REPORT_HTML: &str = r#"<!DOCTYPE html>
+<html lang="{{ lang }}">
+<head>
+<meta charset="utf-8">
+<title>{{ project_name }} — {{ t.title }}</title>
+</head>
+<body>
+<h1>{{ project_name }} — {{ t.title }}</h1>
+<p><em>{{ t.period }}: {{ period_start }} → {{ period_end }} · {{ t.generated }} {{ generated_at }}</em></p>
+
+<h2>{{ t.open_tasks }} ({{ open_tasks | length }})</h2>
+{% if open_tasks %}<ul>
+{% for task in open_tasks %}  <li>{{ task.title }} ({{ task.status }}{% if task.due_date %}, {{ t.due }} {{ task.due_date }}{% endif %})</li>
+{% endfor %}</ul>{% else %}<p>{{ t.no_open_tasks }}</p>{% endif %}
+
+<h2>{{ t.completed }} ({{ completed_tasks | length }})</h2>
+{% if completed_tasks %}<ul>
+{% for task in completed_tasks %}  <li>{{ task.title }}</li>
+{% endfor %}</ul>{% else %}<p>{{ t.nothing_completed }}</p>{% endif %}
+
+<h2>{{ t.time_per_task }}</h2>
+<table>
+<thead><tr><th>{{ t.task }}</th><th>{{ t.time }}</th></tr></thead>
+<tbody>
+{% for task in time_by_task %}<tr><td>{{ task.title }}</td><td>{{ task.tracked }}</td></tr>
+{% endfor %}</tbody>
+<tfoot><tr><th>{{ t.total }}</th><th>{{ total_tracked }}</th></tr></tfoot>
+</table>
+</body>
+</html>
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn parse(raw: Option<&str>) -> Result<ReportFormat, ServiceError> {
+        match raw {
+            None | Some("markdown") | Some("md") => Ok(ReportFormat::Markdown),
+            Some("html") => Ok(ReportFormat::Html),
+            Some(other) => Err(ServiceError::BadRequest(format!(
+                "Invalid report format: {}. Supported: markdown, html",
+                other
+            ))),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "text/markdown; charset=utf-8",
+            ReportFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    // L'extension du nom de template active l'auto-échappement HTML de minijinja
+    fn template_name(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "project_report.md",
+            ReportFormat::Html => "project_report.html",
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ReportTask {
+    pub title: String,
+    pub status: String,
+    pub due_date: Option<String>,
+    pub tracked_seconds: i64,
+    pub tracked: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ProjectReport {
+    pub project_name: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub generated_at: String,
+    pub open_tasks: Vec<ReportTask>,
+    pub completed_tasks: Vec<ReportTask>,
+    pub time_by_task: Vec<ReportTask>,
+    pub total_tracked: String,
+}
+
+// Formate une durée en secondes sous la forme "2h 05m"
+pub fn format_duration(total_seconds: i64) -> String {
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+pub fn render_project_report(
+    report: &ProjectReport,
+    format: ReportFormat,
+) -> Result<String, ServiceError> {
+    let mut env = Environment::new();
+    env.add_template("project_report.md", PROJECT_REPORT_MARKDOWN)
+        .and_then(|_| env.add_template("project_report.html", PROJECT_REPORT_HTML))
+        .map_err(|e| {
+            log::error!("Invalid report template: {}", e);
+            ServiceError::InternalServerError("Invalid report template".to_string())
+        })?;
+
+    // Intitulés dans la langue de la requête (clés "report." du catalogue)
+    let locale = i18n::current_locale();
+    let ctx = context! {
+        lang => locale.code(),
+        t => i18n::messages_with_prefix(locale, "report."),
+        ..Value::from_serialize(report)
+    };
+
+    env.get_template(format.template_name())
+        .and_then(|template| template.render(ctx))
+        .map_err(|e| {
+            log::error!("Failed to render project report: {}", e);
+            ServiceError::InternalServerError("Failed to render report".to_string())
+        })
+}
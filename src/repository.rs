@@ -0,0 +1,101 @@
+// OptiTask/backend-api/src/repository.rs
+use crate::db::{DbConnection, DbPool};
+use crate::error_handler::ServiceError;
+use crate::ids::TaskId;
+use crate::models::{Label, TaskApiResponse};
+use crate::schema::{labels, task_labels, time_entries};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+// Une seule requête à la fois réserve une paire : sans cela, des requêtes concurrentes
+// pourraient chacune tenir une connexion en attendant la seconde et épuiser le pool
+static PAIR_CHECKOUT: Mutex<()> = Mutex::const_new(());
+
+// Deux connexions du pool pour lancer des lectures indépendantes avec tokio::try_join!.
+// Réservé aux lectures : les deux connexions ne partagent ni transaction ni snapshot.
+pub async fn get_connection_pair(
+    pool: &DbPool,
+) -> Result<(DbConnection<'_>, DbConnection<'_>), ServiceError> {
+    let _checkout = PAIR_CHECKOUT.lock().await;
+    let (first_conn, second_conn) = tokio::try_join!(pool.get(), pool.get())?;
+    Ok((first_conn, second_conn))
+}
+
+// Labels d'une tâche, sans contrôle d'accès : l'appelant vérifie la tâche
+// (éventuellement en parallèle) et n'utilise le résultat que si elle est accessible
+pub async fn load_task_labels(
+    conn: &mut AsyncPgConnection,
+    task_uuid: TaskId,
+) -> Result<Vec<Label>, ServiceError> {
+    task_labels::table
+        .inner_join(labels::table.on(labels::id.eq(task_labels::label_id)))
+        .filter(task_labels::task_id.eq(task_uuid))
+        .order(labels::name.asc())
+        .select(Label::as_select())
+        .load::<Label>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Peuple `labels` pour un lot de tâches en une seule requête
+pub async fn attach_labels(
+    conn: &mut AsyncPgConnection,
+    task_responses: &mut [TaskApiResponse],
+) -> Result<(), ServiceError> {
+    if task_responses.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<TaskId> = task_responses.iter().map(|t| t.id).collect();
+    let label_rows = task_labels::table
+        .inner_join(labels::table.on(labels::id.eq(task_labels::label_id)))
+        .filter(task_labels::task_id.eq_any(&task_ids))
+        .order(labels::name.asc())
+        .select((task_labels::task_id, Label::as_select()))
+        .load::<(TaskId, Label)>(conn)
+        .await?;
+
+    let mut labels_by_task: HashMap<TaskId, Vec<Label>> = HashMap::new();
+    for (task_uuid, label) in label_rows {
+        labels_by_task.entry(task_uuid).or_default().push(label);
+    }
+
+    for task_response in task_responses.iter_mut() {
+        task_response.labels = labels_by_task.remove(&task_response.id).unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+// Peuple `pomodoro_count` (pomodoros terminés de l'utilisateur) pour un lot de tâches
+pub async fn attach_pomodoro_counts(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    task_responses: &mut [TaskApiResponse],
+) -> Result<(), ServiceError> {
+    if task_responses.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<TaskId> = task_responses.iter().map(|t| t.id).collect();
+    let mut counts_by_task: HashMap<TaskId, i64> = time_entries::table
+        .filter(time_entries::user_id.eq(user_uuid))
+        .filter(time_entries::task_id.eq_any(&task_ids))
+        .filter(time_entries::is_pomodoro_session.eq(true))
+        .filter(time_entries::end_time.is_not_null())
+        .group_by(time_entries::task_id)
+        .select((time_entries::task_id, diesel::dsl::count_star()))
+        .load::<(TaskId, i64)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    for task_response in task_responses.iter_mut() {
+        task_response.pomodoro_count = Some(counts_by_task.remove(&task_response.id).unwrap_or(0));
+    }
+
+    Ok(())
+}
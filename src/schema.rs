@@ -1,5 +1,339 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    account_deletion_requests (user_id) {
+        user_id -> Uuid,
+        requested_at -> Timestamptz,
+        scheduled_for -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    ai_summaries (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        period_start -> Date,
+        period_end -> Date,
+        summary -> Text,
+        provider -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    analytics_snapshots (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        period_start -> Date,
+        period_end -> Date,
+        metrics -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    announcement_acks (announcement_id, user_id) {
+        announcement_id -> Uuid,
+        user_id -> Uuid,
+        acked_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    announcements (id) {
+        id -> Uuid,
+        title -> Text,
+        body -> Text,
+        #[max_length = 16]
+        kind -> Varchar,
+        published_at -> Nullable<Timestamptz>,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    api_keys (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        name -> Text,
+        key_prefix -> Text,
+        key_hash -> Text,
+        last_used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    app_passwords (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        name -> Text,
+        password_hash -> Text,
+        last_used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    automation_rules (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        name -> Text,
+        trigger -> Text,
+        conditions -> Jsonb,
+        actions -> Jsonb,
+        is_active -> Bool,
+        last_triggered_at -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    backup_configs (user_id) {
+        user_id -> Uuid,
+        target_type -> Text,
+        endpoint_url -> Text,
+        bucket -> Nullable<Text>,
+        region -> Nullable<Text>,
+        path_prefix -> Text,
+        username -> Text,
+        secret -> Text,
+        encryption_key -> Text,
+        enabled -> Bool,
+        last_attempt_at -> Nullable<Timestamptz>,
+        last_success_at -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        last_object_key -> Nullable<Text>,
+        last_size_bytes -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    calendar_event_mappings (integration_id, task_id) {
+        integration_id -> Uuid,
+        task_id -> Uuid,
+        external_event_id -> Text,
+        synced_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    calendar_integrations (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        provider -> Text,
+        access_token -> Text,
+        refresh_token -> Nullable<Text>,
+        token_expires_at -> Timestamptz,
+        calendar_id -> Text,
+        last_synced_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    calendar_oauth_states (state) {
+        state -> Text,
+        user_id -> Uuid,
+        expires_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    calendar_project_links (user_id, project_id) {
+        user_id -> Uuid,
+        project_id -> Uuid,
+        keyword -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    client_preferences (user_id, namespace) {
+        user_id -> Uuid,
+        #[max_length = 64]
+        namespace -> Varchar,
+        data -> Jsonb,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    calendar_suggestions (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        project_id -> Uuid,
+        external_event_id -> Text,
+        title -> Text,
+        start_time -> Timestamptz,
+        end_time -> Timestamptz,
+        status -> Text,
+        time_entry_id -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    confirmation_tokens (token) {
+        token -> Text,
+        user_id -> Uuid,
+        action -> Text,
+        target_id -> Nullable<Uuid>,
+        expires_at -> Timestamptz,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    custom_field_definitions (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        project_id -> Uuid,
+        name -> Text,
+        field_type -> Text,
+        options -> Jsonb,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    daily_tracked_time (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        project_id -> Nullable<Uuid>,
+        day -> Date,
+        tracked_seconds -> Int8,
+    }
+}
+
+diesel::table! {
+    daily_tracked_time_refresh (user_id, day) {
+        user_id -> Uuid,
+        day -> Date,
+        queued_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    devices (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        name -> Text,
+        #[max_length = 16]
+        platform -> Varchar,
+        push_token -> Nullable<Text>,
+        sync_cursor -> Nullable<Text>,
+        last_synced_at -> Nullable<Timestamptz>,
+        last_seen_at -> Timestamptz,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    experiment_assignments (experiment_key, user_id) {
+        #[max_length = 64]
+        experiment_key -> Varchar,
+        user_id -> Uuid,
+        #[max_length = 64]
+        variant -> Varchar,
+        assigned_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    experiment_events (id) {
+        id -> Uuid,
+        #[max_length = 64]
+        experiment_key -> Varchar,
+        user_id -> Uuid,
+        #[max_length = 64]
+        variant -> Varchar,
+        #[max_length = 64]
+        event -> Varchar,
+        value -> Nullable<Float8>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    experiments (key) {
+        #[max_length = 64]
+        key -> Varchar,
+        description -> Nullable<Text>,
+        variants -> Jsonb,
+        is_active -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    feature_flag_overrides (flag_key, user_id) {
+        #[max_length = 64]
+        flag_key -> Varchar,
+        user_id -> Uuid,
+        enabled -> Bool,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    feature_flags (key) {
+        #[max_length = 64]
+        key -> Varchar,
+        description -> Nullable<Text>,
+        enabled -> Bool,
+        rollout_percentage -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    feedback (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 20]
+        category -> Varchar,
+        message -> Text,
+        #[max_length = 50]
+        app_version -> Nullable<Varchar>,
+        diagnostics -> Nullable<Jsonb>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    focus_sessions (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        label_id -> Nullable<Uuid>,
+        project_id -> Nullable<Uuid>,
+        started_at -> Timestamptz,
+        ended_at -> Nullable<Timestamptz>,
+        summary -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    inbound_email_addresses (user_id) {
+        user_id -> Uuid,
+        token -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     labels (id) {
         id -> Uuid,
@@ -8,6 +342,42 @@ diesel::table! {
         color -> Nullable<Text>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        #[max_length = 64]
+        icon -> Nullable<Varchar>,
+        sla_hours -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    maintenance_jobs (id) {
+        id -> Uuid,
+        #[max_length = 64]
+        kind -> Varchar,
+        #[max_length = 16]
+        status -> Varchar,
+        requested_by -> Uuid,
+        result -> Nullable<Jsonb>,
+        error -> Nullable<Text>,
+        created_at -> Timestamptz,
+        started_at -> Nullable<Timestamptz>,
+        finished_at -> Nullable<Timestamptz>,
+        payload -> Nullable<Jsonb>,
+        progress -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        task_id -> Nullable<Uuid>,
+        actor_id -> Nullable<Uuid>,
+        kind -> Text,
+        message -> Text,
+        payload -> Jsonb,
+        read_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        pushed_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -19,6 +389,116 @@ diesel::table! {
         color -> Nullable<Text>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        workspace_id -> Nullable<Uuid>,
+        metadata -> Jsonb,
+        #[max_length = 64]
+        icon -> Nullable<Varchar>,
+        wip_limits -> Jsonb,
+        sla_hours -> Nullable<Int4>,
+        #[max_length = 3]
+        currency -> Nullable<Varchar>,
+        open_task_count -> Int4,
+        completed_task_count -> Int4,
+        theme -> Jsonb,
+        archived_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    pomodoro_interruptions (id) {
+        id -> Uuid,
+        time_entry_id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 20]
+        reason -> Varchar,
+        note -> Nullable<Text>,
+        interrupted_at -> Timestamptz,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    public_badge_tokens (user_id) {
+        user_id -> Uuid,
+        token -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    push_deliveries (id) {
+        id -> Uuid,
+        notification_id -> Uuid,
+        device_id -> Nullable<Uuid>,
+        user_id -> Uuid,
+        provider -> Text,
+        #[max_length = 16]
+        status -> Varchar,
+        provider_message_id -> Nullable<Text>,
+        error -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    recent_views (user_id, entity_type, entity_id) {
+        user_id -> Uuid,
+        entity_type -> Text,
+        entity_id -> Uuid,
+        view_count -> Int4,
+        score -> Float8,
+        last_viewed_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    task_aging_rule_hits (rule_id, task_id) {
+        rule_id -> Uuid,
+        task_id -> Uuid,
+        status_entered_at -> Timestamptz,
+        applied_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    task_aging_rules (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        project_id -> Nullable<Uuid>,
+        name -> Text,
+        status -> Text,
+        max_days -> Int4,
+        add_label -> Nullable<Text>,
+        notify -> Bool,
+        is_active -> Bool,
+        last_run_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    task_custom_values (task_id, field_id) {
+        task_id -> Uuid,
+        field_id -> Uuid,
+        value -> Text,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    task_external_links (id) {
+        id -> Uuid,
+        task_id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 20]
+        kind -> Varchar,
+        reference -> Text,
+        url -> Nullable<Text>,
+        title -> Nullable<Text>,
+        favicon_url -> Nullable<Text>,
+        metadata_fetched_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
     }
 }
 
@@ -29,6 +509,46 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    task_links (source_task_id, target_task_id) {
+        source_task_id -> Uuid,
+        target_task_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    task_sla_breaches (task_id, target_type, target_id) {
+        task_id -> Uuid,
+        user_id -> Uuid,
+        target_type -> Text,
+        target_id -> Uuid,
+        target_hours -> Int4,
+        due_at -> Timestamptz,
+        breached_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    task_status_history (id) {
+        id -> Uuid,
+        task_id -> Uuid,
+        user_id -> Uuid,
+        project_id -> Nullable<Uuid>,
+        old_status -> Nullable<Text>,
+        new_status -> Text,
+        changed_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    task_watchers (task_id, user_id) {
+        task_id -> Uuid,
+        user_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     tasks (id) {
         id -> Uuid,
@@ -41,6 +561,18 @@ diesel::table! {
         task_order -> Nullable<Int4>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        source -> Nullable<Jsonb>,
+        context -> Nullable<Jsonb>,
+        metadata -> Jsonb,
+        scheduled_start -> Nullable<Timestamptz>,
+        scheduled_end -> Nullable<Timestamptz>,
+        is_pinned -> Bool,
+        snoozed_until -> Nullable<Timestamptz>,
+        follow_up -> Nullable<Jsonb>,
+        #[max_length = 16]
+        stage -> Varchar,
+        time_budget_seconds -> Nullable<Int4>,
+        time_budget_alerted_pct -> Int2,
     }
 }
 
@@ -55,31 +587,171 @@ diesel::table! {
         is_pomodoro_session -> Bool,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        is_break -> Bool,
+        clock_skew_seconds -> Nullable<Int4>,
+        suspect_clock -> Bool,
+        last_heartbeat_at -> Nullable<Timestamptz>,
+        auto_stopped -> Bool,
+        focus_session_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    timesheets (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        week_start -> Date,
+        status -> Text,
+        total_duration_seconds -> Int8,
+        submitted_at -> Nullable<Timestamptz>,
+        reviewed_by -> Nullable<Uuid>,
+        reviewed_at -> Nullable<Timestamptz>,
+        review_note -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
 diesel::table! {
-    users (id) {
-        id -> Int4,
-        username -> Varchar,
-        seed -> Varchar,
-        channel_address -> Varchar,
-        last_message -> Varchar,
-        created_at -> Timestamp,
-        updated_at -> Timestamp,
+    user_onboarding (user_id) {
+        user_id -> Uuid,
+        project_id -> Nullable<Uuid>,
+        seeded_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    user_settings (user_id) {
+        user_id -> Uuid,
+        notify_watched_status_changes -> Bool,
+        notify_watched_comments -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        #[max_length = 10]
+        week_start -> Varchar,
+        time_locked_before -> Nullable<Date>,
+        push_due_reminders -> Bool,
+        push_timer_nudges -> Bool,
+        push_mentions -> Bool,
+        quiet_hours_start -> Nullable<Time>,
+        quiet_hours_end -> Nullable<Time>,
+        utc_offset_minutes -> Int4,
+        push_batch_minutes -> Int4,
+        nudge_planned_tasks -> Bool,
+        nudge_no_time_tracked -> Bool,
+        nudge_check_time -> Time,
+        default_project_id -> Nullable<Uuid>,
+        leaderboard_opt_out -> Bool,
+        timer_auto_stop_time -> Nullable<Time>,
+        #[max_length = 10]
+        timer_auto_stop_mode -> Varchar,
+    }
+}
+
+diesel::table! {
+    workspace_members (workspace_id, user_id) {
+        workspace_id -> Uuid,
+        user_id -> Uuid,
+        role -> Text,
+        joined_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    workspaces (id) {
+        id -> Uuid,
+        owner_id -> Uuid,
+        name -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        time_locked_before -> Nullable<Date>,
     }
 }
 
+diesel::joinable!(announcement_acks -> announcements (announcement_id));
+diesel::joinable!(experiment_assignments -> experiments (experiment_key));
+diesel::joinable!(experiment_events -> experiments (experiment_key));
+diesel::joinable!(feature_flag_overrides -> feature_flags (flag_key));
+diesel::joinable!(calendar_event_mappings -> calendar_integrations (integration_id));
+diesel::joinable!(calendar_project_links -> projects (project_id));
+diesel::joinable!(calendar_suggestions -> projects (project_id));
+diesel::joinable!(calendar_suggestions -> time_entries (time_entry_id));
+diesel::joinable!(custom_field_definitions -> projects (project_id));
+diesel::joinable!(focus_sessions -> labels (label_id));
+diesel::joinable!(focus_sessions -> projects (project_id));
+diesel::joinable!(notifications -> tasks (task_id));
+diesel::joinable!(pomodoro_interruptions -> time_entries (time_entry_id));
+diesel::joinable!(projects -> workspaces (workspace_id));
+diesel::joinable!(push_deliveries -> devices (device_id));
+diesel::joinable!(push_deliveries -> notifications (notification_id));
+diesel::joinable!(task_aging_rule_hits -> task_aging_rules (rule_id));
+diesel::joinable!(task_aging_rule_hits -> tasks (task_id));
+diesel::joinable!(task_aging_rules -> projects (project_id));
+diesel::joinable!(task_custom_values -> custom_field_definitions (field_id));
+diesel::joinable!(task_custom_values -> tasks (task_id));
+diesel::joinable!(task_external_links -> tasks (task_id));
 diesel::joinable!(task_labels -> labels (label_id));
 diesel::joinable!(task_labels -> tasks (task_id));
+diesel::joinable!(task_sla_breaches -> tasks (task_id));
+diesel::joinable!(task_status_history -> tasks (task_id));
+diesel::joinable!(task_watchers -> tasks (task_id));
 diesel::joinable!(tasks -> projects (project_id));
+diesel::joinable!(time_entries -> focus_sessions (focus_session_id));
 diesel::joinable!(time_entries -> tasks (task_id));
+diesel::joinable!(user_onboarding -> projects (project_id));
+diesel::joinable!(workspace_members -> workspaces (workspace_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    account_deletion_requests,
+    ai_summaries,
+    analytics_snapshots,
+    announcement_acks,
+    announcements,
+    api_keys,
+    app_passwords,
+    automation_rules,
+    backup_configs,
+    calendar_event_mappings,
+    calendar_integrations,
+    calendar_oauth_states,
+    calendar_project_links,
+    calendar_suggestions,
+    client_preferences,
+    confirmation_tokens,
+    custom_field_definitions,
+    daily_tracked_time,
+    daily_tracked_time_refresh,
+    devices,
+    experiment_assignments,
+    experiment_events,
+    experiments,
+    feature_flag_overrides,
+    feature_flags,
+    feedback,
+    focus_sessions,
+    inbound_email_addresses,
     labels,
+    maintenance_jobs,
+    notifications,
+    pomodoro_interruptions,
     projects,
+    public_badge_tokens,
+    push_deliveries,
+    recent_views,
+    task_aging_rule_hits,
+    task_aging_rules,
+    task_custom_values,
+    task_external_links,
     task_labels,
+    task_links,
+    task_sla_breaches,
+    task_status_history,
+    task_watchers,
     tasks,
     time_entries,
-    users,
+    timesheets,
+    user_onboarding,
+    user_settings,
+    workspace_members,
+    workspaces,
 );
@@ -0,0 +1,389 @@
+// OptiTask/backend-api/src/schema_check.rs
+// Vérification au démarrage : le schéma réel de la base doit correspondre à schema.rs tel
+// qu'il a été compilé. Chaque table et colonne déclarée doit exister, avec un type
+// compatible, et une colonne non nullable côté Diesel ne peut pas être nullable en base.
+// Sans cette vérification, un déploiement dont les migrations n'ont pas été appliquées
+// n'échoue qu'à la première requête concernée, avec une erreur Diesel peu parlante.
+// Les tables et colonnes présentes en base mais absentes de schema.rs sont ignorées.
+// SCHEMA_CHECK (voir config.rs) : strict (arrêt du démarrage), warn (journalisé) ou off.
+use crate::db::DbPool;
+use diesel::sql_types::{Bool, Text};
+use diesel::{sql_query, QueryResult, QueryableByName};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+// schema.rs tel qu'il a été compilé dans le binaire
+const COMPILED_SCHEMA: &str = include_str!("schema.rs");
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaCheckMode {
+    Strict,
+    Warn,
+    Off,
+}
+
+impl FromStr for SchemaCheckMode {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<SchemaCheckMode, ()> {
+        match raw.to_ascii_lowercase().as_str() {
+            "strict" => Ok(SchemaCheckMode::Strict),
+            "warn" => Ok(SchemaCheckMode::Warn),
+            "off" => Ok(SchemaCheckMode::Off),
+            _ => Err(()),
+        }
+    }
+}
+
+// Colonne déclarée dans schema.rs
+#[derive(Debug, PartialEq)]
+struct ExpectedColumn {
+    table: String,
+    column: String,
+    // Type Diesel sans Nullable<> (ex: "Int4", "Array<Text>")
+    sql_type: String,
+    nullable: bool,
+}
+
+#[derive(QueryableByName)]
+struct LiveColumn {
+    #[diesel(sql_type = Text)]
+    table_name: String,
+    #[diesel(sql_type = Text)]
+    column_name: String,
+    #[diesel(sql_type = Text)]
+    udt_name: String,
+    #[diesel(sql_type = Bool)]
+    is_nullable: bool,
+}
+
+#[derive(Debug)]
+pub enum SchemaDrift {
+    MissingTable(String),
+    MissingColumn {
+        table: String,
+        column: String,
+        expected: String,
+    },
+    TypeMismatch {
+        table: String,
+        column: String,
+        expected: String,
+        actual: String,
+    },
+    // NULL en base alors que schema.rs déclare la colonne non nullable
+    UnexpectedNullable {
+        table: String,
+        column: String,
+    },
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaDrift::MissingTable(table) => write!(f, "table {} is missing", table),
+            SchemaDrift::MissingColumn {
+                table,
+                column,
+                expected,
+            } => write!(
+                f,
+                "{}.{} is missing (schema.rs expects {})",
+                table, column, expected
+            ),
+            SchemaDrift::TypeMismatch {
+                table,
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}.{} has type {} in the database, schema.rs expects {}",
+                table, column, actual, expected
+            ),
+            SchemaDrift::UnexpectedNullable { table, column } => write!(
+                f,
+                "{}.{} is nullable in the database but NOT NULL in schema.rs",
+                table, column
+            ),
+        }
+    }
+}
+
+// Colonnes des blocs `diesel::table!` de schema.rs
+fn parse_schema(source: &str) -> Vec<ExpectedColumn> {
+    let mut columns = Vec::new();
+    let mut current_table: Option<String> = None;
+    for line in source.lines().map(str::trim) {
+        if line.starts_with('#') || line.starts_with("//") || line.is_empty() {
+            continue;
+        }
+        if let Some(table) = &current_table {
+            if line == "}" {
+                current_table = None;
+            } else if let Some((column, sql_type)) = line.split_once("->") {
+                let sql_type = sql_type.trim().trim_end_matches(',');
+                let (sql_type, nullable) = match sql_type
+                    .strip_prefix("Nullable<")
+                    .and_then(|inner| inner.strip_suffix('>'))
+                {
+                    Some(inner) => (inner, true),
+                    None => (sql_type, false),
+                };
+                columns.push(ExpectedColumn {
+                    table: table.clone(),
+                    column: column.trim().to_string(),
+                    sql_type: sql_type.to_string(),
+                    nullable,
+                });
+            }
+        } else if line.ends_with('{') && line.contains('(') && !line.starts_with("diesel::") {
+            // "table_name (clé) {"
+            current_table = line
+                .split_whitespace()
+                .next()
+                .map(|table| table.trim_end_matches('(').to_string());
+        }
+    }
+    columns
+}
+
+// Types PostgreSQL (udt_name) acceptés pour un type Diesel ; None si le type n'est pas
+// connu, auquel cas seule la présence de la colonne est vérifiée
+fn compatible_udt_names(sql_type: &str) -> Option<Vec<String>> {
+    if let Some(element) = sql_type
+        .strip_prefix("Array<")
+        .and_then(|inner| inner.strip_suffix('>'))
+    {
+        return compatible_udt_names(element)
+            .map(|names| names.into_iter().map(|name| format!("_{}", name)).collect());
+    }
+    let names: &[&str] = match sql_type {
+        // Diesel lit indifféremment les différents types texte
+        "Text" | "Varchar" => &["text", "varchar", "bpchar", "citext"],
+        "Uuid" => &["uuid"],
+        "Bool" => &["bool"],
+        "Int2" | "SmallInt" => &["int2"],
+        "Int4" | "Integer" => &["int4"],
+        "Int8" | "BigInt" => &["int8"],
+        "Float4" => &["float4"],
+        "Float8" | "Double" => &["float8"],
+        "Numeric" => &["numeric"],
+        "Date" => &["date"],
+        "Time" => &["time"],
+        "Timestamp" => &["timestamp"],
+        "Timestamptz" => &["timestamptz"],
+        "Interval" => &["interval"],
+        "Jsonb" => &["jsonb"],
+        "Json" => &["json"],
+        "Bytea" | "Binary" => &["bytea"],
+        _ => return None,
+    };
+    Some(names.iter().map(|name| name.to_string()).collect())
+}
+
+fn compare(expected: &[ExpectedColumn], live: &[LiveColumn]) -> Vec<SchemaDrift> {
+    let live_by_column: HashMap<(&str, &str), &LiveColumn> = live
+        .iter()
+        .map(|column| {
+            (
+                (column.table_name.as_str(), column.column_name.as_str()),
+                column,
+            )
+        })
+        .collect();
+    let live_tables: HashSet<&str> = live
+        .iter()
+        .map(|column| column.table_name.as_str())
+        .collect();
+
+    let mut drifts = Vec::new();
+    let mut reported_tables = HashSet::new();
+    for column in expected {
+        if !live_tables.contains(column.table.as_str()) {
+            if reported_tables.insert(column.table.as_str()) {
+                drifts.push(SchemaDrift::MissingTable(column.table.clone()));
+            }
+            continue;
+        }
+        let Some(live_column) =
+            live_by_column.get(&(column.table.as_str(), column.column.as_str()))
+        else {
+            drifts.push(SchemaDrift::MissingColumn {
+                table: column.table.clone(),
+                column: column.column.clone(),
+                expected: column.sql_type.clone(),
+            });
+            continue;
+        };
+        if let Some(compatible) = compatible_udt_names(&column.sql_type) {
+            if !compatible.contains(&live_column.udt_name) {
+                drifts.push(SchemaDrift::TypeMismatch {
+                    table: column.table.clone(),
+                    column: column.column.clone(),
+                    expected: column.sql_type.clone(),
+                    actual: live_column.udt_name.clone(),
+                });
+            }
+        }
+        if live_column.is_nullable && !column.nullable {
+            drifts.push(SchemaDrift::UnexpectedNullable {
+                table: column.table.clone(),
+                column: column.column.clone(),
+            });
+        }
+    }
+    drifts
+}
+
+async fn load_live_columns(conn: &mut AsyncPgConnection) -> QueryResult<Vec<LiveColumn>> {
+    sql_query(
+        "SELECT table_name::text AS table_name, column_name::text AS column_name, \
+         udt_name::text AS udt_name, is_nullable = 'YES' AS is_nullable \
+         FROM information_schema.columns WHERE table_schema = current_schema()",
+    )
+    .load::<LiveColumn>(conn)
+    .await
+}
+
+// Vérifie le schéma selon `mode` ; en mode strict, une dérive arrête le démarrage
+pub async fn verify_schema(pool: &DbPool, mode: SchemaCheckMode) -> std::io::Result<()> {
+    if mode == SchemaCheckMode::Off {
+        log::info!("Schema check disabled (SCHEMA_CHECK=off)");
+        return Ok(());
+    }
+
+    let expected = parse_schema(COMPILED_SCHEMA);
+    let live = {
+        let mut conn = pool.get().await.map_err(std::io::Error::other)?;
+        load_live_columns(&mut conn)
+            .await
+            .map_err(std::io::Error::other)?
+    };
+
+    let drifts = compare(&expected, &live);
+    if drifts.is_empty() {
+        log::info!(
+            "Schema check passed: {} columns match schema.rs",
+            expected.len()
+        );
+        return Ok(());
+    }
+
+    for drift in &drifts {
+        log::error!("Schema drift: {}", drift);
+    }
+    let summary = format!(
+        "Database schema does not match schema.rs ({} difference(s)); run the pending migrations or set SCHEMA_CHECK=warn",
+        drifts.len()
+    );
+    match mode {
+        SchemaCheckMode::Strict => Err(std::io::Error::other(summary)),
+        _ => {
+            log::warn!("{}", summary);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_async::{AsyncConnection, SimpleAsyncConnection};
+    use std::fs;
+    use std::path::Path;
+
+    // (dossier, up.sql) de chaque migration, dans l'ordre d'application
+    fn migration_scripts() -> Vec<(String, String)> {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        let mut scripts: Vec<(String, String)> = fs::read_dir(directory)
+            .expect("migrations directory is readable")
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let sql = fs::read_to_string(entry.path().join("up.sql")).ok()?;
+                Some((entry.file_name().to_string_lossy().into_owned(), sql))
+            })
+            .collect();
+        scripts.sort();
+        scripts
+    }
+
+    // `keyword table` suivi d'un caractère qui ne prolonge pas le nom
+    fn mentions(sql: &str, keyword: &str, table: &str) -> bool {
+        let needle = format!("{} {}", keyword, table);
+        sql.match_indices(&needle).any(|(start, _)| {
+            sql[start + needle.len()..]
+                .chars()
+                .next()
+                .is_none_or(|c| !(c.is_ascii_alphanumeric() || c == '_'))
+        })
+    }
+
+    #[test]
+    fn every_schema_table_is_created_by_a_migration() {
+        // Commentaires retirés, mots-clés en minuscules et espaces normalisés
+        let migrations = migration_scripts()
+            .iter()
+            .flat_map(|(_, sql)| sql.lines())
+            .map(|line| line.split("--").next().unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_ascii_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let tables: HashSet<String> = parse_schema(COMPILED_SCHEMA)
+            .into_iter()
+            .map(|column| column.table)
+            .collect();
+        let mut orphans: Vec<&String> = tables
+            .iter()
+            .filter(|table| {
+                !mentions(&migrations, "create table", table)
+                    && !mentions(&migrations, "create table if not exists", table)
+                    && !mentions(&migrations, "rename to", table)
+            })
+            .collect();
+        orphans.sort();
+        assert!(
+            orphans.is_empty(),
+            "schema.rs declares tables that no migration creates: {:?}",
+            orphans
+        );
+    }
+
+    // Nécessite une base vide sur laquelle toutes les migrations sont jouées : un projet
+    // Supabase neuf, ou un PostgreSQL doté du rôle authenticated et de auth.uid() (RLS).
+    // SCHEMA_CHECK_DATABASE_URL=postgres://... cargo test -- --ignored
+    #[tokio::test]
+    #[ignore]
+    async fn schema_matches_freshly_migrated_database() {
+        let url = std::env::var("SCHEMA_CHECK_DATABASE_URL")
+            .expect("SCHEMA_CHECK_DATABASE_URL must point to an empty database");
+        let mut conn = AsyncPgConnection::establish(&url)
+            .await
+            .expect("could not connect to SCHEMA_CHECK_DATABASE_URL");
+        for (migration, sql) in migration_scripts() {
+            conn.batch_execute(&sql)
+                .await
+                .unwrap_or_else(|e| panic!("migration {} failed: {}", migration, e));
+        }
+
+        let live = load_live_columns(&mut conn)
+            .await
+            .expect("could not read information_schema");
+        let drifts = compare(&parse_schema(COMPILED_SCHEMA), &live);
+        assert!(
+            drifts.is_empty(),
+            "schema.rs does not match the migrations:\n{}",
+            drifts
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
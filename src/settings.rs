@@ -0,0 +1,201 @@
+// OptiTask/backend-api/src/settings.rs
+use crate::error_handler::ServiceError;
+use crate::feature_flags;
+use crate::ids::ProjectId;
+use crate::models::{ClientPreferences, UserSettings};
+use crate::permissions::{self, Permission};
+use crate::schema::{client_preferences, user_settings};
+use chrono::{DateTime, Utc, Weekday};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+// Bornes des contraintes de la table user_settings
+pub const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+pub const MAX_PUSH_BATCH_MINUTES: i32 = 60;
+
+// Taille maximale d'un document de préférences client sérialisé (16 Ko)
+pub const MAX_CLIENT_PREFERENCES_BYTES: usize = 16 * 1024;
+// Document modifié depuis la lecture du client (expected_updated_at périmé)
+pub const PREFERENCES_CONFLICT: &str = "PREFERENCES_CONFLICT";
+
+pub async fn find_settings(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<UserSettings>, ServiceError> {
+    user_settings::table
+        .find(user_uuid)
+        .select(UserSettings::as_select())
+        .first::<UserSettings>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// Charge les préférences de l'utilisateur, en créant la ligne par défaut si absente
+pub async fn load_or_create_settings(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<UserSettings, ServiceError> {
+    diesel::insert_into(user_settings::table)
+        .values(user_settings::user_id.eq(user_uuid))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .await?;
+
+    user_settings::table
+        .find(user_uuid)
+        .select(UserSettings::as_select())
+        .first::<UserSettings>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+// Valeurs acceptées pour le premier jour de la semaine
+pub fn parse_week_start(value: &str) -> Result<Weekday, ServiceError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "monday" => Ok(Weekday::Mon),
+        "sunday" => Ok(Weekday::Sun),
+        "saturday" => Ok(Weekday::Sat),
+        other => Err(ServiceError::ValidationError(format!(
+            "Invalid week_start '{}'. Supported: monday, sunday, saturday",
+            other
+        ))),
+    }
+}
+
+pub fn week_start_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Sun => "sunday",
+        Weekday::Sat => "saturday",
+        _ => "monday",
+    }
+}
+
+// Premier jour de la semaine : paramètre explicite, sinon préférence enregistrée (lundi par défaut).
+// Ne crée pas la ligne de préférences, contrairement à load_or_create_settings.
+pub async fn resolve_week_start(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    requested: Option<&str>,
+) -> Result<Weekday, ServiceError> {
+    if let Some(value) = requested {
+        return parse_week_start(value);
+    }
+
+    let stored = user_settings::table
+        .find(user_uuid)
+        .select(user_settings::week_start)
+        .first::<String>(conn)
+        .await
+        .optional()?;
+
+    Ok(stored
+        .and_then(|value| parse_week_start(&value).ok())
+        .unwrap_or(Weekday::Mon))
+}
+
+// Projet appliqué à une tâche créée sans project_id. Un projet par défaut devenu
+// inaccessible (retrait de l'espace partagé) est ignoré : la tâche reste dans l'Inbox.
+pub async fn default_project_for_new_task(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<ProjectId>, ServiceError> {
+    let stored = user_settings::table
+        .find(user_uuid)
+        .select(user_settings::default_project_id)
+        .first::<Option<ProjectId>>(conn)
+        .await
+        .optional()?
+        .flatten();
+    let Some(project_uuid) = stored else {
+        return Ok(None);
+    };
+
+    let role = permissions::project_role(conn, user_uuid, project_uuid).await?;
+    Ok(role
+        .filter(|role| role.allows(Permission::ProjectWrite))
+        .map(|_| project_uuid))
+}
+
+// Espace de noms des préférences client : même règle que les clés de drapeaux
+pub fn validate_namespace(namespace: &str) -> Result<(), ServiceError> {
+    feature_flags::validate_key(namespace)
+}
+
+// Document de préférences : un objet JSON de MAX_CLIENT_PREFERENCES_BYTES au plus
+pub fn validate_client_preferences(data: &serde_json::Value) -> Result<(), ServiceError> {
+    if !data.is_object() {
+        return Err(ServiceError::ValidationError(
+            "data must be a JSON object".to_string(),
+        ));
+    }
+    if serde_json::to_vec(data)?.len() > MAX_CLIENT_PREFERENCES_BYTES {
+        return Err(ServiceError::ValidationError(format!(
+            "data cannot exceed {} bytes",
+            MAX_CLIENT_PREFERENCES_BYTES
+        )));
+    }
+    Ok(())
+}
+
+pub async fn load_client_preferences(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    namespace: &str,
+) -> Result<Option<ClientPreferences>, ServiceError> {
+    client_preferences::table
+        .find((user_uuid, namespace))
+        .select(ClientPreferences::as_select())
+        .first::<ClientPreferences>(conn)
+        .await
+        .optional()
+        .map_err(ServiceError::from)
+}
+
+// Enregistre le document de l'espace de noms. Avec expected_updated_at, l'écriture n'a lieu
+// que si le document stocké porte encore cette date (409 PREFERENCES_CONFLICT sinon).
+pub async fn save_client_preferences(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    namespace: &str,
+    data: &serde_json::Value,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> Result<ClientPreferences, ServiceError> {
+    let saved = match expected_updated_at {
+        Some(expected) => diesel::update(
+            client_preferences::table
+                .find((user_uuid, namespace))
+                .filter(client_preferences::updated_at.eq(expected)),
+        )
+        .set(client_preferences::data.eq(data))
+        .returning(ClientPreferences::as_returning())
+        .get_result::<ClientPreferences>(conn)
+        .await
+        .optional()?,
+        None => Some(
+            diesel::insert_into(client_preferences::table)
+                .values((
+                    client_preferences::user_id.eq(user_uuid),
+                    client_preferences::namespace.eq(namespace),
+                    client_preferences::data.eq(data),
+                ))
+                .on_conflict((client_preferences::user_id, client_preferences::namespace))
+                .do_update()
+                .set(client_preferences::data.eq(data))
+                .returning(ClientPreferences::as_returning())
+                .get_result::<ClientPreferences>(conn)
+                .await?,
+        ),
+    };
+
+    saved.ok_or_else(|| {
+        ServiceError::CodedConflict(
+            PREFERENCES_CONFLICT,
+            format!(
+                "Client preferences '{}' were modified since they were read",
+                namespace
+            ),
+        )
+    })
+}
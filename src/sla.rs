@@ -0,0 +1,351 @@
+// OptiTask/backend-api/src/sla.rs
+// Délais cibles (SLA) : "les tâches portant le label 'bug' doivent être terminées en
+// 48h". La cible se fixe sur un projet ou un label (sla_hours) ; la vérification
+// périodique enregistre les dépassements et notifie le propriétaire de la tâche, une
+// seule fois par tâche et par cible.
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::ids::TaskId;
+use crate::models::{NewNotification, DONE_TASK_STATUSES};
+use crate::notifications::KIND_TASK_SLA_BREACH;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::{
+    labels, notifications, projects, task_labels, task_sla_breaches, task_status_history, tasks,
+};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Un an
+pub const MAX_SLA_HOURS: i32 = 8760;
+// Fréquence de la vérification des dépassements
+const SLA_JOB_INTERVAL_SECS: u64 = 3600;
+
+pub const TARGET_PROJECT: &str = "project";
+pub const TARGET_LABEL: &str = "label";
+
+pub fn validate_sla_hours(hours: Option<i32>) -> Result<Option<i32>, ServiceError> {
+    match hours {
+        Some(h) if !(1..=MAX_SLA_HOURS).contains(&h) => Err(ServiceError::ValidationError(
+            format!("sla_hours must be between 1 and {}", MAX_SLA_HOURS),
+        )),
+        other => Ok(other),
+    }
+}
+
+// Projet ou label portant un délai cible
+#[derive(Debug)]
+struct SlaTarget {
+    target_type: &'static str,
+    target_id: Uuid,
+    name: String,
+    target_hours: i32,
+}
+
+// Tâche encore ouverte au-delà de son échéance SLA
+#[derive(Serialize, Debug)]
+pub struct SlaBreachedTask {
+    pub task_id: TaskId,
+    pub title: String,
+    pub due_at: DateTime<Utc>,
+    pub hours_overdue: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SlaCompliance {
+    pub target_type: &'static str,
+    pub target_id: Uuid,
+    pub name: String,
+    pub target_hours: i32,
+    pub total_tasks: usize,
+    pub completed_on_time: usize,
+    pub completed_late: usize,
+    pub open_on_track: usize,
+    pub open_breached: usize,
+    // Part des tâches terminées dans les temps parmi celles dont l'issue est connue
+    // (terminées, ou ouvertes et déjà en dépassement) ; None si aucune
+    pub compliance_rate: Option<f64>,
+    pub average_resolution_hours: Option<f64>,
+    pub breached_tasks: Vec<SlaBreachedTask>,
+}
+
+// (id, propriétaire, titre, statut, création)
+type SlaTaskRow = (TaskId, Uuid, String, String, NaiveDateTime);
+
+async fn load_targets(
+    conn: &mut AsyncPgConnection,
+    owner: Option<Uuid>,
+) -> Result<Vec<SlaTarget>, ServiceError> {
+    let mut project_query = projects::table
+        .filter(projects::sla_hours.is_not_null())
+        .into_boxed();
+    let mut label_query = labels::table
+        .filter(labels::sla_hours.is_not_null())
+        .into_boxed();
+    if let Some(user_uuid) = owner {
+        project_query = project_query.filter(projects::user_id.eq(user_uuid));
+        label_query = label_query.filter(labels::user_id.eq(user_uuid));
+    }
+
+    let project_targets = project_query
+        .order(projects::name.asc())
+        .select((projects::id, projects::name, projects::sla_hours))
+        .load::<(Uuid, String, Option<i32>)>(conn)
+        .await?;
+    let label_targets = label_query
+        .order(labels::name.asc())
+        .select((labels::id, labels::name, labels::sla_hours))
+        .load::<(Uuid, String, Option<i32>)>(conn)
+        .await?;
+
+    let as_targets = |target_type: &'static str, rows: Vec<(Uuid, String, Option<i32>)>| {
+        rows.into_iter()
+            .filter_map(move |(target_id, name, hours)| {
+                hours.map(|target_hours| SlaTarget {
+                    target_type,
+                    target_id,
+                    name,
+                    target_hours,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut targets = as_targets(TARGET_PROJECT, project_targets);
+    targets.extend(as_targets(TARGET_LABEL, label_targets));
+    Ok(targets)
+}
+
+async fn load_target_tasks(
+    conn: &mut AsyncPgConnection,
+    target: &SlaTarget,
+    created_between: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    open_only: bool,
+) -> Result<Vec<SlaTaskRow>, ServiceError> {
+    let mut task_query = tasks::table.into_boxed();
+    task_query = if target.target_type == TARGET_PROJECT {
+        task_query.filter(tasks::project_id.eq(target.target_id))
+    } else {
+        task_query.filter(
+            tasks::id.eq_any(
+                task_labels::table
+                    .filter(task_labels::label_id.eq(target.target_id))
+                    .select(task_labels::task_id),
+            ),
+        )
+    };
+    if let Some((start, end)) = created_between {
+        task_query = task_query
+            .filter(tasks::created_at.ge(start))
+            .filter(tasks::created_at.le(end));
+    }
+    if open_only {
+        task_query = task_query.filter(tasks::status.ne_all(DONE_TASK_STATUSES));
+    }
+
+    task_query
+        .select((
+            tasks::id,
+            tasks::user_id,
+            tasks::title,
+            tasks::status,
+            tasks::created_at,
+        ))
+        .load::<SlaTaskRow>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
+
+fn due_at(created_at: NaiveDateTime, target_hours: i32) -> DateTime<Utc> {
+    created_at.and_utc() + ChronoDuration::hours(target_hours as i64)
+}
+
+// Dernier passage à un statut terminé, lu dans task_status_history
+async fn load_completion_times(
+    conn: &mut AsyncPgConnection,
+    task_ids: &[TaskId],
+) -> Result<HashMap<TaskId, DateTime<Utc>>, ServiceError> {
+    Ok(task_status_history::table
+        .filter(task_status_history::task_id.eq_any(task_ids))
+        .filter(task_status_history::new_status.eq_any(DONE_TASK_STATUSES))
+        .group_by(task_status_history::task_id)
+        .select((
+            task_status_history::task_id,
+            diesel::dsl::max(task_status_history::changed_at),
+        ))
+        .load::<(TaskId, Option<DateTime<Utc>>)>(conn)
+        .await?
+        .into_iter()
+        .filter_map(|(task_uuid, changed_at)| changed_at.map(|at| (task_uuid, at)))
+        .collect())
+}
+
+async fn evaluate_target(
+    conn: &mut AsyncPgConnection,
+    target: SlaTarget,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<SlaCompliance, ServiceError> {
+    let target_tasks = load_target_tasks(conn, &target, Some((start, end)), false).await?;
+    let task_ids: Vec<TaskId> = target_tasks.iter().map(|row| row.0).collect();
+    let completion_times = load_completion_times(conn, &task_ids).await?;
+
+    let mut compliance = SlaCompliance {
+        target_type: target.target_type,
+        target_id: target.target_id,
+        name: target.name,
+        target_hours: target.target_hours,
+        total_tasks: target_tasks.len(),
+        completed_on_time: 0,
+        completed_late: 0,
+        open_on_track: 0,
+        open_breached: 0,
+        compliance_rate: None,
+        average_resolution_hours: None,
+        breached_tasks: Vec::new(),
+    };
+    let mut resolution_hours = Vec::new();
+
+    for (task_uuid, _, title, task_status, created_at) in target_tasks {
+        let deadline = due_at(created_at, target.target_hours);
+        if DONE_TASK_STATUSES.contains(&task_status.as_str()) {
+            // Sans historique, la tâche est comptée comme terminée dans les temps
+            let Some(completed_at) = completion_times.get(&task_uuid).copied() else {
+                compliance.completed_on_time += 1;
+                continue;
+            };
+            resolution_hours
+                .push((completed_at - created_at.and_utc()).num_minutes() as f64 / 60.0);
+            if completed_at <= deadline {
+                compliance.completed_on_time += 1;
+            } else {
+                compliance.completed_late += 1;
+            }
+        } else if now > deadline {
+            compliance.open_breached += 1;
+            compliance.breached_tasks.push(SlaBreachedTask {
+                task_id: task_uuid,
+                title,
+                due_at: deadline,
+                hours_overdue: (now - deadline).num_hours(),
+            });
+        } else {
+            compliance.open_on_track += 1;
+        }
+    }
+
+    let decided =
+        compliance.completed_on_time + compliance.completed_late + compliance.open_breached;
+    if decided > 0 {
+        compliance.compliance_rate = Some(compliance.completed_on_time as f64 / decided as f64);
+    }
+    if !resolution_hours.is_empty() {
+        compliance.average_resolution_hours =
+            Some(resolution_hours.iter().sum::<f64>() / resolution_hours.len() as f64);
+    }
+    compliance
+        .breached_tasks
+        .sort_by_key(|breached| std::cmp::Reverse(breached.hours_overdue));
+    Ok(compliance)
+}
+
+// Respect des délais cibles de l'utilisateur pour les tâches créées sur la période
+pub async fn compliance_report(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<SlaCompliance>, ServiceError> {
+    let now = Utc::now();
+    let mut report = Vec::new();
+    for target in load_targets(conn, Some(user_uuid)).await? {
+        report.push(evaluate_target(conn, target, start, end, now).await?);
+    }
+    Ok(report)
+}
+
+// Enregistre les nouveaux dépassements et notifie les propriétaires des tâches.
+// owner limite la vérification aux cibles d'un utilisateur.
+pub async fn check_breaches(
+    conn: &mut AsyncPgConnection,
+    owner: Option<Uuid>,
+) -> Result<usize, ServiceError> {
+    let now = Utc::now();
+    let mut flagged = 0;
+
+    for target in load_targets(conn, owner).await? {
+        let open_tasks = load_target_tasks(conn, &target, None, true).await?;
+        for (task_uuid, task_owner, title, _, created_at) in open_tasks {
+            let deadline = due_at(created_at, target.target_hours);
+            if now <= deadline {
+                continue;
+            }
+
+            let inserted = diesel::insert_into(task_sla_breaches::table)
+                .values((
+                    task_sla_breaches::task_id.eq(task_uuid),
+                    task_sla_breaches::user_id.eq(task_owner),
+                    task_sla_breaches::target_type.eq(target.target_type),
+                    task_sla_breaches::target_id.eq(target.target_id),
+                    task_sla_breaches::target_hours.eq(target.target_hours),
+                    task_sla_breaches::due_at.eq(deadline),
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn)
+                .await?;
+            if inserted == 0 {
+                continue;
+            }
+
+            diesel::insert_into(notifications::table)
+                .values(&NewNotification {
+                    user_id: task_owner,
+                    task_id: Some(task_uuid),
+                    actor_id: None,
+                    kind: KIND_TASK_SLA_BREACH.to_string(),
+                    message: format!(
+                        "'{}' exceeded the {}h target of {} '{}'",
+                        title, target.target_hours, target.target_type, target.name
+                    ),
+                    payload: json!({
+                        "target_type": target.target_type,
+                        "target_id": target.target_id,
+                        "target_hours": target.target_hours,
+                        "due_at": deadline,
+                    }),
+                })
+                .execute(conn)
+                .await?;
+            flagged += 1;
+        }
+    }
+    Ok(flagged)
+}
+
+// Lance la tâche de fond qui vérifie périodiquement les dépassements
+pub fn spawn_sla_job(pool: DbPool, read_only: Arc<ReadOnlyMode>) {
+    actix_web::rt::spawn(async move {
+        let mut interval =
+            actix_web::rt::time::interval(std::time::Duration::from_secs(SLA_JOB_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            let result = match pool.get().await {
+                Ok(mut conn) => check_breaches(&mut conn, None).await,
+                Err(e) => Err(ServiceError::from(e)),
+            };
+            match result {
+                Ok(0) => {}
+                Ok(flagged) => log::info!("SLA check flagged {} breach(es)", flagged),
+                Err(e) => log::error!("SLA check job failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,148 @@
+// OptiTask/backend-api/src/themes.rs
+// Thèmes visuels des projets (couleur d'accent, image de couverture, emoji), stockés sur
+// le projet pour être synchronisés entre appareils plutôt que dans le localStorage
+use crate::error_handler::ServiceError;
+use crate::icons;
+use crate::models::{ProjectThemePayload, ThemePreset};
+use serde_json::{Map, Value};
+
+const MAX_COVER_IMAGE_URL_LENGTH: usize = 2048;
+
+pub const THEME_PRESETS: [ThemePreset; 8] = [
+    ThemePreset {
+        key: "ocean",
+        name: "Ocean",
+        accent_color: "#0ea5e9",
+        emoji: "🌊",
+    },
+    ThemePreset {
+        key: "forest",
+        name: "Forest",
+        accent_color: "#16a34a",
+        emoji: "🌲",
+    },
+    ThemePreset {
+        key: "sunset",
+        name: "Sunset",
+        accent_color: "#f97316",
+        emoji: "🌅",
+    },
+    ThemePreset {
+        key: "berry",
+        name: "Berry",
+        accent_color: "#db2777",
+        emoji: "🍇",
+    },
+    ThemePreset {
+        key: "lavender",
+        name: "Lavender",
+        accent_color: "#8b5cf6",
+        emoji: "🪻",
+    },
+    ThemePreset {
+        key: "sand",
+        name: "Sand",
+        accent_color: "#ca8a04",
+        emoji: "🏖️",
+    },
+    ThemePreset {
+        key: "slate",
+        name: "Slate",
+        accent_color: "#475569",
+        emoji: "🪨",
+    },
+    ThemePreset {
+        key: "rocket",
+        name: "Rocket",
+        accent_color: "#dc2626",
+        emoji: "🚀",
+    },
+];
+
+pub fn find_preset(key: &str) -> Option<&'static ThemePreset> {
+    THEME_PRESETS.iter().find(|preset| preset.key == key)
+}
+
+// "#RRGGBB" ou "#RGB", stockée en "#rrggbb"
+fn normalize_accent_color(color: &str) -> Result<String, ServiceError> {
+    let invalid = || {
+        ServiceError::ValidationError(format!(
+            "Invalid accent_color '{}': expected a hex color such as \"#0ea5e9\"",
+            color
+        ))
+    };
+    let hex = color.strip_prefix('#').ok_or_else(invalid)?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    match hex.len() {
+        6 => Ok(format!("#{}", hex.to_ascii_lowercase())),
+        3 => Ok(format!(
+            "#{}",
+            hex.chars()
+                .flat_map(|c| [c, c])
+                .collect::<String>()
+                .to_ascii_lowercase()
+        )),
+        _ => Err(invalid()),
+    }
+}
+
+// Image affichée par tous les appareils : https uniquement
+fn validate_cover_image_url(url: &str) -> Result<String, ServiceError> {
+    let is_https = url.len() <= MAX_COVER_IMAGE_URL_LENGTH
+        && reqwest::Url::parse(url)
+            .ok()
+            .is_some_and(|parsed| parsed.scheme() == "https" && parsed.host_str().is_some());
+    if !is_https {
+        return Err(ServiceError::ValidationError(format!(
+            "cover_image_url must be an https URL of at most {} characters",
+            MAX_COVER_IMAGE_URL_LENGTH
+        )));
+    }
+    Ok(url.to_string())
+}
+
+// Valide le thème reçu et renvoie la valeur JSONB à stocker ; les champs vides sont omis
+pub fn build_theme(theme: &ProjectThemePayload) -> Result<Value, ServiceError> {
+    let present = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    let mut cleaned = Map::new();
+    if let Some(preset) = present(&theme.preset) {
+        if find_preset(&preset).is_none() {
+            return Err(ServiceError::ValidationError(format!(
+                "Unknown theme preset '{}'",
+                preset
+            )));
+        }
+        cleaned.insert("preset".to_string(), Value::from(preset));
+    }
+    if let Some(color) = present(&theme.accent_color) {
+        cleaned.insert(
+            "accent_color".to_string(),
+            Value::from(normalize_accent_color(&color)?),
+        );
+    }
+    if let Some(url) = present(&theme.cover_image_url) {
+        cleaned.insert(
+            "cover_image_url".to_string(),
+            Value::from(validate_cover_image_url(&url)?),
+        );
+    }
+    if let Some(emoji) = present(&theme.emoji) {
+        if !icons::is_emoji(&emoji) {
+            return Err(ServiceError::ValidationError(format!(
+                "Invalid theme emoji '{}': expected a single emoji",
+                emoji
+            )));
+        }
+        cleaned.insert("emoji".to_string(), Value::from(emoji));
+    }
+    Ok(Value::Object(cleaned))
+}
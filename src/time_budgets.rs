@@ -0,0 +1,185 @@
+// OptiTask/backend-api/src/time_budgets.rs
+// Budget de temps par tâche (time_budget_seconds). Le temps suivi est la somme des entrées
+// terminées hors pauses, tous utilisateurs confondus. Quand il franchit 80% puis 100% du
+// budget, le propriétaire de la tâche est notifié et un événement est publié, une seule
+// fois par seuil : time_budget_alerted_pct retient le dernier seuil signalé.
+use crate::error_handler::ServiceError;
+use crate::events::{self, DomainEvent};
+use crate::ids::{ProjectId, TaskId};
+use crate::models::{NewNotification, TaskApiResponse};
+use crate::notifications::KIND_TASK_TIME_BUDGET;
+use crate::schema::{notifications, tasks, time_entries};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// Seuils d'alerte, en pourcentage du budget, par ordre croissant
+pub const BUDGET_ALERT_THRESHOLDS: [i16; 2] = [80, 100];
+
+pub fn validate_time_budget(seconds: i32) -> Result<(), ServiceError> {
+    if seconds <= 0 {
+        return Err(ServiceError::ValidationError(
+            "time_budget_seconds must be greater than 0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// Part du budget consommée, arrondie au dixième
+pub fn budget_used_pct(tracked_seconds: i64, budget_seconds: i32) -> f64 {
+    let pct = tracked_seconds as f64 * 100.0 / f64::from(budget_seconds);
+    (pct * 10.0).round() / 10.0
+}
+
+// Plus haut seuil atteint (0 si aucun)
+fn reached_threshold(used_pct: f64) -> i16 {
+    BUDGET_ALERT_THRESHOLDS
+        .iter()
+        .rev()
+        .find(|threshold| used_pct >= f64::from(**threshold))
+        .copied()
+        .unwrap_or(0)
+}
+
+pub async fn tracked_seconds_by_task(
+    conn: &mut AsyncPgConnection,
+    task_ids: &[TaskId],
+) -> Result<HashMap<TaskId, i64>, ServiceError> {
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let totals = time_entries::table
+        .filter(time_entries::task_id.eq_any(task_ids))
+        .filter(time_entries::is_break.eq(false))
+        .group_by(time_entries::task_id)
+        .select((
+            time_entries::task_id,
+            diesel::dsl::sum(time_entries::duration_seconds),
+        ))
+        .load::<(TaskId, Option<i64>)>(conn)
+        .await?;
+    Ok(totals
+        .into_iter()
+        .map(|(task_id, total)| (task_id, total.unwrap_or(0)))
+        .collect())
+}
+
+// Peuple `budget_used_pct` pour les tâches ayant un budget
+pub async fn attach_budget_usage(
+    conn: &mut AsyncPgConnection,
+    task_responses: &mut [TaskApiResponse],
+) -> Result<(), ServiceError> {
+    let budgeted_ids: Vec<TaskId> = task_responses
+        .iter()
+        .filter(|task| task.time_budget_seconds.is_some())
+        .map(|task| task.id)
+        .collect();
+    let tracked_by_task = tracked_seconds_by_task(conn, &budgeted_ids).await?;
+
+    for task_response in task_responses.iter_mut() {
+        task_response.budget_used_pct = task_response.time_budget_seconds.map(|budget| {
+            budget_used_pct(
+                tracked_by_task.get(&task_response.id).copied().unwrap_or(0),
+                budget,
+            )
+        });
+    }
+    Ok(())
+}
+
+async fn check_budget_thresholds(
+    conn: &mut AsyncPgConnection,
+    task_uuid: TaskId,
+) -> Result<(), ServiceError> {
+    let Some((owner_uuid, project_id, title, budget, alerted_pct)) = tasks::table
+        .find(task_uuid)
+        .select((
+            tasks::user_id,
+            tasks::project_id,
+            tasks::title,
+            tasks::time_budget_seconds,
+            tasks::time_budget_alerted_pct,
+        ))
+        .first::<(Uuid, Option<ProjectId>, String, Option<i32>, i16)>(conn)
+        .await
+        .optional()?
+    else {
+        return Ok(());
+    };
+    let Some(budget) = budget else {
+        return Ok(());
+    };
+
+    let tracked_seconds = tracked_seconds_by_task(conn, &[task_uuid])
+        .await?
+        .remove(&task_uuid)
+        .unwrap_or(0);
+    let used_pct = budget_used_pct(tracked_seconds, budget);
+    let reached = reached_threshold(used_pct);
+    if reached == alerted_pct {
+        return Ok(());
+    }
+    if reached < alerted_pct {
+        // Temps retiré (entrée supprimée ou raccourcie) : le seuil pourra être signalé à nouveau
+        diesel::update(tasks::table.find(task_uuid))
+            .set(tasks::time_budget_alerted_pct.eq(reached))
+            .execute(conn)
+            .await?;
+        return Ok(());
+    }
+
+    // Le filtre sur l'ancien seuil évite une double alerte entre deux requêtes concurrentes
+    let claimed = diesel::update(
+        tasks::table
+            .find(task_uuid)
+            .filter(tasks::time_budget_alerted_pct.lt(reached)),
+    )
+    .set(tasks::time_budget_alerted_pct.eq(reached))
+    .execute(conn)
+    .await?;
+    if claimed == 0 {
+        return Ok(());
+    }
+
+    diesel::insert_into(notifications::table)
+        .values(&NewNotification {
+            user_id: owner_uuid,
+            task_id: Some(task_uuid),
+            actor_id: None,
+            kind: KIND_TASK_TIME_BUDGET.to_string(),
+            message: if reached >= 100 {
+                format!("Task '{}' has exceeded its time budget", title)
+            } else {
+                format!("Task '{}' has used {}% of its time budget", title, reached)
+            },
+            payload: json!({
+                "threshold_pct": reached,
+                "budget_used_pct": used_pct,
+                "tracked_seconds": tracked_seconds,
+                "time_budget_seconds": budget,
+            }),
+        })
+        .execute(conn)
+        .await?;
+
+    events::emit(
+        DomainEvent::new(events::EVENT_TIME_BUDGET_THRESHOLD, owner_uuid)
+            .with_task(task_uuid, project_id)
+            .with_properties(json!({
+                "threshold_pct": reached,
+                "budget_used_pct": used_pct,
+                "time_budget_seconds": budget,
+            })),
+    );
+    Ok(())
+}
+
+// À appeler après toute modification du temps suivi d'une tâche ou de son budget.
+// Un échec est journalisé sans faire échouer la requête d'origine.
+pub async fn check_time_budget(conn: &mut AsyncPgConnection, task_uuid: TaskId) {
+    if let Err(e) = check_budget_thresholds(conn, task_uuid).await {
+        log::error!("Time budget check for task {} failed: {}", task_uuid, e);
+    }
+}
@@ -0,0 +1,241 @@
+// OptiTask/backend-api/src/timer_auto_stop.rs
+// Arrêt quotidien des minuteurs oubliés : un minuteur encore en cours à l'heure locale
+// choisie (timer_auto_stop_time, ex: 03:00) est arrêté à cette heure. En mode "split",
+// le suivi continue dans une nouvelle entrée démarrant à cette heure ; dans les deux cas
+// une notification invite l'utilisateur à vérifier l'entrée.
+use crate::automations::{self, AutomationEvent};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::events::{self, DomainEvent};
+use crate::models::{AutomationTrigger, NewNotification, NewTimeEntry, TimeEntry};
+use crate::notifications::KIND_TIMER_AUTO_STOPPED;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::schema::{notifications, time_entries, user_settings};
+use crate::time_budgets;
+use crate::timesheets::ensure_entry_unlocked;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const TIMER_AUTO_STOP_CAP: &str = "cap";
+pub const TIMER_AUTO_STOP_SPLIT: &str = "split";
+pub const TIMER_AUTO_STOP_MODES: [&str; 2] = [TIMER_AUTO_STOP_CAP, TIMER_AUTO_STOP_SPLIT];
+
+const TIMER_AUTO_STOP_JOB_INTERVAL_SECS: u64 = 300;
+
+// Première occurrence de l'heure locale `stop_time` strictement après `instant`
+fn next_boundary(
+    instant: DateTime<Utc>,
+    stop_time: NaiveTime,
+    utc_offset_minutes: i32,
+) -> DateTime<Utc> {
+    let offset = Duration::minutes(i64::from(utc_offset_minutes));
+    let local = (instant + offset).naive_utc();
+    let mut boundary = local.date().and_time(stop_time);
+    if boundary <= local {
+        boundary += Duration::days(1);
+    }
+    boundary.and_utc() - offset
+}
+
+// Arrête l'entrée à `boundary` (et crée sa suite en mode split) ; None si elle n'est plus
+// en cours ou si sa semaine est verrouillée
+async fn stop_at_boundary(
+    conn: &mut AsyncPgConnection,
+    entry: &TimeEntry,
+    boundary: DateTime<Utc>,
+    split: bool,
+) -> Result<Option<(TimeEntry, Option<TimeEntry>)>, ServiceError> {
+    if ensure_entry_unlocked(conn, entry.user_id, entry.start_time)
+        .await
+        .is_err()
+        || (split
+            && ensure_entry_unlocked(conn, entry.user_id, boundary)
+                .await
+                .is_err())
+    {
+        return Ok(None);
+    }
+
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            // Arrêté entre-temps par l'utilisateur : rien à faire
+            let Some(stopped) = diesel::update(
+                time_entries::table
+                    .filter(time_entries::id.eq(entry.id))
+                    .filter(time_entries::start_time.eq(entry.start_time))
+                    .filter(time_entries::end_time.is_null()),
+            )
+            .set((
+                time_entries::end_time.eq(Some(boundary)),
+                time_entries::duration_seconds
+                    .eq(Some((boundary - entry.start_time).num_seconds() as i32)),
+                time_entries::auto_stopped.eq(true),
+                time_entries::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .get_result::<TimeEntry>(conn)
+            .await
+            .optional()?
+            else {
+                return Ok(None);
+            };
+
+            let continuation = if split {
+                Some(
+                    diesel::insert_into(time_entries::table)
+                        .values(&NewTimeEntry {
+                            user_id: entry.user_id,
+                            task_id: entry.task_id,
+                            start_time: boundary,
+                            end_time: None,
+                            duration_seconds: None,
+                            is_pomodoro_session: Some(entry.is_pomodoro_session),
+                            is_break: None,
+                            clock_skew_seconds: entry.clock_skew_seconds,
+                            suspect_clock: Some(entry.suspect_clock),
+                            focus_session_id: entry.focus_session_id,
+                        })
+                        .get_result::<TimeEntry>(conn)
+                        .await?,
+                )
+            } else {
+                None
+            };
+            Ok(Some((stopped, continuation)))
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+// Arrête les minuteurs ayant franchi l'heure d'arrêt de leur utilisateur.
+// Renvoie le nombre d'entrées arrêtées.
+pub async fn stop_runaway_timers(conn: &mut AsyncPgConnection) -> Result<usize, ServiceError> {
+    let now = Utc::now();
+
+    let subscribers = user_settings::table
+        .filter(user_settings::timer_auto_stop_time.is_not_null())
+        .select((
+            user_settings::user_id,
+            user_settings::timer_auto_stop_time,
+            user_settings::timer_auto_stop_mode,
+            user_settings::utc_offset_minutes,
+        ))
+        .load::<(Uuid, Option<NaiveTime>, String, i32)>(conn)
+        .await?;
+
+    let mut stopped_count = 0;
+    for (user_uuid, stop_time, mode, utc_offset_minutes) in subscribers {
+        let Some(stop_time) = stop_time else {
+            continue;
+        };
+        let split = mode == TIMER_AUTO_STOP_SPLIT;
+
+        let running = time_entries::table
+            .filter(time_entries::user_id.eq(user_uuid))
+            .filter(time_entries::end_time.is_null())
+            .filter(time_entries::is_break.eq(false))
+            .select(TimeEntry::as_select())
+            .load::<TimeEntry>(conn)
+            .await?;
+
+        for entry in running {
+            let mut current = entry;
+            // En mode split, une entrée oubliée plusieurs jours est découpée jour par jour
+            loop {
+                let boundary = next_boundary(current.start_time, stop_time, utc_offset_minutes);
+                if boundary > now {
+                    break;
+                }
+                let Some((stopped, continuation)) =
+                    stop_at_boundary(conn, &current, boundary, split).await?
+                else {
+                    break;
+                };
+                stopped_count += 1;
+                time_budgets::check_time_budget(conn, stopped.task_id).await;
+
+                let local_stop = stop_time.format("%H:%M");
+                diesel::insert_into(notifications::table)
+                    .values(&NewNotification {
+                        user_id: user_uuid,
+                        task_id: Some(stopped.task_id),
+                        actor_id: None,
+                        kind: KIND_TIMER_AUTO_STOPPED.to_string(),
+                        message: if split {
+                            format!(
+                                "Your timer was still running at {}; it was split into a new entry, please review it",
+                                local_stop
+                            )
+                        } else {
+                            format!(
+                                "Your timer was still running at {} and was stopped, please review the entry",
+                                local_stop
+                            )
+                        },
+                        payload: json!({
+                            "time_entry_id": stopped.id,
+                            "stopped_at": boundary,
+                            "mode": mode,
+                            "continuation_id": continuation.as_ref().map(|next| next.id),
+                        }),
+                    })
+                    .execute(conn)
+                    .await?;
+
+                match continuation {
+                    Some(next) => current = next,
+                    None => {
+                        // Arrêt effectif du minuteur : mêmes suites qu'un arrêt manuel
+                        automations::run_automations(
+                            conn,
+                            AutomationEvent::for_task(
+                                AutomationTrigger::TimerStopped,
+                                stopped.task_id,
+                            ),
+                        )
+                        .await;
+                        events::emit(
+                            DomainEvent::new(events::EVENT_TIMER_STOPPED, user_uuid)
+                                .with_task(stopped.task_id, None)
+                                .with_properties(json!({
+                                    "time_entry_id": stopped.id,
+                                    "duration_seconds": stopped.duration_seconds,
+                                    "auto_stopped": true,
+                                })),
+                        );
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    Ok(stopped_count)
+}
+
+pub fn spawn_timer_auto_stop_job(pool: DbPool, read_only: Arc<ReadOnlyMode>) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(
+            TIMER_AUTO_STOP_JOB_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            let result = match pool.get().await {
+                Ok(mut conn) => stop_runaway_timers(&mut conn).await,
+                Err(e) => Err(ServiceError::from(e)),
+            };
+            match result {
+                Ok(0) => {}
+                Ok(stopped) => log::info!("Timer auto-stop stopped {} timer(s)", stopped),
+                Err(e) => log::error!("Timer auto-stop job failed: {}", e),
+            }
+        }
+    });
+}
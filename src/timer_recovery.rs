@@ -0,0 +1,109 @@
+// OptiTask/backend-api/src/timer_recovery.rs
+// Reprise après crash des minuteurs : les clients envoient un signal de vie chaque minute
+// pendant qu'un minuteur tourne. Une entrée en cours dont le dernier signal est plus ancien
+// que le délai configuré est close d'office, sa fin ramenée au dernier signal, au lieu de
+// rester "en cours" pendant des jours après la fermeture du navigateur.
+use crate::automations::{self, AutomationEvent};
+use crate::db::DbPool;
+use crate::error_handler::ServiceError;
+use crate::events::{self, DomainEvent};
+use crate::ids::{TaskId, TimeEntryId};
+use crate::models::AutomationTrigger;
+use crate::read_only_mode::ReadOnlyMode;
+use crate::time_budgets;
+use crate::timesheets::ENTRY_UNLOCKED_SQL;
+use diesel::sql_types::{BigInt, Integer, Nullable, Uuid as DieselUuid};
+use diesel::{sql_query, QueryableByName};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const TIMER_NOT_RUNNING: &str = "TIMER_NOT_RUNNING";
+
+const TIMER_RECOVERY_JOB_INTERVAL_SECS: u64 = 60;
+
+#[derive(QueryableByName)]
+struct StoppedTimer {
+    #[diesel(sql_type = DieselUuid)]
+    id: TimeEntryId,
+    #[diesel(sql_type = DieselUuid)]
+    user_id: Uuid,
+    #[diesel(sql_type = DieselUuid)]
+    task_id: TaskId,
+    #[diesel(sql_type = Nullable<Integer>)]
+    duration_seconds: Option<i32>,
+}
+
+// Clôt les minuteurs sans signal depuis `timeout_minutes` ; renvoie le nombre d'entrées closes.
+// Les semaines verrouillées (feuilles de temps soumises, clôture) ne sont pas modifiées.
+pub async fn close_stale_timers(
+    conn: &mut AsyncPgConnection,
+    timeout_minutes: i64,
+) -> Result<usize, ServiceError> {
+    let stopped = sql_query(format!(
+        "UPDATE time_entries te \
+         SET end_time = GREATEST(te.start_time, te.last_heartbeat_at), \
+             duration_seconds = FLOOR(EXTRACT(EPOCH FROM \
+                 (GREATEST(te.start_time, te.last_heartbeat_at) - te.start_time)))::int, \
+             auto_stopped = TRUE, \
+             updated_at = NOW() \
+         WHERE te.end_time IS NULL \
+           AND te.last_heartbeat_at IS NOT NULL \
+           AND te.last_heartbeat_at < NOW() - make_interval(mins => $1::int) \
+           AND {} \
+         RETURNING te.id, te.user_id, te.task_id, te.duration_seconds",
+        ENTRY_UNLOCKED_SQL
+    ))
+    .bind::<BigInt, _>(timeout_minutes)
+    .load::<StoppedTimer>(conn)
+    .await?;
+
+    for timer in &stopped {
+        log::info!(
+            "Auto-stopped time entry {} of user {} (no heartbeat for {} min)",
+            timer.id,
+            timer.user_id,
+            timeout_minutes
+        );
+        automations::run_automations(
+            conn,
+            AutomationEvent::for_task(AutomationTrigger::TimerStopped, timer.task_id),
+        )
+        .await;
+        time_budgets::check_time_budget(conn, timer.task_id).await;
+        events::emit(
+            DomainEvent::new(events::EVENT_TIMER_STOPPED, timer.user_id)
+                .with_task(timer.task_id, None)
+                .with_properties(json!({
+                    "time_entry_id": timer.id,
+                    "duration_seconds": timer.duration_seconds,
+                    "auto_stopped": true,
+                })),
+        );
+    }
+    Ok(stopped.len())
+}
+
+pub fn spawn_timer_recovery_job(pool: DbPool, read_only: Arc<ReadOnlyMode>, timeout_minutes: i64) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(
+            TIMER_RECOVERY_JOB_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            if read_only.is_enabled() {
+                continue;
+            }
+            let result = match pool.get().await {
+                Ok(mut conn) => close_stale_timers(&mut conn, timeout_minutes).await,
+                Err(e) => Err(ServiceError::from(e)),
+            };
+            match result {
+                Ok(0) => {}
+                Ok(closed) => log::info!("Timer recovery closed {} stale timer(s)", closed),
+                Err(e) => log::error!("Timer recovery job failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,179 @@
+// OptiTask/backend-api/src/timesheets.rs
+// Feuilles de temps hebdomadaires : verrouillage des entrées soumises ou antérieures
+// à la clôture comptable, et droits de validation
+use crate::error_handler::ServiceError;
+use crate::models::{TIMESHEET_STATUS_APPROVED, TIMESHEET_STATUS_SUBMITTED};
+use crate::schema::{timesheets, user_settings, workspace_members, workspaces};
+use chrono::{DateTime, NaiveDate, Utc, Weekday};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub const PERIOD_CLOSED: &str = "PERIOD_CLOSED";
+
+// Les semaines sont calculées en UTC, du lundi au dimanche
+pub fn week_start_of(moment: DateTime<Utc>) -> NaiveDate {
+    moment.date_naive().week(Weekday::Mon).first_day()
+}
+
+// Équivalent SQL de ensure_entry_unlocked pour les traitements en masse
+// (l'entrée de temps doit être aliasée te)
+pub const ENTRY_UNLOCKED_SQL: &str = "NOT EXISTS (SELECT 1 FROM timesheets ts \
+     WHERE ts.user_id = te.user_id \
+     AND ts.week_start = date_trunc('week', te.start_time AT TIME ZONE 'UTC')::date \
+     AND ts.status IN ('submitted', 'approved')) \
+     AND NOT EXISTS (SELECT 1 FROM user_settings us \
+     WHERE us.user_id = te.user_id \
+     AND (te.start_time AT TIME ZONE 'UTC')::date < us.time_locked_before) \
+     AND NOT EXISTS (SELECT 1 FROM workspace_members wm \
+     JOIN workspaces w ON w.id = wm.workspace_id \
+     WHERE wm.user_id = te.user_id AND wm.role <> 'owner' \
+     AND (te.start_time AT TIME ZONE 'UTC')::date < w.time_locked_before)";
+
+// Date de clôture effective : la plus récente entre celle de l'utilisateur et celles des
+// espaces dont il est membre. Le propriétaire d'un espace n'est pas tenu par sa propre
+// clôture, ce qui lui permet de corriger une période déjà facturée.
+pub async fn locked_before(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+) -> Result<Option<NaiveDate>, ServiceError> {
+    let personal_lock = user_settings::table
+        .find(user_uuid)
+        .select(user_settings::time_locked_before)
+        .first::<Option<NaiveDate>>(conn)
+        .await
+        .optional()?
+        .flatten();
+
+    let workspace_lock = workspace_members::table
+        .inner_join(workspaces::table)
+        .filter(workspace_members::user_id.eq(user_uuid))
+        .filter(workspace_members::role.ne("owner"))
+        .select(diesel::dsl::max(workspaces::time_locked_before))
+        .first::<Option<NaiveDate>>(conn)
+        .await?;
+
+    Ok(personal_lock.max(workspace_lock))
+}
+
+// Verrous d'un utilisateur chargés une fois, pour les traitements qui examinent beaucoup
+// d'entrées en Rust (même règle que ensure_entry_unlocked)
+pub struct EntryLocks {
+    locked_before: Option<NaiveDate>,
+    locked_weeks: HashSet<NaiveDate>,
+}
+
+impl EntryLocks {
+    pub async fn load(
+        conn: &mut AsyncPgConnection,
+        user_uuid: Uuid,
+    ) -> Result<EntryLocks, ServiceError> {
+        let locked_weeks = timesheets::table
+            .filter(timesheets::user_id.eq(user_uuid))
+            .filter(
+                timesheets::status.eq_any([TIMESHEET_STATUS_SUBMITTED, TIMESHEET_STATUS_APPROVED]),
+            )
+            .select(timesheets::week_start)
+            .load::<NaiveDate>(conn)
+            .await?
+            .into_iter()
+            .collect();
+        Ok(EntryLocks {
+            locked_before: locked_before(conn, user_uuid).await?,
+            locked_weeks,
+        })
+    }
+
+    pub fn is_locked(&self, entry_start: DateTime<Utc>) -> bool {
+        self.locked_before
+            .is_some_and(|lock_date| entry_start.date_naive() < lock_date)
+            || self.locked_weeks.contains(&week_start_of(entry_start))
+    }
+}
+
+// Refuse toute modification d'une entrée appartenant à une semaine soumise ou validée,
+// ou antérieure à la clôture comptable (409 PERIOD_CLOSED)
+pub async fn ensure_entry_unlocked(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Uuid,
+    entry_start: DateTime<Utc>,
+) -> Result<(), ServiceError> {
+    if let Some(lock_date) = locked_before(conn, user_uuid).await? {
+        if entry_start.date_naive() < lock_date {
+            return Err(ServiceError::CodedConflict(
+                PERIOD_CLOSED,
+                format!(
+                    "Time entries before {} are locked (accounting period closed)",
+                    lock_date
+                ),
+            ));
+        }
+    }
+
+    let week_start = week_start_of(entry_start);
+    let locked_status = timesheets::table
+        .filter(timesheets::user_id.eq(user_uuid))
+        .filter(timesheets::week_start.eq(week_start))
+        .filter(timesheets::status.eq_any([TIMESHEET_STATUS_SUBMITTED, TIMESHEET_STATUS_APPROVED]))
+        .select(timesheets::status)
+        .first::<String>(conn)
+        .await
+        .optional()?;
+
+    match locked_status {
+        Some(status) => Err(ServiceError::ConflictError(format!(
+            "Time entries of the week of {} are locked (timesheet {})",
+            week_start, status
+        ))),
+        None => Ok(()),
+    }
+}
+
+// Un propriétaire d'espace valide les feuilles des membres de cet espace (pas les siennes)
+pub async fn can_review(
+    conn: &mut AsyncPgConnection,
+    reviewer_uuid: Uuid,
+    owner_uuid: Uuid,
+) -> Result<bool, ServiceError> {
+    if reviewer_uuid == owner_uuid {
+        return Ok(false);
+    }
+
+    let owned_workspaces = workspace_members::table
+        .filter(workspace_members::user_id.eq(reviewer_uuid))
+        .filter(workspace_members::role.eq("owner"))
+        .select(workspace_members::workspace_id)
+        .load::<Uuid>(conn)
+        .await?;
+
+    let shared = workspace_members::table
+        .filter(workspace_members::user_id.eq(owner_uuid))
+        .filter(workspace_members::workspace_id.eq_any(&owned_workspaces))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+    Ok(shared > 0)
+}
+
+// Utilisateurs dont le relecteur peut valider les feuilles de temps
+pub async fn reviewable_user_ids(
+    conn: &mut AsyncPgConnection,
+    reviewer_uuid: Uuid,
+) -> Result<Vec<Uuid>, ServiceError> {
+    let owned_workspaces = workspace_members::table
+        .filter(workspace_members::user_id.eq(reviewer_uuid))
+        .filter(workspace_members::role.eq("owner"))
+        .select(workspace_members::workspace_id)
+        .load::<Uuid>(conn)
+        .await?;
+
+    workspace_members::table
+        .filter(workspace_members::workspace_id.eq_any(&owned_workspaces))
+        .filter(workspace_members::user_id.ne(reviewer_uuid))
+        .select(workspace_members::user_id)
+        .distinct()
+        .load::<Uuid>(conn)
+        .await
+        .map_err(ServiceError::from)
+}
@@ -0,0 +1,128 @@
+// OptiTask/backend-api/src/tracked_time.rs
+// Temps suivi agrégé par utilisateur, projet et jour (table daily_tracked_time). Les triggers
+// sur time_entries et tasks notent les jours à recalculer dans daily_tracked_time_refresh ;
+// la tâche de maintenance les traite en continu, et les analytics rattrapent les jours en
+// attente de l'utilisateur avant de lire, pour refléter ses dernières saisies.
+use crate::error_handler::ServiceError;
+use chrono::NaiveDate;
+use diesel::sql_types::{Array, BigInt, Date, Nullable, Uuid as DieselUuid};
+use diesel::{sql_query, QueryableByName};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+// Jours recalculés par transaction
+const REFRESH_BATCH_SIZE: i64 = 500;
+
+// Agrégat d'un ensemble de jours ; $1/$2 = tableaux parallèles (utilisateur, jour)
+const AGGREGATE_DAYS_SQL: &str =
+    "INSERT INTO daily_tracked_time (user_id, project_id, day, tracked_seconds) \
+     SELECT te.user_id, t.project_id, r.day, COALESCE(SUM(te.duration_seconds), 0) \
+     FROM unnest($1::uuid[], $2::date[]) AS r(user_id, day) \
+     JOIN time_entries te ON te.user_id = r.user_id \
+     AND te.start_time >= (r.day::timestamp AT TIME ZONE 'UTC') \
+     AND te.start_time < ((r.day + 1)::timestamp AT TIME ZONE 'UTC') \
+     JOIN tasks t ON t.id = te.task_id \
+     WHERE te.is_break = FALSE \
+     GROUP BY te.user_id, t.project_id, r.day";
+
+#[derive(QueryableByName)]
+struct PendingDay {
+    #[diesel(sql_type = DieselUuid)]
+    user_id: Uuid,
+    #[diesel(sql_type = Date)]
+    day: NaiveDate,
+}
+
+// Recalcule un lot de jours en attente (tous les utilisateurs, ou un seul).
+// Les jours sont retirés de la file dans la même transaction : une écriture concurrente
+// les y remet et ils seront traités au passage suivant.
+async fn refresh_batch(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Option<Uuid>,
+) -> Result<usize, ServiceError> {
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            let pending = sql_query(
+                "DELETE FROM daily_tracked_time_refresh WHERE (user_id, day) IN \
+                 (SELECT user_id, day FROM daily_tracked_time_refresh \
+                 WHERE ($1::uuid IS NULL OR user_id = $1) \
+                 ORDER BY queued_at LIMIT $2 FOR UPDATE SKIP LOCKED) \
+                 RETURNING user_id, day",
+            )
+            .bind::<Nullable<DieselUuid>, _>(user_uuid)
+            .bind::<BigInt, _>(REFRESH_BATCH_SIZE)
+            .load::<PendingDay>(conn)
+            .await?;
+            if pending.is_empty() {
+                return Ok(0);
+            }
+
+            let (user_ids, days): (Vec<Uuid>, Vec<NaiveDate>) = pending
+                .iter()
+                .map(|pending_day| (pending_day.user_id, pending_day.day))
+                .unzip();
+            sql_query(
+                "DELETE FROM daily_tracked_time d \
+                 USING unnest($1::uuid[], $2::date[]) AS r(user_id, day) \
+                 WHERE d.user_id = r.user_id AND d.day = r.day",
+            )
+            .bind::<Array<DieselUuid>, _>(&user_ids)
+            .bind::<Array<Date>, _>(&days)
+            .execute(conn)
+            .await?;
+            sql_query(AGGREGATE_DAYS_SQL)
+                .bind::<Array<DieselUuid>, _>(&user_ids)
+                .bind::<Array<Date>, _>(&days)
+                .execute(conn)
+                .await?;
+
+            Ok(pending.len())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+// Traite la file jusqu'à épuisement ; renvoie le nombre de jours recalculés
+pub async fn refresh_pending(
+    conn: &mut AsyncPgConnection,
+    user_uuid: Option<Uuid>,
+) -> Result<usize, ServiceError> {
+    let mut refreshed = 0;
+    loop {
+        let batch = refresh_batch(conn, user_uuid).await?;
+        refreshed += batch;
+        if batch < REFRESH_BATCH_SIZE as usize {
+            return Ok(refreshed);
+        }
+    }
+}
+
+// Reconstruit toute la table (opération de maintenance rebuild_tracked_time)
+pub async fn rebuild_all(conn: &mut AsyncPgConnection) -> Result<usize, ServiceError> {
+    conn.transaction::<_, ServiceError, _>(|conn| {
+        async move {
+            sql_query("DELETE FROM daily_tracked_time_refresh")
+                .execute(conn)
+                .await?;
+            sql_query("DELETE FROM daily_tracked_time")
+                .execute(conn)
+                .await?;
+            sql_query(
+                "INSERT INTO daily_tracked_time (user_id, project_id, day, tracked_seconds) \
+                 SELECT te.user_id, t.project_id, (te.start_time AT TIME ZONE 'UTC')::date, \
+                 COALESCE(SUM(te.duration_seconds), 0) \
+                 FROM time_entries te \
+                 JOIN tasks t ON t.id = te.task_id \
+                 WHERE te.is_break = FALSE \
+                 GROUP BY te.user_id, t.project_id, (te.start_time AT TIME ZONE 'UTC')::date",
+            )
+            .execute(conn)
+            .await
+            .map_err(ServiceError::from)
+        }
+        .scope_boxed()
+    })
+    .await
+}
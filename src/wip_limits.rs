@@ -0,0 +1,96 @@
+// OptiTask/backend-api/src/wip_limits.rs
+// Limites de travail en cours (WIP) par statut, stockées sur le projet
+// sous la forme {"inprogress": 3, "review": 2}
+use crate::error_handler::ServiceError;
+use crate::ids::{ProjectId, TaskId};
+use crate::schema::{projects, tasks};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub const WIP_LIMIT_EXCEEDED: &str = "WIP_LIMIT_EXCEEDED";
+const MAX_WIP_LIMIT_STATUSES: usize = 50;
+const MAX_STATUS_LENGTH: usize = 50;
+
+// Statut attribué par la base quand la création n'en précise pas
+pub const DEFAULT_TASK_STATUS: &str = "todo";
+
+// Limites telles que stockées ; les entrées invalides sont ignorées
+pub fn parse_limits(stored: &Value) -> BTreeMap<String, i64> {
+    stored
+        .as_object()
+        .map(|limits| {
+            limits
+                .iter()
+                .filter_map(|(status, limit)| limit.as_i64().map(|limit| (status.clone(), limit)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Valide les limites reçues et renvoie la valeur JSONB à stocker
+pub fn build_limits(limits: &BTreeMap<String, i64>) -> Result<Value, ServiceError> {
+    if limits.len() > MAX_WIP_LIMIT_STATUSES {
+        return Err(ServiceError::ValidationError(format!(
+            "At most {} statuses can have a WIP limit",
+            MAX_WIP_LIMIT_STATUSES
+        )));
+    }
+    let mut cleaned = serde_json::Map::new();
+    for (status, limit) in limits {
+        let status = status.trim();
+        if status.is_empty() || status.chars().count() > MAX_STATUS_LENGTH {
+            return Err(ServiceError::ValidationError(format!(
+                "WIP limit statuses must be 1 to {} characters",
+                MAX_STATUS_LENGTH
+            )));
+        }
+        if *limit < 1 {
+            return Err(ServiceError::ValidationError(format!(
+                "WIP limit for status '{}' must be at least 1",
+                status
+            )));
+        }
+        cleaned.insert(status.to_string(), Value::from(*limit));
+    }
+    Ok(Value::Object(cleaned))
+}
+
+// Refuse de placer une tâche dans une colonne déjà pleine ; la tâche déplacée
+// elle-même n'est pas comptée (changement de projet ou de statut)
+pub async fn ensure_capacity(
+    conn: &mut AsyncPgConnection,
+    project_uuid: ProjectId,
+    task_status: &str,
+    moving_task: Option<TaskId>,
+) -> Result<(), ServiceError> {
+    let stored_limits = projects::table
+        .find(project_uuid)
+        .select(projects::wip_limits)
+        .first::<Value>(conn)
+        .await?;
+    let Some(limit) = parse_limits(&stored_limits).get(task_status).copied() else {
+        return Ok(());
+    };
+
+    let mut count_query = tasks::table
+        .filter(tasks::project_id.eq(project_uuid))
+        .filter(tasks::status.eq(task_status))
+        .into_boxed();
+    if let Some(task_uuid) = moving_task {
+        count_query = count_query.filter(tasks::id.ne(task_uuid));
+    }
+    let current = count_query.count().get_result::<i64>(conn).await?;
+
+    if current >= limit {
+        return Err(ServiceError::CodedConflict(
+            WIP_LIMIT_EXCEEDED,
+            format!(
+                "The '{}' column of this project is full ({}/{} tasks). Retry with override=true to exceed the WIP limit.",
+                task_status, current, limit
+            ),
+        ));
+    }
+    Ok(())
+}